@@ -1,7 +1,10 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use zkm_cli::{
-    commands::{build::BuildCmd, new::NewCmd, vkey::VkeyCmd},
+    commands::{
+        build::BuildCmd, debug::DebugCmd, execute::ExecuteCmd, new::NewCmd, profile::ProfileCmd,
+        prove::ProveCmd, verify::VerifyCmd, vkey::VkeyCmd,
+    },
     ZKM_VERSION_MESSAGE,
 };
 
@@ -23,6 +26,11 @@ pub enum ProveCliCommands {
     New(NewCmd),
     Build(BuildCmd),
     Vkey(VkeyCmd),
+    Execute(ExecuteCmd),
+    Prove(ProveCmd),
+    Verify(VerifyCmd),
+    Profile(ProfileCmd),
+    Debug(DebugCmd),
 }
 
 fn main() -> Result<()> {
@@ -32,5 +40,10 @@ fn main() -> Result<()> {
         ProveCliCommands::New(cmd) => cmd.run(),
         ProveCliCommands::Build(cmd) => cmd.run(),
         ProveCliCommands::Vkey(cmd) => cmd.run(),
+        ProveCliCommands::Execute(cmd) => cmd.run(),
+        ProveCliCommands::Prove(cmd) => cmd.run(),
+        ProveCliCommands::Verify(cmd) => cmd.run(),
+        ProveCliCommands::Profile(cmd) => cmd.run(),
+        ProveCliCommands::Debug(cmd) => cmd.run(),
     }
 }
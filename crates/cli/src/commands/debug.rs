@@ -0,0 +1,47 @@
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use zkm_core_executor::{gdb::GdbStub, Executor, Program};
+use zkm_stark::ZKMCoreOpts;
+
+use super::utils::{Elf, StdinArgs};
+
+#[derive(Parser)]
+#[command(name = "debug", about = "Execute a guest under a GDB remote serial protocol stub.")]
+pub struct DebugCmd {
+    /// Path to the ELF.
+    #[command(flatten)]
+    elf: Elf,
+
+    #[command(flatten)]
+    stdin: StdinArgs,
+
+    /// The address to wait for the debugger on: a TCP `host:port`, or `unix:<path>` for a Unix
+    /// socket.
+    #[arg(long, default_value = "127.0.0.1:9001")]
+    listen: String,
+}
+
+impl DebugCmd {
+    pub fn run(&self) -> Result<()> {
+        let elf = self.elf.read()?;
+        let stdin = self.stdin.load()?;
+
+        let program = Program::from(&elf).map_err(|e| anyhow!("failed to load ELF: {e}"))?;
+        let mut runtime = Executor::new(program, ZKMCoreOpts::default());
+        runtime.write_vecs(&stdin.buffer);
+        runtime.write_files(&stdin.files);
+        runtime.set_linux_env(stdin.linux_env);
+        for (proof, vkey) in stdin.proofs.iter() {
+            runtime.write_proof(proof.clone(), vkey.clone());
+        }
+
+        let mut stub = GdbStub::new(runtime);
+        println!("Waiting for gdb on {}", self.listen);
+        match self.listen.strip_prefix("unix:") {
+            Some(path) => stub.listen_unix(path)?,
+            None => stub.listen_tcp(self.listen.as_str())?,
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use zkm_sdk::ProverClient;
+
+use super::utils::{Elf, SnapshotArgs, SnapshotTriggerArgs, StdinArgs};
+
+#[derive(Parser)]
+#[command(name = "execute", about = "Execute a guest without generating a proof.")]
+pub struct ExecuteCmd {
+    /// Path to the ELF.
+    #[command(flatten)]
+    elf: Elf,
+
+    #[command(flatten)]
+    stdin: StdinArgs,
+
+    #[command(flatten)]
+    snapshot: SnapshotArgs,
+
+    /// The maximum number of cycles to execute before aborting.
+    #[arg(long)]
+    max_cycles: Option<u64>,
+
+    /// Write the committed public values to this path.
+    #[arg(long)]
+    public_values: Option<String>,
+
+    /// Stop the execution at `--at-cycle`, `--at-pc` or `--at-syscall`, and write a snapshot of it
+    /// to this path.
+    #[arg(long, requires = "snapshot_trigger")]
+    dump_snapshot: Option<PathBuf>,
+
+    #[command(flatten)]
+    trigger: SnapshotTriggerArgs,
+}
+
+impl ExecuteCmd {
+    pub fn run(&self) -> Result<()> {
+        let elf = self.elf.read()?;
+        let stdin = self.stdin.load()?;
+        let snapshot = self.snapshot.load()?;
+
+        let client = ProverClient::new();
+        let mut execute = client.execute(&elf, stdin);
+        if let Some(snapshot) = &snapshot {
+            execute = execute.from_snapshot(snapshot);
+        }
+        if let Some(max_cycles) = self.max_cycles {
+            execute = execute.max_cycles(max_cycles);
+        }
+
+        if let Some(path) = &self.dump_snapshot {
+            let state = execute
+                .snapshot(self.trigger.trigger()?)?
+                .ok_or_else(|| anyhow!("the program exited before the snapshot was taken"))?;
+            state.save(&mut std::fs::File::create(path)?)?;
+            println!(
+                "Snapshot at cycle {} and pc 0x{:08x} saved to {}",
+                state.global_clk,
+                state.pc,
+                path.display()
+            );
+            return Ok(());
+        }
+
+        let (public_values, report) = execute.run()?;
+
+        println!("{report}");
+        println!("Public values:\n0x{}", hex::encode(public_values.as_slice()));
+
+        if let Some(path) = &self.public_values {
+            std::fs::write(path, public_values.as_slice())?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,3 +1,9 @@
 pub mod build;
+pub mod debug;
+pub mod execute;
 pub mod new;
+pub mod profile;
+pub mod prove;
+pub mod utils;
+pub mod verify;
 pub mod vkey;
@@ -0,0 +1,88 @@
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+};
+
+use anyhow::{Context, Result};
+use clap::{Parser, ValueEnum};
+use zkm_core_executor::profiler::{ProfileWeight, Profiler};
+
+use super::utils::Elf;
+
+/// The output format of the profile.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ProfileFormat {
+    /// Folded stacks, as consumed by `inferno-flamegraph` or `flamegraph.pl`.
+    #[default]
+    Folded,
+    /// An uncompressed pprof protobuf, as consumed by `go tool pprof`.
+    Pprof,
+}
+
+/// The weight of each stack in the folded output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Weight {
+    /// The number of executed instructions.
+    #[default]
+    Cycles,
+    /// The estimated number of trace cells, using the per-chip costs of the prover.
+    TraceArea,
+}
+
+#[derive(Parser)]
+#[command(
+    name = "profile",
+    about = "Build a flamegraph profile of a guest from the trace written to `TRACE_FILE`."
+)]
+pub struct ProfileCmd {
+    /// Path to the ELF.
+    #[command(flatten)]
+    elf: Elf,
+
+    /// The program counter trace, written by a debug build of the executor when `TRACE_FILE` is
+    /// set.
+    #[arg(long)]
+    trace: String,
+
+    /// The path the profile is written to.
+    #[arg(long, short, default_value = "profile.folded")]
+    output: String,
+
+    /// The output format.
+    #[arg(long, value_enum, default_value_t = ProfileFormat::Folded)]
+    format: ProfileFormat,
+
+    /// The weight of each stack. The pprof output always contains both weights.
+    #[arg(long, value_enum, default_value_t = Weight::Cycles)]
+    weight: Weight,
+}
+
+impl ProfileCmd {
+    pub fn run(&self) -> Result<()> {
+        let elf = self.elf.read()?;
+        let mut profiler = Profiler::new(&elf)?;
+
+        let trace =
+            File::open(&self.trace).with_context(|| format!("failed to open {}", self.trace))?;
+        profiler.record_trace(BufReader::new(trace))?;
+
+        let output = BufWriter::new(File::create(&self.output)?);
+        match self.format {
+            ProfileFormat::Folded => {
+                let weight = match self.weight {
+                    Weight::Cycles => ProfileWeight::Cycles,
+                    Weight::TraceArea => ProfileWeight::TraceArea,
+                };
+                profiler.write_folded(output, weight)?;
+            }
+            ProfileFormat::Pprof => profiler.write_pprof(output)?,
+        }
+
+        let total = profiler.total();
+        println!("Instructions: {}", total.cycles);
+        println!("Estimated trace area: {} cells", total.trace_area);
+        println!("Profile saved to {}", self.output);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+use std::time::Instant;
+
+use anyhow::Result;
+use clap::{Parser, ValueEnum};
+use zkm_sdk::{HashableKey, ProverClient};
+
+use super::utils::{Elf, SnapshotArgs, StdinArgs};
+
+/// The kind of proof to generate.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ProofMode {
+    #[default]
+    Core,
+    Compressed,
+    Groth16,
+    Plonk,
+}
+
+#[derive(Parser)]
+#[command(name = "prove", about = "Generate a proof for the execution of a guest.")]
+pub struct ProveCmd {
+    /// Path to the ELF.
+    #[command(flatten)]
+    elf: Elf,
+
+    #[command(flatten)]
+    stdin: StdinArgs,
+
+    #[command(flatten)]
+    snapshot: SnapshotArgs,
+
+    /// The kind of proof to generate.
+    #[arg(long, value_enum, default_value_t = ProofMode::Core)]
+    mode: ProofMode,
+
+    /// The path the bincode-serialized `ZKMProofWithPublicValues` is written to.
+    #[arg(long, short, default_value = "proof.bin")]
+    output: String,
+
+    /// The maximum number of cycles to execute before aborting.
+    #[arg(long)]
+    max_cycles: Option<u64>,
+
+    /// Override the shard size used for proving.
+    #[arg(long)]
+    shard_size: Option<usize>,
+}
+
+impl ProveCmd {
+    pub fn run(&self) -> Result<()> {
+        let elf = self.elf.read()?;
+        let stdin = self.stdin.load()?;
+        let snapshot = self.snapshot.load()?;
+
+        let client = ProverClient::new();
+
+        // Execute first so that the user gets the report even if proving takes a while.
+        let mut execute = client.execute(&elf, stdin.clone());
+        if let Some(snapshot) = &snapshot {
+            execute = execute.from_snapshot(snapshot);
+        }
+        if let Some(max_cycles) = self.max_cycles {
+            execute = execute.max_cycles(max_cycles);
+        }
+        let (_, report) = execute.run()?;
+        println!("{report}");
+
+        let (pk, vk) = match &snapshot {
+            Some(snapshot) => client.setup_from_snapshot(&elf, snapshot)?,
+            None => client.setup(&elf),
+        };
+        println!("Verification Key Hash:\n{}", vk.vk.bytes32());
+
+        let mut prove = client.prove(&pk, stdin);
+        if let Some(snapshot) = &snapshot {
+            prove = prove.from_snapshot(snapshot);
+        }
+        prove = match self.mode {
+            ProofMode::Core => prove.core(),
+            ProofMode::Compressed => prove.compressed(),
+            ProofMode::Groth16 => prove.groth16(),
+            ProofMode::Plonk => prove.plonk(),
+        };
+        if let Some(max_cycles) = self.max_cycles {
+            prove = prove.cycle_limit(max_cycles);
+        }
+        if let Some(shard_size) = self.shard_size {
+            prove = prove.shard_size(shard_size);
+        }
+
+        let start = Instant::now();
+        let proof = prove.run()?;
+        println!("Generated {:?} proof in {:?}", self.mode, start.elapsed());
+
+        proof.save(&self.output)?;
+        println!("Proof saved to {}", self.output);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,210 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use clap::{Args, ValueEnum};
+use zkm_build::{generate_elf_paths, BuildArgs};
+use zkm_core_executor::syscalls::SyscallCode;
+use zkm_sdk::{ExecutionState, SnapshotTrigger, ZKMStdin};
+
+#[derive(Debug, Clone, Args)]
+#[group(required = true, multiple = false)]
+pub struct Elf {
+    /// The path to the ELF file
+    #[arg(long = "elf")]
+    path: Option<String>,
+    /// The crate used to generate the ELF file
+    #[arg(long)]
+    program: Option<String>,
+}
+
+impl Elf {
+    /// Resolve the ELF paths, returning the build target name alongside each path when the ELFs
+    /// were located through `--program`.
+    pub fn paths(&self) -> Result<Vec<(Option<String>, String)>> {
+        if let Some(path) = &self.path {
+            Ok(vec![(None, path.clone())])
+        } else if let Some(program) = &self.program {
+            let metadata_cmd = cargo_metadata::MetadataCommand::new();
+            let metadata = metadata_cmd.exec()?;
+            let build_args = BuildArgs { packages: vec![program.clone()], ..Default::default() };
+
+            Ok(generate_elf_paths(&metadata, Some(&build_args))?
+                .into_iter()
+                .map(|(target, path)| (Some(target), path.to_string()))
+                .collect())
+        } else {
+            unreachable!()
+        }
+    }
+
+    /// Read a single ELF. Fails if `--program` resolves to more than one binary target.
+    pub fn read(&self) -> Result<Vec<u8>> {
+        let paths = self.paths()?;
+        let [(_, path)] = paths.as_slice() else {
+            let targets = paths.iter().filter_map(|(target, _)| target.clone()).collect::<Vec<_>>();
+            return Err(anyhow!(
+                "expected exactly one ELF, found {}: {}",
+                paths.len(),
+                targets.join(", ")
+            ));
+        };
+        fs::read(path).with_context(|| format!("failed to read ELF at {path}"))
+    }
+}
+
+/// The encoding of the file passed through `--stdin`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum StdinFormat {
+    /// A bincode-serialized `ZKMStdin`, e.g. as written by `ZKM_DUMP=1`. Inputs written before
+    /// `ZKMStdin` had files must be written again.
+    #[default]
+    Bincode,
+    /// The whole file is pushed as a single input buffer, read in the guest with
+    /// `zkm_zkvm::io::read_vec`.
+    Raw,
+    /// A JSON array of hex strings. Each string is pushed as its own input buffer.
+    Json,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct StdinArgs {
+    /// The path to the input file. If omitted, the program runs with an empty stdin.
+    #[arg(long)]
+    stdin: Option<PathBuf>,
+    /// The encoding of the input file.
+    #[arg(long, value_enum, default_value_t = StdinFormat::Bincode)]
+    stdin_format: StdinFormat,
+}
+
+impl StdinArgs {
+    pub fn load(&self) -> Result<ZKMStdin> {
+        let Some(path) = &self.stdin else {
+            return Ok(ZKMStdin::new());
+        };
+        let bytes = fs::read(path)
+            .with_context(|| format!("failed to read stdin at {}", path.display()))?;
+
+        match self.stdin_format {
+            StdinFormat::Bincode => {
+                bincode::deserialize(&bytes).context("failed to deserialize bincode stdin")
+            }
+            StdinFormat::Raw => Ok(ZKMStdin::from(&bytes)),
+            StdinFormat::Json => {
+                let entries: Vec<String> =
+                    serde_json::from_slice(&bytes).context("expected a JSON array of strings")?;
+                let mut stdin = ZKMStdin::new();
+                for entry in entries {
+                    let entry = entry.strip_prefix("0x").unwrap_or(&entry);
+                    stdin.write_vec(hex::decode(entry).context("invalid hex in JSON stdin")?);
+                }
+                Ok(stdin)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct SnapshotArgs {
+    /// Resume the execution from the snapshot at this path, as written by `execute
+    /// --dump-snapshot`. The inputs of `--stdin` are read after the ones left unread in it.
+    #[arg(long)]
+    snapshot: Option<PathBuf>,
+}
+
+impl SnapshotArgs {
+    pub fn load(&self) -> Result<Option<ExecutionState>> {
+        let Some(path) = &self.snapshot else {
+            return Ok(None);
+        };
+        let file = fs::File::open(path)
+            .with_context(|| format!("failed to open snapshot at {}", path.display()))?;
+        Ok(Some(ExecutionState::load(&file).context("failed to deserialize snapshot")?))
+    }
+}
+
+/// The point of the execution at which `--dump-snapshot` takes the snapshot.
+#[derive(Debug, Clone, Args)]
+#[group(id = "snapshot_trigger", multiple = false)]
+pub struct SnapshotTriggerArgs {
+    /// Take the snapshot once this many cycles have been executed.
+    #[arg(long, requires = "dump_snapshot")]
+    at_cycle: Option<u64>,
+    /// Take the snapshot when the program counter reaches this address.
+    #[arg(long, requires = "dump_snapshot", value_parser = parse_u32)]
+    at_pc: Option<u32>,
+    /// Take the snapshot right after the first invocation of the syscall with this code.
+    #[arg(long, requires = "dump_snapshot", value_parser = parse_u32)]
+    at_syscall: Option<u32>,
+}
+
+impl SnapshotTriggerArgs {
+    pub fn trigger(&self) -> Result<SnapshotTrigger> {
+        if let Some(cycle) = self.at_cycle {
+            Ok(SnapshotTrigger::Cycle(cycle))
+        } else if let Some(pc) = self.at_pc {
+            Ok(SnapshotTrigger::Pc(pc))
+        } else if let Some(code) = self.at_syscall {
+            match SyscallCode::from_u32(code) {
+                SyscallCode::UNIMPLEMENTED => Err(anyhow!("unknown syscall code 0x{code:x}")),
+                syscall => Ok(SnapshotTrigger::Syscall(syscall)),
+            }
+        } else {
+            Err(anyhow!("one of --at-cycle, --at-pc or --at-syscall is required"))
+        }
+    }
+}
+
+/// Parse a decimal or `0x`-prefixed hexadecimal `u32`.
+fn parse_u32(value: &str) -> Result<u32> {
+    match value.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16),
+        None => value.parse(),
+    }
+    .with_context(|| format!("invalid number {value}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(format: StdinFormat, contents: &[u8]) -> ZKMStdin {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        fs::write(file.path(), contents).unwrap();
+        StdinArgs { stdin: Some(file.path().to_path_buf()), stdin_format: format }.load().unwrap()
+    }
+
+    #[test]
+    fn test_load_stdin_formats() {
+        let mut expected = ZKMStdin::new();
+        expected.write(&10u32);
+        expected.write_vec(vec![1, 2, 3]);
+
+        let stdin = load(StdinFormat::Bincode, &bincode::serialize(&expected).unwrap());
+        assert_eq!(stdin.buffer, expected.buffer);
+
+        let stdin = load(StdinFormat::Json, br#"["0x0a000000", "010203"]"#);
+        assert_eq!(stdin.buffer, expected.buffer);
+
+        let stdin = load(StdinFormat::Raw, &[1, 2, 3]);
+        assert_eq!(stdin.buffer, vec![vec![1, 2, 3]]);
+    }
+
+    #[test]
+    fn test_snapshot_triggers() {
+        let trigger = |at_cycle, at_pc, at_syscall| {
+            SnapshotTriggerArgs { at_cycle, at_pc, at_syscall }.trigger()
+        };
+        assert_eq!(trigger(Some(10), None, None).unwrap(), SnapshotTrigger::Cycle(10));
+        assert_eq!(trigger(None, Some(0x400), None).unwrap(), SnapshotTrigger::Pc(0x400));
+        assert_eq!(
+            trigger(None, None, Some(0x02)).unwrap(),
+            SnapshotTrigger::Syscall(SyscallCode::WRITE)
+        );
+        assert!(trigger(None, None, Some(0x1234)).is_err());
+        assert!(trigger(None, None, None).is_err());
+
+        assert_eq!(parse_u32("0x10").unwrap(), 16);
+        assert_eq!(parse_u32("10").unwrap(), 10);
+        assert!(parse_u32("0xg").is_err());
+    }
+}
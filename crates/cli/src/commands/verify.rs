@@ -0,0 +1,33 @@
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use zkm_sdk::{ProverClient, ZKMProofWithPublicValues};
+
+use super::utils::Elf;
+
+#[derive(Parser)]
+#[command(name = "verify", about = "Verify a proof against the verification key of a guest.")]
+pub struct VerifyCmd {
+    /// Path to the ELF.
+    #[command(flatten)]
+    elf: Elf,
+
+    /// The path to the bincode-serialized `ZKMProofWithPublicValues`.
+    #[arg(long, short, default_value = "proof.bin")]
+    proof: String,
+}
+
+impl VerifyCmd {
+    pub fn run(&self) -> Result<()> {
+        let elf = self.elf.read()?;
+        let proof = ZKMProofWithPublicValues::load(&self.proof)?;
+
+        let client = ProverClient::new();
+        let (_, vk) = client.setup(&elf);
+        client.verify(&proof, &vk).map_err(|e| anyhow!("proof verification failed: {e}"))?;
+
+        println!("Proof verified successfully.");
+        println!("Public values:\n0x{}", hex::encode(proof.public_values.as_slice()));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+/*
+ * Checks the loader of position-independent executables.
+ *
+ * This is a freestanding static-pie mipsel-linux-gnu binary without the self-relocation code of
+ * a libc, so its pointers and GOT entries only hold load addresses if the loader relocated them,
+ * and its thread-local variables are only reachable if the loader set up the TLS block. It is
+ * run by `program::tests::test_static_pie` and halts with the failing line as exit code on the
+ * first mismatch.
+ */
+
+#define SYS_EXIT_GROUP 4246
+#define SYS_SET_THREAD_AREA 4283
+
+#define TLS_TP_OFFSET 0x7000
+
+static int value = 42;
+static int *pointer = &value;
+int global = 7;
+int *global_pointer = &global;
+
+__thread int counter = 5;
+__thread int zeroed;
+
+/* Issues a syscall, returning the result or the negated errno like the kernel wrappers do. */
+static long sys(long n, long a, long b, long c, long d) {
+    register long v0 __asm__("$2") = n;
+    register long a0 __asm__("$4") = a;
+    register long a1 __asm__("$5") = b;
+    register long a2 __asm__("$6") = c;
+    register long a3 __asm__("$7") = d;
+    __asm__ volatile("syscall"
+                     : "+r"(v0), "+r"(a3)
+                     : "r"(a0), "r"(a1), "r"(a2)
+                     : "$1", "$3", "$8", "$9", "$10", "$11", "$12", "$13", "$14", "$15", "$24",
+                       "$25", "hi", "lo", "memory");
+    return a3 ? -v0 : v0;
+}
+
+/* Halts with a non-zero exit code, which the executor reports as an error. */
+static void fail(int line) {
+    register long v0 __asm__("$2") = 0;
+    register long a0 __asm__("$4") = line;
+    __asm__ volatile("syscall" : : "r"(v0), "r"(a0) : "memory");
+    for (;;) {
+    }
+}
+
+#define CHECK(cond)         \
+    do {                    \
+        if (!(cond)) {      \
+            fail(__LINE__); \
+        }                   \
+    } while (0)
+
+static char *thread_pointer(void) {
+    char *tp;
+    __asm__ volatile(".set push\n.set mips32r2\nrdhwr %0, $29\n.set pop" : "=r"(tp));
+    return tp;
+}
+
+static void check_relocations(void) {
+    CHECK(pointer == &value);
+    CHECK(*pointer == 42);
+    CHECK(global_pointer == &global);
+    CHECK(*global_pointer == 7);
+}
+
+static void check_tls(void) {
+    char *tp = thread_pointer();
+    CHECK((char *)&counter == tp - TLS_TP_OFFSET);
+    CHECK(counter == 5);
+    CHECK(zeroed == 0);
+    counter++;
+    CHECK(counter == 6);
+
+    CHECK(sys(SYS_SET_THREAD_AREA, (long)(tp + 4), 0, 0, 0) == 0);
+    CHECK(thread_pointer() == tp + 4);
+    CHECK(sys(SYS_SET_THREAD_AREA, (long)tp, 0, 0, 0) == 0);
+    CHECK(thread_pointer() == tp);
+}
+
+/* Called by `__start` with its own address in $t9, as the PIC calling convention requires. */
+__attribute__((used, noinline)) static void check(void) {
+    check_relocations();
+    check_tls();
+    sys(SYS_EXIT_GROUP, 0, 0, 0, 0);
+    for (;;) {
+    }
+}
+
+/* The default entry point of the MIPS linker scripts. */
+__asm__(".text\n"
+        ".globl __start\n"
+        ".set push\n"
+        ".set noreorder\n"
+        "__start:\n"
+        "    bal 1f\n"
+        "    nop\n"
+        "1:  addiu $25, $31, check - 1b\n"
+        "    jr $25\n"
+        "    nop\n"
+        ".set pop\n");
@@ -0,0 +1,138 @@
+/*
+ * Conformance checks for the o32 Linux personality of the executor.
+ *
+ * This is a freestanding static mipsel-linux-gnu binary which issues the syscalls directly, so
+ * that it checks the kernel ABI rather than a libc. It expects the environment set up by
+ * `linux::tests::test_o32_conformance` and halts with the failing line as exit code on the
+ * first mismatch.
+ */
+
+#define SYS_OPEN 4005
+#define SYS_UNAME 4122
+#define SYS_SCHED_YIELD 4162
+#define SYS_FUTEX 4238
+#define SYS_EXIT_GROUP 4246
+#define SYS_CLOCK_GETTIME 4263
+#define SYS_GETRANDOM 4353
+#define SYS_CLOCK_GETTIME64 4403
+
+#define ENOENT 2
+#define EAGAIN 11
+#define EINVAL 22
+#define ETIMEDOUT 145
+
+#define CLOCK_REALTIME 0
+#define CLOCK_MONOTONIC 1
+
+#define FUTEX_WAIT_PRIVATE 128
+#define FUTEX_WAKE_PRIVATE 129
+
+#define START_TIME 1700000000
+
+struct timespec32 {
+    int tv_sec;
+    int tv_nsec;
+};
+
+struct timespec64 {
+    long long tv_sec;
+    long long tv_nsec;
+};
+
+/* Issues a syscall, returning the result or the negated errno like the kernel wrappers do. */
+static long sys(long n, long a, long b, long c, long d) {
+    register long v0 __asm__("$2") = n;
+    register long a0 __asm__("$4") = a;
+    register long a1 __asm__("$5") = b;
+    register long a2 __asm__("$6") = c;
+    register long a3 __asm__("$7") = d;
+    __asm__ volatile("syscall"
+                     : "+r"(v0), "+r"(a3)
+                     : "r"(a0), "r"(a1), "r"(a2)
+                     : "$1", "$3", "$8", "$9", "$10", "$11", "$12", "$13", "$14", "$15", "$24",
+                       "$25", "hi", "lo", "memory");
+    return a3 ? -v0 : v0;
+}
+
+/* Halts with a non-zero exit code, which the executor reports as an error. */
+static void fail(int line) {
+    register long v0 __asm__("$2") = 0;
+    register long a0 __asm__("$4") = line;
+    __asm__ volatile("syscall" : : "r"(v0), "r"(a0) : "memory");
+    for (;;) {
+    }
+}
+
+#define CHECK(cond)         \
+    do {                    \
+        if (!(cond)) {      \
+            fail(__LINE__); \
+        }                   \
+    } while (0)
+
+static int bytes_eq(const char *a, const char *b, int len) {
+    for (int i = 0; i < len; i++) {
+        if (a[i] != b[i]) {
+            return 0;
+        }
+    }
+    return 1;
+}
+
+static void check_clocks(void) {
+    struct timespec32 ts;
+    CHECK(sys(SYS_CLOCK_GETTIME, CLOCK_REALTIME, (long)&ts, 0, 0) == 0);
+    CHECK(ts.tv_sec == START_TIME && ts.tv_nsec >= 0 && ts.tv_nsec < 1000000000);
+
+    struct timespec32 t1, t2;
+    CHECK(sys(SYS_CLOCK_GETTIME, CLOCK_MONOTONIC, (long)&t1, 0, 0) == 0);
+    CHECK(sys(SYS_CLOCK_GETTIME, CLOCK_MONOTONIC, (long)&t2, 0, 0) == 0);
+    CHECK(t2.tv_sec > t1.tv_sec || (t2.tv_sec == t1.tv_sec && t2.tv_nsec > t1.tv_nsec));
+
+    struct timespec64 ts64;
+    CHECK(sys(SYS_CLOCK_GETTIME64, CLOCK_REALTIME, (long)&ts64, 0, 0) == 0);
+    CHECK(ts64.tv_sec == START_TIME && ts64.tv_nsec < 1000000000);
+
+    CHECK(sys(SYS_CLOCK_GETTIME, 99, (long)&ts, 0, 0) == -EINVAL);
+}
+
+static void check_getrandom(void) {
+    char a[16], b[16];
+    CHECK(sys(SYS_GETRANDOM, (long)a, sizeof(a), 0, 0) == sizeof(a));
+    CHECK(sys(SYS_GETRANDOM, (long)b, sizeof(b), 1, 0) == sizeof(b));
+    CHECK(!bytes_eq(a, b, sizeof(a)));
+    CHECK(sys(SYS_GETRANDOM, (long)a, 0, 0, 0) == 0);
+    CHECK(sys(SYS_GETRANDOM, (long)a, sizeof(a), 8, 0) == -EINVAL);
+}
+
+static void check_uname(void) {
+    char uts[6][65];
+    CHECK(sys(SYS_UNAME, (long)uts, 0, 0, 0) == 0);
+    CHECK(bytes_eq(uts[0], "Linux", 6));
+    CHECK(bytes_eq(uts[4], "mips", 5));
+}
+
+static void check_futex(void) {
+    static volatile int word = 5;
+    struct timespec32 timeout = {0, 1000};
+    CHECK(sys(SYS_FUTEX, (long)&word, FUTEX_WAIT_PRIVATE, 4, 0) == -EAGAIN);
+    CHECK(sys(SYS_FUTEX, (long)&word, FUTEX_WAIT_PRIVATE, 5, (long)&timeout) == -ETIMEDOUT);
+    CHECK(sys(SYS_FUTEX, (long)&word, FUTEX_WAKE_PRIVATE, 1, 0) == 0);
+    CHECK(sys(SYS_SCHED_YIELD, 0, 0, 0, 0) == 0);
+}
+
+static void check_errors(void) {
+    CHECK(sys(SYS_OPEN, (long)"/missing", 0, 0, 0) == -ENOENT);
+}
+
+/* The default entry point of the MIPS linker scripts. */
+void __start(void) {
+    check_clocks();
+    check_getrandom();
+    check_uname();
+    check_futex();
+    check_errors();
+    sys(SYS_EXIT_GROUP, 0, 0, 0, 0);
+    for (;;) {
+    }
+}
@@ -115,6 +115,30 @@ pub enum MipsAirId {
     SysLinux = 47,
     /// The MovCondChip.
     MovCond = 49,
+    /// The BLAKE2s rounds chip.
+    Blake2sRounds = 50,
+    /// The BLAKE3 rounds chip.
+    Blake3Rounds = 51,
+    /// The BLAKE2b rounds chip.
+    Blake2bRounds = 52,
+    /// The BLS12-381 fp6 multiplication chip.
+    Bls12381Fp6MulAssign = 53,
+    /// The BN254 fp6 multiplication chip.
+    Bn254Fp6MulAssign = 54,
+    /// The Secp256k1 scalar multiplication chip.
+    Secp256k1MulAssign = 55,
+    /// The Secp256r1 scalar multiplication chip.
+    Secp256r1MulAssign = 56,
+    /// The BN254 scalar multiplication chip.
+    Bn254MulAssign = 57,
+    /// The BLS12-381 scalar multiplication chip.
+    Bls12381MulAssign = 58,
+    /// The variable-width modular multiplication chip.
+    BigIntMulMod = 59,
+    /// The FPU chip for the rounded floating point operations.
+    FpuArith = 60,
+    /// The FPU chip for the compares, sign operations and 64-bit loads and stores.
+    FpuMisc = 61,
 }
 
 impl MipsAirId {
@@ -195,6 +219,18 @@ impl MipsAirId {
             Self::Byte => "Byte",
             Self::SysLinux => "SysLinux",
             Self::MovCond => "MovCond",
+            Self::Blake2sRounds => "Blake2sRounds",
+            Self::Blake3Rounds => "Blake3Rounds",
+            Self::Blake2bRounds => "Blake2bRounds",
+            Self::Bls12381Fp6MulAssign => "Bls12381Fp6MulAssign",
+            Self::Bn254Fp6MulAssign => "Bn254Fp6MulAssign",
+            Self::Secp256k1MulAssign => "Secp256k1MulAssign",
+            Self::Secp256r1MulAssign => "Secp256r1MulAssign",
+            Self::Bn254MulAssign => "Bn254MulAssign",
+            Self::Bls12381MulAssign => "Bls12381MulAssign",
+            Self::BigIntMulMod => "BigIntMulMod",
+            Self::FpuArith => "FpuArith",
+            Self::FpuMisc => "FpuMisc",
         }
     }
 }
@@ -1,11 +1,13 @@
 use core::mem::take;
+use std::sync::Arc;
 
 use hashbrown::HashMap;
 
 use crate::{
     hook::{hookify, BoxedHook, HookEnv, HookRegistry},
     subproof::SubproofVerifier,
-    ExecutionError,
+    syscalls::{Syscall, SyscallCode},
+    ExecutionError, ExecutionState,
 };
 
 /// Context to run a program inside Ziren.
@@ -24,6 +26,12 @@ pub struct ZKMContext<'a> {
 
     /// Skip deferred proof verification.
     pub skip_deferred_proof_verification: bool,
+
+    /// The snapshot to resume the execution from, see [`crate::Program::resume_from`].
+    pub snapshot: Option<&'a ExecutionState>,
+
+    /// The custom precompiles, keyed by their syscall codes in the custom range.
+    pub custom_syscalls: HashMap<SyscallCode, Arc<dyn Syscall>>,
 }
 
 /// A builder for [`ZKMContext`].
@@ -34,6 +42,8 @@ pub struct ZKMContextBuilder<'a> {
     subproof_verifier: Option<&'a dyn SubproofVerifier>,
     max_cycles: Option<u64>,
     skip_deferred_proof_verification: bool,
+    snapshot: Option<&'a ExecutionState>,
+    custom_syscalls: HashMap<SyscallCode, Arc<dyn Syscall>>,
 }
 
 impl<'a> ZKMContext<'a> {
@@ -73,11 +83,15 @@ impl<'a> ZKMContextBuilder<'a> {
         let subproof_verifier = take(&mut self.subproof_verifier);
         let cycle_limit = take(&mut self.max_cycles);
         let skip_deferred_proof_verification = take(&mut self.skip_deferred_proof_verification);
+        let snapshot = take(&mut self.snapshot);
+        let custom_syscalls = take(&mut self.custom_syscalls);
         ZKMContext {
             hook_registry,
             subproof_verifier,
             max_cycles: cycle_limit,
             skip_deferred_proof_verification,
+            snapshot,
+            custom_syscalls,
         }
     }
 
@@ -123,11 +137,70 @@ impl<'a> ZKMContextBuilder<'a> {
         self.skip_deferred_proof_verification = skip;
         self
     }
+
+    /// Resume the execution from a snapshot.
+    ///
+    /// The program must be resumed from the same snapshot with [`crate::Program::resume_from`].
+    pub fn snapshot(&mut self, snapshot: &'a ExecutionState) -> &mut Self {
+        self.snapshot = Some(snapshot);
+        self
+    }
+
+    /// Register a custom precompile under a syscall code of the custom range.
+    ///
+    /// The machine proving the execution must include a chip for the precompile, see
+    /// `PrecompileRegistry` in `zkm-core-machine`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the code is not a custom syscall code, if it is already registered, or if the
+    /// syscall does not use the number of extra cycles encoded in the code, which the CPU chip
+    /// adds to the clock.
+    pub fn custom_syscall(&mut self, code: SyscallCode, syscall: Arc<dyn Syscall>) -> &mut Self {
+        assert!(code.is_custom(), "{code} is not a custom syscall code");
+        assert_eq!(
+            syscall.num_extra_cycles(),
+            code.num_cycles(),
+            "custom syscall {code} must use {} extra cycles",
+            code.num_cycles()
+        );
+        assert!(
+            self.custom_syscalls.insert(code, syscall).is_none(),
+            "custom syscall {code} is registered twice"
+        );
+        self
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{subproof::NoOpSubproofVerifier, ZKMContext};
+    use std::sync::Arc;
+
+    use crate::{
+        subproof::NoOpSubproofVerifier,
+        syscalls::{Syscall, SyscallCode, SyscallContext},
+        ExecutionError, ZKMContext,
+    };
+
+    struct NoOpSyscall {
+        num_extra_cycles: u32,
+    }
+
+    impl Syscall for NoOpSyscall {
+        fn execute(
+            &self,
+            _: &mut SyscallContext,
+            _: SyscallCode,
+            _: u32,
+            _: u32,
+        ) -> Result<Option<u32>, ExecutionError> {
+            Ok(None)
+        }
+
+        fn num_extra_cycles(&self) -> u32 {
+            self.num_extra_cycles
+        }
+    }
 
     #[test]
     fn defaults() {
@@ -166,4 +239,27 @@ mod tests {
             ZKMContext::builder().subproof_verifier(&verifier).build();
         assert!(subproof_verifier.is_some());
     }
+
+    #[test]
+    fn custom_syscall() {
+        let code = SyscallCode::custom(3).unwrap();
+        let syscall = Arc::new(NoOpSyscall { num_extra_cycles: 1 });
+        let ZKMContext { custom_syscalls, .. } =
+            ZKMContext::builder().custom_syscall(code, syscall).build();
+        assert!(custom_syscalls.contains_key(&code));
+    }
+
+    #[test]
+    #[should_panic(expected = "is not a custom syscall code")]
+    fn custom_syscall_outside_of_range() {
+        let syscall = Arc::new(NoOpSyscall { num_extra_cycles: 0 });
+        ZKMContext::builder().custom_syscall(SyscallCode::SHA_EXTEND, syscall);
+    }
+
+    #[test]
+    #[should_panic(expected = "must use 1 extra cycles")]
+    fn custom_syscall_extra_cycles_mismatch() {
+        let syscall = Arc::new(NoOpSyscall { num_extra_cycles: 0 });
+        ZKMContext::builder().custom_syscall(SyscallCode::custom(0).unwrap(), syscall);
+    }
 }
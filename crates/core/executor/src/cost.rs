@@ -79,6 +79,12 @@ pub fn estimate_mips_lde_size(
     cells += (num_events_per_air[MipsAirId::CloClz]).next_power_of_two()
         * costs_per_air[&MipsAirId::CloClz];
 
+    // Compute the FPU chip contributions.
+    cells += (num_events_per_air[MipsAirId::FpuArith]).next_power_of_two()
+        * costs_per_air[&MipsAirId::FpuArith];
+    cells += (num_events_per_air[MipsAirId::FpuMisc]).next_power_of_two()
+        * costs_per_air[&MipsAirId::FpuMisc];
+
     // Compute the syscall core chip contribution.
     cells += (num_events_per_air[MipsAirId::SyscallCore]).next_power_of_two()
         * costs_per_air[&MipsAirId::SyscallCore];
@@ -107,8 +113,11 @@ pub fn estimate_mips_event_counts(
     events_counts[MipsAirId::AddSub] = opcode_counts[Opcode::ADD] + opcode_counts[Opcode::SUB];
 
     // Compute the number of events in the mul chip.
-    events_counts[MipsAirId::Mul] =
-        opcode_counts[Opcode::MUL] + opcode_counts[Opcode::MULT] + opcode_counts[Opcode::MULTU];
+    events_counts[MipsAirId::Mul] = opcode_counts[Opcode::MUL]
+        + opcode_counts[Opcode::MULT]
+        + opcode_counts[Opcode::MULTU]
+        + opcode_counts[Opcode::MUH]
+        + opcode_counts[Opcode::MUHU];
 
     // Compute the number of events in the bitwise chip.
     events_counts[MipsAirId::Bitwise] = opcode_counts[Opcode::XOR]
@@ -124,7 +133,10 @@ pub fn estimate_mips_event_counts(
         opcode_counts[Opcode::SRL] + opcode_counts[Opcode::SRA] + opcode_counts[Opcode::ROR];
 
     // Compute the number of events in the divrem chip.
-    events_counts[MipsAirId::DivRem] = opcode_counts[Opcode::DIV] + opcode_counts[Opcode::DIVU];
+    events_counts[MipsAirId::DivRem] = opcode_counts[Opcode::DIV]
+        + opcode_counts[Opcode::DIVU]
+        + opcode_counts[Opcode::MOD]
+        + opcode_counts[Opcode::MODU];
 
     // Compute the number of events in the lt chip.
     events_counts[MipsAirId::Lt] = opcode_counts[Opcode::SLT] + opcode_counts[Opcode::SLTU];
@@ -139,12 +151,37 @@ pub fn estimate_mips_event_counts(
         + opcode_counts[Opcode::BGTZ]
         + opcode_counts[Opcode::BGEZ]
         + opcode_counts[Opcode::BLTZ]
-        + opcode_counts[Opcode::BLEZ];
+        + opcode_counts[Opcode::BLEZ]
+        + opcode_counts[Opcode::BLTZAL]
+        + opcode_counts[Opcode::BGEZAL]
+        + opcode_counts[Opcode::BEQL]
+        + opcode_counts[Opcode::BNEL]
+        + opcode_counts[Opcode::BLEZL]
+        + opcode_counts[Opcode::BGTZL]
+        + opcode_counts[Opcode::BLTZL]
+        + opcode_counts[Opcode::BGEZL]
+        + opcode_counts[Opcode::BLTZALL]
+        + opcode_counts[Opcode::BGEZALL]
+        + opcode_counts[Opcode::BEQC]
+        + opcode_counts[Opcode::BNEC]
+        + opcode_counts[Opcode::BLTC]
+        + opcode_counts[Opcode::BGEC]
+        + opcode_counts[Opcode::BLTUC]
+        + opcode_counts[Opcode::BGEUC]
+        + opcode_counts[Opcode::BLEZALC]
+        + opcode_counts[Opcode::BGEZALC]
+        + opcode_counts[Opcode::BGTZALC]
+        + opcode_counts[Opcode::BLTZALC]
+        + opcode_counts[Opcode::BEQZALC]
+        + opcode_counts[Opcode::BNEZALC]
+        + opcode_counts[Opcode::BOVC]
+        + opcode_counts[Opcode::BNVC];
 
     // Compute the number of events in the jump chip.
     events_counts[MipsAirId::Jump] = opcode_counts[Opcode::Jump]
         + opcode_counts[Opcode::Jumpi]
-        + opcode_counts[Opcode::JumpDirect];
+        + opcode_counts[Opcode::JumpDirect]
+        + opcode_counts[Opcode::JumpCompact];
 
     // Compute the number of events in the MemoryInstrs chip.
     events_counts[MipsAirId::MemoryInstrs] = opcode_counts[Opcode::LB]
@@ -170,10 +207,64 @@ pub fn estimate_mips_event_counts(
         + opcode_counts[Opcode::MSUBU]
         + opcode_counts[Opcode::MADD]
         + opcode_counts[Opcode::MSUB]
-        + opcode_counts[Opcode::TEQ];
-
-    events_counts[MipsAirId::MovCond] =
-        opcode_counts[Opcode::WSBH] + opcode_counts[Opcode::MNE] + opcode_counts[Opcode::MEQ];
+        + opcode_counts[Opcode::TEQ]
+        + opcode_counts[Opcode::TNE]
+        + opcode_counts[Opcode::TGE]
+        + opcode_counts[Opcode::TGEU]
+        + opcode_counts[Opcode::TLT]
+        + opcode_counts[Opcode::TLTU]
+        + opcode_counts[Opcode::LSA]
+        + opcode_counts[Opcode::ALIGN]
+        + opcode_counts[Opcode::BITSWAP]
+        + opcode_counts[Opcode::CTC1];
+
+    events_counts[MipsAirId::MovCond] = opcode_counts[Opcode::WSBH]
+        + opcode_counts[Opcode::MNE]
+        + opcode_counts[Opcode::MEQ]
+        + opcode_counts[Opcode::SELEQZ]
+        + opcode_counts[Opcode::SELNEZ];
+
+    // Compute the number of events in the FPU chips.
+    events_counts[MipsAirId::FpuArith] = opcode_counts[Opcode::FADD_S]
+        + opcode_counts[Opcode::FADD_D]
+        + opcode_counts[Opcode::FSUB_S]
+        + opcode_counts[Opcode::FSUB_D]
+        + opcode_counts[Opcode::FMUL_S]
+        + opcode_counts[Opcode::FMUL_D]
+        + opcode_counts[Opcode::FDIV_S]
+        + opcode_counts[Opcode::FDIV_D]
+        + opcode_counts[Opcode::FSQRT_S]
+        + opcode_counts[Opcode::FSQRT_D]
+        + opcode_counts[Opcode::CVT_S_D]
+        + opcode_counts[Opcode::CVT_D_S]
+        + opcode_counts[Opcode::CVT_S_W]
+        + opcode_counts[Opcode::CVT_D_W]
+        + opcode_counts[Opcode::CVT_W_S]
+        + opcode_counts[Opcode::CVT_W_D]
+        + opcode_counts[Opcode::TRUNC_W_S]
+        + opcode_counts[Opcode::TRUNC_W_D];
+
+    events_counts[MipsAirId::FpuMisc] = opcode_counts[Opcode::FABS_S]
+        + opcode_counts[Opcode::FABS_D]
+        + opcode_counts[Opcode::FNEG_S]
+        + opcode_counts[Opcode::FNEG_D]
+        + opcode_counts[Opcode::FMOV_D]
+        + opcode_counts[Opcode::C_UN_S]
+        + opcode_counts[Opcode::C_EQ_S]
+        + opcode_counts[Opcode::C_UEQ_S]
+        + opcode_counts[Opcode::C_OLT_S]
+        + opcode_counts[Opcode::C_ULT_S]
+        + opcode_counts[Opcode::C_OLE_S]
+        + opcode_counts[Opcode::C_ULE_S]
+        + opcode_counts[Opcode::C_UN_D]
+        + opcode_counts[Opcode::C_EQ_D]
+        + opcode_counts[Opcode::C_UEQ_D]
+        + opcode_counts[Opcode::C_OLT_D]
+        + opcode_counts[Opcode::C_ULT_D]
+        + opcode_counts[Opcode::C_OLE_D]
+        + opcode_counts[Opcode::C_ULE_D]
+        + opcode_counts[Opcode::LDC1]
+        + opcode_counts[Opcode::SDC1];
 
     // Compute the number of events in the auipc chip.
     events_counts[MipsAirId::CloClz] = opcode_counts[Opcode::CLO] + opcode_counts[Opcode::CLZ];
@@ -217,6 +308,8 @@ pub fn pad_mips_event_counts(
         MipsAirId::MemoryInstrs => *v += 8 * num_cycles,
         MipsAirId::MiscInstrs => *v += 8 * num_cycles, // TODO: Check this value.
         MipsAirId::CloClz => *v += 3 * num_cycles,     // TODO: Check this value.
+        MipsAirId::FpuArith => *v += num_cycles,
+        MipsAirId::FpuMisc => *v += num_cycles,
         MipsAirId::SyscallCore => *v += 2 * num_cycles,
         MipsAirId::Global => *v += 64 * num_cycles,
         _ => (),
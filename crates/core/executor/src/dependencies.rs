@@ -1,9 +1,13 @@
 use crate::{
     events::{
-        AluEvent, BranchEvent, CompAluEvent, JumpEvent, MemInstrEvent, MemoryRecord,
+        AluEvent, BranchEvent, CompAluEvent, FpuEvent, JumpEvent, MemInstrEvent, MemoryRecord,
         MemoryWriteRecord, MiscEvent,
     },
-    utils::{get_msb, get_quotient_and_remainder, is_signed_operation},
+    fpu::{source_is_double, INVALID_WORD},
+    sign_extend,
+    utils::{
+        branch_operands, get_msb, get_quotient_and_remainder, is_branch_taken, is_signed_operation,
+    },
     Executor, Opcode, DEFAULT_PC_INC, UNUSED_PC,
 };
 
@@ -179,40 +183,51 @@ pub fn emit_memory_dependencies(
 
 /// Emit the dependencies for branch instructions.
 pub fn emit_branch_dependencies(executor: &mut Executor, event: BranchEvent) {
-    let a_eq_b = event.a == event.b;
-    let a_lt_b = (event.a as i32) < (event.b as i32);
-    let a_gt_b = (event.a as i32) > (event.b as i32);
+    let (lhs, rhs) = branch_operands(event.opcode, event.a, event.b);
+    let unsigned = matches!(event.opcode, Opcode::BLTUC | Opcode::BGEUC);
+    let (a_lt_b, a_gt_b, opcode) = if unsigned {
+        (lhs < rhs, lhs > rhs, Opcode::SLTU)
+    } else {
+        ((lhs as i32) < (rhs as i32), (lhs as i32) > (rhs as i32), Opcode::SLT)
+    };
 
     let lt_comp_event = AluEvent {
         pc: UNUSED_PC,
         next_pc: UNUSED_PC + DEFAULT_PC_INC,
-        opcode: Opcode::SLT,
+        opcode,
         hi: 0,
         a: a_lt_b as u32,
-        b: event.a,
-        c: event.b,
+        b: lhs,
+        c: rhs,
     };
     let gt_comp_event = AluEvent {
         pc: UNUSED_PC,
         next_pc: UNUSED_PC + DEFAULT_PC_INC,
-        opcode: Opcode::SLT,
+        opcode,
         hi: 0,
         a: a_gt_b as u32,
-        b: event.b,
-        c: event.a,
+        b: rhs,
+        c: lhs,
     };
     executor.record.lt_events.push(lt_comp_event);
     executor.record.lt_events.push(gt_comp_event);
-    let branching = match event.opcode {
-        Opcode::BEQ => a_eq_b,
-        Opcode::BNE => !a_eq_b,
-        Opcode::BLTZ => a_lt_b,
-        Opcode::BLEZ => a_lt_b || a_eq_b,
-        Opcode::BGTZ => a_gt_b,
-        Opcode::BGEZ => a_eq_b || a_gt_b,
-        _ => unreachable!(),
-    };
-    if branching {
+
+    // The overflow branches compute the wrapping sum of their operands.
+    if matches!(event.opcode, Opcode::BOVC | Opcode::BNVC) {
+        let add_event = AluEvent {
+            pc: UNUSED_PC,
+            next_pc: UNUSED_PC + DEFAULT_PC_INC,
+            opcode: Opcode::ADD,
+            hi: 0,
+            a: lhs.wrapping_add(rhs),
+            b: lhs,
+            c: rhs,
+        };
+        executor.record.add_sub_events.push(add_event);
+    }
+
+    // Compact branches carry their absolute target, only delayed ones add the offset.
+    if is_branch_taken(event.opcode, lhs, rhs) && !event.opcode.is_compact_branch() {
         let add_event = AluEvent {
             pc: UNUSED_PC,
             next_pc: UNUSED_PC + DEFAULT_PC_INC,
@@ -242,6 +257,18 @@ pub fn emit_jump_dependencies(executor: &mut Executor, event: JumpEvent) {
             };
             executor.record.add_sub_events.push(add_event);
         }
+        Opcode::JumpCompact => {
+            let add_event = AluEvent {
+                pc: UNUSED_PC,
+                next_pc: UNUSED_PC + DEFAULT_PC_INC,
+                opcode: Opcode::ADD,
+                hi: 0,
+                a: event.next_pc,
+                b: event.b,
+                c: event.c,
+            };
+            executor.record.add_sub_events.push(add_event);
+        }
         Opcode::Jump | Opcode::Jumpi => {}
         _ => unreachable!(),
     }
@@ -373,5 +400,101 @@ pub fn emit_misc_dependencies(executor: &mut Executor, event: MiscEvent) {
         };
         assert_eq!(event.a, extra_shift.rotate_right(31 - msb));
         executor.record.shift_right_events.push(ror_event2);
+    } else if matches!(event.opcode, Opcode::LSA) {
+        let shift = (event.c & 0x3) + 1;
+        let sll_val = event.b << shift;
+        let sll_event = AluEvent {
+            pc: UNUSED_PC,
+            next_pc: UNUSED_PC + DEFAULT_PC_INC,
+            opcode: Opcode::SLL,
+            hi: 0,
+            a: sll_val,
+            b: event.b,
+            c: shift,
+        };
+        executor.record.shift_left_events.push(sll_event);
+
+        // The misc chip reads `rt` into the slot of `HI`.
+        let add_event = AluEvent {
+            pc: UNUSED_PC,
+            next_pc: UNUSED_PC + DEFAULT_PC_INC,
+            opcode: Opcode::ADD,
+            hi: 0,
+            a: event.a,
+            b: sll_val,
+            c: event.hi_record.value,
+        };
+        executor.record.add_sub_events.push(add_event);
+    } else if matches!(
+        event.opcode,
+        Opcode::TEQ | Opcode::TNE | Opcode::TGE | Opcode::TGEU | Opcode::TLT | Opcode::TLTU
+    ) {
+        let (a_lt_b, a_gt_b, opcode) = if matches!(event.opcode, Opcode::TGEU | Opcode::TLTU) {
+            (event.a < event.b, event.a > event.b, Opcode::SLTU)
+        } else {
+            ((event.a as i32) < (event.b as i32), (event.a as i32) > (event.b as i32), Opcode::SLT)
+        };
+        let lt_comp_event = AluEvent {
+            pc: UNUSED_PC,
+            next_pc: UNUSED_PC + DEFAULT_PC_INC,
+            opcode,
+            hi: 0,
+            a: a_lt_b as u32,
+            b: event.a,
+            c: event.b,
+        };
+        let gt_comp_event = AluEvent {
+            pc: UNUSED_PC,
+            next_pc: UNUSED_PC + DEFAULT_PC_INC,
+            opcode,
+            hi: 0,
+            a: a_gt_b as u32,
+            b: event.b,
+            c: event.a,
+        };
+        executor.record.lt_events.push(lt_comp_event);
+        executor.record.lt_events.push(gt_comp_event);
+    }
+}
+
+/// Emit the dependencies for FPU instructions.
+pub fn emit_fpu_dependencies(executor: &mut Executor, event: FpuEvent) {
+    let alu_event = |opcode: Opcode, a: u32, b: u32, c: u32| AluEvent {
+        pc: UNUSED_PC,
+        next_pc: UNUSED_PC + DEFAULT_PC_INC,
+        opcode,
+        hi: 0,
+        a,
+        b,
+        c,
+    };
+    let (fs_sign, fs_magnitude) = if source_is_double(event.opcode) {
+        (event.fs() >> 63, event.fs() & !(1 << 63))
+    } else {
+        (event.fs() >> 31, event.fs() & 0x7FFF_FFFF)
+    };
+
+    match event.opcode {
+        Opcode::LDC1 | Opcode::SDC1 => {
+            let offset = sign_extend::<16>(event.c & 0xFFFF);
+            let add_event = alu_event(Opcode::ADD, event.b.wrapping_add(offset), event.b, offset);
+            executor.record.add_sub_events.push(add_event);
+        }
+        // The magnitude of a negative word.
+        Opcode::CVT_S_W | Opcode::CVT_D_W if fs_sign == 1 => {
+            let word = event.fs() as u32;
+            let sub_event = alu_event(Opcode::SUB, word.wrapping_neg(), 0, word);
+            executor.record.add_sub_events.push(sub_event);
+        }
+        // The negation of the rounded magnitude, unless the source is a zero or the conversion is
+        // invalid.
+        Opcode::CVT_W_S | Opcode::CVT_W_D | Opcode::TRUNC_W_S | Opcode::TRUNC_W_D
+            if fs_sign == 1 && fs_magnitude != 0 && event.fd() as u32 != INVALID_WORD =>
+        {
+            let word = event.fd() as u32;
+            let sub_event = alu_event(Opcode::SUB, word, 0, word.wrapping_neg());
+            executor.record.add_sub_events.push(sub_event);
+        }
+        _ => {}
     }
 }
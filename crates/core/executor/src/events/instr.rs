@@ -1,3 +1,4 @@
+use super::MemoryReadRecord;
 use super::MemoryRecordEnum;
 use super::MemoryWriteRecord;
 use crate::Opcode;
@@ -308,3 +309,91 @@ impl MovCondEvent {
         Self { pc, next_pc, opcode, a, b, c, prev_a }
     }
 }
+
+/// The register and memory accesses of an FPU instruction.
+///
+/// The FPU chips access the floating point registers themselves, so that a double can occupy a
+/// register pair. The second entry of each pair is only used by doubles.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[repr(C)]
+pub struct FpuAccessRecord {
+    /// The reads of `fs`, at the `b` position.
+    pub fs: [Option<MemoryReadRecord>; 2],
+    /// The reads of `ft`, at the `c` position.
+    pub ft: [Option<MemoryReadRecord>; 2],
+    /// The writes of the destination, or the reads of `ft` for `sdc1`, at the `a` position.
+    pub fd: [Option<MemoryRecordEnum>; 2],
+    /// The accesses of the two memory words of `ldc1` and `sdc1`.
+    pub memory: [Option<MemoryRecordEnum>; 2],
+}
+
+/// FPU Instruction Event.
+///
+/// This object encapsulated the information needed to prove a MIPS FPU operation.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[repr(C)]
+pub struct FpuEvent {
+    /// The shard number.
+    pub shard: u32,
+    /// The clock cycle.
+    pub clk: u32,
+    /// The program counter.
+    pub pc: u32,
+    pub next_pc: u32,
+    /// The opcode.
+    pub opcode: Opcode,
+    /// The second operand value: the packed source registers, or the base address of `ldc1` and
+    /// `sdc1`.
+    pub b: u32,
+    /// The third operand value: the destination register, or the packed register and offset of
+    /// `ldc1` and `sdc1`.
+    pub c: u32,
+    /// The register and memory accesses.
+    pub access: FpuAccessRecord,
+}
+
+impl FpuEvent {
+    /// Create a new [`FpuEvent`].
+    #[must_use]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        shard: u32,
+        clk: u32,
+        pc: u32,
+        next_pc: u32,
+        opcode: Opcode,
+        b: u32,
+        c: u32,
+        access: FpuAccessRecord,
+    ) -> Self {
+        Self { shard, clk, pc, next_pc, opcode, b, c, access }
+    }
+
+    /// The value of `fs`, with a double's odd register in the upper word.
+    #[must_use]
+    pub fn fs(&self) -> u64 {
+        pair(self.access.fs.map(|record| record.map(|record| record.value)))
+    }
+
+    /// The value of `ft`, with a double's odd register in the upper word.
+    #[must_use]
+    pub fn ft(&self) -> u64 {
+        pair(self.access.ft.map(|record| record.map(|record| record.value)))
+    }
+
+    /// The value written to the destination, or stored by `sdc1`.
+    #[must_use]
+    pub fn fd(&self) -> u64 {
+        pair(self.access.fd.map(|record| record.map(|record| record.value())))
+    }
+
+    /// The two memory words accessed by `ldc1` and `sdc1`.
+    #[must_use]
+    pub fn memory(&self) -> u64 {
+        pair(self.access.memory.map(|record| record.map(|record| record.value())))
+    }
+}
+
+fn pair(words: [Option<u32>; 2]) -> u64 {
+    words[0].unwrap_or_default() as u64 | (words[1].unwrap_or_default() as u64) << 32
+}
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+use crate::events::memory::{MemoryLocalEvent, MemoryReadRecord, MemoryWriteRecord};
+
+/// The number of words in a digit of a `BIGINT_MULMOD` operand. Operands are made of 256-bit
+/// digits.
+pub const BIGINT_DIGIT_WORDS: usize = 8;
+
+/// The maximum number of digits of a `BIGINT_MULMOD` operand, i.e. 4096-bit operands.
+pub const BIGINT_MAX_DIGITS: usize = 16;
+
+/// `BigInt` MulMod Event.
+///
+/// This event is emitted when a variable-width modular multiplication is performed.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct BigIntMulModEvent {
+    /// The shard number.
+    pub shard: u32,
+    /// The clock cycle.
+    pub clk: u32,
+    /// The number of 256-bit digits of the operands.
+    pub num_digits: u32,
+    /// The memory record for the number of digits.
+    pub num_digits_memory: MemoryReadRecord,
+    /// The pointer to the x value.
+    pub x_ptr: u32,
+    /// The x value as a list of words.
+    pub x: Vec<u32>,
+    /// The pointer to the y value.
+    pub y_ptr: u32,
+    /// The y value as a list of words.
+    pub y: Vec<u32>,
+    /// The modulus as a list of words.
+    pub modulus: Vec<u32>,
+    /// The memory records for the x value.
+    pub x_memory_records: Vec<MemoryWriteRecord>,
+    /// The memory records for the y value.
+    pub y_memory_records: Vec<MemoryReadRecord>,
+    /// The memory records for the modulus.
+    pub modulus_memory_records: Vec<MemoryReadRecord>,
+    /// The local memory access records.
+    pub local_mem_access: Vec<MemoryLocalEvent>,
+}
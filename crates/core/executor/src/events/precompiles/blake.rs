@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+use crate::events::{
+    memory::{MemoryReadRecord, MemoryWriteRecord},
+    MemoryLocalEvent,
+};
+
+/// BLAKE2s/BLAKE3 Rounds Event.
+///
+/// This event is emitted when the rounds of a BLAKE2s or BLAKE3 compression are performed.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct BlakeRoundsEvent {
+    /// The shard number.
+    pub shard: u32,
+    /// The clock cycle.
+    pub clk: u32,
+    /// The pointer to the working vector.
+    pub v_ptr: u32,
+    /// The pointer to the message block.
+    pub m_ptr: u32,
+    /// The working vector before the rounds.
+    pub v: [u32; 16],
+    /// The message block.
+    pub m: [u32; 16],
+    /// The memory records for the working vector.
+    pub v_memory_records: Vec<MemoryWriteRecord>,
+    /// The memory records for the message block.
+    pub m_memory_records: Vec<MemoryReadRecord>,
+    /// The local memory access records.
+    pub local_mem_access: Vec<MemoryLocalEvent>,
+}
+
+/// BLAKE2b Rounds Event.
+///
+/// This event is emitted when the rounds of a BLAKE2b compression are performed. The 64-bit words
+/// are stored as pairs of little-endian 32-bit words.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct Blake2bRoundsEvent {
+    /// The shard number.
+    pub shard: u32,
+    /// The clock cycle.
+    pub clk: u32,
+    /// The pointer to the working vector.
+    pub v_ptr: u32,
+    /// The pointer to the message block.
+    pub m_ptr: u32,
+    /// The working vector before the rounds.
+    pub v: [u32; 32],
+    /// The message block.
+    pub m: [u32; 32],
+    /// The memory records for the working vector.
+    pub v_memory_records: Vec<MemoryWriteRecord>,
+    /// The memory records for the message block.
+    pub m_memory_records: Vec<MemoryReadRecord>,
+    /// The local memory access records.
+    pub local_mem_access: Vec<MemoryLocalEvent>,
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+use crate::events::{
+    memory::{MemoryReadRecord, MemoryWriteRecord},
+    MemoryLocalEvent,
+};
+
+/// Custom Precompile Event.
+///
+/// This event is emitted by a precompile registered outside of Ziren under one of the custom
+/// syscall codes. The precompile encodes the values its chip needs as words in `data`.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct CustomPrecompileEvent {
+    /// The shard number.
+    pub shard: u32,
+    /// The clock cycle.
+    pub clk: u32,
+    /// The first argument of the syscall.
+    pub arg1: u32,
+    /// The second argument of the syscall.
+    pub arg2: u32,
+    /// The values of the event, in an encoding chosen by the precompile.
+    pub data: Vec<u32>,
+    /// The memory records of the reads.
+    pub read_records: Vec<MemoryReadRecord>,
+    /// The memory records of the writes.
+    pub write_records: Vec<MemoryWriteRecord>,
+    /// The local memory access records.
+    pub local_mem_access: Vec<MemoryLocalEvent>,
+}
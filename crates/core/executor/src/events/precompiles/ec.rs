@@ -63,6 +63,31 @@ pub struct EllipticCurveDoubleEvent {
     pub local_mem_access: Vec<MemoryLocalEvent>,
 }
 
+/// Elliptic Curve Scalar Multiplication Event.
+///
+/// This event is emitted when a point on an elliptic curve is multiplied by a scalar.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct EllipticCurveMulEvent {
+    /// The shard number.
+    pub shard: u32,
+    /// The clock cycle.
+    pub clk: u32,
+    /// The pointer to the point.
+    pub p_ptr: u32,
+    /// The point as a list of words.
+    pub p: Vec<u32>,
+    /// The pointer to the scalar.
+    pub s_ptr: u32,
+    /// The scalar as a list of little-endian words.
+    pub s: Vec<u32>,
+    /// The memory records for the point.
+    pub p_memory_records: Vec<MemoryWriteRecord>,
+    /// The memory records for the scalar.
+    pub s_memory_records: Vec<MemoryReadRecord>,
+    /// The local memory access records.
+    pub local_mem_access: Vec<MemoryLocalEvent>,
+}
+
 /// Elliptic Curve Point Decompress Event.
 ///
 /// This event is emitted when an elliptic curve point decompression operation is performed.
@@ -175,6 +200,72 @@ pub fn create_ec_double_event<E: EllipticCurve>(
     }
 }
 
+/// Create an elliptic curve scalar multiplication event.
+///
+/// It takes a pointer to a point and a pointer to a little-endian scalar with as many words as a
+/// base field element, multiplies the point by the scalar with a least-significant-bit-first
+/// double-and-add, and writes the result back to the point's memory location.
+///
+/// The point must not be the identity, the scalar must be non-zero and smaller than the order of
+/// the point, so that none of the intermediate additions hit an exceptional case.
+pub fn create_ec_mul_event<E: EllipticCurve>(
+    rt: &mut SyscallContext,
+    arg1: u32,
+    arg2: u32,
+) -> EllipticCurveMulEvent {
+    let start_clk = rt.clk;
+    let p_ptr = arg1;
+    if !p_ptr.is_multiple_of(4) {
+        panic!();
+    }
+    let s_ptr = arg2;
+    if !s_ptr.is_multiple_of(4) {
+        panic!();
+    }
+
+    let num_words = <E::BaseField as NumWords>::WordsCurvePoint::USIZE;
+    let num_words_scalar = <E::BaseField as NumWords>::WordsFieldElement::USIZE;
+
+    let p = rt.slice_unsafe(p_ptr, num_words);
+
+    let (s_memory_records, s) = rt.mr_slice(s_ptr, num_words_scalar);
+
+    // The point is written one cycle later since the point and the scalar could overlap.
+    rt.clk += 1;
+
+    let mut acc: Option<AffinePoint<E>> = None;
+    let mut base = AffinePoint::<E>::from_words_le(&p);
+    for word in s.iter() {
+        for i in 0..32 {
+            if (word >> i) & 1 == 1 {
+                acc = Some(match acc {
+                    None => base.clone(),
+                    Some(acc) => {
+                        assert!(acc.x != base.x, "scalar multiplication hit an exceptional case");
+                        acc + base.clone()
+                    }
+                });
+            }
+            base = E::ec_double(&base);
+        }
+    }
+    let result_words = acc.expect("scalar multiplication by zero").to_words_le();
+
+    let p_memory_records = rt.mw_slice(p_ptr, &result_words);
+
+    EllipticCurveMulEvent {
+        shard: rt.current_shard(),
+        clk: start_clk,
+        p_ptr,
+        p,
+        s_ptr,
+        s,
+        p_memory_records,
+        s_memory_records,
+        local_mem_access: rt.postprocess(),
+    }
+}
+
 /// Create an elliptic curve decompress event.
 ///
 /// It takes a pointer to a memory location, reads the point from memory, decompresses it, and
@@ -92,3 +92,26 @@ pub struct Fp2MulEvent {
     /// The local memory access records.
     pub local_mem_access: Vec<MemoryLocalEvent>,
 }
+
+/// Emulated Degree 6 Field Multiplication Events.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct Fp6MulEvent {
+    /// The shard number.
+    pub shard: u32,
+    /// The clock cycle.
+    pub clk: u32,
+    /// The pointer to the x operand.
+    pub x_ptr: u32,
+    /// The x operand.
+    pub x: Vec<u32>,
+    /// The pointer to the y operand.
+    pub y_ptr: u32,
+    /// The y operand.
+    pub y: Vec<u32>,
+    /// The memory records for the x operand.
+    pub x_memory_records: Vec<MemoryWriteRecord>,
+    /// The memory records for the y operand.
+    pub y_memory_records: Vec<MemoryReadRecord>,
+    /// The local memory access records.
+    pub local_mem_access: Vec<MemoryLocalEvent>,
+}
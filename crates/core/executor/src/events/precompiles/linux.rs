@@ -29,4 +29,19 @@ pub struct LinuxEvent {
     pub write_records: Vec<MemoryWriteRecord>,
     /// The local memory accesses.
     pub local_mem_access: Vec<MemoryLocalEvent>,
+    /// The address of the buffer filled by the syscall, e.g. by `read` or `fstat64`.
+    pub buf_addr: u32,
+    /// The number of bytes written to the buffer.
+    pub buf_len: u32,
+    /// The memory records for the words covering the buffer, in increasing address order.
+    pub buf_records: Vec<MemoryWriteRecord>,
+}
+
+impl LinuxEvent {
+    /// The number of `SysLinux` rows used by the event: one for the syscall itself and one for
+    /// each buffer word it writes.
+    #[must_use]
+    pub fn num_rows(&self) -> usize {
+        1 + self.buf_records.len()
+    }
 }
@@ -1,3 +1,6 @@
+mod bigint_mulmod;
+mod blake;
+mod custom;
 mod ec;
 mod edwards;
 mod fptower;
@@ -11,6 +14,9 @@ mod uint256;
 
 use super::{MemoryLocalEvent, SyscallEvent};
 use crate::syscalls::SyscallCode;
+pub use bigint_mulmod::*;
+pub use blake::*;
+pub use custom::*;
 pub use ec::*;
 pub use edwards::*;
 pub use fptower::*;
@@ -42,12 +48,16 @@ pub enum PrecompileEvent {
     Secp256k1Add(EllipticCurveAddEvent),
     /// Secp256k1 curve double precompile event.
     Secp256k1Double(EllipticCurveDoubleEvent),
+    /// Secp256k1 curve scalar multiplication precompile event.
+    Secp256k1Mul(EllipticCurveMulEvent),
     /// Secp256k1 curve decompress precompile event.
     Secp256k1Decompress(EllipticCurveDecompressEvent),
     /// Secp256r1 curve add precompile event.
     Secp256r1Add(EllipticCurveAddEvent),
     /// Secp256r1 curve double precompile event.
     Secp256r1Double(EllipticCurveDoubleEvent),
+    /// Secp256r1 curve scalar multiplication precompile event.
+    Secp256r1Mul(EllipticCurveMulEvent),
     /// Secp256r1 curve decompress precompile event.
     Secp256r1Decompress(EllipticCurveDecompressEvent),
     /// K256 curve decompress precompile event.
@@ -56,16 +66,22 @@ pub enum PrecompileEvent {
     Bn254Add(EllipticCurveAddEvent),
     /// Bn254 curve double precompile event.
     Bn254Double(EllipticCurveDoubleEvent),
+    /// Bn254 curve scalar multiplication precompile event.
+    Bn254Mul(EllipticCurveMulEvent),
     /// Bn254 base field operation precompile event.
     Bn254Fp(FpOpEvent),
     /// Bn254 quadratic field add/sub precompile event.
     Bn254Fp2AddSub(Fp2AddSubEvent),
     /// Bn254 quadratic field mul precompile event.
     Bn254Fp2Mul(Fp2MulEvent),
+    /// Bn254 sextic field mul precompile event.
+    Bn254Fp6Mul(Fp6MulEvent),
     /// Bls12-381 curve add precompile event.
     Bls12381Add(EllipticCurveAddEvent),
     /// Bls12-381 curve double precompile event.
     Bls12381Double(EllipticCurveDoubleEvent),
+    /// Bls12-381 curve scalar multiplication precompile event.
+    Bls12381Mul(EllipticCurveMulEvent),
     /// Bls12-381 curve decompress precompile event.
     Bls12381Decompress(EllipticCurveDecompressEvent),
     /// Bls12-381 base field operation precompile event.
@@ -74,14 +90,26 @@ pub enum PrecompileEvent {
     Bls12381Fp2AddSub(Fp2AddSubEvent),
     /// Bls12-381 quadratic field mul precompile event.
     Bls12381Fp2Mul(Fp2MulEvent),
+    /// Bls12-381 sextic field mul precompile event.
+    Bls12381Fp6Mul(Fp6MulEvent),
     /// Uint256 mul precompile event.
     Uint256Mul(Uint256MulEvent),
     /// U256XU2048 mul precompile event.
     U256xU2048Mul(U256xU2048MulEvent),
+    /// Variable-width modular multiplication precompile event.
+    BigIntMulMod(BigIntMulModEvent),
     /// Poseidon2 permutation precompile event.
     Poseidon2Permute(Poseidon2PermuteEvent),
+    /// BLAKE2s rounds precompile event.
+    Blake2sRounds(BlakeRoundsEvent),
+    /// BLAKE3 rounds precompile event.
+    Blake3Rounds(BlakeRoundsEvent),
+    /// BLAKE2b rounds precompile event.
+    Blake2bRounds(Blake2bRoundsEvent),
     /// linux precompile event.
     Linux(LinuxEvent),
+    /// Custom precompile event.
+    Custom(CustomPrecompileEvent),
 }
 
 /// Trait to retrieve all the local memory events from a vec of precompile events.
@@ -121,6 +149,12 @@ impl PrecompileLocalMemory for Vec<(SyscallEvent, PrecompileEvent)> {
                 | PrecompileEvent::Bls12381Double(e) => {
                     iterators.push(e.local_mem_access.iter());
                 }
+                PrecompileEvent::Secp256k1Mul(e)
+                | PrecompileEvent::Secp256r1Mul(e)
+                | PrecompileEvent::Bn254Mul(e)
+                | PrecompileEvent::Bls12381Mul(e) => {
+                    iterators.push(e.local_mem_access.iter());
+                }
                 PrecompileEvent::Secp256k1Decompress(e)
                 | PrecompileEvent::Secp256r1Decompress(e)
                 | PrecompileEvent::K256Decompress(e)
@@ -133,6 +167,9 @@ impl PrecompileLocalMemory for Vec<(SyscallEvent, PrecompileEvent)> {
                 PrecompileEvent::U256xU2048Mul(e) => {
                     iterators.push(e.local_mem_access.iter());
                 }
+                PrecompileEvent::BigIntMulMod(e) => {
+                    iterators.push(e.local_mem_access.iter());
+                }
                 PrecompileEvent::Bls12381Fp(e) | PrecompileEvent::Bn254Fp(e) => {
                     iterators.push(e.local_mem_access.iter());
                 }
@@ -142,12 +179,24 @@ impl PrecompileLocalMemory for Vec<(SyscallEvent, PrecompileEvent)> {
                 PrecompileEvent::Bls12381Fp2Mul(e) | PrecompileEvent::Bn254Fp2Mul(e) => {
                     iterators.push(e.local_mem_access.iter());
                 }
+                PrecompileEvent::Bls12381Fp6Mul(e) | PrecompileEvent::Bn254Fp6Mul(e) => {
+                    iterators.push(e.local_mem_access.iter());
+                }
                 PrecompileEvent::Poseidon2Permute(e) => {
                     iterators.push(e.local_mem_access.iter());
                 }
+                PrecompileEvent::Blake2sRounds(e) | PrecompileEvent::Blake3Rounds(e) => {
+                    iterators.push(e.local_mem_access.iter());
+                }
+                PrecompileEvent::Blake2bRounds(e) => {
+                    iterators.push(e.local_mem_access.iter());
+                }
                 PrecompileEvent::Linux(e) => {
                     iterators.push(e.local_mem_access.iter());
                 }
+                PrecompileEvent::Custom(e) => {
+                    iterators.push(e.local_mem_access.iter());
+                }
             }
         }
 
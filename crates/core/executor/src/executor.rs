@@ -17,15 +17,19 @@ use crate::{
     context::ZKMContext,
     dependencies::{
         emit_branch_dependencies, emit_cloclz_dependencies, emit_divrem_dependencies,
-        emit_jump_dependencies, emit_memory_dependencies, emit_misc_dependencies,
+        emit_fpu_dependencies, emit_jump_dependencies, emit_memory_dependencies,
+        emit_misc_dependencies,
     },
     estimate_mips_event_counts, estimate_mips_lde_size,
     events::{
-        AluEvent, BranchEvent, CompAluEvent, CpuEvent, JumpEvent, MemInstrEvent,
-        MemoryAccessPosition, MemoryInitializeFinalizeEvent, MemoryLocalEvent, MemoryReadRecord,
-        MemoryRecord, MemoryRecordEnum, MemoryWriteRecord, MiscEvent, MovCondEvent, SyscallEvent,
+        AluEvent, BranchEvent, CompAluEvent, CpuEvent, FpuAccessRecord, FpuEvent, JumpEvent,
+        MemInstrEvent, MemoryAccessPosition, MemoryInitializeFinalizeEvent, MemoryLocalEvent,
+        MemoryReadRecord, MemoryRecord, MemoryRecordEnum, MemoryWriteRecord, MiscEvent,
+        MovCondEvent, SyscallEvent,
     },
+    fpu,
     hook::{HookEnv, HookRegistry},
+    is_branch_taken,
     memory::{Entry, Memory},
     pad_mips_event_counts,
     record::{ExecutionRecord, MemoryAccessRecord},
@@ -304,7 +308,8 @@ impl<'a> Executor<'a> {
         let record = ExecutionRecord::new(program.clone());
 
         // Determine the maximum number of cycles for any syscall.
-        let syscall_map = default_syscall_map();
+        let mut syscall_map = default_syscall_map();
+        syscall_map.extend(context.custom_syscalls);
         let max_syscall_cycles =
             syscall_map.values().map(|syscall| syscall.num_extra_cycles()).max().unwrap_or(0);
 
@@ -322,10 +327,15 @@ impl<'a> Executor<'a> {
         let costs: HashMap<MipsAirId, usize> =
             costs.into_iter().map(|(k, v)| (MipsAirId::from_str(&k).unwrap(), v)).collect();
 
+        let state = match context.snapshot {
+            Some(snapshot) => snapshot.resumed(),
+            None => ExecutionState::new(program.pc_start, program.next_pc),
+        };
+
         Self {
             record,
             records: vec![],
-            state: ExecutionState::new(program.pc_start, program.next_pc),
+            state,
             program,
             memory_accesses: MemoryAccessRecord::default(),
             shard_size: (opts.shard_size as u32) * 4,
@@ -393,6 +403,18 @@ impl<'a> Executor<'a> {
         runtime
     }
 
+    /// Adds custom syscalls to a runtime recovered from a checkpoint, so that it executes the
+    /// checkpoint like the runtime which created it.
+    pub fn add_custom_syscalls(
+        &mut self,
+        custom_syscalls: &HashMap<SyscallCode, Arc<dyn Syscall>>,
+    ) {
+        self.syscall_map
+            .extend(custom_syscalls.iter().map(|(code, syscall)| (*code, syscall.clone())));
+        self.max_syscall_cycles =
+            self.syscall_map.values().map(|syscall| syscall.num_extra_cycles()).max().unwrap_or(0);
+    }
+
     /// Get the current values of the registers.
     #[allow(clippy::single_match_else)]
     #[must_use]
@@ -1147,6 +1169,8 @@ impl<'a> Executor<'a> {
                 hi_or_prev_a.unwrap_or(0),
                 record.hi,
             );
+        } else if instruction.is_fpu_instruction() {
+            self.emit_fpu_event(clk, instruction, b, c, record.fpu);
         } else if instruction.is_syscall_instruction() {
             self.emit_syscall_event(clk, record.a, syscall_code, b, c, next_pc);
         } else {
@@ -1247,7 +1271,7 @@ impl<'a> Executor<'a> {
             Opcode::SLT | Opcode::SLTU => {
                 self.record.lt_events.push(event);
             }
-            Opcode::MUL | Opcode::MULT | Opcode::MULTU => {
+            Opcode::MUL | Opcode::MULT | Opcode::MULTU | Opcode::MUH | Opcode::MUHU => {
                 self.record.mul_events.push(event_comp);
             }
             Opcode::DIV | Opcode::DIVU | Opcode::MOD | Opcode::MODU => {
@@ -1333,14 +1357,26 @@ impl<'a> Executor<'a> {
         prev_a: u32,
         hi_record: Option<MemoryRecordEnum>,
     ) {
-        if matches!(opcode, Opcode::MNE | Opcode::MEQ | Opcode::WSBH) {
+        if matches!(
+            opcode,
+            Opcode::MNE | Opcode::MEQ | Opcode::WSBH | Opcode::SELEQZ | Opcode::SELNEZ
+        ) {
             let event =
                 MovCondEvent::new(self.state.pc, self.state.next_pc, opcode, a, b, c, prev_a);
             self.record.movcond_events.push(event);
         } else {
             let hi_access = match hi_record {
                 Some(MemoryRecordEnum::Write(record)) => record,
-                _ => MemoryWriteRecord::default(),
+                // The read of `rt` by `lsa` and `align`, which leaves the register unchanged.
+                Some(MemoryRecordEnum::Read(record)) => MemoryWriteRecord {
+                    value: record.value,
+                    shard: record.shard,
+                    timestamp: record.timestamp,
+                    prev_value: record.value,
+                    prev_shard: record.prev_shard,
+                    prev_timestamp: record.prev_timestamp,
+                },
+                None => MemoryWriteRecord::default(),
             };
 
             let event = MiscEvent::new(
@@ -1360,6 +1396,34 @@ impl<'a> Executor<'a> {
         }
     }
 
+    /// Emit an FPU event.
+    #[inline]
+    fn emit_fpu_event(
+        &mut self,
+        clk: u32,
+        instruction: &Instruction,
+        b: u32,
+        c: u32,
+        access: FpuAccessRecord,
+    ) {
+        let event = FpuEvent::new(
+            self.shard(),
+            clk,
+            self.state.pc,
+            self.state.next_pc,
+            instruction.opcode,
+            b,
+            c,
+            access,
+        );
+        if instruction.is_fpu_arith_instruction() {
+            self.record.fpu_arith_events.push(event);
+        } else {
+            self.record.fpu_misc_events.push(event);
+        }
+        emit_fpu_dependencies(self, event);
+    }
+
     #[inline]
     #[allow(clippy::too_many_arguments)]
     pub(crate) fn syscall_event(
@@ -1437,7 +1501,9 @@ impl<'a> Executor<'a> {
         c: u32,
     ) -> (Option<u32>, u32, u32, u32) {
         let hi = if op.opcode.is_use_lo_hi_alu() {
-            self.rw_cpu(Register::LO, a, MemoryAccessPosition::A);
+            // `rd` is `LO`, except for the MIPS32r6 divisions that write the quotient to a GPR.
+            // MIPS32r6 has no `HI`, so the remainder left there is never observed.
+            self.rw_cpu(rd, a, MemoryAccessPosition::A);
             self.rw_cpu(Register::HI, hi, MemoryAccessPosition::HI);
             Some(hi)
         } else {
@@ -1497,6 +1563,16 @@ impl<'a> Executor<'a> {
             } else if instruction.is_branch_cmp_instruction() {
                 self.local_counts.event_counts[Opcode::ADD] += 1;
                 self.local_counts.event_counts[Opcode::SLT] += 2;
+            } else if instruction.is_branch_instruction() {
+                if !instruction.opcode.is_compact_branch() {
+                    self.local_counts.event_counts[Opcode::ADD] += 1;
+                }
+                self.local_counts.event_counts[Opcode::SLT] += 2;
+            } else if instruction.is_trap_instruction() {
+                self.local_counts.event_counts[Opcode::SLT] += 2;
+            } else if instruction.opcode == Opcode::LSA {
+                self.local_counts.event_counts[Opcode::SLL] += 1;
+                self.local_counts.event_counts[Opcode::ADD] += 1;
             } else if instruction.is_mov_cond_instruction() {
                 self.local_counts.event_counts[Opcode::ADD] += 1;
             } else if instruction.opcode == Opcode::EXT {
@@ -1521,8 +1597,20 @@ impl<'a> Executor<'a> {
                 self.local_counts.event_counts[Opcode::SLTU] += 1;
             } else if instruction.is_maddsub_instruction() {
                 self.local_counts.event_counts[Opcode::MULT] += 1;
-            } else if instruction.opcode == Opcode::JumpDirect {
+            } else if matches!(instruction.opcode, Opcode::JumpDirect | Opcode::JumpCompact) {
                 self.local_counts.event_counts[Opcode::ADD] += 1;
+            } else if matches!(instruction.opcode, Opcode::LDC1 | Opcode::SDC1) {
+                self.local_counts.event_counts[Opcode::ADD] += 1;
+            } else if matches!(
+                instruction.opcode,
+                Opcode::CVT_S_W
+                    | Opcode::CVT_D_W
+                    | Opcode::CVT_W_S
+                    | Opcode::CVT_W_D
+                    | Opcode::TRUNC_W_S
+                    | Opcode::TRUNC_W_D
+            ) {
+                self.local_counts.event_counts[Opcode::SUB] += 1;
             }
         }
 
@@ -1533,8 +1621,10 @@ impl<'a> Executor<'a> {
         } else if instruction.is_memory_store_instruction() {
             (hi_or_prev_a, a, b, c) = self.execute_store(instruction)?;
         } else if instruction.is_branch_instruction() {
-            (a, b, c, next_next_pc) = self.execute_branch(instruction, next_pc, next_next_pc);
-            self.state.next_is_delayslot = true;
+            (a, b, c, next_pc, next_next_pc) =
+                self.execute_branch(instruction, next_pc, next_next_pc);
+        } else if instruction.opcode == Opcode::JumpCompact {
+            (a, b, c, next_pc, next_next_pc) = self.execute_jump_compact(instruction);
         } else if instruction.is_jump_instruction() {
             // Jump instructions.
             (a, b, c, next_next_pc) = if instruction.opcode == Opcode::Jump {
@@ -1558,8 +1648,14 @@ impl<'a> Executor<'a> {
                 (hi_or_prev_a, a, b, c) = self.execute_ins(instruction);
             } else if instruction.opcode == Opcode::SEXT {
                 (a, b, c) = self.execute_sext(instruction);
-            } else if instruction.opcode == Opcode::TEQ {
-                (a, b, c) = self.execute_teq(instruction)?;
+            } else if instruction.is_trap_instruction() {
+                (a, b, c) = self.execute_trap(instruction)?;
+            } else if matches!(instruction.opcode, Opcode::LSA | Opcode::ALIGN) {
+                (a, b, c) = self.execute_lsa_align(instruction);
+            } else if instruction.opcode == Opcode::BITSWAP {
+                (a, b, c) = self.execute_bitswap(instruction);
+            } else if instruction.opcode == Opcode::CTC1 {
+                (a, b, c) = self.execute_ctc1(instruction)?;
             } else if instruction.opcode == Opcode::MSUBU {
                 (hi_or_prev_a, a, b, c) = self.execute_msubu(instruction);
             } else if instruction.opcode == Opcode::MADD {
@@ -1567,6 +1663,8 @@ impl<'a> Executor<'a> {
             } else if instruction.opcode == Opcode::MSUB {
                 (hi_or_prev_a, a, b, c) = self.execute_msub(instruction);
             }
+        } else if instruction.is_fpu_instruction() {
+            (a, b, c) = self.execute_fpu(instruction)?;
         } else if instruction.opcode == Opcode::SYSCALL {
             let syscall_id = self.register(Register::V0);
             c = self.rr_cpu(Register::A1, MemoryAccessPosition::C);
@@ -1689,6 +1787,110 @@ impl<'a> Executor<'a> {
         Ok(())
     }
 
+    /// Execute an FPU instruction.
+    ///
+    /// The FPU chips access the floating point registers and memory themselves, so the CPU only
+    /// reads the base register of `ldc1` and `sdc1` and writes `$zero`.
+    fn execute_fpu(
+        &mut self,
+        instruction: &Instruction,
+    ) -> Result<(u32, u32, u32), ExecutionError> {
+        let opcode = instruction.opcode;
+        let (b, c) = if matches!(opcode, Opcode::LDC1 | Opcode::SDC1) {
+            let base = self.rr_cpu((instruction.op_b as u8).into(), MemoryAccessPosition::B);
+            let addr = base.wrapping_add(sign_extend::<16>(instruction.op_c & 0xFFFF));
+            if addr & 7 != 0 {
+                return Err(ExecutionError::InvalidMemoryAccess(opcode, addr));
+            }
+            if addr + 7 > MAX_MEMORY as u32 {
+                return Err(ExecutionError::MemoryOutOfBoundsAccess(addr as u64));
+            }
+
+            let ft = (instruction.op_c >> 16) as u8;
+            let timestamp = self.timestamp(&MemoryAccessPosition::Memory);
+            for i in 0..2 {
+                let (register, addr) = (Register::from(ft + i as u8), addr + 4 * i as u32);
+                let record: MemoryRecordEnum = if opcode == Opcode::LDC1 {
+                    let record = self.mr(addr, self.shard(), timestamp, None);
+                    self.fpu_rw(register, record.value, i);
+                    record.into()
+                } else {
+                    let value = self.fpu_rr(register, MemoryAccessPosition::A, i);
+                    self.mw(addr, value, self.shard(), timestamp, None).into()
+                };
+                if self.executor_mode == ExecutorMode::Trace {
+                    self.memory_accesses.fpu.memory[i] = Some(record);
+                }
+            }
+            (base, instruction.op_c)
+        } else {
+            let double = fpu::source_is_double(opcode);
+            let read = |executor: &mut Self, register: u32, position: MemoryAccessPosition| {
+                let lo = executor.fpu_rr(Register::from(register as u8), position, 0);
+                let hi = if double {
+                    executor.fpu_rr(Register::from(register as u8 + 1), position, 1)
+                } else {
+                    0
+                };
+                lo as u64 | (hi as u64) << 32
+            };
+            let ft = if fpu::is_binary(opcode) {
+                read(self, (instruction.op_b >> 8) & 0xFF, MemoryAccessPosition::C)
+            } else {
+                0
+            };
+            let fs = read(self, instruction.op_b & 0xFF, MemoryAccessPosition::B);
+
+            let fd = Register::from(instruction.op_c as u8);
+            if fpu::compare_condition(opcode).is_some() {
+                self.fpu_rw(fd, fpu::compare(opcode, fs, ft) as u32, 0);
+            } else {
+                let result = fpu::execute(opcode, fs, ft);
+                self.fpu_rw(fd, result as u32, 0);
+                if fpu::result_is_double(opcode) {
+                    self.fpu_rw(Register::from(fd as u8 + 1), (result >> 32) as u32, 1);
+                }
+            }
+            (instruction.op_b, instruction.op_c)
+        };
+
+        self.rw_cpu(Register::ZERO, 0, MemoryAccessPosition::A);
+        Ok((0, b, c))
+    }
+
+    /// Read a register on behalf of an FPU chip, recording the access in slot `i`.
+    fn fpu_rr(&mut self, register: Register, position: MemoryAccessPosition, i: usize) -> u32 {
+        if self.executor_mode != ExecutorMode::Trace {
+            return self.rr(register, self.shard(), self.timestamp(&position));
+        }
+
+        let record = self.rr_traced(register, self.shard(), self.timestamp(&position), None);
+        if !self.unconstrained {
+            let access = &mut self.memory_accesses.fpu;
+            match position {
+                MemoryAccessPosition::B => access.fs[i] = Some(record),
+                MemoryAccessPosition::C => access.ft[i] = Some(record),
+                MemoryAccessPosition::A => access.fd[i] = Some(record.into()),
+                _ => unreachable!(),
+            }
+        }
+        record.value
+    }
+
+    /// Write a register on behalf of an FPU chip, recording the access in slot `i`.
+    fn fpu_rw(&mut self, register: Register, value: u32, i: usize) {
+        let timestamp = self.timestamp(&MemoryAccessPosition::A);
+        if self.executor_mode != ExecutorMode::Trace {
+            self.rw(register, value, self.shard(), timestamp);
+            return;
+        }
+
+        let record = self.rw_traced(register, value, self.shard(), timestamp, None);
+        if !self.unconstrained {
+            self.memory_accesses.fpu.fd[i] = Some(record.into());
+        }
+    }
+
     fn execute_maddu(&mut self, instruction: &Instruction) -> (Option<u32>, u32, u32, u32) {
         let (lo, rt, rs) = (
             instruction.op_a.into(),
@@ -1818,21 +2020,92 @@ impl<'a> Executor<'a> {
         (Some(prev_a), a, b, c)
     }
 
-    fn execute_teq(
+    fn execute_trap(
         &mut self,
         instruction: &Instruction,
     ) -> Result<(u32, u32, u32), ExecutionError> {
-        let (rs, rt) = (instruction.op_a.into(), (instruction.op_b as u8).into());
+        let rs = instruction.op_a.into();
 
-        let src2 = self.rr_cpu(rt, MemoryAccessPosition::B);
+        let src2 = if instruction.imm_b {
+            instruction.op_b
+        } else {
+            self.rr_cpu((instruction.op_b as u8).into(), MemoryAccessPosition::B)
+        };
         let src1 = self.rr_cpu(rs, MemoryAccessPosition::A);
 
-        if src1 == src2 {
+        let trap = match instruction.opcode {
+            Opcode::TEQ => src1 == src2,
+            Opcode::TNE => src1 != src2,
+            Opcode::TGE => (src1 as i32) >= (src2 as i32),
+            Opcode::TGEU => src1 >= src2,
+            Opcode::TLT => (src1 as i32) < (src2 as i32),
+            Opcode::TLTU => src1 < src2,
+            _ => unreachable!(),
+        };
+        if trap {
             return Err(ExecutionError::ExceptionOrTrap());
         }
         Ok((src1, src2, 0))
     }
 
+    /// Executes `lsa` and `align`, whose immediate operand packs `rt` above the shift amount.
+    ///
+    /// `rt` is read before `rs`, in the slot of the third operand that the CPU leaves unused.
+    fn execute_lsa_align(&mut self, instruction: &Instruction) -> (u32, u32, u32) {
+        let (rd, rs, c) =
+            (instruction.op_a.into(), (instruction.op_b as u8).into(), instruction.op_c);
+        let rt = self.misc_rr(((c >> 5) as u8).into());
+        let b = self.rr_cpu(rs, MemoryAccessPosition::B);
+        let sa = c & 0x3;
+        let a = if instruction.opcode == Opcode::LSA {
+            (b << (sa + 1)).wrapping_add(rt)
+        } else if sa == 0 {
+            rt
+        } else {
+            (rt << (8 * sa)) | (b >> (8 * (4 - sa)))
+        };
+        self.rw_cpu(rd, a, MemoryAccessPosition::A);
+        (a, b, c)
+    }
+
+    /// Reads `rt` on behalf of the misc chip, recording the access in the slot of `HI`.
+    fn misc_rr(&mut self, register: Register) -> u32 {
+        let timestamp = self.timestamp(&MemoryAccessPosition::C);
+        if self.executor_mode != ExecutorMode::Trace {
+            return self.rr(register, self.shard(), timestamp);
+        }
+
+        let record = self.rr_traced(register, self.shard(), timestamp, None);
+        if !self.unconstrained {
+            self.memory_accesses.hi = Some(record.into());
+        }
+        record.value
+    }
+
+    fn execute_bitswap(&mut self, instruction: &Instruction) -> (u32, u32, u32) {
+        let (rd, rt) = (instruction.op_a.into(), (instruction.op_b as u8).into());
+        let b = self.rr_cpu(rt, MemoryAccessPosition::B);
+        let a = u32::from_le_bytes(b.to_le_bytes().map(u8::reverse_bits));
+        self.rw_cpu(rd, a, MemoryAccessPosition::A);
+        (a, b, 0)
+    }
+
+    /// Executes `ctc1`, which moves `rt` into `fcsr`.
+    ///
+    /// The FPU only rounds to nearest, so selecting any other rounding mode raises an exception.
+    fn execute_ctc1(
+        &mut self,
+        instruction: &Instruction,
+    ) -> Result<(u32, u32, u32), ExecutionError> {
+        let (fcsr, rt) = (instruction.op_a.into(), (instruction.op_b as u8).into());
+        let b = self.rr_cpu(rt, MemoryAccessPosition::B);
+        if b & fpu::FCSR_RM_MASK != 0 {
+            return Err(ExecutionError::ExceptionOrTrap());
+        }
+        self.rw_cpu(fcsr, b, MemoryAccessPosition::A);
+        Ok((b, b, 0))
+    }
+
     fn execute_condmov(&mut self, instruction: &Instruction) -> (Option<u32>, u32, u32, u32) {
         let (rd, rs, rt) = (
             instruction.op_a.into(),
@@ -1844,16 +2117,24 @@ impl<'a> Executor<'a> {
         let c = self.rr_cpu(rt, MemoryAccessPosition::C);
         let b = self.rr_cpu(rs, MemoryAccessPosition::B);
         let mov = match instruction.opcode {
-            Opcode::MEQ => c == 0,
-            Opcode::MNE => c != 0,
+            Opcode::MEQ | Opcode::SELEQZ => c == 0,
+            Opcode::MNE | Opcode::SELNEZ => c != 0,
             _ => {
                 unreachable!()
             }
         };
 
-        let a = if mov { b } else { a };
+        // SELEQZ and SELNEZ clear `rd` instead of keeping it.
+        let keeps_a = matches!(instruction.opcode, Opcode::MEQ | Opcode::MNE);
+        let a = if mov {
+            b
+        } else if keeps_a {
+            a
+        } else {
+            0
+        };
         self.rw_cpu(rd, a, MemoryAccessPosition::A);
-        (Some(prev_a), a, b, c)
+        (keeps_a.then_some(prev_a), a, b, c)
     }
 
     fn execute_alu(
@@ -1908,6 +2189,11 @@ impl<'a> Executor<'a> {
                 let out = b as u64 * c as u64;
                 (out as u32, (out >> 32) as u32) //lo,hi
             }
+            Opcode::MUH => {
+                let out = (((b as i32) as i64) * ((c as i32) as i64)) as u64;
+                ((out >> 32) as u32, 0)
+            }
+            Opcode::MUHU => (((b as u64 * c as u64) >> 32) as u32, 0),
             Opcode::DIV => (
                 ((b as i32) / (c as i32)) as u32, // lo
                 ((b as i32) % (c as i32)) as u32, // hi
@@ -2095,29 +2381,48 @@ impl<'a> Executor<'a> {
         }
     }
 
+    /// Executes a branch, returning its operands and the next two program counters.
+    ///
+    /// Branch-likely instructions skip their delay slot when not taken, and compact branches have
+    /// no delay slot, with the absolute target as their third operand.
     fn execute_branch(
         &mut self,
         instruction: &Instruction,
         next_pc: u32,
-        mut next_next_pc: u32,
-    ) -> (u32, u32, u32, u32) {
-        let (src1, src2, offset) = self.branch_rr(instruction);
-        let should_jump = match instruction.opcode {
-            Opcode::BEQ => src1 == src2,
-            Opcode::BNE => src1 != src2,
-            Opcode::BGEZ => (src1 as i32) >= 0,
-            Opcode::BLEZ => (src1 as i32) <= 0,
-            Opcode::BGTZ => (src1 as i32) > 0,
-            Opcode::BLTZ => (src1 as i32) < 0,
-            _ => {
-                unreachable!()
-            }
+        next_next_pc: u32,
+    ) -> (u32, u32, u32, u32, u32) {
+        let opcode = instruction.opcode;
+        let compact = opcode.is_compact_branch();
+        let (src1, src2, offset, should_jump) = if opcode.is_branch_link() {
+            let src2 = self.rr_cpu((instruction.op_b as u8).into(), MemoryAccessPosition::B);
+            let should_jump = is_branch_taken(opcode, src2, 0);
+            let link = instruction.op_a.into();
+            // Compact branches only link when taken.
+            let src1 = if compact && !should_jump {
+                self.rr_cpu(link, MemoryAccessPosition::A)
+            } else {
+                let return_pc = self.state.pc.wrapping_add(if compact { 4 } else { 8 });
+                self.rw_cpu(link, return_pc, MemoryAccessPosition::A);
+                return_pc
+            };
+            (src1, src2, instruction.op_c, should_jump)
+        } else {
+            let (src1, src2, offset) = self.branch_rr(instruction);
+            (src1, src2, offset, is_branch_taken(opcode, src1, src2))
         };
 
-        if should_jump {
-            next_next_pc = offset.wrapping_add(next_pc);
-        }
-        (src1, src2, offset, next_next_pc)
+        self.state.next_is_delayslot = !compact && (should_jump || !opcode.is_branch_likely());
+        let (next_pc, next_next_pc) = if compact {
+            let next_pc = if should_jump { offset } else { self.state.pc.wrapping_add(4) };
+            (next_pc, next_pc.wrapping_add(4))
+        } else if should_jump {
+            (next_pc, offset.wrapping_add(next_pc))
+        } else if opcode.is_branch_likely() {
+            (self.state.pc.wrapping_add(8), self.state.pc.wrapping_add(12))
+        } else {
+            (next_pc, next_next_pc)
+        };
+        (src1, src2, offset, next_pc, next_next_pc)
     }
 
     /// For jump, jumpi, jumpdirect instructions, we need to set the return address to link register
@@ -2152,6 +2457,19 @@ impl<'a> Executor<'a> {
         (return_pc, offset, 0, target_pc)
     }
 
+    /// Executes `jic` and `jialc`, which jump to `rt` plus an offset without a delay slot.
+    fn execute_jump_compact(&mut self, instruction: &Instruction) -> (u32, u32, u32, u32, u32) {
+        let (link, base, offset) =
+            (instruction.op_a.into(), (instruction.op_b as u8).into(), instruction.op_c);
+        let base = self.rr_cpu(base, MemoryAccessPosition::B);
+        let target_pc = base.wrapping_add(offset);
+
+        let return_pc = self.state.pc.wrapping_add(4);
+        self.rw_cpu(link, return_pc, MemoryAccessPosition::A);
+
+        (return_pc, base, offset, target_pc, target_pc.wrapping_add(4))
+    }
+
     /// Executes one cycle of the program, returning whether the program has finished.
     #[inline]
     #[allow(clippy::too_many_lines)]
@@ -2388,7 +2706,8 @@ impl<'a> Executor<'a> {
         Ok((checkpoint, done))
     }
 
-    fn initialize(&mut self) {
+    /// Load the memory image of the program. [`Self::execute`] does this on the first cycle.
+    pub fn initialize(&mut self) {
         self.state.clk = 0;
 
         tracing::debug!("loading memory image");
@@ -2428,6 +2747,23 @@ impl<'a> Executor<'a> {
         Ok(())
     }
 
+    /// Executes a single instruction, returning whether the program has finished.
+    ///
+    /// This is used by the debugger to stop between instructions. A branch and its delay slot are
+    /// two separate steps, see [`ExecutionState::next_is_delayslot`]. Unlike [`Self::execute`],
+    /// this does not load the memory image, so [`Self::initialize`] must be called first.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the instruction fails to execute.
+    pub fn step(&mut self) -> Result<bool, ExecutionError> {
+        let done = self.execute_cycle()?;
+        if done {
+            self.postprocess();
+        }
+        Ok(done)
+    }
+
     /// Executes up to `self.shard_batch_size` cycles of the program, returning whether the program
     /// has finished.
     pub fn execute(&mut self) -> Result<bool, ExecutionError> {
@@ -2652,9 +2988,9 @@ mod tests {
     };
     use zkm_stark::ZKMCoreOpts;
 
-    use crate::{Instruction, Opcode, Register};
+    use crate::{syscalls::SyscallCode, Instruction, IsaRevision, Opcode, Register};
 
-    use super::{Executor, Program};
+    use super::{ExecutionError, Executor, Program};
 
     fn _assert_send<T: Send>() {}
 
@@ -3144,6 +3480,108 @@ mod tests {
         assert_eq!(runtime.register(5.into()), 12);
     }
 
+    #[test]
+    fn test_bovc_bnvc() {
+        //   bovc/bnvc $29, $30, 12
+        //
+        // BOVC branches when `rs + rt` overflows as a signed addition, and BNVC when it does not.
+
+        // POP10 and POP30 with rs >= rt decode to BOVC and BNVC.
+        let bovc = Instruction::decode_from(0x23bd_0002, 0x100, IsaRevision::R6).unwrap();
+        assert_eq!((bovc.opcode, bovc.op_a, bovc.op_b, bovc.op_c), (Opcode::BOVC, 29, 29, 0x10c));
+        let bnvc = Instruction::decode_from(0x63dd_0002, 0x100, IsaRevision::R6).unwrap();
+        assert_eq!((bnvc.opcode, bnvc.op_a, bnvc.op_b, bnvc.op_c), (Opcode::BNVC, 30, 29, 0x10c));
+
+        let cases = [
+            (1, 2, false),
+            (0x7fff_ffff, 1, true),
+            (0x8000_0000, 0xffff_ffff, true),
+            (0x8000_0000, 0x7fff_ffff, false),
+            (0xffff_ffff, 1, false),
+        ];
+        for (opcode, overflow_branches) in [(Opcode::BOVC, true), (Opcode::BNVC, false)] {
+            for (lhs, rhs, overflows) in cases {
+                let instructions = vec![
+                    Instruction::new(Opcode::ADD, 29, 0, lhs, false, true),
+                    Instruction::new(Opcode::ADD, 30, 0, rhs, false, true),
+                    Instruction::new(opcode, 29, 30, 16, false, true),
+                    Instruction::new(Opcode::ADD, 31, 0, 2, false, true),
+                    Instruction::new(Opcode::ADD, 28, 0, 3, false, true),
+                ];
+                let program = Program::new(instructions, 0, 0);
+                let mut runtime = Executor::new(program, ZKMCoreOpts::default());
+                runtime.run().unwrap();
+                let taken = overflows == overflow_branches;
+                assert_eq!(runtime.register(31.into()), if taken { 0 } else { 2 }, "{opcode}");
+                assert_eq!(runtime.register(28.into()), 3);
+            }
+        }
+    }
+
+    #[test]
+    fn test_ctc1_rounding_mode() {
+        //   ctc1 $8, $31
+        //
+        // The FPU only rounds to nearest, so ctc1 raises an exception for any other rounding mode.
+
+        let ctc1 = Instruction::decode_from(0x44c8_f800, 0, IsaRevision::R2).unwrap();
+        assert_eq!((ctc1.opcode, ctc1.op_a, ctc1.op_b), (Opcode::CTC1, Register::FCSR as u8, 8));
+
+        for rm in 0..4 {
+            let fcsr = 0x0100_0f80 | rm;
+            let instructions = vec![
+                Instruction::new(Opcode::ADD, 8, 0, fcsr, false, true),
+                Instruction::new(Opcode::CTC1, Register::FCSR as u8, 8, 0, false, true),
+            ];
+            let program = Program::new(instructions, 0, 0);
+            let mut runtime = Executor::new(program, ZKMCoreOpts::default());
+            if rm == 0 {
+                runtime.run().unwrap();
+                assert_eq!(runtime.register(Register::FCSR), fcsr);
+            } else {
+                assert!(matches!(runtime.run(), Err(ExecutionError::ExceptionOrTrap())));
+            }
+        }
+    }
+
+    #[test]
+    fn test_bigint_mulmod_invalid_args() {
+        // x at 0x1000 and y at 0x2000 are all ones, followed by the modulus at 0x2020.
+        let run = |x_ptr: u32, num_digits: u32, modulus: u32| {
+            let mut instructions = vec![
+                Instruction::new(Opcode::ADD, 8, 0, u32::MAX, false, true),
+                Instruction::new(Opcode::ADD, 9, 0, 0x1000, false, true),
+                Instruction::new(Opcode::ADD, 10, 0, 0x2000, false, true),
+            ];
+            for i in 0..8 {
+                instructions.push(Instruction::new(Opcode::SW, 8, 9, i * 4, false, true));
+                instructions.push(Instruction::new(Opcode::SW, 8, 10, i * 4, false, true));
+            }
+            instructions.extend([
+                Instruction::new(Opcode::ADD, 8, 0, modulus, false, true),
+                Instruction::new(Opcode::SW, 8, 10, 0x20, false, true),
+                Instruction::new(Opcode::ADD, 2, 0, SyscallCode::BIGINT_MULMOD as u32, false, true),
+                Instruction::new(Opcode::ADD, 4, 0, x_ptr, false, true),
+                Instruction::new(Opcode::ADD, 5, 0, 0x2000, false, true),
+                Instruction::new(Opcode::ADD, 6, 0, num_digits, false, true),
+                Instruction::new(Opcode::SYSCALL, 2, 4, 5, false, false),
+            ]);
+            let program = Program::new(instructions, 0, 0);
+            Executor::new(program, ZKMCoreOpts::default()).run()
+        };
+
+        // The arguments are controlled by the guest, so they fail the execution without panicking.
+        let invalid = |result: Result<(), ExecutionError>| {
+            matches!(result, Err(ExecutionError::InvalidSyscallArgs()))
+        };
+        assert!(invalid(run(0x1002, 1, 7)));
+        assert!(invalid(run(0x1000, 0, 7)));
+        assert!(invalid(run(0x1000, 17, 7)));
+        assert!(invalid(run(0x1000, 1, 0)));
+        // Neither x nor y is reduced, so the quotient is wider than the operands.
+        assert!(invalid(run(0x1000, 1, 7)));
+    }
+
     fn simple_op_code_test(opcode: Opcode, expected: u32, a: u32, b: u32) {
         let instructions = vec![
             Instruction::new(Opcode::ADD, 10, 0, a, false, true),
@@ -0,0 +1,223 @@
+//! IEEE-754 semantics of the FPU instructions.
+//!
+//! Every operation rounds to nearest, ties to even, and never traps. This is the only rounding
+//! mode: `ctc1` raises an exception instead of selecting another one in `fcsr`. A NaN result is
+//! always the default NaN, so results do not depend on NaN payloads. Doubles are passed as
+//! `u64`s with the even register in the low word.
+
+use crate::Opcode;
+
+/// The default single precision NaN of the legacy MIPS NaN encoding.
+pub const DEFAULT_NAN_SINGLE: u32 = 0x7fbf_ffff;
+
+/// The default double precision NaN of the legacy MIPS NaN encoding.
+pub const DEFAULT_NAN_DOUBLE: u64 = 0x7ff7_ffff_ffff_ffff;
+
+/// The rounding mode field of `fcsr`, which must select round to nearest, i.e. be zero.
+pub const FCSR_RM_MASK: u32 = 0x3;
+
+/// The result of converting a NaN, an infinity or an out of range value to a word.
+pub const INVALID_WORD: u32 = 0x7fff_ffff;
+
+/// Returns if the opcode reads its source operands as doubles.
+#[must_use]
+pub const fn source_is_double(opcode: Opcode) -> bool {
+    matches!(
+        opcode,
+        Opcode::FADD_D
+            | Opcode::FSUB_D
+            | Opcode::FMUL_D
+            | Opcode::FDIV_D
+            | Opcode::FSQRT_D
+            | Opcode::FABS_D
+            | Opcode::FNEG_D
+            | Opcode::FMOV_D
+            | Opcode::CVT_S_D
+            | Opcode::CVT_W_D
+            | Opcode::TRUNC_W_D
+            | Opcode::C_UN_D
+            | Opcode::C_EQ_D
+            | Opcode::C_UEQ_D
+            | Opcode::C_OLT_D
+            | Opcode::C_ULT_D
+            | Opcode::C_OLE_D
+            | Opcode::C_ULE_D
+            | Opcode::LDC1
+            | Opcode::SDC1
+    )
+}
+
+/// Returns if the opcode writes a double.
+#[must_use]
+pub const fn result_is_double(opcode: Opcode) -> bool {
+    matches!(
+        opcode,
+        Opcode::FADD_D
+            | Opcode::FSUB_D
+            | Opcode::FMUL_D
+            | Opcode::FDIV_D
+            | Opcode::FSQRT_D
+            | Opcode::FABS_D
+            | Opcode::FNEG_D
+            | Opcode::FMOV_D
+            | Opcode::CVT_D_S
+            | Opcode::CVT_D_W
+            | Opcode::LDC1
+            | Opcode::SDC1
+    )
+}
+
+/// Returns if the opcode has a second source operand.
+#[must_use]
+pub const fn is_binary(opcode: Opcode) -> bool {
+    matches!(
+        opcode,
+        Opcode::FADD_S
+            | Opcode::FADD_D
+            | Opcode::FSUB_S
+            | Opcode::FSUB_D
+            | Opcode::FMUL_S
+            | Opcode::FMUL_D
+            | Opcode::FDIV_S
+            | Opcode::FDIV_D
+    ) || compare_condition(opcode).is_some()
+}
+
+/// Returns the condition of a compare opcode as the `un`, `eq` and `lt` bits of the `c.cond`
+/// encoding.
+#[must_use]
+pub const fn compare_condition(opcode: Opcode) -> Option<u32> {
+    let op = opcode as u8;
+    if op >= Opcode::C_UN_S as u8 && op <= Opcode::C_ULE_S as u8 {
+        Some((op - Opcode::C_UN_S as u8) as u32 + 1)
+    } else if op >= Opcode::C_UN_D as u8 && op <= Opcode::C_ULE_D as u8 {
+        Some((op - Opcode::C_UN_D as u8) as u32 + 1)
+    } else {
+        None
+    }
+}
+
+/// Executes an FPU arithmetic or sign operation on the raw register contents.
+///
+/// Single precision operands and results occupy the low 32 bits.
+#[must_use]
+pub fn execute(opcode: Opcode, fs: u64, ft: u64) -> u64 {
+    let s = |x: u64| f32::from_bits(x as u32);
+    let d = f64::from_bits;
+    match opcode {
+        Opcode::FADD_S => single(s(fs) + s(ft)),
+        Opcode::FADD_D => double(d(fs) + d(ft)),
+        Opcode::FSUB_S => single(s(fs) - s(ft)),
+        Opcode::FSUB_D => double(d(fs) - d(ft)),
+        Opcode::FMUL_S => single(s(fs) * s(ft)),
+        Opcode::FMUL_D => double(d(fs) * d(ft)),
+        Opcode::FDIV_S => single(s(fs) / s(ft)),
+        Opcode::FDIV_D => double(d(fs) / d(ft)),
+        Opcode::FSQRT_S => single(s(fs).sqrt()),
+        Opcode::FSQRT_D => double(d(fs).sqrt()),
+        Opcode::FABS_S => fs & 0x7fff_ffff,
+        Opcode::FABS_D => fs & !(1 << 63),
+        Opcode::FNEG_S => (fs ^ 0x8000_0000) & 0xffff_ffff,
+        Opcode::FNEG_D => fs ^ (1 << 63),
+        Opcode::FMOV_D => fs,
+        Opcode::CVT_S_D => single(d(fs) as f32),
+        Opcode::CVT_D_S => double(s(fs) as f64),
+        Opcode::CVT_S_W => single(fs as u32 as i32 as f32),
+        Opcode::CVT_D_W => double(fs as u32 as i32 as f64),
+        Opcode::CVT_W_S => word(s(fs) as f64, f64::round_ties_even),
+        Opcode::CVT_W_D => word(d(fs), f64::round_ties_even),
+        Opcode::TRUNC_W_S => word(s(fs) as f64, f64::trunc),
+        Opcode::TRUNC_W_D => word(d(fs), f64::trunc),
+        _ => unreachable!("{opcode} is not an FPU arithmetic instruction"),
+    }
+}
+
+/// Evaluates an FPU compare, which never signals.
+#[must_use]
+pub fn compare(opcode: Opcode, fs: u64, ft: u64) -> bool {
+    let cond = compare_condition(opcode).expect("not an FPU compare");
+    let (x, y) = if source_is_double(opcode) {
+        (f64::from_bits(fs), f64::from_bits(ft))
+    } else {
+        (f32::from_bits(fs as u32) as f64, f32::from_bits(ft as u32) as f64)
+    };
+    let unordered = x.is_nan() || y.is_nan();
+    (cond & 1 != 0 && unordered) || (cond & 2 != 0 && x == y) || (cond & 4 != 0 && x < y)
+}
+
+fn single(x: f32) -> u64 {
+    if x.is_nan() {
+        DEFAULT_NAN_SINGLE as u64
+    } else {
+        x.to_bits() as u64
+    }
+}
+
+fn double(x: f64) -> u64 {
+    if x.is_nan() {
+        DEFAULT_NAN_DOUBLE
+    } else {
+        x.to_bits()
+    }
+}
+
+fn word(x: f64, round: fn(f64) -> f64) -> u64 {
+    let rounded = round(x);
+    if rounded.is_nan() || rounded < i32::MIN as f64 || rounded > i32::MAX as f64 {
+        INVALID_WORD as u64
+    } else {
+        rounded as i32 as u32 as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fpu_rounding() {
+        let s = |x: f32| x.to_bits() as u64;
+        let d = |x: f64| x.to_bits();
+        assert_eq!(execute(Opcode::FADD_S, s(1.0), s(f32::EPSILON / 2.0)), s(1.0));
+        assert_eq!(execute(Opcode::FADD_D, d(0.1), d(0.2)), d(0.30000000000000004));
+        assert_eq!(execute(Opcode::FSUB_D, d(1.5), d(1.5)), d(0.0));
+        assert_eq!(execute(Opcode::FDIV_S, s(1.0), s(3.0)), s(1.0 / 3.0));
+        assert_eq!(execute(Opcode::FSQRT_D, d(2.0), 0), d(std::f64::consts::SQRT_2));
+        assert_eq!(execute(Opcode::CVT_S_D, d(1.0 + f64::EPSILON), 0), s(1.0));
+        assert_eq!(execute(Opcode::CVT_D_W, -7i32 as u32 as u64, 0), d(-7.0));
+        assert_eq!(execute(Opcode::CVT_W_S, s(2.5), 0), 2);
+        assert_eq!(execute(Opcode::CVT_W_D, d(-3.5), 0), -4i32 as u32 as u64);
+        assert_eq!(execute(Opcode::TRUNC_W_D, d(-3.5), 0), -3i32 as u32 as u64);
+    }
+
+    #[test]
+    fn test_fpu_special_values() {
+        let s = |x: f32| x.to_bits() as u64;
+        let d = |x: f64| x.to_bits();
+        assert_eq!(execute(Opcode::FSQRT_S, s(-1.0), 0), DEFAULT_NAN_SINGLE as u64);
+        assert_eq!(execute(Opcode::FMUL_D, d(f64::INFINITY), d(0.0)), DEFAULT_NAN_DOUBLE);
+        assert_eq!(execute(Opcode::FDIV_S, s(-1.0), s(0.0)), s(f32::NEG_INFINITY));
+        assert_eq!(execute(Opcode::FMUL_S, s(f32::MAX), s(2.0)), s(f32::INFINITY));
+        assert_eq!(execute(Opcode::FSQRT_D, d(-0.0), 0), d(-0.0));
+        assert_eq!(execute(Opcode::CVT_W_S, s(f32::NAN), 0), INVALID_WORD as u64);
+        assert_eq!(execute(Opcode::CVT_W_D, d(2147483647.5), 0), INVALID_WORD as u64);
+        assert_eq!(execute(Opcode::CVT_W_D, d(-2147483648.4), 0), 0x8000_0000);
+        assert_eq!(execute(Opcode::FNEG_S, s(0.0), 0), s(-0.0));
+        assert_eq!(execute(Opcode::FABS_D, d(-2.0), 0), d(2.0));
+    }
+
+    #[test]
+    fn test_fpu_compare() {
+        let s = |x: f32| x.to_bits() as u64;
+        let d = |x: f64| x.to_bits();
+        assert!(compare(Opcode::C_EQ_S, s(0.0), s(-0.0)));
+        assert!(!compare(Opcode::C_EQ_D, d(f64::NAN), d(f64::NAN)));
+        assert!(compare(Opcode::C_UEQ_D, d(f64::NAN), d(1.0)));
+        assert!(compare(Opcode::C_OLT_S, s(-2.0), s(1.0)));
+        assert!(!compare(Opcode::C_OLT_S, s(1.0), s(1.0)));
+        assert!(compare(Opcode::C_OLE_D, d(1.0), d(1.0)));
+        assert!(compare(Opcode::C_UN_S, s(f32::NAN), s(1.0)));
+        assert_eq!(compare_condition(Opcode::C_ULE_D), Some(7));
+        assert_eq!(compare_condition(Opcode::FADD_S), None);
+    }
+}
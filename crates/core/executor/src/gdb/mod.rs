@@ -0,0 +1,622 @@
+//! A GDB remote serial protocol stub for debugging guest programs.
+//!
+//! The stub wraps an [`Executor`] running in [`ExecutorMode::Simple`] and serves a single
+//! debugger connection over TCP or a Unix socket:
+//!
+//! ```text
+//! cargo ziren debug --elf guest.elf --listen 127.0.0.1:9001
+//! gdb-multiarch guest.elf -ex 'target remote 127.0.0.1:9001'
+//! ```
+//!
+//! Breakpoints, single-stepping, register and memory reads and watchpoints are supported. The
+//! guest state cannot be modified from the debugger.
+//!
+//! A branch or jump is always executed together with its delay slot, so the guest never stops
+//! with `next_is_delayslot` set. This matches GDB, which already moves breakpoints off delay
+//! slots and expects a single step over a branch to land on its target.
+
+mod packet;
+
+use std::{
+    collections::BTreeSet,
+    io::{self, Read, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+};
+#[cfg(unix)]
+use std::{
+    os::unix::net::{UnixListener, UnixStream},
+    path::Path,
+};
+
+use packet::{Message, PacketStream};
+
+use crate::{ExecutionError, Executor, ExecutorMode, Register};
+
+/// The target description sent to the debugger.
+const TARGET_XML: &str = include_str!("target.xml");
+
+/// The number of registers in the target description.
+const NUM_GDB_REGISTERS: usize = 72;
+
+/// The largest packet the debugger may send, and the largest memory read we answer.
+const PACKET_SIZE: usize = 0x4000;
+
+/// How often, in instructions, a running guest checks whether the debugger interrupted it.
+const INTERRUPT_CHECK_FREQUENCY: u64 = 1 << 16;
+
+/// The GDB signal numbers reported in stop replies.
+mod signal {
+    pub const SIGINT: u8 = 2;
+    pub const SIGILL: u8 = 4;
+    pub const SIGTRAP: u8 = 5;
+    pub const SIGABRT: u8 = 6;
+    pub const SIGSEGV: u8 = 11;
+    pub const SIGXCPU: u8 = 24;
+}
+
+/// The kind of memory access that triggers a watchpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchKind {
+    /// Stop when the watched memory is written.
+    Write,
+    /// Stop when the watched memory is read.
+    Read,
+    /// Stop when the watched memory is read or written.
+    Access,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Watchpoint {
+    kind: WatchKind,
+    addr: u32,
+    len: u32,
+}
+
+impl Watchpoint {
+    fn overlaps(&self, addr: u32, len: u32) -> bool {
+        let (start, end) = (self.addr as u64, self.addr as u64 + self.len as u64);
+        (addr as u64) < end && start < addr as u64 + len as u64
+    }
+}
+
+/// The reason the guest stopped, reported to the debugger in a stop reply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    /// The guest stopped with a signal: after a single step, an interrupt or an execution error.
+    Signal(u8),
+    /// The guest reached a breakpoint.
+    Breakpoint,
+    /// An instruction accessed watched memory.
+    Watchpoint {
+        /// The kind of the watchpoint that triggered.
+        kind: WatchKind,
+        /// The watched address.
+        addr: u32,
+    },
+    /// The guest exited with the given exit code.
+    Exited(u32),
+}
+
+/// A connection to the debugger that can be polled for interrupts while the guest runs.
+pub trait Connection: Read + Write {
+    /// Check, without blocking, whether the debugger sent an interrupt request.
+    fn poll_interrupt(&mut self) -> io::Result<bool>;
+}
+
+macro_rules! impl_connection {
+    ($ty:ty) => {
+        impl Connection for $ty {
+            fn poll_interrupt(&mut self) -> io::Result<bool> {
+                self.set_nonblocking(true)?;
+                let mut byte = [0u8];
+                let result = self.read(&mut byte);
+                self.set_nonblocking(false)?;
+                match result {
+                    Ok(1) => Ok(byte[0] == packet::INTERRUPT),
+                    Ok(_) => Ok(false),
+                    Err(e) if e.kind() == io::ErrorKind::WouldBlock => Ok(false),
+                    Err(e) => Err(e),
+                }
+            }
+        }
+    };
+}
+
+impl_connection!(TcpStream);
+#[cfg(unix)]
+impl_connection!(UnixStream);
+
+/// A GDB stub serving the state of an [`Executor`].
+pub struct GdbStub<'a> {
+    executor: Executor<'a>,
+    breakpoints: BTreeSet<u32>,
+    watchpoints: Vec<Watchpoint>,
+    stop: StopReason,
+}
+
+impl<'a> GdbStub<'a> {
+    /// Create a stub for the executor, switching it to [`ExecutorMode::Simple`].
+    ///
+    /// The guest starts stopped at its current program counter.
+    #[must_use]
+    pub fn new(mut executor: Executor<'a>) -> Self {
+        executor.executor_mode = ExecutorMode::Simple;
+        if executor.state.global_clk == 0 {
+            executor.initialize();
+        }
+        Self {
+            executor,
+            breakpoints: BTreeSet::new(),
+            watchpoints: Vec::new(),
+            stop: StopReason::Signal(signal::SIGTRAP),
+        }
+    }
+
+    /// The wrapped executor.
+    #[must_use]
+    pub fn executor(&self) -> &Executor<'a> {
+        &self.executor
+    }
+
+    /// Consume the stub, returning the wrapped executor.
+    #[must_use]
+    pub fn into_executor(self) -> Executor<'a> {
+        self.executor
+    }
+
+    /// Wait for a debugger to connect over TCP and serve it until it detaches.
+    pub fn listen_tcp(&mut self, addr: impl ToSocketAddrs) -> io::Result<()> {
+        let listener = TcpListener::bind(addr)?;
+        tracing::info!("waiting for gdb on {}", listener.local_addr()?);
+        let (stream, peer) = listener.accept()?;
+        tracing::info!("gdb connected from {peer}");
+        stream.set_nodelay(true)?;
+        self.serve(stream)
+    }
+
+    /// Wait for a debugger to connect over a Unix socket and serve it until it detaches.
+    #[cfg(unix)]
+    pub fn listen_unix(&mut self, path: impl AsRef<Path>) -> io::Result<()> {
+        let listener = UnixListener::bind(path.as_ref())?;
+        tracing::info!("waiting for gdb on {}", path.as_ref().display());
+        let (stream, _) = listener.accept()?;
+        tracing::info!("gdb connected");
+        self.serve(stream)
+    }
+
+    /// Serve a debugger over an established connection until it detaches, kills the guest or
+    /// closes the connection.
+    pub fn serve<C: Connection>(&mut self, conn: C) -> io::Result<()> {
+        let mut stream = PacketStream::new(conn);
+        while let Some(message) = stream.read_message()? {
+            match message {
+                // The guest is already stopped.
+                Message::Interrupt => {}
+                Message::Packet(packet) => {
+                    let packet = String::from_utf8_lossy(&packet);
+                    if !self.handle(&packet, &mut stream)? {
+                        break;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Handle a single packet, returning whether the session continues.
+    fn handle<C: Connection>(
+        &mut self,
+        packet: &str,
+        stream: &mut PacketStream<C>,
+    ) -> io::Result<bool> {
+        let reply = match packet {
+            "?" => self.stop_reply(),
+            "QStartNoAckMode" => {
+                stream.write_packet(b"OK")?;
+                stream.set_no_ack();
+                return Ok(true);
+            }
+            "qAttached" => "1".to_string(),
+            "qC" => "QC1".to_string(),
+            "qfThreadInfo" => "m1".to_string(),
+            "qsThreadInfo" => "l".to_string(),
+            "vCont?" => "vCont;c;C;s;S".to_string(),
+            "g" => (0..NUM_GDB_REGISTERS).map(|regnum| self.register_hex(regnum)).collect(),
+            "k" => return Ok(false),
+            _ if packet.starts_with("qSupported") => format!(
+                "PacketSize={PACKET_SIZE:x};qXfer:features:read+;swbreak+;hwbreak+;\
+                 QStartNoAckMode+;vContSupported+"
+            ),
+            _ if packet.starts_with("qXfer:features:read:target.xml:") => {
+                let args = &packet["qXfer:features:read:target.xml:".len()..];
+                read_target_xml(args).unwrap_or_else(|| "E01".to_string())
+            }
+            _ if packet.starts_with('D') => {
+                stream.write_packet(b"OK")?;
+                return Ok(false);
+            }
+            _ if packet.starts_with(['H', 'T']) => "OK".to_string(),
+            _ if packet.starts_with("vCont;") => {
+                let action = packet["vCont;".len()..].split([';', ':']).next().unwrap_or("");
+                match action.as_bytes().first() {
+                    Some(b'c' | b'C') => self.resume(false, stream)?,
+                    Some(b's' | b'S') => self.resume(true, stream)?,
+                    _ => String::new(),
+                }
+            }
+            _ if packet.starts_with(['c', 's']) => {
+                if let Some(addr) = parse_hex(&packet[1..]) {
+                    self.executor.state.pc = addr;
+                    self.executor.state.next_pc = addr.wrapping_add(4);
+                }
+                self.resume(packet.starts_with('s'), stream)?
+            }
+            // Continue or step with a signal. The guest has no signal handlers, so the signal is
+            // dropped.
+            _ if packet.starts_with(['C', 'S']) => self.resume(packet.starts_with('S'), stream)?,
+            _ if packet.starts_with('p') => match parse_hex(&packet[1..]) {
+                Some(regnum) if (regnum as usize) < NUM_GDB_REGISTERS => {
+                    self.register_hex(regnum as usize)
+                }
+                _ => "E01".to_string(),
+            },
+            _ if packet.starts_with('m') => {
+                self.read_memory(&packet[1..]).unwrap_or_else(|| "E01".to_string())
+            }
+            _ if packet.starts_with(['Z', 'z']) => self
+                .update_breakpoint(packet.starts_with('Z'), &packet[1..])
+                .unwrap_or_else(|| "E01".to_string()),
+            // Unsupported packets get an empty reply.
+            _ => String::new(),
+        };
+        stream.write_packet(reply.as_bytes())?;
+        Ok(true)
+    }
+
+    /// Resume the guest until it stops, returning the stop reply.
+    fn resume<C: Connection>(
+        &mut self,
+        step: bool,
+        stream: &mut PacketStream<C>,
+    ) -> io::Result<String> {
+        self.stop = match self.run(step, stream)? {
+            Ok(stop) => stop,
+            Err(e) => {
+                // Show the error in the debugger console, then stop so that the faulting
+                // instruction can be inspected.
+                let message = hex::encode(format!("{e}\n"));
+                stream.write_packet(format!("O{message}").as_bytes())?;
+                StopReason::Signal(error_signal(&e))
+            }
+        };
+        Ok(self.stop_reply())
+    }
+
+    fn run<C: Connection>(
+        &mut self,
+        step: bool,
+        stream: &mut PacketStream<C>,
+    ) -> io::Result<Result<StopReason, ExecutionError>> {
+        if let StopReason::Exited(code) = self.stop {
+            return Ok(Ok(StopReason::Exited(code)));
+        }
+
+        let mut cycles = 0u64;
+        loop {
+            match self.step_instruction() {
+                Ok(Some(stop)) => return Ok(Ok(stop)),
+                Ok(None) => {}
+                Err(e) => return Ok(Err(e)),
+            }
+            if step {
+                return Ok(Ok(StopReason::Signal(signal::SIGTRAP)));
+            }
+            if self.breakpoints.contains(&self.executor.state.pc) {
+                return Ok(Ok(StopReason::Breakpoint));
+            }
+
+            cycles += 1;
+            if cycles.is_multiple_of(INTERRUPT_CHECK_FREQUENCY) && stream.conn().poll_interrupt()? {
+                return Ok(Ok(StopReason::Signal(signal::SIGINT)));
+            }
+        }
+    }
+
+    /// Execute the instruction at the program counter, together with its delay slot if it is a
+    /// branch or a jump.
+    fn step_instruction(&mut self) -> Result<Option<StopReason>, ExecutionError> {
+        let mut hit = None;
+        loop {
+            let access = self.memory_access();
+            let watched = self.watched_bytes();
+            match self.executor.step() {
+                Ok(true) | Err(ExecutionError::HaltWithNonZeroExitCode(_)) => {
+                    return Ok(Some(StopReason::Exited(self.executor.state.exit_code)));
+                }
+                Ok(false) => {}
+                Err(e) => return Err(e),
+            }
+            hit = hit.or_else(|| self.check_watchpoints(access, &watched));
+            if !self.executor.state.next_is_delayslot {
+                return Ok(hit);
+            }
+        }
+    }
+
+    /// The aligned word accessed by the load or store at the program counter, and whether it is a
+    /// store.
+    ///
+    /// Memory written by syscalls is caught by comparing the watched bytes instead.
+    fn memory_access(&mut self) -> Option<(u32, bool)> {
+        if self.watchpoints.is_empty() {
+            return None;
+        }
+        let program = &self.executor.program;
+        let index = self.executor.state.pc.wrapping_sub(program.pc_base) / 4;
+        let instruction = *program.instructions.get(index as usize)?;
+
+        let is_store = instruction.is_memory_store_instruction();
+        if !is_store && !instruction.is_memory_load_instruction() {
+            return None;
+        }
+        let base = self.executor.register((instruction.op_b as u8).into());
+        Some((base.wrapping_add(instruction.op_c) & !3, is_store))
+    }
+
+    /// The current contents of the memory watched for writes.
+    fn watched_bytes(&mut self) -> Vec<Vec<u8>> {
+        let watchpoints = self.watchpoints.clone();
+        watchpoints
+            .iter()
+            .map(|watchpoint| match watchpoint.kind {
+                WatchKind::Read => Vec::new(),
+                WatchKind::Write | WatchKind::Access => {
+                    self.read_bytes(watchpoint.addr, watchpoint.len)
+                }
+            })
+            .collect()
+    }
+
+    fn check_watchpoints(
+        &mut self,
+        access: Option<(u32, bool)>,
+        watched: &[Vec<u8>],
+    ) -> Option<StopReason> {
+        let watchpoints = self.watchpoints.clone();
+        watchpoints.into_iter().zip(watched).find_map(|(watchpoint, before)| {
+            let accessed = access.is_some_and(|(addr, is_store)| {
+                watchpoint.overlaps(addr, 4)
+                    && match watchpoint.kind {
+                        WatchKind::Write => is_store,
+                        WatchKind::Read => !is_store,
+                        WatchKind::Access => true,
+                    }
+            });
+            let changed = watchpoint.kind != WatchKind::Read
+                && *before != self.read_bytes(watchpoint.addr, watchpoint.len);
+            (accessed || changed)
+                .then_some(StopReason::Watchpoint { kind: watchpoint.kind, addr: watchpoint.addr })
+        })
+    }
+
+    fn stop_reply(&self) -> String {
+        match self.stop {
+            StopReason::Signal(signal) => format!("S{signal:02x}"),
+            StopReason::Breakpoint => format!("T{:02x}swbreak:;", signal::SIGTRAP),
+            StopReason::Watchpoint { kind, addr } => {
+                let name = match kind {
+                    WatchKind::Write => "watch",
+                    WatchKind::Read => "rwatch",
+                    WatchKind::Access => "awatch",
+                };
+                format!("T{:02x}{name}:{addr:x};", signal::SIGTRAP)
+            }
+            StopReason::Exited(code) => format!("W{:02x}", code & 0xff),
+        }
+    }
+
+    /// The value of a register in the numbering of the target description, as little-endian hex.
+    fn register_hex(&mut self, regnum: usize) -> String {
+        let value = match regnum {
+            0..=31 => Some(self.executor.register(Register::from(regnum as u8))),
+            33 => Some(self.executor.register(Register::LO)),
+            34 => Some(self.executor.register(Register::HI)),
+            37 => Some(self.executor.state.pc),
+            38..=69 => Some(self.executor.register(Register::fpr(regnum as u32 - 38))),
+            // The condition codes live in their own registers, so merge them into `fcsr`.
+            70 => Some((0..8).fold(self.executor.register(Register::FCSR), |fcsr, cc| {
+                let bit = if cc == 0 { 23 } else { 24 + cc };
+                fcsr | (self.executor.register(Register::fcc(cc)) & 1) << bit
+            })),
+            // The coprocessor 0 registers and `fir` are not modelled.
+            32 | 35 | 36 | 71 => Some(0),
+            _ => None,
+        };
+        value.map_or_else(|| "xxxxxxxx".to_string(), |value| hex::encode(value.to_le_bytes()))
+    }
+
+    fn read_bytes(&mut self, addr: u32, len: u32) -> Vec<u8> {
+        (0..len).map(|i| self.executor.byte(addr.wrapping_add(i))).collect()
+    }
+
+    /// Answer `m addr,length`.
+    fn read_memory(&mut self, args: &str) -> Option<String> {
+        let (addr, len) = args.split_once(',')?;
+        let (addr, len) = (parse_hex(addr)?, parse_hex(len)?);
+        let len = len.min(PACKET_SIZE as u32 / 2);
+        Some(hex::encode(self.read_bytes(addr, len)))
+    }
+
+    /// Answer `Z type,addr,kind` and `z type,addr,kind`.
+    fn update_breakpoint(&mut self, insert: bool, args: &str) -> Option<String> {
+        let mut args = args.split(',');
+        // For breakpoints the last argument is the instruction size, for watchpoints it is the
+        // number of watched bytes.
+        let (ty, addr, len) = (args.next()?, parse_hex(args.next()?)?, parse_hex(args.next()?)?);
+        let kind = match ty {
+            // Software and hardware breakpoints are the same thing here.
+            "0" | "1" => {
+                if insert {
+                    self.breakpoints.insert(addr);
+                } else {
+                    self.breakpoints.remove(&addr);
+                }
+                return Some("OK".to_string());
+            }
+            "2" => WatchKind::Write,
+            "3" => WatchKind::Read,
+            "4" => WatchKind::Access,
+            _ => return Some(String::new()),
+        };
+
+        let watchpoint = Watchpoint { kind, addr, len };
+        if insert {
+            self.watchpoints.push(watchpoint);
+        } else if let Some(index) = self.watchpoints.iter().position(|w| *w == watchpoint) {
+            self.watchpoints.remove(index);
+        }
+        Some("OK".to_string())
+    }
+}
+
+/// Answer `qXfer:features:read:target.xml:offset,length`.
+fn read_target_xml(args: &str) -> Option<String> {
+    let (offset, len) = args.split_once(',')?;
+    let (offset, len) = (parse_hex(offset)? as usize, parse_hex(len)? as usize);
+    let xml = TARGET_XML.as_bytes();
+    let start = offset.min(xml.len());
+    let end = start.saturating_add(len).min(xml.len());
+    let marker = if end == xml.len() { 'l' } else { 'm' };
+    Some(format!("{marker}{}", std::str::from_utf8(&xml[start..end]).ok()?))
+}
+
+fn parse_hex(s: &str) -> Option<u32> {
+    u32::from_str_radix(s, 16).ok()
+}
+
+/// The signal reported to the debugger when the guest fails with an execution error.
+fn error_signal(error: &ExecutionError) -> u8 {
+    match error {
+        ExecutionError::Breakpoint() | ExecutionError::ExceptionOrTrap() => signal::SIGTRAP,
+        ExecutionError::InvalidMemoryAccess(..)
+        | ExecutionError::MemoryOutOfBoundsAccess(_)
+        | ExecutionError::NullPointerReference() => signal::SIGSEGV,
+        ExecutionError::UnsupportedInstruction(_) | ExecutionError::Unimplemented() => {
+            signal::SIGILL
+        }
+        ExecutionError::ExceededCycleLimit(_) => signal::SIGXCPU,
+        _ => signal::SIGABRT,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        net::{TcpListener, TcpStream},
+        thread,
+    };
+
+    use zkm_stark::ZKMCoreOpts;
+
+    use super::{packet::Message, GdbStub, PacketStream};
+    use crate::{
+        programs::tests::simple_memory_program, Executor, Instruction, Opcode, Program, Register,
+    };
+
+    /// Run a debugging session over a loopback connection, returning the reply to each packet.
+    fn session(
+        program: Program,
+        packets: &'static [&'static str],
+    ) -> (Executor<'static>, Vec<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let client = thread::spawn(move || {
+            let mut stream = PacketStream::new(TcpStream::connect(addr).unwrap());
+            let mut replies = Vec::new();
+            for packet in packets {
+                stream.write_packet(packet.as_bytes()).unwrap();
+                if *packet == "k" {
+                    break;
+                }
+                match stream.read_message().unwrap() {
+                    Some(Message::Packet(reply)) => replies.push(String::from_utf8(reply).unwrap()),
+                    message => panic!("unexpected message {message:?}"),
+                }
+            }
+            replies
+        });
+
+        let mut stub = GdbStub::new(Executor::new(program, ZKMCoreOpts::default()));
+        let (conn, _) = listener.accept().unwrap();
+        stub.serve(conn).unwrap();
+        (stub.into_executor(), client.join().unwrap())
+    }
+
+    #[test]
+    fn test_breakpoints_and_watchpoints() {
+        let (_, replies) = session(
+            simple_memory_program(),
+            &[
+                "?",
+                "Z2,27654320,4",
+                "c",
+                "p25",
+                "m27654320,4",
+                "z2,27654320,4",
+                "Z0,c,4",
+                "c",
+                "s",
+                "p25",
+                "p1d",
+                "c",
+                "k",
+            ],
+        );
+        assert_eq!(
+            replies,
+            [
+                "S05",
+                "OK",
+                "T05watch:27654320;",
+                "08000000",
+                "65873412",
+                "OK",
+                "OK",
+                "T05swbreak:;",
+                "S05",
+                "10000000",
+                "65873412",
+                "W00",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_step_over_delay_slot() {
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 29, 0, 1, false, true),
+            Instruction::new(Opcode::BEQ, 29, 29, 8, false, false),
+            Instruction::new(Opcode::ADD, 30, 0, 5, false, true),
+            Instruction::new(Opcode::ADD, 30, 0, 7, false, true),
+            Instruction::new(Opcode::ADD, 31, 30, 29, false, false),
+        ];
+        let (mut executor, replies) =
+            session(Program::new(instructions, 0, 0), &["s", "s", "p25", "p1e", "c", "k"]);
+        assert_eq!(replies, ["S05", "S05", "10000000", "05000000", "W00"]);
+        assert_eq!(executor.register(Register::RA), 6);
+    }
+
+    #[test]
+    fn test_exit_code() {
+        // `HALT` with an exit code of 3, which is reported when stepping onto it as well.
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 2, 0, 0, false, true),
+            Instruction::new(Opcode::ADD, 4, 0, 3, false, true),
+            Instruction::new(Opcode::SYSCALL, 2, 4, 5, false, false),
+        ];
+        let (_, replies) = session(Program::new(instructions.clone(), 0, 0), &["c", "c", "k"]);
+        assert_eq!(replies, ["W03", "W03"]);
+        let (_, replies) = session(Program::new(instructions, 0, 0), &["s", "s", "s", "k"]);
+        assert_eq!(replies, ["S05", "S05", "W03"]);
+    }
+}
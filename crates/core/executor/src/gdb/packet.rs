@@ -0,0 +1,137 @@
+//! Framing of GDB remote serial protocol packets.
+
+use std::io::{self, Read, Write};
+
+/// The byte sent by the debugger to interrupt a running target.
+pub const INTERRUPT: u8 = 0x03;
+
+/// A message received from the debugger.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    /// A command packet, with the framing and checksum removed.
+    Packet(Vec<u8>),
+    /// An interrupt request (`Ctrl-C`).
+    Interrupt,
+}
+
+/// Reads and writes packets over a connection to the debugger.
+pub struct PacketStream<C> {
+    conn: C,
+    no_ack: bool,
+}
+
+impl<C: Read + Write> PacketStream<C> {
+    /// Wrap a connection.
+    pub const fn new(conn: C) -> Self {
+        Self { conn, no_ack: false }
+    }
+
+    /// The underlying connection.
+    pub fn conn(&mut self) -> &mut C {
+        &mut self.conn
+    }
+
+    /// Stop sending and expecting acknowledgments, as negotiated by `QStartNoAckMode`.
+    pub fn set_no_ack(&mut self) {
+        self.no_ack = true;
+    }
+
+    fn read_byte(&mut self) -> io::Result<Option<u8>> {
+        let mut byte = [0u8];
+        loop {
+            match self.conn.read(&mut byte) {
+                Ok(0) => return Ok(None),
+                Ok(_) => return Ok(Some(byte[0])),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Read the next message, returning `None` once the debugger closes the connection.
+    ///
+    /// Packets with a bad checksum are rejected with `-`, which makes the debugger resend them.
+    pub fn read_message(&mut self) -> io::Result<Option<Message>> {
+        loop {
+            let Some(byte) = self.read_byte()? else { return Ok(None) };
+            match byte {
+                INTERRUPT => return Ok(Some(Message::Interrupt)),
+                b'$' => {}
+                // Acknowledgments and line noise between packets.
+                _ => continue,
+            }
+
+            let mut data = Vec::new();
+            let mut sum = 0u8;
+            loop {
+                let Some(byte) = self.read_byte()? else { return Ok(None) };
+                if byte == b'#' {
+                    break;
+                }
+                sum = sum.wrapping_add(byte);
+                // Escaped byte.
+                if byte == b'}' {
+                    let Some(byte) = self.read_byte()? else { return Ok(None) };
+                    sum = sum.wrapping_add(byte);
+                    data.push(byte ^ 0x20);
+                } else {
+                    data.push(byte);
+                }
+            }
+            let mut checksum = [0u8; 2];
+            self.conn.read_exact(&mut checksum)?;
+
+            if self.no_ack {
+                return Ok(Some(Message::Packet(data)));
+            }
+            let valid = std::str::from_utf8(&checksum)
+                .ok()
+                .and_then(|s| u8::from_str_radix(s, 16).ok())
+                .is_some_and(|expected| expected == sum);
+            if valid {
+                self.conn.write_all(b"+")?;
+                return Ok(Some(Message::Packet(data)));
+            }
+            self.conn.write_all(b"-")?;
+        }
+    }
+
+    /// Send a packet, resending it until the debugger acknowledges it.
+    pub fn write_packet(&mut self, data: &[u8]) -> io::Result<()> {
+        let data = escape(data);
+        let mut packet = Vec::with_capacity(data.len() + 4);
+        packet.push(b'$');
+        packet.extend_from_slice(&data);
+        packet.extend_from_slice(format!("#{:02x}", checksum_of(&data)).as_bytes());
+
+        loop {
+            self.conn.write_all(&packet)?;
+            self.conn.flush()?;
+            if self.no_ack {
+                return Ok(());
+            }
+            match self.read_byte()? {
+                Some(b'+') | None => return Ok(()),
+                Some(_) => {}
+            }
+        }
+    }
+}
+
+/// The checksum of the packet data, as sent on the wire.
+fn checksum_of(data: &[u8]) -> u8 {
+    data.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte))
+}
+
+/// Escape the bytes that have a special meaning inside a packet.
+fn escape(data: &[u8]) -> Vec<u8> {
+    let mut escaped = Vec::with_capacity(data.len());
+    for &byte in data {
+        if matches!(byte, b'#' | b'$' | b'}' | b'*') {
+            escaped.extend_from_slice(&[b'}', byte ^ 0x20]);
+        } else {
+            escaped.push(byte);
+        }
+    }
+    escaped
+}
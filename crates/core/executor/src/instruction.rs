@@ -4,6 +4,7 @@ use core::fmt::Debug;
 use serde::{Deserialize, Serialize};
 
 use crate::opcode::Opcode;
+use crate::register::Register;
 use crate::sign_extend;
 use crate::OptionU32;
 
@@ -26,6 +27,35 @@ pub struct Instruction {
     pub raw: Option<u32>,
 }
 
+/// The MIPS32 release a program is compiled for.
+///
+/// Release 6 reassigned a few encodings of earlier releases, so decoding them depends on it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IsaRevision {
+    /// MIPS32 release 2 and earlier.
+    #[default]
+    R2,
+    /// MIPS32 release 6.
+    R6,
+}
+
+impl IsaRevision {
+    /// The mask of the `EF_MIPS_ARCH` field of the ELF header flags.
+    const EF_MIPS_ARCH: u32 = 0xf000_0000;
+    /// The `EF_MIPS_ARCH_32R6` and `EF_MIPS_ARCH_64R6` architectures.
+    const EF_MIPS_ARCH_32R6: u32 = 0x9000_0000;
+    const EF_MIPS_ARCH_64R6: u32 = 0xa000_0000;
+
+    /// Reads the revision from the `e_flags` field of an ELF header.
+    #[must_use]
+    pub const fn from_elf_flags(e_flags: u32) -> Self {
+        match e_flags & Self::EF_MIPS_ARCH {
+            Self::EF_MIPS_ARCH_32R6 | Self::EF_MIPS_ARCH_64R6 => Self::R6,
+            _ => Self::R2,
+        }
+    }
+}
+
 #[derive(Clone, Copy)]
 #[repr(C)]
 pub struct InstructionFfi {
@@ -111,6 +141,8 @@ impl Instruction {
                 | Opcode::CLO
                 | Opcode::MOD
                 | Opcode::MODU
+                | Opcode::MUH
+                | Opcode::MUHU
         )
     }
 
@@ -131,14 +163,30 @@ impl Instruction {
                 | Opcode::TEQ
                 | Opcode::MADD
                 | Opcode::MSUB
-        )
+                | Opcode::SELEQZ
+                | Opcode::SELNEZ
+                | Opcode::LSA
+                | Opcode::ALIGN
+                | Opcode::BITSWAP
+                | Opcode::CTC1
+        ) || self.is_trap_instruction()
     }
 
     /// Returns if the instruction is an mov condition instruction.
     #[must_use]
     #[inline]
     pub const fn is_mov_cond_instruction(&self) -> bool {
-        matches!(self.opcode, Opcode::MEQ | Opcode::MNE)
+        matches!(self.opcode, Opcode::MEQ | Opcode::MNE | Opcode::SELEQZ | Opcode::SELNEZ)
+    }
+
+    /// Returns if the instruction is a conditional trap.
+    #[must_use]
+    #[inline]
+    pub const fn is_trap_instruction(&self) -> bool {
+        matches!(
+            self.opcode,
+            Opcode::TEQ | Opcode::TNE | Opcode::TGE | Opcode::TGEU | Opcode::TLT | Opcode::TLTU
+        )
     }
 
     /// Returns if the instruction is a syscall instruction.
@@ -158,6 +206,8 @@ impl Instruction {
                 | Opcode::MSUBU
                 | Opcode::MADD
                 | Opcode::MSUB
+                | Opcode::LSA
+                | Opcode::ALIGN
                 | Opcode::LH
                 | Opcode::LWL
                 | Opcode::LW
@@ -172,7 +222,7 @@ impl Instruction {
                 | Opcode::LL
                 | Opcode::SC
                 | Opcode::LB
-        )
+        ) || self.is_fpu_instruction()
     }
 
     #[must_use]
@@ -264,7 +314,36 @@ impl Instruction {
     pub const fn is_branch_instruction(&self) -> bool {
         matches!(
             self.opcode,
-            Opcode::BEQ | Opcode::BNE | Opcode::BLTZ | Opcode::BGEZ | Opcode::BLEZ | Opcode::BGTZ
+            Opcode::BEQ
+                | Opcode::BNE
+                | Opcode::BLTZ
+                | Opcode::BGEZ
+                | Opcode::BLEZ
+                | Opcode::BGTZ
+                | Opcode::BLTZAL
+                | Opcode::BGEZAL
+                | Opcode::BEQL
+                | Opcode::BNEL
+                | Opcode::BLEZL
+                | Opcode::BGTZL
+                | Opcode::BLTZL
+                | Opcode::BGEZL
+                | Opcode::BLTZALL
+                | Opcode::BGEZALL
+                | Opcode::BEQC
+                | Opcode::BNEC
+                | Opcode::BLTC
+                | Opcode::BGEC
+                | Opcode::BLTUC
+                | Opcode::BGEUC
+                | Opcode::BLEZALC
+                | Opcode::BGEZALC
+                | Opcode::BGTZALC
+                | Opcode::BLTZALC
+                | Opcode::BEQZALC
+                | Opcode::BNEZALC
+                | Opcode::BOVC
+                | Opcode::BNVC
         )
     }
 
@@ -275,6 +354,17 @@ impl Instruction {
         matches!(self.opcode, Opcode::BLTZ | Opcode::BGEZ | Opcode::BLEZ | Opcode::BGTZ)
     }
 
+    /// Returns if the instruction sets its own next program counter instead of inheriting it
+    /// from the previous instruction: branch-likely instructions skip their delay slot when not
+    /// taken, and compact branches and jumps have none.
+    #[must_use]
+    #[inline]
+    pub fn is_redirect_instruction(&self) -> bool {
+        self.opcode.is_branch_likely()
+            || self.opcode.is_compact_branch()
+            || self.opcode == Opcode::JumpCompact
+    }
+
     /// Returns if the instruction is a clz or clo instruction.
     #[must_use]
     #[inline]
@@ -306,10 +396,85 @@ impl Instruction {
     #[must_use]
     #[inline]
     pub const fn is_jump_instruction(&self) -> bool {
-        matches!(self.opcode, Opcode::Jump | Opcode::Jumpi | Opcode::JumpDirect)
+        matches!(
+            self.opcode,
+            Opcode::Jump | Opcode::Jumpi | Opcode::JumpDirect | Opcode::JumpCompact
+        )
+    }
+
+    /// Returns if the instruction is executed by one of the FPU chips.
+    ///
+    /// These instructions access their floating point registers themselves, so `op_a` is always
+    /// `$zero` and `op_b`/`op_c` carry the register indices.
+    #[must_use]
+    #[inline]
+    pub const fn is_fpu_instruction(&self) -> bool {
+        self.is_fpu_arith_instruction() || self.is_fpu_misc_instruction()
+    }
+
+    /// Returns if the instruction is an FPU instruction that needs rounding.
+    #[must_use]
+    #[inline]
+    pub const fn is_fpu_arith_instruction(&self) -> bool {
+        matches!(
+            self.opcode,
+            Opcode::FADD_S
+                | Opcode::FADD_D
+                | Opcode::FSUB_S
+                | Opcode::FSUB_D
+                | Opcode::FMUL_S
+                | Opcode::FMUL_D
+                | Opcode::FDIV_S
+                | Opcode::FDIV_D
+                | Opcode::FSQRT_S
+                | Opcode::FSQRT_D
+                | Opcode::CVT_S_D
+                | Opcode::CVT_D_S
+                | Opcode::CVT_S_W
+                | Opcode::CVT_D_W
+                | Opcode::CVT_W_S
+                | Opcode::CVT_W_D
+                | Opcode::TRUNC_W_S
+                | Opcode::TRUNC_W_D
+        )
     }
 
-    pub fn decode_from(insn: u32) -> anyhow::Result<Self> {
+    /// Returns if the instruction is an exact FPU instruction: sign operations, compares and the
+    /// 64-bit loads and stores.
+    #[must_use]
+    #[inline]
+    pub const fn is_fpu_misc_instruction(&self) -> bool {
+        matches!(
+            self.opcode,
+            Opcode::FABS_S
+                | Opcode::FABS_D
+                | Opcode::FNEG_S
+                | Opcode::FNEG_D
+                | Opcode::FMOV_D
+                | Opcode::C_UN_S
+                | Opcode::C_EQ_S
+                | Opcode::C_UEQ_S
+                | Opcode::C_OLT_S
+                | Opcode::C_ULT_S
+                | Opcode::C_OLE_S
+                | Opcode::C_ULE_S
+                | Opcode::C_UN_D
+                | Opcode::C_EQ_D
+                | Opcode::C_UEQ_D
+                | Opcode::C_OLT_D
+                | Opcode::C_ULT_D
+                | Opcode::C_OLE_D
+                | Opcode::C_ULE_D
+                | Opcode::LDC1
+                | Opcode::SDC1
+        )
+    }
+
+    /// Decodes the instruction `insn` located at `pc`.
+    ///
+    /// The address is needed for the PC-relative MIPS32r6 instructions, whose targets are baked
+    /// into the decoded operands. `isa` selects between the encodings that release 6 reassigned.
+    pub fn decode_from(insn: u32, pc: u32, isa: IsaRevision) -> anyhow::Result<Self> {
         let opcode = ((insn >> 26) & 0x3F).to_le_bytes()[0];
         let func = (insn & 0x3F).to_le_bytes()[0];
         let rt = ((insn >> 16) & 0x1F).to_le_bytes()[0] as u32;
@@ -320,6 +485,14 @@ impl Instruction {
         let offset_ext16 = sign_extend::<16>(offset);
         let target = insn & 0x3ffffff;
         let target_ext = sign_extend::<26>(target);
+        let branch_offset = offset_ext16.overflowing_shl(2).0;
+        // The absolute target of a compact branch with an offset of `N` bits.
+        let compact_target = |bits: u32| match bits {
+            16 => pc.wrapping_add(4).wrapping_add(branch_offset),
+            21 => pc.wrapping_add(4).wrapping_add(sign_extend::<21>(insn & 0x1fffff) << 2),
+            _ => pc.wrapping_add(4).wrapping_add(target_ext << 2),
+        };
+        let unimpl = Self::new_with_raw(Opcode::UNIMPL, 0, 0, insn, true, true, insn);
         log::trace!("op {opcode}, func {func}, rt {rt}, rs {rs}, rd {rd}");
         log::trace!("decode: insn {insn:X}, opcode {opcode:X}, func {func:X}");
 
@@ -365,29 +538,57 @@ impl Instruction {
             // MUL: rd = rt * rs
             (0b011100, 0b000010) => Ok(Self::new(Opcode::MUL, rd, rt, rs, false, false)), // MUL: rd = rt * rs
             // MULT: (hi, lo) = rt * rs
-            (0b000000, 0b011000) => Ok(Self::new(Opcode::MULT, 32, rt, rs, false, false)), // MULT: (hi, lo) = rt * rs
+            (0b000000, 0b011000) => match sa {
+                // MUL (r6): rd = rt * rs
+                2 => Ok(Self::new(Opcode::MUL, rd, rt, rs, false, false)),
+                // MUH: rd = hi(rt * rs)
+                3 => Ok(Self::new(Opcode::MUH, rd, rt, rs, false, false)),
+                // MULT: (hi, lo) = rt * rs
+                _ => Ok(Self::new(Opcode::MULT, 32, rt, rs, false, false)),
+            },
             // MULTU: (hi, lo) = rt * rs
-            (0b000000, 0b011001) => Ok(Self::new(Opcode::MULTU, 32, rt, rs, false, false)), // MULTU: (hi, lo) = rt * rs
+            (0b000000, 0b011001) => match sa {
+                // MULU: rd = rt * rs
+                2 => Ok(Self::new(Opcode::MUL, rd, rt, rs, false, false)),
+                // MUHU: rd = hi(rt * rs)
+                3 => Ok(Self::new(Opcode::MUHU, rd, rt, rs, false, false)),
+                // MULTU: (hi, lo) = rt * rs
+                _ => Ok(Self::new(Opcode::MULTU, 32, rt, rs, false, false)),
+            },
             // DIV: hi = rt % rs, lo = rt / rs, signed
-            (0b000000, 0b011010) => {
-                if sa == 3 {
-                    Ok(Self::new(Opcode::MOD, rd, rs, rt, false, false)) // MOD: rd = rs % rt
-                } else {
-                    Ok(Self::new(Opcode::DIV, 32, rs, rt, false, false)) // DIV: (hi, lo) = rs / rt
-                }
-            }
+            (0b000000, 0b011010) => match sa {
+                // DIV (r6): rd = rs / rt
+                2 => Ok(Self::new(Opcode::DIV, rd, rs, rt, false, false)),
+                // MOD: rd = rs % rt
+                3 => Ok(Self::new(Opcode::MOD, rd, rs, rt, false, false)),
+                // DIV: (hi, lo) = rs / rt
+                _ => Ok(Self::new(Opcode::DIV, 32, rs, rt, false, false)),
+            },
             // DIVU: hi = rt % rs, lo = rt / rs, unsigned
-            (0b000000, 0b011011) => {
-                if sa == 3 {
-                    Ok(Self::new(Opcode::MODU, rd, rs, rt, false, false)) // MODU: rd = rs % rt
-                } else {
-                    Ok(Self::new(Opcode::DIVU, 32, rs, rt, false, false)) // DIVU: (hi, lo) = rs / rt
-                }
-            }
+            (0b000000, 0b011011) => match sa {
+                // DIVU (r6): rd = rs / rt
+                2 => Ok(Self::new(Opcode::DIVU, rd, rs, rt, false, false)),
+                // MODU: rd = rs % rt
+                3 => Ok(Self::new(Opcode::MODU, rd, rs, rt, false, false)),
+                // DIVU: (hi, lo) = rs / rt
+                _ => Ok(Self::new(Opcode::DIVU, 32, rs, rt, false, false)),
+            },
+            // CLZ (r6): rd = count_leading_zeros(rs)
+            (0b000000, 0b010000) if sa == 1 => Ok(Self::new(Opcode::CLZ, rd, rs, 0, false, true)),
+            // CLO (r6): rd = count_leading_ones(rs)
+            (0b000000, 0b010001) if sa == 1 => Ok(Self::new(Opcode::CLO, rd, rs, 0, false, true)),
             // MFHI: rd = hi
             (0b000000, 0b010000) => Ok(Self::new(Opcode::ADD, rd, 33, 0, false, true)), // MFHI: rd = hi
             // MTHI: hi = rs
             (0b000000, 0b010001) => Ok(Self::new(Opcode::ADD, 33, rs, 0, false, true)), // MTHI: hi = rs
+            // LSA: rd = (rs << (sa + 1)) + rt, the chip reads rt itself
+            (0b000000, 0b000101) => {
+                Ok(Self::new(Opcode::LSA, rd, rs, rt << 5 | (sa & 0x3), false, true))
+            }
+            // SELEQZ: rd = rt == 0 ? rs : 0
+            (0b000000, 0b110101) => Ok(Self::new(Opcode::SELEQZ, rd, rs, rt, false, false)),
+            // SELNEZ: rd = rt != 0 ? rs : 0
+            (0b000000, 0b110111) => Ok(Self::new(Opcode::SELNEZ, rd, rs, rt, false, false)),
             // MFLO: rd = lo
             (0b000000, 0b010010) => Ok(Self::new(Opcode::ADD, rd, 32, 0, false, true)), // MFLO: rd = lo
             // MTLO: lo = rs
@@ -402,44 +603,33 @@ impl Instruction {
             (0x00, 0x08) => Ok(Self::new(Opcode::Jump, 0u8, rs, 0, false, true)), // JR
             // JALR
             (0x00, 0x09) => Ok(Self::new(Opcode::Jump, rd, rs, 0, false, true)), // JALR
-            (0x01, _) => {
-                if rt == 1 {
-                    // BGEZ
-                    Ok(Self::new(
-                        Opcode::BGEZ,
-                        rs as u8,
-                        0u32,
-                        offset_ext16.overflowing_shl(2).0,
-                        true,
-                        true,
-                    ))
-                } else if rt == 0 {
-                    // BLTZ
-                    Ok(Self::new(
-                        Opcode::BLTZ,
-                        rs as u8,
-                        0u32,
-                        offset_ext16.overflowing_shl(2).0,
-                        true,
-                        true,
-                    ))
-                } else if rt == 0x11 && rs == 0 {
-                    // BAL
-                    Ok(Self::new(
-                        Opcode::JumpDirect,
-                        31,
-                        offset_ext16.overflowing_shl(2).0,
-                        0,
-                        true,
-                        true,
-                    ))
-                } else if rt == 0x1f {
-                    // SYNCI
-                    Ok(Self::new(Opcode::ADD, 0, 0, 0, true, true))
-                } else {
-                    Ok(Self::new_with_raw(Opcode::UNIMPL, 0, 0, insn, true, true, insn))
+            (0x01, _) => match rt {
+                // BLTZ, BGEZ, BLTZL, BGEZL
+                0x00 => Ok(Self::new(Opcode::BLTZ, rs as u8, 0, branch_offset, true, true)),
+                0x01 => Ok(Self::new(Opcode::BGEZ, rs as u8, 0, branch_offset, true, true)),
+                0x02 => Ok(Self::new(Opcode::BLTZL, rs as u8, 0, branch_offset, true, true)),
+                0x03 => Ok(Self::new(Opcode::BGEZL, rs as u8, 0, branch_offset, true, true)),
+                // TGEI, TGEIU, TLTI, TLTIU, TEQI, TNEI
+                0x08 => Ok(Self::new(Opcode::TGE, rs as u8, offset_ext16, 0, true, true)),
+                0x09 => Ok(Self::new(Opcode::TGEU, rs as u8, offset_ext16, 0, true, true)),
+                0x0a => Ok(Self::new(Opcode::TLT, rs as u8, offset_ext16, 0, true, true)),
+                0x0b => Ok(Self::new(Opcode::TLTU, rs as u8, offset_ext16, 0, true, true)),
+                0x0c => Ok(Self::new(Opcode::TEQ, rs as u8, offset_ext16, 0, true, true)),
+                0x0e => Ok(Self::new(Opcode::TNE, rs as u8, offset_ext16, 0, true, true)),
+                // BLTZAL, which is NAL when rs is zero as it then only links
+                0x10 => Ok(Self::new(Opcode::BLTZAL, 31, rs, branch_offset, false, true)),
+                // BAL
+                0x11 if rs == 0 => {
+                    Ok(Self::new(Opcode::JumpDirect, 31, branch_offset, 0, true, true))
                 }
-            }
+                // BGEZAL, BLTZALL, BGEZALL
+                0x11 => Ok(Self::new(Opcode::BGEZAL, 31, rs, branch_offset, false, true)),
+                0x12 => Ok(Self::new(Opcode::BLTZALL, 31, rs, branch_offset, false, true)),
+                0x13 => Ok(Self::new(Opcode::BGEZALL, 31, rs, branch_offset, false, true)),
+                // SYNCI
+                0x1f => Ok(Self::new(Opcode::ADD, 0, 0, 0, true, true)),
+                _ => Ok(unimpl),
+            },
             // J
             (0x02, _) => {
                 // Ignore the upper 4 most significant bits，since they are always 0 currently.
@@ -450,41 +640,109 @@ impl Instruction {
                 Ok(Self::new(Opcode::Jumpi, 31u8, target_ext.overflowing_shl(2).0, 0, true, true))
             }
             // BEQ
-            (0x04, _) => Ok(Self::new(
-                Opcode::BEQ,
-                rs as u8,
-                rt,
-                offset_ext16.overflowing_shl(2).0,
-                false,
-                true,
-            )),
+            (0x04, _) => Ok(Self::new(Opcode::BEQ, rs as u8, rt, branch_offset, false, true)),
             // BNE
-            (0x05, _) => Ok(Self::new(
-                Opcode::BNE,
-                rs as u8,
-                rt,
-                offset_ext16.overflowing_shl(2).0,
-                false,
-                true,
-            )),
+            (0x05, _) => Ok(Self::new(Opcode::BNE, rs as u8, rt, branch_offset, false, true)),
             // BLEZ
-            (0x06, _) => Ok(Self::new(
-                Opcode::BLEZ,
-                rs as u8,
-                0u32,
-                offset_ext16.overflowing_shl(2).0,
-                true,
-                true,
-            )),
+            (0x06, _) if rt == 0 => {
+                Ok(Self::new(Opcode::BLEZ, rs as u8, 0, branch_offset, true, true))
+            }
+            // POP06: BLEZALC, BGEZALC, BGEUC
+            (0x06, _) => {
+                let target = compact_target(16);
+                if rs == 0 {
+                    Ok(Self::new(Opcode::BLEZALC, 31, rt, target, false, true))
+                } else if rs == rt {
+                    Ok(Self::new(Opcode::BGEZALC, 31, rt, target, false, true))
+                } else {
+                    Ok(Self::new(Opcode::BGEUC, rs as u8, rt, target, false, true))
+                }
+            }
             // BGTZ
-            (0x07, _) => Ok(Self::new(
-                Opcode::BGTZ,
-                rs as u8,
-                0u32,
-                offset_ext16.overflowing_shl(2).0,
-                true,
-                true,
-            )),
+            (0x07, _) if rt == 0 => {
+                Ok(Self::new(Opcode::BGTZ, rs as u8, 0, branch_offset, true, true))
+            }
+            // POP07: BGTZALC, BLTZALC, BLTUC
+            (0x07, _) => {
+                let target = compact_target(16);
+                if rs == 0 {
+                    Ok(Self::new(Opcode::BGTZALC, 31, rt, target, false, true))
+                } else if rs == rt {
+                    Ok(Self::new(Opcode::BLTZALC, 31, rt, target, false, true))
+                } else {
+                    Ok(Self::new(Opcode::BLTUC, rs as u8, rt, target, false, true))
+                }
+            }
+            // POP10: BOVC, BEQZALC, BEQC
+            (0x08, _) if isa == IsaRevision::R6 => {
+                if rs >= rt {
+                    Ok(Self::new(Opcode::BOVC, rs as u8, rt, compact_target(16), false, true))
+                } else if rs == 0 {
+                    Ok(Self::new(Opcode::BEQZALC, 31, rt, compact_target(16), false, true))
+                } else {
+                    Ok(Self::new(Opcode::BEQC, rs as u8, rt, compact_target(16), false, true))
+                }
+            }
+            // POP30: BNVC, BNEZALC, BNEC
+            (0x18, _) => {
+                if rs >= rt {
+                    Ok(Self::new(Opcode::BNVC, rs as u8, rt, compact_target(16), false, true))
+                } else if rs == 0 {
+                    Ok(Self::new(Opcode::BNEZALC, 31, rt, compact_target(16), false, true))
+                } else {
+                    Ok(Self::new(Opcode::BNEC, rs as u8, rt, compact_target(16), false, true))
+                }
+            }
+            // BEQL, BNEL
+            (0x14, _) => Ok(Self::new(Opcode::BEQL, rs as u8, rt, branch_offset, false, true)),
+            (0x15, _) => Ok(Self::new(Opcode::BNEL, rs as u8, rt, branch_offset, false, true)),
+            // BLEZL
+            (0x16, _) if rt == 0 => {
+                Ok(Self::new(Opcode::BLEZL, rs as u8, 0, branch_offset, true, true))
+            }
+            // POP26: BLEZC, BGEZC, BGEC
+            (0x16, _) => {
+                let target = compact_target(16);
+                if rs == 0 {
+                    Ok(Self::new(Opcode::BGEC, 0, rt, target, false, true))
+                } else if rs == rt {
+                    Ok(Self::new(Opcode::BGEC, rt as u8, 0, target, false, true))
+                } else {
+                    Ok(Self::new(Opcode::BGEC, rs as u8, rt, target, false, true))
+                }
+            }
+            // BGTZL
+            (0x17, _) if rt == 0 => {
+                Ok(Self::new(Opcode::BGTZL, rs as u8, 0, branch_offset, true, true))
+            }
+            // POP27: BGTZC, BLTZC, BLTC
+            (0x17, _) => {
+                let target = compact_target(16);
+                if rs == 0 {
+                    Ok(Self::new(Opcode::BLTC, 0, rt, target, false, true))
+                } else if rs == rt {
+                    Ok(Self::new(Opcode::BLTC, rt as u8, 0, target, false, true))
+                } else {
+                    Ok(Self::new(Opcode::BLTC, rs as u8, rt, target, false, true))
+                }
+            }
+            // BC, BALC
+            (0x32, _) => Ok(Self::new(Opcode::BEQC, 0, 0, compact_target(26), false, true)),
+            (0x3a, _) => Ok(Self::new(Opcode::BEQZALC, 31, 0, compact_target(26), false, true)),
+            // JIC: pc = rt + sext(imm)
+            (0x36, _) if rs == 0 => {
+                Ok(Self::new(Opcode::JumpCompact, 0, rt, offset_ext16, false, true))
+            }
+            // BEQZC
+            (0x36, _) => Ok(Self::new(Opcode::BEQC, rs as u8, 0, compact_target(21), false, true)),
+            // JIALC: ra = pc + 4, pc = rt + sext(imm)
+            (0x3e, _) if rs == 0 => {
+                Ok(Self::new(Opcode::JumpCompact, 31, rt, offset_ext16, false, true))
+            }
+            // BNEZC
+            (0x3e, _) => Ok(Self::new(Opcode::BNEC, rs as u8, 0, compact_target(21), false, true)),
+            // PCREL
+            (0x3b, _) => Ok(Self::decode_pcrel(insn, pc)),
 
             // LB
             (0b100000, _) => Ok(Self::new(Opcode::LB, rt as u8, rs, offset_ext16, false, true)),
@@ -532,6 +790,10 @@ impl Instruction {
             // SLTU: rd = rs < rt
             (0b000000, 0b101011) => Ok(Self::new(Opcode::SLTU, rd, rs, rt, false, false)), // SLTU: rd = rs < rt
 
+            // AUI: rt = rs + (imm << 16)
+            (0b001111, _) if rs != 0 => {
+                Ok(Self::new(Opcode::ADD, rt as u8, rs, offset << 16, false, true))
+            }
             // LUI: rt = imm << 16
             (0b001111, _) => Ok(Self::new(Opcode::SLL, rt as u8, offset_ext16, 16, true, true)), // LUI: rt = imm << 16
             // AND: rd = rs & rt
@@ -552,10 +814,25 @@ impl Instruction {
             (0b000000, 0b001100) => Ok(Self::new(Opcode::SYSCALL, 2, 4, 5, false, false)), // Syscall
             // PREF (nop)
             (0b110011, _) => Ok(Self::new(Opcode::ADD, 0, 0, 0, true, true)), // Pref
-            // TEQ
+            // CACHE (nop)
+            (0b101111, _) => Ok(Self::new(Opcode::ADD, 0, 0, 0, true, true)),
+            // TGE, TGEU, TLT, TLTU, TEQ, TNE
+            (0b000000, 0b110000) => Ok(Self::new(Opcode::TGE, rs as u8, rt, 0, false, true)),
+            (0b000000, 0b110001) => Ok(Self::new(Opcode::TGEU, rs as u8, rt, 0, false, true)),
+            (0b000000, 0b110010) => Ok(Self::new(Opcode::TLT, rs as u8, rt, 0, false, true)),
+            (0b000000, 0b110011) => Ok(Self::new(Opcode::TLTU, rs as u8, rt, 0, false, true)),
             (0b000000, 0b110100) => Ok(Self::new(Opcode::TEQ, rs as u8, rt, 0, false, true)), // teq
+            (0b000000, 0b110110) => Ok(Self::new(Opcode::TNE, rs as u8, rt, 0, false, true)),
+            // BREAK, which always traps
+            (0b000000, 0b001101) => Ok(Self::new(Opcode::TEQ, 0, 0, 0, false, true)),
             (0b011111, 0b100000) => {
-                if sa == 0b010000 {
+                if sa == 0b000000 {
+                    // BITSWAP
+                    Ok(Self::new(Opcode::BITSWAP, rd, rt, 0, false, true))
+                } else if sa & 0b11100 == 0b01000 {
+                    // ALIGN: rd = (rt << 8 * bp) | (rs >> 8 * (4 - bp)), the chip reads rt itself
+                    Ok(Self::new(Opcode::ALIGN, rd, rs, rt << 5 | (sa & 0x3), false, true))
+                } else if sa == 0b010000 {
                     // SEB
                     Ok(Self::new(Opcode::SEXT, rd, rt, 0, false, true))
                 } else if sa == 0b011000 {
@@ -565,9 +842,22 @@ impl Instruction {
                     // WSBH
                     Ok(Self::new(Opcode::WSBH, rd, rt, 0, false, true))
                 } else {
-                    Ok(Self::new_with_raw(Opcode::UNIMPL, 0, 0, insn, true, true, insn))
+                    Ok(unimpl)
                 }
             }
+            // LL, SC (r6) with a 9-bit offset
+            (0b011111, 0b110110) => {
+                let offset = sign_extend::<9>((insn >> 7) & 0x1ff);
+                Ok(Self::new(Opcode::LL, rt as u8, rs, offset, false, true))
+            }
+            (0b011111, 0b100110) => {
+                let offset = sign_extend::<9>((insn >> 7) & 0x1ff);
+                Ok(Self::new(Opcode::SC, rt as u8, rs, offset, false, true))
+            }
+            // PREF, CACHE (r6, nop)
+            (0b011111, 0b110101) | (0b011111, 0b100101) => {
+                Ok(Self::new(Opcode::ADD, 0, 0, 0, true, true))
+            }
             // EXT
             (0b011111, 0b000000) => {
                 Ok(Self::new(Opcode::EXT, rt as u8, rs, (rd as u32) << 5 | sa, false, true))
@@ -576,6 +866,47 @@ impl Instruction {
             (0b011111, 0b000100) => {
                 Ok(Self::new(Opcode::INS, rt as u8, rs, (rd as u32) << 5 | sa, false, true))
             }
+            // RDHWR $29: rt = thread pointer, the only hardware register that is supported
+            (0b011111, 0b111011) if rd == 29 => {
+                Ok(Self::new(Opcode::ADD, rt as u8, 36, 0, false, true))
+            }
+            // MOVF, MOVT: rd = rs if the condition code is false/true
+            (0b000000, 0b000001) => {
+                let fcc = Register::fcc((insn >> 18) & 0x7) as u32;
+                if insn & (1 << 16) != 0 {
+                    Ok(Self::new(Opcode::MNE, rd, rs, fcc, false, false))
+                } else {
+                    Ok(Self::new(Opcode::MEQ, rd, rs, fcc, false, false))
+                }
+            }
+            // COP1
+            (0b010001, _) => Ok(Self::decode_cop1(insn)),
+            // LWC1
+            (0b110001, _) => {
+                Ok(Self::new(Opcode::LW, Register::fpr(rt) as u8, rs, offset_ext16, false, true))
+            }
+            // SWC1
+            (0b111001, _) => {
+                Ok(Self::new(Opcode::SW, Register::fpr(rt) as u8, rs, offset_ext16, false, true))
+            }
+            // LDC1, the register index is packed above the unextended offset
+            (0b110101, _) if rt % 2 == 0 => Ok(Self::new(
+                Opcode::LDC1,
+                0,
+                rs,
+                (Register::fpr(rt) as u32) << 16 | offset,
+                false,
+                true,
+            )),
+            // SDC1
+            (0b111101, _) if rt % 2 == 0 => Ok(Self::new(
+                Opcode::SDC1,
+                0,
+                rs,
+                (Register::fpr(rt) as u32) << 16 | offset,
+                false,
+                true,
+            )),
             // MADDU
             (0b011100, 0b000001) => Ok(Self::new(Opcode::MADDU, 32, rt, rs, false, false)),
             // MSUBU
@@ -586,8 +917,160 @@ impl Instruction {
             (0b011100, 0b000100) => Ok(Self::new(Opcode::MSUB, 32, rt, rs, false, false)),
             _ => {
                 log::debug!("decode: invalid opcode {opcode:#08b} {func:#08b}");
-                Ok(Self::new_with_raw(Opcode::UNIMPL, 0, 0, insn, true, true, insn))
+                Ok(unimpl)
+            }
+        }
+    }
+
+    /// Decodes a MIPS32r6 PC-relative instruction, folding `pc` into the operands.
+    fn decode_pcrel(insn: u32, pc: u32) -> Self {
+        let rs = ((insn >> 21) & 0x1F) as u8;
+        let offset = sign_extend::<19>(insn & 0x7ffff) << 2;
+        let upper = (insn & 0xffff) << 16;
+        match (insn >> 19) & 0x3 {
+            // ADDIUPC: rs = pc + sext(imm << 2)
+            0 => Self::new(Opcode::ADD, rs, pc.wrapping_add(offset), 0, true, true),
+            // LWPC: rs = mem[pc + sext(imm << 2)]
+            1 => Self::new(Opcode::LW, rs, 0, pc.wrapping_add(offset), false, true),
+            _ => match (insn >> 16) & 0x1F {
+                // AUIPC: rs = pc + (imm << 16)
+                0x1e => Self::new(Opcode::ADD, rs, pc.wrapping_add(upper), 0, true, true),
+                // ALUIPC: rs = ~0xffff & (pc + (imm << 16))
+                0x1f => Self::new(Opcode::ADD, rs, pc.wrapping_add(upper) & !0xffff, 0, true, true),
+                _ => Self::new_with_raw(Opcode::UNIMPL, 0, 0, insn, true, true, insn),
+            },
+        }
+    }
+
+    /// Decodes a COP1 instruction, where the `rs` field selects the format.
+    ///
+    /// Doubles occupy an even/odd register pair (FR=0), so double operands in odd registers are
+    /// left unimplemented. Register moves and branches reuse the integer opcodes.
+    fn decode_cop1(insn: u32) -> Self {
+        let fmt = (insn >> 21) & 0x1F;
+        let ft = (insn >> 16) & 0x1F;
+        let fs = (insn >> 11) & 0x1F;
+        let fd = (insn >> 6) & 0x1F;
+        let func = insn & 0x3F;
+        let fpr = |r: u32| Register::fpr(r) as u32;
+        // The FPU chips access the registers themselves: `op_b` packs `fs` and `ft`, and `op_c`
+        // is the destination register.
+        let fpu = |opcode: Opcode, srcs: u32, dst: u32| Self::new(opcode, 0, srcs, dst, true, true);
+        let unimpl = Self::new_with_raw(Opcode::UNIMPL, 0, 0, insn, true, true, insn);
+
+        match fmt {
+            // MFC1: rt = fs
+            0x00 => Self::new(Opcode::ADD, ft as u8, fpr(fs), 0, false, true),
+            // CFC1: rt = fcsr, the only control register that is supported
+            0x02 if fs == 31 => {
+                Self::new(Opcode::ADD, ft as u8, Register::FCSR as u32, 0, false, true)
+            }
+            // MFHC1: rt = high word of fs
+            0x03 if fs % 2 == 0 => Self::new(Opcode::ADD, ft as u8, fpr(fs + 1), 0, false, true),
+            // MTC1: fs = rt
+            0x04 => Self::new(Opcode::ADD, fpr(fs) as u8, ft, 0, false, true),
+            // CTC1: fcsr = rt, raising an exception for a rounding mode other than to nearest
+            0x06 if fs == 31 => Self::new(Opcode::CTC1, Register::FCSR as u8, ft, 0, false, true),
+            // MTHC1: high word of fs = rt
+            0x07 if fs % 2 == 0 => Self::new(Opcode::ADD, fpr(fs + 1) as u8, ft, 0, false, true),
+            // BC1F, BC1T
+            0x08 if insn & (1 << 17) == 0 => {
+                let fcc = Register::fcc((insn >> 18) & 0x7) as u8;
+                let offset = sign_extend::<16>(insn & 0xFFFF).overflowing_shl(2).0;
+                if insn & (1 << 16) != 0 {
+                    Self::new(Opcode::BNE, fcc, 0, offset, false, true)
+                } else {
+                    Self::new(Opcode::BEQ, fcc, 0, offset, false, true)
+                }
+            }
+            // S, D
+            0x10 | 0x11 => {
+                let double = fmt == 0x11;
+                let even = |r: u32| !double || r % 2 == 0;
+                let pick =
+                    |single: Opcode, double_op: Opcode| if double { double_op } else { single };
+                match func {
+                    0x00..=0x03 if even(fs) && even(ft) && even(fd) => {
+                        let opcode = match func {
+                            0x00 => pick(Opcode::FADD_S, Opcode::FADD_D),
+                            0x01 => pick(Opcode::FSUB_S, Opcode::FSUB_D),
+                            0x02 => pick(Opcode::FMUL_S, Opcode::FMUL_D),
+                            _ => pick(Opcode::FDIV_S, Opcode::FDIV_D),
+                        };
+                        fpu(opcode, fpr(fs) | fpr(ft) << 8, fpr(fd))
+                    }
+                    // SQRT
+                    0x04 if even(fs) && even(fd) => {
+                        fpu(pick(Opcode::FSQRT_S, Opcode::FSQRT_D), fpr(fs), fpr(fd))
+                    }
+                    // ABS
+                    0x05 if even(fs) && even(fd) => {
+                        fpu(pick(Opcode::FABS_S, Opcode::FABS_D), fpr(fs), fpr(fd))
+                    }
+                    // MOV.S is a plain register move.
+                    0x06 if !double => {
+                        Self::new(Opcode::ADD, fpr(fd) as u8, fpr(fs), 0, false, true)
+                    }
+                    // MOV.D
+                    0x06 if even(fs) && even(fd) => fpu(Opcode::FMOV_D, fpr(fs), fpr(fd)),
+                    // NEG
+                    0x07 if even(fs) && even(fd) => {
+                        fpu(pick(Opcode::FNEG_S, Opcode::FNEG_D), fpr(fs), fpr(fd))
+                    }
+                    // TRUNC.W
+                    0x0D if even(fs) => {
+                        fpu(pick(Opcode::TRUNC_W_S, Opcode::TRUNC_W_D), fpr(fs), fpr(fd))
+                    }
+                    // MOVF.S, MOVT.S: fd = fs if the condition code is false/true
+                    0x11 if !double => {
+                        let fcc = Register::fcc((insn >> 18) & 0x7) as u32;
+                        let opcode = if ft & 1 != 0 { Opcode::MNE } else { Opcode::MEQ };
+                        Self::new(opcode, fpr(fd) as u8, fpr(fs), fcc, false, false)
+                    }
+                    // MOVZ.S: fd = fs if rt == 0
+                    0x12 if !double => {
+                        Self::new(Opcode::MEQ, fpr(fd) as u8, fpr(fs), ft, false, false)
+                    }
+                    // MOVN.S: fd = fs if rt != 0
+                    0x13 if !double => {
+                        Self::new(Opcode::MNE, fpr(fd) as u8, fpr(fs), ft, false, false)
+                    }
+                    // CVT.S.D
+                    0x20 if double && even(fs) => fpu(Opcode::CVT_S_D, fpr(fs), fpr(fd)),
+                    // CVT.D.S
+                    0x21 if !double && fd % 2 == 0 => fpu(Opcode::CVT_D_S, fpr(fs), fpr(fd)),
+                    // CVT.W
+                    0x24 if even(fs) => {
+                        fpu(pick(Opcode::CVT_W_S, Opcode::CVT_W_D), fpr(fs), fpr(fd))
+                    }
+                    // C.cond: the signaling predicates behave as their quiet counterparts.
+                    0x30..=0x3F if even(fs) && even(ft) => {
+                        let fcc = Register::fcc((insn >> 8) & 0x7) as u32;
+                        let opcode = match func & 0x7 {
+                            // C.F is always false.
+                            0 => return Self::new(Opcode::ADD, fcc as u8, 0, 0, true, true),
+                            1 => pick(Opcode::C_UN_S, Opcode::C_UN_D),
+                            2 => pick(Opcode::C_EQ_S, Opcode::C_EQ_D),
+                            3 => pick(Opcode::C_UEQ_S, Opcode::C_UEQ_D),
+                            4 => pick(Opcode::C_OLT_S, Opcode::C_OLT_D),
+                            5 => pick(Opcode::C_ULT_S, Opcode::C_ULT_D),
+                            6 => pick(Opcode::C_OLE_S, Opcode::C_OLE_D),
+                            _ => pick(Opcode::C_ULE_S, Opcode::C_ULE_D),
+                        };
+                        fpu(opcode, fpr(fs) | fpr(ft) << 8, fcc)
+                    }
+                    _ => unimpl,
+                }
             }
+            // W
+            0x14 => match func {
+                // CVT.S.W
+                0x20 => fpu(Opcode::CVT_S_W, fpr(fs), fpr(fd)),
+                // CVT.D.W
+                0x21 if fd % 2 == 0 => fpu(Opcode::CVT_D_W, fpr(fs), fpr(fd)),
+                _ => unimpl,
+            },
+            _ => unimpl,
         }
     }
 }
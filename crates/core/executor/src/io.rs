@@ -4,7 +4,7 @@ use serde::{de::DeserializeOwned, Serialize};
 use zkm_stark::{koala_bear_poseidon2::KoalaBearPoseidon2, StarkVerifyingKey};
 
 use super::Executor;
-use crate::ZKMReduceProof;
+use crate::{linux::LinuxEnv, ZKMReduceProof};
 
 impl Read for Executor<'_> {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
@@ -33,6 +33,25 @@ impl Executor<'_> {
         }
     }
 
+    /// Register a file in the virtual filesystem, making it readable through the Linux file
+    /// syscalls.
+    pub fn write_file(&mut self, path: &str, contents: Vec<u8>) {
+        self.state.vfs.insert(path, contents);
+    }
+
+    /// Register a set of files in the virtual filesystem.
+    pub fn write_files<'a>(&mut self, files: impl IntoIterator<Item = (&'a String, &'a Vec<u8>)>) {
+        for (path, contents) in files {
+            self.write_file(path, contents.clone());
+        }
+    }
+
+    /// Set the environment of the Linux syscalls, i.e. the time and the seed of the random bytes
+    /// seen by the program.
+    pub fn set_linux_env(&mut self, env: LinuxEnv) {
+        self.state.linux.env = env;
+    }
+
     /// Write a proof and verifying key to the proof stream.
     pub fn write_proof(
         &mut self,
@@ -4,11 +4,16 @@ mod cost;
 mod dependencies;
 pub mod events;
 mod executor;
+pub mod fpu;
+pub mod gdb;
 pub mod hook;
 mod instruction;
 mod io;
+pub mod linux;
 pub mod memory;
 mod opcode;
+#[cfg(feature = "profiling")]
+pub mod profiler;
 mod program;
 #[cfg(test)]
 pub mod programs;
@@ -16,10 +21,12 @@ mod record;
 pub mod reduce;
 mod register;
 pub mod report;
+mod snapshot;
 mod state;
 pub mod subproof;
 pub mod syscalls;
 mod utils;
+pub mod vfs;
 
 pub use air::*;
 pub use context::*;
@@ -33,6 +40,7 @@ pub use record::*;
 pub use reduce::*;
 pub use register::*;
 pub use report::*;
+pub use snapshot::*;
 pub use state::*;
 pub use subproof::*;
 pub use utils::*;
@@ -0,0 +1,237 @@
+//! The Linux o32 personality of the executor.
+//!
+//! Besides memory management and the virtual filesystem, guests compiled for `mipsel-linux-gnu`
+//! or `GOOS=linux GOARCH=mipsle` expect a few syscalls to behave like they do on a real kernel:
+//! reading the clocks, `getrandom`, `uname`, and the futex operations of a threading runtime.
+//! The guest runs as a single-threaded process whose clocks and random bytes derive from a
+//! [`LinuxEnv`] chosen by the host, so that executing the same program on the same input always
+//! gives the same trace. Like file contents, the time and the random bytes are untrusted input to
+//! the program.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::vfs::errno;
+
+/// The size of the MIPS o32 `struct timespec` written by `clock_gettime`.
+pub const TIMESPEC_SIZE: u32 = 8;
+
+/// The size of the `struct __kernel_timespec` written by `clock_gettime64`.
+pub const TIMESPEC64_SIZE: u32 = 16;
+
+/// The size of the `struct new_utsname` written by `uname`.
+pub const UTSNAME_SIZE: u32 = 390;
+
+/// The number of nanoseconds the clocks advance per executed instruction.
+pub const NANOS_PER_CYCLE: u64 = 1;
+
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+const CLOCK_REALTIME: u32 = 0;
+const CLOCK_MONOTONIC: u32 = 1;
+const CLOCK_PROCESS_CPUTIME_ID: u32 = 2;
+const CLOCK_THREAD_CPUTIME_ID: u32 = 3;
+const CLOCK_MONOTONIC_RAW: u32 = 4;
+const CLOCK_REALTIME_COARSE: u32 = 5;
+const CLOCK_MONOTONIC_COARSE: u32 = 6;
+const CLOCK_BOOTTIME: u32 = 7;
+
+/// `GRND_NONBLOCK | GRND_RANDOM | GRND_INSECURE`, which make no difference here.
+const GRND_FLAGS: u32 = 0b111;
+
+const FUTEX_WAIT: u32 = 0;
+const FUTEX_WAKE: u32 = 1;
+const FUTEX_WAIT_BITSET: u32 = 9;
+const FUTEX_WAKE_BITSET: u32 = 10;
+const FUTEX_PRIVATE_FLAG: u32 = 128;
+const FUTEX_CLOCK_REALTIME: u32 = 256;
+
+/// The fields of the `struct new_utsname` reported by `uname`. The release is recent enough for
+/// the kernel version checks of glibc and the Go runtime.
+const UTSNAME: [&str; 6] = ["Linux", "zkm", "5.10.0", "#1 SMP", "mips", "(none)"];
+
+/// The environment of the Linux syscalls, chosen by the host.
+///
+/// The environment is an unconstrained input of the prover: it is neither committed to the public
+/// values nor checked by the verifier, so a proof only shows that the program ran correctly for
+/// some time and some random bytes. Programs which rely on them must commit them to their public
+/// values themselves.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinuxEnv {
+    /// The Unix time in nanoseconds reported by `CLOCK_REALTIME` when the program starts.
+    pub realtime_ns: u64,
+    /// The seed of the bytes returned by `getrandom`.
+    pub random_seed: [u8; 32],
+}
+
+impl LinuxEnv {
+    /// Create an environment starting at the given Unix time, in nanoseconds.
+    #[must_use]
+    pub const fn new(realtime_ns: u64, random_seed: [u8; 32]) -> Self {
+        Self { realtime_ns, random_seed }
+    }
+}
+
+/// The environment of the Linux syscalls and the state derived from it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinuxState {
+    /// The environment chosen by the host.
+    pub env: LinuxEnv,
+    /// The number of bytes returned by `getrandom` so far.
+    pub random_offset: u64,
+}
+
+impl LinuxState {
+    /// Read a clock after `global_clk` cycles, returning the seconds and nanoseconds.
+    ///
+    /// The realtime clocks start at the time of the environment and the other clocks start at
+    /// zero. All of them advance by [`NANOS_PER_CYCLE`] per executed instruction.
+    pub fn clock_gettime(&self, clock_id: u32, global_clk: u64) -> Result<(u64, u32), u32> {
+        let uptime = global_clk.saturating_mul(NANOS_PER_CYCLE);
+        let nanos = match clock_id {
+            CLOCK_REALTIME | CLOCK_REALTIME_COARSE => self.env.realtime_ns.saturating_add(uptime),
+            CLOCK_MONOTONIC
+            | CLOCK_PROCESS_CPUTIME_ID
+            | CLOCK_THREAD_CPUTIME_ID
+            | CLOCK_MONOTONIC_RAW
+            | CLOCK_MONOTONIC_COARSE
+            | CLOCK_BOOTTIME => uptime,
+            _ => return Err(errno::EINVAL),
+        };
+        Ok((nanos / NANOS_PER_SEC, (nanos % NANOS_PER_SEC) as u32))
+    }
+
+    /// Return the next `len` bytes of the random stream.
+    ///
+    /// The stream is the concatenation of `sha256(seed || i)` for the little-endian 64-bit block
+    /// indices `i`, so splitting a request across several calls gives the same bytes.
+    pub fn getrandom(&mut self, len: u32, flags: u32) -> Result<Vec<u8>, u32> {
+        if flags & !GRND_FLAGS != 0 {
+            return Err(errno::EINVAL);
+        }
+        let mut bytes = Vec::with_capacity(len as usize);
+        while bytes.len() < len as usize {
+            let block = Sha256::new()
+                .chain_update(self.env.random_seed)
+                .chain_update((self.random_offset / 32).to_le_bytes())
+                .finalize();
+            let start = (self.random_offset % 32) as usize;
+            let end = block.len().min(start + len as usize - bytes.len());
+            bytes.extend_from_slice(&block[start..end]);
+            self.random_offset += (end - start) as u64;
+        }
+        Ok(bytes)
+    }
+}
+
+/// Encode a MIPS o32 `struct timespec`, whose fields are 32-bit.
+pub fn timespec(secs: u64, nanos: u32) -> Result<Vec<u8>, u32> {
+    let secs = i32::try_from(secs).map_err(|_| errno::EOVERFLOW)?;
+    Ok([secs.to_le_bytes(), nanos.to_le_bytes()].concat())
+}
+
+/// Encode a `struct __kernel_timespec`, whose fields are 64-bit.
+#[must_use]
+pub fn timespec64(secs: u64, nanos: u32) -> Vec<u8> {
+    [secs.to_le_bytes(), u64::from(nanos).to_le_bytes()].concat()
+}
+
+/// Run a futex operation of a single-threaded process, where `word` is the current value of the
+/// futex word and `val` the value expected by the caller.
+///
+/// There are never other threads to wake up. A wait that would block forever returns at once,
+/// which callers must already handle as a spurious wakeup, and a wait with a timeout times out.
+pub fn futex(op: u32, word: u32, val: u32, has_timeout: bool) -> Result<u32, u32> {
+    match op & !(FUTEX_PRIVATE_FLAG | FUTEX_CLOCK_REALTIME) {
+        FUTEX_WAIT | FUTEX_WAIT_BITSET if word != val => Err(errno::EAGAIN),
+        FUTEX_WAIT | FUTEX_WAIT_BITSET if has_timeout => Err(errno::ETIMEDOUT),
+        FUTEX_WAIT | FUTEX_WAIT_BITSET | FUTEX_WAKE | FUTEX_WAKE_BITSET => Ok(0),
+        _ => Err(errno::ENOSYS),
+    }
+}
+
+/// Build the `struct new_utsname` reported by `uname`.
+#[must_use]
+pub fn utsname() -> [u8; UTSNAME_SIZE as usize] {
+    let mut utsname = [0u8; UTSNAME_SIZE as usize];
+    for (field, value) in utsname.chunks_exact_mut(UTSNAME_SIZE as usize / 6).zip(UTSNAME) {
+        field[..value.len()].copy_from_slice(value.as_bytes());
+    }
+    utsname
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, process::Command};
+
+    use super::*;
+    use crate::{Executor, Program, ZKMCoreOpts};
+
+    #[test]
+    fn test_clocks() {
+        let state =
+            LinuxState { env: LinuxEnv::new(5 * NANOS_PER_SEC - 1, [0; 32]), ..Default::default() };
+        assert_eq!(state.clock_gettime(CLOCK_REALTIME, 0), Ok((4, 999_999_999)));
+        assert_eq!(state.clock_gettime(CLOCK_REALTIME, 1), Ok((5, 0)));
+        assert_eq!(state.clock_gettime(CLOCK_MONOTONIC, 1_000_000_007), Ok((1, 7)));
+        assert_eq!(state.clock_gettime(CLOCK_BOOTTIME + 1, 0), Err(errno::EINVAL));
+
+        assert_eq!(timespec(1 << 31, 0), Err(errno::EOVERFLOW));
+        assert_eq!(timespec64(1 << 31, 3)[..12], [0, 0, 0, 0x80, 0, 0, 0, 0, 3, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_getrandom() {
+        let mut state = LinuxState { env: LinuxEnv::new(0, [1; 32]), ..Default::default() };
+        let bytes = state.getrandom(100, 0).unwrap();
+        assert_eq!(state.random_offset, 100);
+
+        let mut split = LinuxState { env: state.env, ..Default::default() };
+        let mut split_bytes = split.getrandom(7, 0).unwrap();
+        split_bytes.extend(split.getrandom(93, 1).unwrap());
+        assert_eq!(bytes, split_bytes);
+        assert_eq!(split.getrandom(1, 8), Err(errno::EINVAL));
+
+        let mut other = LinuxState { env: LinuxEnv::new(0, [2; 32]), ..Default::default() };
+        assert_ne!(bytes, other.getrandom(100, 0).unwrap());
+    }
+
+    #[test]
+    fn test_futex() {
+        assert_eq!(futex(FUTEX_WAIT | FUTEX_PRIVATE_FLAG, 1, 2, false), Err(errno::EAGAIN));
+        assert_eq!(futex(FUTEX_WAIT, 1, 1, true), Err(errno::ETIMEDOUT));
+        assert_eq!(futex(FUTEX_WAIT_BITSET, 1, 1, false), Ok(0));
+        assert_eq!(futex(FUTEX_WAKE | FUTEX_PRIVATE_FLAG, 0, 1, false), Ok(0));
+        assert_eq!(futex(3, 0, 0, false), Err(errno::ENOSYS));
+    }
+
+    #[test]
+    fn test_utsname() {
+        let utsname = utsname();
+        assert_eq!(&utsname[..6], b"Linux\0");
+        assert_eq!(&utsname[260..265], b"mips\0");
+    }
+
+    /// Runs the conformance program, a static `mipsel-linux-gnu` binary which checks the results
+    /// of the syscalls and halts with the failing line as exit code on a mismatch.
+    #[test]
+    #[ignore = "requires the mipsel-linux-gnu-gcc cross compiler"]
+    fn test_o32_conformance() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("linux-conformance");
+        let elf = std::env::temp_dir().join(format!("zkm-o32-conformance-{}", std::process::id()));
+        let status = Command::new("mipsel-linux-gnu-gcc")
+            .args(["-static", "-nostdlib", "-ffreestanding", "-fno-builtin", "-fno-pic"])
+            .args(["-mno-abicalls", "-msoft-float", "-march=mips32r2", "-G0", "-O2", "-o"])
+            .arg(&elf)
+            .arg(dir.join("syscalls.c"))
+            .status()
+            .expect("failed to run mipsel-linux-gnu-gcc");
+        assert!(status.success());
+
+        let program = Program::from(&std::fs::read(&elf).unwrap()).unwrap();
+        std::fs::remove_file(&elf).unwrap();
+        let mut runtime = Executor::new(program, ZKMCoreOpts::default());
+        runtime.set_linux_env(LinuxEnv::new(1_700_000_000 * NANOS_PER_SEC, [7; 32]));
+        runtime.run().unwrap();
+    }
+}
@@ -74,6 +74,87 @@ pub enum Opcode {
     TEQ = 54,   // TEQ
     SEXT = 55,  // SEXT
 
+    // FPU
+    FADD_S = 56,    // FPU_ARITH
+    FADD_D = 57,    // FPU_ARITH
+    FSUB_S = 58,    // FPU_ARITH
+    FSUB_D = 59,    // FPU_ARITH
+    FMUL_S = 60,    // FPU_ARITH
+    FMUL_D = 61,    // FPU_ARITH
+    FDIV_S = 62,    // FPU_ARITH
+    FDIV_D = 63,    // FPU_ARITH
+    FSQRT_S = 64,   // FPU_ARITH
+    FSQRT_D = 65,   // FPU_ARITH
+    FABS_S = 66,    // FPU_MISC
+    FABS_D = 67,    // FPU_MISC
+    FNEG_S = 68,    // FPU_MISC
+    FNEG_D = 69,    // FPU_MISC
+    FMOV_D = 70,    // FPU_MISC
+    CVT_S_D = 71,   // FPU_ARITH
+    CVT_D_S = 72,   // FPU_ARITH
+    CVT_S_W = 73,   // FPU_ARITH
+    CVT_D_W = 74,   // FPU_ARITH
+    CVT_W_S = 75,   // FPU_ARITH
+    CVT_W_D = 76,   // FPU_ARITH
+    TRUNC_W_S = 77, // FPU_ARITH
+    TRUNC_W_D = 78, // FPU_ARITH
+    C_UN_S = 79,    // FPU_MISC
+    C_EQ_S = 80,    // FPU_MISC
+    C_UEQ_S = 81,   // FPU_MISC
+    C_OLT_S = 82,   // FPU_MISC
+    C_ULT_S = 83,   // FPU_MISC
+    C_OLE_S = 84,   // FPU_MISC
+    C_ULE_S = 85,   // FPU_MISC
+    C_UN_D = 86,    // FPU_MISC
+    C_EQ_D = 87,    // FPU_MISC
+    C_UEQ_D = 88,   // FPU_MISC
+    C_OLT_D = 89,   // FPU_MISC
+    C_ULT_D = 90,   // FPU_MISC
+    C_OLE_D = 91,   // FPU_MISC
+    C_ULE_D = 92,   // FPU_MISC
+    LDC1 = 93,      // FPU_MISC
+    SDC1 = 94,      // FPU_MISC
+
+    // MIPS32r2 traps and branches, MIPS32r6
+    TNE = 95,          // TRAP
+    TGE = 96,          // TRAP
+    TGEU = 97,         // TRAP
+    TLT = 98,          // TRAP
+    TLTU = 99,         // TRAP
+    SELEQZ = 100,      // MOVCOND
+    SELNEZ = 101,      // MOVCOND
+    LSA = 102,         // LSA
+    ALIGN = 103,       // ALIGN
+    BITSWAP = 104,     // BITSWAP
+    MUH = 105,         // MUL
+    MUHU = 106,        // MUL
+    BLTZAL = 107,      // BRANCH
+    BGEZAL = 108,      // BRANCH
+    BEQL = 109,        // BRANCH
+    BNEL = 110,        // BRANCH
+    BLEZL = 111,       // BRANCH
+    BGTZL = 112,       // BRANCH
+    BLTZL = 113,       // BRANCH
+    BGEZL = 114,       // BRANCH
+    BLTZALL = 115,     // BRANCH
+    BGEZALL = 116,     // BRANCH
+    BEQC = 117,        // BRANCH
+    BNEC = 118,        // BRANCH
+    BLTC = 119,        // BRANCH
+    BGEC = 120,        // BRANCH
+    BLTUC = 121,       // BRANCH
+    BGEUC = 122,       // BRANCH
+    BLEZALC = 123,     // BRANCH
+    BGEZALC = 124,     // BRANCH
+    BGTZALC = 125,     // BRANCH
+    BLTZALC = 126,     // BRANCH
+    BEQZALC = 127,     // BRANCH
+    BNEZALC = 128,     // BRANCH
+    JumpCompact = 129, // JUMP
+    BOVC = 130,        // BRANCH
+    BNVC = 131,        // BRANCH
+    CTC1 = 132,        // MISC
+
     // Syscall
     UNIMPL = 0xff,
 }
@@ -139,6 +220,83 @@ impl Opcode {
             Opcode::MODU => "modu",
             Opcode::MADD => "madd",
             Opcode::MSUB => "msub",
+            Opcode::FADD_S => "add.s",
+            Opcode::FADD_D => "add.d",
+            Opcode::FSUB_S => "sub.s",
+            Opcode::FSUB_D => "sub.d",
+            Opcode::FMUL_S => "mul.s",
+            Opcode::FMUL_D => "mul.d",
+            Opcode::FDIV_S => "div.s",
+            Opcode::FDIV_D => "div.d",
+            Opcode::FSQRT_S => "sqrt.s",
+            Opcode::FSQRT_D => "sqrt.d",
+            Opcode::FABS_S => "abs.s",
+            Opcode::FABS_D => "abs.d",
+            Opcode::FNEG_S => "neg.s",
+            Opcode::FNEG_D => "neg.d",
+            Opcode::FMOV_D => "mov.d",
+            Opcode::CVT_S_D => "cvt.s.d",
+            Opcode::CVT_D_S => "cvt.d.s",
+            Opcode::CVT_S_W => "cvt.s.w",
+            Opcode::CVT_D_W => "cvt.d.w",
+            Opcode::CVT_W_S => "cvt.w.s",
+            Opcode::CVT_W_D => "cvt.w.d",
+            Opcode::TRUNC_W_S => "trunc.w.s",
+            Opcode::TRUNC_W_D => "trunc.w.d",
+            Opcode::C_UN_S => "c.un.s",
+            Opcode::C_EQ_S => "c.eq.s",
+            Opcode::C_UEQ_S => "c.ueq.s",
+            Opcode::C_OLT_S => "c.olt.s",
+            Opcode::C_ULT_S => "c.ult.s",
+            Opcode::C_OLE_S => "c.ole.s",
+            Opcode::C_ULE_S => "c.ule.s",
+            Opcode::C_UN_D => "c.un.d",
+            Opcode::C_EQ_D => "c.eq.d",
+            Opcode::C_UEQ_D => "c.ueq.d",
+            Opcode::C_OLT_D => "c.olt.d",
+            Opcode::C_ULT_D => "c.ult.d",
+            Opcode::C_OLE_D => "c.ole.d",
+            Opcode::C_ULE_D => "c.ule.d",
+            Opcode::LDC1 => "ldc1",
+            Opcode::SDC1 => "sdc1",
+            Opcode::TNE => "tne",
+            Opcode::TGE => "tge",
+            Opcode::TGEU => "tgeu",
+            Opcode::TLT => "tlt",
+            Opcode::TLTU => "tltu",
+            Opcode::SELEQZ => "seleqz",
+            Opcode::SELNEZ => "selnez",
+            Opcode::LSA => "lsa",
+            Opcode::ALIGN => "align",
+            Opcode::BITSWAP => "bitswap",
+            Opcode::MUH => "muh",
+            Opcode::MUHU => "muhu",
+            Opcode::BLTZAL => "bltzal",
+            Opcode::BGEZAL => "bgezal",
+            Opcode::BEQL => "beql",
+            Opcode::BNEL => "bnel",
+            Opcode::BLEZL => "blezl",
+            Opcode::BGTZL => "bgtzl",
+            Opcode::BLTZL => "bltzl",
+            Opcode::BGEZL => "bgezl",
+            Opcode::BLTZALL => "bltzall",
+            Opcode::BGEZALL => "bgezall",
+            Opcode::BEQC => "beqc",
+            Opcode::BNEC => "bnec",
+            Opcode::BLTC => "bltc",
+            Opcode::BGEC => "bgec",
+            Opcode::BLTUC => "bltuc",
+            Opcode::BGEUC => "bgeuc",
+            Opcode::BLEZALC => "blezalc",
+            Opcode::BGEZALC => "bgezalc",
+            Opcode::BGTZALC => "bgtzalc",
+            Opcode::BLTZALC => "bltzalc",
+            Opcode::BEQZALC => "beqzalc",
+            Opcode::BNEZALC => "bnezalc",
+            Opcode::JumpCompact => "jump_compact",
+            Opcode::BOVC => "bovc",
+            Opcode::BNVC => "bnvc",
+            Opcode::CTC1 => "ctc1",
             Opcode::UNIMPL => "unimpl",
         }
     }
@@ -164,7 +322,71 @@ impl Opcode {
     }
 
     pub fn only_one_operand(&self) -> bool {
-        matches!(self, Opcode::BGEZ | Opcode::BLEZ | Opcode::BGTZ | Opcode::BLTZ)
+        matches!(
+            self,
+            Opcode::BGEZ
+                | Opcode::BLEZ
+                | Opcode::BGTZ
+                | Opcode::BLTZ
+                | Opcode::BGEZL
+                | Opcode::BLEZL
+                | Opcode::BGTZL
+                | Opcode::BLTZL
+        )
+    }
+
+    /// Whether the opcode is a branch-likely, which skips its delay slot when not taken.
+    pub fn is_branch_likely(&self) -> bool {
+        matches!(
+            self,
+            Opcode::BEQL
+                | Opcode::BNEL
+                | Opcode::BLEZL
+                | Opcode::BGTZL
+                | Opcode::BLTZL
+                | Opcode::BGEZL
+                | Opcode::BLTZALL
+                | Opcode::BGEZALL
+        )
+    }
+
+    /// Whether the opcode is a MIPS32r6 compact branch, which has no delay slot and whose
+    /// third operand is the absolute target.
+    pub fn is_compact_branch(&self) -> bool {
+        matches!(
+            self,
+            Opcode::BEQC
+                | Opcode::BNEC
+                | Opcode::BLTC
+                | Opcode::BGEC
+                | Opcode::BLTUC
+                | Opcode::BGEUC
+                | Opcode::BLEZALC
+                | Opcode::BGEZALC
+                | Opcode::BGTZALC
+                | Opcode::BLTZALC
+                | Opcode::BEQZALC
+                | Opcode::BNEZALC
+                | Opcode::BOVC
+                | Opcode::BNVC
+        )
+    }
+
+    /// Whether the opcode is a branch that links to `op_a` and compares `op_b` with zero.
+    pub fn is_branch_link(&self) -> bool {
+        matches!(
+            self,
+            Opcode::BLTZAL
+                | Opcode::BGEZAL
+                | Opcode::BLTZALL
+                | Opcode::BGEZALL
+                | Opcode::BLEZALC
+                | Opcode::BGEZALC
+                | Opcode::BGTZALC
+                | Opcode::BLTZALC
+                | Opcode::BEQZALC
+                | Opcode::BNEZALC
+        )
     }
 }
 
@@ -0,0 +1,395 @@
+//! A guest profiler that turns the program counter stream written to `TRACE_FILE` into
+//! symbolized call stacks.
+//!
+//! The executor writes the big-endian program counter of every constrained instruction to the
+//! file named by `TRACE_FILE` (debug builds only). The [`Profiler`] replays that stream against
+//! the decoded program, rebuilds the call stack from `JAL`/`JALR`/`BAL` calls and `JR $ra`
+//! returns, and aggregates every instruction into its stack. The result can be written as folded
+//! stacks (for `inferno`/`flamegraph.pl`) or as a pprof protobuf.
+
+mod pprof;
+mod symbols;
+
+use std::{
+    io::{ErrorKind, Read, Write},
+    str::FromStr,
+    sync::Arc,
+};
+
+use anyhow::Result;
+use hashbrown::HashMap;
+use prost::Message;
+
+pub use symbols::{FunctionSymbol, SymbolizedFrame, Symbolizer};
+
+use crate::{Instruction, MipsAirId, Opcode, Program, MIPS_COSTS};
+
+/// The weight attributed to each stack in the folded output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProfileWeight {
+    /// Weight stacks by the number of executed instructions.
+    #[default]
+    Cycles,
+    /// Weight stacks by the estimated number of trace cells the instructions occupy, using the
+    /// per-row costs in `mips_costs.json`.
+    TraceArea,
+}
+
+/// The accumulated weights of a stack.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Weights {
+    /// The number of executed instructions.
+    pub cycles: u64,
+    /// The estimated number of trace cells.
+    pub trace_area: u64,
+}
+
+impl Weights {
+    fn get(&self, weight: ProfileWeight) -> u64 {
+        match weight {
+            ProfileWeight::Cycles => self.cycles,
+            ProfileWeight::TraceArea => self.trace_area,
+        }
+    }
+}
+
+/// The kind of control flow transfer performed by a jump instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Transfer {
+    Call,
+    Return,
+}
+
+/// A control flow transfer waiting for its delay slot to be executed.
+#[derive(Debug, Clone, Copy)]
+struct PendingTransfer {
+    transfer: Transfer,
+    /// The address of the jump instruction.
+    pc: u32,
+    /// Whether the delay slot has already been executed.
+    delay_slot_done: bool,
+}
+
+/// A node of the call tree. Stacks are interned so that recording an instruction does not need
+/// to clone the current stack.
+#[derive(Debug, Clone, Copy)]
+struct StackNode {
+    parent: usize,
+    /// The address of the call instruction that entered this frame.
+    call_site: u32,
+}
+
+/// The root of the call tree.
+const ROOT: usize = 0;
+
+/// Rebuilds guest call stacks from a program counter trace.
+pub struct Profiler {
+    program: Program,
+    symbolizer: Symbolizer,
+    costs: HashMap<MipsAirId, u64>,
+    /// The interned call tree, indexed by node id.
+    nodes: Vec<StackNode>,
+    children: HashMap<(usize, u32), usize>,
+    /// The current call stack, as a path of node ids starting at [`ROOT`].
+    stack: Vec<usize>,
+    pending: Option<PendingTransfer>,
+    /// The weights keyed by the current stack node and the program counter of the leaf.
+    samples: HashMap<(usize, u32), Weights>,
+    total: Weights,
+}
+
+impl Profiler {
+    /// Create a profiler for the given guest ELF.
+    pub fn new(elf: &[u8]) -> Result<Self> {
+        let program = Program::from(elf)?;
+        let symbolizer = Symbolizer::new(elf)?;
+
+        let costs: HashMap<String, u64> = serde_json::from_str(MIPS_COSTS)?;
+        let costs = costs
+            .into_iter()
+            .map(|(k, v)| Ok((MipsAirId::from_str(&k).map_err(anyhow::Error::msg)?, v)))
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            program,
+            symbolizer,
+            costs,
+            nodes: vec![StackNode { parent: ROOT, call_site: 0 }],
+            children: HashMap::new(),
+            stack: vec![ROOT],
+            pending: None,
+            samples: HashMap::new(),
+            total: Weights::default(),
+        })
+    }
+
+    /// Record every program counter of a trace written by the executor to `TRACE_FILE`.
+    pub fn record_trace(&mut self, mut reader: impl Read) -> Result<()> {
+        let mut buf = [0u8; 4];
+        loop {
+            match reader.read_exact(&mut buf) {
+                Ok(()) => self.record(u32::from_be_bytes(buf)),
+                Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(()),
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    /// Record the execution of the instruction at `pc`.
+    pub fn record(&mut self, pc: u32) {
+        // Control flow transfers take effect after their delay slot has been executed.
+        if let Some(pending) = self.pending.as_mut() {
+            if pending.delay_slot_done {
+                let pending = self.pending.take().unwrap();
+                self.apply_transfer(pending, pc);
+            } else {
+                pending.delay_slot_done = true;
+            }
+        }
+
+        let instruction = self.instruction(pc);
+
+        let cost = instruction.map_or(0, |instruction| self.trace_area(&instruction));
+        let node = *self.stack.last().unwrap();
+        let weights = self.samples.entry((node, pc)).or_default();
+        weights.cycles += 1;
+        weights.trace_area += cost;
+        self.total.cycles += 1;
+        self.total.trace_area += cost;
+
+        if let Some(transfer) = instruction.as_ref().and_then(classify) {
+            self.pending = Some(PendingTransfer { transfer, pc, delay_slot_done: false });
+        }
+    }
+
+    /// Returns the total weights recorded so far.
+    pub fn total(&self) -> Weights {
+        self.total
+    }
+
+    /// Write the recorded stacks in the folded format, one `frame;frame;frame weight` line per
+    /// stack, as consumed by `inferno-flamegraph` or `flamegraph.pl`.
+    pub fn write_folded(&mut self, mut writer: impl Write, weight: ProfileWeight) -> Result<()> {
+        let samples = std::mem::take(&mut self.samples);
+        let mut folded: HashMap<String, u64> = HashMap::new();
+        for (&(node, pc), weights) in &samples {
+            let frames = self
+                .frames(node, pc)
+                .into_iter()
+                .flat_map(|(_, frames)| {
+                    frames.iter().map(|f| f.function.clone()).collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            *folded.entry(frames.join(";")).or_default() += weights.get(weight);
+        }
+        self.samples = samples;
+
+        let mut folded = folded.into_iter().filter(|(_, w)| *w > 0).collect::<Vec<_>>();
+        folded.sort();
+        for (stack, weight) in folded {
+            writeln!(writer, "{stack} {weight}")?;
+        }
+        Ok(())
+    }
+
+    /// Write the recorded stacks as an uncompressed pprof protobuf, with an `instructions` and a
+    /// `trace_area` sample value.
+    pub fn write_pprof(&mut self, mut writer: impl Write) -> Result<()> {
+        let mut builder =
+            pprof::ProfileBuilder::new(&[("instructions", "count"), ("trace_area", "cells")]);
+
+        let samples = std::mem::take(&mut self.samples);
+        for (&(node, pc), weights) in &samples {
+            let mut location_ids = Vec::new();
+            // pprof expects the leaf first, and the innermost inlined function first.
+            for (address, frames) in self.frames(node, pc).into_iter().rev() {
+                let id = builder.location(address, |builder| {
+                    frames
+                        .iter()
+                        .rev()
+                        .map(|frame| pprof::Line {
+                            function_id: builder.function(&frame.function, frame.file.as_deref()),
+                            line: frame.line.unwrap_or_default() as i64,
+                        })
+                        .collect()
+                });
+                location_ids.push(id);
+            }
+            builder.sample(location_ids, vec![weights.cycles as i64, weights.trace_area as i64]);
+        }
+        self.samples = samples;
+
+        writer.write_all(&builder.build().encode_to_vec())?;
+        Ok(())
+    }
+
+    /// Symbolize a stack, from the outermost call site to the leaf program counter.
+    fn frames(&mut self, mut node: usize, pc: u32) -> Vec<(u32, Arc<[SymbolizedFrame]>)> {
+        let mut addresses = vec![pc];
+        while node != ROOT {
+            addresses.push(self.nodes[node].call_site);
+            node = self.nodes[node].parent;
+        }
+        addresses
+            .into_iter()
+            .rev()
+            .map(|address| (address, self.symbolizer.symbolize(address)))
+            .collect()
+    }
+
+    fn apply_transfer(&mut self, pending: PendingTransfer, target: u32) {
+        match pending.transfer {
+            Transfer::Call => {
+                let parent = *self.stack.last().unwrap();
+                let next_id = self.nodes.len();
+                let id = *self.children.entry((parent, pending.pc)).or_insert(next_id);
+                if id == next_id {
+                    self.nodes.push(StackNode { parent, call_site: pending.pc });
+                }
+                self.stack.push(id);
+            }
+            Transfer::Return => {
+                // Unwind to the frame whose return address is the jump target. Returns that do
+                // not match any frame (e.g. from frames entered before the trace started) leave
+                // the stack untouched.
+                if let Some(depth) = self.stack.iter().rposition(|&id| {
+                    id != ROOT && self.nodes[id].call_site.wrapping_add(8) == target
+                }) {
+                    self.stack.truncate(depth);
+                }
+            }
+        }
+    }
+
+    fn instruction(&self, pc: u32) -> Option<Instruction> {
+        let idx = (pc.checked_sub(self.program.pc_base)? / 4) as usize;
+        self.program.instructions.get(idx).copied()
+    }
+
+    /// The estimated number of trace cells used by an instruction: its CPU row plus its row in
+    /// the chip that handles the opcode.
+    fn trace_area(&self, instruction: &Instruction) -> u64 {
+        let cpu = self.costs.get(&MipsAirId::Cpu).copied().unwrap_or_default();
+        let chip = air_id(instruction).and_then(|id| self.costs.get(&id)).copied();
+        cpu + chip.unwrap_or_default()
+    }
+}
+
+/// Classify the control flow transfer performed by an instruction, if any.
+fn classify(instruction: &Instruction) -> Option<Transfer> {
+    match instruction.opcode {
+        // JAL, BAL and JIALC link into `$ra`.
+        Opcode::Jumpi | Opcode::JumpDirect | Opcode::JumpCompact if instruction.op_a == 31 => {
+            Some(Transfer::Call)
+        }
+        Opcode::JumpCompact if instruction.op_b == 31 => Some(Transfer::Return),
+        // JALR links into `rd`, JR has `rd == 0`.
+        Opcode::Jump if instruction.op_a != 0 => Some(Transfer::Call),
+        Opcode::Jump if instruction.op_b == 31 => Some(Transfer::Return),
+        _ => None,
+    }
+}
+
+/// The chip that proves an instruction, besides the CPU chip.
+fn air_id(instruction: &Instruction) -> Option<MipsAirId> {
+    let id = match instruction.opcode {
+        Opcode::ADD | Opcode::SUB => MipsAirId::AddSub,
+        Opcode::XOR | Opcode::OR | Opcode::AND | Opcode::NOR => MipsAirId::Bitwise,
+        Opcode::SLL => MipsAirId::ShiftLeft,
+        Opcode::SRL | Opcode::SRA | Opcode::ROR => MipsAirId::ShiftRight,
+        Opcode::SLT | Opcode::SLTU => MipsAirId::Lt,
+        Opcode::MUL | Opcode::MULT | Opcode::MULTU | Opcode::MUH | Opcode::MUHU => MipsAirId::Mul,
+        Opcode::DIV | Opcode::DIVU | Opcode::MOD | Opcode::MODU => MipsAirId::DivRem,
+        Opcode::CLZ | Opcode::CLO => MipsAirId::CloClz,
+        Opcode::MNE | Opcode::MEQ | Opcode::WSBH | Opcode::SELEQZ | Opcode::SELNEZ => {
+            MipsAirId::MovCond
+        }
+        _ if instruction.is_misc_instruction() => MipsAirId::MiscInstrs,
+        _ if instruction.is_fpu_arith_instruction() => MipsAirId::FpuArith,
+        _ if instruction.is_fpu_misc_instruction() => MipsAirId::FpuMisc,
+        _ if instruction.is_memory_instruction() => MipsAirId::MemoryInstrs,
+        _ if instruction.is_branch_instruction() => MipsAirId::Branch,
+        _ if instruction.is_jump_instruction() => MipsAirId::Jump,
+        _ if instruction.is_syscall_instruction() => MipsAirId::SyscallInstrs,
+        _ => return None,
+    };
+    Some(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jal(target: u32) -> Instruction {
+        Instruction::new(Opcode::Jumpi, 31, target, 0, true, true)
+    }
+
+    fn jr_ra() -> Instruction {
+        Instruction::new(Opcode::Jump, 0, 31, 0, false, true)
+    }
+
+    fn nop() -> Instruction {
+        Instruction::new(Opcode::ADD, 0, 0, 0, true, true)
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(classify(&jal(0x100)), Some(Transfer::Call));
+        assert_eq!(
+            classify(&Instruction::new(Opcode::Jump, 31, 25, 0, false, true)),
+            Some(Transfer::Call)
+        );
+        assert_eq!(classify(&jr_ra()), Some(Transfer::Return));
+        assert_eq!(classify(&Instruction::new(Opcode::Jump, 0, 25, 0, false, true)), None);
+        // JIALC links into `$ra`, and JIC through `$ra` returns.
+        assert_eq!(
+            classify(&Instruction::new(Opcode::JumpCompact, 31, 25, 0, false, true)),
+            Some(Transfer::Call)
+        );
+        assert_eq!(
+            classify(&Instruction::new(Opcode::JumpCompact, 0, 31, 0, false, true)),
+            Some(Transfer::Return)
+        );
+        assert_eq!(classify(&Instruction::new(Opcode::JumpCompact, 0, 25, 0, false, true)), None);
+        assert_eq!(classify(&nop()), None);
+    }
+
+    #[test]
+    fn test_call_stack() {
+        // 0x00: jal 0x10
+        // 0x04: nop (delay slot)
+        // 0x08: nop
+        // 0x0c: nop
+        // 0x10: nop
+        // 0x14: jr $ra
+        // 0x18: nop (delay slot)
+        let program =
+            Program::new(vec![jal(0x10), nop(), nop(), nop(), nop(), jr_ra(), nop()], 0, 0);
+        let mut profiler = Profiler {
+            program,
+            symbolizer: Symbolizer::empty(),
+            costs: HashMap::new(),
+            nodes: vec![StackNode { parent: ROOT, call_site: 0 }],
+            children: HashMap::new(),
+            stack: vec![ROOT],
+            pending: None,
+            samples: HashMap::new(),
+            total: Weights::default(),
+        };
+
+        for pc in [0x00, 0x04, 0x10, 0x14, 0x18, 0x08] {
+            profiler.record(pc);
+        }
+
+        assert_eq!(profiler.total().cycles, 6);
+        assert_eq!(profiler.stack, vec![ROOT]);
+        // The callee and its delay slot are attributed to the frame entered from 0x00.
+        let callee = profiler.children[&(ROOT, 0x00)];
+        for pc in [0x10, 0x14, 0x18] {
+            assert_eq!(profiler.samples[&(callee, pc)].cycles, 1);
+        }
+        for pc in [0x00, 0x04, 0x08] {
+            assert_eq!(profiler.samples[&(ROOT, pc)].cycles, 1);
+        }
+    }
+}
@@ -0,0 +1,143 @@
+//! A minimal subset of the pprof `profile.proto` schema.
+//!
+//! See <https://github.com/google/pprof/blob/main/proto/profile.proto> for the full definition.
+//! Only the messages needed to describe symbolized call stacks are declared here.
+
+use hashbrown::HashMap;
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Profile {
+    #[prost(message, repeated, tag = "1")]
+    pub sample_type: Vec<ValueType>,
+    #[prost(message, repeated, tag = "2")]
+    pub sample: Vec<Sample>,
+    #[prost(message, repeated, tag = "4")]
+    pub location: Vec<Location>,
+    #[prost(message, repeated, tag = "5")]
+    pub function: Vec<Function>,
+    #[prost(string, repeated, tag = "6")]
+    pub string_table: Vec<String>,
+    #[prost(message, optional, tag = "11")]
+    pub period_type: Option<ValueType>,
+    #[prost(int64, tag = "12")]
+    pub period: i64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ValueType {
+    #[prost(int64, tag = "1")]
+    pub r#type: i64,
+    #[prost(int64, tag = "2")]
+    pub unit: i64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Sample {
+    /// The leaf location comes first.
+    #[prost(uint64, repeated, tag = "1")]
+    pub location_id: Vec<u64>,
+    #[prost(int64, repeated, tag = "2")]
+    pub value: Vec<i64>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Location {
+    #[prost(uint64, tag = "1")]
+    pub id: u64,
+    #[prost(uint64, tag = "3")]
+    pub address: u64,
+    /// The innermost inlined function comes first.
+    #[prost(message, repeated, tag = "4")]
+    pub line: Vec<Line>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Line {
+    #[prost(uint64, tag = "1")]
+    pub function_id: u64,
+    #[prost(int64, tag = "2")]
+    pub line: i64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Function {
+    #[prost(uint64, tag = "1")]
+    pub id: u64,
+    #[prost(int64, tag = "2")]
+    pub name: i64,
+    #[prost(int64, tag = "3")]
+    pub system_name: i64,
+    #[prost(int64, tag = "4")]
+    pub filename: i64,
+}
+
+/// Interns strings, functions and locations while a [`Profile`] is being assembled.
+#[derive(Default)]
+pub(crate) struct ProfileBuilder {
+    profile: Profile,
+    strings: HashMap<String, i64>,
+    functions: HashMap<(String, Option<String>), u64>,
+    locations: HashMap<u32, u64>,
+}
+
+impl ProfileBuilder {
+    pub(crate) fn new(sample_types: &[(&str, &str)]) -> Self {
+        let mut builder = Self::default();
+        // The first entry of the string table must be the empty string.
+        builder.string("");
+        for (ty, unit) in sample_types {
+            let value_type = ValueType { r#type: builder.string(ty), unit: builder.string(unit) };
+            builder.profile.sample_type.push(value_type);
+        }
+        builder.profile.period_type = builder.profile.sample_type.first().cloned();
+        builder.profile.period = 1;
+        builder
+    }
+
+    pub(crate) fn string(&mut self, s: &str) -> i64 {
+        if let Some(id) = self.strings.get(s) {
+            return *id;
+        }
+        let id = self.profile.string_table.len() as i64;
+        self.profile.string_table.push(s.to_string());
+        self.strings.insert(s.to_string(), id);
+        id
+    }
+
+    pub(crate) fn function(&mut self, name: &str, file: Option<&str>) -> u64 {
+        let key = (name.to_string(), file.map(str::to_string));
+        if let Some(id) = self.functions.get(&key) {
+            return *id;
+        }
+        let id = self.profile.function.len() as u64 + 1;
+        let name = self.string(name);
+        let filename = file.map_or(0, |file| self.string(file));
+        self.profile.function.push(Function { id, name, system_name: name, filename });
+        self.functions.insert(key, id);
+        id
+    }
+
+    /// Returns the location of `address`, creating it from `lines` if it doesn't exist yet.
+    pub(crate) fn location(
+        &mut self,
+        address: u32,
+        lines: impl FnOnce(&mut Self) -> Vec<Line>,
+    ) -> u64 {
+        if let Some(id) = self.locations.get(&address) {
+            return *id;
+        }
+        let id = self.profile.location.len() as u64 + 1;
+        let line = lines(self);
+        self.profile.location.push(Location { id, address: address as u64, line });
+        self.locations.insert(address, id);
+        id
+    }
+
+    pub(crate) fn sample(&mut self, location_id: Vec<u64>, value: Vec<i64>) {
+        self.profile.sample.push(Sample { location_id, value });
+    }
+
+    pub(crate) fn build(self) -> Profile {
+        self.profile
+    }
+}
@@ -0,0 +1,172 @@
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use elf::{abi::STT_FUNC, endian::LittleEndian, ElfBytes};
+use hashbrown::HashMap;
+
+type DwarfReader = gimli::EndianArcSlice<gimli::LittleEndian>;
+
+/// A function symbol from the ELF symbol table.
+#[derive(Debug, Clone)]
+pub struct FunctionSymbol {
+    /// The start address of the function.
+    pub start: u32,
+    /// The size of the function in bytes. Zero if the symbol table did not record it.
+    pub size: u32,
+    /// The demangled name of the function.
+    pub name: String,
+}
+
+/// A single symbolized frame. A program counter may resolve to several frames when functions
+/// were inlined into each other.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SymbolizedFrame {
+    /// The demangled function name.
+    pub function: String,
+    /// The source file, if DWARF info is available.
+    pub file: Option<String>,
+    /// The source line, if DWARF info is available.
+    pub line: Option<u32>,
+}
+
+/// Resolves guest program counters to function names using the ELF symbol table and, if present,
+/// the DWARF debug info.
+pub struct Symbolizer {
+    /// The function symbols, sorted by start address.
+    functions: Vec<FunctionSymbol>,
+    /// The DWARF context, used to expand inlined frames and attach source locations.
+    dwarf: Option<addr2line::Context<DwarfReader>>,
+    /// A cache of previously symbolized program counters.
+    cache: HashMap<u32, Arc<[SymbolizedFrame]>>,
+}
+
+impl Symbolizer {
+    /// Build a symbolizer from the bytes of a guest ELF.
+    pub fn new(elf_code: &[u8]) -> Result<Self> {
+        let elf = ElfBytes::<LittleEndian>::minimal_parse(elf_code)
+            .map_err(|err| anyhow!("Elf parse error: {err}"))?;
+
+        let mut functions = Vec::new();
+        if let Some((symbols, strings)) =
+            elf.symbol_table().map_err(|err| anyhow!("failed to read symbol table: {err}"))?
+        {
+            for symbol in symbols.iter().filter(|s| s.st_symtype() == STT_FUNC && s.st_value != 0) {
+                let Ok(name) = strings.get(symbol.st_name as usize) else {
+                    continue;
+                };
+                functions.push(FunctionSymbol {
+                    start: symbol.st_value as u32,
+                    size: symbol.st_size as u32,
+                    name: rustc_demangle::demangle(name).to_string(),
+                });
+            }
+        }
+        functions.sort_by_key(|f| f.start);
+        functions.dedup_by_key(|f| f.start);
+
+        let dwarf = Self::load_dwarf(&elf)
+            .ok()
+            .and_then(|dwarf| addr2line::Context::from_dwarf(dwarf).ok());
+
+        Ok(Self { functions, dwarf, cache: HashMap::new() })
+    }
+
+    /// A symbolizer without any symbols, for tests.
+    #[cfg(test)]
+    pub(crate) fn empty() -> Self {
+        Self { functions: Vec::new(), dwarf: None, cache: HashMap::new() }
+    }
+
+    fn load_dwarf(elf: &ElfBytes<LittleEndian>) -> Result<gimli::Dwarf<DwarfReader>> {
+        gimli::Dwarf::load(|id| -> Result<DwarfReader> {
+            let data = match elf.section_header_by_name(id.name())? {
+                Some(header) => {
+                    let (data, compression) = elf.section_data(&header)?;
+                    if compression.is_some() {
+                        // Compressed debug sections are not supported, fall back to the symbol
+                        // table.
+                        &[][..]
+                    } else {
+                        data
+                    }
+                }
+                None => &[][..],
+            };
+            Ok(gimli::EndianArcSlice::new(Arc::from(data), gimli::LittleEndian))
+        })
+    }
+
+    /// Returns the function symbols, sorted by start address.
+    pub fn functions(&self) -> &[FunctionSymbol] {
+        &self.functions
+    }
+
+    /// Returns the function symbol containing the given program counter.
+    pub fn function(&self, pc: u32) -> Option<&FunctionSymbol> {
+        let idx = self.functions.partition_point(|f| f.start <= pc).checked_sub(1)?;
+        let function = &self.functions[idx];
+        // `pc >= function.start`, and the end of a function may not fit in a `u32`.
+        (function.size == 0 || pc - function.start < function.size).then_some(function)
+    }
+
+    /// Symbolize a program counter, returning the frames from the outermost to the innermost
+    /// inlined function.
+    pub fn symbolize(&mut self, pc: u32) -> Arc<[SymbolizedFrame]> {
+        if let Some(frames) = self.cache.get(&pc) {
+            return frames.clone();
+        }
+
+        let mut frames = self.dwarf_frames(pc).unwrap_or_default();
+        if frames.is_empty() {
+            let function = self
+                .function(pc)
+                .map_or_else(|| format!("[unknown 0x{pc:08x}]"), |f| f.name.clone());
+            frames.push(SymbolizedFrame { function, file: None, line: None });
+        }
+
+        let frames: Arc<[SymbolizedFrame]> = frames.into();
+        self.cache.insert(pc, frames.clone());
+        frames
+    }
+
+    fn dwarf_frames(&self, pc: u32) -> Option<Vec<SymbolizedFrame>> {
+        let dwarf = self.dwarf.as_ref()?;
+        let mut iter = dwarf.find_frames(pc as u64).skip_all_loads().ok()?;
+
+        let mut frames = Vec::new();
+        while let Ok(Some(frame)) = iter.next() {
+            let Some(function) = frame.function.as_ref().and_then(|f| f.demangle().ok()) else {
+                continue;
+            };
+            frames.push(SymbolizedFrame {
+                function: function.into_owned(),
+                file: frame.location.as_ref().and_then(|l| l.file).map(str::to_string),
+                line: frame.location.as_ref().and_then(|l| l.line),
+            });
+        }
+
+        // `find_frames` yields the innermost frame first.
+        frames.reverse();
+        Some(frames)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_function_at_top_of_address_space() {
+        let mut symbolizer = Symbolizer::empty();
+        symbolizer.functions = vec![
+            FunctionSymbol { start: 0x100, size: 0x10, name: "low".to_string() },
+            FunctionSymbol { start: 0xffff_fff0, size: 0x10, name: "high".to_string() },
+        ];
+
+        assert_eq!(symbolizer.function(0x10c).unwrap().name, "low");
+        assert!(symbolizer.function(0x110).is_none());
+        assert!(symbolizer.function(0xffff_ffef).is_none());
+        assert_eq!(symbolizer.function(0xffff_fff0).unwrap().name, "high");
+        assert_eq!(symbolizer.function(u32::MAX).unwrap().name, "high");
+    }
+}
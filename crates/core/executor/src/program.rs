@@ -4,7 +4,8 @@ extern crate alloc;
 
 use alloc::collections::BTreeMap;
 use anyhow::{anyhow, bail, Context, Result};
-use elf::{endian::LittleEndian, file::Class, ElfBytes};
+use elf::{endian::LittleEndian, file::Class, segment::ProgramHeader, ElfBytes};
+use std::ops::Range;
 use std::str::FromStr;
 
 use p3_field::Field;
@@ -12,7 +13,7 @@ use p3_field::FieldExtensionAlgebra;
 use p3_field::PrimeField32;
 use p3_maybe_rayon::prelude::IntoParallelIterator;
 use p3_maybe_rayon::prelude::IntoParallelRefIterator;
-use p3_maybe_rayon::prelude::{ParallelBridge, ParallelIterator};
+use p3_maybe_rayon::prelude::{IndexedParallelIterator, ParallelBridge, ParallelIterator};
 use serde::{Deserialize, Serialize};
 use zkm_stark::air::{MachineAir, MachineProgram};
 use zkm_stark::septic_curve::{SepticCurve, SepticCurveComplete};
@@ -21,13 +22,42 @@ use zkm_stark::septic_extension::SepticExtension;
 use zkm_stark::shape::Shape;
 use zkm_stark::LookupKind;
 
-use crate::{Instruction, MipsAirId, Register};
+use crate::{ExecutionState, Instruction, IsaRevision, MipsAirId, Register};
 
 pub const MAX_MEMORY: usize = 0x7F000000;
 pub const MAX_CODE_MEMORY: usize = 0x3F000000;
 pub const INIT_SP: u32 = MAX_MEMORY as u32 - 0x4000;
 pub const WORD_SIZE: usize = core::mem::size_of::<u32>();
 
+/// The address at which position-independent (`ET_DYN`) executables are loaded.
+pub const PIE_LOAD_BASE: u32 = 0x0040_0000;
+/// The offset of the thread pointer past the start of the TLS block, as in the MIPS TLS ABI.
+pub const TLS_TP_OFFSET: u32 = 0x7000;
+/// The size of the thread control block placed right before the TLS block.
+const TLS_TCB_SIZE: u32 = 8;
+
+const DT_NULL: u32 = 0;
+const DT_PLTGOT: u32 = 3;
+const DT_SYMTAB: u32 = 6;
+const DT_RELA: u32 = 7;
+const DT_SYMENT: u32 = 11;
+const DT_REL: u32 = 17;
+const DT_RELSZ: u32 = 18;
+const DT_RELENT: u32 = 19;
+const DT_TEXTREL: u32 = 22;
+const DT_MIPS_LOCAL_GOTNO: u32 = 0x7000_000a;
+const DT_MIPS_SYMTABNO: u32 = 0x7000_0011;
+const DT_MIPS_GOTSYM: u32 = 0x7000_0013;
+
+const R_MIPS_NONE: u32 = 0;
+const R_MIPS_REL32: u32 = 3;
+
+const SHN_UNDEF: u32 = 0;
+const SHN_ABS: u32 = 0xfff1;
+const STB_WEAK: u32 = 2;
+/// The flag of the second GOT entry marking it as reserved for the GNU dynamic linker.
+const MIPS_GNU_GOT1_MASK: u32 = 0x8000_0000;
+
 /// A program that can be executed by the ZKM.
 #[derive(PartialEq, Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Program {
@@ -61,17 +91,22 @@ impl Program {
         if elf.ehdr.e_machine != elf::abi::EM_MIPS {
             bail!("Invalid machine type, must be MIPS");
         }
-        if elf.ehdr.e_type != elf::abi::ET_EXEC {
-            bail!("Invalid ELF type, must be executable");
-        }
+        // Position-independent executables, such as static-pie binaries, are linked at address 0
+        // and loaded at a fixed bias.
+        let bias = match elf.ehdr.e_type {
+            elf::abi::ET_EXEC => 0,
+            elf::abi::ET_DYN => PIE_LOAD_BASE,
+            _ => bail!("Invalid ELF type, must be executable or position-independent executable"),
+        };
 
         let mut patch_list: BTreeMap<u32, u32> = BTreeMap::new();
-        patch_elf(&elf, &mut patch_list);
+        patch_elf(&elf, bias, &mut patch_list);
         let entry: u32 = elf
             .ehdr
             .e_entry
             .try_into()
             .map_err(|err| anyhow!("e_entry was larger than 32 bits. {err}"))?;
+        let entry = entry.checked_add(bias).context("Invalid entrypoint")?;
         if entry >= max_mem || !entry.is_multiple_of(WORD_SIZE as u32) {
             bail!("Invalid entrypoint");
         }
@@ -82,6 +117,7 @@ impl Program {
 
         let mut instructions: Vec<u32> = Vec::new();
         let mut base_address = u32::MAX;
+        let mut code_ranges: Vec<Range<u32>> = Vec::new();
 
         let mut hiaddr = 0u32;
 
@@ -104,11 +140,13 @@ impl Program {
                 .p_vaddr
                 .try_into()
                 .map_err(|err| anyhow!("vaddr is larger than 32 bits. {err}"))?;
+            let vaddr = vaddr.checked_add(bias).context("Invalid segment vaddr")?;
             if !vaddr.is_multiple_of(WORD_SIZE as u32) {
                 bail!("vaddr {vaddr:08x} is unaligned");
             }
-            if (segment.p_flags & elf::abi::PF_X) != 0 && base_address > vaddr {
-                base_address = vaddr;
+            if (segment.p_flags & elf::abi::PF_X) != 0 {
+                base_address = base_address.min(vaddr);
+                code_ranges.push(vaddr..vaddr.saturating_add(file_size));
             }
 
             let offset: u32 = segment
@@ -148,14 +186,37 @@ impl Program {
             }
         }
 
+        if bias != 0 {
+            if let Some(dynamic) = segments.iter().find(|x| x.p_type == elf::abi::PT_DYNAMIC) {
+                let dynamic = u32::try_from(dynamic.p_vaddr)
+                    .ok()
+                    .and_then(|vaddr| vaddr.checked_add(bias))
+                    .context("Invalid dynamic segment vaddr")?;
+                relocate(&mut image, dynamic, bias, &code_ranges)?;
+            }
+        }
+
+        if let Some(tls) = segments.iter().find(|x| x.p_type == elf::abi::PT_TLS) {
+            let (tp, tls_end) = load_tls(&mut image, &tls, bias, hiaddr)?;
+            image.insert(Register::LOCAL_USER as u32, tp);
+            hiaddr = tls_end;
+        }
+
         image.insert(Register::BRK as u32, hiaddr); // $brk
         image.insert(Register::HEAP as u32, 0x20000000); // $heap
 
         patch_stack(&mut image);
 
-        // decode each instruction
-        let instructions: Vec<_> =
-            instructions.par_iter().map(|inst| Instruction::decode_from(*inst).unwrap()).collect();
+        // decode each instruction at the address it is fetched from
+        let isa = IsaRevision::from_elf_flags(elf.ehdr.e_flags);
+        let instructions: Vec<_> = instructions
+            .par_iter()
+            .enumerate()
+            .map(|(i, inst)| {
+                let pc = base_address.wrapping_add((i * WORD_SIZE) as u32);
+                Instruction::decode_from(*inst, pc, isa).unwrap()
+            })
+            .collect();
 
         Ok(Program {
             instructions,
@@ -167,6 +228,41 @@ impl Program {
         })
     }
 
+    /// Resume the program from a snapshot of its execution, see [`crate::SnapshotTrigger`].
+    ///
+    /// The resumed program starts at the program counter of the snapshot, and its initial memory
+    /// image is the memory of the snapshot, registers included. Both are committed to by the
+    /// verifying key of the resumed program, which therefore identifies the snapshot.
+    pub fn resume_from(&self, snapshot: &ExecutionState) -> Result<Program> {
+        if !snapshot.is_resumable() {
+            bail!(
+                "Cannot resume from pc 0x{:08x} with next pc 0x{:08x}",
+                snapshot.pc,
+                snapshot.next_pc
+            );
+        }
+        if snapshot.pc.wrapping_sub(self.pc_base) >= (self.instructions.len() * WORD_SIZE) as u32 {
+            bail!("Snapshot pc 0x{:08x} is outside of the program", snapshot.pc);
+        }
+
+        // The register `$zero` is initialized by the memory chips, outside of the image.
+        let image = snapshot
+            .memory
+            .clone()
+            .into_iter()
+            .filter(|&(addr, _)| addr != 0)
+            .map(|(addr, record)| (addr, record.value))
+            .collect();
+        Ok(Program {
+            instructions: self.instructions.clone(),
+            pc_start: snapshot.pc,
+            pc_base: self.pc_base,
+            next_pc: snapshot.next_pc,
+            image,
+            preprocessed_shape: None,
+        })
+    }
+
     /// Custom logic for padding the trace to a power of two according to the proof shape.
     pub fn fixed_log2_rows<F: Field, A: MachineAir<F>>(&self, air: &A) -> Option<usize> {
         let id = MipsAirId::from_str(&air.name()).unwrap();
@@ -186,7 +282,9 @@ impl Program {
     }
 }
 
-pub fn patch_elf(f: &elf::ElfBytes<LittleEndian>, patch_list: &mut BTreeMap<u32, u32>) {
+/// Patch the runtime functions of the program that cannot run in the zkVM, given the load bias
+/// of the program.
+pub fn patch_elf(f: &elf::ElfBytes<LittleEndian>, bias: u32, patch_list: &mut BTreeMap<u32, u32>) {
     let symbols = f
         .symbol_table()
         .expect("failed to read symbols table, cannot patch program")
@@ -215,35 +313,35 @@ pub fn patch_elf(f: &elf::ElfBytes<LittleEndian>, patch_list: &mut BTreeMap<u32,
                 | "_dl_discover_osversion"
                 | "internal/runtime/exithook.Run" => {
                     patch_list.insert(
-                        symbol.st_value as u32,
+                        (symbol.st_value as u32).wrapping_add(bias),
                         0x03e00008, // jalr $ra, $zero
                     );
                     patch_list.insert(
-                        (symbol.st_value + 4) as u32,
+                        (symbol.st_value as u32).wrapping_add(bias) + 4,
                         0x0, // nop
                     );
                 }
 
                 "runtime.exit" => {
-                    exit_old = symbol.st_value as u32;
+                    exit_old = (symbol.st_value as u32).wrapping_add(bias);
                 }
                 "runtime.MemProfileRate" => {
                     patch_list.insert(
-                        symbol.st_value as u32,
+                        (symbol.st_value as u32).wrapping_add(bias),
                         0x0, // nop
                     );
                 }
                 "zkvm.RuntimeExit" => {
-                    exit_new = symbol.st_value as u32;
+                    exit_new = (symbol.st_value as u32).wrapping_add(bias);
                 }
                 _ => {
                     if name.contains("sys_common") && name.contains("thread_info") {
                         patch_list.insert(
-                            symbol.st_value as u32,
+                            (symbol.st_value as u32).wrapping_add(bias),
                             0x03e00008, // jalr $ra, $zero
                         );
                         patch_list.insert(
-                            (symbol.st_value + 4) as u32,
+                            (symbol.st_value as u32).wrapping_add(bias) + 4,
                             0x0, // nop
                         );
                     }
@@ -325,6 +423,171 @@ pub fn patch_stack(image: &mut BTreeMap<u32, u32>) {
     store_mem(cur_sp, 0x00); // auxv[term] = 0
 }
 
+/// Apply the dynamic relocations of a position-independent executable loaded at `bias`, whose
+/// dynamic section starts at `dynamic`.
+///
+/// The `R_MIPS_REL32` relocations and the GOT are processed like the dynamic linker would, with
+/// the global GOT entries resolved to the symbols of the executable itself. Afterwards, the size
+/// of the relocation table and of the GOT are cleared in the dynamic section, so that the
+/// self-relocation code in the startup files of a static-pie libc finds nothing left to do.
+fn relocate(
+    image: &mut BTreeMap<u32, u32>,
+    dynamic: u32,
+    bias: u32,
+    code_ranges: &[Range<u32>],
+) -> Result<()> {
+    let word = |image: &BTreeMap<u32, u32>, addr: u32| {
+        image.get(&addr).copied().with_context(|| format!("Address [0x{addr:08x}] is not loaded"))
+    };
+
+    // The tags of the dynamic section, with the address and the value of their entries.
+    let mut tags: BTreeMap<u32, (u32, u32)> = BTreeMap::new();
+    for addr in (dynamic..u32::MAX).step_by(8) {
+        let tag = word(image, addr)?;
+        if tag == DT_NULL {
+            break;
+        }
+        tags.insert(tag, (addr + 4, word(image, addr + 4)?));
+    }
+    let tag = |tag: u32| tags.get(&tag).map(|&(_, value)| value);
+    if tags.contains_key(&DT_RELA) {
+        bail!("RELA relocations are not supported");
+    }
+    if tags.contains_key(&DT_TEXTREL) {
+        bail!("Text relocations are not supported");
+    }
+
+    // Resolve a dynamic symbol to its load address.
+    let symtab = tag(DT_SYMTAB).map(|symtab| symtab.wrapping_add(bias));
+    let syment = tag(DT_SYMENT).unwrap_or(16);
+    let resolve = |image: &BTreeMap<u32, u32>, index: u32| -> Result<u32> {
+        let sym = symtab
+            .zip(index.checked_mul(syment))
+            .and_then(|(symtab, offset)| symtab.checked_add(offset))
+            .with_context(|| format!("Invalid dynamic symbol {index}"))?;
+        let value = word(image, sym + 4)?;
+        // st_info, st_other and st_shndx.
+        let info = word(image, sym + 12)?;
+        match info >> 16 {
+            SHN_UNDEF if (info & 0xff) >> 4 == STB_WEAK => Ok(0),
+            SHN_UNDEF => bail!("Undefined dynamic symbol {index}"),
+            SHN_ABS => Ok(value),
+            _ => Ok(value.wrapping_add(bias)),
+        }
+    };
+
+    if let Some(rel) = tag(DT_REL) {
+        let rel = rel.wrapping_add(bias);
+        let relent = tag(DT_RELENT).unwrap_or(8);
+        if relent < 8 {
+            bail!("Invalid relocation entry size {relent}");
+        }
+        for offset in (0..tag(DT_RELSZ).unwrap_or(0)).step_by(relent as usize) {
+            let entry = rel.checked_add(offset).context("Invalid relocation table")?;
+            let r_info = word(image, entry + 4)?;
+            let addr = word(image, entry)?.wrapping_add(bias);
+            match r_info & 0xff {
+                R_MIPS_NONE => {}
+                R_MIPS_REL32 => {
+                    if code_ranges.iter().any(|range| range.contains(&addr)) {
+                        bail!("Text relocations are not supported");
+                    }
+                    let sym = r_info >> 8;
+                    let value = if sym == 0 { bias } else { resolve(image, sym)? };
+                    let relocated = word(image, addr)?.wrapping_add(value);
+                    image.insert(addr, relocated);
+                }
+                ty => bail!("Unsupported relocation type {ty} at [0x{addr:08x}]"),
+            }
+        }
+    }
+
+    let gotsym = tag(DT_MIPS_GOTSYM).unwrap_or(0);
+    if let (Some(got), Some(local_gotno)) = (tag(DT_PLTGOT), tag(DT_MIPS_LOCAL_GOTNO)) {
+        let got = got.wrapping_add(bias);
+        let entry = |i: u32| {
+            i.checked_mul(WORD_SIZE as u32)
+                .and_then(|offset| got.checked_add(offset))
+                .context("Invalid GOT entry")
+        };
+        // The first entry, and the second one if it is marked, are reserved for the dynamic
+        // linker. The other local entries hold link-time addresses.
+        let reserved = if local_gotno > 1 && word(image, entry(1)?)? & MIPS_GNU_GOT1_MASK != 0 {
+            2
+        } else {
+            1
+        };
+        for i in reserved..local_gotno {
+            let addr = entry(i)?;
+            let relocated = word(image, addr)?.wrapping_add(bias);
+            image.insert(addr, relocated);
+        }
+        // The global entries hold the addresses of the dynamic symbols from `gotsym` on.
+        for index in gotsym..tag(DT_MIPS_SYMTABNO).unwrap_or(0) {
+            let addr = entry(local_gotno + index - gotsym)?;
+            let value = resolve(image, index)?;
+            image.insert(addr, value);
+        }
+    }
+
+    for (tag, value) in [(DT_RELSZ, 0), (DT_MIPS_LOCAL_GOTNO, 0), (DT_MIPS_SYMTABNO, gotsym)] {
+        if let Some(&(addr, _)) = tags.get(&tag) {
+            image.insert(addr, value);
+        }
+    }
+    Ok(())
+}
+
+/// Set up the thread-local storage of the initial thread from the `PT_TLS` segment.
+///
+/// The TLS block is placed after `hiaddr`, the highest loaded address, and preceded by the
+/// thread control block. It starts with the initialization image, as relocated in the loaded
+/// segments, followed by zeros. Returns the thread pointer, [`TLS_TP_OFFSET`] bytes past the
+/// start of the block, and the new highest loaded address.
+fn load_tls(
+    image: &mut BTreeMap<u32, u32>,
+    tls: &ProgramHeader,
+    bias: u32,
+    hiaddr: u32,
+) -> Result<(u32, u32)> {
+    let field = |value: u64, name: &str| {
+        u32::try_from(value).map_err(|err| anyhow!("TLS {name} is larger than 32 bits. {err}"))
+    };
+    let vaddr = field(tls.p_vaddr, "vaddr")?.checked_add(bias).context("Invalid TLS vaddr")?;
+    let file_size = field(tls.p_filesz, "file_size")?;
+    let mem_size = field(tls.p_memsz, "mem_size")?;
+    let align = field(tls.p_align, "alignment")?.max(WORD_SIZE as u32);
+    if !align.is_power_of_two() || !vaddr.is_multiple_of(WORD_SIZE as u32) || file_size > mem_size {
+        bail!("Invalid TLS segment");
+    }
+
+    let start = hiaddr
+        .checked_add(WORD_SIZE as u32 + TLS_TCB_SIZE + align - 1)
+        .map(|addr| addr & !(align - 1))
+        .context("Invalid TLS segment")?;
+    let end = start
+        .checked_add(mem_size)
+        .filter(|&end| end <= MAX_CODE_MEMORY as u32)
+        .context("TLS segment exceeds maximum address for guest programs")?;
+
+    for addr in (start - TLS_TCB_SIZE..start).step_by(WORD_SIZE) {
+        image.insert(addr, 0);
+    }
+    for i in (0..mem_size).step_by(WORD_SIZE) {
+        let mut word = 0;
+        if i < file_size {
+            let addr = vaddr.checked_add(i).context("Invalid TLS vaddr")?;
+            word = image.get(&addr).copied().context("TLS initialization image is not loaded")?;
+            if file_size - i < WORD_SIZE as u32 {
+                word &= (1 << (8 * (file_size - i))) - 1;
+            }
+        }
+        image.insert(start + i, word);
+    }
+
+    Ok((start + TLS_TP_OFFSET, end.next_multiple_of(WORD_SIZE as u32) - WORD_SIZE as u32))
+}
+
 impl<F: PrimeField32> MachineProgram<F> for Program {
     fn pc_start(&self) -> F {
         F::from_canonical_u32(self.pc_start)
@@ -357,3 +620,116 @@ impl<F: PrimeField32> MachineProgram<F> for Program {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, process::Command};
+
+    use super::*;
+    use crate::{Executor, Opcode, ZKMCoreOpts};
+
+    /// Build a static-pie ELF linked at address 0, whose data segment holds a dynamic section, a
+    /// pointer relocated against the load address and one relocated against a symbol, a GOT, the
+    /// dynamic symbols and a TLS initialization image of 6 bytes.
+    fn pie_elf(symbol_reloc: u32) -> Vec<u8> {
+        let mut elf = vec![0u8; 0x1598];
+        let mut put = |offset: usize, words: &[u32]| {
+            for (i, word) in words.iter().enumerate() {
+                elf[offset + 4 * i..offset + 4 * i + 4].copy_from_slice(&word.to_le_bytes());
+            }
+        };
+        // The ELF header: ET_DYN for EM_MIPS, entry 0x100, 4 program and 3 section headers.
+        put(0x00, &[0x464c457f, 0x00010101, 0, 0, 0x0008_0003, 1, 0x100, 0x34, 0x1520, 0]);
+        put(0x28, &[0x0020_0034, 0x0028_0004, 0x0000_0003]);
+        // The program headers: text, data, dynamic and TLS segments.
+        put(0x34, &[elf::abi::PT_LOAD, 0, 0, 0, 0x200, 0x200, 5, 0x1000]);
+        put(0x54, &[elf::abi::PT_LOAD, 0x1000, 0x1000, 0x1000, 0x410, 0x420, 6, 0x1000]);
+        put(0x74, &[elf::abi::PT_DYNAMIC, 0x1000, 0x1000, 0x1000, 80, 80, 6, 4]);
+        put(0x94, &[elf::abi::PT_TLS, 0x1408, 0x1408, 0x1408, 6, 16, 4, 8]);
+        // rdhwr $3, $29
+        put(0x100, &[0x7c03e83b]);
+        let dynamic = [
+            (DT_REL, 0x1100),
+            (DT_RELSZ, 16),
+            (DT_RELENT, 8),
+            (DT_PLTGOT, 0x1200),
+            (DT_MIPS_LOCAL_GOTNO, 3),
+            (DT_SYMTAB, 0x1300),
+            (DT_SYMENT, 16),
+            (DT_MIPS_GOTSYM, 1),
+            (DT_MIPS_SYMTABNO, 3),
+            (DT_NULL, 0),
+        ];
+        put(0x1000, &dynamic.iter().flat_map(|&(tag, value)| [tag, value]).collect::<Vec<_>>());
+        put(0x1100, &[0x1400, R_MIPS_REL32, 0x1404, 1 << 8 | symbol_reloc]);
+        put(0x1200, &[0, MIPS_GNU_GOT1_MASK, 0x1400, 0, 0]);
+        // A defined global symbol and an undefined weak one.
+        put(0x1310, &[0, 0x1408, 4, 0x0002_0011]);
+        put(0x1320, &[0, 0, 0, 0x0000_0020]);
+        put(0x1400, &[0x1408, 4, 0x11223344, 0x88775566]);
+        // The section headers, with an empty symbol table and its string table.
+        put(0x1548, &[0, elf::abi::SHT_SYMTAB, 0, 0, 0x1500, 16, 2, 0, 4, 16]);
+        put(0x1570, &[0, elf::abi::SHT_STRTAB, 0, 0, 0x1510, 1, 0, 0, 1, 0]);
+        elf
+    }
+
+    #[test]
+    fn test_pie_relocations_and_tls() {
+        let program = Program::from(&pie_elf(R_MIPS_REL32)).unwrap();
+        let base = PIE_LOAD_BASE;
+        assert_eq!(program.pc_start, base + 0x100);
+        assert_eq!(program.pc_base, base);
+        assert_eq!(
+            program.fetch(base + 0x100),
+            Instruction::new(Opcode::ADD, 3, Register::LOCAL_USER as u32, 0, false, true)
+        );
+
+        let words = |addr: u32, len: u32| {
+            (addr..addr + 4 * len).step_by(4).map(|a| program.image[&a]).collect::<Vec<_>>()
+        };
+        assert_eq!(words(base + 0x1400, 2), [base + 0x1408, base + 0x140c]);
+        assert_eq!(
+            words(base + 0x1200, 5),
+            [0, MIPS_GNU_GOT1_MASK, base + 0x1400, base + 0x1408, 0]
+        );
+        // The relocation table and the GOT look empty to the startup code.
+        assert_eq!(words(base + 0x100c, 1), [0]);
+        assert_eq!(words(base + 0x1024, 1), [0]);
+        assert_eq!(words(base + 0x1044, 1), [1]);
+
+        // The TLS block follows the data segment and its thread control block, aligned to 8.
+        let tls = base + 0x1428;
+        assert_eq!(words(tls - TLS_TCB_SIZE, 6), [0, 0, 0x11223344, 0x5566, 0, 0]);
+        assert_eq!(program.image[&(Register::LOCAL_USER as u32)], tls + TLS_TP_OFFSET);
+        assert_eq!(program.image[&(Register::BRK as u32)], tls + 12);
+    }
+
+    #[test]
+    fn test_pie_unsupported_relocation() {
+        let err = Program::from(&pie_elf(2)).unwrap_err();
+        assert!(err.to_string().contains("Unsupported relocation type 2"));
+    }
+
+    /// Runs the static-pie program, which checks its relocated pointers and its thread-local
+    /// variables and halts with the failing line as exit code on a mismatch.
+    #[test]
+    #[ignore = "requires the mipsel-linux-gnu-gcc cross compiler"]
+    fn test_static_pie() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("linux-conformance");
+        let elf = std::env::temp_dir().join(format!("zkm-static-pie-{}", std::process::id()));
+        let status = Command::new("mipsel-linux-gnu-gcc")
+            .args(["-static-pie", "-fpie", "-nostdlib", "-ffreestanding", "-fno-builtin"])
+            .args(["-ftls-model=local-exec", "-msoft-float", "-march=mips32r2", "-O2", "-o"])
+            .arg(&elf)
+            .arg(dir.join("pie.c"))
+            .status()
+            .expect("failed to run mipsel-linux-gnu-gcc");
+        assert!(status.success());
+
+        let program = Program::from(&std::fs::read(&elf).unwrap()).unwrap();
+        std::fs::remove_file(&elf).unwrap();
+        assert!(program.pc_start >= PIE_LOAD_BASE);
+        let mut runtime = Executor::new(program, ZKMCoreOpts::default());
+        runtime.run().unwrap();
+    }
+}
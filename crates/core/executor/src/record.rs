@@ -14,9 +14,9 @@ use std::{mem::take, str::FromStr, sync::Arc};
 use crate::{
     events::{
         AluEvent, BranchEvent, ByteLookupEvent, ByteRecord, CompAluEvent, CpuEvent,
-        GlobalLookupEvent, JumpEvent, MemInstrEvent, MemoryInitializeFinalizeEvent,
-        MemoryLocalEvent, MemoryRecordEnum, MiscEvent, MovCondEvent, PrecompileEvent,
-        PrecompileEvents, SyscallEvent,
+        FpuAccessRecord, FpuEvent, GlobalLookupEvent, JumpEvent, MemInstrEvent,
+        MemoryInitializeFinalizeEvent, MemoryLocalEvent, MemoryRecordEnum, MiscEvent, MovCondEvent,
+        PrecompileEvent, PrecompileEvents, SyscallEvent,
     },
     syscalls::{precompiles::keccak::sponge::GENERAL_BLOCK_SIZE_U32S, SyscallCode},
     MipsAirId, Program,
@@ -58,6 +58,10 @@ pub struct ExecutionRecord {
     pub movcond_events: Vec<MovCondEvent>,
     /// A trace of the misc events.
     pub misc_events: Vec<MiscEvent>,
+    /// A trace of the rounded FPU operations.
+    pub fpu_arith_events: Vec<FpuEvent>,
+    /// A trace of the FPU compares, sign operations, and 64-bit loads and stores.
+    pub fpu_misc_events: Vec<FpuEvent>,
     /// A trace of the byte lookups that are needed.
     pub byte_lookups: HashMap<ByteLookupEvent, usize>,
     /// A trace of the precompile events.
@@ -76,6 +80,9 @@ pub struct ExecutionRecord {
     pub public_values: PublicValues<u32, u32>,
     /// The shape of the proof.
     pub shape: Option<Shape<MipsAirId>>,
+    /// The name and the log height of the custom precompile chip of a precompile shard, which is
+    /// not part of `shape` since it has no [`MipsAirId`].
+    pub custom_shape: Option<(String, usize)>,
     /// The predicted counts of the proof.
     pub counts: Option<EnumMap<MipsAirId, u64>>,
 }
@@ -142,28 +149,43 @@ impl ExecutionRecord {
                 SyscallCode::KECCAK_SPONGE => opts.keccak,
                 SyscallCode::SHA_EXTEND => opts.sha_extend,
                 SyscallCode::SHA_COMPRESS => opts.sha_compress,
-                _ => opts.deferred,
+                SyscallCode::BLAKE2S_ROUNDS => opts.blake2s,
+                SyscallCode::BLAKE3_ROUNDS => opts.blake3,
+                SyscallCode::BLAKE2B_ROUNDS => opts.blake2b,
+                SyscallCode::SECP256K1_MUL
+                | SyscallCode::SECP256R1_MUL
+                | SyscallCode::BN254_MUL
+                | SyscallCode::BLS12381_MUL => opts.weierstrass_mul,
+                SyscallCode::BIGINT_MULMOD => opts.bigint_mulmod,
+                code => code.custom_index().map_or(opts.deferred, |i| opts.custom_threshold(i)),
             };
 
             let mut shards_input = Vec::new();
-            let remainder = if syscall_code == SyscallCode::KECCAK_SPONGE {
+            let remainder = if syscall_code == SyscallCode::KECCAK_SPONGE
+                || syscall_code == SyscallCode::SYS_LINUX
+            {
                 let mut current_shard = Vec::new();
                 let mut current_len = 0;
 
                 for (syscall_event, event) in events {
-                    if let PrecompileEvent::KeccakSponge(event) = &event {
+                    let input_len = match &event {
                         // Here, input_len_u32s must be a multiple of GENERAL_BLOCK_SIZE_U32S.
-                        let input_len = event.input_len_u32s as usize / GENERAL_BLOCK_SIZE_U32S;
-
-                        if current_len + input_len > threshold && !current_shard.is_empty() {
-                            let mut record = ExecutionRecord::new(self.program.clone());
-                            record.precompile_events.insert(syscall_code, current_shard);
-                            shards_input.push(record);
-                            current_shard = Vec::new();
-                            current_len = 0;
+                        PrecompileEvent::KeccakSponge(event) => {
+                            event.input_len_u32s as usize / GENERAL_BLOCK_SIZE_U32S
                         }
-                        current_len += input_len;
+                        // `read` and `fstat64` use one extra row per buffer word.
+                        PrecompileEvent::Linux(event) => event.num_rows(),
+                        _ => 1,
+                    };
+
+                    if current_len + input_len > threshold && !current_shard.is_empty() {
+                        let mut record = ExecutionRecord::new(self.program.clone());
+                        record.precompile_events.insert(syscall_code, current_shard);
+                        shards_input.push(record);
+                        current_shard = Vec::new();
+                        current_len = 0;
                     }
+                    current_len += input_len;
                     current_shard.push((syscall_event, event));
                 }
 
@@ -258,9 +280,16 @@ impl ExecutionRecord {
     /// struct.
     pub fn fixed_log2_rows<F: PrimeField, A: MachineAir<F>>(&self, air: &A) -> Option<usize> {
         self.shape.as_ref().map(|shape| {
-            shape
-                .log2_height(&MipsAirId::from_str(&air.name()).unwrap())
-                .unwrap_or_else(|| panic!("Chip {} not found in specified shape", air.name()))
+            let name = air.name();
+            match MipsAirId::from_str(&name) {
+                Ok(id) => shape.log2_height(&id),
+                Err(_) => self
+                    .custom_shape
+                    .as_ref()
+                    .filter(|(custom_name, _)| *custom_name == name)
+                    .map(|(_, log2_height)| *log2_height),
+            }
+            .unwrap_or_else(|| panic!("Chip {name} not found in specified shape"))
         })
     }
 
@@ -291,6 +320,17 @@ impl ExecutionRecord {
         self.precompile_events.get_events(syscall_code).expect("Precompile events not found")
     }
 
+    /// Whether the custom precompile chip with the given name, proving the events of a custom
+    /// syscall code, is included in the shard.
+    #[must_use]
+    pub fn custom_chip_included(&self, name: &str, syscall_code: SyscallCode) -> bool {
+        if self.shape.is_some() {
+            self.custom_shape.as_ref().is_some_and(|(custom_name, _)| custom_name == name)
+        } else {
+            !self.get_precompile_events(syscall_code).is_empty()
+        }
+    }
+
     /// Get all the local memory events.
     #[inline]
     pub fn get_local_mem_events(&self) -> impl Iterator<Item = &MemoryLocalEvent> {
@@ -313,6 +353,8 @@ pub struct MemoryAccessRecord {
     pub hi: Option<MemoryRecordEnum>,
     /// The memory access of the `memory` register.
     pub memory: Option<MemoryRecordEnum>,
+    /// The accesses made on behalf of the FPU chips.
+    pub fpu: FpuAccessRecord,
 }
 
 impl MachineRecord for ExecutionRecord {
@@ -333,6 +375,8 @@ impl MachineRecord for ExecutionRecord {
         stats.insert("branch_events".to_string(), self.branch_events.len());
         stats.insert("jump_events".to_string(), self.jump_events.len());
         stats.insert("misc_events".to_string(), self.misc_events.len());
+        stats.insert("fpu_arith_events".to_string(), self.fpu_arith_events.len());
+        stats.insert("fpu_misc_events".to_string(), self.fpu_misc_events.len());
 
         for (syscall_code, events) in self.precompile_events.iter() {
             stats.insert(format!("syscall {syscall_code:?}"), events.len());
@@ -369,6 +413,8 @@ impl MachineRecord for ExecutionRecord {
         self.branch_events.append(&mut other.branch_events);
         self.jump_events.append(&mut other.jump_events);
         self.misc_events.append(&mut other.misc_events);
+        self.fpu_arith_events.append(&mut other.fpu_arith_events);
+        self.fpu_misc_events.append(&mut other.fpu_misc_events);
         self.syscall_events.append(&mut other.syscall_events);
 
         self.precompile_events.append(&mut other.precompile_events);
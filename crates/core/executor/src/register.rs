@@ -1,6 +1,6 @@
 //! Registers for the Ziren zkVM.
 
-pub const NUM_REGISTERS: usize = 36;
+pub const NUM_REGISTERS: usize = 78;
 /// A register stores a 32-bit value used by operations.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Register {
@@ -40,6 +40,52 @@ pub enum Register {
     HI = 33,
     BRK = 34,
     HEAP = 35,
+    /// The `UserLocal` hardware register read by `rdhwr $29`, which holds the thread pointer.
+    LOCAL_USER = 36,
+    /// The floating point registers of coprocessor 1.
+    F0 = 37,
+    F1 = 38,
+    F2 = 39,
+    F3 = 40,
+    F4 = 41,
+    F5 = 42,
+    F6 = 43,
+    F7 = 44,
+    F8 = 45,
+    F9 = 46,
+    F10 = 47,
+    F11 = 48,
+    F12 = 49,
+    F13 = 50,
+    F14 = 51,
+    F15 = 52,
+    F16 = 53,
+    F17 = 54,
+    F18 = 55,
+    F19 = 56,
+    F20 = 57,
+    F21 = 58,
+    F22 = 59,
+    F23 = 60,
+    F24 = 61,
+    F25 = 62,
+    F26 = 63,
+    F27 = 64,
+    F28 = 65,
+    F29 = 66,
+    F30 = 67,
+    F31 = 68,
+    /// The floating point control and status register.
+    FCSR = 69,
+    /// The floating point condition codes, each holding a single bit.
+    FCC0 = 70,
+    FCC1 = 71,
+    FCC2 = 72,
+    FCC3 = 73,
+    FCC4 = 74,
+    FCC5 = 75,
+    FCC6 = 76,
+    FCC7 = 77,
 }
 
 impl From<u8> for Register {
@@ -82,7 +128,67 @@ impl From<u8> for Register {
             33 => Register::HI,
             34 => Register::BRK,
             35 => Register::HEAP,
+            36 => Register::LOCAL_USER,
+            37 => Register::F0,
+            38 => Register::F1,
+            39 => Register::F2,
+            40 => Register::F3,
+            41 => Register::F4,
+            42 => Register::F5,
+            43 => Register::F6,
+            44 => Register::F7,
+            45 => Register::F8,
+            46 => Register::F9,
+            47 => Register::F10,
+            48 => Register::F11,
+            49 => Register::F12,
+            50 => Register::F13,
+            51 => Register::F14,
+            52 => Register::F15,
+            53 => Register::F16,
+            54 => Register::F17,
+            55 => Register::F18,
+            56 => Register::F19,
+            57 => Register::F20,
+            58 => Register::F21,
+            59 => Register::F22,
+            60 => Register::F23,
+            61 => Register::F24,
+            62 => Register::F25,
+            63 => Register::F26,
+            64 => Register::F27,
+            65 => Register::F28,
+            66 => Register::F29,
+            67 => Register::F30,
+            68 => Register::F31,
+            69 => Register::FCSR,
+            70 => Register::FCC0,
+            71 => Register::FCC1,
+            72 => Register::FCC2,
+            73 => Register::FCC3,
+            74 => Register::FCC4,
+            75 => Register::FCC5,
+            76 => Register::FCC6,
+            77 => Register::FCC7,
             _ => panic!("invalid register {value}"),
         }
     }
 }
+
+impl Register {
+    /// The register holding floating point register `$f{index}`.
+    #[inline]
+    #[must_use]
+    pub fn fpr(index: u32) -> Self {
+        assert!(index < 32, "invalid floating point register {index}");
+        Self::from(Register::F0 as u8 + index as u8)
+    }
+
+    /// The register holding floating point condition code `cc`.
+    #[inline]
+    #[must_use]
+    pub fn fcc(cc: u32) -> Self {
+        assert!(cc < 8, "invalid condition code {cc}");
+        Self::from(Register::FCC0 as u8 + cc as u8)
+    }
+}
@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    syscalls::SyscallCode, ExecutionError, ExecutionState, Executor, ExecutorMode, Opcode, Register,
+};
+
+/// The point of the execution at which a snapshot is taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SnapshotTrigger {
+    /// Once this many cycles have been executed.
+    Cycle(u64),
+    /// When the program counter reaches this address, before executing the instruction there.
+    Pc(u32),
+    /// Right after the first invocation of this syscall.
+    Syscall(SyscallCode),
+}
+
+impl ExecutionState {
+    /// Whether the execution can be resumed from this state with [`crate::Program::resume_from`].
+    ///
+    /// A resumed program starts with `next_pc = pc + 4`, so this is false in the middle of a
+    /// branch or jump and its delay slot.
+    #[must_use]
+    pub fn is_resumable(&self) -> bool {
+        !self.exited && !self.next_is_delayslot && self.next_pc == self.pc.wrapping_add(4)
+    }
+
+    /// The state to start the execution of a program resumed from this snapshot with.
+    ///
+    /// The memory is loaded from the image of the resumed program, and the clocks start over as
+    /// in a fresh execution. The input, proof and public values streams, the files and the Linux
+    /// state are carried over, so that inputs written afterwards are read after the ones left
+    /// unread in the snapshot.
+    #[must_use]
+    pub fn resumed(&self) -> Self {
+        let mut state = Self::new(self.pc, self.next_pc);
+        state.uninitialized_memory = self.uninitialized_memory.clone();
+        state.input_stream = self.input_stream.clone();
+        state.input_stream_ptr = self.input_stream_ptr;
+        state.proof_stream = self.proof_stream.clone();
+        state.proof_stream_ptr = self.proof_stream_ptr;
+        state.public_values_stream = self.public_values_stream.clone();
+        state.public_values_stream_ptr = self.public_values_stream_ptr;
+        state.vfs = self.vfs.clone();
+        state.linux = self.linux.clone();
+        state
+    }
+}
+
+impl Executor<'_> {
+    /// Executes the program until `trigger` fires, and returns the state at that point, or `None`
+    /// if the program finishes first.
+    ///
+    /// The snapshot is taken at the first resumable state once the trigger fired, see
+    /// [`ExecutionState::is_resumable`], i.e. one instruction later if it fires on a delay slot.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the program execution fails.
+    pub fn run_until_snapshot(
+        &mut self,
+        trigger: SnapshotTrigger,
+    ) -> Result<Option<ExecutionState>, ExecutionError> {
+        self.executor_mode = ExecutorMode::Simple;
+        self.print_report = true;
+        if self.state.global_clk == 0 {
+            self.initialize();
+        }
+
+        let mut fired = false;
+        loop {
+            fired |= match trigger {
+                SnapshotTrigger::Cycle(cycle) => self.state.global_clk >= cycle,
+                SnapshotTrigger::Pc(pc) => self.state.pc == pc,
+                SnapshotTrigger::Syscall(_) => false,
+            };
+            if fired && !self.unconstrained && self.state.is_resumable() {
+                self.records.clear();
+                return Ok(Some(self.state.clone()));
+            }
+
+            let calls_syscall = match trigger {
+                SnapshotTrigger::Syscall(code) => {
+                    !self.unconstrained
+                        && self.program.fetch(self.state.pc).opcode == Opcode::SYSCALL
+                        && self.register(Register::V0) == code as u32
+                }
+                _ => false,
+            };
+            if self.step()? {
+                return Ok(None);
+            }
+            fired |= calls_syscall;
+
+            // Records are bumped at shard boundaries, but none of them are needed here.
+            self.records.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zkm_stark::ZKMCoreOpts;
+
+    use super::*;
+    use crate::{programs::tests::fibonacci_program, ZKMContext};
+
+    fn run_resumed(snapshot: &ExecutionState) -> Vec<u8> {
+        let program = fibonacci_program().resume_from(snapshot).unwrap();
+        let context = ZKMContext::builder().snapshot(snapshot).build();
+        let mut runtime = Executor::with_context(program, ZKMCoreOpts::default(), context);
+        runtime.run_fast().unwrap();
+        runtime.state.public_values_stream
+    }
+
+    #[test]
+    fn test_resume_from_cycle_snapshot() {
+        let mut runtime = Executor::new(fibonacci_program(), ZKMCoreOpts::default());
+        runtime.run_fast().unwrap();
+        let expected = runtime.state.public_values_stream;
+
+        let mut runtime = Executor::new(fibonacci_program(), ZKMCoreOpts::default());
+        let snapshot = runtime.run_until_snapshot(SnapshotTrigger::Cycle(1000)).unwrap().unwrap();
+        assert!(snapshot.global_clk >= 1000);
+        assert!(snapshot.is_resumable());
+        assert_eq!(run_resumed(&snapshot), expected);
+    }
+
+    #[test]
+    fn test_resume_from_syscall_snapshot() {
+        let mut runtime = Executor::new(fibonacci_program(), ZKMCoreOpts::default());
+        runtime.run_fast().unwrap();
+        let expected = runtime.state.public_values_stream;
+
+        // The first commit has been written to the public values stream, but not the second one.
+        let mut runtime = Executor::new(fibonacci_program(), ZKMCoreOpts::default());
+        let trigger = SnapshotTrigger::Syscall(SyscallCode::WRITE);
+        let snapshot = runtime.run_until_snapshot(trigger).unwrap().unwrap();
+        assert_eq!(snapshot.public_values_stream, expected[..4]);
+        assert_eq!(run_resumed(&snapshot), expected);
+    }
+
+    #[test]
+    fn test_snapshot_after_exit() {
+        let mut runtime = Executor::new(fibonacci_program(), ZKMCoreOpts::default());
+        assert!(runtime.run_until_snapshot(SnapshotTrigger::Pc(0)).unwrap().is_none());
+    }
+}
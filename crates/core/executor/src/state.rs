@@ -9,9 +9,11 @@ use zkm_stark::{koala_bear_poseidon2::KoalaBearPoseidon2, StarkVerifyingKey};
 
 use crate::{
     events::MemoryRecord,
+    linux::LinuxState,
     memory::Memory,
     record::{ExecutionRecord, MemoryAccessRecord},
     syscalls::SyscallCode,
+    vfs::VirtualFs,
     ExecutorMode, ZKMReduceProof,
 };
 
@@ -31,6 +33,9 @@ pub struct ExecutionState {
     /// if exit
     pub exited: bool,
 
+    /// The exit code passed to `HALT` or `exit_group`, which is nonzero when the execution failed.
+    pub exit_code: u32,
+
     /// if the next instruction is in delay slot for branch and jump.
     pub next_is_delayslot: bool,
 
@@ -70,6 +75,14 @@ pub struct ExecutionState {
     pub public_values_stream_ptr: usize,
     // /// Keeps track of how many times a certain syscall has been called.
     pub syscall_counts: HashMap<SyscallCode, u64>,
+
+    /// The files visible to the Linux file syscalls and the descriptors opened on them. The field
+    /// changed the bincode encoding of the state, so states serialized without it don't load.
+    pub vfs: VirtualFs,
+
+    /// The environment of the Linux syscalls and the state derived from it. Like `vfs`, it is
+    /// part of the bincode encoding of the state.
+    pub linux: LinuxState,
 }
 
 impl ExecutionState {
@@ -84,6 +97,7 @@ impl ExecutionState {
             pc: pc_start,
             next_pc,
             exited: false,
+            exit_code: 0,
             next_is_delayslot: false,
             memory: Memory::new_preallocated(),
             uninitialized_memory: Memory::new_preallocated(),
@@ -94,6 +108,8 @@ impl ExecutionState {
             proof_stream: Vec::new(),
             proof_stream_ptr: 0,
             syscall_counts: HashMap::new(),
+            vfs: VirtualFs::default(),
+            linux: LinuxState::default(),
         }
     }
 }
@@ -127,4 +143,9 @@ impl ExecutionState {
         writer.seek(std::io::SeekFrom::Start(0))?;
         Ok(())
     }
+
+    /// Load an execution state saved with [`Self::save`].
+    pub fn load(file: &File) -> bincode::Result<Self> {
+        bincode::deserialize_from(std::io::BufReader::new(file))
+    }
 }
@@ -2,6 +2,11 @@ use enum_map::Enum;
 use serde::{Deserialize, Serialize};
 use strum_macros::EnumIter;
 
+pub use zkm_stark::NUM_CUSTOM_SYSCALLS;
+
+/// The code of the first custom syscall, [`SyscallCode::CUSTOM_0`].
+const CUSTOM_SYSCALL_CODE_START: u32 = 0x01_01_00_C0;
+
 /// System Calls.
 ///
 /// A system call is invoked by the `syscall` instruction with a specific value in register V0.
@@ -162,24 +167,85 @@ pub enum SyscallCode {
     /// Fcntl
     SYS_FCNTL = 4055,
 
-    /// follows are executed as NOP syscalls
+    /// Virtual filesystem syscalls
     SYS_OPEN = 4005,
     SYS_CLOSE = 4006,
+    SYS_LSEEK = 4019,
+    SYS_FSTAT64 = 4215,
+    SYS_OPENAT = 4288,
+
+    /// Linux personality syscalls, see `crate::linux`
+    SYS_UNAME = 4122,
+    SYS_FUTEX = 4238,
+    SYS_CLOCK_GETTIME = 4263,
+    SYS_GETRANDOM = 4353,
+    SYS_CLOCK_GETTIME64 = 4403,
+
+    /// Set the thread pointer, see `crate::Register::LOCAL_USER`
+    SYS_SET_THREAD_AREA = 4283,
+
+    /// follows are executed as NOP syscalls
     SYS_MUNMAP = 4091,
     SYS_RT_SIGACTION = 4194,
     SYS_RT_SIGPROCMASK = 4195,
     SYS_SIGALTSTACK = 4206,
-    SYS_FSTAT64 = 4215,
     SYS_MADVISE = 4218,
     SYS_GETTID = 4222,
+    SYS_SCHED_YIELD = 4162,
     SYS_SCHED_GETAFFINITY = 4240,
-    SYS_CLOCK_GETTIME = 4263,
-    SYS_OPENAT = 4288,
     SYS_PRLIMIT64 = 4338,
 
     /// Executes the `POSEIDON2_PERMUTE` precompile.
     POSEIDON2_PERMUTE = 0x00_01_00_30,
 
+    /// Executes the `BLAKE2S_ROUNDS` precompile.
+    BLAKE2S_ROUNDS = 0x01_01_00_31,
+
+    /// Executes the `BLAKE3_ROUNDS` precompile.
+    BLAKE3_ROUNDS = 0x01_01_00_32,
+
+    /// Executes the `BLAKE2B_ROUNDS` precompile.
+    BLAKE2B_ROUNDS = 0x01_01_00_33,
+
+    /// Executes the `BLS12381_FP6_MUL` precompile.
+    BLS12381_FP6_MUL = 0x01_01_00_34,
+
+    /// Executes the `BN254_FP6_MUL` precompile.
+    BN254_FP6_MUL = 0x01_01_00_35,
+
+    /// Executes the `SECP256K1_MUL` precompile.
+    SECP256K1_MUL = 0x01_01_00_36,
+
+    /// Executes the `SECP256R1_MUL` precompile.
+    SECP256R1_MUL = 0x01_01_00_37,
+
+    /// Executes the `BN254_MUL` precompile.
+    BN254_MUL = 0x01_01_00_38,
+
+    /// Executes the `BLS12381_MUL` precompile.
+    BLS12381_MUL = 0x01_01_00_39,
+
+    /// Executes the `BIGINT_MULMOD` precompile.
+    BIGINT_MULMOD = 0x01_01_00_3A,
+
+    /// Custom precompiles registered outside of Ziren, see [`SyscallCode::custom`].
+    CUSTOM_0 = 0x01_01_00_C0,
+    CUSTOM_1 = 0x01_01_00_C1,
+    CUSTOM_2 = 0x01_01_00_C2,
+    CUSTOM_3 = 0x01_01_00_C3,
+    CUSTOM_4 = 0x01_01_00_C4,
+    CUSTOM_5 = 0x01_01_00_C5,
+    CUSTOM_6 = 0x01_01_00_C6,
+    CUSTOM_7 = 0x01_01_00_C7,
+    CUSTOM_8 = 0x01_01_00_C8,
+    CUSTOM_9 = 0x01_01_00_C9,
+    CUSTOM_10 = 0x01_01_00_CA,
+    CUSTOM_11 = 0x01_01_00_CB,
+    CUSTOM_12 = 0x01_01_00_CC,
+    CUSTOM_13 = 0x01_01_00_CD,
+    CUSTOM_14 = 0x01_01_00_CE,
+    CUSTOM_15 = 0x01_01_00_CF,
+
     SYS_LINUX = 4000, // not real syscall, used for represent all linux syscalls
 
     UNIMPLEMENTED = 0xFF_FF_FF_FF,
@@ -212,6 +278,16 @@ impl SyscallCode {
             0x00_00_00_1A => SyscallCode::COMMIT_DEFERRED_PROOFS,
             0x00_00_00_1B => SyscallCode::VERIFY_ZKM_PROOF,
             0x00_01_00_30 => SyscallCode::POSEIDON2_PERMUTE,
+            0x01_01_00_31 => SyscallCode::BLAKE2S_ROUNDS,
+            0x01_01_00_32 => SyscallCode::BLAKE3_ROUNDS,
+            0x01_01_00_33 => SyscallCode::BLAKE2B_ROUNDS,
+            0x01_01_00_34 => SyscallCode::BLS12381_FP6_MUL,
+            0x01_01_00_35 => SyscallCode::BN254_FP6_MUL,
+            0x01_01_00_36 => SyscallCode::SECP256K1_MUL,
+            0x01_01_00_37 => SyscallCode::SECP256R1_MUL,
+            0x01_01_00_38 => SyscallCode::BN254_MUL,
+            0x01_01_00_39 => SyscallCode::BLS12381_MUL,
+            0x01_01_00_3A => SyscallCode::BIGINT_MULMOD,
             0x00_01_00_1C => SyscallCode::BLS12381_DECOMPRESS,
             0x01_01_00_1D => SyscallCode::UINT256_MUL,
             0x01_01_00_1E => SyscallCode::BLS12381_ADD,
@@ -232,16 +308,35 @@ impl SyscallCode {
             0x00_01_00_2D => SyscallCode::SECP256R1_DOUBLE,
             0x00_01_00_2E => SyscallCode::SECP256R1_DECOMPRESS,
             0x01_01_00_2F => SyscallCode::U256XU2048_MUL,
+            0x01_01_00_C0 => SyscallCode::CUSTOM_0,
+            0x01_01_00_C1 => SyscallCode::CUSTOM_1,
+            0x01_01_00_C2 => SyscallCode::CUSTOM_2,
+            0x01_01_00_C3 => SyscallCode::CUSTOM_3,
+            0x01_01_00_C4 => SyscallCode::CUSTOM_4,
+            0x01_01_00_C5 => SyscallCode::CUSTOM_5,
+            0x01_01_00_C6 => SyscallCode::CUSTOM_6,
+            0x01_01_00_C7 => SyscallCode::CUSTOM_7,
+            0x01_01_00_C8 => SyscallCode::CUSTOM_8,
+            0x01_01_00_C9 => SyscallCode::CUSTOM_9,
+            0x01_01_00_CA => SyscallCode::CUSTOM_10,
+            0x01_01_00_CB => SyscallCode::CUSTOM_11,
+            0x01_01_00_CC => SyscallCode::CUSTOM_12,
+            0x01_01_00_CD => SyscallCode::CUSTOM_13,
+            0x01_01_00_CE => SyscallCode::CUSTOM_14,
+            0x01_01_00_CF => SyscallCode::CUSTOM_15,
             4000 => SyscallCode::SYS_LINUX,
             4003 => SyscallCode::SYS_READ,
             4004 => SyscallCode::SYS_WRITE,
             4005 => SyscallCode::SYS_OPEN,
             4006 => SyscallCode::SYS_CLOSE,
+            4019 => SyscallCode::SYS_LSEEK,
             4055 => SyscallCode::SYS_FCNTL,
             4045 => SyscallCode::SYS_BRK,
             4090 => SyscallCode::SYS_MMAP2,
             4091 => SyscallCode::SYS_MUNMAP,
             4120 => SyscallCode::SYS_CLONE,
+            4122 => SyscallCode::SYS_UNAME,
+            4162 => SyscallCode::SYS_SCHED_YIELD,
             4194 => SyscallCode::SYS_RT_SIGACTION,
             4195 => SyscallCode::SYS_RT_SIGPROCMASK,
             4206 => SyscallCode::SYS_SIGALTSTACK,
@@ -249,15 +344,44 @@ impl SyscallCode {
             4215 => SyscallCode::SYS_FSTAT64,
             4218 => SyscallCode::SYS_MADVISE,
             4222 => SyscallCode::SYS_GETTID,
+            4238 => SyscallCode::SYS_FUTEX,
             4240 => SyscallCode::SYS_SCHED_GETAFFINITY,
             4246 => SyscallCode::SYS_EXT_GROUP,
             4263 => SyscallCode::SYS_CLOCK_GETTIME,
+            4283 => SyscallCode::SYS_SET_THREAD_AREA,
             4288 => SyscallCode::SYS_OPENAT,
             4338 => SyscallCode::SYS_PRLIMIT64,
+            4353 => SyscallCode::SYS_GETRANDOM,
+            4403 => SyscallCode::SYS_CLOCK_GETTIME64,
             _ => SyscallCode::UNIMPLEMENTED,
         }
     }
 
+    /// The custom syscall with the given index, in `0..NUM_CUSTOM_SYSCALLS`.
+    ///
+    /// Custom syscalls have the identifiers `0xC0..0xD0`, which Ziren never assigns to its own
+    /// syscalls, and have their own table.
+    #[must_use]
+    pub fn custom(index: usize) -> Option<Self> {
+        (index < NUM_CUSTOM_SYSCALLS)
+            .then(|| Self::from_u32(CUSTOM_SYSCALL_CODE_START + index as u32))
+    }
+
+    /// Whether the syscall is in the range reserved for custom precompiles.
+    #[must_use]
+    pub fn is_custom(self) -> bool {
+        self.custom_index().is_some()
+    }
+
+    /// The index of the syscall in the range reserved for custom precompiles, if it is custom.
+    #[must_use]
+    pub fn custom_index(self) -> Option<usize> {
+        (self as u32)
+            .checked_sub(CUSTOM_SYSCALL_CODE_START)
+            .map(|index| index as usize)
+            .filter(|index| *index < NUM_CUSTOM_SYSCALLS)
+    }
+
     /// Get the system call identifier.
     #[must_use]
     pub fn syscall_id(self) -> u32 {
@@ -61,6 +61,19 @@ impl<'a, 'b> SyscallContext<'a, 'b> {
         }
     }
 
+    /// Create the syscall event of a precompile started at `clk`, to add with its precompile
+    /// event.
+    #[must_use]
+    pub fn syscall_event(
+        &self,
+        clk: u32,
+        syscall_code: SyscallCode,
+        arg1: u32,
+        arg2: u32,
+    ) -> SyscallEvent {
+        self.rt.syscall_event(clk, None, self.next_pc, syscall_code.syscall_id(), arg1, arg2)
+    }
+
     /// Get the current shard.
     #[must_use]
     pub fn current_shard(&self) -> u32 {
@@ -180,8 +193,9 @@ impl<'a, 'b> SyscallContext<'a, 'b> {
         self.next_pc = next_pc;
     }
 
-    /// Set the exit code.
+    /// Set the exit code, which is also kept in the state of the runtime.
     pub fn set_exit_code(&mut self, exit_code: u32) {
         self.exit_code = exit_code;
+        self.rt.state.exit_code = exit_code;
     }
 }
@@ -22,21 +22,27 @@ pub use code::*;
 pub use context::*;
 use hint::{HintLenSyscall, HintReadSyscall};
 use precompiles::{
+    bigint_mulmod::BigIntMulModSyscall,
+    blake::{blake2b::Blake2bRoundsSyscall, rounds::BlakeRoundsSyscall},
     edwards::{add::EdwardsAddAssignSyscall, decompress::EdwardsDecompressSyscall},
-    fptower::{Fp2AddSubSyscall, Fp2MulSyscall, FpOpSyscall},
+    fptower::{Fp2AddSubSyscall, Fp2MulSyscall, Fp6MulSyscall, FpOpSyscall},
     keccak::sponge::KeccakSpongeSyscall,
     poseidon2::permute::Poseidon2PermuteSyscall,
     sha256::{compress::Sha256CompressSyscall, extend::Sha256ExtendSyscall},
     sys_linux::{
-        sysbrk::SysBrkSyscall, sysclone::SysCloneSyscall, sysexitgroup::SysExitGroupSyscall,
-        sysfcntl::SysFcntlSyscall, sysmmap::SysMmapSyscall, sysnop::SysNopSyscall,
-        sysread::SysReadSyscall, syswrite::SysWriteSyscall,
+        sysbrk::SysBrkSyscall, sysclock::SysClockGettimeSyscall, sysclone::SysCloneSyscall,
+        sysclose::SysCloseSyscall, sysexitgroup::SysExitGroupSyscall, sysfcntl::SysFcntlSyscall,
+        sysfstat::SysFstat64Syscall, sysfutex::SysFutexSyscall, sysgetrandom::SysGetrandomSyscall,
+        syslseek::SysLseekSyscall, sysmmap::SysMmapSyscall, sysnop::SysNopSyscall,
+        sysopen::SysOpenSyscall, sysread::SysReadSyscall,
+        syssetthreadarea::SysSetThreadAreaSyscall, sysuname::SysUnameSyscall,
+        syswrite::SysWriteSyscall,
     },
     u256x2048_mul::U256xU2048MulSyscall,
     uint256::Uint256MulSyscall,
     weierstrass::{
         add::WeierstrassAddAssignSyscall, decompress::WeierstrassDecompressSyscall,
-        double::WeierstrassDoubleAssignSyscall,
+        double::WeierstrassDoubleAssignSyscall, mul::WeierstrassMulAssignSyscall,
     },
 };
 
@@ -104,6 +110,12 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
 
     syscall_map.insert(SyscallCode::KECCAK_SPONGE, Arc::new(KeccakSpongeSyscall));
 
+    syscall_map.insert(SyscallCode::BLAKE2S_ROUNDS, Arc::new(BlakeRoundsSyscall::blake2s()));
+
+    syscall_map.insert(SyscallCode::BLAKE3_ROUNDS, Arc::new(BlakeRoundsSyscall::blake3()));
+
+    syscall_map.insert(SyscallCode::BLAKE2B_ROUNDS, Arc::new(Blake2bRoundsSyscall));
+
     syscall_map.insert(
         SyscallCode::SECP256K1_ADD,
         Arc::new(WeierstrassAddAssignSyscall::<Secp256k1>::new()),
@@ -114,6 +126,11 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
         Arc::new(WeierstrassDoubleAssignSyscall::<Secp256k1>::new()),
     );
 
+    syscall_map.insert(
+        SyscallCode::SECP256K1_MUL,
+        Arc::new(WeierstrassMulAssignSyscall::<Secp256k1>::new()),
+    );
+
     syscall_map.insert(
         SyscallCode::SECP256K1_DECOMPRESS,
         Arc::new(WeierstrassDecompressSyscall::<Secp256k1>::new()),
@@ -129,6 +146,11 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
         Arc::new(WeierstrassDoubleAssignSyscall::<Secp256r1>::new()),
     );
 
+    syscall_map.insert(
+        SyscallCode::SECP256R1_MUL,
+        Arc::new(WeierstrassMulAssignSyscall::<Secp256r1>::new()),
+    );
+
     syscall_map.insert(
         SyscallCode::SECP256R1_DECOMPRESS,
         Arc::new(WeierstrassDecompressSyscall::<Secp256r1>::new()),
@@ -142,6 +164,9 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
         Arc::new(WeierstrassDoubleAssignSyscall::<Bn254>::new()),
     );
 
+    syscall_map
+        .insert(SyscallCode::BN254_MUL, Arc::new(WeierstrassMulAssignSyscall::<Bn254>::new()));
+
     syscall_map.insert(
         SyscallCode::BLS12381_ADD,
         Arc::new(WeierstrassAddAssignSyscall::<Bls12381>::new()),
@@ -152,10 +177,17 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
         Arc::new(WeierstrassDoubleAssignSyscall::<Bls12381>::new()),
     );
 
+    syscall_map.insert(
+        SyscallCode::BLS12381_MUL,
+        Arc::new(WeierstrassMulAssignSyscall::<Bls12381>::new()),
+    );
+
     syscall_map.insert(SyscallCode::UINT256_MUL, Arc::new(Uint256MulSyscall));
 
     syscall_map.insert(SyscallCode::U256XU2048_MUL, Arc::new(U256xU2048MulSyscall));
 
+    syscall_map.insert(SyscallCode::BIGINT_MULMOD, Arc::new(BigIntMulModSyscall));
+
     syscall_map.insert(
         SyscallCode::BLS12381_FP_ADD,
         Arc::new(FpOpSyscall::<Bls12381BaseField>::new(FieldOperation::Add)),
@@ -184,6 +216,9 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
     syscall_map
         .insert(SyscallCode::BLS12381_FP2_MUL, Arc::new(Fp2MulSyscall::<Bls12381BaseField>::new()));
 
+    syscall_map
+        .insert(SyscallCode::BLS12381_FP6_MUL, Arc::new(Fp6MulSyscall::<Bls12381BaseField>::new()));
+
     syscall_map.insert(
         SyscallCode::BN254_FP_ADD,
         Arc::new(FpOpSyscall::<Bn254BaseField>::new(FieldOperation::Add)),
@@ -212,6 +247,9 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
     syscall_map
         .insert(SyscallCode::BN254_FP2_MUL, Arc::new(Fp2MulSyscall::<Bn254BaseField>::new()));
 
+    syscall_map
+        .insert(SyscallCode::BN254_FP6_MUL, Arc::new(Fp6MulSyscall::<Bn254BaseField>::new()));
+
     syscall_map.insert(SyscallCode::ENTER_UNCONSTRAINED, Arc::new(EnterUnconstrainedSyscall));
 
     syscall_map.insert(SyscallCode::EXIT_UNCONSTRAINED, Arc::new(ExitUnconstrainedSyscall));
@@ -243,18 +281,25 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
     syscall_map.insert(SyscallCode::SYS_MMAP2, Arc::new(SysMmapSyscall));
     syscall_map.insert(SyscallCode::SYS_CLONE, Arc::new(SysCloneSyscall));
     syscall_map.insert(SyscallCode::SYS_FCNTL, Arc::new(SysFcntlSyscall));
-    syscall_map.insert(SyscallCode::SYS_OPEN, Arc::new(SysNopSyscall));
-    syscall_map.insert(SyscallCode::SYS_CLOSE, Arc::new(SysNopSyscall));
+    syscall_map.insert(SyscallCode::SYS_OPEN, Arc::new(SysOpenSyscall));
+    syscall_map.insert(SyscallCode::SYS_OPENAT, Arc::new(SysOpenSyscall));
+    syscall_map.insert(SyscallCode::SYS_CLOSE, Arc::new(SysCloseSyscall));
+    syscall_map.insert(SyscallCode::SYS_LSEEK, Arc::new(SysLseekSyscall));
+    syscall_map.insert(SyscallCode::SYS_FSTAT64, Arc::new(SysFstat64Syscall));
+    syscall_map.insert(SyscallCode::SYS_UNAME, Arc::new(SysUnameSyscall));
+    syscall_map.insert(SyscallCode::SYS_FUTEX, Arc::new(SysFutexSyscall));
+    syscall_map.insert(SyscallCode::SYS_CLOCK_GETTIME, Arc::new(SysClockGettimeSyscall));
+    syscall_map.insert(SyscallCode::SYS_CLOCK_GETTIME64, Arc::new(SysClockGettimeSyscall));
+    syscall_map.insert(SyscallCode::SYS_GETRANDOM, Arc::new(SysGetrandomSyscall));
+    syscall_map.insert(SyscallCode::SYS_SET_THREAD_AREA, Arc::new(SysSetThreadAreaSyscall));
+    syscall_map.insert(SyscallCode::SYS_SCHED_YIELD, Arc::new(SysNopSyscall));
     syscall_map.insert(SyscallCode::SYS_RT_SIGACTION, Arc::new(SysNopSyscall));
     syscall_map.insert(SyscallCode::SYS_RT_SIGPROCMASK, Arc::new(SysNopSyscall));
     syscall_map.insert(SyscallCode::SYS_MADVISE, Arc::new(SysNopSyscall));
     syscall_map.insert(SyscallCode::SYS_GETTID, Arc::new(SysNopSyscall));
     syscall_map.insert(SyscallCode::SYS_SCHED_GETAFFINITY, Arc::new(SysNopSyscall));
-    syscall_map.insert(SyscallCode::SYS_CLOCK_GETTIME, Arc::new(SysNopSyscall));
     syscall_map.insert(SyscallCode::SYS_PRLIMIT64, Arc::new(SysNopSyscall));
     syscall_map.insert(SyscallCode::SYS_SIGALTSTACK, Arc::new(SysNopSyscall));
-    syscall_map.insert(SyscallCode::SYS_OPENAT, Arc::new(SysNopSyscall));
-    syscall_map.insert(SyscallCode::SYS_FSTAT64, Arc::new(SysNopSyscall));
     syscall_map.insert(SyscallCode::SYS_MUNMAP, Arc::new(SysNopSyscall));
 
     syscall_map
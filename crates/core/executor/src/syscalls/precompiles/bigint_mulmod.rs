@@ -0,0 +1,95 @@
+use num::{BigUint, Zero};
+
+use zkm_primitives::consts::{words_to_bytes_le_vec, WORD_SIZE};
+
+use crate::{
+    events::{BigIntMulModEvent, PrecompileEvent, BIGINT_DIGIT_WORDS, BIGINT_MAX_DIGITS},
+    syscalls::{Syscall, SyscallCode, SyscallContext},
+    ExecutionError,
+    Register::A2,
+};
+
+pub(crate) struct BigIntMulModSyscall;
+
+impl Syscall for BigIntMulModSyscall {
+    fn execute(
+        &self,
+        rt: &mut SyscallContext,
+        syscall_code: SyscallCode,
+        arg1: u32,
+        arg2: u32,
+    ) -> Result<Option<u32>, ExecutionError> {
+        let clk = rt.clk;
+
+        // The arguments are controlled by the guest, so invalid ones fail the execution instead
+        // of panicking.
+        let x_ptr = arg1;
+        let y_ptr = arg2;
+        if !x_ptr.is_multiple_of(4) || !y_ptr.is_multiple_of(4) {
+            return Err(ExecutionError::InvalidSyscallArgs());
+        }
+
+        // The width of the operands, in 256-bit digits, is passed in A2.
+        let (num_digits_memory, num_digits) = rt.rr_traced(A2);
+        if !(1..=BIGINT_MAX_DIGITS as u32).contains(&num_digits) {
+            return Err(ExecutionError::InvalidSyscallArgs());
+        }
+        let num_words = num_digits as usize * BIGINT_DIGIT_WORDS;
+
+        // First read the words for the x value. We can read a slice_unsafe here because we write
+        // the computed result to x later.
+        let x = rt.slice_unsafe(x_ptr, num_words);
+
+        // Read the y value, followed by the modulus.
+        let (y_memory_records, y) = rt.mr_slice(y_ptr, num_words);
+        let modulus_ptr = y_ptr + (num_words * WORD_SIZE) as u32;
+        let (modulus_memory_records, modulus) = rt.mr_slice(modulus_ptr, num_words);
+
+        let bigint_x = BigUint::from_bytes_le(&words_to_bytes_le_vec(&x));
+        let bigint_y = BigUint::from_bytes_le(&words_to_bytes_le_vec(&y));
+        let bigint_modulus = BigUint::from_bytes_le(&words_to_bytes_le_vec(&modulus));
+        if bigint_modulus.is_zero() {
+            return Err(ExecutionError::InvalidSyscallArgs());
+        }
+
+        // The quotient is a witness of the same width as the operands, which holds as soon as one
+        // of x and y is reduced.
+        let product = bigint_x * bigint_y;
+        if (&product / &bigint_modulus).bits() > (num_words * 32) as u64 {
+            return Err(ExecutionError::InvalidSyscallArgs());
+        }
+        let mut result = (product % &bigint_modulus).to_u32_digits();
+        result.resize(num_words, 0);
+
+        // Increment clk so that the write is not at the same cycle as the read.
+        rt.clk += 1;
+        // Write the result to x and keep track of the memory records.
+        let x_memory_records = rt.mw_slice(x_ptr, &result);
+
+        let shard = rt.current_shard();
+        let event = PrecompileEvent::BigIntMulMod(BigIntMulModEvent {
+            shard,
+            clk,
+            num_digits,
+            num_digits_memory,
+            x_ptr,
+            x,
+            y_ptr,
+            y,
+            modulus,
+            x_memory_records,
+            y_memory_records,
+            modulus_memory_records,
+            local_mem_access: rt.postprocess(),
+        });
+        let syscall_event =
+            rt.rt.syscall_event(clk, None, rt.next_pc, syscall_code.syscall_id(), arg1, arg2);
+        rt.add_precompile_event(syscall_code, syscall_event, event);
+
+        Ok(None)
+    }
+
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{
+    events::{Blake2bRoundsEvent, PrecompileEvent},
+    syscalls::{Syscall, SyscallCode, SyscallContext},
+    ExecutionError,
+};
+
+use super::{blake2b_round, BLAKE2_SIGMA};
+
+/// The number of rounds of the BLAKE2b compression function.
+pub const NUM_BLAKE2B_ROUNDS: usize = 12;
+
+/// Runs the twelve rounds of the BLAKE2b compression function on a working vector.
+///
+/// `arg1` points to the working vector of 16 little-endian `u64`s, which is overwritten with the
+/// result, and `arg2` points to the message block of 16 little-endian `u64`s.
+pub(crate) struct Blake2bRoundsSyscall;
+
+impl Syscall for Blake2bRoundsSyscall {
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+
+    fn execute(
+        &self,
+        rt: &mut SyscallContext,
+        syscall_code: SyscallCode,
+        arg1: u32,
+        arg2: u32,
+    ) -> Result<Option<u32>, ExecutionError> {
+        let clk = rt.clk;
+
+        let v_ptr = arg1;
+        if !v_ptr.is_multiple_of(4) {
+            panic!("v_ptr must be aligned");
+        }
+        let m_ptr = arg2;
+        if !m_ptr.is_multiple_of(4) {
+            panic!("m_ptr must be aligned");
+        }
+
+        // We can read a slice_unsafe here because the result is written back to v later.
+        let v: [u32; 32] = rt.slice_unsafe(v_ptr, 32).try_into().unwrap();
+        let (m_memory_records, m) = rt.mr_slice(m_ptr, 32);
+        let m: [u32; 32] = m.try_into().unwrap();
+
+        let mut state = words_to_u64s(&v);
+        let message = words_to_u64s(&m);
+        for r in 0..NUM_BLAKE2B_ROUNDS {
+            blake2b_round(&mut state, &message, &BLAKE2_SIGMA[r % 10]);
+        }
+        let result: Vec<u32> = state.iter().flat_map(|x| [*x as u32, (x >> 32) as u32]).collect();
+
+        // Increment clk so that the write is not at the same cycle as the read.
+        rt.clk += 1;
+        let v_memory_records = rt.mw_slice(v_ptr, &result);
+
+        let shard = rt.current_shard();
+        let event = PrecompileEvent::Blake2bRounds(Blake2bRoundsEvent {
+            shard,
+            clk,
+            v_ptr,
+            m_ptr,
+            v,
+            m,
+            v_memory_records,
+            m_memory_records,
+            local_mem_access: rt.postprocess(),
+        });
+        let syscall_event =
+            rt.rt.syscall_event(clk, None, rt.next_pc, syscall_code.syscall_id(), arg1, arg2);
+        rt.add_precompile_event(syscall_code, syscall_event, event);
+
+        Ok(None)
+    }
+}
+
+/// Pack pairs of little-endian words into `u64`s.
+fn words_to_u64s<const N: usize>(words: &[u32]) -> [u64; N] {
+    core::array::from_fn(|i| words[2 * i] as u64 | ((words[2 * i + 1] as u64) << 32))
+}
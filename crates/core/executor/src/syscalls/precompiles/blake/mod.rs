@@ -0,0 +1,144 @@
+pub mod blake2b;
+pub mod rounds;
+
+/// The message schedule of BLAKE2s and BLAKE2b. BLAKE2b wraps around after the tenth round.
+pub const BLAKE2_SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// The message schedule of BLAKE3, i.e. the message permutation applied `r` times in round `r`.
+pub const BLAKE3_MSG_SCHEDULE: [[usize; 16]; 7] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8],
+    [3, 4, 10, 12, 13, 2, 7, 14, 6, 5, 9, 0, 11, 15, 8, 1],
+    [10, 7, 12, 9, 14, 3, 13, 15, 4, 0, 11, 2, 5, 8, 1, 6],
+    [12, 13, 9, 11, 15, 10, 14, 8, 7, 2, 5, 3, 0, 1, 6, 4],
+    [9, 14, 11, 5, 8, 12, 15, 1, 13, 3, 0, 10, 2, 6, 4, 7],
+    [11, 15, 5, 0, 1, 9, 8, 6, 14, 10, 2, 12, 3, 4, 7, 13],
+];
+
+/// The state positions `(a, b, c, d)` mixed by each of the eight G applications of a round: four
+/// columns followed by four diagonals.
+pub const G_INDICES: [[usize; 4]; 8] = [
+    [0, 4, 8, 12],
+    [1, 5, 9, 13],
+    [2, 6, 10, 14],
+    [3, 7, 11, 15],
+    [0, 5, 10, 15],
+    [1, 6, 11, 12],
+    [2, 7, 8, 13],
+    [3, 4, 9, 14],
+];
+
+/// Apply one round of the 32-bit G function, shared by BLAKE2s and BLAKE3, to the working vector.
+pub fn blake_round(v: &mut [u32; 16], m: &[u32; 16], schedule: &[usize; 16]) {
+    for (i, [a, b, c, d]) in G_INDICES.into_iter().enumerate() {
+        let (x, y) = (m[schedule[2 * i]], m[schedule[2 * i + 1]]);
+        v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+        v[d] = (v[d] ^ v[a]).rotate_right(16);
+        v[c] = v[c].wrapping_add(v[d]);
+        v[b] = (v[b] ^ v[c]).rotate_right(12);
+        v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+        v[d] = (v[d] ^ v[a]).rotate_right(8);
+        v[c] = v[c].wrapping_add(v[d]);
+        v[b] = (v[b] ^ v[c]).rotate_right(7);
+    }
+}
+
+/// Apply one round of the BLAKE2b G function to the working vector.
+pub fn blake2b_round(v: &mut [u64; 16], m: &[u64; 16], schedule: &[usize; 16]) {
+    for (i, [a, b, c, d]) in G_INDICES.into_iter().enumerate() {
+        let (x, y) = (m[schedule[2 * i]], m[schedule[2 * i + 1]]);
+        v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+        v[d] = (v[d] ^ v[a]).rotate_right(32);
+        v[c] = v[c].wrapping_add(v[d]);
+        v[b] = (v[b] ^ v[c]).rotate_right(24);
+        v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+        v[d] = (v[d] ^ v[a]).rotate_right(16);
+        v[c] = v[c].wrapping_add(v[d]);
+        v[b] = (v[b] ^ v[c]).rotate_right(63);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// BLAKE2s-256 of "abc" from RFC 7693, appendix B.
+    #[test]
+    fn test_blake2s_abc() {
+        const IV: [u32; 8] = [
+            0x6A09E667, 0xBB67AE85, 0x3C6EF372, 0xA54FF53A, 0x510E527F, 0x9B05688C, 0x1F83D9AB,
+            0x5BE0CD19,
+        ];
+        let mut h = IV;
+        h[0] ^= 0x0101_0020;
+        let mut m = [0u32; 16];
+        m[0] = u32::from_le_bytes([b'a', b'b', b'c', 0]);
+
+        let mut v = [0u32; 16];
+        v[..8].copy_from_slice(&h);
+        v[8..].copy_from_slice(&IV);
+        v[12] ^= 3;
+        v[14] = !v[14];
+        for schedule in &BLAKE2_SIGMA {
+            blake_round(&mut v, &m, schedule);
+        }
+        for i in 0..8 {
+            h[i] ^= v[i] ^ v[i + 8];
+        }
+
+        let digest: Vec<u8> = h.iter().flat_map(|w| w.to_le_bytes()).collect();
+        assert_eq!(
+            hex::encode(digest),
+            "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982"
+        );
+    }
+
+    /// BLAKE2b-512 of "abc" from RFC 7693, appendix A.
+    #[test]
+    fn test_blake2b_abc() {
+        const IV: [u64; 8] = [
+            0x6A09E667F3BCC908,
+            0xBB67AE8584CAA73B,
+            0x3C6EF372FE94F82B,
+            0xA54FF53A5F1D36F1,
+            0x510E527FADE682D1,
+            0x9B05688C2B3E6C1F,
+            0x1F83D9ABFB41BD6B,
+            0x5BE0CD19137E2179,
+        ];
+        let mut h = IV;
+        h[0] ^= 0x0101_0040;
+        let mut m = [0u64; 16];
+        m[0] = u64::from_le_bytes([b'a', b'b', b'c', 0, 0, 0, 0, 0]);
+
+        let mut v = [0u64; 16];
+        v[..8].copy_from_slice(&h);
+        v[8..].copy_from_slice(&IV);
+        v[12] ^= 3;
+        v[14] = !v[14];
+        for r in 0..12 {
+            blake2b_round(&mut v, &m, &BLAKE2_SIGMA[r % 10]);
+        }
+        for i in 0..8 {
+            h[i] ^= v[i] ^ v[i + 8];
+        }
+
+        let digest: Vec<u8> = h.iter().flat_map(|w| w.to_le_bytes()).collect();
+        assert_eq!(
+            hex::encode(digest),
+            "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1\
+             7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"
+        );
+    }
+}
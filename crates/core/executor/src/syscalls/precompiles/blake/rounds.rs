@@ -0,0 +1,88 @@
+use crate::{
+    events::{BlakeRoundsEvent, PrecompileEvent},
+    syscalls::{Syscall, SyscallCode, SyscallContext},
+    ExecutionError,
+};
+
+use super::{blake_round, BLAKE2_SIGMA, BLAKE3_MSG_SCHEDULE};
+
+/// Runs all the rounds of the BLAKE2s or BLAKE3 compression function on a working vector.
+///
+/// `arg1` points to the 16-word working vector, which is overwritten with the result, and `arg2`
+/// points to the 16-word message block. Initializing the working vector and folding it back into
+/// the chaining value is left to the guest.
+pub(crate) struct BlakeRoundsSyscall {
+    schedule: &'static [[usize; 16]],
+}
+
+impl BlakeRoundsSyscall {
+    pub const fn blake2s() -> Self {
+        Self { schedule: &BLAKE2_SIGMA }
+    }
+
+    pub const fn blake3() -> Self {
+        Self { schedule: &BLAKE3_MSG_SCHEDULE }
+    }
+}
+
+impl Syscall for BlakeRoundsSyscall {
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+
+    fn execute(
+        &self,
+        rt: &mut SyscallContext,
+        syscall_code: SyscallCode,
+        arg1: u32,
+        arg2: u32,
+    ) -> Result<Option<u32>, ExecutionError> {
+        let clk = rt.clk;
+
+        let v_ptr = arg1;
+        if !v_ptr.is_multiple_of(4) {
+            panic!("v_ptr must be aligned");
+        }
+        let m_ptr = arg2;
+        if !m_ptr.is_multiple_of(4) {
+            panic!("m_ptr must be aligned");
+        }
+
+        // We can read a slice_unsafe here because the result is written back to v later.
+        let v: [u32; 16] = rt.slice_unsafe(v_ptr, 16).try_into().unwrap();
+        let (m_memory_records, m) = rt.mr_slice(m_ptr, 16);
+        let m: [u32; 16] = m.try_into().unwrap();
+
+        let mut result = v;
+        for schedule in self.schedule {
+            blake_round(&mut result, &m, schedule);
+        }
+
+        // Increment clk so that the write is not at the same cycle as the read.
+        rt.clk += 1;
+        let v_memory_records = rt.mw_slice(v_ptr, &result);
+
+        let shard = rt.current_shard();
+        let event = BlakeRoundsEvent {
+            shard,
+            clk,
+            v_ptr,
+            m_ptr,
+            v,
+            m,
+            v_memory_records,
+            m_memory_records,
+            local_mem_access: rt.postprocess(),
+        };
+        let event = match syscall_code {
+            SyscallCode::BLAKE2S_ROUNDS => PrecompileEvent::Blake2sRounds(event),
+            SyscallCode::BLAKE3_ROUNDS => PrecompileEvent::Blake3Rounds(event),
+            _ => unreachable!(),
+        };
+        let syscall_event =
+            rt.rt.syscall_event(clk, None, rt.next_pc, syscall_code.syscall_id(), arg1, arg2);
+        rt.add_precompile_event(syscall_code, syscall_event, event);
+
+        Ok(None)
+    }
+}
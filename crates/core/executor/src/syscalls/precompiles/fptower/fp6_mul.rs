@@ -0,0 +1,143 @@
+use std::marker::PhantomData;
+
+use num::BigUint;
+use typenum::Unsigned;
+use zkm_curves::{
+    params::NumWords,
+    weierstrass::{FieldType, FpOpField},
+};
+
+use crate::{
+    events::{Fp6MulEvent, PrecompileEvent},
+    syscalls::{Syscall, SyscallCode, SyscallContext},
+    ExecutionError,
+};
+
+pub struct Fp6MulSyscall<P> {
+    _marker: PhantomData<P>,
+}
+
+impl<P> Fp6MulSyscall<P> {
+    pub const fn new() -> Self {
+        Self { _marker: PhantomData }
+    }
+}
+
+/// A reduced element `c0 + c1 * u` of the quadratic extension.
+type Fp2 = (BigUint, BigUint);
+
+fn fp2_add(a: &Fp2, b: &Fp2, modulus: &BigUint) -> Fp2 {
+    ((&a.0 + &b.0) % modulus, (&a.1 + &b.1) % modulus)
+}
+
+fn fp2_mul(a: &Fp2, b: &Fp2, modulus: &BigUint) -> Fp2 {
+    let c0 = (&a.0 * &b.0 + modulus * modulus - &a.1 * &b.1) % modulus;
+    let c1 = (&a.0 * &b.1 + &a.1 * &b.0) % modulus;
+    (c0, c1)
+}
+
+impl<P: FpOpField> Syscall for Fp6MulSyscall<P> {
+    fn execute(
+        &self,
+        rt: &mut SyscallContext,
+        syscall_code: SyscallCode,
+        arg1: u32,
+        arg2: u32,
+    ) -> Result<Option<u32>, ExecutionError> {
+        let clk = rt.clk;
+        let x_ptr = arg1;
+        if !x_ptr.is_multiple_of(4) {
+            panic!();
+        }
+        let y_ptr = arg2;
+        if !y_ptr.is_multiple_of(4) {
+            panic!();
+        }
+
+        // An Fp6 element is made of three Fp2 coefficients, each the size of a curve point.
+        let num_words = 3 * <P as NumWords>::WordsCurvePoint::USIZE;
+
+        let x = rt.slice_unsafe(x_ptr, num_words);
+        let (y_memory_records, y) = rt.mr_slice(y_ptr, num_words);
+        rt.clk += 1;
+
+        let modulus = &BigUint::from_bytes_le(P::MODULUS);
+        let to_fp6 = |words: &[u32]| -> Vec<Fp2> {
+            words
+                .chunks_exact(num_words / 6)
+                .map(|limbs| BigUint::from_slice(limbs) % modulus)
+                .collect::<Vec<_>>()
+                .chunks_exact(2)
+                .map(|c| (c[0].clone(), c[1].clone()))
+                .collect()
+        };
+        let a = to_fp6(&x);
+        let b = to_fp6(&y);
+
+        // Multiplying by `v^3` is multiplying by the non-residue `xi`.
+        let (xi_0, xi_1) = P::FP6_NON_RESIDUE;
+        let xi = (BigUint::from(xi_0), BigUint::from(xi_1));
+        let mul_by_xi = |c: &Fp2| fp2_mul(c, &xi, modulus);
+
+        let c0 = fp2_add(
+            &fp2_mul(&a[0], &b[0], modulus),
+            &mul_by_xi(&fp2_add(
+                &fp2_mul(&a[1], &b[2], modulus),
+                &fp2_mul(&a[2], &b[1], modulus),
+                modulus,
+            )),
+            modulus,
+        );
+        let c1 = fp2_add(
+            &fp2_add(&fp2_mul(&a[0], &b[1], modulus), &fp2_mul(&a[1], &b[0], modulus), modulus),
+            &mul_by_xi(&fp2_mul(&a[2], &b[2], modulus)),
+            modulus,
+        );
+        let c2 = fp2_add(
+            &fp2_add(&fp2_mul(&a[0], &b[2], modulus), &fp2_mul(&a[1], &b[1], modulus), modulus),
+            &fp2_mul(&a[2], &b[0], modulus),
+            modulus,
+        );
+
+        let mut result = Vec::with_capacity(num_words);
+        for coefficient in [c0.0, c0.1, c1.0, c1.1, c2.0, c2.1] {
+            let mut words = coefficient.to_u32_digits();
+            words.resize(num_words / 6, 0);
+            result.extend_from_slice(&words);
+        }
+
+        let x_memory_records = rt.mw_slice(x_ptr, &result);
+
+        let shard = rt.current_shard();
+        let event = Fp6MulEvent {
+            shard,
+            clk,
+            x_ptr,
+            x,
+            y_ptr,
+            y,
+            x_memory_records,
+            y_memory_records,
+            local_mem_access: rt.postprocess(),
+        };
+        let syscall_event =
+            rt.rt.syscall_event(clk, None, rt.next_pc, syscall_code.syscall_id(), arg1, arg2);
+        match P::FIELD_TYPE {
+            FieldType::Bn254 => rt.add_precompile_event(
+                syscall_code,
+                syscall_event,
+                PrecompileEvent::Bn254Fp6Mul(event),
+            ),
+            FieldType::Bls12381 => rt.add_precompile_event(
+                syscall_code,
+                syscall_event,
+                PrecompileEvent::Bls12381Fp6Mul(event),
+            ),
+        };
+        Ok(None)
+    }
+
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+}
@@ -1,3 +1,5 @@
+pub mod bigint_mulmod;
+pub mod blake;
 pub mod edwards;
 pub mod fptower;
 pub mod keccak;
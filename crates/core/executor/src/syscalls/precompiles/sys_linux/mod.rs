@@ -1,8 +1,98 @@
 pub mod sysbrk;
+pub mod sysclock;
 pub mod sysclone;
+pub mod sysclose;
 pub mod sysexitgroup;
 pub mod sysfcntl;
+pub mod sysfstat;
+pub mod sysfutex;
+pub mod sysgetrandom;
+pub mod syslseek;
 pub mod sysmmap;
 pub mod sysnop;
+pub mod sysopen;
 pub mod sysread;
+pub mod syssetthreadarea;
+pub mod sysuname;
 pub mod syswrite;
+
+use crate::{
+    events::{LinuxEvent, MemoryReadRecord, MemoryWriteRecord, PrecompileEvent},
+    syscalls::{SyscallCode, SyscallContext},
+    vfs::errno,
+    Register, NUM_REGISTERS,
+};
+
+/// Check that the guest buffer `[addr, addr + len)` lies in main memory.
+pub(crate) fn check_buffer(addr: u32, len: u32) -> Result<(), u32> {
+    if len > 0 && (addr < NUM_REGISTERS as u32 || addr.checked_add(len).is_none()) {
+        return Err(errno::EFAULT);
+    }
+    Ok(())
+}
+
+/// Write `bytes` to guest memory starting at the possibly unaligned address `addr`.
+///
+/// The bytes of the first and last words that lie outside of the buffer are preserved.
+fn write_buffer(rt: &mut SyscallContext, addr: u32, bytes: &[u8]) -> Vec<MemoryWriteRecord> {
+    let end = addr + bytes.len() as u32;
+    (addr & !3..end)
+        .step_by(4)
+        .map(|word_addr| {
+            let mut word = rt.word_unsafe(word_addr).to_le_bytes();
+            for (i, byte) in word.iter_mut().enumerate() {
+                let byte_addr = word_addr + i as u32;
+                if (addr..end).contains(&byte_addr) {
+                    *byte = bytes[(byte_addr - addr) as usize];
+                }
+            }
+            rt.mw(word_addr, u32::from_le_bytes(word))
+        })
+        .collect()
+}
+
+/// Finish a syscall which either fails with an errno or returns a value and fills a buffer.
+///
+/// On success, `v0` is set to the returned value, A3 is cleared and `buf` is written to guest
+/// memory. On failure, `v0` is set to the errno and A3 to 1, as the o32 ABI prescribes.
+pub(crate) fn finish_linux_syscall(
+    rt: &mut SyscallContext,
+    syscall_code: SyscallCode,
+    a0: u32,
+    a1: u32,
+    result: Result<(u32, Option<(u32, Vec<u8>)>), u32>,
+    read_records: Vec<MemoryReadRecord>,
+) -> u32 {
+    let start_clk = rt.clk;
+    let (v0, a3, buf) = match result {
+        Ok((v0, buf)) => (v0, 0, buf),
+        Err(errno) => (errno, 1, None),
+    };
+    let a3_record = rt.rw_traced(Register::A3, a3);
+    let (buf_addr, buf_len, buf_records) = match buf {
+        Some((addr, bytes)) if !bytes.is_empty() => {
+            (addr, bytes.len() as u32, write_buffer(rt, addr, &bytes))
+        }
+        _ => (0, 0, vec![]),
+    };
+
+    let shard = rt.current_shard();
+    let event = PrecompileEvent::Linux(LinuxEvent {
+        shard,
+        clk: start_clk,
+        a0,
+        a1,
+        v0,
+        syscall_code: syscall_code.syscall_id(),
+        read_records,
+        write_records: vec![a3_record],
+        local_mem_access: rt.postprocess(),
+        buf_addr,
+        buf_len,
+        buf_records,
+    });
+    let syscall_event =
+        rt.rt.syscall_event(start_clk, None, rt.next_pc, syscall_code.syscall_id(), a0, a1);
+    rt.add_precompile_event(SyscallCode::SYS_LINUX, syscall_event, event);
+    v0
+}
@@ -33,6 +33,7 @@ impl Syscall for SysBrkSyscall {
             read_records: vec![record],
             write_records: vec![a3_record],
             local_mem_access: rt.postprocess(),
+            ..Default::default()
         });
         let syscall_event =
             rt.rt.syscall_event(start_clk, None, rt.next_pc, syscall_code.syscall_id(), a0, a1);
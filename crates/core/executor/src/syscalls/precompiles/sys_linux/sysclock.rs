@@ -0,0 +1,43 @@
+use crate::{
+    linux::{timespec, timespec64, TIMESPEC64_SIZE, TIMESPEC_SIZE},
+    syscalls::{Syscall, SyscallCode, SyscallContext},
+    ExecutionError,
+};
+
+use super::{check_buffer, finish_linux_syscall};
+
+/// Handles both `clock_gettime(clockid, tp)` and `clock_gettime64(clockid, tp)`, which differ in
+/// the width of the `struct timespec` fields.
+pub(crate) struct SysClockGettimeSyscall;
+
+impl Syscall for SysClockGettimeSyscall {
+    fn num_extra_cycles(&self) -> u32 {
+        0
+    }
+
+    fn execute(
+        &self,
+        rt: &mut SyscallContext,
+        syscall_code: SyscallCode,
+        a0: u32,
+        a1: u32,
+    ) -> Result<Option<u32>, ExecutionError> {
+        let is_time64 = syscall_code == SyscallCode::SYS_CLOCK_GETTIME64;
+        let size = if is_time64 { TIMESPEC64_SIZE } else { TIMESPEC_SIZE };
+        let global_clk = rt.rt.state.global_clk;
+        let result =
+            check_buffer(a1, size)
+                .and_then(|()| rt.rt.state.linux.clock_gettime(a0, global_clk))
+                .and_then(|(secs, nanos)| {
+                    if is_time64 {
+                        Ok(timespec64(secs, nanos))
+                    } else {
+                        timespec(secs, nanos)
+                    }
+                })
+                .map(|bytes| (0, Some((a1, bytes))));
+
+        let v0 = finish_linux_syscall(rt, syscall_code, a0, a1, result, vec![]);
+        Ok(Some(v0))
+    }
+}
@@ -0,0 +1,27 @@
+use crate::{
+    syscalls::{Syscall, SyscallCode, SyscallContext},
+    ExecutionError,
+};
+
+use super::finish_linux_syscall;
+
+pub(crate) struct SysCloseSyscall;
+
+impl Syscall for SysCloseSyscall {
+    fn num_extra_cycles(&self) -> u32 {
+        0
+    }
+
+    fn execute(
+        &self,
+        rt: &mut SyscallContext,
+        syscall_code: SyscallCode,
+        a0: u32,
+        a1: u32,
+    ) -> Result<Option<u32>, ExecutionError> {
+        let result = rt.rt.state.vfs.close(a0).map(|v0| (v0, None));
+
+        let v0 = finish_linux_syscall(rt, syscall_code, a0, a1, result, vec![]);
+        Ok(Some(v0))
+    }
+}
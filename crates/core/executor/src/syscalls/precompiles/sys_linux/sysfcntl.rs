@@ -36,8 +36,8 @@ impl Syscall for SysFcntlSyscall {
                     rt.rw_traced(Register::A3, 0)
                 }
                 _ => {
-                    v0 = 0xffffffff;
-                    rt.rw_traced(Register::A3, MIPS_EBADF)
+                    v0 = MIPS_EBADF;
+                    rt.rw_traced(Register::A3, 1)
                 }
             }
         } else if a1 == 1 {
@@ -48,13 +48,13 @@ impl Syscall for SysFcntlSyscall {
                     rt.rw_traced(Register::A3, 0)
                 }
                 _ => {
-                    v0 = 0xffffffff;
-                    rt.rw_traced(Register::A3, MIPS_EBADF)
+                    v0 = MIPS_EBADF;
+                    rt.rw_traced(Register::A3, 1)
                 }
             }
         } else {
-            v0 = 0xffffffff;
-            rt.rw_traced(Register::A3, MIPS_EBADF)
+            v0 = MIPS_EBADF;
+            rt.rw_traced(Register::A3, 1)
         };
 
         let shard = rt.current_shard();
@@ -68,6 +68,7 @@ impl Syscall for SysFcntlSyscall {
             read_records: vec![],
             write_records: vec![a3_record],
             local_mem_access: rt.postprocess(),
+            ..Default::default()
         });
         let syscall_event =
             rt.rt.syscall_event(start_clk, None, rt.next_pc, syscall_code.syscall_id(), a0, a1);
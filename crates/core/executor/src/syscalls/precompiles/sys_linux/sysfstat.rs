@@ -0,0 +1,33 @@
+use crate::{
+    syscalls::{Syscall, SyscallCode, SyscallContext},
+    vfs::{errno, STAT64_SIZE},
+    ExecutionError,
+};
+
+use super::{check_buffer, finish_linux_syscall};
+
+pub(crate) struct SysFstat64Syscall;
+
+impl Syscall for SysFstat64Syscall {
+    fn num_extra_cycles(&self) -> u32 {
+        0
+    }
+
+    fn execute(
+        &self,
+        rt: &mut SyscallContext,
+        syscall_code: SyscallCode,
+        a0: u32,
+        a1: u32,
+    ) -> Result<Option<u32>, ExecutionError> {
+        let fd = a0;
+        let buf = a1;
+        let result = check_buffer(buf, STAT64_SIZE)
+            .and_then(|()| if buf.is_multiple_of(4) { Ok(()) } else { Err(errno::EFAULT) })
+            .and_then(|()| rt.rt.state.vfs.stat(fd))
+            .map(|stat| (0, Some((buf, stat.to_vec()))));
+
+        let v0 = finish_linux_syscall(rt, syscall_code, a0, a1, result, vec![]);
+        Ok(Some(v0))
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{
+    linux::futex,
+    syscalls::{Syscall, SyscallCode, SyscallContext},
+    vfs::errno,
+    ExecutionError, Register,
+};
+
+use super::{check_buffer, finish_linux_syscall};
+
+/// Handles `futex(uaddr, op, val, timeout)` for a single-threaded process.
+pub(crate) struct SysFutexSyscall;
+
+impl Syscall for SysFutexSyscall {
+    fn num_extra_cycles(&self) -> u32 {
+        0
+    }
+
+    fn execute(
+        &self,
+        rt: &mut SyscallContext,
+        syscall_code: SyscallCode,
+        a0: u32,
+        a1: u32,
+    ) -> Result<Option<u32>, ExecutionError> {
+        let uaddr = a0;
+        let val = rt.register_unsafe(Register::A2);
+        let timeout = rt.register_unsafe(Register::A3);
+        let result = check_buffer(uaddr, 4)
+            .and_then(|()| if uaddr.is_multiple_of(4) { Ok(()) } else { Err(errno::EINVAL) })
+            .and_then(|()| futex(a1, rt.word_unsafe(uaddr), val, timeout != 0))
+            .map(|v0| (v0, None));
+
+        let v0 = finish_linux_syscall(rt, syscall_code, a0, a1, result, vec![]);
+        Ok(Some(v0))
+    }
+}
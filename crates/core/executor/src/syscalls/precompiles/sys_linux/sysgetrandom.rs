@@ -0,0 +1,34 @@
+use crate::{
+    syscalls::{Syscall, SyscallCode, SyscallContext},
+    vfs::MAX_READ_LEN,
+    ExecutionError, Register,
+};
+
+use super::{check_buffer, finish_linux_syscall};
+
+/// Handles `getrandom(buf, buflen, flags)`, serving the random stream of `crate::linux`.
+pub(crate) struct SysGetrandomSyscall;
+
+impl Syscall for SysGetrandomSyscall {
+    fn num_extra_cycles(&self) -> u32 {
+        0
+    }
+
+    fn execute(
+        &self,
+        rt: &mut SyscallContext,
+        syscall_code: SyscallCode,
+        a0: u32,
+        a1: u32,
+    ) -> Result<Option<u32>, ExecutionError> {
+        let buf = a0;
+        let len = a1.min(MAX_READ_LEN);
+        let flags = rt.register_unsafe(Register::A2);
+        let result = check_buffer(buf, len)
+            .and_then(|()| rt.rt.state.linux.getrandom(len, flags))
+            .map(|bytes| (bytes.len() as u32, Some((buf, bytes))));
+
+        let v0 = finish_linux_syscall(rt, syscall_code, a0, a1, result, vec![]);
+        Ok(Some(v0))
+    }
+}
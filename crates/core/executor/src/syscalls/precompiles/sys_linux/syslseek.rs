@@ -0,0 +1,28 @@
+use crate::{
+    syscalls::{Syscall, SyscallCode, SyscallContext},
+    ExecutionError, Register,
+};
+
+use super::finish_linux_syscall;
+
+pub(crate) struct SysLseekSyscall;
+
+impl Syscall for SysLseekSyscall {
+    fn num_extra_cycles(&self) -> u32 {
+        0
+    }
+
+    fn execute(
+        &self,
+        rt: &mut SyscallContext,
+        syscall_code: SyscallCode,
+        a0: u32,
+        a1: u32,
+    ) -> Result<Option<u32>, ExecutionError> {
+        let whence = rt.register_unsafe(Register::A2);
+        let result = rt.rt.state.vfs.lseek(a0, a1, whence).map(|offset| (offset, None));
+
+        let v0 = finish_linux_syscall(rt, syscall_code, a0, a1, result, vec![]);
+        Ok(Some(v0))
+    }
+}
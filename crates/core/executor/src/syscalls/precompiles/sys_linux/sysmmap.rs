@@ -50,6 +50,7 @@ impl Syscall for SysMmapSyscall {
             read_records: vec![],
             write_records,
             local_mem_access: rt.postprocess(),
+            ..Default::default()
         });
         let syscall_event =
             rt.rt.syscall_event(start_clk, None, rt.next_pc, syscall_code.syscall_id(), a0, a1);
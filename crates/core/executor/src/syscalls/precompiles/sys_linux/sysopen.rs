@@ -0,0 +1,55 @@
+use crate::{
+    syscalls::{Syscall, SyscallCode, SyscallContext},
+    vfs::errno,
+    ExecutionError, Register,
+};
+
+use super::finish_linux_syscall;
+
+/// The maximum length of a path, including the terminating NUL byte.
+const PATH_MAX: u32 = 4096;
+
+/// AT_FDCWD, the `dirfd` implied by `open`.
+const AT_FDCWD: u32 = -100i32 as u32;
+
+/// Handles both `open(path, flags)` and `openat(dirfd, path, flags)`.
+pub(crate) struct SysOpenSyscall;
+
+impl Syscall for SysOpenSyscall {
+    fn num_extra_cycles(&self) -> u32 {
+        0
+    }
+
+    fn execute(
+        &self,
+        rt: &mut SyscallContext,
+        syscall_code: SyscallCode,
+        a0: u32,
+        a1: u32,
+    ) -> Result<Option<u32>, ExecutionError> {
+        let (dirfd, path_ptr, flags) = if syscall_code == SyscallCode::SYS_OPENAT {
+            (a0, a1, rt.register_unsafe(Register::A2))
+        } else {
+            (AT_FDCWD, a0, a1)
+        };
+
+        let result = read_path(rt, path_ptr)
+            .and_then(|path| rt.rt.state.vfs.open(dirfd, &path, flags))
+            .map(|fd| (fd, None));
+
+        let v0 = finish_linux_syscall(rt, syscall_code, a0, a1, result, vec![]);
+        Ok(Some(v0))
+    }
+}
+
+/// Read a NUL-terminated path from guest memory.
+fn read_path(rt: &mut SyscallContext, ptr: u32) -> Result<String, u32> {
+    let mut path = Vec::new();
+    for i in 0..PATH_MAX {
+        match rt.byte_unsafe(ptr.wrapping_add(i)) {
+            0 => return String::from_utf8(path).map_err(|_| errno::ENOENT),
+            byte => path.push(byte),
+        }
+    }
+    Err(errno::ENAMETOOLONG)
+}
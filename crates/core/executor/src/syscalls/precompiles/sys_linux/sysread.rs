@@ -1,11 +1,13 @@
 use crate::{
-    events::{LinuxEvent, PrecompileEvent},
     syscalls::{Syscall, SyscallCode, SyscallContext},
-    ExecutionError, Register,
+    vfs::MAX_READ_LEN,
+    ExecutionError, ExecutionState, Register,
 };
+
+use super::{check_buffer, finish_linux_syscall};
+
 pub use zkm_primitives::consts::fd::*;
 
-pub const MIPS_EBADF: u32 = 9;
 pub(crate) struct SysReadSyscall;
 
 impl Syscall for SysReadSyscall {
@@ -20,31 +22,39 @@ impl Syscall for SysReadSyscall {
         a0: u32,
         a1: u32,
     ) -> Result<Option<u32>, ExecutionError> {
-        let start_clk = rt.clk;
+        let (record, count) = rt.rr_traced(Register::A2);
         let fd = a0;
-        let mut v0 = 0;
-        let a3_record = if fd != FD_STDIN {
-            v0 = 0xffffffff; // Return error for non-stdin reads.
-            rt.rw_traced(Register::A3, MIPS_EBADF)
-        } else {
-            rt.rw_traced(Register::A3, 0)
-        };
-
-        let shard = rt.current_shard();
-        let event = PrecompileEvent::Linux(LinuxEvent {
-            shard,
-            clk: start_clk,
-            a0,
-            a1,
-            v0,
-            syscall_code: syscall_code.syscall_id(),
-            read_records: vec![],
-            write_records: vec![a3_record],
-            local_mem_access: rt.postprocess(),
+        let buf = a1;
+        let len = count.min(MAX_READ_LEN);
+
+        let result = check_buffer(buf, len).and_then(|()| {
+            let bytes = if fd == FD_STDIN {
+                read_stdin(&mut rt.rt.state, len)
+            } else {
+                rt.rt.state.vfs.read(fd, len)?
+            };
+            Ok((bytes.len() as u32, Some((buf, bytes))))
         });
-        let syscall_event =
-            rt.rt.syscall_event(start_clk, None, rt.next_pc, syscall_code.syscall_id(), a0, a1);
-        rt.add_precompile_event(SyscallCode::SYS_LINUX, syscall_event, event);
+
+        let v0 = finish_linux_syscall(rt, syscall_code, a0, a1, result, vec![record]);
         Ok(Some(v0))
     }
 }
+
+/// Read up to `len` bytes from stdin, draining the input stream entries in order.
+fn read_stdin(state: &mut ExecutionState, len: u32) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    while bytes.len() < len as usize && state.input_stream_ptr < state.input_stream.len() {
+        let entry = &state.input_stream[state.input_stream_ptr];
+        let start = state.vfs.stdin_offset;
+        let end = entry.len().min(start + len as usize - bytes.len());
+        bytes.extend_from_slice(&entry[start..end]);
+        if end == entry.len() {
+            state.input_stream_ptr += 1;
+            state.vfs.stdin_offset = 0;
+        } else {
+            state.vfs.stdin_offset = end;
+        }
+    }
+    bytes
+}
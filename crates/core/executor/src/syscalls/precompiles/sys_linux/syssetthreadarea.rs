@@ -0,0 +1,44 @@
+use crate::{
+    events::{LinuxEvent, PrecompileEvent},
+    syscalls::{Syscall, SyscallCode, SyscallContext},
+    ExecutionError, Register,
+};
+
+/// Sets the thread pointer read by `rdhwr $29` to a0.
+pub(crate) struct SysSetThreadAreaSyscall;
+
+impl Syscall for SysSetThreadAreaSyscall {
+    fn num_extra_cycles(&self) -> u32 {
+        0
+    }
+
+    fn execute(
+        &self,
+        rt: &mut SyscallContext,
+        syscall_code: SyscallCode,
+        a0: u32,
+        a1: u32,
+    ) -> Result<Option<u32>, ExecutionError> {
+        let v0 = 0;
+        let start_clk = rt.clk;
+        let a3_record = rt.rw_traced(Register::A3, 0);
+        let tp_record = rt.rw_traced(Register::LOCAL_USER, a0);
+        let shard = rt.current_shard();
+        let event = PrecompileEvent::Linux(LinuxEvent {
+            shard,
+            clk: start_clk,
+            a0,
+            a1,
+            v0,
+            syscall_code: syscall_code.syscall_id(),
+            read_records: vec![],
+            write_records: vec![a3_record, tp_record],
+            local_mem_access: rt.postprocess(),
+            ..Default::default()
+        });
+        let syscall_event =
+            rt.rt.syscall_event(start_clk, None, rt.next_pc, syscall_code.syscall_id(), a0, a1);
+        rt.add_precompile_event(SyscallCode::SYS_LINUX, syscall_event, event);
+        Ok(Some(v0))
+    }
+}
@@ -0,0 +1,29 @@
+use crate::{
+    linux::{utsname, UTSNAME_SIZE},
+    syscalls::{Syscall, SyscallCode, SyscallContext},
+    ExecutionError,
+};
+
+use super::{check_buffer, finish_linux_syscall};
+
+/// Handles `uname(buf)`.
+pub(crate) struct SysUnameSyscall;
+
+impl Syscall for SysUnameSyscall {
+    fn num_extra_cycles(&self) -> u32 {
+        0
+    }
+
+    fn execute(
+        &self,
+        rt: &mut SyscallContext,
+        syscall_code: SyscallCode,
+        a0: u32,
+        a1: u32,
+    ) -> Result<Option<u32>, ExecutionError> {
+        let result = check_buffer(a0, UTSNAME_SIZE).map(|()| (0, Some((a0, utsname().to_vec()))));
+
+        let v0 = finish_linux_syscall(rt, syscall_code, a0, a1, result, vec![]);
+        Ok(Some(v0))
+    }
+}
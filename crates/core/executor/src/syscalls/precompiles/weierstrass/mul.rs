@@ -0,0 +1,64 @@
+use std::marker::PhantomData;
+
+use zkm_curves::{CurveType, EllipticCurve};
+
+use crate::{
+    events::{create_ec_mul_event, PrecompileEvent},
+    syscalls::{Syscall, SyscallCode, SyscallContext},
+    ExecutionError,
+};
+
+pub(crate) struct WeierstrassMulAssignSyscall<E: EllipticCurve> {
+    _phantom: PhantomData<E>,
+}
+
+impl<E: EllipticCurve> WeierstrassMulAssignSyscall<E> {
+    /// Create a new instance of the [`WeierstrassMulAssignSyscall`].
+    pub const fn new() -> Self {
+        Self { _phantom: PhantomData }
+    }
+}
+
+impl<E: EllipticCurve> Syscall for WeierstrassMulAssignSyscall<E> {
+    fn execute(
+        &self,
+        rt: &mut SyscallContext,
+        syscall_code: SyscallCode,
+        arg1: u32,
+        arg2: u32,
+    ) -> Result<Option<u32>, ExecutionError> {
+        let event = create_ec_mul_event::<E>(rt, arg1, arg2);
+        let syscall_event =
+            rt.rt.syscall_event(event.clk, None, rt.next_pc, syscall_code.syscall_id(), arg1, arg2);
+        match E::CURVE_TYPE {
+            CurveType::Secp256k1 => rt.add_precompile_event(
+                syscall_code,
+                syscall_event,
+                PrecompileEvent::Secp256k1Mul(event),
+            ),
+            CurveType::Bn254 => {
+                rt.add_precompile_event(
+                    syscall_code,
+                    syscall_event,
+                    PrecompileEvent::Bn254Mul(event),
+                );
+            }
+            CurveType::Bls12381 => rt.add_precompile_event(
+                syscall_code,
+                syscall_event,
+                PrecompileEvent::Bls12381Mul(event),
+            ),
+            CurveType::Secp256r1 => rt.record_mut().add_precompile_event(
+                syscall_code,
+                syscall_event,
+                PrecompileEvent::Secp256r1Mul(event),
+            ),
+            _ => panic!("Unsupported curve"),
+        }
+        Ok(None)
+    }
+
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+}
@@ -48,6 +48,47 @@ pub const fn get_msb(a: u32) -> u8 {
     ((a >> 31) & 1) as u8
 }
 
+/// Returns the two values a branch compares.
+///
+/// Link branches write `op_a` and compare `op_b` with zero, the others compare `op_a` and `op_b`.
+#[must_use]
+pub fn branch_operands(opcode: Opcode, a: u32, b: u32) -> (u32, u32) {
+    if opcode.is_branch_link() {
+        (b, 0)
+    } else {
+        (a, b)
+    }
+}
+
+/// Returns whether a branch is taken, given the two values it compares.
+#[must_use]
+pub fn is_branch_taken(opcode: Opcode, lhs: u32, rhs: u32) -> bool {
+    let (lhs_signed, rhs_signed) = (lhs as i32, rhs as i32);
+    match opcode {
+        Opcode::BEQ | Opcode::BEQL | Opcode::BEQC | Opcode::BEQZALC => lhs == rhs,
+        Opcode::BNE | Opcode::BNEL | Opcode::BNEC | Opcode::BNEZALC => lhs != rhs,
+        Opcode::BLTZ
+        | Opcode::BLTZL
+        | Opcode::BLTZAL
+        | Opcode::BLTZALL
+        | Opcode::BLTC
+        | Opcode::BLTZALC => lhs_signed < rhs_signed,
+        Opcode::BGEZ
+        | Opcode::BGEZL
+        | Opcode::BGEZAL
+        | Opcode::BGEZALL
+        | Opcode::BGEC
+        | Opcode::BGEZALC => lhs_signed >= rhs_signed,
+        Opcode::BLEZ | Opcode::BLEZL | Opcode::BLEZALC => lhs_signed <= rhs_signed,
+        Opcode::BGTZ | Opcode::BGTZL | Opcode::BGTZALC => lhs_signed > rhs_signed,
+        Opcode::BLTUC => lhs < rhs,
+        Opcode::BGEUC => lhs >= rhs,
+        Opcode::BOVC => lhs_signed.checked_add(rhs_signed).is_none(),
+        Opcode::BNVC => lhs_signed.checked_add(rhs_signed).is_some(),
+        _ => unreachable!("{opcode} is not a branch"),
+    }
+}
+
 /// Load the cost of each air from the predefined JSON.
 #[must_use]
 pub fn mips_costs() -> HashMap<MipsAirId, usize> {
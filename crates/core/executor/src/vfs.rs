@@ -0,0 +1,236 @@
+//! A read-only virtual filesystem backing the Linux file syscalls.
+//!
+//! The host registers named files through `ZKMStdin::write_file`. The guest can then `open`,
+//! `read`, `lseek`, `fstat64` and `close` them like regular files. File contents are untrusted
+//! input to the program, exactly like the bytes read through `zkm_zkvm::io::read`.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use zkm_primitives::consts::fd::{FD_STDERR, FD_STDIN, FD_STDOUT};
+
+/// The first file descriptor handed out by `open`. It is above all the file descriptors that have
+/// a special meaning for `write` (see `zkm_primitives::consts::fd`).
+pub const FD_VFS_START: u32 = 64;
+
+/// The maximum number of files the guest can have open at once, like the default `RLIMIT_NOFILE`
+/// of Linux. Further `open` calls fail with `EMFILE`.
+pub const MAX_OPEN_FILES: usize = 1024;
+
+/// The maximum number of bytes a single `read` syscall transfers. Larger reads are shortened,
+/// which Linux allows, so that the number of trace rows per syscall stays bounded.
+pub const MAX_READ_LEN: u32 = 4096;
+
+/// The size of the MIPS o32 `struct stat64` written by `fstat64`.
+pub const STAT64_SIZE: u32 = 104;
+
+/// Errno values returned by the Linux syscalls, as numbered by the MIPS ABI.
+pub mod errno {
+    /// No such file or directory.
+    pub const ENOENT: u32 = 2;
+    /// Bad file descriptor.
+    pub const EBADF: u32 = 9;
+    /// Resource temporarily unavailable.
+    pub const EAGAIN: u32 = 11;
+    /// Bad address.
+    pub const EFAULT: u32 = 14;
+    /// Invalid argument.
+    pub const EINVAL: u32 = 22;
+    /// Too many open files.
+    pub const EMFILE: u32 = 24;
+    /// Illegal seek.
+    pub const ESPIPE: u32 = 29;
+    /// Read-only file system.
+    pub const EROFS: u32 = 30;
+    /// File name too long. MIPS numbers the errno values above 34 differently from other
+    /// architectures.
+    pub const ENAMETOOLONG: u32 = 78;
+    /// Value too large for the data type.
+    pub const EOVERFLOW: u32 = 79;
+    /// Function not implemented.
+    pub const ENOSYS: u32 = 89;
+    /// Connection timed out.
+    pub const ETIMEDOUT: u32 = 145;
+}
+
+const O_ACCMODE: u32 = 0o3;
+const O_RDONLY: u32 = 0;
+const AT_FDCWD: u32 = -100i32 as u32;
+
+const SEEK_SET: u32 = 0;
+const SEEK_CUR: u32 = 1;
+const SEEK_END: u32 = 2;
+
+const S_IFCHR: u32 = 0o020000;
+const S_IFREG: u32 = 0o100000;
+
+/// A file opened by the guest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenFile {
+    /// The normalized path of the file.
+    pub path: String,
+    /// The current read offset.
+    pub offset: u32,
+}
+
+/// The files registered by the host and the file descriptors opened by the guest.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VirtualFs {
+    /// The file contents, keyed by normalized path.
+    pub files: BTreeMap<String, Vec<u8>>,
+    /// The open file descriptors.
+    pub open_files: BTreeMap<u32, OpenFile>,
+    /// The number of bytes of the current input stream entry already consumed by reads from
+    /// stdin.
+    pub stdin_offset: usize,
+}
+
+/// Normalize a path so that `/a/./b`, `a/b` and `a//b` all refer to the same file.
+#[must_use]
+pub fn normalize_path(path: &str) -> String {
+    path.split('/').filter(|s| !s.is_empty() && *s != ".").collect::<Vec<_>>().join("/")
+}
+
+impl VirtualFs {
+    /// Register a file, replacing any file previously registered under the same path.
+    pub fn insert(&mut self, path: &str, contents: Vec<u8>) {
+        self.files.insert(normalize_path(path), contents);
+    }
+
+    /// Open the file at `path`, returning the new file descriptor or an errno.
+    ///
+    /// `dirfd` is the directory file descriptor of `openat`. Directories cannot be opened, so
+    /// relative paths are only accepted with `AT_FDCWD`.
+    pub fn open(&mut self, dirfd: u32, path: &str, flags: u32) -> Result<u32, u32> {
+        if dirfd != AT_FDCWD && !path.starts_with('/') {
+            return Err(errno::EBADF);
+        }
+        let path = normalize_path(path);
+        if !self.files.contains_key(&path) {
+            return Err(errno::ENOENT);
+        }
+        if flags & O_ACCMODE != O_RDONLY {
+            return Err(errno::EROFS);
+        }
+
+        if self.open_files.len() >= MAX_OPEN_FILES {
+            return Err(errno::EMFILE);
+        }
+        let fd = (FD_VFS_START..=u32::MAX)
+            .find(|fd| !self.open_files.contains_key(fd))
+            .ok_or(errno::EMFILE)?;
+        self.open_files.insert(fd, OpenFile { path, offset: 0 });
+        Ok(fd)
+    }
+
+    /// Close a file descriptor.
+    pub fn close(&mut self, fd: u32) -> Result<u32, u32> {
+        match fd {
+            FD_STDIN | FD_STDOUT | FD_STDERR => Ok(0),
+            _ => self.open_files.remove(&fd).map(|_| 0).ok_or(errno::EBADF),
+        }
+    }
+
+    /// Read up to `len` bytes from an open file, advancing its offset.
+    ///
+    /// Reads from stdin are served from the input stream by the caller.
+    pub fn read(&mut self, fd: u32, len: u32) -> Result<Vec<u8>, u32> {
+        let file = self.open_files.get_mut(&fd).ok_or(errno::EBADF)?;
+        let contents = &self.files[&file.path];
+        let start = (file.offset as usize).min(contents.len());
+        let end = start.saturating_add(len as usize).min(contents.len());
+        file.offset += (end - start) as u32;
+        Ok(contents[start..end].to_vec())
+    }
+
+    /// Reposition the offset of an open file, returning the new offset.
+    pub fn lseek(&mut self, fd: u32, offset: u32, whence: u32) -> Result<u32, u32> {
+        let file = match self.open_files.get_mut(&fd) {
+            Some(file) => file,
+            None if fd <= FD_STDERR => return Err(errno::ESPIPE),
+            None => return Err(errno::EBADF),
+        };
+        let base = match whence {
+            SEEK_SET => 0,
+            SEEK_CUR => file.offset as i64,
+            SEEK_END => self.files[&file.path].len() as i64,
+            _ => return Err(errno::EINVAL),
+        };
+        let new_offset = base + offset as i32 as i64;
+        if !(0..=i32::MAX as i64).contains(&new_offset) {
+            return Err(errno::EINVAL);
+        }
+        file.offset = new_offset as u32;
+        Ok(file.offset)
+    }
+
+    /// Build the MIPS o32 `struct stat64` of an open file descriptor.
+    pub fn stat(&self, fd: u32) -> Result<[u8; STAT64_SIZE as usize], u32> {
+        let (mode, size, ino) = match fd {
+            FD_STDIN | FD_STDOUT | FD_STDERR => (S_IFCHR | 0o620, 0, fd as u64 + 1),
+            _ => {
+                let file = self.open_files.get(&fd).ok_or(errno::EBADF)?;
+                let ino = self.files.keys().position(|path| path == &file.path).unwrap();
+                (S_IFREG | 0o444, self.files[&file.path].len() as u64, ino as u64 + 16)
+            }
+        };
+
+        let mut stat = [0u8; STAT64_SIZE as usize];
+        let mut put = |offset: usize, bytes: &[u8]| {
+            stat[offset..offset + bytes.len()].copy_from_slice(bytes);
+        };
+        put(16, &ino.to_le_bytes()); // st_ino
+        put(24, &mode.to_le_bytes()); // st_mode
+        put(28, &1u32.to_le_bytes()); // st_nlink
+        put(56, &size.to_le_bytes()); // st_size
+        put(88, &4096u32.to_le_bytes()); // st_blksize
+        put(96, &size.div_ceil(512).to_le_bytes()); // st_blocks
+        Ok(stat)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_read_seek() {
+        let mut vfs = VirtualFs::default();
+        vfs.insert("/data/input.txt", b"hello world".to_vec());
+
+        assert_eq!(vfs.open(AT_FDCWD, "missing", 0), Err(errno::ENOENT));
+        assert_eq!(vfs.open(AT_FDCWD, "data/input.txt", 1), Err(errno::EROFS));
+
+        let fd = vfs.open(AT_FDCWD, "./data//input.txt", 0).unwrap();
+        assert_eq!(fd, FD_VFS_START);
+        assert_eq!(vfs.read(fd, 5).unwrap(), b"hello");
+        assert_eq!(vfs.lseek(fd, 1, SEEK_CUR), Ok(6));
+        assert_eq!(vfs.read(fd, 100).unwrap(), b"world");
+        assert_eq!(vfs.read(fd, 100).unwrap(), b"");
+        assert_eq!(vfs.lseek(fd, -5i32 as u32, SEEK_END), Ok(6));
+        assert_eq!(vfs.lseek(fd, -1i32 as u32, SEEK_SET), Err(errno::EINVAL));
+        assert_eq!(vfs.lseek(FD_STDIN, 0, SEEK_SET), Err(errno::ESPIPE));
+
+        let stat = vfs.stat(fd).unwrap();
+        assert_eq!(u32::from_le_bytes(stat[24..28].try_into().unwrap()), S_IFREG | 0o444);
+        assert_eq!(u64::from_le_bytes(stat[56..64].try_into().unwrap()), 11);
+
+        assert_eq!(vfs.close(fd), Ok(0));
+        assert_eq!(vfs.close(fd), Err(errno::EBADF));
+        assert_eq!(vfs.read(fd, 1), Err(errno::EBADF));
+    }
+
+    #[test]
+    fn test_too_many_open_files() {
+        let mut vfs = VirtualFs::default();
+        vfs.insert("input", vec![]);
+        for i in 0..MAX_OPEN_FILES as u32 {
+            assert_eq!(vfs.open(AT_FDCWD, "input", 0), Ok(FD_VFS_START + i));
+        }
+        assert_eq!(vfs.open(AT_FDCWD, "input", 0), Err(errno::EMFILE));
+
+        // Closing a file frees its descriptor for the next `open`.
+        assert_eq!(vfs.close(FD_VFS_START + 1), Ok(0));
+        assert_eq!(vfs.open(AT_FDCWD, "input", 0), Ok(FD_VFS_START + 1));
+    }
+}
@@ -50,9 +50,12 @@ __ZKM_HOSTDEV__ void event_to_row(
 
     populate_instruction<F>(cols.instruction, instruction);
 
+    // Link branches write `op_a`, compact ones only when taken.
+    bool writes_a = event.a_record.tag == OptionMemoryRecordEnumTag::Write;
     cols.op_a_immutable = F::from_bool(
         is_memory_store_instruction_except_sc(instruction)
-            || is_branch_instruction(instruction)
+            || is_trap_instruction(instruction)
+            || (is_branch_instruction(instruction) && !writes_a)
     );
 
     cols.is_rw_a = F::from_bool(is_rw_a_instruction(instruction));
@@ -102,6 +105,7 @@ __ZKM_HOSTDEV__ void event_to_row(
             && !is_branch_instruction(instruction)
             && !is_jump_instruction(instruction)
     );
+    cols.is_redirect = F::from_bool(is_redirect_instruction(instruction));
 
     // Assert that the instruction is not a no-op.
     cols.is_real = F::one();
@@ -16,6 +16,30 @@ __ZKM_HOSTDEV__ bool is_branch_instruction(const InstructionFfi& instruction) {
         case Opcode::BGEZ:
         case Opcode::BLEZ:
         case Opcode::BGTZ:
+        case Opcode::BLTZAL:
+        case Opcode::BGEZAL:
+        case Opcode::BEQL:
+        case Opcode::BNEL:
+        case Opcode::BLEZL:
+        case Opcode::BGTZL:
+        case Opcode::BLTZL:
+        case Opcode::BGEZL:
+        case Opcode::BLTZALL:
+        case Opcode::BGEZALL:
+        case Opcode::BEQC:
+        case Opcode::BNEC:
+        case Opcode::BLTC:
+        case Opcode::BGEC:
+        case Opcode::BLTUC:
+        case Opcode::BGEUC:
+        case Opcode::BLEZALC:
+        case Opcode::BGEZALC:
+        case Opcode::BGTZALC:
+        case Opcode::BLTZALC:
+        case Opcode::BEQZALC:
+        case Opcode::BNEZALC:
+        case Opcode::BOVC:
+        case Opcode::BNVC:
             return true;
         default:
             return false;
@@ -27,6 +51,52 @@ __ZKM_HOSTDEV__ bool is_jump_instruction(const InstructionFfi& instruction) {
         case Opcode::Jump:
         case Opcode::Jumpi:
         case Opcode::JumpDirect:
+        case Opcode::JumpCompact:
+            return true;
+        default:
+            return false;
+    }
+}
+
+__ZKM_HOSTDEV__ bool is_trap_instruction(const InstructionFfi& instruction) {
+    switch (instruction.opcode) {
+        case Opcode::TEQ:
+        case Opcode::TNE:
+        case Opcode::TGE:
+        case Opcode::TGEU:
+        case Opcode::TLT:
+        case Opcode::TLTU:
+            return true;
+        default:
+            return false;
+    }
+}
+
+__ZKM_HOSTDEV__ bool is_redirect_instruction(const InstructionFfi& instruction) {
+    switch (instruction.opcode) {
+        case Opcode::BEQL:
+        case Opcode::BNEL:
+        case Opcode::BLEZL:
+        case Opcode::BGTZL:
+        case Opcode::BLTZL:
+        case Opcode::BGEZL:
+        case Opcode::BLTZALL:
+        case Opcode::BGEZALL:
+        case Opcode::BEQC:
+        case Opcode::BNEC:
+        case Opcode::BLTC:
+        case Opcode::BGEC:
+        case Opcode::BLTUC:
+        case Opcode::BGEUC:
+        case Opcode::BLEZALC:
+        case Opcode::BGEZALC:
+        case Opcode::BGTZALC:
+        case Opcode::BLTZALC:
+        case Opcode::BEQZALC:
+        case Opcode::BNEZALC:
+        case Opcode::BOVC:
+        case Opcode::BNVC:
+        case Opcode::JumpCompact:
             return true;
         default:
             return false;
@@ -40,6 +110,8 @@ __ZKM_HOSTDEV__ bool is_check_memory_instruction(const InstructionFfi& instructi
         case Opcode::MSUBU:
         case Opcode::MADD:
         case Opcode::MSUB:
+        case Opcode::LSA:
+        case Opcode::ALIGN:
         case Opcode::LH:
         case Opcode::LWL:
         case Opcode::LW:
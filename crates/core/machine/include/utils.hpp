@@ -119,6 +119,30 @@ namespace opcode_utils {
             case Opcode::BGEZ:
             case Opcode::BLEZ:
             case Opcode::BGTZ:
+            case Opcode::BLTZAL:
+            case Opcode::BGEZAL:
+            case Opcode::BEQL:
+            case Opcode::BNEL:
+            case Opcode::BLEZL:
+            case Opcode::BGTZL:
+            case Opcode::BLTZL:
+            case Opcode::BGEZL:
+            case Opcode::BLTZALL:
+            case Opcode::BGEZALL:
+            case Opcode::BEQC:
+            case Opcode::BNEC:
+            case Opcode::BLTC:
+            case Opcode::BGEC:
+            case Opcode::BLTUC:
+            case Opcode::BGEUC:
+            case Opcode::BLEZALC:
+            case Opcode::BGEZALC:
+            case Opcode::BGTZALC:
+            case Opcode::BLTZALC:
+            case Opcode::BEQZALC:
+            case Opcode::BNEZALC:
+            case Opcode::BOVC:
+            case Opcode::BNVC:
                 return true;
             default:
                 return false;
@@ -130,6 +154,7 @@ namespace opcode_utils {
             case Opcode::Jump:
             case Opcode::Jumpi:
             case Opcode::JumpDirect:
+            case Opcode::JumpCompact:
                 return true;
             default:
                 return false;
@@ -212,6 +212,7 @@ where
             AB::Expr::zero(),
             AB::Expr::zero(),
             AB::Expr::one(),
+            AB::Expr::zero(),
             local.is_add,
         );
 
@@ -233,6 +234,7 @@ where
             AB::Expr::zero(),
             AB::Expr::zero(),
             AB::Expr::one(),
+            AB::Expr::zero(),
             local.is_sub,
         );
 
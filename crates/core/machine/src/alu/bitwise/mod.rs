@@ -222,6 +222,7 @@ where
             AB::Expr::zero(),
             AB::Expr::zero(),
             AB::Expr::one(),
+            AB::Expr::zero(),
             local.is_xor + local.is_or + local.is_and + local.is_nor,
         );
 
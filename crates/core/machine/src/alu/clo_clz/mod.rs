@@ -230,6 +230,7 @@ where
             AB::Expr::zero(),
             AB::Expr::zero(),
             AB::Expr::one(),
+            AB::Expr::zero(),
             local.is_real,
         );
 
@@ -710,6 +710,7 @@ where
                 AB::Expr::one(),
                 AB::Expr::zero(),
                 AB::Expr::one(),
+                AB::Expr::zero(),
                 local.is_div + local.is_divu,
             );
 
@@ -730,6 +731,7 @@ where
                 AB::Expr::zero(),
                 AB::Expr::zero(),
                 AB::Expr::one(),
+                AB::Expr::zero(),
                 local.is_mod + local.is_modu,
             );
 
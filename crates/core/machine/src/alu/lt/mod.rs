@@ -462,6 +462,7 @@ where
             AB::Expr::zero(),
             AB::Expr::zero(),
             AB::Expr::one(),
+            AB::Expr::zero(),
             is_real,
         );
     }
@@ -69,7 +69,7 @@ const BYTE_SIZE: usize = 8;
 /// The mask for a byte.
 const BYTE_MASK: u8 = 0xff;
 
-/// A chip that implements multiplication for the opcode MUL, MULT and MULTU.
+/// A chip that implements multiplication for the opcode MUL, MULT, MULTU, MUH and MUHU.
 #[derive(Default)]
 pub struct MulChip;
 
@@ -120,6 +120,12 @@ pub struct MulCols<T> {
     /// Flag indicating whether the opcode is `MULTU`.
     pub is_multu: T,
 
+    /// Flag indicating whether the opcode is `MUH`.
+    pub is_muh: T,
+
+    /// Flag indicating whether the opcode is `MUHU`.
+    pub is_muhu: T,
+
     /// Selector to know whether this row is enabled.
     pub is_real: T,
 
@@ -252,14 +258,16 @@ impl MulChip {
             let c_msb = get_msb(c_word);
             cols.c_msb = F::from_canonical_u8(c_msb);
 
+            let is_signed = matches!(event.opcode, Opcode::MULT | Opcode::MUH);
+
             // If b is signed and it is negative, sign extend b.
-            if event.opcode == Opcode::MULT && b_msb == 1 {
+            if is_signed && b_msb == 1 {
                 cols.b_sign_extend = F::ONE;
                 b.resize(PRODUCT_SIZE, BYTE_MASK);
             }
 
             // If c is signed and it is negative, sign extend c.
-            if event.opcode == Opcode::MULT && c_msb == 1 {
+            if is_signed && c_msb == 1 {
                 cols.c_sign_extend = F::ONE;
                 c.resize(PRODUCT_SIZE, BYTE_MASK);
             }
@@ -313,6 +321,8 @@ impl MulChip {
         cols.is_mul = F::from_bool(event.opcode == Opcode::MUL);
         cols.is_mult = F::from_bool(event.opcode == Opcode::MULT);
         cols.is_multu = F::from_bool(event.opcode == Opcode::MULTU);
+        cols.is_muh = F::from_bool(event.opcode == Opcode::MUH);
+        cols.is_muhu = F::from_bool(event.opcode == Opcode::MUHU);
 
         // Range check.
         {
@@ -357,8 +367,8 @@ where
 
         // Calculate whether to extend b and c's sign.
         let (b_sign_extend, c_sign_extend) = {
-            let is_b_i32 = local.is_mult;
-            let is_c_i32 = local.is_mult;
+            let is_b_i32 = local.is_mult + local.is_muh;
+            let is_c_i32 = local.is_mult + local.is_muh;
 
             builder.assert_eq(local.b_sign_extend, is_b_i32 * b_msb);
             builder.assert_eq(local.c_sign_extend, is_c_i32 * c_msb);
@@ -409,8 +419,10 @@ where
         // Compare the product's appropriate bytes with that of the result.
         {
             let has_hi = local.is_mult + local.is_multu;
+            let is_upper = local.is_muh + local.is_muhu;
             for i in 0..WORD_SIZE {
-                builder.assert_eq(product[i], local.a[i]);
+                builder.when_not(is_upper.clone()).assert_eq(product[i], local.a[i]);
+                builder.when(is_upper.clone()).assert_eq(product[i + WORD_SIZE], local.a[i]);
                 builder.when(has_hi.clone()).assert_eq(product[i + WORD_SIZE], local.hi[i]);
             }
         }
@@ -425,6 +437,8 @@ where
                 local.is_mul,
                 local.is_mult,
                 local.is_multu,
+                local.is_muh,
+                local.is_muhu,
                 local.is_real,
                 local.hi_record_is_real,
             ];
@@ -440,12 +454,20 @@ where
         // Calculate the opcode.
         let opcode = {
             // Exactly one of the op codes must be on.
-            builder.when(local.is_real).assert_one(local.is_mul + local.is_mult + local.is_multu);
+            builder.when(local.is_real).assert_one(
+                local.is_mul + local.is_mult + local.is_multu + local.is_muh + local.is_muhu,
+            );
 
             let mul: AB::Expr = AB::F::from_canonical_u32(Opcode::MUL as u32).into();
             let mult: AB::Expr = AB::F::from_canonical_u32(Opcode::MULT as u32).into();
             let multu: AB::Expr = AB::F::from_canonical_u32(Opcode::MULTU as u32).into();
-            local.is_mul * mul + local.is_mult * mult + local.is_multu * multu
+            let muh: AB::Expr = AB::F::from_canonical_u32(Opcode::MUH as u32).into();
+            let muhu: AB::Expr = AB::F::from_canonical_u32(Opcode::MUHU as u32).into();
+            local.is_mul * mul
+                + local.is_mult * mult
+                + local.is_multu * multu
+                + local.is_muh * muh
+                + local.is_muhu * muhu
         };
 
         // Range check.
@@ -476,6 +498,7 @@ where
             local.hi_record_is_real,
             AB::Expr::zero(),
             AB::Expr::one(),
+            AB::Expr::zero(),
             local.is_real,
         );
 
@@ -549,6 +572,11 @@ mod tests {
             (Opcode::MUL, 0x00000001, 0xffffffff, 0xffffffff),
             (Opcode::MUL, 0xffffffff, 0xffffffff, 0x00000001),
             (Opcode::MUL, 0xffffffff, 0x00000001, 0xffffffff),
+            (Opcode::MUH, 0xffffffff, 0xffffffff, 0x00000001),
+            (Opcode::MUH, 0x00000000, 0xffffffff, 0xffffffff),
+            (Opcode::MUH, 0x40000000, 0x80000000, 0x80000000),
+            (Opcode::MUHU, 0x00000000, 0xffffffff, 0x00000001),
+            (Opcode::MUHU, 0xfffffffe, 0xffffffff, 0xffffffff),
         ];
         for t in mul_instructions.iter() {
             mul_events.push(CompAluEvent::new(0, t.0, t.1, t.2, t.3));
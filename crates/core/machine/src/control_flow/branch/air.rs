@@ -3,7 +3,7 @@ use std::borrow::Borrow;
 use p3_air::{Air, AirBuilder};
 use p3_field::FieldAlgebra;
 use p3_matrix::Matrix;
-use zkm_core_executor::Opcode;
+use zkm_core_executor::{ByteOpcode, Opcode};
 use zkm_stark::{
     air::{BaseAirBuilder, ZKMAirBuilder},
     Word,
@@ -16,11 +16,15 @@ use super::{BranchChip, BranchColumns};
 /// Verifies all the branching related columns.
 ///
 /// It does this in few parts:
-/// 1. It verifies that the next next pc is correct based on the branching column.  That column is a
-///    boolean that indicates whether the branch condition is true.
+/// 1. It verifies that the next pc and next next pc are correct based on the branching column.
+///    That column is a boolean that indicates whether the branch condition is true.  Delayed
+///    branches jump after their delay slot, branch-likely instructions skip the delay slot when
+///    not taken, and compact branches have no delay slot at all.
 /// 2. It verifies the correct value of branching based on the helper bool columns (a_eq_b,
 ///    a_gt_b, a_lt_b).
-/// 3. It verifies the correct values of the helper bool columns based on op_a and op_b.
+/// 3. It verifies the correct values of the helper bool columns based on the compared values.
+/// 4. It verifies the return address written by link branches.
+/// 5. It verifies the signed overflow of `op_a + op_b` for BOVC and BNVC.
 ///
 impl<AB> Air<AB> for BranchChip
 where
@@ -33,37 +37,110 @@ where
         let local = main.row_slice(0);
         let local: &BranchColumns<AB::Var> = (*local).borrow();
 
-        // SAFETY: All selectors `is_beq`, `is_bne`, `is_bltz`, `is_bgez`, `is_blez`, `is_bgtz` are checked to be boolean.
-        // Each "real" row has exactly one selector turned on, as `is_real`, the sum of the six selectors, is boolean.
+        let selectors = [
+            (local.is_beq, Opcode::BEQ),
+            (local.is_bne, Opcode::BNE),
+            (local.is_bltz, Opcode::BLTZ),
+            (local.is_bgez, Opcode::BGEZ),
+            (local.is_blez, Opcode::BLEZ),
+            (local.is_bgtz, Opcode::BGTZ),
+            (local.is_bltzal, Opcode::BLTZAL),
+            (local.is_bgezal, Opcode::BGEZAL),
+            (local.is_beql, Opcode::BEQL),
+            (local.is_bnel, Opcode::BNEL),
+            (local.is_blezl, Opcode::BLEZL),
+            (local.is_bgtzl, Opcode::BGTZL),
+            (local.is_bltzl, Opcode::BLTZL),
+            (local.is_bgezl, Opcode::BGEZL),
+            (local.is_bltzall, Opcode::BLTZALL),
+            (local.is_bgezall, Opcode::BGEZALL),
+            (local.is_beqc, Opcode::BEQC),
+            (local.is_bnec, Opcode::BNEC),
+            (local.is_bltc, Opcode::BLTC),
+            (local.is_bgec, Opcode::BGEC),
+            (local.is_bltuc, Opcode::BLTUC),
+            (local.is_bgeuc, Opcode::BGEUC),
+            (local.is_blezalc, Opcode::BLEZALC),
+            (local.is_bgezalc, Opcode::BGEZALC),
+            (local.is_bgtzalc, Opcode::BGTZALC),
+            (local.is_bltzalc, Opcode::BLTZALC),
+            (local.is_beqzalc, Opcode::BEQZALC),
+            (local.is_bnezalc, Opcode::BNEZALC),
+            (local.is_bovc, Opcode::BOVC),
+            (local.is_bnvc, Opcode::BNVC),
+        ];
+
+        // SAFETY: All selectors are checked to be boolean.
+        // Each "real" row has exactly one selector turned on, as `is_real`, the sum of the
+        // selectors, is boolean.
         // Therefore, the `opcode` matches the corresponding opcode.
-        builder.assert_bool(local.is_beq);
-        builder.assert_bool(local.is_bne);
-        builder.assert_bool(local.is_bltz);
-        builder.assert_bool(local.is_bgez);
-        builder.assert_bool(local.is_blez);
-        builder.assert_bool(local.is_bgtz);
-        let is_real = local.is_beq
-            + local.is_bne
-            + local.is_bltz
-            + local.is_bgez
-            + local.is_blez
-            + local.is_bgtz;
+        let mut is_real = AB::Expr::zero();
+        let mut opcode = AB::Expr::zero();
+        for (selector, op) in selectors {
+            builder.assert_bool(selector);
+            is_real = is_real + selector;
+            opcode = opcode + selector * op.as_field::<AB::F>();
+        }
         builder.assert_bool(is_real.clone());
 
-        let opcode = local.is_beq * Opcode::BEQ.as_field::<AB::F>()
-            + local.is_bne * Opcode::BNE.as_field::<AB::F>()
-            + local.is_bltz * Opcode::BLTZ.as_field::<AB::F>()
-            + local.is_bgez * Opcode::BGEZ.as_field::<AB::F>()
-            + local.is_blez * Opcode::BLEZ.as_field::<AB::F>()
-            + local.is_bgtz * Opcode::BGTZ.as_field::<AB::F>();
+        // The selectors grouped by the condition they branch on.
+        let is_eq = local.is_beq + local.is_beql + local.is_beqc + local.is_beqzalc;
+        let is_ne = local.is_bne + local.is_bnel + local.is_bnec + local.is_bnezalc;
+        let is_lt = local.is_bltz
+            + local.is_bltzl
+            + local.is_bltzal
+            + local.is_bltzall
+            + local.is_bltc
+            + local.is_bltuc
+            + local.is_bltzalc;
+        let is_ge = local.is_bgez
+            + local.is_bgezl
+            + local.is_bgezal
+            + local.is_bgezall
+            + local.is_bgec
+            + local.is_bgeuc
+            + local.is_bgezalc;
+        let is_le = local.is_blez + local.is_blezl + local.is_blezalc;
+        let is_gt = local.is_bgtz + local.is_bgtzl + local.is_bgtzalc;
+        let is_unsigned = local.is_bltuc + local.is_bgeuc;
+        let is_overflow = local.is_bovc + local.is_bnvc;
+
+        // The selectors grouped by how the instruction links and redirects.
+        let is_likely = local.is_beql
+            + local.is_bnel
+            + local.is_blezl
+            + local.is_bgtzl
+            + local.is_bltzl
+            + local.is_bgezl
+            + local.is_bltzall
+            + local.is_bgezall;
+        let is_delayed_link =
+            local.is_bltzal + local.is_bgezal + local.is_bltzall + local.is_bgezall;
+        let is_compact_link = local.is_blezalc
+            + local.is_bgezalc
+            + local.is_bgtzalc
+            + local.is_bltzalc
+            + local.is_beqzalc
+            + local.is_bnezalc;
+        let is_compact = local.is_beqc
+            + local.is_bnec
+            + local.is_bltc
+            + local.is_bgec
+            + local.is_bltuc
+            + local.is_bgeuc
+            + is_overflow.clone()
+            + is_compact_link.clone();
+        let is_link = is_delayed_link.clone() + is_compact_link.clone();
 
         // SAFETY: This checks the following.
         // - `num_extra_cycles = 0`
-        // - `op_a_val` will be constrained in the BranchChip as `op_a_immutable = 1`
-        // - `op_a_immutable = 1`, as this is a branch instruction
+        // - `op_a_val` will be constrained in the BranchChip when it is written, and is immutable
+        //   otherwise
+        // - `op_a_immutable = 1 - writes_link`
         // - `is_rw_a = 0`
         // - `is_syscall = 0`
         // - `is_halt = 0`
+        // - `is_redirect = 1` for branch-likely and compact instructions
         // `next_pc` still has to be constrained, and this is done below.
         builder.receive_instruction(
             AB::Expr::zero(),
@@ -77,11 +154,12 @@ where
             local.op_b_value,
             local.op_c_value,
             Word([AB::Expr::zero(), AB::Expr::zero(), AB::Expr::zero(), AB::Expr::zero()]),
-            AB::Expr::one(),
+            AB::Expr::one() - local.writes_link,
             AB::Expr::zero(),
             AB::Expr::zero(),
             AB::Expr::zero(),
             AB::Expr::zero(),
+            is_likely.clone() + is_compact.clone(),
             is_real.clone(),
         );
 
@@ -106,32 +184,56 @@ where
                 is_real.clone(),
             );
 
+            // Delayed branches jump to `next_pc + c` once the delay slot has executed.
+            let is_delayed_branching = local.is_branching * (AB::Expr::one() - is_compact.clone());
+
             // When we are branching, assert that local.target_pc <==> local.next_pc + c.
             builder.send_alu(
                 Opcode::ADD.as_field::<AB::F>(),
                 local.target_pc,
                 local.next_pc,
                 local.op_c_value,
-                local.is_branching,
+                is_delayed_branching.clone(),
             );
 
-            // When we are not branching, assert that local.next_pc + 4 <==> next.next_next_pc.
-            builder.when(is_real.clone()).when_not(local.is_branching).assert_eq(
+            // Unless a delayed branch is taken, assert that
+            // local.next_pc + 4 <==> local.next_next_pc.
+            builder.when(is_real.clone() - is_delayed_branching.clone()).assert_eq(
                 local.next_pc.reduce::<AB>() + AB::Expr::from_canonical_u32(4),
                 local.next_next_pc.reduce::<AB>(),
             );
 
-            // check local.next_pc/next_next_pc to be valid word when we are not branching.
-            // they are checked as valid value by the ADD ALU table when we are branching.
-            builder.slice_range_check_u8(&local.next_pc.0, is_real.clone() - local.is_branching);
-            builder
-                .slice_range_check_u8(&local.next_next_pc.0, is_real.clone() - local.is_branching);
+            // check local.next_pc/next_next_pc to be valid word unless a delayed branch is taken.
+            // they are checked as valid value by the ADD ALU table when it is.
+            builder.slice_range_check_u8(
+                &local.next_pc.0,
+                is_real.clone() - is_delayed_branching.clone(),
+            );
+            builder.slice_range_check_u8(
+                &local.next_next_pc.0,
+                is_real.clone() - is_delayed_branching.clone(),
+            );
+
+            // When a delayed branch is taken, assert that local.next_next_pc <==> local.target_pc.
+            builder.when(is_delayed_branching).assert_word_eq(local.target_pc, local.next_next_pc);
+
+            // Branch-likely instructions execute the delay slot only when branching, and skip it
+            // otherwise.
+            builder.when(is_likely).assert_eq(
+                local.next_pc.reduce::<AB>() + local.is_branching * AB::F::from_canonical_u32(4),
+                local.pc + AB::F::from_canonical_u32(8),
+            );
 
-            // When we are branching, assert that local.next_next_pc <==> next.target_pc.
+            // Compact branches carry their absolute target in `op_c`, and continue at `pc + 4`
+            // when not branching.
+            builder.when(is_compact.clone()).assert_word_eq(local.target_pc, local.op_c_value);
+            builder
+                .when(is_compact.clone() * local.is_branching)
+                .assert_word_eq(local.target_pc, local.next_pc);
             builder
-                .when(is_real.clone())
-                .when(local.is_branching)
-                .assert_word_eq(local.target_pc, local.next_next_pc);
+                .when(is_compact.clone())
+                .when_not(local.is_branching)
+                .assert_eq(local.next_pc.reduce::<AB>(), local.pc + AB::F::from_canonical_u32(4));
 
             // To prevent the ALU send above to be non-zero when the row is a padding row.
             builder.when_not(is_real.clone()).assert_zero(local.is_branching);
@@ -140,71 +242,156 @@ where
             builder.when(is_real.clone()).assert_bool(local.is_branching);
         }
 
+        // Evaluate link constraints.
+        {
+            // Delayed link branches always link, compact ones only when branching.
+            builder.assert_eq(
+                local.writes_link,
+                is_delayed_link + is_compact_link * local.is_branching,
+            );
+
+            // The return address skips the delay slot of delayed branches.
+            builder.when(local.writes_link).assert_eq(
+                local.op_a_value.reduce::<AB>(),
+                local.pc + AB::Expr::from_canonical_u32(8)
+                    - is_compact.clone() * AB::F::from_canonical_u32(4),
+            );
+
+            // SAFETY: `writes_link` is boolean, as at most one of the link selectors is set.
+            // `op_a_value` is checked to be a valid word in the CpuChip's `eval_registers`.
+            KoalaBearWordRangeChecker::<AB::F>::range_check(
+                builder,
+                local.op_a_value,
+                local.op_a_range_checker,
+                local.writes_link,
+            );
+        }
+
+        // Evaluate the compared values.
+        for i in 0..4 {
+            builder.assert_eq(
+                local.cmp_lhs[i],
+                local.op_a_value[i] + is_link.clone() * (local.op_b_value[i] - local.op_a_value[i]),
+            );
+            builder.assert_eq(
+                local.cmp_rhs[i],
+                local.op_b_value[i] - is_link.clone() * local.op_b_value[i],
+            );
+        }
+
         // Evaluate branching value constraints.
         {
-            // When the opcode is BEQ and we are branching, assert that a_gt_b + a_lt_b is false.
+            // When the condition is EQ and we are branching, assert that a_gt_b + a_lt_b is false.
             builder
-                .when(local.is_beq * local.is_branching)
+                .when(is_eq.clone() * local.is_branching)
                 .assert_zero(local.a_gt_b + local.a_lt_b);
 
-            // When the opcode is BEQ and we are not branching, assert that either a_gt_b or a_lt_b
-            // is true.
+            // When the condition is EQ and we are not branching, assert that either a_gt_b or
+            // a_lt_b is true.
             builder
-                .when(local.is_beq)
+                .when(is_eq)
                 .when_not(local.is_branching)
                 .assert_one(local.a_gt_b + local.a_lt_b);
 
-            // When the opcode is BNE and we are branching, assert that either a_gt_b or a_lt_b is
-            // true.
-            builder.when(local.is_bne * local.is_branching).assert_one(local.a_gt_b + local.a_lt_b);
+            // When the condition is NE and we are branching, assert that either a_gt_b or a_lt_b
+            // is true.
+            builder
+                .when(is_ne.clone() * local.is_branching)
+                .assert_one(local.a_gt_b + local.a_lt_b);
 
-            // When the opcode is BNE and we are not branching, assert that a_gt_b + a_lt_b is false.
+            // When the condition is NE and we are not branching, assert that a_gt_b + a_lt_b is
+            // false.
             builder
-                .when(local.is_bne)
+                .when(is_ne)
                 .when_not(local.is_branching)
                 .assert_zero(local.a_gt_b + local.a_lt_b);
 
-            // When the opcode is BLTZ and we are branching, assert that a_lt_b is true.
-            builder.when(local.is_bltz * local.is_branching).assert_one(local.a_lt_b);
+            // When the condition is LT and we are branching, assert that a_lt_b is true.
+            builder.when(is_lt.clone() * local.is_branching).assert_one(local.a_lt_b);
+
+            // When the condition is LT and we are not branching, assert a_lt_b is false.
+            builder.when(is_lt).when_not(local.is_branching).assert_zero(local.a_lt_b);
+
+            // When the condition is LE and we are branching, assert that either a_gt_b is false
+            builder.when(is_le.clone() * local.is_branching).assert_zero(local.a_gt_b);
+
+            // When the condition is LE and we are not branching, assert that a_gt_b is true.
+            builder.when(is_le).when_not(local.is_branching).assert_one(local.a_gt_b);
+
+            // When the condition is GT and we are branching, assert that a_gt_b is true.
+            builder.when(is_gt.clone() * local.is_branching).assert_one(local.a_gt_b);
 
-            // When the opcode is BLTZ and we are not branching, assert a_lt_b is false.
-            builder.when(local.is_bltz).when_not(local.is_branching).assert_zero(local.a_lt_b);
+            // When the condition is GT and we are not branching, assert that a_gt_b is false.
+            builder.when(is_gt).when_not(local.is_branching).assert_zero(local.a_gt_b);
 
-            // When the opcode is BLEZ and we are branching, assert that either a_gt_b is false
-            builder.when(local.is_blez * local.is_branching).assert_zero(local.a_gt_b);
+            // When the condition is GE and we are branching, assert that a_lt_b is false.
+            builder.when(is_ge.clone() * local.is_branching).assert_zero(local.a_lt_b);
 
-            // When the opcode is BLEZ and we are not branching, assert that a_gt_b is true.
-            builder.when(local.is_blez).when_not(local.is_branching).assert_one(local.a_gt_b);
+            // When the condition is GE and we are not branching, assert that a_lt_b is true.
+            builder.when(is_ge).when_not(local.is_branching).assert_one(local.a_lt_b);
 
-            // When the opcode is BGTZ and we are branching, assert that a_gt_b is true.
-            builder.when(local.is_bgtz * local.is_branching).assert_one(local.a_gt_b);
+            // BOVC branches when the signed addition overflows, and BNVC when it does not.
+            builder.when(local.is_bovc).assert_eq(local.is_branching, local.overflows);
+            builder
+                .when(local.is_bnvc)
+                .assert_eq(local.is_branching, AB::Expr::one() - local.overflows);
+        }
 
-            // When the opcode is BGTZ and we are not branching, assert that a_gt_b is false.
-            builder.when(local.is_bgtz).when_not(local.is_branching).assert_zero(local.a_gt_b);
+        // Evaluate the overflow of `op_a + op_b` for the overflow branches.
+        {
+            // SAFETY: `is_overflow` is boolean, since at most one selector is turned on.
+            // The ADD ALU table checks that `cmp_lhs`, `cmp_rhs` and `sum` are valid words.
+            builder.send_alu(
+                Opcode::ADD.as_field::<AB::F>(),
+                local.sum,
+                local.cmp_lhs,
+                local.cmp_rhs,
+                is_overflow.clone(),
+            );
 
-            // When the opcode is BGEZ and we are branching, assert that a_lt_b is false.
-            builder.when(local.is_bgez * local.is_branching).assert_zero(local.a_lt_b);
+            // The byte lookups constrain the sign bits to be boolean.
+            for (msb, word) in [
+                (local.a_msb, local.cmp_lhs),
+                (local.b_msb, local.cmp_rhs),
+                (local.sum_msb, local.sum),
+            ] {
+                builder.send_byte(
+                    ByteOpcode::MSB.as_field::<AB::F>(),
+                    msb,
+                    word[3],
+                    AB::Expr::zero(),
+                    is_overflow.clone(),
+                );
+            }
 
-            // When the opcode is BGEZ and we are not branching, assert that a_lt_b is true.
-            builder.when(local.is_bgez).when_not(local.is_branching).assert_one(local.a_lt_b);
+            // The addition overflows exactly when both operands have the same sign and the sum
+            // has the other one.
+            let (a_msb, b_msb, sum_msb) = (local.a_msb, local.b_msb, local.sum_msb);
+            builder.assert_eq(
+                local.overflows,
+                a_msb * b_msb * (AB::Expr::one() - sum_msb)
+                    + (AB::Expr::one() - a_msb) * (AB::Expr::one() - b_msb) * sum_msb,
+            );
         }
 
         // Calculate a_lt_b <==> a < b (using appropriate signedness).
-        // SAFETY: `use_signed_comparison` is boolean, since at most one selector is turned on.
+        // SAFETY: `is_unsigned` is boolean, since at most one selector is turned on.
+        let slt_opcode = Opcode::SLT.as_field::<AB::F>()
+            + is_unsigned * (Opcode::SLTU.as_field::<AB::F>() - Opcode::SLT.as_field::<AB::F>());
         builder.send_alu(
-            Opcode::SLT.as_field::<AB::F>(),
+            slt_opcode.clone(),
             Word::extend_var::<AB>(local.a_lt_b),
-            local.op_a_value,
-            local.op_b_value,
+            local.cmp_lhs,
+            local.cmp_rhs,
             is_real.clone(),
         );
 
         // Calculate a_gt_b <==> a > b (using appropriate signedness).
         builder.send_alu(
-            Opcode::SLT.as_field::<AB::F>(),
+            slt_opcode,
             Word::extend_var::<AB>(local.a_gt_b),
-            local.op_b_value,
-            local.op_a_value,
+            local.cmp_rhs,
+            local.cmp_lhs,
             is_real.clone(),
         );
     }
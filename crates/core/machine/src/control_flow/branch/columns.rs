@@ -35,6 +35,10 @@ pub struct BranchColumns<T> {
     /// The value of the third operand.
     pub op_c_value: Word<T>,
 
+    /// The values compared by the branch: `(op_a, op_b)`, or `(op_b, 0)` for link branches.
+    pub cmp_lhs: Word<T>,
+    pub cmp_rhs: Word<T>,
+
     /// Branch Instructions Selectors.
     pub is_beq: T,
     pub is_bne: T,
@@ -42,20 +46,69 @@ pub struct BranchColumns<T> {
     pub is_blez: T,
     pub is_bgtz: T,
     pub is_bgez: T,
+    pub is_bltzal: T,
+    pub is_bgezal: T,
+
+    /// Branch-likely Instructions Selectors.
+    pub is_beql: T,
+    pub is_bnel: T,
+    pub is_blezl: T,
+    pub is_bgtzl: T,
+    pub is_bltzl: T,
+    pub is_bgezl: T,
+    pub is_bltzall: T,
+    pub is_bgezall: T,
+
+    /// Compact Branch Instructions Selectors.
+    pub is_beqc: T,
+    pub is_bnec: T,
+    pub is_bltc: T,
+    pub is_bgec: T,
+    pub is_bltuc: T,
+    pub is_bgeuc: T,
+    pub is_blezalc: T,
+    pub is_bgezalc: T,
+    pub is_bgtzalc: T,
+    pub is_bltzalc: T,
+    pub is_beqzalc: T,
+    pub is_bnezalc: T,
+    pub is_bovc: T,
+    pub is_bnvc: T,
 
     /// The branching column is equal to:
     ///
-    /// > is_beq & a_eq_b ||
-    /// > is_bne & !a_eq_b ||
-    /// > is_bltz & a_lt_0 ||
-    /// > is_bgtz & a_gt_0 ||
-    /// > is_blez & (a_lt_0  | a_eq_0) ||
-    /// > is_bgez & (a_gt_0  | a_eq_0)
+    /// > is_eq & a_eq_b ||
+    /// > is_ne & !a_eq_b ||
+    /// > is_lt & a_lt_b ||
+    /// > is_gt & a_gt_b ||
+    /// > is_le & (a_lt_b | a_eq_b) ||
+    /// > is_ge & (a_gt_b | a_eq_b)
+    ///
+    /// where `a` and `b` are `cmp_lhs` and `cmp_rhs`, and each kind groups all the selectors with
+    /// that condition.
     pub is_branching: T,
 
+    /// Whether the instruction writes the return address to `op_a`.
+    pub writes_link: T,
+
+    /// A range checker for `op_a` which may contain the return address.
+    pub op_a_range_checker: KoalaBearWordRangeChecker<T>,
+
     /// Whether a is greater than b.
     pub a_gt_b: T,
 
     /// Whether a is less than b.
     pub a_lt_b: T,
+
+    /// The wrapping sum `op_a + op_b` of the overflow branches.
+    pub sum: Word<T>,
+
+    /// The sign bits of `op_a`, `op_b` and `sum` for the overflow branches.
+    pub a_msb: T,
+    pub b_msb: T,
+    pub sum_msb: T,
+
+    /// Whether `op_a + op_b` overflows as a signed addition, i.e. whether both operands have the
+    /// same sign and the sum has the other one.
+    pub overflows: T,
 }
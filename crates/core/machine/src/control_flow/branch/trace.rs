@@ -6,8 +6,9 @@ use p3_field::PrimeField32;
 use p3_matrix::dense::RowMajorMatrix;
 use rayon::iter::{ParallelBridge, ParallelIterator};
 use zkm_core_executor::{
+    branch_operands,
     events::{BranchEvent, ByteLookupEvent, ByteRecord},
-    ExecutionRecord, Opcode, Program,
+    get_msb, is_branch_taken, ByteOpcode, ExecutionRecord, Opcode, Program,
 };
 use zkm_stark::{air::MachineAir, Word};
 
@@ -93,37 +94,84 @@ impl BranchChip {
         cols.is_bgtz = F::from_bool(matches!(event.opcode, Opcode::BGTZ));
         cols.is_blez = F::from_bool(matches!(event.opcode, Opcode::BLEZ));
         cols.is_bgez = F::from_bool(matches!(event.opcode, Opcode::BGEZ));
+        cols.is_bltzal = F::from_bool(matches!(event.opcode, Opcode::BLTZAL));
+        cols.is_bgezal = F::from_bool(matches!(event.opcode, Opcode::BGEZAL));
+        cols.is_beql = F::from_bool(matches!(event.opcode, Opcode::BEQL));
+        cols.is_bnel = F::from_bool(matches!(event.opcode, Opcode::BNEL));
+        cols.is_blezl = F::from_bool(matches!(event.opcode, Opcode::BLEZL));
+        cols.is_bgtzl = F::from_bool(matches!(event.opcode, Opcode::BGTZL));
+        cols.is_bltzl = F::from_bool(matches!(event.opcode, Opcode::BLTZL));
+        cols.is_bgezl = F::from_bool(matches!(event.opcode, Opcode::BGEZL));
+        cols.is_bltzall = F::from_bool(matches!(event.opcode, Opcode::BLTZALL));
+        cols.is_bgezall = F::from_bool(matches!(event.opcode, Opcode::BGEZALL));
+        cols.is_beqc = F::from_bool(matches!(event.opcode, Opcode::BEQC));
+        cols.is_bnec = F::from_bool(matches!(event.opcode, Opcode::BNEC));
+        cols.is_bltc = F::from_bool(matches!(event.opcode, Opcode::BLTC));
+        cols.is_bgec = F::from_bool(matches!(event.opcode, Opcode::BGEC));
+        cols.is_bltuc = F::from_bool(matches!(event.opcode, Opcode::BLTUC));
+        cols.is_bgeuc = F::from_bool(matches!(event.opcode, Opcode::BGEUC));
+        cols.is_blezalc = F::from_bool(matches!(event.opcode, Opcode::BLEZALC));
+        cols.is_bgezalc = F::from_bool(matches!(event.opcode, Opcode::BGEZALC));
+        cols.is_bgtzalc = F::from_bool(matches!(event.opcode, Opcode::BGTZALC));
+        cols.is_bltzalc = F::from_bool(matches!(event.opcode, Opcode::BLTZALC));
+        cols.is_beqzalc = F::from_bool(matches!(event.opcode, Opcode::BEQZALC));
+        cols.is_bnezalc = F::from_bool(matches!(event.opcode, Opcode::BNEZALC));
+        cols.is_bovc = F::from_bool(matches!(event.opcode, Opcode::BOVC));
+        cols.is_bnvc = F::from_bool(matches!(event.opcode, Opcode::BNVC));
 
         cols.op_a_value = event.a.into();
         cols.op_b_value = event.b.into();
         cols.op_c_value = event.c.into();
 
-        let a_eq_b = event.a == event.b;
-
-        let a_lt_b = (event.a as i32) < (event.b as i32);
-        let a_gt_b = (event.a as i32) > (event.b as i32);
+        let (lhs, rhs) = branch_operands(event.opcode, event.a, event.b);
+        cols.cmp_lhs = lhs.into();
+        cols.cmp_rhs = rhs.into();
 
+        let (a_lt_b, a_gt_b) = if matches!(event.opcode, Opcode::BLTUC | Opcode::BGEUC) {
+            (lhs < rhs, lhs > rhs)
+        } else {
+            ((lhs as i32) < (rhs as i32), (lhs as i32) > (rhs as i32))
+        };
         cols.a_lt_b = F::from_bool(a_lt_b);
         cols.a_gt_b = F::from_bool(a_gt_b);
 
-        let branching = match event.opcode {
-            Opcode::BEQ => a_eq_b,
-            Opcode::BNE => !a_eq_b,
-            Opcode::BLTZ => a_lt_b,
-            Opcode::BLEZ => a_lt_b || a_eq_b,
-            Opcode::BGTZ => a_gt_b,
-            Opcode::BGEZ => a_eq_b || a_gt_b,
-            _ => panic!("Invalid opcode: {}", event.opcode),
-        };
+        if matches!(event.opcode, Opcode::BOVC | Opcode::BNVC) {
+            let sum = lhs.wrapping_add(rhs);
+            cols.sum = sum.into();
+            let msbs = [lhs, rhs, sum].map(|value| {
+                let top = (value >> 24) as u8;
+                blu.add_byte_lookup_event(ByteLookupEvent {
+                    opcode: ByteOpcode::MSB,
+                    a1: (top >> 7) as u16,
+                    a2: 0,
+                    b: top,
+                    c: 0,
+                });
+                get_msb(value)
+            });
+            cols.a_msb = F::from_canonical_u8(msbs[0]);
+            cols.b_msb = F::from_canonical_u8(msbs[1]);
+            cols.sum_msb = F::from_canonical_u8(msbs[2]);
+            cols.overflows = F::from_bool((lhs as i32).checked_add(rhs as i32).is_none());
+        }
+
+        let branching = is_branch_taken(event.opcode, lhs, rhs);
+        let compact = event.opcode.is_compact_branch();
+
+        let writes_link = event.opcode.is_branch_link() && (branching || !compact);
+        cols.writes_link = F::from_bool(writes_link);
+        if writes_link {
+            cols.op_a_range_checker.populate(event.a);
+        }
 
-        let target_pc = event.next_pc.wrapping_add(event.c);
+        let target_pc = if compact { event.c } else { event.next_pc.wrapping_add(event.c) };
         cols.next_pc = Word::from(event.next_pc);
         cols.target_pc = Word::from(target_pc);
         cols.next_next_pc = Word::from(event.next_next_pc);
         cols.next_pc_range_checker.populate(event.next_pc);
         cols.next_next_pc_range_checker.populate(event.next_next_pc);
         cols.is_branching = F::from_bool(branching);
-        if !branching {
+        if !branching || compact {
             blu.add_u8_range_checks(&event.next_pc.to_le_bytes());
             blu.add_u8_range_checks(&event.next_next_pc.to_le_bytes());
         }
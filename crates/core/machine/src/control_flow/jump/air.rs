@@ -23,18 +23,21 @@ where
         let local = main.row_slice(0);
         let local: &JumpColumns<AB::Var> = (*local).borrow();
 
-        // SAFETY: All selectors `is_jump`, `is_jumpi`, `is_jumpdirect`  are checked to be boolean.
-        // Each "real" row has exactly one selector turned on, as `is_real = is_jump + is_jumpi + is_jumpdirect` is boolean.
+        // SAFETY: All selectors `is_jump`, `is_jumpi`, `is_jumpdirect`, `is_jumpcompact` are
+        // checked to be boolean. Each "real" row has exactly one selector turned on, as `is_real`,
+        // the sum of the selectors, is boolean.
         // Therefore, the `opcode` matches the corresponding opcode.
         builder.assert_bool(local.is_jump);
         builder.assert_bool(local.is_jumpi);
         builder.assert_bool(local.is_jumpdirect);
-        let is_real = local.is_jump + local.is_jumpi + local.is_jumpdirect;
+        builder.assert_bool(local.is_jumpcompact);
+        let is_real = local.is_jump + local.is_jumpi + local.is_jumpdirect + local.is_jumpcompact;
         builder.assert_bool(is_real.clone());
 
         let opcode = local.is_jump * Opcode::Jump.as_field::<AB::F>()
             + local.is_jumpi * Opcode::Jumpi.as_field::<AB::F>()
-            + local.is_jumpdirect * Opcode::JumpDirect.as_field::<AB::F>();
+            + local.is_jumpdirect * Opcode::JumpDirect.as_field::<AB::F>()
+            + local.is_jumpcompact * Opcode::JumpCompact.as_field::<AB::F>();
 
         // SAFETY: This checks the following.
         // - `num_extra_cycles = 0`
@@ -42,6 +45,7 @@ where
         // - `is_rw_a = 0`
         // - `is_syscall = 0`
         // - `is_halt = 0`
+        // - `is_redirect = is_jumpcompact`, as compact jumps have no delay slot
         // `next_pc` and `op_a_value` still has to be constrained, and this is done below.
         builder.receive_instruction(
             AB::Expr::zero(),
@@ -60,15 +64,21 @@ where
             AB::Expr::zero(),
             AB::Expr::zero(),
             AB::Expr::zero(),
+            local.is_jumpcompact,
             is_real.clone(),
         );
 
-        // Verify that the local.next_pc + 4 is op_a_value for all jump instructions.
-        builder.when(is_real.clone()).assert_eq(
+        // Verify that the local.next_pc + 4 is op_a_value for all delayed jump instructions.
+        builder.when(is_real.clone() - local.is_jumpcompact).assert_eq(
             local.op_a_value.reduce::<AB>(),
             local.next_pc.reduce::<AB>() + AB::F::from_canonical_u32(4),
         );
 
+        // Compact jumps have no delay slot, so they link to `pc + 4`.
+        builder
+            .when(local.is_jumpcompact)
+            .assert_eq(local.op_a_value.reduce::<AB>(), local.pc + AB::F::from_canonical_u32(4));
+
         // Range check op_a, pc, and next_pc.
         // SAFETY: `is_real` is already checked to be boolean.
         // `op_a_value` is checked to be a valid word, as it matches the one in the CpuChip.
@@ -111,5 +121,20 @@ where
             local.op_b_value,
             local.is_jumpdirect,
         );
+
+        // Verify that JIC/JIALC jump to `op_b + op_c` and continue sequentially from there.
+        // SAFETY: `is_jumpcompact` is boolean, and zero for padding rows.
+        builder.send_alu(
+            AB::Expr::from_canonical_u32(Opcode::ADD as u32),
+            local.next_pc,
+            local.op_b_value,
+            local.op_c_value,
+            local.is_jumpcompact,
+        );
+        builder.when(local.is_jumpcompact).assert_eq(
+            local.next_next_pc.reduce::<AB>(),
+            local.next_pc.reduce::<AB>() + AB::F::from_canonical_u32(4),
+        );
+        builder.slice_range_check_u8(&local.next_next_pc.0, local.is_jumpcompact);
     }
 }
@@ -31,6 +31,7 @@ pub struct JumpColumns<T> {
     pub is_jump: T,
     pub is_jumpi: T,
     pub is_jumpdirect: T,
+    pub is_jumpcompact: T,
 
     // A range checker for `op_a` which may contain `next_pc + 4`.
     pub op_a_range_checker: KoalaBearWordRangeChecker<T>,
@@ -84,12 +84,13 @@ impl JumpChip {
         &self,
         event: &JumpEvent,
         cols: &mut JumpColumns<F>,
-        _blu: &mut HashMap<ByteLookupEvent, usize>,
+        blu: &mut HashMap<ByteLookupEvent, usize>,
     ) {
         cols.pc = F::from_canonical_u32(event.pc);
         cols.is_jump = F::from_bool(matches!(event.opcode, Opcode::Jump));
         cols.is_jumpi = F::from_bool(matches!(event.opcode, Opcode::Jumpi));
         cols.is_jumpdirect = F::from_bool(matches!(event.opcode, Opcode::JumpDirect));
+        cols.is_jumpcompact = F::from_bool(matches!(event.opcode, Opcode::JumpCompact));
 
         cols.op_a_value = event.a.into();
         cols.op_b_value = event.b.into();
@@ -99,5 +100,8 @@ impl JumpChip {
         cols.next_pc_range_checker.populate(event.next_pc);
         cols.next_next_pc = Word::from(event.next_next_pc);
         cols.next_next_pc_range_checker.populate(event.next_next_pc);
+        if event.opcode == Opcode::JumpCompact {
+            blu.add_u8_range_checks(&event.next_next_pc.to_le_bytes());
+        }
     }
 }
@@ -73,6 +73,7 @@ where
             local.is_check_memory,
             local.is_halt,
             local.is_sequential,
+            local.is_redirect,
             local.is_real,
         );
 
@@ -154,10 +155,12 @@ impl CpuChip {
         // Verify the public value's start pc.
         builder.when_first_row().assert_eq(public_values.start_pc, local.pc);
 
-        // Verify the relationship between initial start pc and initial next pc.
+        // Verify the relationship between initial start pc and initial next pc.  Redirecting
+        // instructions constrain their next pc in their own chips.
+        builder.assert_bool(local.is_redirect);
         builder
             .when_first_row()
-            .when_not(local.is_halt)
+            .when(AB::Expr::one() - local.is_halt - local.is_redirect)
             .assert_eq(local.pc + AB::Expr::from_canonical_u32(4), local.next_pc);
 
         // Verify the pc, next_pc, and next_next_pc
@@ -165,7 +168,7 @@ impl CpuChip {
         builder
             .when_transition()
             .when(next.is_real)
-            .when_not(next.is_halt)
+            .when(AB::Expr::one() - next.is_halt - next.is_redirect)
             .assert_eq(local.next_next_pc, next.next_pc);
 
         builder
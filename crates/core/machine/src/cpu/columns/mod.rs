@@ -58,6 +58,9 @@ pub struct CpuCols<T: Copy> {
     /// Whether this is a sequential instruction (not branch or jump or halt).
     pub is_sequential: T,
 
+    /// Whether the instruction sets its own next pc (branch-likely and compact instructions).
+    pub is_redirect: T,
+
     /// Operand values, either from registers or immediate values.
     pub op_a_value: Word<T>,
     pub hi_or_prev_a: Word<T>,
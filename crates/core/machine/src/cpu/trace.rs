@@ -132,9 +132,12 @@ impl CpuChip {
         cols.next_next_pc = F::from_canonical_u32(event.next_next_pc);
         cols.instruction.populate(instruction);
 
+        // Link branches write `op_a`, compact ones only when taken.
+        let writes_a = matches!(event.a_record, Some(MemoryRecordEnum::Write(_)));
         cols.op_a_immutable = F::from_bool(
             instruction.is_memory_store_instruction_except_sc()
-                || instruction.is_branch_instruction(),
+                || instruction.is_trap_instruction()
+                || (instruction.is_branch_instruction() && !writes_a),
         );
 
         cols.is_rw_a = F::from_bool(instruction.is_rw_a_instruction());
@@ -193,6 +196,7 @@ impl CpuChip {
         cols.is_sequential = F::from_bool(
             !is_halt && !instruction.is_branch_instruction() && !instruction.is_jump_instruction(),
         );
+        cols.is_redirect = F::from_bool(instruction.is_redirect_instruction());
 
         // Populate range checks for a.
         let a_bytes = cols
@@ -0,0 +1,847 @@
+use std::borrow::Borrow;
+
+use p3_air::{Air, AirBuilder};
+use p3_field::FieldAlgebra;
+use p3_matrix::Matrix;
+use zkm_core_executor::{
+    events::MemoryAccessPosition,
+    fpu::{DEFAULT_NAN_DOUBLE, DEFAULT_NAN_SINGLE, INVALID_WORD},
+    ByteOpcode, Opcode,
+};
+use zkm_stark::{
+    air::{Polynomial, ZKMAirBuilder},
+    Word,
+};
+
+use crate::{
+    air::{MemoryAirBuilder, WordAirBuilder},
+    fpu::{compare::FpCompareCols, identity::FpIdentityCols, unpack::FpUnpackCols},
+    memory::MemoryCols,
+    operations::IsZeroOperation,
+};
+
+use super::{
+    columns::{FpuArithCols, D_BYTES, MANTISSA_BYTES, N_BYTES},
+    rounding::MAX_SHIFT,
+    FpuArithChip,
+};
+
+/// Returns the instruction flags with their opcodes.
+pub(crate) fn opcode_flags<T: Copy>(local: &FpuArithCols<T>) -> [(T, Opcode); 18] {
+    [
+        (local.is_add_s, Opcode::FADD_S),
+        (local.is_add_d, Opcode::FADD_D),
+        (local.is_sub_s, Opcode::FSUB_S),
+        (local.is_sub_d, Opcode::FSUB_D),
+        (local.is_mul_s, Opcode::FMUL_S),
+        (local.is_mul_d, Opcode::FMUL_D),
+        (local.is_div_s, Opcode::FDIV_S),
+        (local.is_div_d, Opcode::FDIV_D),
+        (local.is_sqrt_s, Opcode::FSQRT_S),
+        (local.is_sqrt_d, Opcode::FSQRT_D),
+        (local.is_cvt_s_d, Opcode::CVT_S_D),
+        (local.is_cvt_d_s, Opcode::CVT_D_S),
+        (local.is_cvt_s_w, Opcode::CVT_S_W),
+        (local.is_cvt_d_w, Opcode::CVT_D_W),
+        (local.is_cvt_w_s, Opcode::CVT_W_S),
+        (local.is_cvt_w_d, Opcode::CVT_W_D),
+        (local.is_trunc_w_s, Opcode::TRUNC_W_S),
+        (local.is_trunc_w_d, Opcode::TRUNC_W_D),
+    ]
+}
+
+/// The instruction classes, as linear combinations of the instruction flags.
+pub(crate) struct FpuArithFlags<E> {
+    pub is_real: E,
+    /// Whether the sources are doubles.
+    pub sd: E,
+    /// Whether the result is a double.
+    pub rd: E,
+    pub is_binary: E,
+    pub is_binary_d: E,
+    pub is_add_op: E,
+    pub is_sub_op: E,
+    pub is_mul: E,
+    pub is_div: E,
+    pub is_sqrt: E,
+    pub is_f2f: E,
+    pub is_i2f: E,
+    pub is_f2i: E,
+    pub is_trunc: E,
+    /// Whether the result is a float computed from float operands.
+    pub is_fp: E,
+}
+
+impl<E: FieldAlgebra> FpuArithFlags<E> {
+    pub(crate) fn new<T: Into<E> + Copy>(local: &FpuArithCols<T>) -> Self {
+        let e = |value: T| -> E { value.into() };
+        let is_add_op =
+            e(local.is_add_s) + e(local.is_add_d) + e(local.is_sub_s) + e(local.is_sub_d);
+        let is_mul = e(local.is_mul_s) + e(local.is_mul_d);
+        let is_div = e(local.is_div_s) + e(local.is_div_d);
+        let is_sqrt = e(local.is_sqrt_s) + e(local.is_sqrt_d);
+        let is_f2f = e(local.is_cvt_s_d) + e(local.is_cvt_d_s);
+        let is_i2f = e(local.is_cvt_s_w) + e(local.is_cvt_d_w);
+        let is_trunc = e(local.is_trunc_w_s) + e(local.is_trunc_w_d);
+        let is_f2i = e(local.is_cvt_w_s) + e(local.is_cvt_w_d) + is_trunc.clone();
+        let is_binary_d =
+            e(local.is_add_d) + e(local.is_sub_d) + e(local.is_mul_d) + e(local.is_div_d);
+        let is_binary = is_add_op.clone() + is_mul.clone() + is_div.clone();
+        let sd = is_binary_d.clone()
+            + e(local.is_sqrt_d)
+            + e(local.is_cvt_s_d)
+            + e(local.is_cvt_w_d)
+            + e(local.is_trunc_w_d);
+        let rd =
+            is_binary_d.clone() + e(local.is_sqrt_d) + e(local.is_cvt_d_s) + e(local.is_cvt_d_w);
+        let is_real =
+            is_binary.clone() + is_sqrt.clone() + is_f2f.clone() + is_i2f.clone() + is_f2i.clone();
+        Self {
+            is_fp: is_real.clone() - is_i2f.clone() - is_f2i.clone(),
+            is_real,
+            sd,
+            rd,
+            is_binary,
+            is_binary_d,
+            is_add_op,
+            is_sub_op: e(local.is_sub_s) + e(local.is_sub_d),
+            is_mul,
+            is_div,
+            is_sqrt,
+            is_f2f,
+            is_i2f,
+            is_f2i,
+            is_trunc,
+        }
+    }
+}
+
+/// The bytes of `2^L` given its one-hot byte limb and bit.
+fn power_of_two<T: Into<E> + Copy, E: FieldAlgebra>(limbs: &[T], bits: &[T; 8]) -> Vec<E> {
+    let bit: E =
+        bits.iter().enumerate().map(|(i, &b)| b.into() * E::from_canonical_u32(1 << i)).sum();
+    limbs.iter().map(|&limb| limb.into() * bit.clone()).collect()
+}
+
+fn polynomial<T: Into<E> + Copy, E: FieldAlgebra>(limbs: &[T]) -> Polynomial<E> {
+    limbs.iter().map(|&limb| limb.into()).collect()
+}
+
+/// Returns the polynomials in the byte limbs that vanish at `x = 256`:
+///
+/// - `N - n_lhs * n_rhs -+ s`,
+/// - `D - M * 2^R`,
+/// - `q * D + r - N * 2^L`,
+/// - `Z - q - round_up - (e_q - 1) * 2^(p - 1)`, on rows with a finite rounded result.
+///
+/// They are shared by the AIR and the trace, so that padding rows vanish as well.
+pub(crate) fn vanishing_polynomials<T: Into<E> + Copy, E: FieldAlgebra>(
+    local: &FpuArithCols<T>,
+) -> [Polynomial<E>; 4] {
+    let flags = FpuArithFlags::<E>::new(local);
+    let one = E::one();
+
+    let sign = one.clone() - local.eff_sub.into() * E::two();
+    let s = polynomial::<T, E>(&local.s) * sign;
+    let n = polynomial::<T, E>(&local.n)
+        - &(polynomial::<T, E>(&local.n_lhs) * polynomial(&local.n_rhs))
+        - s;
+
+    let p2r = Polynomial::from_coefficients(&power_of_two::<T, E>(&local.r_limb, &local.r_bit));
+    let d = polynomial::<T, E>(&local.d) - polynomial::<T, E>(&local.m) * p2r;
+
+    let p2l = Polynomial::from_coefficients(&power_of_two::<T, E>(&local.l_limb, &local.l_bit));
+    let main = polynomial::<T, E>(&local.q) * polynomial(&local.d) + polynomial(&local.r)
+        - polynomial::<T, E>(&local.n) * p2l;
+
+    // The exponent field starts at bit 23 of a single and bit 52 of a double.
+    let exponent: E = local.exponent.into();
+    let mut rounded = vec![E::zero(); 8];
+    for (i, &q) in local.q.iter().enumerate() {
+        rounded[i] = q.into();
+    }
+    rounded[0] += local.round_up.into();
+    rounded[2] += (one - flags.rd.clone()) * exponent.clone() * E::from_canonical_u32(128);
+    rounded[6] += flags.rd * exponent * E::from_canonical_u32(16);
+    let z = (polynomial::<T, E>(&local.z) - Polynomial::from_coefficients(&rounded))
+        * local.z_valid.into();
+
+    [n, d, main, z]
+}
+
+/// The bytes of a constant of a single and of a double, selected by `rd`.
+fn constant_bytes<E: FieldAlgebra>(single: u64, double: u64, rd: E) -> [E; 8] {
+    let (single, double) = (single.to_le_bytes(), double.to_le_bytes());
+    core::array::from_fn(|i| {
+        (E::one() - rd.clone()) * E::from_canonical_u8(single[i])
+            + rd.clone() * E::from_canonical_u8(double[i])
+    })
+}
+
+impl<AB> Air<AB> for FpuArithChip
+where
+    AB: ZKMAirBuilder,
+    AB::Var: Sized,
+{
+    #[inline(never)]
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let local: &FpuArithCols<AB::Var> = (*local).borrow();
+        let flags = FpuArithFlags::<AB::Expr>::new(local);
+
+        let mut opcode = AB::Expr::zero();
+        for (flag, op) in opcode_flags(local) {
+            builder.assert_bool(flag);
+            opcode = opcode + flag * op.as_field::<AB::F>();
+        }
+        builder.assert_bool(flags.is_real.clone());
+
+        builder.receive_instruction(
+            local.shard,
+            local.clk,
+            local.pc,
+            local.next_pc,
+            local.next_pc + AB::Expr::from_canonical_u32(4),
+            AB::Expr::zero(),
+            opcode,
+            Word([AB::Expr::zero(), AB::Expr::zero(), AB::Expr::zero(), AB::Expr::zero()]),
+            local.op_b_value,
+            local.op_c_value,
+            Word([AB::Expr::zero(), AB::Expr::zero(), AB::Expr::zero(), AB::Expr::zero()]),
+            AB::Expr::zero(),
+            AB::Expr::zero(),
+            AB::Expr::one(),
+            AB::Expr::zero(),
+            AB::Expr::one(),
+            AB::Expr::zero(),
+            flags.is_real.clone(),
+        );
+
+        self.eval_registers(builder, local, &flags);
+
+        // Unpack the operands. The second operand of a unary instruction is zero.
+        FpUnpackCols::<AB::F>::eval(
+            builder,
+            *local.fs_access[0].value(),
+            *local.fs_access[1].value(),
+            flags.sd.clone(),
+            local.x,
+            flags.is_real.clone(),
+        );
+        FpUnpackCols::<AB::F>::eval(
+            builder,
+            *local.ft_access[0].value(),
+            *local.ft_access[1].value(),
+            flags.sd.clone(),
+            local.y,
+            flags.is_real.clone(),
+        );
+
+        self.eval_special(builder, local, &flags);
+        self.eval_significand(builder, local, &flags);
+        self.eval_division(builder, local, &flags);
+        self.eval_int(builder, local, &flags);
+        self.eval_result(builder, local, &flags);
+
+        let [n, d, main, z] = vanishing_polynomials::<AB::Var, AB::Expr>(local);
+        FpIdentityCols::<AB::F, { N_BYTES - 1 }>::eval(
+            builder,
+            &n,
+            local.n_identity,
+            flags.is_real.clone(),
+        );
+        FpIdentityCols::<AB::F, { D_BYTES - 1 }>::eval(
+            builder,
+            &d,
+            local.d_identity,
+            flags.is_real.clone(),
+        );
+        FpIdentityCols::<AB::F, { MANTISSA_BYTES + D_BYTES - 2 }>::eval(
+            builder,
+            &main,
+            local.main_identity,
+            flags.is_real.clone(),
+        );
+        FpIdentityCols::<AB::F, 7>::eval(builder, &z, local.z_identity, flags.is_real.clone());
+    }
+}
+
+impl FpuArithChip {
+    /// Evaluates the register accesses. A double occupies the even register and the next one.
+    fn eval_registers<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &FpuArithCols<AB::Var>,
+        flags: &FpuArithFlags<AB::Expr>,
+    ) {
+        let fs_register = local.op_b_value[0];
+        let ft_register = local.op_b_value[1];
+        let fd_register = local.op_c_value[0];
+        let a_clk = local.clk + AB::F::from_canonical_u32(MemoryAccessPosition::A as u32);
+        let b_clk = local.clk + AB::F::from_canonical_u32(MemoryAccessPosition::B as u32);
+        let c_clk = local.clk + AB::F::from_canonical_u32(MemoryAccessPosition::C as u32);
+
+        builder.eval_memory_access(
+            local.shard,
+            b_clk.clone(),
+            fs_register,
+            &local.fs_access[0],
+            flags.is_real.clone(),
+        );
+        builder.eval_memory_access(
+            local.shard,
+            b_clk,
+            fs_register + AB::Expr::one(),
+            &local.fs_access[1],
+            flags.sd.clone(),
+        );
+        builder.eval_memory_access(
+            local.shard,
+            c_clk.clone(),
+            ft_register,
+            &local.ft_access[0],
+            flags.is_binary.clone(),
+        );
+        builder.eval_memory_access(
+            local.shard,
+            c_clk,
+            ft_register + AB::Expr::one(),
+            &local.ft_access[1],
+            flags.is_binary_d.clone(),
+        );
+        for access in local.fs_access.iter().chain(local.ft_access.iter()) {
+            builder.assert_word_eq(*access.value(), *access.prev_value());
+        }
+        builder.when_not(flags.sd.clone()).assert_word_zero(*local.fs_access[1].value());
+        builder.when_not(flags.is_binary.clone()).assert_word_zero(*local.ft_access[0].value());
+        builder.when_not(flags.is_binary_d.clone()).assert_word_zero(*local.ft_access[1].value());
+
+        builder.eval_memory_access(
+            local.shard,
+            a_clk.clone(),
+            fd_register,
+            &local.fd_access[0],
+            flags.is_real.clone(),
+        );
+        builder.eval_memory_access(
+            local.shard,
+            a_clk,
+            fd_register + AB::Expr::one(),
+            &local.fd_access[1],
+            flags.rd.clone(),
+        );
+    }
+
+    /// Evaluates the classification of the operands into NaN, infinite and zero results, and
+    /// whether the result is rounded.
+    fn eval_special<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &FpuArithCols<AB::Var>,
+        flags: &FpuArithFlags<AB::Expr>,
+    ) {
+        let one = AB::Expr::one();
+        let (x, y) = (local.x, local.y);
+        let xor = |a: AB::Expr, b: AB::Expr| a.clone() + b.clone() - a * b * AB::F::TWO;
+        let or = |a: AB::Expr, b: AB::Expr| a.clone() + b.clone() - a * b;
+
+        builder.assert_eq(local.y_sign, xor(y.sign.into(), flags.is_sub_op.clone()));
+        builder.assert_eq(local.sign_xor, xor(x.sign.into(), y.sign.into()));
+        builder.assert_eq(local.eff_sub, xor(x.sign.into(), local.y_sign.into()));
+
+        // The magnitude of a negative word is its negation.
+        let word = *local.fs_access[0].value();
+        builder.assert_eq(local.word_neg, flags.is_i2f.clone() * x.sign);
+        builder.send_alu(
+            Opcode::SUB.as_field::<AB::F>(),
+            local.abs_word,
+            Word([AB::Expr::zero(), AB::Expr::zero(), AB::Expr::zero(), AB::Expr::zero()]),
+            word,
+            local.word_neg,
+        );
+        builder.when(flags.is_i2f.clone() - local.word_neg).assert_word_eq(local.abs_word, word);
+
+        builder.assert_eq(local.any_nan, or(x.is_nan.into(), y.is_nan.into()));
+        builder.assert_eq(local.any_inf, or(x.is_inf.into(), y.is_inf.into()));
+        builder.assert_eq(local.both_inf, x.is_inf * y.is_inf);
+        builder.assert_eq(local.zero_inf, x.is_zero * y.is_inf + x.is_inf * y.is_zero);
+        builder.assert_eq(local.zero_zero_inf_inf, x.is_zero * y.is_zero + x.is_inf * y.is_inf);
+        builder.assert_eq(local.div_inf, or(x.is_inf.into(), y.is_zero.into()));
+
+        // Invalid operations and NaN operands give the default NaN.
+        builder.assert_eq(
+            local.op_nan,
+            flags.is_add_op.clone() * local.both_inf * local.eff_sub
+                + flags.is_mul.clone() * local.zero_inf
+                + flags.is_div.clone() * local.zero_zero_inf_inf
+                + flags.is_sqrt.clone() * x.sign * (one.clone() - x.is_zero),
+        );
+        builder.assert_eq(
+            local.res_nan,
+            flags.is_fp.clone() * or(local.any_nan.into(), local.op_nan.into()),
+        );
+        builder.assert_eq(
+            local.special_inf,
+            (one.clone() - local.res_nan)
+                * ((flags.is_add_op.clone() + flags.is_mul.clone()) * local.any_inf
+                    + flags.is_div.clone() * local.div_inf
+                    + (flags.is_sqrt.clone() + flags.is_f2f.clone()) * x.is_inf),
+        );
+        builder.assert_eq(
+            local.special_zero,
+            flags.is_div.clone() * y.is_inf * (one.clone() - local.res_nan),
+        );
+
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            local.n.iter().fold(AB::Expr::zero(), |acc, &byte| acc + byte),
+            local.n_zero,
+            flags.is_real.clone(),
+        );
+
+        // A conversion to a word is invalid if the exponent is at least 32.
+        builder.assert_bool(local.int_big);
+        builder.when_not(flags.is_f2i.clone()).assert_zero(local.int_big);
+        let x_exponent = x.exponent + x.exponent_zero.result;
+        let bias = flags.sd.clone() * AB::F::from_canonical_u32(1023 - 127)
+            + AB::F::from_canonical_u32(127);
+        builder.when(flags.is_f2i.clone()).assert_eq(
+            local.int_big_gap,
+            local.int_big * (x_exponent.clone() - bias.clone() - AB::F::from_canonical_u32(32))
+                + (one.clone() - local.int_big)
+                    * (bias + AB::F::from_canonical_u32(31) - x_exponent),
+        );
+        builder.slice_range_check_u16(&[local.int_big_gap], flags.is_f2i.clone());
+
+        builder.assert_eq(
+            local.is_round,
+            (flags.is_real.clone() - local.res_nan - local.special_inf - local.special_zero)
+                * (one.clone() - local.n_zero.result)
+                * (one - local.int_big),
+        );
+    }
+
+    /// Evaluates the exact significand `N = n_lhs * n_rhs +- s`.
+    ///
+    /// A sum aligns the larger addend to the smaller one, up to a gap of `p + 3` beyond which the
+    /// smaller addend only contributes a sticky bit. A product multiplies the significands, and
+    /// the other instructions take the significand of `fs` or the magnitude of the word.
+    fn eval_significand<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &FpuArithCols<AB::Var>,
+        flags: &FpuArithFlags<AB::Expr>,
+    ) {
+        let one = AB::Expr::one();
+        let (x, y) = (local.x, local.y);
+        let is_add_op = flags.is_add_op.clone();
+
+        builder.assert_bool(local.swap);
+        builder.assert_bool(local.clamp);
+        builder.when_not(is_add_op.clone()).assert_zero(local.swap);
+        builder.when_not(is_add_op.clone()).assert_zero(local.clamp);
+
+        let x_exponent = x.exponent + x.exponent_zero.result;
+        let y_exponent = y.exponent + y.exponent_zero.result;
+        builder.assert_eq(
+            local.big_exponent,
+            local.swap * y_exponent.clone() + (one.clone() - local.swap) * x_exponent.clone(),
+        );
+        builder.assert_eq(
+            local.big_sign,
+            local.swap * local.y_sign + (one.clone() - local.swap) * x.sign,
+        );
+
+        // The gap is the difference of the exponents, which is nonnegative as the shift is.
+        eval_one_hot(builder, &local.shift_limb, &local.shift_bit, is_add_op.clone());
+        let shift = one_hot_value::<AB::Var, AB::Expr>(&local.shift_limb, &local.shift_bit);
+        let gap = local.big_exponent * AB::F::TWO - x_exponent - y_exponent;
+        let max_shift =
+            flags.rd.clone() * AB::F::from_canonical_u32(53 - 24) + AB::F::from_canonical_u32(27);
+        let mut builder_add = builder.when(is_add_op.clone());
+        builder_add.assert_eq(
+            shift,
+            local.clamp * max_shift.clone() + (one.clone() - local.clamp) * gap.clone(),
+        );
+        builder_add.assert_eq(
+            local.clamp_gap,
+            local.clamp * (gap.clone() - max_shift.clone() - one.clone())
+                + (one.clone() - local.clamp) * (max_shift - gap),
+        );
+        builder.slice_range_check_u16(&[local.clamp_gap], is_add_op.clone());
+
+        // The smaller addend, or its sticky bit if it is shifted out.
+        for i in 0..MANTISSA_BYTES {
+            builder.assert_eq(
+                local.small[i],
+                local.swap * x.mantissa[i] + (one.clone() - local.swap) * y.mantissa[i],
+            );
+        }
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            local.small.iter().fold(AB::Expr::zero(), |acc, &byte| acc + byte),
+            local.small_zero,
+            is_add_op.clone(),
+        );
+        for i in 0..MANTISSA_BYTES {
+            let sticky = if i == 0 {
+                local.clamp * (one.clone() - local.small_zero.result)
+            } else {
+                AB::Expr::zero()
+            };
+            builder.assert_eq(
+                local.s[i],
+                is_add_op.clone() * (sticky + (one.clone() - local.clamp) * local.small[i]),
+            );
+        }
+
+        let others = flags.is_real.clone() - is_add_op.clone() - flags.is_i2f.clone();
+        for i in 0..MANTISSA_BYTES {
+            let word =
+                if i < 4 { flags.is_i2f.clone() * local.abs_word[i] } else { AB::Expr::zero() };
+            builder.assert_eq(
+                local.n_lhs[i],
+                is_add_op.clone()
+                    * (local.swap * y.mantissa[i] + (one.clone() - local.swap) * x.mantissa[i])
+                    + word
+                    + others.clone() * x.mantissa[i],
+            );
+        }
+        let power = power_of_two::<AB::Var, AB::Expr>(&local.shift_limb, &local.shift_bit);
+        let others = flags.is_real.clone() - flags.is_mul.clone() - is_add_op;
+        for (i, power) in power.into_iter().enumerate() {
+            let mantissa = if i < MANTISSA_BYTES {
+                flags.is_mul.clone() * y.mantissa[i]
+            } else {
+                AB::Expr::zero()
+            };
+            let unit = if i == 0 { others.clone() } else { AB::Expr::zero() };
+            builder.assert_eq(local.n_rhs[i], mantissa + power + unit);
+        }
+
+        builder.slice_range_check_u8(&local.n, flags.is_real.clone());
+    }
+
+    /// Evaluates the division `q * D + r = N * 2^L` with `D = M * 2^R`, its rounding, and the
+    /// exponent of the result.
+    fn eval_division<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &FpuArithCols<AB::Var>,
+        flags: &FpuArithFlags<AB::Expr>,
+    ) {
+        let one = AB::Expr::one();
+        let x = local.x;
+        let is_real = flags.is_real.clone();
+
+        // The shifts, where only the right shift may exceed `MAX_SHIFT`.
+        eval_one_hot(builder, &local.l_limb, &local.l_bit, is_real.clone());
+        eval_one_hot(builder, &local.r_limb, &local.r_bit, is_real.clone());
+        let l_shift = one_hot_value::<AB::Var, AB::Expr>(&local.l_limb, &local.l_bit);
+        let r_shift = one_hot_value::<AB::Var, AB::Expr>(&local.r_limb, &local.r_bit);
+        let mut builder_sqrt = builder.when(flags.is_sqrt.clone());
+        builder_sqrt.assert_one(local.r_limb[0]);
+        builder_sqrt.assert_one(local.r_bit[0]);
+        builder.slice_range_check_u16(&[local.excess], is_real.clone());
+        builder
+            .assert_zero(local.excess * (r_shift.clone() - AB::F::from_canonical_u32(MAX_SHIFT)));
+        builder.assert_zero(local.excess * l_shift.clone());
+
+        // The divisor is the significand of `ft` for `div.fmt` and the quotient for `sqrt.fmt`.
+        let others = is_real.clone() - flags.is_div.clone() - flags.is_sqrt.clone();
+        for i in 0..MANTISSA_BYTES {
+            let unit = if i == 0 { others.clone() } else { AB::Expr::zero() };
+            builder.assert_eq(
+                local.m[i],
+                flags.is_div.clone() * local.y.mantissa[i]
+                    + flags.is_sqrt.clone() * local.q[i]
+                    + unit,
+            );
+        }
+        builder.slice_range_check_u8(&local.d, is_real.clone());
+
+        // Range check the quotient and the remainder through their most significant bits.
+        for (&byte, &msb) in local.q.iter().zip(local.q_msb.iter()) {
+            builder.send_byte(
+                ByteOpcode::MSB.as_field::<AB::F>(),
+                msb,
+                byte,
+                AB::Expr::zero(),
+                is_real.clone(),
+            );
+        }
+        for (&byte, &msb) in local.r.iter().zip(local.r_msb.iter()) {
+            builder.send_byte(
+                ByteOpcode::MSB.as_field::<AB::F>(),
+                msb,
+                byte,
+                AB::Expr::zero(),
+                is_real.clone(),
+            );
+        }
+        builder.send_byte(
+            ByteOpcode::AND.as_field::<AB::F>(),
+            local.q_odd,
+            local.q[0],
+            AB::Expr::one(),
+            is_real.clone(),
+        );
+
+        // The quotient has at most `p` bits, or 32 bits for a conversion to a word, and the top
+        // bit of a float significand is bit 23 of a single and bit 52 of a double.
+        let is_single_float = is_real.clone() - flags.rd.clone() - flags.is_f2i.clone();
+        let mut builder_single = builder.when(is_real.clone() - flags.rd.clone());
+        for i in 4..MANTISSA_BYTES {
+            builder_single.assert_zero(local.q[i]);
+        }
+        builder.when(is_single_float.clone()).assert_zero(local.q[3]);
+        builder.assert_eq(
+            local.q_top_byte,
+            flags.is_f2i.clone() * local.q[3]
+                + flags.rd.clone() * local.q[6] * AB::F::from_canonical_u32(8)
+                + is_single_float * local.q[2],
+        );
+        builder.send_byte(
+            ByteOpcode::MSB.as_field::<AB::F>(),
+            local.q_top,
+            local.q_top_byte,
+            AB::Expr::zero(),
+            is_real.clone(),
+        );
+
+        // The exponent `e_q` of the result is the smallest one for which the quotient fits, and
+        // is at least one.
+        builder.slice_range_check_u16(&[local.exponent], is_real.clone());
+        builder.when_not(local.is_round).assert_zero(local.exponent);
+        builder.when(flags.is_f2i.clone()).assert_zero(local.exponent);
+        builder.assert_zero(local.is_round * (one.clone() - local.q_top) * local.exponent);
+
+        // The shifts scale the quotient to the exponent `e_q`.
+        let e_q = local.exponent + one.clone();
+        let x_exponent = x.exponent + x.exponent_zero.result;
+        let y_exponent = local.y.exponent + local.y.exponent_zero.result;
+        let shift = one_hot_value::<AB::Var, AB::Expr>(&local.shift_limb, &local.shift_bit);
+        let unit = |is_double: AB::Expr| {
+            is_double * AB::F::from_canonical_u32(1075 - 150) + AB::F::from_canonical_u32(150)
+        };
+        let (unit_s, unit_r) = (unit(flags.sd.clone()), unit(flags.rd.clone()));
+        let k = flags.is_add_op.clone() * (e_q.clone() - local.big_exponent + shift)
+            + flags.is_mul.clone()
+                * (e_q.clone() - x_exponent.clone() - y_exponent.clone() + unit_r.clone())
+            + flags.is_div.clone()
+                * (e_q.clone() - x_exponent.clone() + y_exponent - unit_r.clone())
+            + flags.is_sqrt.clone()
+                * (e_q.clone() * AB::F::TWO - x_exponent.clone() - unit_r.clone())
+            + flags.is_f2f.clone()
+                * (e_q.clone() - x_exponent.clone() + unit_s.clone() - unit_r.clone())
+            + flags.is_i2f.clone() * (e_q.clone() - unit_r)
+            + flags.is_f2i.clone() * (unit_s - x_exponent);
+        builder.when(local.is_round).assert_eq(r_shift + local.excess - l_shift, k);
+
+        // The result overflows if the exponent reaches the all ones exponent field.
+        builder.assert_bool(local.overflow);
+        builder.when_not(local.is_round).assert_zero(local.overflow);
+        let emax = flags.rd.clone() * AB::F::from_canonical_u32(2047 - 255)
+            + AB::F::from_canonical_u32(255);
+        builder.when(local.is_round).assert_eq(
+            local.overflow_gap,
+            local.overflow * (e_q.clone() - emax.clone())
+                + (one.clone() - local.overflow) * (emax - one.clone() - e_q),
+        );
+        builder.slice_range_check_u16(&[local.overflow_gap], local.is_round);
+        builder.assert_eq(local.z_valid, local.is_round * (one.clone() - local.overflow));
+
+        // The remainder is less than the divisor, or at most `2q` for `sqrt.fmt`.
+        let twice = |bytes: &[AB::Var], msb: &[AB::Var], len: usize| -> Vec<AB::Expr> {
+            (0..len)
+                .map(|i| {
+                    let byte = if i < bytes.len() {
+                        bytes[i] * AB::F::TWO - msb[i] * AB::F::from_canonical_u32(256)
+                    } else {
+                        AB::Expr::zero()
+                    };
+                    let carry = if i > 0 && i <= bytes.len() {
+                        msb[i - 1].into()
+                    } else {
+                        AB::Expr::zero()
+                    };
+                    byte + carry
+                })
+                .collect()
+        };
+        let twice_q = twice(&local.q, &local.q_msb, D_BYTES);
+        let bound: Vec<AB::Expr> = (0..D_BYTES)
+            .map(|i| {
+                let unit = if i == 0 { AB::Expr::one() } else { AB::Expr::zero() };
+                (one.clone() - flags.is_sqrt.clone()) * local.d[i]
+                    + flags.is_sqrt.clone() * (twice_q[i].clone() + unit)
+            })
+            .collect();
+        let remainder: Vec<AB::Expr> = local.r.iter().map(|&byte| byte.into()).collect();
+        let (lt, _, _) = FpCompareCols::<AB::F, D_BYTES>::eval(
+            builder,
+            &remainder,
+            &bound,
+            local.remainder,
+            local.is_round.into(),
+        );
+        builder.assert_eq(lt, local.is_round);
+
+        // Round to nearest, ties to even, by comparing twice the remainder with the bound.
+        let twice_r = twice(&local.r, &local.r_msb, D_BYTES + 1);
+        let mut bound = bound;
+        bound.push(AB::Expr::zero());
+        let (_, eq, gt) = FpCompareCols::<AB::F, { D_BYTES + 1 }>::eval(
+            builder,
+            &twice_r,
+            &bound,
+            local.half,
+            local.is_round.into(),
+        );
+        builder.assert_eq(local.round_up, (one - flags.is_trunc.clone()) * (gt + eq * local.q_odd));
+
+        builder.slice_range_check_u8(&local.z, is_real);
+    }
+
+    /// Evaluates the word result of a conversion to a word, which is invalid unless the rounded
+    /// magnitude is less than `2^31`, or equal to it for a negative source.
+    fn eval_int<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &FpuArithCols<AB::Var>,
+        flags: &FpuArithFlags<AB::Expr>,
+    ) {
+        let one = AB::Expr::one();
+        let z = local.z;
+        builder.send_byte(
+            ByteOpcode::MSB.as_field::<AB::F>(),
+            local.z_msb,
+            z[3],
+            AB::Expr::zero(),
+            flags.is_f2i.clone(),
+        );
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            z[0] + z[1] + z[2] + z[3] - local.z_msb * AB::F::from_canonical_u32(128),
+            local.z_rest_zero,
+            flags.is_f2i.clone(),
+        );
+        builder.assert_eq(
+            local.int_valid,
+            flags.is_f2i.clone() * (one - z[4] - local.z_msb)
+                + local.x.sign * local.z_msb * local.z_rest_zero.result,
+        );
+        builder.assert_eq(local.res_int, local.is_round * flags.is_f2i.clone() * local.int_valid);
+
+        // A negative source negates the rounded magnitude.
+        builder.assert_eq(local.int_neg, local.res_int * local.x.sign);
+        let magnitude = Word([z[0], z[1], z[2], z[3]]);
+        builder.send_alu(
+            Opcode::SUB.as_field::<AB::F>(),
+            local.int_value,
+            Word([AB::Expr::zero(), AB::Expr::zero(), AB::Expr::zero(), AB::Expr::zero()]),
+            magnitude,
+            local.int_neg,
+        );
+        builder.when(local.res_int - local.int_neg).assert_word_eq(local.int_value, magnitude);
+    }
+
+    /// Evaluates the result written to `fd`.
+    fn eval_result<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &FpuArithCols<AB::Var>,
+        flags: &FpuArithFlags<AB::Expr>,
+    ) {
+        let one = AB::Expr::one();
+        let x = local.x;
+
+        // Exactly one kind of result applies to a real row.
+        builder.assert_eq(local.res_inf, local.special_inf + local.is_round * local.overflow);
+        builder.assert_eq(
+            local.res_zero,
+            local.special_zero
+                + (flags.is_real.clone() - local.res_nan - local.special_inf - local.special_zero)
+                    * local.n_zero.result,
+        );
+        builder.assert_eq(
+            local.res_float,
+            local.is_round * (one.clone() - flags.is_f2i.clone()) * (one.clone() - local.overflow),
+        );
+        builder.assert_eq(
+            local.res_invalid,
+            flags.is_f2i.clone() * (local.int_big + local.is_round) - local.res_int,
+        );
+
+        // The sign of an exact zero sum is negative only if both addends are negative.
+        let is_mul_div = flags.is_mul.clone() + flags.is_div.clone();
+        let is_unary = flags.is_sqrt.clone() + flags.is_f2f.clone();
+        builder.assert_eq(
+            local.zero_sign,
+            flags.is_add_op.clone() * x.sign * local.y_sign
+                + is_mul_div.clone() * local.sign_xor
+                + is_unary.clone() * x.sign,
+        );
+        builder.assert_eq(
+            local.add_sign,
+            x.is_inf * x.sign
+                + local.y.is_inf * (one.clone() - x.is_inf) * local.y_sign
+                + (one - local.any_inf) * local.big_sign,
+        );
+        builder.assert_eq(
+            local.sign,
+            flags.is_add_op.clone() * local.add_sign
+                + is_mul_div * local.sign_xor
+                + (is_unary + flags.is_i2f.clone()) * x.sign,
+        );
+        builder.assert_eq(
+            local.out_sign,
+            local.res_zero * local.zero_sign + (local.res_inf + local.res_float) * local.sign,
+        );
+
+        let inf = constant_bytes::<AB::Expr>(0x7f80_0000, 0x7ff0_0000_0000_0000, flags.rd.clone());
+        let nan = constant_bytes::<AB::Expr>(
+            DEFAULT_NAN_SINGLE as u64,
+            DEFAULT_NAN_DOUBLE,
+            flags.rd.clone(),
+        );
+        let sign_bit = constant_bytes::<AB::Expr>(1 << 31, 1 << 63, flags.rd.clone());
+        let invalid = (INVALID_WORD as u64).to_le_bytes();
+        let fd = [*local.fd_access[0].value(), *local.fd_access[1].value()];
+        for i in 0..8 {
+            let int = if i < 4 { local.res_int * local.int_value[i] } else { AB::Expr::zero() };
+            let out = local.res_float * local.z[i]
+                + local.res_inf * inf[i].clone()
+                + local.res_nan * nan[i].clone()
+                + int
+                + local.res_invalid * AB::F::from_canonical_u8(invalid[i])
+                + local.out_sign * sign_bit[i].clone();
+            builder.assert_eq(fd[i / 4][i % 4], out);
+        }
+    }
+}
+
+/// Evaluates a one-hot byte limb and bit, which are all zero unless `is_real` is set.
+fn eval_one_hot<AB: ZKMAirBuilder>(
+    builder: &mut AB,
+    limbs: &[AB::Var],
+    bits: &[AB::Var; 8],
+    is_real: AB::Expr,
+) {
+    for &flag in limbs.iter().chain(bits.iter()) {
+        builder.assert_bool(flag);
+    }
+    let limb_sum = limbs.iter().fold(AB::Expr::zero(), |acc, &limb| acc + limb);
+    let bit_sum = bits.iter().fold(AB::Expr::zero(), |acc, &bit| acc + bit);
+    builder.assert_eq(limb_sum, is_real.clone());
+    builder.assert_eq(bit_sum, is_real);
+}
+
+/// Returns the value `8 * limb + bit` encoded by a one-hot byte limb and bit.
+fn one_hot_value<T: Into<E> + Copy, E: FieldAlgebra>(limbs: &[T], bits: &[T; 8]) -> E {
+    let limb: E =
+        limbs.iter().enumerate().map(|(i, &l)| l.into() * E::from_canonical_usize(8 * i)).sum();
+    let bit: E = bits.iter().enumerate().map(|(i, &b)| b.into() * E::from_canonical_usize(i)).sum();
+    limb + bit
+}
@@ -0,0 +1,222 @@
+use std::mem::size_of;
+use zkm_derive::AlignedBorrow;
+use zkm_stark::Word;
+
+use crate::{
+    fpu::{compare::FpCompareCols, identity::FpIdentityCols, unpack::FpUnpackCols},
+    memory::{MemoryReadCols, MemoryReadWriteCols},
+    operations::IsZeroOperation,
+};
+
+pub const NUM_FPU_ARITH_COLS: usize = size_of::<FpuArithCols<u8>>();
+
+/// The number of bytes of a significand.
+pub const MANTISSA_BYTES: usize = 7;
+
+/// The number of bytes of the exact significand `N`.
+pub const N_BYTES: usize = 14;
+
+/// The number of bytes of the shifted divisor and the remainder.
+pub const D_BYTES: usize = 23;
+
+/// The number of byte limbs of the powers of two `2^L` and `2^R`.
+pub const SHIFT_LIMBS: usize = 16;
+
+/// The column layout for the FPU arithmetic and conversion instructions.
+///
+/// The columns witness the integer model in [`super::rounding`].
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct FpuArithCols<T> {
+    /// The current/next program counter of the instruction.
+    pub pc: T,
+    pub next_pc: T,
+
+    /// The shard number.
+    pub shard: T,
+    /// The clock cycle number.
+    pub clk: T,
+
+    /// The value of the second operand.
+    pub op_b_value: Word<T>,
+    /// The value of the third operand.
+    pub op_c_value: Word<T>,
+
+    /// The instruction flags.
+    pub is_add_s: T,
+    pub is_add_d: T,
+    pub is_sub_s: T,
+    pub is_sub_d: T,
+    pub is_mul_s: T,
+    pub is_mul_d: T,
+    pub is_div_s: T,
+    pub is_div_d: T,
+    pub is_sqrt_s: T,
+    pub is_sqrt_d: T,
+    pub is_cvt_s_d: T,
+    pub is_cvt_d_s: T,
+    pub is_cvt_s_w: T,
+    pub is_cvt_d_w: T,
+    pub is_cvt_w_s: T,
+    pub is_cvt_w_d: T,
+    pub is_trunc_w_s: T,
+    pub is_trunc_w_d: T,
+
+    /// The reads of `fs`.
+    pub fs_access: [MemoryReadCols<T>; 2],
+    /// The reads of `ft`.
+    pub ft_access: [MemoryReadCols<T>; 2],
+    /// The writes of `fd`.
+    pub fd_access: [MemoryReadWriteCols<T>; 2],
+
+    /// The unpacked `fs`.
+    pub x: FpUnpackCols<T>,
+    /// The unpacked `ft`, which is zero for unary instructions.
+    pub y: FpUnpackCols<T>,
+
+    /// The sign of `ft`, flipped for `sub.fmt`.
+    pub y_sign: T,
+    /// The exclusive or of the signs of `fs` and `ft`.
+    pub sign_xor: T,
+    /// Whether the signs of the addends differ.
+    pub eff_sub: T,
+
+    /// Whether the source word of `cvt.fmt.w` is negative.
+    pub word_neg: T,
+    /// The magnitude of the source word of `cvt.fmt.w`.
+    pub abs_word: Word<T>,
+
+    /// Whether either operand is a NaN.
+    pub any_nan: T,
+    /// Whether either operand is an infinity.
+    pub any_inf: T,
+    /// Whether both operands are infinities.
+    pub both_inf: T,
+    /// Whether the operands are a zero and an infinity, in either order.
+    pub zero_inf: T,
+    /// Whether the operands are both zeros or both infinities.
+    pub zero_zero_inf_inf: T,
+    /// Whether `fs` is an infinity or `ft` is a zero.
+    pub div_inf: T,
+    /// Whether the operation is invalid on non-NaN operands.
+    pub op_nan: T,
+    /// Whether the result is the default NaN.
+    pub res_nan: T,
+    /// Whether the result is an infinity from an infinite operand or a division by zero.
+    pub special_inf: T,
+    /// Whether the result is a zero from a division by an infinity.
+    pub special_zero: T,
+    /// Whether the exact significand is zero.
+    pub n_zero: IsZeroOperation<T>,
+    /// Whether the source of a conversion to a word is at least `2^32` in magnitude.
+    pub int_big: T,
+    /// The range checked distance of the exponent from the bound of `int_big`.
+    pub int_big_gap: T,
+    /// Whether the result is rounded from the exact significand.
+    pub is_round: T,
+
+    /// Whether `ft` has the larger magnitude of the two addends.
+    pub swap: T,
+    /// The exponent of the larger addend.
+    pub big_exponent: T,
+    /// The sign of the larger addend.
+    pub big_sign: T,
+    /// Whether the exponent gap exceeds `p + 3`.
+    pub clamp: T,
+    /// The range checked distance of the exponent gap from `p + 3`.
+    pub clamp_gap: T,
+    /// The clamped exponent gap as a one-hot byte limb and a one-hot bit.
+    pub shift_limb: [T; 8],
+    pub shift_bit: [T; 8],
+    /// The significand of the smaller addend.
+    pub small: [T; MANTISSA_BYTES],
+    /// Whether the significand of the smaller addend is zero.
+    pub small_zero: IsZeroOperation<T>,
+    /// The addend of `N`, which is the smaller addend or its sticky bit.
+    pub s: [T; MANTISSA_BYTES],
+
+    /// The factors of `N = n_lhs * n_rhs +- s`.
+    pub n_lhs: [T; MANTISSA_BYTES],
+    pub n_rhs: [T; 8],
+    /// The exact significand.
+    pub n: [T; N_BYTES],
+    pub n_identity: FpIdentityCols<T, { N_BYTES - 1 }>,
+
+    /// The left shift `L` of `N` as a one-hot byte limb and a one-hot bit.
+    pub l_limb: [T; SHIFT_LIMBS],
+    pub l_bit: [T; 8],
+    /// The right shift `R` of the divisor as a one-hot byte limb and a one-hot bit.
+    pub r_limb: [T; SHIFT_LIMBS],
+    pub r_bit: [T; 8],
+    /// The part of the right shift that exceeds the largest `R`.
+    pub excess: T,
+
+    /// The unshifted divisor, which is the quotient itself for `sqrt.fmt`.
+    pub m: [T; MANTISSA_BYTES],
+    /// The shifted divisor `M * 2^R`.
+    pub d: [T; D_BYTES],
+    pub d_identity: FpIdentityCols<T, { D_BYTES - 1 }>,
+
+    /// The quotient and its most significant bits.
+    pub q: [T; MANTISSA_BYTES],
+    pub q_msb: [T; MANTISSA_BYTES],
+    /// The byte of the quotient holding the top bit of the significand of the result.
+    pub q_top_byte: T,
+    /// Whether the top bit of the significand of the result is set.
+    pub q_top: T,
+    /// Whether the quotient is odd.
+    pub q_odd: T,
+    /// The remainder and its most significant bits.
+    pub r: [T; D_BYTES],
+    pub r_msb: [T; D_BYTES],
+    pub main_identity: FpIdentityCols<T, { MANTISSA_BYTES + D_BYTES - 2 }>,
+
+    /// The comparison of the remainder with the divisor, or with `2q + 1` for `sqrt.fmt`.
+    pub remainder: FpCompareCols<T, D_BYTES>,
+    /// The comparison of twice the remainder with the same bound.
+    pub half: FpCompareCols<T, { D_BYTES + 1 }>,
+    /// Whether to round the quotient up.
+    pub round_up: T,
+
+    /// The biased exponent of the result minus one.
+    pub exponent: T,
+    /// Whether the result overflows to an infinity.
+    pub overflow: T,
+    /// The range checked distance of the exponent from the overflow bound.
+    pub overflow_gap: T,
+    /// Whether the result is rounded and does not overflow.
+    pub z_valid: T,
+    /// The rounded result without its sign.
+    pub z: [T; 8],
+    pub z_identity: FpIdentityCols<T, 7>,
+
+    /// The most significant bit of the rounded magnitude of a conversion to a word.
+    pub z_msb: T,
+    /// Whether the rounded magnitude is `2^31`, given its most significant bit.
+    pub z_rest_zero: IsZeroOperation<T>,
+    /// Whether the rounded magnitude fits in a word.
+    pub int_valid: T,
+    /// Whether the result is a word.
+    pub res_int: T,
+    /// Whether the result is a negative word.
+    pub int_neg: T,
+    /// The word result.
+    pub int_value: Word<T>,
+
+    /// Whether the result is an infinity.
+    pub res_inf: T,
+    /// Whether the result is a zero.
+    pub res_zero: T,
+    /// Whether the result is a rounded float.
+    pub res_float: T,
+    /// Whether the result is the invalid word.
+    pub res_invalid: T,
+    /// The sign of a zero result.
+    pub zero_sign: T,
+    /// The sign of a nonzero sum.
+    pub add_sign: T,
+    /// The sign of a nonzero result.
+    pub sign: T,
+    /// The sign of the result.
+    pub out_sign: T,
+}
@@ -0,0 +1,105 @@
+use columns::NUM_FPU_ARITH_COLS;
+use p3_air::BaseAir;
+
+pub mod air;
+pub mod columns;
+pub mod rounding;
+pub mod trace;
+
+/// A chip for the FPU arithmetic and conversion instructions, which round to nearest, ties to
+/// even.
+#[derive(Default)]
+pub struct FpuArithChip;
+
+impl<F> BaseAir<F> for FpuArithChip {
+    fn width(&self) -> usize {
+        NUM_FPU_ARITH_COLS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{utils, utils::run_test};
+
+    use zkm_core_executor::{Instruction, Opcode, Program, Register};
+
+    use zkm_stark::CpuProver;
+
+    #[test]
+    fn test_fpu_arith_prove() {
+        utils::setup_logger();
+        let fpr = |i: u32| Register::fpr(i) as u32;
+        let fpu = |opcode: Opcode, fs: u32, ft: u32, fd: u32| {
+            Instruction::new(opcode, 0, fpr(fs) | fpr(ft) << 8, fpr(fd), true, true)
+        };
+        let mut instructions = vec![];
+        for (register, value) in [
+            // f0 = 1.5, f1 = 3.0, (f2, f3) = 0.1, (f4, f5) = -3.0, f6 = inf, f7 = 0.0,
+            // f8 = -2.0, f9 = NaN, (f10, f11) = the largest double, f12 = -7, f13 = 2^31.
+            (0, 0x3fc0_0000),
+            (1, 0x4040_0000),
+            (2, 0x9999_999a),
+            (3, 0x3fb9_9999),
+            (4, 0),
+            (5, 0xc008_0000),
+            (6, 0x7f80_0000),
+            (7, 0),
+            (8, 0xc000_0000),
+            (9, 0x7fc0_0000),
+            (10, 0xffff_ffff),
+            (11, 0x7fef_ffff),
+            (12, -7i32 as u32),
+            (13, 0x4f00_0000),
+        ] {
+            instructions.push(Instruction::new(Opcode::ADD, 29, 0, value, false, true));
+            instructions.push(Instruction::new(
+                Opcode::ADD,
+                fpr(register) as u8,
+                29,
+                0,
+                false,
+                true,
+            ));
+        }
+        for opcode in [Opcode::FADD_S, Opcode::FSUB_S, Opcode::FMUL_S, Opcode::FDIV_S] {
+            instructions.push(fpu(opcode, 0, 1, 16));
+            instructions.push(fpu(opcode, 1, 8, 16));
+            instructions.push(fpu(opcode, 6, 6, 16));
+            instructions.push(fpu(opcode, 0, 7, 16));
+            instructions.push(fpu(opcode, 9, 0, 16));
+        }
+        for opcode in [Opcode::FADD_D, Opcode::FSUB_D, Opcode::FMUL_D, Opcode::FDIV_D] {
+            instructions.push(fpu(opcode, 2, 4, 18));
+            instructions.push(fpu(opcode, 4, 4, 18));
+            instructions.push(fpu(opcode, 10, 10, 18));
+            instructions.push(fpu(opcode, 2, 10, 18));
+        }
+        instructions.extend([
+            fpu(Opcode::FSQRT_S, 1, 0, 16),
+            fpu(Opcode::FSQRT_S, 8, 0, 16),
+            fpu(Opcode::FSQRT_S, 7, 0, 16),
+            fpu(Opcode::FSQRT_D, 2, 0, 18),
+            fpu(Opcode::FSQRT_D, 10, 0, 18),
+            fpu(Opcode::CVT_S_D, 2, 0, 16),
+            fpu(Opcode::CVT_S_D, 10, 0, 16),
+            fpu(Opcode::CVT_D_S, 0, 0, 18),
+            fpu(Opcode::CVT_D_S, 9, 0, 18),
+            fpu(Opcode::CVT_S_W, 12, 0, 16),
+            fpu(Opcode::CVT_S_W, 13, 0, 16),
+            fpu(Opcode::CVT_D_W, 12, 0, 18),
+            fpu(Opcode::CVT_D_W, 7, 0, 18),
+            fpu(Opcode::CVT_W_S, 0, 0, 16),
+            fpu(Opcode::CVT_W_S, 8, 0, 16),
+            fpu(Opcode::CVT_W_S, 6, 0, 16),
+            fpu(Opcode::CVT_W_S, 13, 0, 16),
+            fpu(Opcode::CVT_W_D, 2, 0, 16),
+            fpu(Opcode::CVT_W_D, 4, 0, 16),
+            fpu(Opcode::TRUNC_W_S, 0, 0, 16),
+            fpu(Opcode::TRUNC_W_D, 4, 0, 16),
+            fpu(Opcode::TRUNC_W_D, 10, 0, 16),
+        ]);
+        let program = Program::new(instructions, 0, 0);
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+}
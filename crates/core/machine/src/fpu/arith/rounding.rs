@@ -0,0 +1,470 @@
+//! The integer model of an FPU arithmetic instruction that the `FpuArith` chip constrains.
+//!
+//! Every operation is reduced to an exact integer `N`, the significand of the exact result up to
+//! a power of two, which is divided and rounded as
+//!
+//! `q * D + r = N * 2^L`, with `D = M * 2^R` and `r < D`,
+//!
+//! where `M` is the divisor's significand for `div.fmt`, `q` itself for `sqrt.fmt` (so that
+//! `q * q + r = N * 2^L` with `r <= 2 * q`), and one otherwise. The shifts `L` and `R` are chosen
+//! so that `q` has exactly the result precision, or the result is subnormal.
+
+use num::{BigUint, Integer, One, ToPrimitive};
+use zkm_core_executor::{
+    fpu::{
+        is_binary, result_is_double, source_is_double, DEFAULT_NAN_DOUBLE, DEFAULT_NAN_SINGLE,
+        INVALID_WORD,
+    },
+    Opcode,
+};
+
+/// The largest right shift `R` of the divisor. Larger shifts only produce zero quotients.
+pub const MAX_SHIFT: u32 = 127;
+
+/// The kind of an FPU arithmetic instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FpuArithKind {
+    Add,
+    Mul,
+    Div,
+    Sqrt,
+    /// A conversion between single and double precision.
+    FloatToFloat,
+    /// A conversion from a word.
+    IntToFloat,
+    /// A conversion to a word.
+    FloatToInt,
+}
+
+impl FpuArithKind {
+    /// Returns the kind of the opcode, and whether it subtracts or truncates.
+    #[must_use]
+    pub fn of(opcode: Opcode) -> (Self, bool) {
+        match opcode {
+            Opcode::FADD_S | Opcode::FADD_D => (Self::Add, false),
+            Opcode::FSUB_S | Opcode::FSUB_D => (Self::Add, true),
+            Opcode::FMUL_S | Opcode::FMUL_D => (Self::Mul, false),
+            Opcode::FDIV_S | Opcode::FDIV_D => (Self::Div, false),
+            Opcode::FSQRT_S | Opcode::FSQRT_D => (Self::Sqrt, false),
+            Opcode::CVT_S_D | Opcode::CVT_D_S => (Self::FloatToFloat, false),
+            Opcode::CVT_S_W | Opcode::CVT_D_W => (Self::IntToFloat, false),
+            Opcode::CVT_W_S | Opcode::CVT_W_D => (Self::FloatToInt, false),
+            Opcode::TRUNC_W_S | Opcode::TRUNC_W_D => (Self::FloatToInt, true),
+            _ => unreachable!("{opcode} is not an FPU arithmetic instruction"),
+        }
+    }
+}
+
+/// The parameters of a precision.
+#[derive(Debug, Clone, Copy)]
+pub struct Precision {
+    /// The number of significand bits, including the implicit bit.
+    pub p: u32,
+    /// The exponent bias.
+    pub bias: u32,
+    /// The all ones exponent field.
+    pub emax: u32,
+}
+
+impl Precision {
+    #[must_use]
+    pub const fn new(is_double: bool) -> Self {
+        if is_double {
+            Self { p: 53, bias: 1023, emax: 2047 }
+        } else {
+            Self { p: 24, bias: 127, emax: 255 }
+        }
+    }
+
+    /// The exponent of the unit of the significand of a value with exponent field one.
+    #[must_use]
+    pub const fn unit(&self) -> u32 {
+        self.bias + self.p - 1
+    }
+}
+
+/// An unpacked operand.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Operand {
+    pub sign: bool,
+    /// The exponent field, or one for zeros and subnormals.
+    pub exponent: u32,
+    /// The significand including the implicit bit.
+    pub mantissa: u64,
+    pub is_nan: bool,
+    pub is_inf: bool,
+    pub is_zero: bool,
+}
+
+impl Operand {
+    #[must_use]
+    pub fn unpack(value: u64, is_double: bool) -> Self {
+        let (sign, field, fraction, max, shift) = if is_double {
+            (value >> 63 == 1, (value >> 52) & 0x7ff, value & ((1 << 52) - 1), 0x7ff, 52)
+        } else {
+            ((value >> 31) & 1 == 1, (value >> 23) & 0xff, value & ((1 << 23) - 1), 0xff, 23)
+        };
+        Self {
+            sign,
+            exponent: field.max(1) as u32,
+            mantissa: fraction | ((field != 0) as u64) << shift,
+            is_nan: field == max && fraction != 0,
+            is_inf: field == max && fraction == 0,
+            is_zero: field == 0 && fraction == 0,
+        }
+    }
+}
+
+/// The witness of an FPU arithmetic instruction.
+#[derive(Debug, Clone, Default)]
+pub struct FpuRounding {
+    pub x: Operand,
+    pub y: Operand,
+    /// The sign of `ft`, flipped for `sub.fmt`.
+    pub y_sign: bool,
+    /// The magnitude of the source word of `cvt.fmt.w`.
+    pub abs_word: u32,
+
+    /// Whether `ft` has the larger magnitude of the two addends.
+    pub swap: bool,
+    /// Whether the signs of the addends differ.
+    pub eff_sub: bool,
+    /// The exponent of the larger addend.
+    pub big_exponent: u32,
+    /// Whether the exponent gap exceeds `p + 3`, so that the smaller addend only acts as a sticky
+    /// bit.
+    pub clamp: bool,
+    /// The exponent gap, clamped to `p + 3`.
+    pub shift: u32,
+    /// The significand of the smaller addend.
+    pub small: u64,
+
+    /// The operands of `N = n_lhs * n_rhs +- s`.
+    pub n_lhs: u64,
+    pub n_rhs: u64,
+    pub s: u64,
+    pub n: u128,
+
+    pub res_nan: bool,
+    pub special_inf: bool,
+    pub special_zero: bool,
+    /// Whether the source of a conversion to a word is at least `2^32` in magnitude.
+    pub int_big: bool,
+    /// Whether the result is rounded from `N`.
+    pub is_round: bool,
+
+    /// The biased exponent of the result, which is one for subnormal results.
+    pub e_q: u32,
+    pub l: u32,
+    pub r: u32,
+    /// The part of the right shift that exceeds `MAX_SHIFT`.
+    pub excess: u32,
+    pub m: u64,
+    pub d: BigUint,
+    pub q: u64,
+    pub rem: BigUint,
+    /// Whether to round the quotient up.
+    pub round_up: bool,
+    /// Whether the result overflows to an infinity.
+    pub overflow: bool,
+    /// The result without its sign, or the rounded magnitude of a conversion to a word.
+    pub z: u64,
+
+    /// The sign of a nonzero result.
+    pub sign: bool,
+    /// The sign of a zero result.
+    pub zero_sign: bool,
+    /// The raw result.
+    pub result: u64,
+}
+
+impl FpuRounding {
+    /// Computes the witness of `opcode` applied to the raw register contents.
+    #[must_use]
+    pub fn new(opcode: Opcode, fs: u64, ft: u64) -> Self {
+        let (kind, flag) = FpuArithKind::of(opcode);
+        let (sd, rd) = (source_is_double(opcode), result_is_double(opcode));
+        let (src, dst) = (Precision::new(sd), Precision::new(rd));
+        let x = Operand::unpack(fs, sd);
+        let y = Operand::unpack(if is_binary(opcode) { ft } else { 0 }, sd);
+        let y_sign = y.sign ^ (kind == FpuArithKind::Add && flag);
+        let mut w = Self { x, y, y_sign, e_q: 1, ..Self::default() };
+
+        // Reduce the operation to `N`.
+        let (n_lhs, n_rhs) = match kind {
+            FpuArithKind::Add => {
+                w.eff_sub = x.sign ^ y_sign;
+                w.swap = (x.exponent, x.mantissa) < (y.exponent, y.mantissa);
+                let (big, small) = if w.swap { (y, x) } else { (x, y) };
+                w.big_exponent = big.exponent;
+                w.small = small.mantissa;
+                let gap = big.exponent - small.exponent;
+                w.clamp = gap > src.p + 3;
+                w.shift = if w.clamp { src.p + 3 } else { gap };
+                w.s = if w.clamp { (small.mantissa != 0) as u64 } else { small.mantissa };
+                (big.mantissa, 1 << w.shift)
+            }
+            FpuArithKind::Mul => (x.mantissa, y.mantissa),
+            FpuArithKind::IntToFloat => {
+                let word = fs as u32;
+                w.abs_word = if word >> 31 == 1 { word.wrapping_neg() } else { word };
+                (w.abs_word as u64, 1)
+            }
+            _ => (x.mantissa, 1),
+        };
+        w.n_lhs = n_lhs;
+        w.n_rhs = n_rhs;
+        let product = n_lhs as u128 * n_rhs as u128;
+        w.n = if w.eff_sub { product - w.s as u128 } else { product + w.s as u128 };
+
+        // Classify the special cases.
+        let is_fp = !matches!(kind, FpuArithKind::IntToFloat | FpuArithKind::FloatToInt);
+        let any_nan = x.is_nan || y.is_nan;
+        let op_nan = match kind {
+            FpuArithKind::Add => x.is_inf && y.is_inf && w.eff_sub,
+            FpuArithKind::Mul => (x.is_zero && y.is_inf) || (x.is_inf && y.is_zero),
+            FpuArithKind::Div => (x.is_zero && y.is_zero) || (x.is_inf && y.is_inf),
+            FpuArithKind::Sqrt => x.sign && !x.is_zero,
+            _ => false,
+        };
+        w.res_nan = is_fp && (any_nan || op_nan);
+        w.special_inf = !w.res_nan
+            && match kind {
+                FpuArithKind::Add | FpuArithKind::Mul => x.is_inf || y.is_inf,
+                FpuArithKind::Div => x.is_inf || y.is_zero,
+                FpuArithKind::Sqrt | FpuArithKind::FloatToFloat => x.is_inf,
+                _ => false,
+            };
+        w.special_zero = kind == FpuArithKind::Div && y.is_inf && !w.res_nan;
+        let is_special = w.res_nan || w.special_inf || w.special_zero;
+        w.int_big = kind == FpuArithKind::FloatToInt && x.exponent >= src.bias + 32;
+        w.is_round = !is_special && w.n != 0 && !w.int_big;
+
+        w.m = match kind {
+            FpuArithKind::Div => y.mantissa,
+            FpuArithKind::Sqrt => 0,
+            _ => 1,
+        };
+        if w.is_round {
+            w.round(kind, kind == FpuArithKind::FloatToInt && flag, src, dst);
+        } else {
+            w.d = BigUint::from(w.m);
+            w.rem = BigUint::from(w.n);
+        }
+
+        // The signs of nonzero and zero results.
+        let x_sign = if kind == FpuArithKind::IntToFloat { (fs >> 31) & 1 == 1 } else { x.sign };
+        w.sign = match kind {
+            FpuArithKind::Add if x.is_inf => x.sign,
+            FpuArithKind::Add if y.is_inf => y_sign,
+            FpuArithKind::Add if w.swap => y_sign,
+            FpuArithKind::Mul | FpuArithKind::Div => x.sign ^ y.sign,
+            _ => x_sign,
+        };
+        w.zero_sign = match kind {
+            FpuArithKind::Add => x.sign && y_sign,
+            FpuArithKind::Mul | FpuArithKind::Div => x.sign ^ y.sign,
+            FpuArithKind::Sqrt | FpuArithKind::FloatToFloat => x.sign,
+            _ => false,
+        };
+        w.result = w.encode(kind, rd);
+        w
+    }
+
+    /// Chooses the shifts, divides `N` and rounds the quotient to nearest, ties to even.
+    fn round(&mut self, kind: FpuArithKind, trunc: bool, src: Precision, dst: Precision) {
+        let (x, y) = (self.x, self.y);
+        let unit = dst.unit() as i64;
+        // The right shift is `R - L = a * e_q + b`.
+        let (a, b) = match kind {
+            FpuArithKind::Add => (1, self.shift as i64 - self.big_exponent as i64),
+            FpuArithKind::Mul => (1, unit - x.exponent as i64 - y.exponent as i64),
+            FpuArithKind::Div => (1, y.exponent as i64 - x.exponent as i64 - unit),
+            FpuArithKind::Sqrt => (2, -(x.exponent as i64) - unit),
+            FpuArithKind::FloatToFloat => (1, src.unit() as i64 - unit - x.exponent as i64),
+            FpuArithKind::IntToFloat => (1, -unit),
+            FpuArithKind::FloatToInt => (0, src.unit() as i64 - x.exponent as i64),
+        };
+        let n = BigUint::from(self.n);
+        let divide = |k: i64, m: u64| {
+            let (l, r) = if k < 0 { (-k as u32, 0) } else { (0, k as u32) };
+            (n.clone() << l) / (BigUint::from(m) << r)
+        };
+
+        self.e_q = match kind {
+            FpuArithKind::Sqrt => {
+                // The shifted radicand must have `2p - 1` or `2p` bits, with `L` of the parity of
+                // `b`.
+                let bits = n.bits() as i64;
+                let l = 2 * dst.p as i64 - bits;
+                let l = if (l + b) % 2 == 0 { l } else { l - 1 };
+                let e_q = (-l - b) / 2;
+                assert!(e_q >= 1);
+                e_q as u32
+            }
+            FpuArithKind::FloatToInt => 1,
+            _ => {
+                // Find the quotient with the smallest exponent, and normalize it if it has more
+                // than `p` bits.
+                let q = divide(a + b, self.m);
+                let bits = q.bits() as u32;
+                if bits >= dst.p {
+                    1 + bits - dst.p
+                } else {
+                    1
+                }
+            }
+        };
+
+        let k = a * self.e_q as i64 + b;
+        if k > MAX_SHIFT as i64 {
+            (self.l, self.r, self.excess) = (0, MAX_SHIFT, (k - MAX_SHIFT as i64) as u32);
+        } else if k >= 0 {
+            (self.l, self.r) = (0, k as u32);
+        } else {
+            (self.l, self.r) = ((-k) as u32, 0);
+        }
+        assert!(self.l <= MAX_SHIFT);
+
+        let nl = n << self.l;
+        let h = if kind == FpuArithKind::Sqrt {
+            let q = nl.sqrt();
+            self.m = q.to_u64().unwrap();
+            self.d = q.clone();
+            self.rem = &nl - &q * &q;
+            self.q = self.m;
+            (q << 1) + BigUint::one()
+        } else {
+            self.d = BigUint::from(self.m) << self.r;
+            let (q, rem) = nl.div_rem(&self.d);
+            self.q = q.to_u64().unwrap();
+            self.rem = rem;
+            self.d.clone()
+        };
+        let t = &self.rem << 1;
+        self.round_up = !trunc && (t > h || (t == h && self.q & 1 == 1));
+
+        self.z = self.q + self.round_up as u64;
+        if kind != FpuArithKind::FloatToInt {
+            // An overflowing result is not encoded, and its exponent may not fit.
+            self.overflow = self.e_q >= dst.emax;
+            self.z =
+                if self.overflow { 0 } else { self.z + ((self.e_q as u64 - 1) << (dst.p - 1)) };
+        }
+    }
+
+    /// Whether a conversion to a word is representable.
+    #[must_use]
+    pub fn int_valid(&self) -> bool {
+        self.z < 1 << 31 || (self.x.sign && self.z == 1 << 31)
+    }
+
+    fn encode(&self, kind: FpuArithKind, rd: bool) -> u64 {
+        let (nan, inf, sign_bit) = if rd {
+            (DEFAULT_NAN_DOUBLE, 0x7ff0_0000_0000_0000, 1 << 63)
+        } else {
+            (DEFAULT_NAN_SINGLE as u64, 0x7f80_0000, 1 << 31)
+        };
+        let with_sign = |value: u64, sign: bool| if sign { value | sign_bit } else { value };
+        if kind == FpuArithKind::FloatToInt {
+            return if self.int_big || (self.is_round && !self.int_valid()) {
+                INVALID_WORD as u64
+            } else if self.x.sign {
+                (self.z as u32).wrapping_neg() as u64
+            } else {
+                self.z
+            };
+        }
+        if self.res_nan {
+            nan
+        } else if self.special_inf || (self.is_round && self.overflow) {
+            with_sign(inf, self.sign)
+        } else if self.is_round {
+            with_sign(self.z, self.sign)
+        } else {
+            with_sign(0, self.zero_sign)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zkm_core_executor::{
+        fpu::{execute, source_is_double},
+        Opcode,
+    };
+
+    use super::FpuRounding;
+
+    #[test]
+    fn test_rounding_matches_execute() {
+        let singles: [u64; 16] = [
+            0,
+            0x8000_0000,
+            1,
+            0x007f_ffff,
+            0x0080_0000,
+            0x3f80_0000,
+            0x3fc0_0001,
+            0x3eaa_aaab,
+            0x4b80_0001,
+            0x4f00_0000,
+            0xcf00_0000,
+            0x7f7f_ffff,
+            0x7f80_0000,
+            0xff80_0000,
+            0x7fc0_0000,
+            0xc0a0_0000,
+        ];
+        let doubles: [u64; 16] = [
+            0,
+            1 << 63,
+            1,
+            0x000f_ffff_ffff_ffff,
+            0x0010_0000_0000_0000,
+            0x3ff0_0000_0000_0000,
+            0x3fb9_9999_9999_999a,
+            0x3fd5_5555_5555_5555,
+            0x41df_ffff_ffe0_0000,
+            0x41e0_0000_0000_0000,
+            0xc1e0_0000_0000_0000,
+            0x7fef_ffff_ffff_ffff,
+            0x7ff0_0000_0000_0000,
+            0xfff0_0000_0000_0000,
+            0x7ff8_0000_0000_0000,
+            0xc014_0000_0000_0000,
+        ];
+        for opcode in [
+            Opcode::FADD_S,
+            Opcode::FADD_D,
+            Opcode::FSUB_S,
+            Opcode::FSUB_D,
+            Opcode::FMUL_S,
+            Opcode::FMUL_D,
+            Opcode::FDIV_S,
+            Opcode::FDIV_D,
+            Opcode::FSQRT_S,
+            Opcode::FSQRT_D,
+            Opcode::CVT_S_D,
+            Opcode::CVT_D_S,
+            Opcode::CVT_S_W,
+            Opcode::CVT_D_W,
+            Opcode::CVT_W_S,
+            Opcode::CVT_W_D,
+            Opcode::TRUNC_W_S,
+            Opcode::TRUNC_W_D,
+        ] {
+            let values = if source_is_double(opcode) { &doubles } else { &singles };
+            for &fs in values {
+                for &ft in values {
+                    let rounding = FpuRounding::new(opcode, fs, ft);
+                    assert_eq!(
+                        rounding.result,
+                        execute(opcode, fs, ft),
+                        "{opcode} {fs:#x} {ft:#x}"
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,417 @@
+use std::borrow::BorrowMut;
+
+use hashbrown::HashMap;
+use itertools::Itertools;
+use num::BigUint;
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use rayon::iter::{ParallelBridge, ParallelIterator};
+use zkm_core_executor::{
+    events::{ByteLookupEvent, ByteRecord, FpuEvent},
+    fpu::{is_binary, result_is_double, source_is_double},
+    ByteOpcode, ExecutionRecord, Opcode, Program,
+};
+use zkm_stark::{air::MachineAir, Word};
+
+use crate::{
+    utils::{next_power_of_two, zeroed_f_vec},
+    CoreChipError,
+};
+
+use super::{
+    air::{vanishing_polynomials, FpuArithFlags},
+    columns::{FpuArithCols, D_BYTES, MANTISSA_BYTES, NUM_FPU_ARITH_COLS, N_BYTES},
+    rounding::{FpuArithKind, FpuRounding},
+    FpuArithChip,
+};
+
+impl<F: PrimeField32> MachineAir<F> for FpuArithChip {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    type Error = CoreChipError;
+
+    fn name(&self) -> String {
+        "FpuArith".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> Result<RowMajorMatrix<F>, Self::Error> {
+        let chunk_size = std::cmp::max((input.fpu_arith_events.len()) / num_cpus::get(), 1);
+        let nb_rows = input.fpu_arith_events.len();
+        let size_log2 = input.fixed_log2_rows::<F, _>(self);
+        let padded_nb_rows = next_power_of_two(nb_rows, size_log2);
+        let mut values = zeroed_f_vec(padded_nb_rows * NUM_FPU_ARITH_COLS);
+
+        let blu_events = values
+            .chunks_mut(chunk_size * NUM_FPU_ARITH_COLS)
+            .enumerate()
+            .par_bridge()
+            .map(|(i, rows)| {
+                let mut blu: HashMap<ByteLookupEvent, usize> = HashMap::new();
+                rows.chunks_mut(NUM_FPU_ARITH_COLS).enumerate().for_each(|(j, row)| {
+                    let idx = i * chunk_size + j;
+                    let cols: &mut FpuArithCols<F> = row.borrow_mut();
+
+                    if idx < input.fpu_arith_events.len() {
+                        let event = &input.fpu_arith_events[idx];
+                        self.event_to_row(event, cols, &mut blu);
+                    }
+                    // The identities are checked on padding rows as well.
+                    self.populate_identities(cols, &mut blu, idx < input.fpu_arith_events.len());
+                });
+                blu
+            })
+            .collect::<Vec<_>>();
+
+        output.add_byte_lookup_events_from_maps(blu_events.iter().collect_vec());
+
+        // Convert the trace to a row major matrix.
+        Ok(RowMajorMatrix::new(values, NUM_FPU_ARITH_COLS))
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        if let Some(shape) = shard.shape.as_ref() {
+            shape.included::<F, _>(self)
+        } else {
+            !shard.fpu_arith_events.is_empty()
+        }
+    }
+
+    fn local_only(&self) -> bool {
+        true
+    }
+}
+
+/// Returns the `N` little endian bytes of `value`.
+fn bytes<F: PrimeField32, const N: usize>(value: &BigUint) -> [F; N] {
+    let mut bytes = value.to_bytes_le();
+    debug_assert!(bytes.len() <= N);
+    bytes.resize(N, 0);
+    core::array::from_fn(|i| F::from_canonical_u8(bytes[i]))
+}
+
+fn add_msb_events(blu: &mut impl ByteRecord, bytes: &[u8]) {
+    for &byte in bytes {
+        blu.add_byte_lookup_event(ByteLookupEvent {
+            opcode: ByteOpcode::MSB,
+            a1: (byte >> 7) as u16,
+            a2: 0,
+            b: byte,
+            c: 0,
+        });
+    }
+}
+
+impl FpuArithChip {
+    fn event_to_row<F: PrimeField32>(
+        &self,
+        event: &FpuEvent,
+        cols: &mut FpuArithCols<F>,
+        blu: &mut impl ByteRecord,
+    ) {
+        cols.pc = F::from_canonical_u32(event.pc);
+        cols.next_pc = F::from_canonical_u32(event.next_pc);
+        cols.shard = F::from_canonical_u32(event.shard);
+        cols.clk = F::from_canonical_u32(event.clk);
+        cols.op_b_value = event.b.into();
+        cols.op_c_value = event.c.into();
+
+        cols.is_add_s = F::from_bool(event.opcode == Opcode::FADD_S);
+        cols.is_add_d = F::from_bool(event.opcode == Opcode::FADD_D);
+        cols.is_sub_s = F::from_bool(event.opcode == Opcode::FSUB_S);
+        cols.is_sub_d = F::from_bool(event.opcode == Opcode::FSUB_D);
+        cols.is_mul_s = F::from_bool(event.opcode == Opcode::FMUL_S);
+        cols.is_mul_d = F::from_bool(event.opcode == Opcode::FMUL_D);
+        cols.is_div_s = F::from_bool(event.opcode == Opcode::FDIV_S);
+        cols.is_div_d = F::from_bool(event.opcode == Opcode::FDIV_D);
+        cols.is_sqrt_s = F::from_bool(event.opcode == Opcode::FSQRT_S);
+        cols.is_sqrt_d = F::from_bool(event.opcode == Opcode::FSQRT_D);
+        cols.is_cvt_s_d = F::from_bool(event.opcode == Opcode::CVT_S_D);
+        cols.is_cvt_d_s = F::from_bool(event.opcode == Opcode::CVT_D_S);
+        cols.is_cvt_s_w = F::from_bool(event.opcode == Opcode::CVT_S_W);
+        cols.is_cvt_d_w = F::from_bool(event.opcode == Opcode::CVT_D_W);
+        cols.is_cvt_w_s = F::from_bool(event.opcode == Opcode::CVT_W_S);
+        cols.is_cvt_w_d = F::from_bool(event.opcode == Opcode::CVT_W_D);
+        cols.is_trunc_w_s = F::from_bool(event.opcode == Opcode::TRUNC_W_S);
+        cols.is_trunc_w_d = F::from_bool(event.opcode == Opcode::TRUNC_W_D);
+
+        // Populate the register accesses.
+        for (access, record) in cols.fs_access.iter_mut().zip(event.access.fs) {
+            if let Some(record) = record {
+                access.populate(record, blu);
+            }
+        }
+        for (access, record) in cols.ft_access.iter_mut().zip(event.access.ft) {
+            if let Some(record) = record {
+                access.populate(record, blu);
+            }
+        }
+        for (access, record) in cols.fd_access.iter_mut().zip(event.access.fd) {
+            if let Some(record) = record {
+                access.populate(record, blu);
+            }
+        }
+
+        let is_double = source_is_double(event.opcode);
+        let (fs, ft) = (event.fs(), if is_binary(event.opcode) { event.ft() } else { 0 });
+        cols.x.populate(blu, fs, is_double);
+        cols.y.populate(blu, ft, is_double);
+
+        let rounding = FpuRounding::new(event.opcode, fs, ft);
+        let flags = FpuArithFlags::<F>::new(cols);
+        self.populate_special(cols, &flags, &rounding, blu);
+        self.populate_significand(cols, &flags, &rounding, blu);
+        self.populate_division(cols, &flags, &rounding, event.opcode, blu);
+        self.populate_result(cols, &flags, &rounding);
+        debug_assert_eq!(rounding.result, event.fd());
+    }
+
+    fn populate_special<F: PrimeField32>(
+        &self,
+        cols: &mut FpuArithCols<F>,
+        flags: &FpuArithFlags<F>,
+        rounding: &FpuRounding,
+        blu: &mut impl ByteRecord,
+    ) {
+        let xor = |a: F, b: F| a + b - a * b * F::TWO;
+        let or = |a: F, b: F| a + b - a * b;
+        let (x, y) = (cols.x, cols.y);
+
+        cols.y_sign = xor(y.sign, flags.is_sub_op);
+        cols.sign_xor = xor(x.sign, y.sign);
+        cols.eff_sub = xor(x.sign, cols.y_sign);
+        cols.word_neg = flags.is_i2f * x.sign;
+        if flags.is_i2f == F::ONE {
+            cols.abs_word = Word::from(rounding.abs_word);
+        }
+
+        cols.any_nan = or(x.is_nan, y.is_nan);
+        cols.any_inf = or(x.is_inf, y.is_inf);
+        cols.both_inf = x.is_inf * y.is_inf;
+        cols.zero_inf = x.is_zero * y.is_inf + x.is_inf * y.is_zero;
+        cols.zero_zero_inf_inf = x.is_zero * y.is_zero + x.is_inf * y.is_inf;
+        cols.div_inf = or(x.is_inf, y.is_zero);
+        cols.op_nan = flags.is_add_op * cols.both_inf * cols.eff_sub
+            + flags.is_mul * cols.zero_inf
+            + flags.is_div * cols.zero_zero_inf_inf
+            + flags.is_sqrt * x.sign * (F::ONE - x.is_zero);
+        cols.res_nan = flags.is_fp * or(cols.any_nan, cols.op_nan);
+        cols.special_inf = (F::ONE - cols.res_nan)
+            * ((flags.is_add_op + flags.is_mul) * cols.any_inf
+                + flags.is_div * cols.div_inf
+                + (flags.is_sqrt + flags.is_f2f) * x.is_inf);
+        cols.special_zero = flags.is_div * y.is_inf * (F::ONE - cols.res_nan);
+
+        let n = rounding.n.to_le_bytes();
+        cols.n = core::array::from_fn(|i| F::from_canonical_u8(n[i]));
+        cols.n_zero.populate(n[..N_BYTES].iter().map(|&byte| byte as u32).sum());
+        blu.add_u8_range_checks(&n[..N_BYTES]);
+
+        cols.int_big = F::from_bool(rounding.int_big);
+        if flags.is_f2i == F::ONE {
+            let x_exponent = x.exponent + x.exponent_zero.result;
+            let bias = flags.sd * F::from_canonical_u32(1023 - 127) + F::from_canonical_u32(127);
+            cols.int_big_gap = cols.int_big * (x_exponent - bias - F::from_canonical_u32(32))
+                + (F::ONE - cols.int_big) * (bias + F::from_canonical_u32(31) - x_exponent);
+            blu.add_u16_range_check(cols.int_big_gap.as_canonical_u32() as u16);
+        }
+
+        cols.is_round = (F::ONE - cols.res_nan - cols.special_inf - cols.special_zero)
+            * (F::ONE - cols.n_zero.result)
+            * (F::ONE - cols.int_big);
+        debug_assert_eq!(cols.is_round, F::from_bool(rounding.is_round));
+    }
+
+    fn populate_significand<F: PrimeField32>(
+        &self,
+        cols: &mut FpuArithCols<F>,
+        flags: &FpuArithFlags<F>,
+        rounding: &FpuRounding,
+        blu: &mut impl ByteRecord,
+    ) {
+        let (x, y) = (cols.x, cols.y);
+        cols.swap = F::from_bool(rounding.swap);
+        cols.clamp = F::from_bool(rounding.clamp);
+        let x_exponent = x.exponent + x.exponent_zero.result;
+        let y_exponent = y.exponent + y.exponent_zero.result;
+        cols.big_exponent = cols.swap * y_exponent + (F::ONE - cols.swap) * x_exponent;
+        cols.big_sign = cols.swap * cols.y_sign + (F::ONE - cols.swap) * x.sign;
+
+        for i in 0..MANTISSA_BYTES {
+            cols.small[i] = cols.swap * x.mantissa[i] + (F::ONE - cols.swap) * y.mantissa[i];
+        }
+        cols.small_zero.populate_from_field_element(cols.small.iter().copied().sum());
+
+        if flags.is_add_op == F::ONE {
+            let shift = rounding.shift as usize;
+            cols.shift_limb[shift / 8] = F::ONE;
+            cols.shift_bit[shift % 8] = F::ONE;
+            let gap = cols.big_exponent * F::TWO - x_exponent - y_exponent;
+            let max_shift = flags.rd * F::from_canonical_u32(53 - 24) + F::from_canonical_u32(27);
+            cols.clamp_gap =
+                cols.clamp * (gap - max_shift - F::ONE) + (F::ONE - cols.clamp) * (max_shift - gap);
+            blu.add_u16_range_check(cols.clamp_gap.as_canonical_u32() as u16);
+
+            let small = rounding.s.to_le_bytes();
+            cols.s = core::array::from_fn(|i| F::from_canonical_u8(small[i]));
+        }
+
+        let lhs = rounding.n_lhs.to_le_bytes();
+        cols.n_lhs = core::array::from_fn(|i| F::from_canonical_u8(lhs[i]));
+        let rhs = rounding.n_rhs.to_le_bytes();
+        cols.n_rhs = core::array::from_fn(|i| F::from_canonical_u8(rhs[i]));
+    }
+
+    fn populate_division<F: PrimeField32>(
+        &self,
+        cols: &mut FpuArithCols<F>,
+        flags: &FpuArithFlags<F>,
+        rounding: &FpuRounding,
+        opcode: Opcode,
+        blu: &mut impl ByteRecord,
+    ) {
+        let (l, r) = (rounding.l as usize, rounding.r as usize);
+        cols.l_limb[l / 8] = F::ONE;
+        cols.l_bit[l % 8] = F::ONE;
+        cols.r_limb[r / 8] = F::ONE;
+        cols.r_bit[r % 8] = F::ONE;
+        cols.excess = F::from_canonical_u32(rounding.excess);
+        blu.add_u16_range_check(rounding.excess as u16);
+
+        let m = rounding.m.to_le_bytes();
+        cols.m = core::array::from_fn(|i| F::from_canonical_u8(m[i]));
+        cols.d = bytes(&rounding.d);
+        blu.add_u8_range_checks_field(&cols.d);
+
+        let q = rounding.q.to_le_bytes();
+        cols.q = core::array::from_fn(|i| F::from_canonical_u8(q[i]));
+        cols.q_msb = core::array::from_fn(|i| F::from_canonical_u8(q[i] >> 7));
+        add_msb_events(blu, &q[..MANTISSA_BYTES]);
+        let r_bytes = bytes::<F, D_BYTES>(&rounding.rem);
+        cols.r = r_bytes;
+        cols.r_msb = r_bytes.map(|byte| F::from_canonical_u32(byte.as_canonical_u32() >> 7));
+        add_msb_events(blu, &r_bytes.map(|byte| byte.as_canonical_u32() as u8));
+        cols.q_odd = F::from_canonical_u8(q[0] & 1);
+        blu.add_byte_lookup_event(ByteLookupEvent {
+            opcode: ByteOpcode::AND,
+            a1: (q[0] & 1) as u16,
+            a2: 0,
+            b: q[0],
+            c: 1,
+        });
+
+        let top_byte = match FpuArithKind::of(opcode).0 {
+            FpuArithKind::FloatToInt => q[3],
+            _ if result_is_double(opcode) => q[6] << 3,
+            _ => q[2],
+        };
+        cols.q_top_byte = F::from_canonical_u8(top_byte);
+        cols.q_top = F::from_canonical_u8(top_byte >> 7);
+        add_msb_events(blu, &[top_byte]);
+
+        cols.exponent = F::from_canonical_u32(rounding.e_q - 1);
+        blu.add_u16_range_check((rounding.e_q - 1) as u16);
+        cols.overflow = F::from_bool(rounding.overflow);
+        if rounding.is_round {
+            let e_q = cols.exponent + F::ONE;
+            let emax = flags.rd * F::from_canonical_u32(2047 - 255) + F::from_canonical_u32(255);
+            cols.overflow_gap =
+                cols.overflow * (e_q - emax) + (F::ONE - cols.overflow) * (emax - F::ONE - e_q);
+            blu.add_u16_range_check(cols.overflow_gap.as_canonical_u32() as u16);
+
+            // Compare the remainder, and twice the remainder, with the bound.
+            let bound = if FpuArithKind::of(opcode).0 == FpuArithKind::Sqrt {
+                BigUint::from(rounding.q) * 2u32 + 1u32
+            } else {
+                rounding.d.clone()
+            };
+            let byte_string = |value: &BigUint, len: usize| {
+                let mut bytes = value.to_bytes_le();
+                bytes.resize(len, 0);
+                bytes
+            };
+            let twice = &rounding.rem * 2u32;
+            cols.remainder.populate(
+                blu,
+                &byte_string(&rounding.rem, D_BYTES),
+                &byte_string(&bound, D_BYTES),
+            );
+            cols.half.populate(
+                blu,
+                &byte_string(&twice, D_BYTES + 1),
+                &byte_string(&bound, D_BYTES + 1),
+            );
+        }
+        cols.z_valid = cols.is_round * (F::ONE - cols.overflow);
+        cols.round_up = F::from_bool(rounding.round_up);
+
+        let z = rounding.z.to_le_bytes();
+        cols.z = core::array::from_fn(|i| F::from_canonical_u8(z[i]));
+        blu.add_u8_range_checks(&z);
+
+        if flags.is_f2i == F::ONE {
+            let msb = z[3] >> 7;
+            cols.z_msb = F::from_canonical_u8(msb);
+            add_msb_events(blu, &[z[3]]);
+            cols.z_rest_zero
+                .populate(z[0] as u32 + z[1] as u32 + z[2] as u32 + z[3] as u32 - 128 * msb as u32);
+        }
+    }
+
+    fn populate_result<F: PrimeField32>(
+        &self,
+        cols: &mut FpuArithCols<F>,
+        flags: &FpuArithFlags<F>,
+        rounding: &FpuRounding,
+    ) {
+        let x = cols.x;
+        cols.int_valid = flags.is_f2i * (F::ONE - cols.z[4] - cols.z_msb)
+            + x.sign * cols.z_msb * cols.z_rest_zero.result;
+        cols.res_int = cols.is_round * flags.is_f2i * cols.int_valid;
+        cols.int_neg = cols.res_int * x.sign;
+        if cols.res_int == F::ONE {
+            let magnitude = rounding.z as u32;
+            let value = if cols.int_neg == F::ONE { magnitude.wrapping_neg() } else { magnitude };
+            cols.int_value = Word::from(value);
+        }
+
+        cols.res_inf = cols.special_inf + cols.is_round * cols.overflow;
+        cols.res_zero = cols.special_zero
+            + (flags.is_real - cols.res_nan - cols.special_inf - cols.special_zero)
+                * cols.n_zero.result;
+        cols.res_float = cols.is_round * (F::ONE - flags.is_f2i) * (F::ONE - cols.overflow);
+        cols.res_invalid = flags.is_f2i * (cols.int_big + cols.is_round) - cols.res_int;
+
+        let is_mul_div = flags.is_mul + flags.is_div;
+        let is_unary = flags.is_sqrt + flags.is_f2f;
+        cols.zero_sign =
+            flags.is_add_op * x.sign * cols.y_sign + is_mul_div * cols.sign_xor + is_unary * x.sign;
+        cols.add_sign = x.is_inf * x.sign
+            + cols.y.is_inf * (F::ONE - x.is_inf) * cols.y_sign
+            + (F::ONE - cols.any_inf) * cols.big_sign;
+        cols.sign = flags.is_add_op * cols.add_sign
+            + is_mul_div * cols.sign_xor
+            + (is_unary + flags.is_i2f) * x.sign;
+        cols.out_sign =
+            cols.res_zero * cols.zero_sign + (cols.res_inf + cols.res_float) * cols.sign;
+    }
+
+    /// Populates the quotients of the identities, which vanish on padding rows.
+    fn populate_identities<F: PrimeField32>(
+        &self,
+        cols: &mut FpuArithCols<F>,
+        blu: &mut impl ByteRecord,
+        is_real: bool,
+    ) {
+        let [n, d, main, z] = vanishing_polynomials::<F, F>(cols);
+        cols.n_identity.populate(blu, n.coefficients(), is_real);
+        cols.d_identity.populate(blu, d.coefficients(), is_real);
+        cols.main_identity.populate(blu, main.coefficients(), is_real);
+        cols.z_identity.populate(blu, z.coefficients(), is_real);
+    }
+}
@@ -0,0 +1,128 @@
+//! An operation to compare two byte strings of the same length.
+//!
+//! Unlike the comparators in `operations`, this one is three-way: it proves which of `a < b`,
+//! `a == b` and `a > b` holds.
+
+use std::cmp::Ordering;
+
+use p3_air::AirBuilder;
+use p3_field::{Field, FieldAlgebra, PrimeField32};
+use zkm_core_executor::{
+    events::{ByteLookupEvent, ByteRecord},
+    ByteOpcode,
+};
+use zkm_derive::AlignedBorrow;
+use zkm_stark::air::ZKMAirBuilder;
+
+/// A set of columns needed to compare two little endian byte strings.
+#[derive(AlignedBorrow, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct FpCompareCols<T, const N: usize> {
+    /// A one-hot flag on the most significant byte at which the strings differ, or all zero if
+    /// the strings are equal.
+    pub first_diff: [T; N],
+
+    /// The byte of `a` at `first_diff`.
+    pub a_byte: T,
+
+    /// The byte of `b` at `first_diff`.
+    pub b_byte: T,
+
+    /// The inverse of `a_byte - b_byte`.
+    pub diff_inverse: T,
+
+    /// Whether `a < b`.
+    pub is_lt: T,
+}
+
+impl<T: Default, const N: usize> Default for FpCompareCols<T, N> {
+    fn default() -> Self {
+        Self {
+            first_diff: core::array::from_fn(|_| T::default()),
+            a_byte: T::default(),
+            b_byte: T::default(),
+            diff_inverse: T::default(),
+            is_lt: T::default(),
+        }
+    }
+}
+
+impl<F: PrimeField32, const N: usize> FpCompareCols<F, N> {
+    /// Populates the columns from the bytes of `a` and `b`, which are assumed to be range checked.
+    pub fn populate(&mut self, record: &mut impl ByteRecord, a: &[u8], b: &[u8]) -> Ordering {
+        debug_assert!(a.len() == N && b.len() == N);
+        *self = Self::default();
+        let Some(i) = (0..N).rev().find(|&i| a[i] != b[i]) else {
+            return Ordering::Equal;
+        };
+        self.first_diff[i] = F::ONE;
+        self.a_byte = F::from_canonical_u8(a[i]);
+        self.b_byte = F::from_canonical_u8(b[i]);
+        self.diff_inverse = (self.a_byte - self.b_byte).inverse();
+        self.is_lt = F::from_bool(a[i] < b[i]);
+        record.add_byte_lookup_event(ByteLookupEvent {
+            opcode: ByteOpcode::LTU,
+            a1: (a[i] < b[i]) as u16,
+            a2: 0,
+            b: a[i],
+            c: b[i],
+        });
+        a[i].cmp(&b[i])
+    }
+}
+
+impl<F: Field, const N: usize> FpCompareCols<F, N> {
+    /// Evaluates the comparison of `a` and `b` and returns the `lt`, `eq` and `gt` flags.
+    ///
+    /// The flags are all zero if `is_real` is zero, and otherwise exactly one of them is one.
+    pub fn eval<AB: ZKMAirBuilder>(
+        builder: &mut AB,
+        a: &[AB::Expr],
+        b: &[AB::Expr],
+        cols: FpCompareCols<AB::Var, N>,
+        is_real: AB::Expr,
+    ) -> (AB::Expr, AB::Expr, AB::Expr) {
+        debug_assert!(a.len() == N && b.len() == N);
+
+        // At most one flag is set, and none on padding rows.
+        let mut differs = AB::Expr::zero();
+        for flag in cols.first_diff {
+            builder.assert_bool(flag);
+            differs = differs + flag;
+        }
+        builder.assert_bool(differs.clone());
+        builder.when_not(is_real.clone()).assert_zero(differs.clone());
+
+        // The bytes above the flag are equal, and the flagged bytes are selected.
+        let mut visited = AB::Expr::zero();
+        let mut a_byte = AB::Expr::zero();
+        let mut b_byte = AB::Expr::zero();
+        for i in (0..N).rev() {
+            let flag = cols.first_diff[i];
+            visited = visited + flag;
+            a_byte = a_byte + a[i].clone() * flag;
+            b_byte = b_byte + b[i].clone() * flag;
+            builder
+                .assert_zero((is_real.clone() - visited.clone()) * (a[i].clone() - b[i].clone()));
+        }
+        builder.assert_eq(cols.a_byte, a_byte);
+        builder.assert_eq(cols.b_byte, b_byte);
+
+        // The flagged bytes differ.
+        builder.assert_eq((cols.a_byte - cols.b_byte) * cols.diff_inverse, differs.clone());
+
+        // Compare the flagged bytes.
+        builder.assert_bool(cols.is_lt);
+        builder.when_not(differs.clone()).assert_zero(cols.is_lt);
+        builder.send_byte(
+            ByteOpcode::LTU.as_field::<AB::F>(),
+            cols.is_lt,
+            cols.a_byte,
+            cols.b_byte,
+            differs.clone(),
+        );
+
+        let lt: AB::Expr = cols.is_lt.into();
+        (lt.clone(), is_real - differs.clone(), differs - lt)
+    }
+}
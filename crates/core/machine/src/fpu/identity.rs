@@ -0,0 +1,88 @@
+//! An operation to check a polynomial identity between byte limbed integers.
+//!
+//! The integers are given as polynomials in `x` whose coefficients are small but not necessarily
+//! bytes. The identity holds over the integers if the vanishing polynomial has a root at `x = 256`,
+//! which is witnessed by the quotient of the vanishing polynomial by `x - 256`.
+
+use p3_field::{Field, FieldAlgebra, PrimeField32};
+use zkm_core_executor::events::ByteRecord;
+use zkm_derive::AlignedBorrow;
+use zkm_stark::air::{Polynomial, ZKMAirBuilder};
+
+use crate::{
+    air::WordAirBuilder,
+    operations::field::util::{compute_root_quotient_and_shift, split_u16_limbs_to_u8_limbs},
+};
+
+/// The bound on the absolute value of the quotient coefficients.
+const WITNESS_OFFSET: usize = 1 << 14;
+
+/// A set of columns needed to check that a polynomial vanishes at `x = 256`.
+#[derive(AlignedBorrow, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct FpIdentityCols<T, const W: usize> {
+    /// The low bytes of the shifted quotient coefficients.
+    pub witness_low: [T; W],
+
+    /// The high bytes of the shifted quotient coefficients.
+    pub witness_high: [T; W],
+}
+
+impl<T: Default, const W: usize> Default for FpIdentityCols<T, W> {
+    fn default() -> Self {
+        Self {
+            witness_low: core::array::from_fn(|_| T::default()),
+            witness_high: core::array::from_fn(|_| T::default()),
+        }
+    }
+}
+
+impl<F: PrimeField32, const W: usize> FpIdentityCols<F, W> {
+    /// Populates the quotient of `vanishing`, which must vanish at `x = 256`.
+    ///
+    /// Padding rows must be populated with the zero polynomial, as the identity is checked on
+    /// every row.
+    pub fn populate(&mut self, record: &mut impl ByteRecord, vanishing: &[F], is_real: bool) {
+        debug_assert!(vanishing.len() <= W + 1);
+        let vanishing = Polynomial::from_coefficients(vanishing);
+        let witness = compute_root_quotient_and_shift(&vanishing, WITNESS_OFFSET, 8, W);
+        let (witness_low, witness_high) = split_u16_limbs_to_u8_limbs(&witness);
+        self.witness_low = witness_low.try_into().unwrap();
+        self.witness_high = witness_high.try_into().unwrap();
+        if is_real {
+            record.add_u8_range_checks_field(&self.witness_low);
+            record.add_u8_range_checks_field(&self.witness_high);
+        }
+    }
+}
+
+impl<F: Field, const W: usize> FpIdentityCols<F, W> {
+    /// Evaluates the identity `vanishing(256) = 0` with degree three constraints, as long as
+    /// `vanishing` has degree at most three in the columns.
+    pub fn eval<AB: ZKMAirBuilder>(
+        builder: &mut AB,
+        vanishing: &Polynomial<AB::Expr>,
+        cols: FpIdentityCols<AB::Var, W>,
+        is_real: AB::Expr,
+    ) {
+        builder.slice_range_check_u8(&cols.witness_low, is_real.clone());
+        builder.slice_range_check_u8(&cols.witness_high, is_real);
+
+        // Reconstruct and shift back the quotient.
+        let limb = AB::F::from_canonical_u32(256);
+        let offset = AB::F::from_canonical_usize(WITNESS_OFFSET);
+        let witness = cols
+            .witness_low
+            .into_iter()
+            .zip(cols.witness_high)
+            .map(|(low, high)| low + high * limb - offset)
+            .collect::<Polynomial<AB::Expr>>();
+
+        // Multiply by `x - 256` and compare with the vanishing polynomial.
+        let root_monomial = Polynomial::new(vec![(-limb).into(), AB::Expr::one()]);
+        let constraints = vanishing - &(witness * root_monomial);
+        for constraint in constraints.as_coefficients() {
+            builder.assert_zero(constraint);
+        }
+    }
+}
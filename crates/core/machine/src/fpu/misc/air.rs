@@ -0,0 +1,405 @@
+use std::borrow::Borrow;
+
+use p3_air::{Air, AirBuilder};
+use p3_field::FieldAlgebra;
+use p3_matrix::Matrix;
+use zkm_core_executor::{events::MemoryAccessPosition, ByteOpcode, Opcode, NUM_REGISTERS};
+use zkm_stark::{air::ZKMAirBuilder, Word};
+
+use crate::{
+    air::{MemoryAirBuilder, WordAirBuilder},
+    fpu::{compare::FpCompareCols, unpack::FpUnpackCols},
+    memory::MemoryCols,
+    operations::{IsZeroOperation, KoalaBearWordRangeChecker},
+};
+
+use super::{
+    columns::{FpuMiscCols, NUM_CONDITIONS},
+    FpuMiscChip,
+};
+
+/// The compare opcodes of each precision, indexed by `cond - 1`.
+const COMPARE_S: [Opcode; NUM_CONDITIONS] = [
+    Opcode::C_UN_S,
+    Opcode::C_EQ_S,
+    Opcode::C_UEQ_S,
+    Opcode::C_OLT_S,
+    Opcode::C_ULT_S,
+    Opcode::C_OLE_S,
+    Opcode::C_ULE_S,
+];
+const COMPARE_D: [Opcode; NUM_CONDITIONS] = [
+    Opcode::C_UN_D,
+    Opcode::C_EQ_D,
+    Opcode::C_UEQ_D,
+    Opcode::C_OLT_D,
+    Opcode::C_ULT_D,
+    Opcode::C_OLE_D,
+    Opcode::C_ULE_D,
+];
+
+impl<AB> Air<AB> for FpuMiscChip
+where
+    AB: ZKMAirBuilder,
+    AB::Var: Sized,
+{
+    #[inline(never)]
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let local: &FpuMiscCols<AB::Var> = (*local).borrow();
+
+        let is_compare_s =
+            local.is_compare_s.iter().fold(AB::Expr::zero(), |acc, &flag| acc + flag);
+        let is_compare_d =
+            local.is_compare_d.iter().fold(AB::Expr::zero(), |acc, &flag| acc + flag);
+        let is_compare = is_compare_s.clone() + is_compare_d.clone();
+        let is_memory = local.is_ldc1 + local.is_sdc1;
+        let is_sign = local.is_abs_s + local.is_abs_d + local.is_neg_s + local.is_neg_d;
+        let is_real = is_sign.clone() + local.is_mov_d + is_compare.clone() + is_memory.clone();
+
+        builder.assert_bool(local.is_abs_s);
+        builder.assert_bool(local.is_abs_d);
+        builder.assert_bool(local.is_neg_s);
+        builder.assert_bool(local.is_neg_d);
+        builder.assert_bool(local.is_mov_d);
+        for (&s, &d) in local.is_compare_s.iter().zip(local.is_compare_d.iter()) {
+            builder.assert_bool(s);
+            builder.assert_bool(d);
+        }
+        builder.assert_bool(local.is_ldc1);
+        builder.assert_bool(local.is_sdc1);
+        builder.assert_bool(is_real.clone());
+
+        let mut opcode = local.is_abs_s * Opcode::FABS_S.as_field::<AB::F>()
+            + local.is_abs_d * Opcode::FABS_D.as_field::<AB::F>()
+            + local.is_neg_s * Opcode::FNEG_S.as_field::<AB::F>()
+            + local.is_neg_d * Opcode::FNEG_D.as_field::<AB::F>()
+            + local.is_mov_d * Opcode::FMOV_D.as_field::<AB::F>()
+            + local.is_ldc1 * Opcode::LDC1.as_field::<AB::F>()
+            + local.is_sdc1 * Opcode::SDC1.as_field::<AB::F>();
+        for i in 0..NUM_CONDITIONS {
+            opcode = opcode
+                + local.is_compare_s[i] * COMPARE_S[i].as_field::<AB::F>()
+                + local.is_compare_d[i] * COMPARE_D[i].as_field::<AB::F>();
+        }
+
+        builder.receive_instruction(
+            local.shard,
+            local.clk,
+            local.pc,
+            local.next_pc,
+            local.next_pc + AB::Expr::from_canonical_u32(4),
+            AB::Expr::zero(),
+            opcode,
+            Word([AB::Expr::zero(), AB::Expr::zero(), AB::Expr::zero(), AB::Expr::zero()]),
+            local.op_b_value,
+            local.op_c_value,
+            Word([AB::Expr::zero(), AB::Expr::zero(), AB::Expr::zero(), AB::Expr::zero()]),
+            AB::Expr::zero(),
+            AB::Expr::zero(),
+            AB::Expr::one(),
+            AB::Expr::zero(),
+            AB::Expr::one(),
+            AB::Expr::zero(),
+            is_real.clone(),
+        );
+
+        // Read the source registers. A double occupies the even register and the next one.
+        let source_double = local.is_abs_d + local.is_neg_d + local.is_mov_d + is_compare_d.clone();
+        let reads_fs = is_sign.clone() + local.is_mov_d + is_compare.clone();
+        let fs_register = local.op_b_value[0];
+        let ft_register = local.op_b_value[1];
+        let b_clk = local.clk + AB::F::from_canonical_u32(MemoryAccessPosition::B as u32);
+        let c_clk = local.clk + AB::F::from_canonical_u32(MemoryAccessPosition::C as u32);
+        builder.eval_memory_access(
+            local.shard,
+            b_clk.clone(),
+            fs_register,
+            &local.fs_access[0],
+            reads_fs.clone(),
+        );
+        builder.eval_memory_access(
+            local.shard,
+            b_clk,
+            fs_register + AB::Expr::one(),
+            &local.fs_access[1],
+            source_double.clone(),
+        );
+        builder.eval_memory_access(
+            local.shard,
+            c_clk.clone(),
+            ft_register,
+            &local.ft_access[0],
+            is_compare.clone(),
+        );
+        builder.eval_memory_access(
+            local.shard,
+            c_clk,
+            ft_register + AB::Expr::one(),
+            &local.ft_access[1],
+            is_compare_d.clone(),
+        );
+        for access in local.fs_access.iter().chain(local.ft_access.iter()) {
+            builder.assert_word_eq(*access.value(), *access.prev_value());
+        }
+        builder.when_not(source_double.clone()).assert_word_zero(*local.fs_access[1].value());
+        builder.when_not(is_compare.clone()).assert_word_zero(*local.ft_access[0].value());
+        builder.when_not(is_compare_d.clone()).assert_word_zero(*local.ft_access[1].value());
+
+        // Write the destination registers, which are read by `sdc1`.
+        let result_double = local.is_abs_d + local.is_neg_d + local.is_mov_d + is_memory.clone();
+        builder.assert_eq(
+            local.fd_register,
+            is_memory.clone() * local.op_c_value[2]
+                + (AB::Expr::one() - is_memory.clone()) * local.op_c_value[0],
+        );
+        let a_clk = local.clk + AB::F::from_canonical_u32(MemoryAccessPosition::A as u32);
+        builder.eval_memory_access(
+            local.shard,
+            a_clk.clone(),
+            local.fd_register,
+            &local.fd_access[0],
+            is_real.clone(),
+        );
+        builder.eval_memory_access(
+            local.shard,
+            a_clk,
+            local.fd_register + AB::Expr::one(),
+            &local.fd_access[1],
+            result_double,
+        );
+
+        // Unpack the operands.
+        let x_lo = *local.fs_access[0].value();
+        let x_hi = *local.fs_access[1].value();
+        let y_lo = *local.ft_access[0].value();
+        let y_hi = *local.ft_access[1].value();
+        FpUnpackCols::<AB::F>::eval(
+            builder,
+            x_lo,
+            x_hi,
+            local.is_abs_d + local.is_neg_d + is_compare_d.clone(),
+            local.x,
+            is_sign.clone() + is_compare.clone(),
+        );
+        FpUnpackCols::<AB::F>::eval(
+            builder,
+            y_lo,
+            y_hi,
+            is_compare_d.clone(),
+            local.y,
+            is_compare.clone(),
+        );
+
+        self.eval_sign(builder, local);
+        self.eval_compare(builder, local, is_compare_d, is_compare);
+        self.eval_memory(builder, local, is_memory);
+    }
+}
+
+impl FpuMiscChip {
+    /// Evaluates `abs`, `neg` and `mov.d`, which only change the sign bit.
+    fn eval_sign<AB: ZKMAirBuilder>(&self, builder: &mut AB, local: &FpuMiscCols<AB::Var>) {
+        let fs = [*local.fs_access[0].value(), *local.fs_access[1].value()];
+        let fd = [*local.fd_access[0].value(), *local.fd_access[1].value()];
+
+        // `abs` clears the sign bit and `neg` flips it.
+        let is_abs = local.is_abs_s + local.is_abs_d;
+        let is_neg = local.is_neg_s + local.is_neg_d;
+        let top_byte = local.x.top_byte + is_neg.clone() * AB::F::from_canonical_u32(128)
+            - local.x.sign * (is_abs + is_neg * AB::F::TWO) * AB::F::from_canonical_u32(128);
+
+        let single = local.is_abs_s + local.is_neg_s;
+        let mut builder_single = builder.when(single);
+        for i in 0..3 {
+            builder_single.assert_eq(fd[0][i], fs[0][i]);
+        }
+        builder_single.assert_eq(fd[0][3], top_byte.clone());
+
+        let double = local.is_abs_d + local.is_neg_d;
+        let mut builder_double = builder.when(double);
+        builder_double.assert_word_eq(fd[0], fs[0]);
+        for i in 0..3 {
+            builder_double.assert_eq(fd[1][i], fs[1][i]);
+        }
+        builder_double.assert_eq(fd[1][3], top_byte);
+
+        let mut builder_mov = builder.when(local.is_mov_d);
+        builder_mov.assert_word_eq(fd[0], fs[0]);
+        builder_mov.assert_word_eq(fd[1], fs[1]);
+    }
+
+    /// Evaluates `c.cond`, which writes whether the condition holds to a condition code.
+    fn eval_compare<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &FpuMiscCols<AB::Var>,
+        is_double: AB::Expr,
+        is_compare: AB::Expr,
+    ) {
+        let one = AB::Expr::one();
+        let (x, y) = (local.x, local.y);
+
+        // The magnitudes are the operands without the sign bit, as 8 bytes.
+        let magnitude = |lo: Word<AB::Var>, hi: Word<AB::Var>, sign: AB::Var| {
+            let sign_weight = sign * AB::F::from_canonical_u32(128);
+            [
+                lo[0].into(),
+                lo[1].into(),
+                lo[2].into(),
+                lo[3] - (one.clone() - is_double.clone()) * sign_weight.clone(),
+                is_double.clone() * hi[0],
+                is_double.clone() * hi[1],
+                is_double.clone() * hi[2],
+                is_double.clone() * (hi[3] - sign_weight),
+            ]
+        };
+        let x_magnitude =
+            magnitude(*local.fs_access[0].value(), *local.fs_access[1].value(), x.sign);
+        let y_magnitude =
+            magnitude(*local.ft_access[0].value(), *local.ft_access[1].value(), y.sign);
+        let (magnitude_lt, magnitude_eq, magnitude_gt) = FpCompareCols::<AB::F, 8>::eval(
+            builder,
+            &x_magnitude,
+            &y_magnitude,
+            local.magnitude,
+            is_compare.clone(),
+        );
+
+        // Order the operands by sign and magnitude. Zeros of either sign are equal. The degree
+        // three relations are checked on every row, and hold on other rows since the comparison is
+        // zero.
+        builder.assert_eq(
+            local.signed_lt,
+            x.sign * (one.clone() - y.sign)
+                + (one.clone() - x.sign) * (one.clone() - y.sign) * magnitude_lt
+                + x.sign * y.sign * magnitude_gt,
+        );
+        builder.assert_eq(
+            local.same_sign_eq,
+            (one.clone() - x.sign - y.sign + x.sign * y.sign * AB::F::TWO) * magnitude_eq,
+        );
+        let mut builder_compare = builder.when(is_compare.clone());
+        builder_compare.assert_eq(local.is_unordered, x.is_nan + y.is_nan - x.is_nan * y.is_nan);
+        builder_compare.assert_eq(local.both_zero, x.is_zero * y.is_zero);
+        builder_compare.assert_eq(
+            local.is_ordered,
+            (one.clone() - local.is_unordered) * (one.clone() - local.both_zero),
+        );
+        builder_compare.assert_eq(local.ordered_lt, local.is_ordered * local.signed_lt);
+        builder_compare.assert_eq(
+            local.ordered_eq,
+            (one.clone() - local.is_unordered) * local.both_zero
+                + local.is_ordered * local.same_sign_eq,
+        );
+
+        // The condition is a disjunction of the `un`, `eq` and `lt` bits of `cond`.
+        let condition_bit = |bit: usize| {
+            (0..NUM_CONDITIONS).filter(|i| (i + 1) & bit != 0).fold(AB::Expr::zero(), |acc, i| {
+                acc + local.is_compare_s[i] + local.is_compare_d[i]
+            })
+        };
+        let result = condition_bit(1) * local.is_unordered
+            + condition_bit(2) * local.ordered_eq
+            + condition_bit(4) * local.ordered_lt;
+        let fd = *local.fd_access[0].value();
+        let mut builder_compare = builder.when(is_compare);
+        builder_compare.assert_eq(fd[0], result);
+        builder_compare.assert_zero(fd[1]);
+        builder_compare.assert_zero(fd[2]);
+        builder_compare.assert_zero(fd[3]);
+    }
+
+    /// Evaluates `ldc1` and `sdc1`, which move a double between memory and a register pair.
+    fn eval_memory<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &FpuMiscCols<AB::Var>,
+        is_memory: AB::Expr,
+    ) {
+        // The address is the base plus the sign extended 16-bit offset.
+        let sign_byte = local.offset_sign * AB::F::from_canonical_u32(0xFF);
+        builder.send_byte(
+            ByteOpcode::MSB.as_field::<AB::F>(),
+            local.offset_sign,
+            local.op_c_value[1],
+            AB::Expr::zero(),
+            is_memory.clone(),
+        );
+        builder.send_alu(
+            AB::Expr::from_canonical_u32(Opcode::ADD as u32),
+            local.addr_word,
+            local.op_b_value,
+            Word([
+                local.op_c_value[0].into(),
+                local.op_c_value[1].into(),
+                sign_byte.clone(),
+                sign_byte,
+            ]),
+            is_memory.clone(),
+        );
+
+        // Range check the address to be a valid koalabear word.
+        KoalaBearWordRangeChecker::<AB::F>::range_check(
+            builder,
+            local.addr_word,
+            local.addr_word_range_checker,
+            is_memory.clone(),
+        );
+        builder.slice_range_check_u8(&local.addr_word.0[1..3], is_memory.clone());
+
+        // The address is aligned to 8 bytes, which also range checks its least significant byte.
+        builder.send_byte(
+            ByteOpcode::AND.as_field::<AB::F>(),
+            AB::Expr::zero(),
+            local.addr_word[0],
+            AB::Expr::from_canonical_u8(0b111),
+            is_memory.clone(),
+        );
+
+        // We check that `addr_word > NUM_REGISTERS - 1` to avoid registers.
+        builder.send_byte(
+            ByteOpcode::LTU.as_field::<AB::F>(),
+            AB::Expr::one(),
+            AB::Expr::from_canonical_u8(NUM_REGISTERS as u8 - 1),
+            local.addr_word[0],
+            local.most_sig_bytes_zero.result,
+        );
+        builder.when(local.most_sig_bytes_zero.result).assert_one(is_memory.clone());
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            local.addr_word[1] + local.addr_word[2] + local.addr_word[3],
+            local.most_sig_bytes_zero,
+            is_memory.clone(),
+        );
+
+        let addr = local.addr_word.reduce::<AB>();
+        let memory_clk = local.clk + AB::F::from_canonical_u32(MemoryAccessPosition::Memory as u32);
+        builder.eval_memory_access(
+            local.shard,
+            memory_clk.clone(),
+            addr.clone(),
+            &local.memory_access[0],
+            is_memory.clone(),
+        );
+        builder.eval_memory_access(
+            local.shard,
+            memory_clk,
+            addr + AB::Expr::from_canonical_u32(4),
+            &local.memory_access[1],
+            is_memory,
+        );
+
+        // `ldc1` copies memory to the registers, and `sdc1` copies the registers to memory.
+        for (memory, fd) in local.memory_access.iter().zip(local.fd_access.iter()) {
+            let mut builder_ldc1 = builder.when(local.is_ldc1);
+            builder_ldc1.assert_word_eq(*memory.value(), *memory.prev_value());
+            builder_ldc1.assert_word_eq(*fd.value(), *memory.value());
+
+            let mut builder_sdc1 = builder.when(local.is_sdc1);
+            builder_sdc1.assert_word_eq(*fd.value(), *fd.prev_value());
+            builder_sdc1.assert_word_eq(*memory.value(), *fd.value());
+        }
+    }
+}
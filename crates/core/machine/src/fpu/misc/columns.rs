@@ -0,0 +1,94 @@
+use std::mem::size_of;
+use zkm_derive::AlignedBorrow;
+use zkm_stark::Word;
+
+use crate::{
+    fpu::{compare::FpCompareCols, unpack::FpUnpackCols},
+    memory::{MemoryReadCols, MemoryReadWriteCols},
+    operations::{IsZeroOperation, KoalaBearWordRangeChecker},
+};
+
+pub const NUM_FPU_MISC_COLS: usize = size_of::<FpuMiscCols<u8>>();
+
+/// The number of `c.cond` conditions.
+pub const NUM_CONDITIONS: usize = 7;
+
+/// The column layout for the FPU sign, move, compare and doubleword memory instructions.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct FpuMiscCols<T> {
+    /// The current/next program counter of the instruction.
+    pub pc: T,
+    pub next_pc: T,
+
+    /// The shard number.
+    pub shard: T,
+    /// The clock cycle number.
+    pub clk: T,
+
+    /// The value of the second operand.
+    pub op_b_value: Word<T>,
+    /// The value of the third operand.
+    pub op_c_value: Word<T>,
+
+    /// Whether the instruction is `abs.s`.
+    pub is_abs_s: T,
+    /// Whether the instruction is `abs.d`.
+    pub is_abs_d: T,
+    /// Whether the instruction is `neg.s`.
+    pub is_neg_s: T,
+    /// Whether the instruction is `neg.d`.
+    pub is_neg_d: T,
+    /// Whether the instruction is `mov.d`.
+    pub is_mov_d: T,
+    /// Whether the instruction is `c.cond.s`, indexed by `cond - 1`.
+    pub is_compare_s: [T; NUM_CONDITIONS],
+    /// Whether the instruction is `c.cond.d`, indexed by `cond - 1`.
+    pub is_compare_d: [T; NUM_CONDITIONS],
+    /// Whether the instruction is `ldc1`.
+    pub is_ldc1: T,
+    /// Whether the instruction is `sdc1`.
+    pub is_sdc1: T,
+
+    /// The reads of `fs`.
+    pub fs_access: [MemoryReadCols<T>; 2],
+    /// The reads of `ft`.
+    pub ft_access: [MemoryReadCols<T>; 2],
+    /// The destination register accesses, which are reads for `sdc1`.
+    pub fd_access: [MemoryReadWriteCols<T>; 2],
+    /// The address of the first destination register.
+    pub fd_register: T,
+    /// The memory accesses of `ldc1` and `sdc1`.
+    pub memory_access: [MemoryReadWriteCols<T>; 2],
+
+    /// The unpacked `fs`.
+    pub x: FpUnpackCols<T>,
+    /// The unpacked `ft`.
+    pub y: FpUnpackCols<T>,
+
+    /// The comparison of the magnitudes of `fs` and `ft`.
+    pub magnitude: FpCompareCols<T, 8>,
+    /// Whether either operand is a NaN.
+    pub is_unordered: T,
+    /// Whether both operands are zeros.
+    pub both_zero: T,
+    /// Whether the operands are ordered and not both zero.
+    pub is_ordered: T,
+    /// Whether `fs < ft`, assuming the operands are ordered and not both zero.
+    pub signed_lt: T,
+    /// Whether the operands have the same sign and magnitude.
+    pub same_sign_eq: T,
+    /// Whether the operands are ordered and `fs < ft`.
+    pub ordered_lt: T,
+    /// Whether the operands are ordered and `fs == ft`.
+    pub ordered_eq: T,
+
+    /// The address of `ldc1` and `sdc1`.
+    pub addr_word: Word<T>,
+    /// Range checker for `addr_word`.
+    pub addr_word_range_checker: KoalaBearWordRangeChecker<T>,
+    /// Whether the three most significant bytes of `addr_word` are zero.
+    pub most_sig_bytes_zero: IsZeroOperation<T>,
+    /// The sign of the offset of `ldc1` and `sdc1`.
+    pub offset_sign: T,
+}
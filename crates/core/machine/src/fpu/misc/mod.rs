@@ -0,0 +1,85 @@
+use columns::NUM_FPU_MISC_COLS;
+use p3_air::BaseAir;
+
+pub mod air;
+pub mod columns;
+pub mod trace;
+
+/// A chip for the FPU sign, move, compare and doubleword memory instructions.
+#[derive(Default)]
+pub struct FpuMiscChip;
+
+impl<F> BaseAir<F> for FpuMiscChip {
+    fn width(&self) -> usize {
+        NUM_FPU_MISC_COLS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{utils, utils::run_test};
+
+    use zkm_core_executor::{Instruction, Opcode, Program, Register};
+
+    use zkm_stark::CpuProver;
+
+    #[test]
+    fn test_fpu_misc_prove() {
+        utils::setup_logger();
+        let fpr = |i: u32| Register::fpr(i) as u32;
+        let fcc = |cc: u32| Register::fcc(cc) as u32;
+        let fpu = |opcode: Opcode, fs: u32, ft: u32, fd: u32| {
+            Instruction::new(opcode, 0, fpr(fs) | fpr(ft) << 8, fd, true, true)
+        };
+        let mut instructions = vec![
+            // f0 = -1.5, f1 = 2.0, (f2, f3) = -1.5, (f4, f5) = 0.5, f6 = NaN.
+            Instruction::new(Opcode::ADD, 29, 0, 0xbfc0_0000, false, true),
+            Instruction::new(Opcode::ADD, fpr(0) as u8, 29, 0, false, true),
+            Instruction::new(Opcode::ADD, 29, 0, 0x4000_0000, false, true),
+            Instruction::new(Opcode::ADD, fpr(1) as u8, 29, 0, false, true),
+            Instruction::new(Opcode::ADD, 29, 0, 0xbff8_0000, false, true),
+            Instruction::new(Opcode::ADD, fpr(3) as u8, 29, 0, false, true),
+            Instruction::new(Opcode::ADD, 29, 0, 0x3fe0_0000, false, true),
+            Instruction::new(Opcode::ADD, fpr(5) as u8, 29, 0, false, true),
+            Instruction::new(Opcode::ADD, 29, 0, 0x7fc0_0000, false, true),
+            Instruction::new(Opcode::ADD, fpr(6) as u8, 29, 0, false, true),
+            fpu(Opcode::FABS_S, 0, 0, fpr(8)),
+            fpu(Opcode::FNEG_S, 1, 0, fpr(9)),
+            fpu(Opcode::FABS_D, 2, 0, fpr(10)),
+            fpu(Opcode::FNEG_D, 4, 0, fpr(12)),
+            fpu(Opcode::FMOV_D, 2, 0, fpr(14)),
+        ];
+        for (i, opcode) in [
+            Opcode::C_UN_S,
+            Opcode::C_EQ_S,
+            Opcode::C_UEQ_S,
+            Opcode::C_OLT_S,
+            Opcode::C_ULT_S,
+            Opcode::C_OLE_S,
+            Opcode::C_ULE_S,
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            instructions.push(fpu(opcode, 0, 1, fcc(i as u32)));
+            instructions.push(fpu(opcode, 1, 0, fcc(i as u32)));
+            instructions.push(fpu(opcode, 0, 0, fcc(i as u32)));
+            instructions.push(fpu(opcode, 6, 0, fcc(i as u32)));
+        }
+        for opcode in [Opcode::C_EQ_D, Opcode::C_OLT_D, Opcode::C_ULE_D] {
+            instructions.push(fpu(opcode, 2, 4, fcc(7)));
+            instructions.push(fpu(opcode, 4, 2, fcc(7)));
+            instructions.push(fpu(opcode, 2, 2, fcc(7)));
+        }
+        instructions.extend([
+            // Store (f2, f3) below and load it back into (f16, f17).
+            Instruction::new(Opcode::ADD, 29, 0, 0x1008, false, true),
+            Instruction::new(Opcode::SDC1, 0, 29, fpr(2) << 16 | 0xfff8, false, true),
+            Instruction::new(Opcode::LDC1, 0, 29, fpr(16) << 16 | 0xfff8, false, true),
+            Instruction::new(Opcode::LDC1, 0, 29, fpr(18) << 16, false, true),
+        ]);
+        let program = Program::new(instructions, 0, 0);
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+}
@@ -0,0 +1,230 @@
+use std::borrow::BorrowMut;
+
+use hashbrown::HashMap;
+use itertools::Itertools;
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use rayon::iter::{ParallelBridge, ParallelIterator};
+use zkm_core_executor::{
+    events::{ByteLookupEvent, ByteRecord, FpuEvent},
+    fpu::{compare_condition, source_is_double},
+    ByteOpcode, ExecutionRecord, Opcode, Program, NUM_REGISTERS,
+};
+use zkm_stark::{air::MachineAir, Word};
+
+use crate::{
+    utils::{next_power_of_two, zeroed_f_vec},
+    CoreChipError,
+};
+
+use super::{
+    columns::{FpuMiscCols, NUM_FPU_MISC_COLS},
+    FpuMiscChip,
+};
+
+impl<F: PrimeField32> MachineAir<F> for FpuMiscChip {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    type Error = CoreChipError;
+
+    fn name(&self) -> String {
+        "FpuMisc".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> Result<RowMajorMatrix<F>, Self::Error> {
+        let chunk_size = std::cmp::max((input.fpu_misc_events.len()) / num_cpus::get(), 1);
+        let nb_rows = input.fpu_misc_events.len();
+        let size_log2 = input.fixed_log2_rows::<F, _>(self);
+        let padded_nb_rows = next_power_of_two(nb_rows, size_log2);
+        let mut values = zeroed_f_vec(padded_nb_rows * NUM_FPU_MISC_COLS);
+
+        let blu_events = values
+            .chunks_mut(chunk_size * NUM_FPU_MISC_COLS)
+            .enumerate()
+            .par_bridge()
+            .map(|(i, rows)| {
+                let mut blu: HashMap<ByteLookupEvent, usize> = HashMap::new();
+                rows.chunks_mut(NUM_FPU_MISC_COLS).enumerate().for_each(|(j, row)| {
+                    let idx = i * chunk_size + j;
+                    let cols: &mut FpuMiscCols<F> = row.borrow_mut();
+
+                    if idx < input.fpu_misc_events.len() {
+                        let event = &input.fpu_misc_events[idx];
+                        self.event_to_row(event, cols, &mut blu);
+                    }
+                });
+                blu
+            })
+            .collect::<Vec<_>>();
+
+        output.add_byte_lookup_events_from_maps(blu_events.iter().collect_vec());
+
+        // Convert the trace to a row major matrix.
+        Ok(RowMajorMatrix::new(values, NUM_FPU_MISC_COLS))
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        if let Some(shape) = shard.shape.as_ref() {
+            shape.included::<F, _>(self)
+        } else {
+            !shard.fpu_misc_events.is_empty()
+        }
+    }
+
+    fn local_only(&self) -> bool {
+        true
+    }
+}
+
+impl FpuMiscChip {
+    fn event_to_row<F: PrimeField32>(
+        &self,
+        event: &FpuEvent,
+        cols: &mut FpuMiscCols<F>,
+        blu: &mut impl ByteRecord,
+    ) {
+        cols.pc = F::from_canonical_u32(event.pc);
+        cols.next_pc = F::from_canonical_u32(event.next_pc);
+        cols.shard = F::from_canonical_u32(event.shard);
+        cols.clk = F::from_canonical_u32(event.clk);
+        cols.op_b_value = event.b.into();
+        cols.op_c_value = event.c.into();
+
+        cols.is_abs_s = F::from_bool(event.opcode == Opcode::FABS_S);
+        cols.is_abs_d = F::from_bool(event.opcode == Opcode::FABS_D);
+        cols.is_neg_s = F::from_bool(event.opcode == Opcode::FNEG_S);
+        cols.is_neg_d = F::from_bool(event.opcode == Opcode::FNEG_D);
+        cols.is_mov_d = F::from_bool(event.opcode == Opcode::FMOV_D);
+        cols.is_ldc1 = F::from_bool(event.opcode == Opcode::LDC1);
+        cols.is_sdc1 = F::from_bool(event.opcode == Opcode::SDC1);
+        let condition = compare_condition(event.opcode);
+        if let Some(condition) = condition {
+            let flags = if source_is_double(event.opcode) {
+                &mut cols.is_compare_d
+            } else {
+                &mut cols.is_compare_s
+            };
+            flags[condition as usize - 1] = F::ONE;
+        }
+
+        // Populate the register and memory accesses.
+        for (access, record) in cols.fs_access.iter_mut().zip(event.access.fs) {
+            if let Some(record) = record {
+                access.populate(record, blu);
+            }
+        }
+        for (access, record) in cols.ft_access.iter_mut().zip(event.access.ft) {
+            if let Some(record) = record {
+                access.populate(record, blu);
+            }
+        }
+        for (access, record) in cols.fd_access.iter_mut().zip(event.access.fd) {
+            if let Some(record) = record {
+                access.populate(record, blu);
+            }
+        }
+        for (access, record) in cols.memory_access.iter_mut().zip(event.access.memory) {
+            if let Some(record) = record {
+                access.populate(record, blu);
+            }
+        }
+
+        if matches!(event.opcode, Opcode::LDC1 | Opcode::SDC1) {
+            cols.fd_register = F::from_canonical_u32((event.c >> 16) & 0xFF);
+            self.populate_memory(cols, event, blu);
+        } else {
+            cols.fd_register = F::from_canonical_u32(event.c & 0xFF);
+            let is_double = source_is_double(event.opcode);
+            if event.opcode != Opcode::FMOV_D {
+                cols.x.populate(blu, event.fs(), is_double);
+            }
+            if condition.is_some() {
+                cols.y.populate(blu, event.ft(), is_double);
+                self.populate_compare(cols, event, is_double, blu);
+            }
+        }
+
+        // These relations are checked on every row.
+        let (x_sign, y_sign) = (cols.x.sign, cols.y.sign);
+        let lt = cols.magnitude.is_lt;
+        let differs: F = cols.magnitude.first_diff.iter().copied().sum();
+        let (gt, eq) = (differs - lt, F::from_bool(condition.is_some()) - differs);
+        cols.signed_lt = x_sign * (F::ONE - y_sign)
+            + (F::ONE - x_sign) * (F::ONE - y_sign) * lt
+            + x_sign * y_sign * gt;
+        cols.same_sign_eq = (F::ONE - x_sign - y_sign + x_sign * y_sign * F::TWO) * eq;
+        if condition.is_some() {
+            cols.ordered_lt = cols.is_ordered * cols.signed_lt;
+            cols.ordered_eq =
+                (F::ONE - cols.is_unordered) * cols.both_zero + cols.is_ordered * cols.same_sign_eq;
+        }
+    }
+
+    fn populate_compare<F: PrimeField32>(
+        &self,
+        cols: &mut FpuMiscCols<F>,
+        event: &FpuEvent,
+        is_double: bool,
+        blu: &mut impl ByteRecord,
+    ) {
+        let magnitude = |value: u64| {
+            let mask = if is_double { !(1 << 63) } else { 0x7fff_ffff };
+            (value & mask).to_le_bytes()
+        };
+        cols.magnitude.populate(blu, &magnitude(event.fs()), &magnitude(event.ft()));
+
+        let (x, y) = (&cols.x, &cols.y);
+        cols.is_unordered = x.is_nan + y.is_nan - x.is_nan * y.is_nan;
+        cols.both_zero = x.is_zero * y.is_zero;
+        cols.is_ordered = (F::ONE - cols.is_unordered) * (F::ONE - cols.both_zero);
+    }
+
+    fn populate_memory<F: PrimeField32>(
+        &self,
+        cols: &mut FpuMiscCols<F>,
+        event: &FpuEvent,
+        blu: &mut impl ByteRecord,
+    ) {
+        let offset_sign = (event.c >> 15) & 1;
+        cols.offset_sign = F::from_canonical_u32(offset_sign);
+        blu.add_byte_lookup_event(ByteLookupEvent {
+            opcode: ByteOpcode::MSB,
+            a1: offset_sign as u16,
+            a2: 0,
+            b: (event.c >> 8) as u8,
+            c: 0,
+        });
+
+        let offset = (event.c & 0xFFFF) as i16 as i32 as u32;
+        let addr = event.b.wrapping_add(offset);
+        cols.addr_word = Word::from(addr);
+        cols.addr_word_range_checker.populate(addr);
+        blu.add_u8_range_checks(&addr.to_le_bytes()[1..3]);
+        blu.add_byte_lookup_event(ByteLookupEvent {
+            opcode: ByteOpcode::AND,
+            a1: 0,
+            a2: 0,
+            b: addr as u8,
+            c: 0b111,
+        });
+
+        let addr_bytes = addr.to_le_bytes();
+        let most_sig_bytes_sum = addr_bytes[1] as u32 + addr_bytes[2] as u32 + addr_bytes[3] as u32;
+        cols.most_sig_bytes_zero.populate(most_sig_bytes_sum);
+        if most_sig_bytes_sum == 0 {
+            blu.add_byte_lookup_event(ByteLookupEvent {
+                opcode: ByteOpcode::LTU,
+                a1: 1,
+                a2: 0,
+                b: NUM_REGISTERS as u8 - 1,
+                c: addr_bytes[0],
+            });
+        }
+    }
+}
@@ -0,0 +1,8 @@
+pub mod arith;
+mod compare;
+mod identity;
+pub mod misc;
+mod unpack;
+
+pub use arith::FpuArithChip;
+pub use misc::FpuMiscChip;
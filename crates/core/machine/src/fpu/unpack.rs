@@ -0,0 +1,220 @@
+//! An operation to unpack an IEEE-754 operand into its sign, exponent and significand.
+//!
+//! A single occupies `lo` and a double occupies `lo` and `hi`, with the sign in the top byte of
+//! the high word. Both formats are unpacked by the same columns, selected by `is_double`.
+
+use p3_air::AirBuilder;
+use p3_field::{Field, FieldAlgebra, PrimeField32};
+use zkm_core_executor::{
+    events::{ByteLookupEvent, ByteRecord},
+    ByteOpcode,
+};
+use zkm_derive::AlignedBorrow;
+use zkm_stark::{air::ZKMAirBuilder, Word};
+
+use crate::operations::IsZeroOperation;
+
+/// A set of columns needed to unpack a single or double precision operand.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct FpUnpackCols<T> {
+    /// The byte holding the sign and the top bits of the exponent.
+    pub top_byte: T,
+
+    /// The bits of the byte below `top_byte`, holding the low exponent bits and the top fraction
+    /// bits.
+    pub next_bits: [T; 8],
+
+    /// The sign bit, which is the most significant bit of `top_byte`.
+    pub sign: T,
+
+    /// The biased exponent field.
+    pub exponent: T,
+
+    /// The fraction bits held in the byte below `top_byte`.
+    pub fraction_top: T,
+
+    /// The sum of the fraction bytes, which is zero if and only if the fraction is zero.
+    pub fraction_sum: T,
+
+    /// Whether the exponent field is zero.
+    pub exponent_zero: IsZeroOperation<T>,
+
+    /// Whether the exponent field is all ones.
+    pub exponent_max: IsZeroOperation<T>,
+
+    /// Whether the fraction is zero.
+    pub fraction_zero: IsZeroOperation<T>,
+
+    /// Whether the operand is a NaN.
+    pub is_nan: T,
+
+    /// Whether the operand is an infinity.
+    pub is_inf: T,
+
+    /// Whether the operand is a zero of either sign.
+    pub is_zero: T,
+
+    /// The significand including the implicit bit, as little endian bytes.
+    pub mantissa: [T; 7],
+}
+
+impl<F: PrimeField32> FpUnpackCols<F> {
+    /// Populates the columns from the raw bits of the operand and returns its significand.
+    pub fn populate(&mut self, record: &mut impl ByteRecord, value: u64, is_double: bool) -> u64 {
+        let bytes = value.to_le_bytes();
+        let (top, next) = if is_double { (bytes[7], bytes[6]) } else { (bytes[3], bytes[2]) };
+        let (exponent, fraction) = if is_double {
+            ((value >> 52) & 0x7ff, value & ((1 << 52) - 1))
+        } else {
+            ((value >> 23) & 0xff, value & ((1 << 23) - 1))
+        };
+        let max = if is_double { 0x7ff } else { 0xff };
+        let fraction_top = if is_double { next & 0xf } else { next & 0x7f };
+        let fraction_sum = bytes[0] as u32
+            + bytes[1] as u32
+            + fraction_top as u32
+            + if is_double { bytes[2..6].iter().map(|&b| b as u32).sum() } else { 0 };
+
+        self.top_byte = F::from_canonical_u8(top);
+        self.next_bits = core::array::from_fn(|i| F::from_canonical_u8((next >> i) & 1));
+        self.sign = F::from_canonical_u8(top >> 7);
+        self.exponent = F::from_canonical_u64(exponent);
+        self.fraction_top = F::from_canonical_u8(fraction_top);
+        self.fraction_sum = F::from_canonical_u32(fraction_sum);
+        self.exponent_zero.populate(exponent as u32);
+        self.exponent_max.populate_from_field_element(
+            F::from_canonical_u64(exponent) - F::from_canonical_u64(max),
+        );
+        self.fraction_zero.populate(fraction_sum);
+        self.is_nan = F::from_bool(exponent == max && fraction != 0);
+        self.is_inf = F::from_bool(exponent == max && fraction == 0);
+        self.is_zero = F::from_bool(exponent == 0 && fraction == 0);
+
+        let shift = if is_double { 52 } else { 23 };
+        let mantissa = fraction | ((exponent != 0) as u64) << shift;
+        self.mantissa = core::array::from_fn(|i| F::from_canonical_u8((mantissa >> (8 * i)) as u8));
+
+        record.add_byte_lookup_event(ByteLookupEvent {
+            opcode: ByteOpcode::MSB,
+            a1: (top >> 7) as u16,
+            a2: 0,
+            b: top,
+            c: 0,
+        });
+        mantissa
+    }
+}
+
+impl<F: Field> FpUnpackCols<F> {
+    /// Evaluates the unpacking of `lo` and `hi`, whose bytes are assumed to be range checked.
+    pub fn eval<AB: ZKMAirBuilder>(
+        builder: &mut AB,
+        lo: Word<AB::Var>,
+        hi: Word<AB::Var>,
+        is_double: AB::Expr,
+        cols: FpUnpackCols<AB::Var>,
+        is_real: AB::Expr,
+    ) {
+        let one = AB::Expr::one();
+        let is_single = one.clone() - is_double.clone();
+        let mut builder_when_real = builder.when(is_real.clone());
+
+        // Select the top two bytes of the operand.
+        builder_when_real
+            .assert_eq(cols.top_byte, is_double.clone() * hi[3] + is_single.clone() * lo[3]);
+        let next_byte =
+            cols.next_bits.iter().enumerate().fold(AB::Expr::zero(), |acc, (i, &bit)| {
+                acc + bit * AB::F::from_canonical_u32(1 << i)
+            });
+        builder_when_real
+            .assert_eq(next_byte, is_double.clone() * hi[2] + is_single.clone() * lo[2]);
+        for bit in cols.next_bits {
+            builder_when_real.assert_bool(bit);
+        }
+
+        // The exponent is the top byte without the sign, followed by the top bit of the next byte
+        // for a single or its top four bits for a double.
+        let bits = cols.next_bits;
+        let exponent_low = is_double.clone()
+            * (bits[4] + bits[5] * AB::F::TWO + bits[6] * AB::F::from_canonical_u32(4))
+            + bits[7] * (is_double.clone() * AB::F::from_canonical_u32(8) + is_single.clone());
+        builder_when_real.assert_eq(
+            cols.exponent,
+            (is_double.clone() * AB::F::from_canonical_u32(14) + AB::F::TWO)
+                * (cols.top_byte - cols.sign * AB::F::from_canonical_u32(128))
+                + exponent_low,
+        );
+        builder_when_real.assert_eq(
+            cols.fraction_top,
+            bits[0]
+                + bits[1] * AB::F::TWO
+                + bits[2] * AB::F::from_canonical_u32(4)
+                + bits[3] * AB::F::from_canonical_u32(8)
+                + is_single.clone()
+                    * (bits[4] * AB::F::from_canonical_u32(16)
+                        + bits[5] * AB::F::from_canonical_u32(32)
+                        + bits[6] * AB::F::from_canonical_u32(64)),
+        );
+        builder_when_real.assert_eq(
+            cols.fraction_sum,
+            lo[0] + lo[1] + cols.fraction_top + is_double.clone() * (lo[2] + lo[3] + hi[0] + hi[1]),
+        );
+
+        // The significand, with the implicit bit set unless the exponent field is zero.
+        let hidden = one.clone() - cols.exponent_zero.result;
+        let mantissa = [
+            lo[0].into(),
+            lo[1].into(),
+            is_double.clone() * lo[2]
+                + is_single * (cols.fraction_top + hidden.clone() * AB::F::from_canonical_u32(128)),
+            is_double.clone() * lo[3],
+            is_double.clone() * hi[0],
+            is_double.clone() * hi[1],
+            is_double.clone() * (cols.fraction_top + hidden * AB::F::from_canonical_u32(16)),
+        ];
+        for (m, expected) in cols.mantissa.into_iter().zip(mantissa) {
+            builder_when_real.assert_eq(m, expected);
+        }
+
+        // Classify the operand.
+        let exponent_max =
+            is_double * AB::F::from_canonical_u32(0x7ff - 0xff) + AB::F::from_canonical_u32(0xff);
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            cols.exponent.into(),
+            cols.exponent_zero,
+            is_real.clone(),
+        );
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            cols.exponent - exponent_max,
+            cols.exponent_max,
+            is_real.clone(),
+        );
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            cols.fraction_sum.into(),
+            cols.fraction_zero,
+            is_real.clone(),
+        );
+        let mut builder_when_real = builder.when(is_real.clone());
+        builder_when_real.assert_eq(
+            cols.is_nan,
+            cols.exponent_max.result * (one.clone() - cols.fraction_zero.result),
+        );
+        builder_when_real
+            .assert_eq(cols.is_inf, cols.exponent_max.result * cols.fraction_zero.result);
+        builder_when_real
+            .assert_eq(cols.is_zero, cols.exponent_zero.result * cols.fraction_zero.result);
+
+        // The sign is the most significant bit of the top byte, which also range checks it.
+        builder.send_byte(
+            ByteOpcode::MSB.as_field::<AB::F>(),
+            cols.sign,
+            cols.top_byte,
+            AB::Expr::zero(),
+            is_real,
+        );
+    }
+}
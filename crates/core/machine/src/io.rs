@@ -1,5 +1,8 @@
+use std::collections::BTreeMap;
+
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use zkm_core_executor::ZKMReduceProof;
+use zkm_core_executor::{linux::LinuxEnv, ZKMReduceProof};
+use zkm_primitives::codec::Codec;
 use zkm_stark::{koala_bear_poseidon2::KoalaBearPoseidon2, StarkVerifyingKey};
 
 /// Standard input for the prover.
@@ -10,17 +13,29 @@ pub struct ZKMStdin {
     pub buffer: Vec<Vec<u8>>,
     pub ptr: usize,
     pub proofs: Vec<(ZKMReduceProof<KoalaBearPoseidon2>, StarkVerifyingKey<KoalaBearPoseidon2>)>,
+    /// Named files readable by the program through the Linux `open`/`read` syscalls. The field
+    /// changed the bincode encoding of `ZKMStdin`, so inputs serialized without it don't load.
+    pub files: BTreeMap<String, Vec<u8>>,
+    /// The time and the seed of the random bytes seen by the program through the Linux syscalls.
+    /// Like `files`, it is part of the bincode encoding of `ZKMStdin`.
+    pub linux_env: LinuxEnv,
 }
 
 impl ZKMStdin {
     /// Create a new `ZKMStdin`.
     pub const fn new() -> Self {
-        Self { buffer: Vec::new(), ptr: 0, proofs: Vec::new() }
+        Self {
+            buffer: Vec::new(),
+            ptr: 0,
+            proofs: Vec::new(),
+            files: BTreeMap::new(),
+            linux_env: LinuxEnv::new(0, [0; 32]),
+        }
     }
 
     /// Create a `ZKMStdin` from a slice of bytes.
     pub fn from(data: &[u8]) -> Self {
-        Self { buffer: vec![data.to_vec()], ptr: 0, proofs: Vec::new() }
+        Self { buffer: vec![data.to_vec()], ..Self::new() }
     }
 
     /// Read a value from the buffer.
@@ -31,6 +46,13 @@ impl ZKMStdin {
         result
     }
 
+    /// Read a value encoded with `codec` from the buffer.
+    pub fn read_with<T: DeserializeOwned>(&mut self, codec: Codec) -> T {
+        let (result, _) = codec.decode(&self.buffer[self.ptr]).expect("failed to deserialize");
+        self.ptr += 1;
+        result
+    }
+
     /// Read a slice of bytes from the buffer.
     pub fn read_slice(&mut self, slice: &mut [u8]) {
         slice.copy_from_slice(&self.buffer[self.ptr]);
@@ -44,6 +66,12 @@ impl ZKMStdin {
         self.buffer.push(tmp);
     }
 
+    /// Write a value to the buffer, encoded with `codec`. The program reads it with
+    /// `zkm_zkvm::io::read_with` and the same codec.
+    pub fn write_with<T: Serialize>(&mut self, data: &T, codec: Codec) {
+        self.buffer.push(codec.encode(data).expect("serialization failed"));
+    }
+
     /// Write a slice of bytes to the buffer.
     pub fn write_slice(&mut self, slice: &[u8]) {
         self.buffer.push(slice.to_vec());
@@ -53,6 +81,29 @@ impl ZKMStdin {
         self.buffer.push(vec);
     }
 
+    /// Register a file that the program can open at `path`, e.g. with `std::fs::File::open` in
+    /// Go or `fopen` in C. Paths are relative to the root directory.
+    pub fn write_file(&mut self, path: &str, contents: Vec<u8>) {
+        self.files.insert(path.to_string(), contents);
+    }
+
+    /// Set the Unix time, in nanoseconds, reported by `clock_gettime(CLOCK_REALTIME)` when the
+    /// program starts. The clocks then advance with the executed instructions.
+    ///
+    /// Like the rest of the input, the time is not committed to the public values, see
+    /// [`LinuxEnv`].
+    pub fn set_realtime(&mut self, realtime_ns: u64) {
+        self.linux_env.realtime_ns = realtime_ns;
+    }
+
+    /// Set the seed of the bytes returned by the `getrandom` syscall.
+    ///
+    /// Like the rest of the input, the seed is not committed to the public values, so the bytes
+    /// are not random to the verifier, see [`LinuxEnv`].
+    pub fn set_random_seed(&mut self, seed: [u8; 32]) {
+        self.linux_env.random_seed = seed;
+    }
+
     pub fn write_proof(
         &mut self,
         proof: ZKMReduceProof<KoalaBearPoseidon2>,
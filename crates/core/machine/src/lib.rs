@@ -19,6 +19,7 @@ pub mod alu;
 pub mod bytes;
 pub mod control_flow;
 pub mod cpu;
+pub mod fpu;
 pub mod global;
 pub mod io;
 pub mod memory;
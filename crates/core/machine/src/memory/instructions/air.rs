@@ -92,6 +92,7 @@ where
             AB::Expr::one(),
             AB::Expr::zero(),
             AB::Expr::one(),
+            AB::Expr::zero(),
             is_real,
         );
     }
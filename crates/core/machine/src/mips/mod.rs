@@ -2,7 +2,7 @@ use crate::{
     global::GlobalChip,
     memory::{MemoryChipType, MemoryLocalChip, NUM_LOCAL_MEMORY_ENTRIES_PER_ROW},
     syscall::precompiles::{
-        fptower::{Fp2AddSubAssignChip, Fp2MulAssignChip, FpOpChip},
+        fptower::{Fp2AddSubAssignChip, Fp2MulAssignChip, Fp6MulAssignChip, FpOpChip},
         poseidon2::Poseidon2PermuteChip,
     },
 };
@@ -22,6 +22,9 @@ use zkm_stark::{
     Chip, LookupKind, StarkGenericConfig, StarkMachine,
 };
 
+mod registry;
+pub use registry::*;
+
 /// A module for importing all the different MIPS chips.
 pub(crate) mod mips_chips {
     pub use crate::{
@@ -32,6 +35,7 @@ pub(crate) mod mips_chips {
         bytes::ByteChip,
         control_flow::{BranchChip, JumpChip},
         cpu::CpuChip,
+        fpu::{FpuArithChip, FpuMiscChip},
         memory::{MemoryGlobalChip, MemoryInstructionsChip},
         misc::{MiscInstrsChip, MovCondChip},
         program::ProgramChip,
@@ -39,6 +43,8 @@ pub(crate) mod mips_chips {
             chip::SyscallChip,
             instructions::SyscallInstrsChip,
             precompiles::{
+                bigint_mulmod::BigIntMulModChip,
+                blake::{Blake2bRoundsChip, BlakeRoundsChip},
                 edwards::{EdAddAssignChip, EdDecompressChip},
                 keccak_sponge::KeccakSpongeChip,
                 sha256::{ShaCompressChip, ShaExtendChip},
@@ -47,7 +53,7 @@ pub(crate) mod mips_chips {
                 uint256::Uint256MulChip,
                 weierstrass::{
                     WeierstrassAddAssignChip, WeierstrassDecompressChip,
-                    WeierstrassDoubleAssignChip,
+                    WeierstrassDoubleAssignChip, WeierstrassMulAssignChip,
                 },
             },
         },
@@ -173,6 +179,30 @@ pub enum MipsAir<F: PrimeField32> {
     Bn254Fp2AddSub(Fp2AddSubAssignChip<Bn254BaseField>),
     /// A precompile for Linux Syscall.
     SysLinux(SysLinuxChip),
+    /// A precompile for the rounds of the BLAKE2s compression function.
+    Blake2sRounds(BlakeRoundsChip),
+    /// A precompile for the rounds of the BLAKE3 compression function.
+    Blake3Rounds(BlakeRoundsChip),
+    /// A precompile for the rounds of the BLAKE2b compression function.
+    Blake2bRounds(Blake2bRoundsChip),
+    /// A precompile for BLS12-381 fp6 multiplication.
+    Bls12381Fp6Mul(Fp6MulAssignChip<Bls12381BaseField>),
+    /// A precompile for BN-254 fp6 multiplication.
+    Bn254Fp6Mul(Fp6MulAssignChip<Bn254BaseField>),
+    /// A precompile for scalar multiplication on the Secp256k1 curve.
+    Secp256k1Mul(WeierstrassMulAssignChip<SwCurve<Secp256k1Parameters>>),
+    /// A precompile for scalar multiplication on the Secp256r1 curve.
+    Secp256r1Mul(WeierstrassMulAssignChip<SwCurve<Secp256r1Parameters>>),
+    /// A precompile for scalar multiplication on the BN254 curve.
+    Bn254Mul(WeierstrassMulAssignChip<SwCurve<Bn254Parameters>>),
+    /// A precompile for scalar multiplication on the BLS12-381 curve.
+    Bls12381Mul(WeierstrassMulAssignChip<SwCurve<Bls12381Parameters>>),
+    /// A precompile for variable-width modular multiplication.
+    BigIntMulMod(BigIntMulModChip),
+    /// An AIR for MIPS FPU arithmetic and conversion instructions.
+    FpuArith(FpuArithChip),
+    /// An AIR for MIPS FPU sign, move, compare and doubleword memory instructions.
+    FpuMisc(FpuMiscChip),
 }
 
 impl<F: PrimeField32> MipsAir<F> {
@@ -436,6 +466,83 @@ impl<F: PrimeField32> MipsAir<F> {
         costs.insert(movcond_instrs.name(), movcond_instrs.cost());
         chips.push(movcond_instrs);
 
+        let blake2s_rounds = Chip::new(MipsAir::Blake2sRounds(BlakeRoundsChip::blake2s()));
+        costs.insert(blake2s_rounds.name(), 10 * blake2s_rounds.cost());
+        chips.push(blake2s_rounds);
+
+        let blake3_rounds = Chip::new(MipsAir::Blake3Rounds(BlakeRoundsChip::blake3()));
+        costs.insert(blake3_rounds.name(), 7 * blake3_rounds.cost());
+        chips.push(blake3_rounds);
+
+        let blake2b_rounds = Chip::new(MipsAir::Blake2bRounds(Blake2bRoundsChip::new()));
+        costs.insert(blake2b_rounds.name(), 12 * blake2b_rounds.cost());
+        chips.push(blake2b_rounds);
+
+        let bls12381_fp6_mul =
+            Chip::new(MipsAir::Bls12381Fp6Mul(Fp6MulAssignChip::<Bls12381BaseField>::new()));
+        costs.insert(bls12381_fp6_mul.name(), bls12381_fp6_mul.cost());
+        chips.push(bls12381_fp6_mul);
+
+        let bn254_fp6_mul =
+            Chip::new(MipsAir::Bn254Fp6Mul(Fp6MulAssignChip::<Bn254BaseField>::new()));
+        costs.insert(bn254_fp6_mul.name(), bn254_fp6_mul.cost());
+        chips.push(bn254_fp6_mul);
+
+        let secp256k1_mul = Chip::new(MipsAir::Secp256k1Mul(WeierstrassMulAssignChip::<
+            SwCurve<Secp256k1Parameters>,
+        >::new()));
+        costs.insert(
+            secp256k1_mul.name(),
+            WeierstrassMulAssignChip::<SwCurve<Secp256k1Parameters>>::rows_per_event() as u64
+                * secp256k1_mul.cost(),
+        );
+        chips.push(secp256k1_mul);
+
+        let secp256r1_mul = Chip::new(MipsAir::Secp256r1Mul(WeierstrassMulAssignChip::<
+            SwCurve<Secp256r1Parameters>,
+        >::new()));
+        costs.insert(
+            secp256r1_mul.name(),
+            WeierstrassMulAssignChip::<SwCurve<Secp256r1Parameters>>::rows_per_event() as u64
+                * secp256r1_mul.cost(),
+        );
+        chips.push(secp256r1_mul);
+
+        let bn254_mul = Chip::new(MipsAir::Bn254Mul(WeierstrassMulAssignChip::<
+            SwCurve<Bn254Parameters>,
+        >::new()));
+        costs.insert(
+            bn254_mul.name(),
+            WeierstrassMulAssignChip::<SwCurve<Bn254Parameters>>::rows_per_event() as u64
+                * bn254_mul.cost(),
+        );
+        chips.push(bn254_mul);
+
+        let bls12381_mul = Chip::new(MipsAir::Bls12381Mul(WeierstrassMulAssignChip::<
+            SwCurve<Bls12381Parameters>,
+        >::new()));
+        costs.insert(
+            bls12381_mul.name(),
+            WeierstrassMulAssignChip::<SwCurve<Bls12381Parameters>>::rows_per_event() as u64
+                * bls12381_mul.cost(),
+        );
+        chips.push(bls12381_mul);
+
+        let bigint_mulmod = Chip::new(MipsAir::BigIntMulMod(BigIntMulModChip::new()));
+        costs.insert(
+            bigint_mulmod.name(),
+            BigIntMulModChip::rows_per_event() as u64 * bigint_mulmod.cost(),
+        );
+        chips.push(bigint_mulmod);
+
+        let fpu_arith = Chip::new(MipsAir::FpuArith(FpuArithChip::default()));
+        costs.insert(fpu_arith.name(), fpu_arith.cost());
+        chips.push(fpu_arith);
+
+        let fpu_misc = Chip::new(MipsAir::FpuMisc(FpuMiscChip::default()));
+        costs.insert(fpu_misc.name(), fpu_misc.cost());
+        chips.push(fpu_misc);
+
         (chips, costs)
     }
 
@@ -475,6 +582,8 @@ impl<F: PrimeField32> MipsAir<F> {
                 2 * record.get_local_mem_events().count() + record.syscall_events.len(),
             ),
             (MipsAirId::SyscallCore, record.syscall_events.len()),
+            (MipsAirId::FpuArith, record.fpu_arith_events.len()),
+            (MipsAirId::FpuMisc, record.fpu_misc_events.len()),
         ]
     }
 
@@ -489,6 +598,13 @@ impl<F: PrimeField32> MipsAir<F> {
             .map(|events| {
                 let events_len = match self {
                     Self::KeccakSponge(_) => self.keccak_permutation_in_record(record),
+                    Self::SysLinux(_) => events
+                        .iter()
+                        .map(|(_, event)| match event {
+                            PrecompileEvent::Linux(event) => event.num_rows(),
+                            _ => unreachable!(),
+                        })
+                        .sum(),
                     _ => events.len(),
                 };
                 let num_rows = events_len * self.rows_per_event();
@@ -532,6 +648,8 @@ impl<F: PrimeField32> MipsAir<F> {
             MipsAir::MemoryLocal(MemoryLocalChip::new()),
             MipsAir::Global(GlobalChip),
             MipsAir::SyscallCore(SyscallChip::core()),
+            MipsAir::FpuArith(FpuArithChip::default()),
+            MipsAir::FpuMisc(FpuMiscChip::default()),
         ]
     }
 
@@ -582,6 +700,22 @@ impl<F: PrimeField32> MipsAir<F> {
             Self::Sha256Compress(_) => 80,
             Self::Sha256Extend(_) => 48,
             Self::KeccakSponge(_) => 24,
+            Self::Blake2sRounds(_) => 10,
+            Self::Blake3Rounds(_) => 7,
+            Self::Blake2bRounds(_) => 12,
+            Self::Secp256k1Mul(_) => {
+                WeierstrassMulAssignChip::<SwCurve<Secp256k1Parameters>>::rows_per_event()
+            }
+            Self::Secp256r1Mul(_) => {
+                WeierstrassMulAssignChip::<SwCurve<Secp256r1Parameters>>::rows_per_event()
+            }
+            Self::Bn254Mul(_) => {
+                WeierstrassMulAssignChip::<SwCurve<Bn254Parameters>>::rows_per_event()
+            }
+            Self::Bls12381Mul(_) => {
+                WeierstrassMulAssignChip::<SwCurve<Bls12381Parameters>>::rows_per_event()
+            }
+            Self::BigIntMulMod(_) => BigIntMulModChip::rows_per_event(),
             _ => 1,
         }
     }
@@ -633,6 +767,16 @@ impl<F: PrimeField32> MipsAir<F> {
             Self::Poseidon2Permute(_) => SyscallCode::POSEIDON2_PERMUTE,
             Self::KeccakSponge(_) => SyscallCode::KECCAK_SPONGE,
             Self::SysLinux(_) => SyscallCode::SYS_LINUX,
+            Self::Blake2sRounds(_) => SyscallCode::BLAKE2S_ROUNDS,
+            Self::Blake3Rounds(_) => SyscallCode::BLAKE3_ROUNDS,
+            Self::Blake2bRounds(_) => SyscallCode::BLAKE2B_ROUNDS,
+            Self::Bls12381Fp6Mul(_) => SyscallCode::BLS12381_FP6_MUL,
+            Self::Bn254Fp6Mul(_) => SyscallCode::BN254_FP6_MUL,
+            Self::Secp256k1Mul(_) => SyscallCode::SECP256K1_MUL,
+            Self::Secp256r1Mul(_) => SyscallCode::SECP256R1_MUL,
+            Self::Bn254Mul(_) => SyscallCode::BN254_MUL,
+            Self::Bls12381Mul(_) => SyscallCode::BLS12381_MUL,
+            Self::BigIntMulMod(_) => SyscallCode::BIGINT_MULMOD,
             Self::Add(_) => unreachable!("Invalid for core chip"),
             Self::Bitwise(_) => unreachable!("Invalid for core chip"),
             Self::DivRem(_) => unreachable!("Invalid for core chip"),
@@ -657,6 +801,8 @@ impl<F: PrimeField32> MipsAir<F> {
             Self::MemoryInstrs(_) => unreachable!("Invalid for core chip"),
             Self::MiscInstrs(_) => unreachable!("Invalid for core chip"),
             Self::MovCond(_) => unreachable!("Invalid for core chip"),
+            Self::FpuArith(_) => unreachable!("Invalid for core chip"),
+            Self::FpuMisc(_) => unreachable!("Invalid for core chip"),
         }
     }
 }
@@ -704,8 +850,8 @@ pub mod tests {
     use zkm_core_executor::{Instruction, MipsAirId, Opcode, Program};
     use zkm_stark::air::MachineAir;
     use zkm_stark::{
-        koala_bear_poseidon2::KoalaBearPoseidon2, CpuProver, StarkProvingKey, StarkVerifyingKey,
-        ZKMCoreOpts,
+        koala_bear_poseidon2::KoalaBearPoseidon2, AirReport, CpuProver, StarkProvingKey,
+        StarkVerifyingKey, ZKMCoreOpts,
     };
 
     #[test]
@@ -716,6 +862,14 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_air_report() {
+        let report = AirReport::new(&MipsAir::<KoalaBear>::chips());
+        assert!(report.is_balanced(), "unbalanced lookups:\n{report}");
+        let violations = report.degree_violations(3);
+        assert!(violations.is_empty(), "degree above 3:\n{report}");
+    }
+
     #[test]
     fn core_air_cost_consistency() {
         let file = std::fs::File::open("../executor/src/artifacts/mips_costs.json").unwrap();
@@ -812,6 +966,152 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_branch_likely_prove() {
+        utils::setup_logger();
+        let branch_ops = [Opcode::BLTZL, Opcode::BGEZL, Opcode::BLEZL, Opcode::BGTZL];
+        let operands = [0, 1, 0xFFFF_FFFF];
+        for branch_op in branch_ops.iter() {
+            for operand in operands.iter() {
+                let instructions = vec![
+                    Instruction::new(Opcode::ADD, 29, 0, *operand, false, true),
+                    Instruction::new(*branch_op, 29, 0, 100, true, true),
+                    Instruction::new(Opcode::ADD, 30, 0, 1, false, true),
+                    Instruction::new(Opcode::ADD, 31, 0, 2, false, true),
+                ];
+                let program = Program::new(instructions, 0, 0);
+                run_test::<CpuProver<_, _>>(program).unwrap();
+            }
+        }
+        for (branch_op, operand) in [(Opcode::BEQL, 1), (Opcode::BEQL, 2), (Opcode::BNEL, 1)] {
+            let instructions = vec![
+                Instruction::new(Opcode::ADD, 29, 0, 1, false, true),
+                Instruction::new(Opcode::ADD, 30, 0, operand, false, true),
+                Instruction::new(branch_op, 29, 30, 100, false, true),
+                Instruction::new(Opcode::ADD, 31, 0, 2, false, true),
+                Instruction::new(Opcode::ADD, 28, 0, 3, false, true),
+            ];
+            let program = Program::new(instructions, 0, 0);
+            run_test::<CpuProver<_, _>>(program).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_branch_link_prove() {
+        utils::setup_logger();
+        let branch_ops = [Opcode::BLTZAL, Opcode::BGEZAL, Opcode::BLTZALL, Opcode::BGEZALL];
+        let operands = [0, 1, 0xFFFF_FFFF];
+        for branch_op in branch_ops.iter() {
+            for operand in operands.iter() {
+                let instructions = vec![
+                    Instruction::new(Opcode::ADD, 29, 0, *operand, false, true),
+                    Instruction::new(*branch_op, 31, 29, 100, false, true),
+                    Instruction::new(Opcode::ADD, 30, 0, 1, false, true),
+                    Instruction::new(Opcode::ADD, 28, 0, 2, false, true),
+                ];
+                let program = Program::new(instructions, 0, 0);
+                run_test::<CpuProver<_, _>>(program).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_compact_branch_prove() {
+        utils::setup_logger();
+        let branch_ops =
+            [Opcode::BEQC, Opcode::BNEC, Opcode::BLTC, Opcode::BGEC, Opcode::BLTUC, Opcode::BGEUC];
+        let operands = [(1, 1), (1, 2), (0xFFFF_FFFF, 1)];
+        for branch_op in branch_ops.iter() {
+            for (lhs, rhs) in operands.iter() {
+                let instructions = vec![
+                    Instruction::new(Opcode::ADD, 29, 0, *lhs, false, true),
+                    Instruction::new(Opcode::ADD, 30, 0, *rhs, false, true),
+                    Instruction::new(*branch_op, 29, 30, 16, false, true),
+                    Instruction::new(Opcode::ADD, 31, 0, 2, false, true),
+                    Instruction::new(Opcode::ADD, 28, 0, 3, false, true),
+                ];
+                let program = Program::new(instructions, 0, 0);
+                run_test::<CpuProver<_, _>>(program).unwrap();
+            }
+        }
+
+        // BOVC and BNVC, across positive and negative overflows.
+        let operands = [
+            (1, 2),
+            (0x7FFF_FFFF, 1),
+            (0x7FFF_FFFF, 0x7FFF_FFFF),
+            (0x8000_0000, 0xFFFF_FFFF),
+            (0x8000_0000, 0x8000_0000),
+            (0xFFFF_FFFF, 1),
+        ];
+        for branch_op in [Opcode::BOVC, Opcode::BNVC] {
+            for (lhs, rhs) in operands.iter() {
+                let instructions = vec![
+                    Instruction::new(Opcode::ADD, 29, 0, *lhs, false, true),
+                    Instruction::new(Opcode::ADD, 30, 0, *rhs, false, true),
+                    Instruction::new(branch_op, 29, 30, 16, false, true),
+                    Instruction::new(Opcode::ADD, 31, 0, 2, false, true),
+                    Instruction::new(Opcode::ADD, 28, 0, 3, false, true),
+                ];
+                let program = Program::new(instructions, 0, 0);
+                run_test::<CpuProver<_, _>>(program).unwrap();
+            }
+        }
+
+        let branch_ops = [
+            Opcode::BLEZALC,
+            Opcode::BGEZALC,
+            Opcode::BGTZALC,
+            Opcode::BLTZALC,
+            Opcode::BEQZALC,
+            Opcode::BNEZALC,
+        ];
+        let operands = [0, 1, 0xFFFF_FFFF];
+        for branch_op in branch_ops.iter() {
+            for operand in operands.iter() {
+                let instructions = vec![
+                    Instruction::new(Opcode::ADD, 29, 0, *operand, false, true),
+                    Instruction::new(*branch_op, 31, 29, 12, false, true),
+                    Instruction::new(Opcode::ADD, 30, 0, 1, false, true),
+                    Instruction::new(Opcode::ADD, 28, 0, 2, false, true),
+                ];
+                let program = Program::new(instructions, 0, 0);
+                run_test::<CpuProver<_, _>>(program).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_jump_compact_prove() {
+        utils::setup_logger();
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 29, 0, 8, false, true),
+            Instruction::new(Opcode::JumpCompact, 31, 29, 4, false, true),
+            Instruction::new(Opcode::ADD, 30, 0, 1, false, true),
+            Instruction::new(Opcode::ADD, 28, 0, 2, false, true),
+            Instruction::new(Opcode::JumpCompact, 0, 29, 100, false, true),
+        ];
+        let program = Program::new(instructions, 0, 0);
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    fn test_mul_high_prove() {
+        utils::setup_logger();
+        let operands = [(1, 1), (0xFFFF_FFFF, 1), (0xFFFF_FFFF, 0xFFFF_FFFF), (0x8000_0000, 3)];
+        for mul_op in [Opcode::MUH, Opcode::MUHU] {
+            for (lhs, rhs) in operands.iter() {
+                let instructions = vec![
+                    Instruction::new(Opcode::ADD, 29, 0, *lhs, false, true),
+                    Instruction::new(Opcode::ADD, 30, 0, *rhs, false, true),
+                    Instruction::new(mul_op, 31, 29, 30, false, false),
+                ];
+                let program = Program::new(instructions, 0, 0);
+                run_test::<CpuProver<_, _>>(program).unwrap();
+            }
+        }
+    }
+
     #[test]
     fn test_shift_prove() {
         utils::setup_logger();
@@ -0,0 +1,586 @@
+//! A registry of precompiles defined outside of Ziren.
+//!
+//! A custom precompile is a syscall executed by the [`zkm_core_executor::Executor`] under one of
+//! the codes reserved by [`SyscallCode::custom`], and a chip proving its events. The chips of the
+//! MIPS machine are not aware of custom chips, so a crate adding precompiles defines its own AIR
+//! with a variant for [`MipsAir`] and one for each of its chips:
+//!
+//! ```ignore
+//! #[derive(zkm_derive::MachineAir)]
+//! #[builder_path = "zkm_core_machine::air::ZKMCoreAirBuilder<F = F>"]
+//! #[error_path = "zkm_core_machine::CoreChipError"]
+//! pub enum ExtendedAir<F: PrimeField32> {
+//!     Mips(MipsAir<F>),
+//!     MyHash(MyHashChip),
+//! }
+//! ```
+//!
+//! and registers its precompiles to build the machine and the execution context. A custom chip
+//! reads its events with `record.precompile_events.get_events(code)`, as
+//! [`PrecompileEvent::Custom`](zkm_core_executor::events::PrecompileEvent::Custom), and receives
+//! the syscall lookup sent by the precompile syscall table for each of them. In a precompile shard
+//! with a fixed shape, its log height is given by `record.custom_shape`, and
+//! [`ExecutionRecord::custom_chip_included`] tells whether the chip is part of the shard.
+//!
+//! The AIR implements [`CoreMachineAir`] to be the core AIR of the `ZKMProver`.
+
+use std::sync::Arc;
+
+use hashbrown::HashMap;
+use p3_air::Air;
+use p3_field::PrimeField32;
+use p3_uni_stark::SymbolicAirBuilder;
+use zkm_core_executor::{
+    syscalls::{Syscall, SyscallCode},
+    ExecutionRecord, Program, ZKMContext, ZKMContextBuilder,
+};
+use zkm_stark::{
+    air::{LookupScope, MachineAir, ZKM_PROOF_NUM_PV_ELTS},
+    Chip, LookupBuilder, LookupKind, SplitOpts, StarkGenericConfig, StarkMachine,
+};
+
+use super::MipsAir;
+use crate::shape::{precompile_shapes, CoreShapeConfig};
+
+/// The AIR of the core shards: the MIPS chips, and the chips of its custom precompiles.
+pub trait CoreMachineAir<F: PrimeField32>:
+    MachineAir<F, Record = ExecutionRecord, Program = Program>
+    + From<MipsAir<F>>
+    + Air<LookupBuilder<F>>
+    + Air<SymbolicAirBuilder<F>>
+{
+    /// The custom precompiles of the machine.
+    fn precompiles() -> PrecompileRegistry<Self>;
+}
+
+impl<F: PrimeField32> CoreMachineAir<F> for MipsAir<F> {
+    fn precompiles() -> PrecompileRegistry<Self> {
+        PrecompileRegistry::new()
+    }
+}
+
+/// A precompile defined outside of Ziren.
+pub struct CustomPrecompile<A> {
+    /// The syscall code of the precompile, in the custom range.
+    pub code: SyscallCode,
+    /// The implementation of the syscall in the executor.
+    pub syscall: Arc<dyn Syscall>,
+    /// The constructor of the chip proving the events of the syscall.
+    pub air: fn() -> A,
+    /// The number of rows of the chip for each event.
+    pub rows_per_event: usize,
+    /// The log heights allowed for the chip in a precompile shard.
+    pub log_heights: Vec<usize>,
+}
+
+/// The precompiles defined outside of Ziren, in the order of registration.
+pub struct PrecompileRegistry<A> {
+    precompiles: Vec<CustomPrecompile<A>>,
+}
+
+impl<A> Default for PrecompileRegistry<A> {
+    fn default() -> Self {
+        Self { precompiles: Vec::new() }
+    }
+}
+
+impl<A> PrecompileRegistry<A> {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a precompile.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the code is not a custom syscall code, if it is already registered, or if the
+    /// syscall does not take the extra cycles encoded in the code.
+    pub fn register(&mut self, precompile: CustomPrecompile<A>) -> &mut Self {
+        let code = precompile.code;
+        assert!(code.is_custom(), "{code} is not a custom syscall code");
+        assert_eq!(
+            precompile.syscall.num_extra_cycles(),
+            code.num_cycles(),
+            "custom syscall {code} must use {} extra cycles",
+            code.num_cycles()
+        );
+        assert!(
+            self.precompiles.iter().all(|p| p.code != code),
+            "custom syscall {code} is registered twice"
+        );
+        assert!(precompile.rows_per_event > 0, "custom syscall {code} has no rows per event");
+        self.precompiles.push(precompile);
+        self
+    }
+
+    /// The registered precompiles.
+    pub fn precompiles(&self) -> &[CustomPrecompile<A>] {
+        &self.precompiles
+    }
+
+    /// Registers the syscalls of the precompiles in an execution context.
+    pub fn register_syscalls(&self, context: &mut ZKMContextBuilder<'_>) {
+        for precompile in &self.precompiles {
+            context.custom_syscall(precompile.code, precompile.syscall.clone());
+        }
+    }
+
+    /// Adds the syscalls of the precompiles to a built execution context, keeping the custom
+    /// syscalls the context already has.
+    pub fn add_missing_syscalls(&self, context: &mut ZKMContext<'_>) {
+        for precompile in &self.precompiles {
+            context
+                .custom_syscalls
+                .entry(precompile.code)
+                .or_insert_with(|| precompile.syscall.clone());
+        }
+    }
+
+    /// The syscalls of the precompiles, by code.
+    pub fn syscalls(&self) -> HashMap<SyscallCode, Arc<dyn Syscall>> {
+        self.precompiles.iter().map(|p| (p.code, p.syscall.clone())).collect()
+    }
+
+    /// The split options with the thresholds of the precompiles, so that the events of a
+    /// precompile shard fit in the largest allowed height of its chip.
+    pub fn split_opts(&self, opts: SplitOpts) -> SplitOpts {
+        self.precompiles.iter().fold(opts, |opts, precompile| {
+            let index = precompile.code.custom_index().expect("registered codes are custom");
+            opts.with_custom_rows_per_event(index, precompile.rows_per_event)
+        })
+    }
+}
+
+impl<A> PrecompileRegistry<A> {
+    /// The chips of the MIPS machine followed by the chips of the precompiles.
+    ///
+    /// The order only depends on the order of registration, so that the verifying keys of the
+    /// machine are reproducible.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a chip of a precompile has the name of a chip of the MIPS machine or of another
+    /// precompile.
+    pub fn chips<F>(&self) -> Vec<Chip<F, A>>
+    where
+        F: PrimeField32,
+        A: From<MipsAir<F>> + MachineAir<F> + Air<LookupBuilder<F>> + Air<SymbolicAirBuilder<F>>,
+    {
+        let mut chips = MipsAir::<F>::chips()
+            .into_iter()
+            .map(|chip| Chip {
+                air: A::from(chip.air),
+                sends: chip.sends,
+                receives: chip.receives,
+                log_quotient_degree: chip.log_quotient_degree,
+            })
+            .collect::<Vec<_>>();
+
+        for precompile in &self.precompiles {
+            let chip = Chip::new((precompile.air)());
+            assert!(
+                chips.iter().all(|c| c.name() != chip.name()),
+                "the chip {} of custom syscall {} is named as another chip",
+                chip.name(),
+                precompile.code
+            );
+            chips.push(chip);
+        }
+        chips
+    }
+
+    /// The machine with the chips of the MIPS machine and of the precompiles.
+    pub fn machine<F, SC>(&self, config: SC) -> StarkMachine<SC, A>
+    where
+        F: PrimeField32,
+        SC: StarkGenericConfig<Val = F>,
+        A: From<MipsAir<F>> + MachineAir<F> + Air<LookupBuilder<F>> + Air<SymbolicAirBuilder<F>>,
+    {
+        StarkMachine::new(config, self.chips(), ZKM_PROOF_NUM_PV_ELTS)
+    }
+
+    /// The precompile-only parts of the shapes of the shards of the precompiles, for each allowed
+    /// log height.
+    ///
+    /// Together with the preprocessed shapes, they determine the recursion programs to add to the
+    /// verifying key map.
+    pub fn precompile_shapes<F>(&self) -> Vec<[(String, usize); 4]>
+    where
+        F: PrimeField32,
+        A: MachineAir<F> + Air<LookupBuilder<F>> + Air<SymbolicAirBuilder<F>>,
+    {
+        self.precompiles
+            .iter()
+            .flat_map(|precompile| {
+                let chip = Chip::new((precompile.air)());
+                let memory_events_per_row = memory_events_per_row(&chip);
+                precompile.log_heights.iter().flat_map(move |log_height| {
+                    precompile_shapes(
+                        chip.name(),
+                        precompile.rows_per_event,
+                        memory_events_per_row,
+                        *log_height,
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Allows the shapes of the shards of the precompiles in a core shape configuration.
+    pub fn extend_shape_config<F>(&self, config: &mut CoreShapeConfig<F>)
+    where
+        F: PrimeField32,
+        A: MachineAir<F> + Air<LookupBuilder<F>> + Air<SymbolicAirBuilder<F>>,
+    {
+        for precompile in &self.precompiles {
+            let chip = Chip::new((precompile.air)());
+            config.add_custom_precompile(
+                precompile.code,
+                chip.name(),
+                precompile.rows_per_event,
+                memory_events_per_row(&chip),
+                precompile.log_heights.clone(),
+            );
+        }
+    }
+}
+
+/// The number of local memory events of a row of a chip.
+fn memory_events_per_row<F: PrimeField32, A: MachineAir<F>>(chip: &Chip<F, A>) -> usize {
+    chip.sends()
+        .iter()
+        .chain(chip.receives())
+        .filter(|lookup| lookup.kind == LookupKind::Memory && lookup.scope == LookupScope::Local)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        borrow::{Borrow, BorrowMut},
+        mem::size_of,
+        sync::Arc,
+    };
+
+    use p3_air::{Air, AirBuilder, BaseAir};
+    use p3_field::{FieldAlgebra, PrimeField32};
+    use p3_koala_bear::KoalaBear;
+    use p3_matrix::{dense::RowMajorMatrix, Matrix};
+    use zkm_core_executor::{
+        events::{CustomPrecompileEvent, PrecompileEvent},
+        syscalls::{Syscall, SyscallCode, SyscallContext},
+        ExecutionError, ExecutionRecord, Instruction, Opcode, Program, ZKMContext,
+    };
+    use zkm_derive::AlignedBorrow;
+    use zkm_stark::{
+        air::{LookupScope, MachineAir, ZKMAirBuilder},
+        koala_bear_poseidon2::KoalaBearPoseidon2,
+        CpuProver, MachineProver, StarkGenericConfig, ZKMCoreOpts,
+    };
+
+    use super::{CustomPrecompile, PrecompileRegistry};
+    use crate::{
+        air::MemoryAirBuilder,
+        io::ZKMStdin,
+        memory::{MemoryCols, MemoryWriteCols},
+        mips::{ByteChip, MipsAir},
+        shape::CoreShapeConfig,
+        utils::{pad_rows_fixed, prove_with_context, setup_logger},
+        CoreChipError,
+    };
+
+    struct NoOpSyscall;
+
+    impl Syscall for NoOpSyscall {
+        fn execute(
+            &self,
+            _: &mut SyscallContext,
+            _: SyscallCode,
+            _: u32,
+            _: u32,
+        ) -> Result<Option<u32>, ExecutionError> {
+            Ok(None)
+        }
+
+        fn num_extra_cycles(&self) -> u32 {
+            1
+        }
+    }
+
+    fn byte_precompile(code: SyscallCode) -> CustomPrecompile<MipsAir<KoalaBear>> {
+        CustomPrecompile {
+            code,
+            syscall: Arc::new(NoOpSyscall),
+            air: || MipsAir::ByteLookup(ByteChip::default()),
+            rows_per_event: 1,
+            log_heights: vec![10],
+        }
+    }
+
+    /// The code of the byte swap precompile.
+    const BYTE_SWAP: SyscallCode = SyscallCode::CUSTOM_0;
+
+    /// Reverses the bytes of the word at `arg1`.
+    struct ByteSwapSyscall;
+
+    impl Syscall for ByteSwapSyscall {
+        fn execute(
+            &self,
+            ctx: &mut SyscallContext,
+            syscall_code: SyscallCode,
+            arg1: u32,
+            arg2: u32,
+        ) -> Result<Option<u32>, ExecutionError> {
+            let clk = ctx.clk;
+            let value = ctx.word_unsafe(arg1);
+            let record = ctx.mw(arg1, value.swap_bytes());
+            let event = PrecompileEvent::Custom(CustomPrecompileEvent {
+                shard: ctx.current_shard(),
+                clk,
+                arg1,
+                arg2,
+                write_records: vec![record],
+                local_mem_access: ctx.postprocess(),
+                ..Default::default()
+            });
+            let syscall_event = ctx.syscall_event(clk, syscall_code, arg1, arg2);
+            ctx.add_precompile_event(syscall_code, syscall_event, event);
+            Ok(None)
+        }
+
+        fn num_extra_cycles(&self) -> u32 {
+            1
+        }
+    }
+
+    #[derive(AlignedBorrow)]
+    #[repr(C)]
+    struct ByteSwapCols<T: Copy> {
+        shard: T,
+        clk: T,
+        addr: T,
+        word: MemoryWriteCols<T>,
+        is_real: T,
+    }
+
+    const NUM_BYTE_SWAP_COLS: usize = size_of::<ByteSwapCols<u8>>();
+
+    struct ByteSwapChip;
+
+    impl<F> BaseAir<F> for ByteSwapChip {
+        fn width(&self) -> usize {
+            NUM_BYTE_SWAP_COLS
+        }
+    }
+
+    impl<F: PrimeField32> MachineAir<F> for ByteSwapChip {
+        type Record = ExecutionRecord;
+        type Program = Program;
+        type Error = CoreChipError;
+
+        fn name(&self) -> String {
+            "ByteSwap".to_string()
+        }
+
+        fn generate_trace(
+            &self,
+            input: &ExecutionRecord,
+            output: &mut ExecutionRecord,
+        ) -> Result<RowMajorMatrix<F>, Self::Error> {
+            let mut rows = Vec::new();
+            for (_, event) in input.get_precompile_events(BYTE_SWAP) {
+                let PrecompileEvent::Custom(event) = event else { unreachable!() };
+                let mut row = [F::ZERO; NUM_BYTE_SWAP_COLS];
+                let cols: &mut ByteSwapCols<F> = row.as_mut_slice().borrow_mut();
+                cols.shard = F::from_canonical_u32(event.shard);
+                cols.clk = F::from_canonical_u32(event.clk);
+                cols.addr = F::from_canonical_u32(event.arg1);
+                cols.word.populate(event.write_records[0], output);
+                cols.is_real = F::ONE;
+                rows.push(row);
+            }
+            pad_rows_fixed(
+                &mut rows,
+                || [F::ZERO; NUM_BYTE_SWAP_COLS],
+                input.fixed_log2_rows::<F, _>(self),
+            );
+            Ok(RowMajorMatrix::new(rows.into_iter().flatten().collect(), NUM_BYTE_SWAP_COLS))
+        }
+
+        fn included(&self, shard: &ExecutionRecord) -> bool {
+            shard.custom_chip_included(&MachineAir::<F>::name(self), BYTE_SWAP)
+        }
+    }
+
+    impl<AB: ZKMAirBuilder> Air<AB> for ByteSwapChip {
+        fn eval(&self, builder: &mut AB) {
+            let main = builder.main();
+            let local = main.row_slice(0);
+            let local: &ByteSwapCols<AB::Var> = (*local).borrow();
+
+            builder.assert_bool(local.is_real);
+            for i in 0..4 {
+                builder
+                    .when(local.is_real)
+                    .assert_eq(local.word.value()[i], local.word.prev_value()[3 - i]);
+            }
+            builder.eval_memory_access(
+                local.shard,
+                local.clk,
+                local.addr,
+                &local.word,
+                local.is_real,
+            );
+            builder.receive_syscall(
+                local.shard,
+                local.clk,
+                AB::F::from_canonical_u32(BYTE_SWAP.syscall_id()),
+                local.addr,
+                AB::Expr::zero(),
+                local.is_real,
+                LookupScope::Local,
+            );
+        }
+    }
+
+    #[derive(zkm_derive::MachineAir)]
+    enum ByteSwapAir<F: PrimeField32> {
+        Mips(MipsAir<F>),
+        ByteSwap(ByteSwapChip),
+    }
+
+    impl<F: PrimeField32> From<MipsAir<F>> for ByteSwapAir<F> {
+        fn from(air: MipsAir<F>) -> Self {
+            Self::Mips(air)
+        }
+    }
+
+    fn byte_swap_registry() -> PrecompileRegistry<ByteSwapAir<KoalaBear>> {
+        let mut registry = PrecompileRegistry::new();
+        registry.register(CustomPrecompile {
+            code: BYTE_SWAP,
+            syscall: Arc::new(ByteSwapSyscall),
+            air: || ByteSwapAir::ByteSwap(ByteSwapChip),
+            rows_per_event: 1,
+            log_heights: vec![4, 8],
+        });
+        registry
+    }
+
+    fn byte_swap_program() -> Program {
+        let addr = 100;
+        let mut instructions = vec![
+            Instruction::new(Opcode::ADD, 29, 0, 0x1234_5678, false, true),
+            Instruction::new(Opcode::ADD, 30, 0, addr, false, true),
+            Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+        ];
+        for _ in 0..3 {
+            instructions.extend([
+                Instruction::new(Opcode::ADD, 2, 0, BYTE_SWAP as u32, false, true),
+                Instruction::new(Opcode::ADD, 4, 0, addr, false, true),
+                Instruction::new(Opcode::ADD, 5, 0, 0, false, true),
+                Instruction::new(Opcode::SYSCALL, 2, 4, 5, false, false),
+            ]);
+        }
+        instructions.push(Instruction::new(Opcode::LW, 31, 30, 0, false, true));
+        Program::new(instructions, 0, 0)
+    }
+
+    #[test]
+    fn test_empty_registry_chips() {
+        let registry = PrecompileRegistry::<MipsAir<KoalaBear>>::new();
+        let names = registry.chips::<KoalaBear>().iter().map(|c| c.name()).collect::<Vec<_>>();
+        let mips_names = MipsAir::<KoalaBear>::chips().iter().map(|c| c.name()).collect::<Vec<_>>();
+        assert_eq!(names, mips_names);
+    }
+
+    #[test]
+    #[should_panic(expected = "is not a custom syscall code")]
+    fn test_register_outside_of_range() {
+        PrecompileRegistry::new().register(byte_precompile(SyscallCode::KECCAK_SPONGE));
+    }
+
+    #[test]
+    #[should_panic(expected = "is registered twice")]
+    fn test_register_twice() {
+        let code = SyscallCode::custom(0).unwrap();
+        PrecompileRegistry::new().register(byte_precompile(code)).register(byte_precompile(code));
+    }
+
+    #[test]
+    #[should_panic(expected = "must use 1 extra cycles")]
+    fn test_register_extra_cycles_mismatch() {
+        struct ZeroCycleSyscall;
+
+        impl Syscall for ZeroCycleSyscall {
+            fn execute(
+                &self,
+                _: &mut SyscallContext,
+                _: SyscallCode,
+                _: u32,
+                _: u32,
+            ) -> Result<Option<u32>, ExecutionError> {
+                Ok(None)
+            }
+        }
+
+        let mut precompile = byte_precompile(SyscallCode::custom(0).unwrap());
+        precompile.syscall = Arc::new(ZeroCycleSyscall);
+        PrecompileRegistry::new().register(precompile);
+    }
+
+    #[test]
+    #[should_panic(expected = "is named as another chip")]
+    fn test_chip_name_collision() {
+        let mut registry = PrecompileRegistry::new();
+        registry.register(byte_precompile(SyscallCode::custom(0).unwrap()));
+        registry.chips::<KoalaBear>();
+    }
+
+    #[test]
+    fn test_split_opts() {
+        let mut registry = PrecompileRegistry::<MipsAir<KoalaBear>>::new();
+        let mut precompile = byte_precompile(SyscallCode::custom(2).unwrap());
+        precompile.rows_per_event = 4;
+        registry.register(precompile);
+        let opts = registry.split_opts(ZKMCoreOpts::default().split_opts);
+        assert_eq!(opts.custom_threshold(2), opts.deferred / 4);
+        assert_eq!(opts.custom_threshold(0), opts.deferred);
+    }
+
+    #[test]
+    fn test_prove_byte_swap() {
+        setup_logger();
+        let registry = byte_swap_registry();
+        let mut shape_config = CoreShapeConfig::default();
+        registry.extend_shape_config(&mut shape_config);
+        let mut program = byte_swap_program();
+        shape_config.fix_preprocessed_shape(&mut program).unwrap();
+
+        let prover = CpuProver::new(registry.machine(KoalaBearPoseidon2::new()));
+        let (pk, vk) = prover.setup(&program);
+        let mut context = ZKMContext::builder();
+        registry.register_syscalls(&mut context);
+        let mut opts = ZKMCoreOpts::default();
+        opts.split_opts = registry.split_opts(opts.split_opts);
+        let (proof, _, _) = prove_with_context(
+            &prover,
+            &pk,
+            program,
+            &ZKMStdin::new(),
+            opts,
+            context.build(),
+            Some(&shape_config),
+        )
+        .unwrap();
+
+        assert!(proof.shard_proofs.iter().any(|shard_proof| shard_proof
+            .shape()
+            .inner
+            .iter()
+            .any(|(name, _)| name == "ByteSwap")));
+        let mut challenger = prover.config().challenger();
+        prover.machine().verify(&vk, &proof, &mut challenger).unwrap();
+    }
+}
@@ -28,7 +28,7 @@ use crate::utils::{next_power_of_two, zeroed_f_vec};
 /// The number of main trace columns for `MovCondChip`.
 pub const NUM_MOV_COND_COLS: usize = size_of::<MovCondCols<u8>>();
 
-/// A chip that implements condition mov for the opcode MNE，MEQ.
+/// A chip that implements condition mov for the opcode MNE，MEQ, SELEQZ, SELNEZ.
 #[derive(Default)]
 pub struct MovCondChip;
 
@@ -59,6 +59,12 @@ pub struct MovCondCols<T> {
 
     /// Flag indicating whether the opcode is `WSBH`.
     pub is_wsbh: T,
+
+    /// Flag indicating whether the opcode is `SELEQZ`.
+    pub is_seleqz: T,
+
+    /// Flag indicating whether the opcode is `SELNEZ`.
+    pub is_selnez: T,
 }
 
 impl<F: PrimeField32> MachineAir<F> for MovCondChip {
@@ -142,6 +148,8 @@ impl MovCondChip {
         cols.is_meq = F::from_bool(matches!(event.opcode, Opcode::MEQ));
         cols.is_mne = F::from_bool(matches!(event.opcode, Opcode::MNE));
         cols.is_wsbh = F::from_bool(matches!(event.opcode, Opcode::WSBH));
+        cols.is_seleqz = F::from_bool(matches!(event.opcode, Opcode::SELEQZ));
+        cols.is_selnez = F::from_bool(matches!(event.opcode, Opcode::SELNEZ));
     }
 }
 
@@ -159,11 +167,14 @@ where
         let main = builder.main();
         let local = main.row_slice(0);
         let local: &MovCondCols<AB::Var> = (*local).borrow();
-        let is_real = local.is_mne + local.is_meq + local.is_wsbh;
+        let is_real =
+            local.is_mne + local.is_meq + local.is_wsbh + local.is_seleqz + local.is_selnez;
 
         let cpu_opcode = local.is_wsbh * Opcode::WSBH.as_field::<AB::F>()
             + local.is_meq * Opcode::MEQ.as_field::<AB::F>()
-            + local.is_mne * Opcode::MNE.as_field::<AB::F>();
+            + local.is_mne * Opcode::MNE.as_field::<AB::F>()
+            + local.is_seleqz * Opcode::SELEQZ.as_field::<AB::F>()
+            + local.is_selnez * Opcode::SELNEZ.as_field::<AB::F>();
 
         builder.receive_instruction(
             AB::Expr::zero(),
@@ -182,6 +193,7 @@ where
             AB::Expr::zero(),
             AB::Expr::zero(),
             AB::Expr::one(),
+            AB::Expr::zero(),
             is_real.clone(),
         );
 
@@ -194,7 +206,7 @@ where
 
         // Constraints for condition move result:
         // op_a = op_b, when condition is true.
-        // Otherwise, op_a remains unchanged.
+        // Otherwise, op_a remains unchanged for MEQ/MNE, and is cleared for SELEQZ/SELNEZ.
         {
             builder
                 .when(local.is_meq)
@@ -215,6 +227,26 @@ where
                 .when(local.is_mne)
                 .when(local.c_eq_0.result)
                 .assert_word_eq(local.op_a_value, local.prev_a_value);
+
+            builder
+                .when(local.is_seleqz)
+                .when(local.c_eq_0.result)
+                .assert_word_eq(local.op_a_value, local.op_b_value);
+
+            builder
+                .when(local.is_seleqz)
+                .when_not(local.c_eq_0.result)
+                .assert_word_zero(local.op_a_value);
+
+            builder
+                .when(local.is_selnez)
+                .when_not(local.c_eq_0.result)
+                .assert_word_eq(local.op_a_value, local.op_b_value);
+
+            builder
+                .when(local.is_selnez)
+                .when(local.c_eq_0.result)
+                .assert_word_zero(local.op_a_value);
         }
 
         self.eval_wsbh(builder, local);
@@ -222,6 +254,8 @@ where
         builder.assert_bool(local.is_mne);
         builder.assert_bool(local.is_meq);
         builder.assert_bool(local.is_wsbh);
+        builder.assert_bool(local.is_seleqz);
+        builder.assert_bool(local.is_selnez);
         builder.assert_bool(is_real);
     }
 }
@@ -266,6 +300,10 @@ mod tests {
             Instruction::new(Opcode::WSBH, 32, 29, 0, false, true),
             Instruction::new(Opcode::WSBH, 32, 31, 0, false, true),
             Instruction::new(Opcode::WSBH, 0, 29, 0, false, true),
+            Instruction::new(Opcode::SELEQZ, 30, 29, 0, false, false),
+            Instruction::new(Opcode::SELEQZ, 30, 29, 28, false, false),
+            Instruction::new(Opcode::SELNEZ, 30, 29, 28, false, false),
+            Instruction::new(Opcode::SELNEZ, 30, 29, 0, false, false),
         ];
         let program = Program::new(instructions, 0, 0);
         run_test::<CpuProver<_, _>>(program).unwrap();
@@ -4,7 +4,7 @@ use crate::memory::MemoryCols;
 use p3_air::{Air, AirBuilder};
 use p3_field::FieldAlgebra;
 use p3_matrix::Matrix;
-use zkm_core_executor::{events::MemoryAccessPosition, ByteOpcode, Opcode};
+use zkm_core_executor::{events::MemoryAccessPosition, fpu::FCSR_RM_MASK, ByteOpcode, Opcode};
 use zkm_primitives::consts::WORD_SIZE;
 use zkm_stark::{air::ZKMAirBuilder, Word};
 
@@ -33,7 +33,16 @@ where
             + local.is_msubu * Opcode::MSUBU.as_field::<AB::F>()
             + local.is_madd * Opcode::MADD.as_field::<AB::F>()
             + local.is_msub * Opcode::MSUB.as_field::<AB::F>()
-            + local.is_teq * Opcode::TEQ.as_field::<AB::F>();
+            + local.is_teq * Opcode::TEQ.as_field::<AB::F>()
+            + local.is_tne * Opcode::TNE.as_field::<AB::F>()
+            + local.is_tge * Opcode::TGE.as_field::<AB::F>()
+            + local.is_tgeu * Opcode::TGEU.as_field::<AB::F>()
+            + local.is_tlt * Opcode::TLT.as_field::<AB::F>()
+            + local.is_tltu * Opcode::TLTU.as_field::<AB::F>()
+            + local.is_lsa * Opcode::LSA.as_field::<AB::F>()
+            + local.is_align * Opcode::ALIGN.as_field::<AB::F>()
+            + local.is_bitswap * Opcode::BITSWAP.as_field::<AB::F>()
+            + local.is_ctc1 * Opcode::CTC1.as_field::<AB::F>();
 
         let is_real = local.is_sext
             + local.is_ins
@@ -42,7 +51,16 @@ where
             + local.is_msubu
             + local.is_madd
             + local.is_msub
-            + local.is_teq;
+            + local.is_teq
+            + local.is_tne
+            + local.is_tge
+            + local.is_tgeu
+            + local.is_tlt
+            + local.is_tltu
+            + local.is_lsa
+            + local.is_align
+            + local.is_bitswap
+            + local.is_ctc1;
 
         builder.assert_bool(local.is_sext);
         builder.assert_bool(local.is_ins);
@@ -52,12 +70,34 @@ where
         builder.assert_bool(local.is_madd);
         builder.assert_bool(local.is_msub);
         builder.assert_bool(local.is_teq);
+        builder.assert_bool(local.is_tne);
+        builder.assert_bool(local.is_tge);
+        builder.assert_bool(local.is_tgeu);
+        builder.assert_bool(local.is_tlt);
+        builder.assert_bool(local.is_tltu);
+        builder.assert_bool(local.is_lsa);
+        builder.assert_bool(local.is_align);
+        builder.assert_bool(local.is_bitswap);
+        builder.assert_bool(local.is_ctc1);
         builder.assert_bool(is_real.clone());
 
         let is_rw_a =
             local.is_maddu + local.is_msubu + local.is_madd + local.is_msub + local.is_ins;
 
-        let is_check_memory = local.is_maddu + local.is_msubu + local.is_madd + local.is_msub;
+        let is_check_memory = local.is_maddu
+            + local.is_msubu
+            + local.is_madd
+            + local.is_msub
+            + local.is_lsa
+            + local.is_align;
+
+        // Traps only read their operands.
+        let is_trap = local.is_teq
+            + local.is_tne
+            + local.is_tge
+            + local.is_tgeu
+            + local.is_tlt
+            + local.is_tltu;
 
         builder.receive_instruction(
             local.shard,
@@ -71,11 +111,12 @@ where
             local.op_b_value,
             local.op_c_value,
             local.prev_a_value,
-            AB::Expr::zero(),
+            is_trap.clone(),
             is_rw_a.clone(),
             is_check_memory.clone(),
             AB::Expr::zero(),
             AB::Expr::one(),
+            AB::Expr::zero(),
             is_check_memory,
         );
 
@@ -91,18 +132,28 @@ where
             local.op_b_value,
             local.op_c_value,
             local.prev_a_value,
-            AB::Expr::zero(),
+            is_trap.clone(),
             is_rw_a,
             AB::Expr::zero(),
             AB::Expr::zero(),
             AB::Expr::one(),
-            local.is_sext + local.is_teq + local.is_ext + local.is_ins,
+            AB::Expr::zero(),
+            local.is_sext
+                + is_trap
+                + local.is_ext
+                + local.is_ins
+                + local.is_bitswap
+                + local.is_ctc1,
         );
 
         self.eval_ext(builder, local);
         self.eval_ins(builder, local);
         self.eval_maddsub(builder, local);
         self.eval_sext(builder, local);
+        self.eval_trap(builder, local);
+        self.eval_lsa_align(builder, local);
+        self.eval_bitswap(builder, local);
+        self.eval_ctc1(builder, local);
 
         builder.when(local.is_ins + local.is_ext).assert_zero(local.op_c_value[2]);
         builder.when(local.is_ins + local.is_ext).assert_zero(local.op_c_value[3]);
@@ -117,12 +168,6 @@ impl MiscInstrsChip {
     ) {
         let sext_cols = local.misc_specific_columns.sext();
 
-        builder
-            .when(local.is_teq * sext_cols.a_eq_b)
-            .assert_word_eq(local.op_a_value, local.op_b_value);
-
-        builder.when(local.is_teq).assert_zero(sext_cols.a_eq_b);
-
         // most_sig_bit is bit 7 of sig_byte.
         builder.send_byte(
             ByteOpcode::MSB.as_field::<AB::F>(),
@@ -415,4 +460,165 @@ impl MiscInstrsChip {
             local.is_ext,
         );
     }
+
+    pub(crate) fn eval_ctc1<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &MiscInstrColumns<AB::Var>,
+    ) {
+        // `fcsr` is set to `rt`.
+        builder.when(local.is_ctc1).assert_word_eq(local.op_a_value, local.op_b_value);
+
+        // The rounding mode must be round to nearest, as any other one raises an exception.
+        builder.send_byte(
+            ByteOpcode::AND.as_field::<AB::F>(),
+            AB::Expr::zero(),
+            local.op_b_value[0],
+            AB::Expr::from_canonical_u32(FCSR_RM_MASK),
+            local.is_ctc1,
+        );
+    }
+
+    pub(crate) fn eval_trap<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &MiscInstrColumns<AB::Var>,
+    ) {
+        let trap_cols = local.misc_specific_columns.trap();
+        let is_trap = local.is_teq
+            + local.is_tne
+            + local.is_tge
+            + local.is_tgeu
+            + local.is_tlt
+            + local.is_tltu;
+
+        // Calculate a_lt_b <==> a < b and a_gt_b <==> a > b (using appropriate signedness).
+        // SAFETY: `is_unsigned` is boolean, since at most one selector is turned on.
+        let is_unsigned = local.is_tgeu + local.is_tltu;
+        let opcode = Opcode::SLT.as_field::<AB::F>()
+            + is_unsigned * (Opcode::SLTU.as_field::<AB::F>() - Opcode::SLT.as_field::<AB::F>());
+        builder.send_alu(
+            opcode.clone(),
+            Word::extend_var::<AB>(trap_cols.a_lt_b),
+            local.op_a_value,
+            local.op_b_value,
+            is_trap.clone(),
+        );
+        builder.send_alu(
+            opcode,
+            Word::extend_var::<AB>(trap_cols.a_gt_b),
+            local.op_b_value,
+            local.op_a_value,
+            is_trap,
+        );
+
+        // The trap condition must not hold, as a taken trap ends the execution.
+        builder.when(local.is_teq).assert_one(trap_cols.a_lt_b + trap_cols.a_gt_b);
+        builder.when(local.is_tne).assert_zero(trap_cols.a_lt_b + trap_cols.a_gt_b);
+        builder.when(local.is_tge + local.is_tgeu).assert_one(trap_cols.a_lt_b);
+        builder.when(local.is_tlt + local.is_tltu).assert_zero(trap_cols.a_lt_b);
+    }
+
+    pub(crate) fn eval_lsa_align<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &MiscInstrColumns<AB::Var>,
+    ) {
+        let lsa_align_cols = local.misc_specific_columns.lsa_align();
+        let is_real = local.is_lsa + local.is_align;
+        let rt_value = *lsa_align_cols.rt_access.value();
+
+        // op_c = (rt << 5) + sa
+        builder.when(is_real.clone()).assert_eq(
+            local.op_c_value.reduce::<AB>(),
+            lsa_align_cols.sa + lsa_align_cols.rt_reg * AB::Expr::from_canonical_u32(32),
+        );
+
+        // 0 <= sa < 4, 0 <= rt < 32.
+        builder.send_byte(
+            ByteOpcode::LTU.as_field::<AB::F>(),
+            AB::Expr::one(),
+            lsa_align_cols.sa,
+            AB::Expr::from_canonical_u32(4),
+            is_real.clone(),
+        );
+        builder.send_byte(
+            ByteOpcode::LTU.as_field::<AB::F>(),
+            AB::Expr::one(),
+            lsa_align_cols.rt_reg,
+            AB::Expr::from_canonical_u32(32),
+            is_real.clone(),
+        );
+
+        // rt is read and left unchanged.
+        builder.eval_memory_access(
+            local.shard,
+            local.clk + AB::F::from_canonical_u32(MemoryAccessPosition::C as u32),
+            lsa_align_cols.rt_reg,
+            &lsa_align_cols.rt_access,
+            is_real.clone(),
+        );
+        builder.when(is_real).assert_word_eq(rt_value, lsa_align_cols.rt_access.prev_value);
+
+        // Lsa can be divided into 2 operations:
+        //    sll_val = op_b << (sa + 1)
+        //    result = sll_val + rt
+        builder.send_alu(
+            Opcode::SLL.as_field::<AB::F>(),
+            lsa_align_cols.sll_val,
+            local.op_b_value,
+            Word([
+                AB::Expr::one() + lsa_align_cols.sa,
+                AB::Expr::zero(),
+                AB::Expr::zero(),
+                AB::Expr::zero(),
+            ]),
+            local.is_lsa,
+        );
+        builder.send_alu(
+            Opcode::ADD.as_field::<AB::F>(),
+            local.op_a_value,
+            lsa_align_cols.sll_val,
+            rt_value,
+            local.is_lsa,
+        );
+
+        // Align concatenates the low `4 - bp` bytes of rt with the high `bp` bytes of op_b.
+        let mut bp_sum = AB::Expr::zero();
+        let mut bp = AB::Expr::zero();
+        for (i, is_bp) in lsa_align_cols.is_bp.iter().enumerate() {
+            builder.when(local.is_align).assert_bool(*is_bp);
+            bp_sum = bp_sum + *is_bp;
+            bp = bp + *is_bp * AB::F::from_canonical_usize(i);
+            for j in 0..WORD_SIZE {
+                let byte =
+                    if j >= i { rt_value[j - i] } else { local.op_b_value[WORD_SIZE - i + j] };
+                builder.when(local.is_align * *is_bp).assert_eq(local.op_a_value[j], byte);
+            }
+        }
+        builder.when(local.is_align).assert_one(bp_sum);
+        builder.when(local.is_align).assert_eq(lsa_align_cols.sa, bp);
+    }
+
+    pub(crate) fn eval_bitswap<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &MiscInstrColumns<AB::Var>,
+    ) {
+        let bitswap_cols = local.misc_specific_columns.bitswap();
+
+        // Each byte of op_a is the corresponding byte of op_b with its bits reversed.
+        for i in 0..WORD_SIZE {
+            let mut b_byte = AB::Expr::zero();
+            let mut a_byte = AB::Expr::zero();
+            for j in 0..8 {
+                let bit = bitswap_cols.b_bits[i * 8 + j];
+                builder.when(local.is_bitswap).assert_bool(bit);
+                b_byte = b_byte + bit * AB::F::from_canonical_u32(1 << j);
+                a_byte = a_byte + bit * AB::F::from_canonical_u32(1 << (7 - j));
+            }
+            builder.when(local.is_bitswap).assert_eq(local.op_b_value[i], b_byte);
+            builder.when(local.is_bitswap).assert_eq(local.op_a_value[i], a_byte);
+        }
+    }
 }
@@ -0,0 +1,12 @@
+use std::mem::size_of;
+use zkm_derive::AlignedBorrow;
+
+pub const NUM_BITSWAP_COLS: usize = size_of::<BitswapCols<u8>>();
+
+/// The column layout for bitswap.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct BitswapCols<T> {
+    /// The bits of op_b, in little-endian order.
+    pub b_bits: [T; 32],
+}
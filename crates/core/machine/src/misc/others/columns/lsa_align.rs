@@ -0,0 +1,24 @@
+use crate::memory::MemoryReadWriteCols;
+use std::mem::size_of;
+use zkm_derive::AlignedBorrow;
+use zkm_stark::Word;
+
+pub const NUM_LSA_ALIGN_COLS: usize = size_of::<LsaAlignCols<u8>>();
+
+/// The column layout for lsa and align.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct LsaAlignCols<T> {
+    /// The rt register and the shift amount, packed into op_c.
+    pub rt_reg: T,
+    pub sa: T,
+
+    /// Access to rt register, which is read and left unchanged.
+    pub rt_access: MemoryReadWriteCols<T>,
+
+    /// Result value of intermediate sll operation of lsa.
+    pub sll_val: Word<T>,
+
+    /// Byte position selectors of align.
+    pub is_bp: [T; 4],
+}
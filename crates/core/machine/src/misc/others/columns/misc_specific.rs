@@ -1,4 +1,6 @@
-use crate::misc::columns::{ExtCols, InsCols, MaddsubCols, SextCols};
+use crate::misc::columns::{
+    BitswapCols, ExtCols, InsCols, LsaAlignCols, MaddsubCols, SextCols, TrapCols,
+};
 use std::{
     fmt::{Debug, Formatter},
     mem::{size_of, transmute},
@@ -16,6 +18,9 @@ pub union MiscSpecificCols<T: Copy> {
     sext: SextCols<T>,
     ext: ExtCols<T>,
     ins: InsCols<T>,
+    trap: TrapCols<T>,
+    lsa_align: LsaAlignCols<T>,
+    bitswap: BitswapCols<T>,
 }
 
 impl<T: Copy + Default> Default for MiscSpecificCols<T> {
@@ -62,4 +67,22 @@ impl<T: Copy> MiscSpecificCols<T> {
     pub fn ins_mut(&mut self) -> &mut InsCols<T> {
         unsafe { &mut self.ins }
     }
+    pub fn trap(&self) -> &TrapCols<T> {
+        unsafe { &self.trap }
+    }
+    pub fn trap_mut(&mut self) -> &mut TrapCols<T> {
+        unsafe { &mut self.trap }
+    }
+    pub fn lsa_align(&self) -> &LsaAlignCols<T> {
+        unsafe { &self.lsa_align }
+    }
+    pub fn lsa_align_mut(&mut self) -> &mut LsaAlignCols<T> {
+        unsafe { &mut self.lsa_align }
+    }
+    pub fn bitswap(&self) -> &BitswapCols<T> {
+        unsafe { &self.bitswap }
+    }
+    pub fn bitswap_mut(&mut self) -> &mut BitswapCols<T> {
+        unsafe { &mut self.bitswap }
+    }
 }
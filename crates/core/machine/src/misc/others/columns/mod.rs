@@ -1,14 +1,20 @@
+mod bitswap;
 mod ext;
 mod ins;
+mod lsa_align;
 mod maddsub;
 mod misc_specific;
 mod sext;
+mod trap;
 
+pub use bitswap::*;
 pub use ext::*;
 pub use ins::*;
+pub use lsa_align::*;
 pub use maddsub::*;
 pub use misc_specific::*;
 pub use sext::*;
+pub use trap::*;
 
 use std::mem::size_of;
 use zkm_derive::AlignedBorrow;
@@ -47,4 +53,13 @@ pub struct MiscInstrColumns<T: Copy> {
     pub is_madd: T,
     pub is_msub: T,
     pub is_teq: T,
+    pub is_tne: T,
+    pub is_tge: T,
+    pub is_tgeu: T,
+    pub is_tlt: T,
+    pub is_tltu: T,
+    pub is_lsa: T,
+    pub is_align: T,
+    pub is_bitswap: T,
+    pub is_ctc1: T,
 }
@@ -19,7 +19,7 @@ mod tests {
 
     use crate::{utils, utils::run_test};
 
-    use zkm_core_executor::{Instruction, Opcode, Program};
+    use zkm_core_executor::{Instruction, Opcode, Program, Register};
 
     use zkm_stark::CpuProver;
 
@@ -56,6 +56,25 @@ mod tests {
             Instruction::new(Opcode::TEQ, 28, 29, 0, false, true),
             Instruction::new(Opcode::TEQ, 28, 0, 0, false, true),
             Instruction::new(Opcode::TEQ, 0, 28, 0, false, true),
+            Instruction::new(Opcode::TNE, 28, 28, 0, false, true),
+            Instruction::new(Opcode::TGE, 29, 28, 0, false, true),
+            Instruction::new(Opcode::TGEU, 29, 28, 0, false, true),
+            Instruction::new(Opcode::TLT, 28, 29, 0, false, true),
+            Instruction::new(Opcode::TLTU, 28, 29, 0, false, true),
+            Instruction::new(Opcode::TNE, 28, 0x8F8F, 0, true, true),
+            Instruction::new(Opcode::TGE, 29, 0x10, 0, true, true),
+            Instruction::new(Opcode::LSA, 30, 29, 28 << 5, false, true),
+            Instruction::new(Opcode::LSA, 30, 29, 28 << 5 | 3, false, true),
+            Instruction::new(Opcode::LSA, 0, 29, 28 << 5 | 1, false, true),
+            Instruction::new(Opcode::ALIGN, 30, 29, 28 << 5, false, true),
+            Instruction::new(Opcode::ALIGN, 30, 29, 28 << 5 | 1, false, true),
+            Instruction::new(Opcode::ALIGN, 30, 29, 28 << 5 | 2, false, true),
+            Instruction::new(Opcode::ALIGN, 30, 29, 28 << 5 | 3, false, true),
+            Instruction::new(Opcode::BITSWAP, 30, 28, 0, false, true),
+            Instruction::new(Opcode::BITSWAP, 0, 31, 0, false, true),
+            Instruction::new(Opcode::ADD, 27, 0, 0x0100_007c, false, true),
+            Instruction::new(Opcode::CTC1, Register::FCSR as u8, 27, 0, false, true),
+            Instruction::new(Opcode::CTC1, Register::FCSR as u8, 0, 0, false, true),
         ];
         let program = Program::new(instructions, 0, 0);
         run_test::<CpuProver<_, _>>(program).unwrap();
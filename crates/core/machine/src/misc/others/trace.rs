@@ -7,6 +7,7 @@ use p3_matrix::dense::RowMajorMatrix;
 use rayon::iter::{ParallelBridge, ParallelIterator};
 use zkm_core_executor::{
     events::{ByteLookupEvent, ByteRecord, MemoryRecordEnum, MiscEvent},
+    fpu::FCSR_RM_MASK,
     ByteOpcode, ExecutionRecord, Opcode, Program,
 };
 use zkm_stark::{air::MachineAir, Word};
@@ -102,11 +103,33 @@ impl MiscInstrsChip {
         cols.is_madd = F::from_bool(matches!(event.opcode, Opcode::MADD));
         cols.is_msub = F::from_bool(matches!(event.opcode, Opcode::MSUB));
         cols.is_teq = F::from_bool(matches!(event.opcode, Opcode::TEQ));
+        cols.is_tne = F::from_bool(matches!(event.opcode, Opcode::TNE));
+        cols.is_tge = F::from_bool(matches!(event.opcode, Opcode::TGE));
+        cols.is_tgeu = F::from_bool(matches!(event.opcode, Opcode::TGEU));
+        cols.is_tlt = F::from_bool(matches!(event.opcode, Opcode::TLT));
+        cols.is_tltu = F::from_bool(matches!(event.opcode, Opcode::TLTU));
+        cols.is_lsa = F::from_bool(matches!(event.opcode, Opcode::LSA));
+        cols.is_align = F::from_bool(matches!(event.opcode, Opcode::ALIGN));
+        cols.is_bitswap = F::from_bool(matches!(event.opcode, Opcode::BITSWAP));
+        cols.is_ctc1 = F::from_bool(matches!(event.opcode, Opcode::CTC1));
 
         self.populate_sext(cols, event, blu);
         self.populate_maddsub(cols, event, blu);
         self.populate_ext(cols, event, blu);
         self.populate_ins(cols, event, blu);
+        self.populate_trap(cols, event);
+        self.populate_lsa_align(cols, event, blu);
+        self.populate_bitswap(cols, event);
+
+        if event.opcode == Opcode::CTC1 {
+            blu.add_byte_lookup_event(ByteLookupEvent {
+                opcode: ByteOpcode::AND,
+                a1: 0,
+                a2: 0,
+                b: event.b as u8,
+                c: FCSR_RM_MASK as u8,
+            });
+        }
     }
 
     fn populate_sext<F: PrimeField32>(
@@ -115,7 +138,7 @@ impl MiscInstrsChip {
         event: &MiscEvent,
         blu: &mut impl ByteRecord,
     ) {
-        if !matches!(event.opcode, Opcode::SEXT | Opcode::TEQ) {
+        if !matches!(event.opcode, Opcode::SEXT) {
             return;
         }
         let sext_cols = cols.misc_specific_columns.sext_mut();
@@ -130,17 +153,13 @@ impl MiscInstrsChip {
         sext_cols.most_sig_bit = F::from_canonical_u16(sig_bit);
         sext_cols.sig_byte = F::from_canonical_u8(sig_byte);
 
-        sext_cols.a_eq_b = F::from_bool(event.b == event.a);
-
-        if matches!(event.opcode, Opcode::SEXT) {
-            blu.add_byte_lookup_event(ByteLookupEvent {
-                opcode: ByteOpcode::MSB,
-                a1: sig_bit,
-                a2: 0,
-                b: sig_byte,
-                c: 0,
-            });
-        }
+        blu.add_byte_lookup_event(ByteLookupEvent {
+            opcode: ByteOpcode::MSB,
+            a1: sig_bit,
+            a2: 0,
+            b: sig_byte,
+            c: 0,
+        });
     }
 
     fn populate_maddsub<F: PrimeField32>(
@@ -260,4 +279,67 @@ impl MiscInstrsChip {
             c: 32,
         });
     }
+
+    fn populate_trap<F: PrimeField32>(&self, cols: &mut MiscInstrColumns<F>, event: &MiscEvent) {
+        let unsigned = match event.opcode {
+            Opcode::TGEU | Opcode::TLTU => true,
+            Opcode::TEQ | Opcode::TNE | Opcode::TGE | Opcode::TLT => false,
+            _ => return,
+        };
+        let trap_cols = cols.misc_specific_columns.trap_mut();
+        let (a_lt_b, a_gt_b) = if unsigned {
+            (event.a < event.b, event.a > event.b)
+        } else {
+            ((event.a as i32) < (event.b as i32), (event.a as i32) > (event.b as i32))
+        };
+        trap_cols.a_lt_b = F::from_bool(a_lt_b);
+        trap_cols.a_gt_b = F::from_bool(a_gt_b);
+    }
+
+    fn populate_lsa_align<F: PrimeField32>(
+        &self,
+        cols: &mut MiscInstrColumns<F>,
+        event: &MiscEvent,
+        blu: &mut impl ByteRecord,
+    ) {
+        if !matches!(event.opcode, Opcode::LSA | Opcode::ALIGN) {
+            return;
+        }
+        let lsa_align_cols = cols.misc_specific_columns.lsa_align_mut();
+        let rt_reg = event.c >> 5;
+        let sa = event.c & 0x3;
+        lsa_align_cols.rt_reg = F::from_canonical_u32(rt_reg);
+        lsa_align_cols.sa = F::from_canonical_u32(sa);
+        lsa_align_cols.rt_access.populate(MemoryRecordEnum::Write(event.hi_record), blu);
+        if event.opcode == Opcode::LSA {
+            lsa_align_cols.sll_val = Word::from(event.b << (sa + 1));
+        } else {
+            lsa_align_cols.is_bp[sa as usize] = F::ONE;
+        }
+
+        blu.add_byte_lookup_event(ByteLookupEvent {
+            opcode: ByteOpcode::LTU,
+            a1: 1,
+            a2: 0,
+            b: sa as u8,
+            c: 4,
+        });
+        blu.add_byte_lookup_event(ByteLookupEvent {
+            opcode: ByteOpcode::LTU,
+            a1: 1,
+            a2: 0,
+            b: rt_reg as u8,
+            c: 32,
+        });
+    }
+
+    fn populate_bitswap<F: PrimeField32>(&self, cols: &mut MiscInstrColumns<F>, event: &MiscEvent) {
+        if !matches!(event.opcode, Opcode::BITSWAP) {
+            return;
+        }
+        let bitswap_cols = cols.misc_specific_columns.bitswap_mut();
+        for (i, bit) in bitswap_cols.b_bits.iter_mut().enumerate() {
+            *bit = F::from_canonical_u32((event.b >> i) & 1);
+        }
+    }
 }
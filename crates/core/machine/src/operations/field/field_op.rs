@@ -51,17 +51,34 @@ impl<F: PrimeField32, P: FieldParameters> FieldOpCols<F, P> {
         b: &BigUint,
         c: &BigUint,
         modulus: &BigUint,
+    ) -> (BigUint, BigUint) {
+        self.populate_mul_add_and_carry(record, a, b, c, &BigUint::ZERO, modulus)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    /// Populate result and carry columns from the equation (a*b + c + d) % modulus, where the
+    /// addends `c` and `d` are added limb by limb, as in a multiply-accumulate step that also takes
+    /// the carry of the previous step.
+    pub fn populate_mul_add_and_carry(
+        &mut self,
+        record: &mut impl ByteRecord,
+        a: &BigUint,
+        b: &BigUint,
+        c: &BigUint,
+        d: &BigUint,
+        modulus: &BigUint,
     ) -> (BigUint, BigUint) {
         let p_a: Polynomial<F> = P::to_limbs_field::<F, _>(a).into();
         let p_b: Polynomial<F> = P::to_limbs_field::<F, _>(b).into();
         let p_c: Polynomial<F> = P::to_limbs_field::<F, _>(c).into();
+        let p_d: Polynomial<F> = P::to_limbs_field::<F, _>(d).into();
 
-        let mul_add = a * b + c;
+        let mul_add = a * b + c + d;
         let result = &mul_add % modulus;
         let carry = (mul_add - &result) / modulus;
         debug_assert!(&result < modulus);
         debug_assert!(&carry < modulus);
-        debug_assert_eq!(&carry * modulus, a * b + c - &result);
+        debug_assert_eq!(&carry * modulus, a * b + c + d - &result);
 
         let p_modulus_limbs =
             modulus.to_bytes_le().iter().map(|x| F::from_canonical_u8(*x)).collect::<Vec<F>>();
@@ -69,7 +86,7 @@ impl<F: PrimeField32, P: FieldParameters> FieldOpCols<F, P> {
         let p_result: Polynomial<F> = P::to_limbs_field::<F, _>(&result).into();
         let p_carry: Polynomial<F> = P::to_limbs_field::<F, _>(&carry).into();
 
-        let p_op = &p_a * &p_b + &p_c;
+        let p_op = &p_a * &p_b + &p_c + &p_d;
         let p_vanishing = &p_op - &p_result - &p_carry * &p_modulus;
 
         let p_witness = compute_root_quotient_and_shift(
@@ -0,0 +1,115 @@
+use p3_field::{Field, FieldAlgebra};
+use zkm_core_executor::{
+    events::{ByteLookupEvent, ByteRecord},
+    ByteOpcode,
+};
+use zkm_derive::AlignedBorrow;
+use zkm_primitives::consts::WORD_SIZE;
+use zkm_stark::{air::ZKMAirBuilder, Word};
+
+use crate::bytes::utils::shr_carry;
+
+/// The number of bytes in a double word.
+const DOUBLE_WORD_SIZE: usize = 2 * WORD_SIZE;
+
+/// A set of columns needed to compute `rotateright` of a double word with a fixed offset R.
+///
+/// Note that we decompose shifts into a byte shift and a bit shift.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct FixedRotateRightDoubleOperation<T> {
+    /// The output value.
+    pub value: Word<T>,
+    pub value_hi: Word<T>,
+
+    /// The shift output of `shrcarry` on each byte of a double word.
+    pub shift: [T; DOUBLE_WORD_SIZE],
+
+    /// The carry output of `shrcarry` on each byte of a double word.
+    pub carry: [T; DOUBLE_WORD_SIZE],
+}
+
+impl<F: Field> FixedRotateRightDoubleOperation<F> {
+    pub const fn nb_bytes_to_shift(rotation: usize) -> usize {
+        rotation / 8
+    }
+
+    pub const fn nb_bits_to_shift(rotation: usize) -> usize {
+        rotation % 8
+    }
+
+    pub const fn carry_multiplier(rotation: usize) -> u32 {
+        let nb_bits_to_shift = Self::nb_bits_to_shift(rotation);
+        1 << (8 - nb_bits_to_shift)
+    }
+
+    pub fn populate(&mut self, record: &mut impl ByteRecord, input: u64, rotation: usize) -> u64 {
+        let input_bytes = input.to_le_bytes();
+        let expected = input.rotate_right(rotation as u32);
+
+        // Compute some constants with respect to the rotation needed for the rotation.
+        let nb_bytes_to_shift = Self::nb_bytes_to_shift(rotation);
+        let nb_bits_to_shift = Self::nb_bits_to_shift(rotation);
+        let carry_multiplier = F::from_canonical_u32(Self::carry_multiplier(rotation));
+
+        // For each byte of the byte-rotated input, calculate the shift and carry. The output byte
+        // combines the shifted byte with the carry of the next byte.
+        let mut value = [F::ZERO; DOUBLE_WORD_SIZE];
+        for i in 0..DOUBLE_WORD_SIZE {
+            let b = input_bytes[(i + nb_bytes_to_shift) % DOUBLE_WORD_SIZE];
+            let c = nb_bits_to_shift as u8;
+
+            let (shift, carry) = shr_carry(b, c);
+
+            let byte_event =
+                ByteLookupEvent { opcode: ByteOpcode::ShrCarry, a1: shift as u16, a2: carry, b, c };
+            record.add_byte_lookup_event(byte_event);
+
+            self.shift[i] = F::from_canonical_u8(shift);
+            self.carry[i] = F::from_canonical_u8(carry);
+        }
+        for (i, byte) in value.iter_mut().enumerate() {
+            *byte = self.shift[i] + self.carry[(i + 1) % DOUBLE_WORD_SIZE] * carry_multiplier;
+        }
+        self.value = Word(value[..WORD_SIZE].try_into().unwrap());
+        self.value_hi = Word(value[WORD_SIZE..].try_into().unwrap());
+
+        // Check that the value is correct.
+        assert_eq!(self.value.to_u32() as u64 | ((self.value_hi.to_u32() as u64) << 32), expected);
+
+        expected
+    }
+
+    pub fn eval<AB: ZKMAirBuilder>(
+        builder: &mut AB,
+        input: Word<AB::Var>,
+        input_hi: Word<AB::Var>,
+        rotation: usize,
+        cols: FixedRotateRightDoubleOperation<AB::Var>,
+        is_real: AB::Var,
+    ) {
+        // Compute some constants with respect to the rotation needed for the rotation.
+        let nb_bytes_to_shift = Self::nb_bytes_to_shift(rotation);
+        let nb_bits_to_shift = Self::nb_bits_to_shift(rotation);
+        let carry_multiplier = AB::F::from_canonical_u32(Self::carry_multiplier(rotation));
+
+        let input_bytes = input.into_iter().chain(input_hi).collect::<Vec<_>>();
+        let value_bytes = cols.value.into_iter().chain(cols.value_hi).collect::<Vec<_>>();
+
+        for i in 0..DOUBLE_WORD_SIZE {
+            builder.send_byte_pair(
+                AB::F::from_canonical_u32(ByteOpcode::ShrCarry as u32),
+                cols.shift[i],
+                cols.carry[i],
+                input_bytes[(i + nb_bytes_to_shift) % DOUBLE_WORD_SIZE],
+                AB::F::from_canonical_usize(nb_bits_to_shift),
+                is_real,
+            );
+
+            builder.assert_eq(
+                value_bytes[i],
+                cols.shift[i] + cols.carry[(i + 1) % DOUBLE_WORD_SIZE] * carry_multiplier,
+            );
+        }
+    }
+}
@@ -12,6 +12,7 @@ mod and;
 mod cmp;
 pub mod field;
 mod fixed_rotate_right;
+mod fixed_rotate_right_double;
 mod fixed_shift_right;
 mod global_accumulation;
 mod global_lookup;
@@ -32,6 +33,7 @@ pub use adddouble::*;
 pub use and::*;
 pub use cmp::*;
 pub use fixed_rotate_right::*;
+pub use fixed_rotate_right_double::*;
 pub use fixed_shift_right::*;
 pub use global_accumulation::*;
 pub use global_lookup::*;
@@ -9,19 +9,17 @@ use p3_koala_bear::KoalaBear;
 use p3_util::log2_ceil_usize;
 use thiserror::Error;
 
-use zkm_core_executor::{ExecutionRecord, MipsAirId, Program};
+use zkm_core_executor::{
+    events::PrecompileLocalMemory, syscalls::SyscallCode, ExecutionRecord, MipsAirId, Program,
+};
 use zkm_stark::{
     air::MachineAir,
     shape::{OrderedShape, Shape, ShapeCluster},
     MachineRecord,
 };
 
-use super::mips::mips_chips::{ByteChip, ProgramChip, SyscallChip};
-use crate::{
-    global::GlobalChip,
-    memory::{MemoryLocalChip, NUM_LOCAL_MEMORY_ENTRIES_PER_ROW},
-    mips::MipsAir,
-};
+use super::mips::mips_chips::{ByteChip, ProgramChip};
+use crate::{memory::NUM_LOCAL_MEMORY_ENTRIES_PER_ROW, mips::MipsAir};
 
 /// The set of maximal shapes.
 ///
@@ -43,9 +41,31 @@ pub struct CoreShapeConfig<F: PrimeField32> {
     partial_memory_shapes: ShapeCluster<MipsAirId>,
     partial_precompile_shapes: HashMap<MipsAir<F>, (usize, Vec<usize>)>,
     partial_small_shapes: Vec<ShapeCluster<MipsAirId>>,
+    custom_precompile_shapes: Vec<CustomPrecompileShapes>,
     costs: HashMap<MipsAirId, usize>,
 }
 
+/// The allowed shapes of the shards of a custom precompile.
+#[derive(Debug, Clone)]
+struct CustomPrecompileShapes {
+    code: SyscallCode,
+    name: String,
+    rows_per_event: usize,
+    memory_events_per_row: usize,
+    allowed_log2_heights: Vec<usize>,
+}
+
+impl CustomPrecompileShapes {
+    fn shapes(&self, allowed_log2_height: usize) -> Vec<[(String, usize); 4]> {
+        precompile_shapes(
+            self.name.clone(),
+            self.rows_per_event,
+            self.memory_events_per_row,
+            allowed_log2_height,
+        )
+    }
+}
+
 impl<F: PrimeField32> CoreShapeConfig<F> {
     /// Fix the preprocessed shape of the proof.
     pub fn fix_preprocessed_shape(&self, program: &mut Program) -> Result<(), CoreShapeError> {
@@ -200,83 +220,124 @@ impl<F: PrimeField32> CoreShapeConfig<F> {
             if let Some((height, num_memory_local_events, num_global_events)) =
                 air.precompile_heights(record)
             {
-                for allowed_log2_height in allowed_log2_heights {
-                    let allowed_height = 1 << allowed_log2_height;
-                    if height <= allowed_height {
-                        for shape in self.get_precompile_shapes(
-                            air,
-                            *memory_events_per_row,
-                            *allowed_log2_height,
-                        ) {
-                            let mem_events_height = shape[2].1;
-                            let global_events_height = shape[3].1;
-                            if num_memory_local_events.div_ceil(NUM_LOCAL_MEMORY_ENTRIES_PER_ROW)
-                                <= (1 << mem_events_height)
-                                && num_global_events <= (1 << global_events_height)
-                            {
-                                record.shape.as_mut().unwrap().extend(
-                                    shape.iter().map(|x| (MipsAirId::from_str(&x.0).unwrap(), x.1)),
-                                );
-                                return Ok(());
-                            }
-                        }
-                    }
-                }
+                let Some(shape) = Self::find_precompile_shape(
+                    height,
+                    num_memory_local_events,
+                    num_global_events,
+                    allowed_log2_heights,
+                    |allowed_log2_height| {
+                        self.get_precompile_shapes(air, *memory_events_per_row, allowed_log2_height)
+                    },
+                ) else {
+                    tracing::error!(
+                        "Cannot find shape for precompile {:?}, height {:?}, and mem events {:?}",
+                        air.name(),
+                        height,
+                        num_memory_local_events
+                    );
+                    return Err(CoreShapeError::ShapeError(record.stats()));
+                };
+                record
+                    .shape
+                    .as_mut()
+                    .unwrap()
+                    .extend(shape.iter().map(|x| (MipsAirId::from_str(&x.0).unwrap(), x.1)));
+                return Ok(());
+            }
+        }
+
+        // Try to fix the shape as a custom precompile record.
+        for custom in self.custom_precompile_shapes.iter() {
+            let Some(events) =
+                record.precompile_events.get_events(custom.code).filter(|e| !e.is_empty())
+            else {
+                continue;
+            };
+            let height = events.len() * custom.rows_per_event;
+            let num_memory_local_events = events.get_local_mem_events().into_iter().count();
+            let Some([custom_shape, shape @ ..]) = Self::find_precompile_shape(
+                height,
+                num_memory_local_events,
+                record.global_lookup_events.len(),
+                &custom.allowed_log2_heights,
+                |allowed_log2_height| custom.shapes(allowed_log2_height),
+            ) else {
                 tracing::error!(
-                    "Cannot find shape for precompile {:?}, height {:?}, and mem events {:?}",
-                    air.name(),
+                    "Cannot find shape for custom precompile {:?}, height {:?}, mem events {:?}",
+                    custom.name,
                     height,
                     num_memory_local_events
                 );
                 return Err(CoreShapeError::ShapeError(record.stats()));
-            }
+            };
+            record
+                .shape
+                .as_mut()
+                .unwrap()
+                .extend(shape.iter().map(|x| (MipsAirId::from_str(&x.0).unwrap(), x.1)));
+            record.custom_shape = Some(custom_shape);
+            return Ok(());
         }
 
         Err(CoreShapeError::PrecompileNotIncluded(record.stats()))
     }
 
+    /// Finds the first allowed shape of a precompile shard whose precompile chip has `height` rows
+    /// and which fits the local memory and global events of the shard.
+    fn find_precompile_shape(
+        height: usize,
+        num_memory_local_events: usize,
+        num_global_events: usize,
+        allowed_log2_heights: &[usize],
+        shapes: impl Fn(usize) -> Vec<[(String, usize); 4]>,
+    ) -> Option<[(String, usize); 4]> {
+        allowed_log2_heights
+            .iter()
+            .filter(|allowed_log2_height| height <= 1 << **allowed_log2_height)
+            .flat_map(|allowed_log2_height| shapes(*allowed_log2_height))
+            .find(|shape| {
+                let mem_events_height = shape[2].1;
+                let global_events_height = shape[3].1;
+                num_memory_local_events.div_ceil(NUM_LOCAL_MEMORY_ENTRIES_PER_ROW)
+                    <= (1 << mem_events_height)
+                    && num_global_events <= (1 << global_events_height)
+            })
+    }
+
+    /// Allows the shards of the custom precompile with the given syscall code.
+    ///
+    /// The chip of the precompile is named `name`, has `rows_per_event` rows per event and
+    /// `memory_events_per_row` local memory events per row, and may have any of the
+    /// `allowed_log2_heights` in a precompile shard.
+    pub fn add_custom_precompile(
+        &mut self,
+        code: SyscallCode,
+        name: String,
+        rows_per_event: usize,
+        memory_events_per_row: usize,
+        allowed_log2_heights: Vec<usize>,
+    ) {
+        self.custom_precompile_shapes.push(CustomPrecompileShapes {
+            code,
+            name,
+            rows_per_event,
+            memory_events_per_row,
+            allowed_log2_heights,
+        });
+    }
+
     fn get_precompile_shapes(
         &self,
         air: &MipsAir<F>,
         memory_events_per_row: usize,
         allowed_log2_height: usize,
     ) -> Vec<[(String, usize); 4]> {
-        // TODO: This is a temporary fix to the shape, concretely fix this
-        (1..=4 * air.rows_per_event())
-            .rev()
-            .map(|rows_per_event| {
-                let num_local_mem_events =
-                    ((1 << allowed_log2_height) * memory_events_per_row).div_ceil(rows_per_event);
-                [
-                    (air.name(), allowed_log2_height),
-                    (
-                        MipsAir::<F>::SyscallPrecompile(SyscallChip::precompile()).name(),
-                        ((1 << allowed_log2_height)
-                            .div_ceil(&air.rows_per_event())
-                            .next_power_of_two()
-                            .ilog2() as usize)
-                            .max(4),
-                    ),
-                    (
-                        MipsAir::<F>::MemoryLocal(MemoryLocalChip::new()).name(),
-                        (num_local_mem_events
-                            .div_ceil(NUM_LOCAL_MEMORY_ENTRIES_PER_ROW)
-                            .next_power_of_two()
-                            .ilog2() as usize)
-                            .max(4),
-                    ),
-                    (
-                        MipsAir::<F>::Global(GlobalChip).name(),
-                        ((2 * num_local_mem_events
-                            + (1 << allowed_log2_height).div_ceil(&air.rows_per_event()))
-                        .next_power_of_two()
-                        .ilog2() as usize)
-                            .max(4),
-                    ),
-                ]
-            })
-            .filter(|shape| shape[3].1 <= 22)
-            .collect::<Vec<_>>()
+        precompile_shapes(
+            air.name(),
+            air.rows_per_event(),
+            memory_events_per_row,
+            allowed_log2_height,
+        )
     }
 
     fn generate_all_shapes_from_allowed_log_heights(
@@ -316,6 +377,14 @@ impl<F: PrimeField32> CoreShapeConfig<F> {
                 })
             },
         );
+        let custom_precompile_only_shapes =
+            self.custom_precompile_shapes.iter().flat_map(|custom| {
+                custom
+                    .allowed_log2_heights
+                    .iter()
+                    .flat_map(move |allowed_log_height| custom.shapes(*allowed_log_height))
+            });
+        let precompile_only_shapes = precompile_only_shapes.chain(custom_precompile_only_shapes);
 
         let precompile_shapes =
             Self::generate_all_shapes_from_allowed_log_heights(preprocessed_heights.clone())
@@ -508,6 +577,7 @@ impl<F: PrimeField32> Default for CoreShapeConfig<F> {
             partial_core_shapes: core_allowed_log2_heights,
             partial_memory_shapes: ShapeCluster::new(memory_allowed_log2_heights),
             partial_precompile_shapes: precompile_allowed_log2_heights,
+            custom_precompile_shapes: Vec::new(),
             partial_small_shapes: small_shapes
                 .into_iter()
                 .map(|x| {
@@ -594,6 +664,12 @@ fn derive_cluster_from_maximal_shape(shape: &Shape<MipsAirId>) -> ShapeCluster<M
     let global_log_height = shape.log2_height(&MipsAirId::Global);
     maybe_log2_heights.insert(MipsAirId::Global, heuristic(global_log_height, 1));
 
+    let fpu_arith_log_height = shape.log2_height(&MipsAirId::FpuArith);
+    maybe_log2_heights.insert(MipsAirId::FpuArith, heuristic(fpu_arith_log_height, 0));
+
+    let fpu_misc_log_height = shape.log2_height(&MipsAirId::FpuMisc);
+    maybe_log2_heights.insert(MipsAirId::FpuMisc, heuristic(fpu_misc_log_height, 0));
+
     assert!(maybe_log2_heights.len() >= shape.len(), "not all chips were included in the shape");
 
     ShapeCluster::new(maybe_log2_heights)
@@ -615,6 +691,55 @@ pub enum CoreShapeError {
     PrecompileNotIncluded(HashMap<String, usize>),
 }
 
+/// The shapes of a precompile shard whose precompile chip has the given log height.
+///
+/// Besides the precompile chip, a precompile shard has the precompile syscall table, the local
+/// memory table and the global table, sized for a precompile chip with `rows_per_event` rows
+/// per event and `memory_events_per_row` local memory events per row.
+pub fn precompile_shapes(
+    name: String,
+    rows_per_event: usize,
+    memory_events_per_row: usize,
+    allowed_log2_height: usize,
+) -> Vec<[(String, usize); 4]> {
+    // TODO: This is a temporary fix to the shape, concretely fix this
+    (1..=4 * rows_per_event)
+        .rev()
+        .map(|rows_per_event_bound| {
+            let num_local_mem_events =
+                ((1 << allowed_log2_height) * memory_events_per_row).div_ceil(rows_per_event_bound);
+            [
+                (name.clone(), allowed_log2_height),
+                (
+                    MipsAirId::SyscallPrecompile.to_string(),
+                    ((1 << allowed_log2_height)
+                        .div_ceil(&rows_per_event)
+                        .next_power_of_two()
+                        .ilog2() as usize)
+                        .max(4),
+                ),
+                (
+                    MipsAirId::MemoryLocal.to_string(),
+                    (num_local_mem_events
+                        .div_ceil(NUM_LOCAL_MEMORY_ENTRIES_PER_ROW)
+                        .next_power_of_two()
+                        .ilog2() as usize)
+                        .max(4),
+                ),
+                (
+                    MipsAirId::Global.to_string(),
+                    ((2 * num_local_mem_events
+                        + (1 << allowed_log2_height).div_ceil(&rows_per_event))
+                    .next_power_of_two()
+                    .ilog2() as usize)
+                        .max(4),
+                ),
+            ]
+        })
+        .filter(|shape| shape[3].1 <= 22)
+        .collect::<Vec<_>>()
+}
+
 #[cfg(test)]
 pub mod tests {
     use std::sync::Arc;
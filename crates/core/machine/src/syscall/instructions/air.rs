@@ -70,6 +70,7 @@ where
             AB::Expr::one(),
             local.is_halt,
             is_sequential,
+            AB::Expr::zero(),
             local.is_real,
         );
 
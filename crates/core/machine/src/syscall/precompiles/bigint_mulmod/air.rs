@@ -0,0 +1,616 @@
+use crate::{
+    air::MemoryAirBuilder,
+    memory::{MemoryCols, MemoryReadCols, MemoryWriteCols},
+    operations::field::field_op::FieldOpCols,
+    utils::{next_power_of_two, zeroed_f_vec},
+    CoreChipError,
+};
+
+use num::{BigUint, One, Zero};
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::{FieldAlgebra, PrimeField32};
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use p3_maybe_rayon::prelude::{IntoParallelRefIterator, ParallelBridge, ParallelIterator};
+use std::{
+    borrow::{Borrow, BorrowMut},
+    mem::size_of,
+};
+use zkm_core_executor::{
+    events::{
+        BigIntMulModEvent, ByteLookupEvent, ByteRecord, PrecompileEvent, BIGINT_DIGIT_WORDS,
+        BIGINT_MAX_DIGITS,
+    },
+    syscalls::SyscallCode,
+    ExecutionRecord, Program, Register,
+};
+use zkm_curves::{
+    params::{FieldParameters, Limbs, NumLimbs},
+    uint256::U256Field,
+};
+use zkm_derive::AlignedBorrow;
+use zkm_primitives::consts::words_to_bytes_le_vec;
+use zkm_stark::air::{LookupScope, MachineAir, Polynomial, ZKMAirBuilder};
+
+/// The number of columns in the BigIntMulModCols.
+pub const NUM_BIGINT_MULMOD_COLS: usize = size_of::<BigIntMulModCols<u8>>();
+
+/// The number of bytes in a 256-bit digit.
+const DIGIT_BYTES: usize = 32;
+
+const NUM_DIGITS_REGISTER: u32 = Register::A2 as u32;
+
+type Digit<T> = Limbs<T, <U256Field as NumLimbs>::Limbs>;
+
+#[derive(Default)]
+pub struct BigIntMulModChip;
+
+impl BigIntMulModChip {
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// The number of rows used by a single modular multiplication, one per digit.
+    pub const fn rows_per_event() -> usize {
+        BIGINT_MAX_DIGITS
+    }
+}
+
+/// A set of columns for the variable-width modular multiplication `x * y % modulus`.
+///
+/// The operands are made of up to `BIGINT_MAX_DIGITS` digits of 256 bits, and an event spans one
+/// row per digit. The prover supplies the quotient `q` and the result `r`, and the rows check that
+/// `x * y == q * m + r` by accumulating both products one digit of `y` and `q` at a time. Each
+/// row emits the lowest digit of both running sums, which must agree, and shifts the rest down.
+/// Alongside, the rows run the subtraction `m - r - 1` digit by digit to check that `r < m`.
+///
+/// Digits past the width of the operands are not read from memory and are constrained to zero.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct BigIntMulModCols<T> {
+    /// The shard number of the syscall.
+    pub shard: T,
+
+    /// The clock cycle of the syscall.
+    pub clk: T,
+
+    /// The pointer to the first input, which is overwritten with the result.
+    pub x_ptr: T,
+
+    /// The pointer to the second input, which contains the y value followed by the modulus.
+    pub y_ptr: T,
+
+    /// The number of digits of the operands.
+    pub num_digits: T,
+
+    /// One-hot selector of the digit processed by this row.
+    pub digit_selector: [T; BIGINT_MAX_DIGITS],
+
+    /// The byte offset of the digit processed by this row.
+    pub digit_offset: T,
+
+    /// Whether the digit processed by this row is part of the operands.
+    pub is_active: T,
+
+    /// The number of active rows of the event up to this one.
+    pub active_count: T,
+
+    pub is_real: T,
+    pub is_first_real: T,
+    pub is_last_real: T,
+
+    /// `is_real * is_active`, the multiplicity of the memory accesses of this row.
+    pub is_memory: T,
+
+    // Memory columns.
+    pub num_digits_memory: MemoryReadCols<T>,
+    pub x_memory: [MemoryWriteCols<T>; BIGINT_DIGIT_WORDS],
+    pub y_memory: [MemoryReadCols<T>; BIGINT_DIGIT_WORDS],
+    pub modulus_memory: [MemoryReadCols<T>; BIGINT_DIGIT_WORDS],
+
+    /// The digits of x, the modulus and the result, copied across the rows of an event.
+    pub x: [Digit<T>; BIGINT_MAX_DIGITS],
+    pub modulus: [Digit<T>; BIGINT_MAX_DIGITS],
+    pub result: [Digit<T>; BIGINT_MAX_DIGITS],
+
+    /// The digits of y, the quotient, the modulus and the result processed by this row.
+    pub y_digit: Digit<T>,
+    pub quotient_digit: Digit<T>,
+    pub modulus_digit: Digit<T>,
+    pub result_digit: Digit<T>,
+
+    /// The running sums of `x * y` and `q * m + r`, shifted down to the digit of this row.
+    pub xy_acc: [Digit<T>; BIGINT_MAX_DIGITS],
+    pub qm_acc: [Digit<T>; BIGINT_MAX_DIGITS],
+
+    /// The multiply-accumulate steps `xy_acc + x * y_digit` and `qm_acc + m * q_digit`.
+    pub xy: [FieldOpCols<T, U256Field>; BIGINT_MAX_DIGITS],
+    pub qm: [FieldOpCols<T, U256Field>; BIGINT_MAX_DIGITS],
+
+    /// The digit of `m - r - 1`, and the step `r_digit + lt_diff + lt_carry_in = m_digit`.
+    pub lt_diff: Digit<T>,
+    pub lt_carry_in: T,
+    pub lt: FieldOpCols<T, U256Field>,
+}
+
+/// Splits `value` into its `BIGINT_MAX_DIGITS` digits.
+fn to_digits(value: &BigUint) -> Vec<BigUint> {
+    let mask = (BigUint::one() << (8 * DIGIT_BYTES)) - BigUint::one();
+    (0..BIGINT_MAX_DIGITS).map(|j| (value >> (8 * DIGIT_BYTES * j)) & &mask).collect()
+}
+
+impl BigIntMulModChip {
+    /// Populates the rows of a modular multiplication event, or padding rows if there is none.
+    fn populate_rows<F: PrimeField32>(
+        event: Option<&BigIntMulModEvent>,
+        rows: &mut [F],
+        blu_events: &mut Vec<ByteLookupEvent>,
+    ) {
+        let is_real = event.is_some();
+        let (x, y, modulus, num_digits) = match event {
+            Some(event) => (
+                BigUint::from_bytes_le(&words_to_bytes_le_vec(&event.x)),
+                BigUint::from_bytes_le(&words_to_bytes_le_vec(&event.y)),
+                BigUint::from_bytes_le(&words_to_bytes_le_vec(&event.modulus)),
+                event.num_digits as usize,
+            ),
+            None => (BigUint::zero(), BigUint::zero(), BigUint::zero(), 0),
+        };
+        let (quotient, result) = if is_real {
+            let product = &x * &y;
+            (&product / &modulus, &product % &modulus)
+        } else {
+            (BigUint::zero(), BigUint::zero())
+        };
+
+        let x_digits = to_digits(&x);
+        let y_digits = to_digits(&y);
+        let modulus_digits = to_digits(&modulus);
+        let quotient_digits = to_digits(&quotient);
+        let result_digits = to_digits(&result);
+
+        let digit_modulus = BigUint::one() << (8 * DIGIT_BYTES);
+        let to_limbs = |digit: &BigUint| U256Field::to_limbs_field::<F, _>(digit);
+
+        let mut xy_acc = vec![BigUint::zero(); BIGINT_MAX_DIGITS];
+        let mut qm_acc = result_digits.clone();
+        let mut lt_carry = BigUint::from(is_real as u32);
+
+        for (index, row) in rows.chunks_mut(NUM_BIGINT_MULMOD_COLS).enumerate() {
+            let cols: &mut BigIntMulModCols<F> = row.borrow_mut();
+            let is_active = index < num_digits;
+
+            cols.is_real = F::from_bool(is_real);
+            cols.digit_selector[index] = F::ONE;
+            cols.digit_offset = F::from_canonical_usize(index * DIGIT_BYTES);
+            cols.is_active = F::from_bool(is_active);
+            cols.active_count = F::from_canonical_usize(num_digits.min(index + 1));
+            cols.is_first_real = F::from_bool(is_real && index == 0);
+            cols.is_last_real = F::from_bool(is_real && index == BIGINT_MAX_DIGITS - 1);
+            cols.is_memory = F::from_bool(is_real && is_active);
+
+            if let Some(event) = event {
+                cols.shard = F::from_canonical_u32(event.shard);
+                cols.clk = F::from_canonical_u32(event.clk);
+                cols.x_ptr = F::from_canonical_u32(event.x_ptr);
+                cols.y_ptr = F::from_canonical_u32(event.y_ptr);
+                cols.num_digits = F::from_canonical_u32(event.num_digits);
+
+                if index == 0 {
+                    cols.num_digits_memory.populate(event.num_digits_memory, blu_events);
+                }
+                if is_active {
+                    for i in 0..BIGINT_DIGIT_WORDS {
+                        let word = index * BIGINT_DIGIT_WORDS + i;
+                        cols.x_memory[i].populate(event.x_memory_records[word], blu_events);
+                        cols.y_memory[i].populate(event.y_memory_records[word], blu_events);
+                        cols.modulus_memory[i]
+                            .populate(event.modulus_memory_records[word], blu_events);
+                    }
+                }
+            }
+
+            for i in 0..BIGINT_MAX_DIGITS {
+                cols.x[i] = to_limbs(&x_digits[i]);
+                cols.modulus[i] = to_limbs(&modulus_digits[i]);
+                cols.result[i] = to_limbs(&result_digits[i]);
+                cols.xy_acc[i] = to_limbs(&xy_acc[i]);
+                cols.qm_acc[i] = to_limbs(&qm_acc[i]);
+            }
+            cols.y_digit = to_limbs(&y_digits[index]);
+            cols.quotient_digit = to_limbs(&quotient_digits[index]);
+            cols.modulus_digit = to_limbs(&modulus_digits[index]);
+            cols.result_digit = to_limbs(&result_digits[index]);
+            if is_real {
+                blu_events.add_u8_range_checks_field(&cols.quotient_digit.0);
+                blu_events.add_u8_range_checks_field(&cols.result_digit.0);
+            }
+
+            // Multiply-accumulate the digit of y into x * y, and the digit of q into q * m + r.
+            let mut xy_carry = BigUint::zero();
+            let mut qm_carry = BigUint::zero();
+            let mut next_xy_acc = Vec::with_capacity(BIGINT_MAX_DIGITS);
+            let mut next_qm_acc = Vec::with_capacity(BIGINT_MAX_DIGITS);
+            for i in 0..BIGINT_MAX_DIGITS {
+                let (xy_result, carry) = cols.xy[i].populate_mul_add_and_carry(
+                    blu_events,
+                    &x_digits[i],
+                    &y_digits[index],
+                    &xy_acc[i],
+                    &xy_carry,
+                    &digit_modulus,
+                );
+                next_xy_acc.push(xy_result);
+                xy_carry = carry;
+
+                let (qm_result, carry) = cols.qm[i].populate_mul_add_and_carry(
+                    blu_events,
+                    &modulus_digits[i],
+                    &quotient_digits[index],
+                    &qm_acc[i],
+                    &qm_carry,
+                    &digit_modulus,
+                );
+                next_qm_acc.push(qm_result);
+                qm_carry = carry;
+            }
+            debug_assert_eq!(next_xy_acc[0], next_qm_acc[0]);
+            next_xy_acc.push(xy_carry);
+            next_qm_acc.push(qm_carry);
+            xy_acc = next_xy_acc.split_off(1);
+            qm_acc = next_qm_acc.split_off(1);
+
+            // Subtract the digit of r and the borrow from the digit of m.
+            let lt_diff =
+                (&modulus_digits[index] + &digit_modulus - &result_digits[index] - &lt_carry)
+                    % &digit_modulus;
+            cols.lt_diff = to_limbs(&lt_diff);
+            cols.lt_carry_in = F::from_canonical_u32(if lt_carry.is_zero() { 0 } else { 1 });
+            if is_real {
+                blu_events.add_u8_range_checks_field(&cols.lt_diff.0);
+            }
+            let (lt_result, carry) = cols.lt.populate_mul_add_and_carry(
+                blu_events,
+                &lt_diff,
+                &BigUint::one(),
+                &result_digits[index],
+                &lt_carry,
+                &digit_modulus,
+            );
+            debug_assert_eq!(lt_result, modulus_digits[index]);
+            lt_carry = carry;
+        }
+        debug_assert!(!is_real || lt_carry.is_zero());
+    }
+}
+
+impl<F: PrimeField32> MachineAir<F> for BigIntMulModChip {
+    type Record = ExecutionRecord;
+    type Program = Program;
+    type Error = CoreChipError;
+
+    fn name(&self) -> String {
+        "BigIntMulMod".to_string()
+    }
+
+    fn generate_dependencies(
+        &self,
+        input: &Self::Record,
+        output: &mut Self::Record,
+    ) -> Result<(), Self::Error> {
+        let events = input.get_precompile_events(SyscallCode::BIGINT_MULMOD);
+
+        let blu_events: Vec<Vec<ByteLookupEvent>> = events
+            .par_iter()
+            .map(|(_, event)| {
+                let event = if let PrecompileEvent::BigIntMulMod(event) = event {
+                    event
+                } else {
+                    unreachable!()
+                };
+                let mut blu = Vec::new();
+                let mut rows = zeroed_f_vec::<F>(Self::rows_per_event() * NUM_BIGINT_MULMOD_COLS);
+                Self::populate_rows(Some(event), &mut rows, &mut blu);
+                blu
+            })
+            .collect();
+
+        for blu in blu_events {
+            output.add_byte_lookup_events(blu);
+        }
+        Ok(())
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        _: &mut ExecutionRecord,
+    ) -> Result<RowMajorMatrix<F>, Self::Error> {
+        let events = input.get_precompile_events(SyscallCode::BIGINT_MULMOD);
+
+        let rows_per_event = Self::rows_per_event();
+        let num_rows =
+            next_power_of_two(events.len() * rows_per_event, input.fixed_log2_rows::<F, _>(self));
+        let mut values = zeroed_f_vec(num_rows * NUM_BIGINT_MULMOD_COLS);
+
+        // Padding rows keep cycling through the digit selectors, so that the transition
+        // constraints hold across the whole trace.
+        values
+            .chunks_mut(rows_per_event * NUM_BIGINT_MULMOD_COLS)
+            .enumerate()
+            .par_bridge()
+            .for_each(|(i, rows)| {
+                let event = events.get(i).map(|(_, event)| {
+                    if let PrecompileEvent::BigIntMulMod(event) = event {
+                        event
+                    } else {
+                        unreachable!()
+                    }
+                });
+                Self::populate_rows(event, rows, &mut Vec::new());
+            });
+
+        Ok(RowMajorMatrix::new(values, NUM_BIGINT_MULMOD_COLS))
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        if let Some(shape) = shard.shape.as_ref() {
+            shape.included::<F, _>(self)
+        } else {
+            !shard.get_precompile_events(SyscallCode::BIGINT_MULMOD).is_empty()
+        }
+    }
+
+    fn local_only(&self) -> bool {
+        true
+    }
+}
+
+impl<F> BaseAir<F> for BigIntMulModChip {
+    fn width(&self) -> usize {
+        NUM_BIGINT_MULMOD_COLS
+    }
+}
+
+impl<AB> Air<AB> for BigIntMulModChip
+where
+    AB: ZKMAirBuilder,
+    Limbs<AB::Var, <U256Field as NumLimbs>::Limbs>: Copy,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &BigIntMulModCols<AB::Var> = (*local).borrow();
+        let next: &BigIntMulModCols<AB::Var> = (*next).borrow();
+
+        let is_first = local.digit_selector[0];
+        let is_last = local.digit_selector[BIGINT_MAX_DIGITS - 1];
+
+        // The selectors start at the first digit and cycle through the digits.
+        builder.when_first_row().assert_one(is_first);
+        for i in 1..BIGINT_MAX_DIGITS {
+            builder.when_first_row().assert_zero(local.digit_selector[i]);
+        }
+        for i in 0..BIGINT_MAX_DIGITS {
+            builder.when_transition().assert_eq(
+                next.digit_selector[(i + 1) % BIGINT_MAX_DIGITS],
+                local.digit_selector[i],
+            );
+        }
+        builder.when(is_first).assert_zero(local.digit_offset);
+        builder.when_transition().when_not(is_last).assert_eq(
+            next.digit_offset,
+            local.digit_offset + AB::F::from_canonical_usize(DIGIT_BYTES),
+        );
+
+        // An event is made of consecutive real rows, and the trace cannot end in the middle of it.
+        builder.assert_bool(local.is_real);
+        builder.assert_eq(local.is_first_real, is_first * local.is_real);
+        builder.assert_eq(local.is_last_real, is_last * local.is_real);
+        builder.when_transition().when_not(is_last).assert_eq(next.is_real, local.is_real);
+        builder.when_last_row().when(local.is_real).assert_one(is_last);
+
+        // Copy the inputs across the rows of an event.
+        {
+            let mut when_continue = builder.when_transition();
+            let mut when_continue = when_continue.when(local.is_real);
+            let mut when_continue = when_continue.when_not(is_last);
+            when_continue.assert_eq(next.shard, local.shard);
+            when_continue.assert_eq(next.clk, local.clk);
+            when_continue.assert_eq(next.x_ptr, local.x_ptr);
+            when_continue.assert_eq(next.y_ptr, local.y_ptr);
+            when_continue.assert_eq(next.num_digits, local.num_digits);
+            for i in 0..BIGINT_MAX_DIGITS {
+                when_continue.assert_all_eq(next.x[i], local.x[i]);
+                when_continue.assert_all_eq(next.modulus[i], local.modulus[i]);
+                when_continue.assert_all_eq(next.result[i], local.result[i]);
+            }
+        }
+
+        // The active rows are the first `num_digits` rows of the event.
+        builder.assert_bool(local.is_active);
+        builder.assert_eq(local.is_memory, local.is_real * local.is_active);
+        builder.when(local.is_first_real).assert_one(local.is_active);
+        builder.when(is_first).assert_eq(local.active_count, local.is_active);
+        {
+            let mut when_continue = builder.when_transition();
+            let mut when_continue = when_continue.when(local.is_real);
+            let mut when_continue = when_continue.when_not(is_last);
+            when_continue.assert_zero(next.is_active * (AB::Expr::one() - local.is_active));
+            when_continue.assert_eq(next.active_count, local.active_count + next.is_active);
+        }
+        builder.when(local.is_last_real).assert_eq(local.active_count, local.num_digits);
+
+        // Read the number of digits on the first row.
+        builder.eval_memory_access(
+            local.shard,
+            local.clk,
+            AB::Expr::from_canonical_u32(NUM_DIGITS_REGISTER),
+            &local.num_digits_memory,
+            local.is_first_real,
+        );
+        builder
+            .when(local.is_first_real)
+            .assert_eq(local.num_digits, local.num_digits_memory.value().reduce::<AB>());
+
+        // Read the digits of y and the modulus, and write the digit of the result over x. The
+        // modulus is stored right after the `num_digits` digits of y.
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.clk.into() + AB::Expr::one(),
+            local.x_ptr + local.digit_offset,
+            &local.x_memory,
+            local.is_memory,
+        );
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.clk,
+            local.y_ptr + local.digit_offset,
+            &local.y_memory,
+            local.is_memory,
+        );
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.clk,
+            local.y_ptr
+                + local.num_digits * AB::F::from_canonical_usize(DIGIT_BYTES)
+                + local.digit_offset,
+            &local.modulus_memory,
+            local.is_memory,
+        );
+
+        // Select the digits of this row. Digits which are not read from memory are zero.
+        for l in 0..DIGIT_BYTES {
+            let (word, byte) = (l / 4, l % 4);
+            let mut x_digit = AB::Expr::zero();
+            let mut modulus_digit = AB::Expr::zero();
+            let mut result_digit = AB::Expr::zero();
+            for i in 0..BIGINT_MAX_DIGITS {
+                x_digit = x_digit + local.digit_selector[i] * local.x[i][l];
+                modulus_digit = modulus_digit + local.digit_selector[i] * local.modulus[i][l];
+                result_digit = result_digit + local.digit_selector[i] * local.result[i][l];
+            }
+            let mut when_real = builder.when(local.is_real);
+            when_real.assert_eq(x_digit, local.x_memory[word].prev_value()[byte] * local.is_active);
+            when_real.assert_eq(local.modulus_digit[l], modulus_digit);
+            when_real.assert_eq(
+                local.modulus_digit[l],
+                local.modulus_memory[word].value()[byte] * local.is_active,
+            );
+            when_real.assert_eq(local.result_digit[l], result_digit);
+            when_real.assert_eq(
+                local.result_digit[l],
+                local.x_memory[word].value()[byte] * local.is_active,
+            );
+            when_real
+                .assert_eq(local.y_digit[l], local.y_memory[word].value()[byte] * local.is_active);
+        }
+        builder.slice_range_check_u8(&local.quotient_digit.0, local.is_real);
+        builder.slice_range_check_u8(&local.result_digit.0, local.is_real);
+        builder.slice_range_check_u8(&local.lt_diff.0, local.is_real);
+
+        let mut coeff_2_256 = Vec::new();
+        coeff_2_256.resize(DIGIT_BYTES, AB::Expr::zero());
+        coeff_2_256.push(AB::Expr::one());
+        let modulus_polynomial: Polynomial<AB::Expr> = Polynomial::from_coefficients(&coeff_2_256);
+
+        // Multiply-accumulate the digit of y into x * y, and the digit of q into q * m + r.
+        for i in 0..BIGINT_MAX_DIGITS {
+            let (xy_addend, qm_addend): (Polynomial<AB::Expr>, Polynomial<AB::Expr>) = if i == 0 {
+                (local.xy_acc[0].into(), local.qm_acc[0].into())
+            } else {
+                (
+                    Polynomial::<AB::Expr>::from(local.xy_acc[i])
+                        + Polynomial::from(local.xy[i - 1].carry),
+                    Polynomial::<AB::Expr>::from(local.qm_acc[i])
+                        + Polynomial::from(local.qm[i - 1].carry),
+                )
+            };
+            local.xy[i].eval_mul_and_carry(
+                builder,
+                &local.x[i],
+                &local.y_digit,
+                &xy_addend,
+                &modulus_polynomial,
+                local.is_real,
+            );
+            local.qm[i].eval_mul_and_carry(
+                builder,
+                &local.modulus[i],
+                &local.quotient_digit,
+                &qm_addend,
+                &modulus_polynomial,
+                local.is_real,
+            );
+        }
+
+        // The digit emitted by both sums must agree.
+        builder.when(local.is_real).assert_all_eq(local.xy[0].result, local.qm[0].result);
+
+        // The sums start from zero and from the result, and are shifted down by one digit on
+        // each row.
+        for i in 0..BIGINT_MAX_DIGITS {
+            builder.when(local.is_first_real).assert_all_zero(local.xy_acc[i]);
+            builder.when(local.is_first_real).assert_all_eq(local.qm_acc[i], local.result[i]);
+        }
+        {
+            let mut when_continue = builder.when_transition();
+            let mut when_continue = when_continue.when(local.is_real);
+            let mut when_continue = when_continue.when_not(is_last);
+            for i in 0..BIGINT_MAX_DIGITS - 1 {
+                when_continue.assert_all_eq(next.xy_acc[i], local.xy[i + 1].result);
+                when_continue.assert_all_eq(next.qm_acc[i], local.qm[i + 1].result);
+            }
+            when_continue.assert_all_eq(
+                next.xy_acc[BIGINT_MAX_DIGITS - 1],
+                local.xy[BIGINT_MAX_DIGITS - 1].carry,
+            );
+            when_continue.assert_all_eq(
+                next.qm_acc[BIGINT_MAX_DIGITS - 1],
+                local.qm[BIGINT_MAX_DIGITS - 1].carry,
+            );
+        }
+
+        // The upper digits of both sums left after the last row must agree as well.
+        for i in 0..BIGINT_MAX_DIGITS - 1 {
+            builder
+                .when(local.is_last_real)
+                .assert_all_eq(local.xy[i + 1].result, local.qm[i + 1].result);
+        }
+        builder.when(local.is_last_real).assert_all_eq(
+            local.xy[BIGINT_MAX_DIGITS - 1].carry,
+            local.qm[BIGINT_MAX_DIGITS - 1].carry,
+        );
+
+        // Check that `r < m` by computing `r + (m - r - 1) + 1 = m` without overflow.
+        let one = U256Field::to_limbs_field::<AB::Expr, _>(&BigUint::one());
+        let lt_carry_in: AB::Expr = local.lt_carry_in.into();
+        let lt_addend = Polynomial::<AB::Expr>::from(local.result_digit) + lt_carry_in;
+        local.lt.eval_mul_and_carry(
+            builder,
+            &local.lt_diff,
+            &one,
+            &lt_addend,
+            &modulus_polynomial,
+            local.is_real,
+        );
+        builder.when(local.is_real).assert_all_eq(local.lt.result, local.modulus_digit);
+        builder.when(local.is_first_real).assert_one(local.lt_carry_in);
+        builder
+            .when_transition()
+            .when(local.is_real)
+            .when_not(is_last)
+            .assert_eq(next.lt_carry_in, local.lt.carry[0]);
+        builder.when(local.is_last_real).assert_zero(local.lt.carry[0]);
+
+        // Receive the arguments.
+        builder.receive_syscall(
+            local.shard,
+            local.clk,
+            AB::F::from_canonical_u32(SyscallCode::BIGINT_MULMOD.syscall_id()),
+            local.x_ptr,
+            local.y_ptr,
+            local.is_first_real,
+            LookupScope::Local,
+        );
+    }
+}
@@ -0,0 +1,61 @@
+mod air;
+
+pub use air::*;
+
+#[cfg(test)]
+mod tests {
+    use zkm_core_executor::{
+        events::BIGINT_DIGIT_WORDS, syscalls::SyscallCode, Instruction, Opcode, Program, Register,
+    };
+    use zkm_stark::CpuProver;
+
+    use crate::utils::{run_test, setup_logger};
+
+    /// Deterministic operand words.
+    fn operand(num_words: usize, seed: u32) -> Vec<u32> {
+        (0..num_words as u32).map(|i| i.wrapping_mul(0x9e37_79b9).wrapping_add(seed)).collect()
+    }
+
+    /// Multiply `x` and `y` modulo `modulus` for each of the given widths in digits, keeping the
+    /// top word of `x` below the one of the modulus.
+    fn mulmod_program(widths: &[u32]) -> Program {
+        let x_ptr = 100;
+        let y_ptr = 1000;
+
+        let mut instructions = vec![];
+        for &num_digits in widths {
+            let num_words = num_digits as usize * BIGINT_DIGIT_WORDS;
+            let mut x = operand(num_words, 0x1234_5678);
+            let y = operand(num_words, 0xdead_beef);
+            let mut modulus = operand(num_words, 0x0bad_cafe);
+            x[num_words - 1] = 0x0fff_ffff;
+            modulus[num_words - 1] = 0xf000_0001;
+
+            let mut memory = vec![];
+            memory.extend(x.into_iter().enumerate().map(|(i, w)| (x_ptr, i, w)));
+            memory.extend(y.into_iter().chain(modulus).enumerate().map(|(i, w)| (y_ptr, i, w)));
+            for (ptr, i, word) in memory {
+                instructions.extend(vec![
+                    Instruction::new(Opcode::ADD, 29, 0, word, false, true),
+                    Instruction::new(Opcode::ADD, 30, 0, ptr + 4 * i as u32, false, true),
+                    Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+                ]);
+            }
+            instructions.extend(vec![
+                Instruction::new(Opcode::ADD, 2, 0, SyscallCode::BIGINT_MULMOD as u32, false, true),
+                Instruction::new(Opcode::ADD, 4, 0, x_ptr, false, true),
+                Instruction::new(Opcode::ADD, 5, 0, y_ptr, false, true),
+                Instruction::new(Opcode::ADD, Register::A2 as u8, 0, num_digits, false, true),
+                Instruction::new(Opcode::SYSCALL, 2, 4, 5, false, false),
+            ]);
+        }
+        Program::new(instructions, 0, 0)
+    }
+
+    #[test]
+    fn test_bigint_mulmod() {
+        setup_logger();
+        let program = mulmod_program(&[1, 8, 16]);
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+}
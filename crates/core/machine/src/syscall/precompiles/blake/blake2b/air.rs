@@ -0,0 +1,280 @@
+use core::borrow::Borrow;
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::FieldAlgebra;
+use p3_matrix::Matrix;
+use zkm_core_executor::syscalls::SyscallCode;
+use zkm_stark::{
+    air::{BaseAirBuilder, LookupScope, ZKMAirBuilder},
+    Word,
+};
+
+use super::{
+    columns::{Blake2bRoundsCols, NUM_BLAKE2B_ROUNDS_COLS},
+    Blake2bRoundsChip, NUM_BLAKE2B_ROUNDS,
+};
+use crate::{
+    air::{MemoryAirBuilder, WordAirBuilder},
+    memory::MemoryCols,
+    operations::{AddDoubleOperation, FixedRotateRightDoubleOperation, XorOperation},
+    syscall::precompiles::blake::{BLAKE2_SIGMA, G_INDICES},
+};
+
+impl<F> BaseAir<F> for Blake2bRoundsChip {
+    fn width(&self) -> usize {
+        NUM_BLAKE2B_ROUNDS_COLS
+    }
+}
+
+impl<AB> Air<AB> for Blake2bRoundsChip
+where
+    AB: ZKMAirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &Blake2bRoundsCols<AB::Var> = (*local).borrow();
+        let next: &Blake2bRoundsCols<AB::Var> = (*next).borrow();
+
+        self.eval_control_flow_flags(builder, local, next);
+
+        self.eval_message_schedule(builder, local);
+
+        let v_out = self.eval_round(builder, local);
+
+        // The output of a round is the input of the next one, and the output of the last round
+        // is the final working vector.
+        for i in 0..32 {
+            builder
+                .when_transition()
+                .when(local.is_real)
+                .when_not(local.is_last_round)
+                .assert_word_eq(v_out[i], next.v[i]);
+            builder.when(local.is_last_round).assert_word_eq(v_out[i], local.v_final[i]);
+        }
+
+        self.eval_memory(builder, local);
+
+        builder.receive_syscall(
+            local.shard,
+            local.clk,
+            AB::F::from_canonical_u32(SyscallCode::BLAKE2B_ROUNDS.syscall_id()),
+            local.v_ptr,
+            local.m_ptr,
+            local.is_first_round,
+            LookupScope::Local,
+        );
+    }
+}
+
+impl Blake2bRoundsChip {
+    fn eval_control_flow_flags<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Blake2bRoundsCols<AB::Var>,
+        next: &Blake2bRoundsCols<AB::Var>,
+    ) {
+        // Verify that the round columns are a one-hot encoding.
+        let mut round_sum = AB::Expr::zero();
+        for i in 0..NUM_BLAKE2B_ROUNDS {
+            builder.assert_bool(local.round[i]);
+            round_sum = round_sum.clone() + local.round[i].into();
+        }
+        builder.assert_one(round_sum);
+
+        // The first row is the first round, and the rounds cycle from there on, including in the
+        // padding rows.
+        builder.when_first_row().assert_one(local.round[0]);
+        for i in 0..NUM_BLAKE2B_ROUNDS {
+            builder
+                .when_transition()
+                .when(local.round[i])
+                .assert_one(next.round[(i + 1) % NUM_BLAKE2B_ROUNDS]);
+        }
+
+        builder.assert_bool(local.is_real);
+        builder.assert_eq(local.is_first_round, local.is_real * local.round[0]);
+        builder.assert_eq(local.is_last_round, local.is_real * local.round[NUM_BLAKE2B_ROUNDS - 1]);
+
+        // If this row is real and not the last round, then the next row belongs to the same
+        // syscall.
+        let mut when_same_syscall =
+            builder.when_transition().when(local.is_real).when_not(local.is_last_round);
+        when_same_syscall.assert_one(next.is_real);
+        when_same_syscall.assert_eq(local.shard, next.shard);
+        when_same_syscall.assert_eq(local.clk, next.clk);
+        when_same_syscall.assert_eq(local.v_ptr, next.v_ptr);
+        when_same_syscall.assert_eq(local.m_ptr, next.m_ptr);
+        for i in 0..32 {
+            when_same_syscall.assert_word_eq(local.m[i], next.m[i]);
+            when_same_syscall.assert_word_eq(local.v_final[i], next.v_final[i]);
+        }
+
+        // Once the is_real flag is changed to false, it should not be changed back.
+        builder.when_transition().when_not(local.is_real).assert_zero(next.is_real);
+
+        // The number of rounds is not a power of two, so the table always ends in padding.
+        builder.when_last_row().assert_zero(local.is_real);
+    }
+
+    /// Constrain the message double words of every G step to the ones selected by the round's
+    /// schedule.
+    fn eval_message_schedule<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Blake2bRoundsCols<AB::Var>,
+    ) {
+        for (i, g) in local.g.iter().enumerate() {
+            for (word, offset) in [(g.mx, 0), (g.mx_hi, 1), (g.my, 2), (g.my_hi, 3)] {
+                for byte in 0..4 {
+                    let selected = (0..NUM_BLAKE2B_ROUNDS).fold(AB::Expr::zero(), |acc, round| {
+                        let schedule = BLAKE2_SIGMA[round % BLAKE2_SIGMA.len()];
+                        let m = 2 * schedule[2 * i + offset / 2] + offset % 2;
+                        acc + local.round[round] * local.m[m][byte]
+                    });
+                    builder.when(local.is_real).assert_eq(word[byte], selected);
+                }
+            }
+        }
+    }
+
+    /// Evaluate the eight G steps of the round and return the resulting working vector.
+    fn eval_round<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &Blake2bRoundsCols<AB::Var>,
+    ) -> [Word<AB::Var>; 32] {
+        let mut v = local.v;
+        for (g, indices) in local.g.iter().zip(G_INDICES) {
+            let [a, a_hi, b, b_hi, c, c_hi, d, d_hi]: [usize; 8] =
+                core::array::from_fn(|i| 2 * indices[i / 2] + i % 2);
+
+            AddDoubleOperation::<AB::F>::eval(
+                builder,
+                v[a],
+                v[a_hi],
+                v[b],
+                v[b_hi],
+                g.a_plus_b,
+                local.is_real.into(),
+            );
+            AddDoubleOperation::<AB::F>::eval(
+                builder,
+                g.a_plus_b.value,
+                g.a_plus_b.value_hi,
+                g.mx,
+                g.mx_hi,
+                g.a_1,
+                local.is_real.into(),
+            );
+            (v[a], v[a_hi]) = (g.a_1.value, g.a_1.value_hi);
+
+            XorOperation::<AB::F>::eval(builder, v[d], v[a], g.d_xor_a, local.is_real);
+            XorOperation::<AB::F>::eval(builder, v[d_hi], v[a_hi], g.d_xor_a_hi, local.is_real);
+            (v[d], v[d_hi]) = rotate_bytes(g.d_xor_a.value, g.d_xor_a_hi.value, 4);
+
+            AddDoubleOperation::<AB::F>::eval(
+                builder,
+                v[c],
+                v[c_hi],
+                v[d],
+                v[d_hi],
+                g.c_1,
+                local.is_real.into(),
+            );
+            (v[c], v[c_hi]) = (g.c_1.value, g.c_1.value_hi);
+
+            XorOperation::<AB::F>::eval(builder, v[b], v[c], g.b_xor_c, local.is_real);
+            XorOperation::<AB::F>::eval(builder, v[b_hi], v[c_hi], g.b_xor_c_hi, local.is_real);
+            (v[b], v[b_hi]) = rotate_bytes(g.b_xor_c.value, g.b_xor_c_hi.value, 3);
+
+            AddDoubleOperation::<AB::F>::eval(
+                builder,
+                v[a],
+                v[a_hi],
+                v[b],
+                v[b_hi],
+                g.a_plus_b_2,
+                local.is_real.into(),
+            );
+            AddDoubleOperation::<AB::F>::eval(
+                builder,
+                g.a_plus_b_2.value,
+                g.a_plus_b_2.value_hi,
+                g.my,
+                g.my_hi,
+                g.a_2,
+                local.is_real.into(),
+            );
+            (v[a], v[a_hi]) = (g.a_2.value, g.a_2.value_hi);
+
+            XorOperation::<AB::F>::eval(builder, v[d], v[a], g.d_xor_a_2, local.is_real);
+            XorOperation::<AB::F>::eval(builder, v[d_hi], v[a_hi], g.d_xor_a_2_hi, local.is_real);
+            (v[d], v[d_hi]) = rotate_bytes(g.d_xor_a_2.value, g.d_xor_a_2_hi.value, 2);
+
+            AddDoubleOperation::<AB::F>::eval(
+                builder,
+                v[c],
+                v[c_hi],
+                v[d],
+                v[d_hi],
+                g.c_2,
+                local.is_real.into(),
+            );
+            (v[c], v[c_hi]) = (g.c_2.value, g.c_2.value_hi);
+
+            XorOperation::<AB::F>::eval(builder, v[b], v[c], g.b_xor_c_2, local.is_real);
+            XorOperation::<AB::F>::eval(builder, v[b_hi], v[c_hi], g.b_xor_c_2_hi, local.is_real);
+            FixedRotateRightDoubleOperation::<AB::F>::eval(
+                builder,
+                g.b_xor_c_2.value,
+                g.b_xor_c_2_hi.value,
+                63,
+                g.b_2,
+                local.is_real,
+            );
+            (v[b], v[b_hi]) = (g.b_2.value, g.b_2.value_hi);
+        }
+        v
+    }
+
+    /// On the first round, read the message block and overwrite the working vector with the
+    /// final one.
+    fn eval_memory<AB: ZKMAirBuilder>(&self, builder: &mut AB, local: &Blake2bRoundsCols<AB::Var>) {
+        for i in 0..32 {
+            builder
+                .when(local.is_first_round)
+                .assert_word_eq(*local.v_memory[i].prev_value(), local.v[i]);
+            builder
+                .when(local.is_first_round)
+                .assert_word_eq(*local.v_memory[i].value(), local.v_final[i]);
+            builder
+                .when(local.is_first_round)
+                .assert_word_eq(*local.m_memory[i].value(), local.m[i]);
+        }
+
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.clk.into(),
+            local.m_ptr,
+            &local.m_memory,
+            local.is_first_round,
+        );
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.clk.into() + AB::Expr::one(),
+            local.v_ptr,
+            &local.v_memory,
+            local.is_first_round,
+        );
+    }
+}
+
+/// Rotate a double word, given as its low and high words, right by a whole number of bytes.
+fn rotate_bytes<T: Copy>(lo: Word<T>, hi: Word<T>, nb_bytes: usize) -> (Word<T>, Word<T>) {
+    let bytes: [T; 8] = core::array::from_fn(|i| if i < 4 { lo[i] } else { hi[i - 4] });
+    (
+        Word(core::array::from_fn(|i| bytes[(i + nb_bytes) % 8])),
+        Word(core::array::from_fn(|i| bytes[(i + 4 + nb_bytes) % 8])),
+    )
+}
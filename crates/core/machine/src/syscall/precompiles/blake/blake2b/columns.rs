@@ -0,0 +1,87 @@
+use std::mem::size_of;
+
+use zkm_derive::AlignedBorrow;
+use zkm_stark::Word;
+
+use super::NUM_BLAKE2B_ROUNDS;
+use crate::{
+    memory::{MemoryReadCols, MemoryWriteCols},
+    operations::{AddDoubleOperation, FixedRotateRightDoubleOperation, XorOperation},
+};
+
+pub const NUM_BLAKE2B_ROUNDS_COLS: usize = size_of::<Blake2bRoundsCols<u8>>();
+
+/// The columns of one application of the G function on double words.
+///
+/// The rotations by 32, 24 and 16 are byte permutations and need no columns.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct G64Cols<T> {
+    /// The two message double words mixed in, selected by the schedule of the current round.
+    pub mx: Word<T>,
+    pub mx_hi: Word<T>,
+    pub my: Word<T>,
+    pub my_hi: Word<T>,
+
+    /// `a := a + b + mx`.
+    pub a_plus_b: AddDoubleOperation<T>,
+    pub a_1: AddDoubleOperation<T>,
+    /// `d := (d ^ a) >>> 32`.
+    pub d_xor_a: XorOperation<T>,
+    pub d_xor_a_hi: XorOperation<T>,
+    /// `c := c + d`.
+    pub c_1: AddDoubleOperation<T>,
+    /// `b := (b ^ c) >>> 24`.
+    pub b_xor_c: XorOperation<T>,
+    pub b_xor_c_hi: XorOperation<T>,
+
+    /// `a := a + b + my`.
+    pub a_plus_b_2: AddDoubleOperation<T>,
+    pub a_2: AddDoubleOperation<T>,
+    /// `d := (d ^ a) >>> 16`.
+    pub d_xor_a_2: XorOperation<T>,
+    pub d_xor_a_2_hi: XorOperation<T>,
+    /// `c := c + d`.
+    pub c_2: AddDoubleOperation<T>,
+    /// `b := (b ^ c) >>> 63`.
+    pub b_xor_c_2: XorOperation<T>,
+    pub b_xor_c_2_hi: XorOperation<T>,
+    pub b_2: FixedRotateRightDoubleOperation<T>,
+}
+
+/// A set of columns needed to compute the rounds of the BLAKE2b compression function.
+///
+/// Each syscall is processed over one row per round, in the same way as the BLAKE2s rounds. Double
+/// words are kept as pairs of words, low word first, matching their layout in memory.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Blake2bRoundsCols<T> {
+    /// Inputs.
+    pub shard: T,
+    pub clk: T,
+    pub v_ptr: T,
+    pub m_ptr: T,
+
+    /// Which round we are currently processing.
+    pub round: [T; NUM_BLAKE2B_ROUNDS],
+
+    pub is_real: T,
+    /// `is_real` on the first round, where memory is accessed and the syscall is received.
+    pub is_first_round: T,
+    /// `is_real` on the last round.
+    pub is_last_round: T,
+
+    /// The working vector is read and overwritten with the result.
+    pub v_memory: [MemoryWriteCols<T>; 32],
+    pub m_memory: [MemoryReadCols<T>; 32],
+
+    /// The message block.
+    pub m: [Word<T>; 32],
+    /// The working vector at the start of the current round.
+    pub v: [Word<T>; 32],
+    /// The working vector after the last round.
+    pub v_final: [Word<T>; 32],
+
+    /// The column steps followed by the diagonal steps of the round.
+    pub g: [G64Cols<T>; 8],
+}
@@ -0,0 +1,58 @@
+mod air;
+mod columns;
+mod trace;
+
+/// The number of rounds of BLAKE2b.
+pub const NUM_BLAKE2B_ROUNDS: usize = 12;
+
+/// Implements all the rounds of the BLAKE2b compression function on a 16-double-word working
+/// vector. The inputs to the syscall are a pointer to the working vector, which is overwritten
+/// with the result, and a pointer to the 16-double-word message block. Double words are stored as
+/// two little-endian words.
+///
+/// In the AIR, each syscall takes up one row per round, so 12 rows.
+#[derive(Default)]
+pub struct Blake2bRoundsChip;
+
+impl Blake2bRoundsChip {
+    pub const fn new() -> Self {
+        Self {}
+    }
+}
+
+#[cfg(test)]
+pub mod blake2b_tests {
+    use zkm_core_executor::{syscalls::SyscallCode, Instruction, Opcode, Program};
+    use zkm_stark::CpuProver;
+
+    use crate::utils::{run_test, setup_logger};
+
+    pub fn blake2b_rounds_program() -> Program {
+        let v_ptr = 100;
+        let m_ptr = 1000;
+        let mut instructions = vec![];
+        for i in 0..32 {
+            instructions.extend(vec![
+                Instruction::new(Opcode::ADD, 29, 0, 0x0101_0101 * (i + 1), false, true),
+                Instruction::new(Opcode::ADD, 30, 0, v_ptr + i * 4, false, true),
+                Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+                Instruction::new(Opcode::ADD, 30, 0, m_ptr + i * 4, false, true),
+                Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+            ]);
+        }
+        instructions.extend(vec![
+            Instruction::new(Opcode::ADD, 2, 0, SyscallCode::BLAKE2B_ROUNDS as u32, false, true),
+            Instruction::new(Opcode::ADD, 4, 0, v_ptr, false, true),
+            Instruction::new(Opcode::ADD, 5, 0, m_ptr, false, true),
+            Instruction::new(Opcode::SYSCALL, 2, 4, 5, false, false),
+        ]);
+        Program::new(instructions, 0, 0)
+    }
+
+    #[test]
+    fn prove_blake2b_koalabear() {
+        setup_logger();
+        let program = blake2b_rounds_program();
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+}
@@ -0,0 +1,214 @@
+use std::borrow::BorrowMut;
+
+use hashbrown::HashMap;
+use itertools::Itertools;
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_maybe_rayon::prelude::{ParallelIterator, ParallelSlice};
+use zkm_core_executor::{
+    events::{Blake2bRoundsEvent, ByteLookupEvent, ByteRecord, PrecompileEvent},
+    syscalls::SyscallCode,
+    ExecutionRecord, Program,
+};
+use zkm_stark::{air::MachineAir, Word};
+
+use super::{
+    columns::{Blake2bRoundsCols, G64Cols, NUM_BLAKE2B_ROUNDS_COLS},
+    Blake2bRoundsChip, NUM_BLAKE2B_ROUNDS,
+};
+use crate::{
+    operations::XorOperation,
+    syscall::precompiles::blake::{BLAKE2_SIGMA, G_INDICES},
+    utils::pad_rows_fixed,
+    CoreChipError,
+};
+
+impl<F: PrimeField32> MachineAir<F> for Blake2bRoundsChip {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    type Error = CoreChipError;
+
+    fn name(&self) -> String {
+        "Blake2bRounds".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        _: &mut ExecutionRecord,
+    ) -> Result<RowMajorMatrix<F>, Self::Error> {
+        let mut wrapped_rows = Some(Vec::new());
+        for (_, event) in input.get_precompile_events(SyscallCode::BLAKE2B_ROUNDS) {
+            let event = if let PrecompileEvent::Blake2bRounds(event) = event {
+                event
+            } else {
+                unreachable!()
+            };
+            self.event_to_rows(event, &mut wrapped_rows, &mut Vec::new());
+        }
+        let mut rows = wrapped_rows.unwrap();
+
+        let num_real_rows = rows.len();
+
+        pad_rows_fixed(
+            &mut rows,
+            || [F::ZERO; NUM_BLAKE2B_ROUNDS_COLS],
+            input.fixed_log2_rows::<F, _>(self),
+        );
+
+        // Keep cycling through the rounds in the padded rows.
+        for (i, row) in rows[num_real_rows..].iter_mut().enumerate() {
+            let cols: &mut Blake2bRoundsCols<F> = row.as_mut_slice().borrow_mut();
+            cols.round[i % NUM_BLAKE2B_ROUNDS] = F::ONE;
+        }
+
+        // Convert the trace to a row major matrix.
+        Ok(RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_BLAKE2B_ROUNDS_COLS,
+        ))
+    }
+
+    fn generate_dependencies(
+        &self,
+        input: &Self::Record,
+        output: &mut Self::Record,
+    ) -> Result<(), Self::Error> {
+        let events = input.get_precompile_events(SyscallCode::BLAKE2B_ROUNDS);
+        let chunk_size = std::cmp::max(events.len() / num_cpus::get(), 1);
+
+        let blu_batches = events
+            .par_chunks(chunk_size)
+            .map(|events| {
+                let mut blu: HashMap<ByteLookupEvent, usize> = HashMap::new();
+                events.iter().for_each(|(_, event)| {
+                    let event = if let PrecompileEvent::Blake2bRounds(event) = event {
+                        event
+                    } else {
+                        unreachable!()
+                    };
+                    self.event_to_rows::<F>(event, &mut None, &mut blu);
+                });
+                blu
+            })
+            .collect::<Vec<_>>();
+
+        output.add_byte_lookup_events_from_maps(blu_batches.iter().collect_vec());
+        Ok(())
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        if let Some(shape) = shard.shape.as_ref() {
+            shape.included::<F, _>(self)
+        } else {
+            !shard.get_precompile_events(SyscallCode::BLAKE2B_ROUNDS).is_empty()
+        }
+    }
+}
+
+impl Blake2bRoundsChip {
+    fn event_to_rows<F: PrimeField32>(
+        &self,
+        event: &Blake2bRoundsEvent,
+        rows: &mut Option<Vec<[F; NUM_BLAKE2B_ROUNDS_COLS]>>,
+        blu: &mut impl ByteRecord,
+    ) {
+        let v_final = event.v_memory_records.iter().map(|record| record.value).collect_vec();
+        let m: [u64; 16] = core::array::from_fn(|i| join(event.m[2 * i], event.m[2 * i + 1]));
+
+        let mut v: [u64; 16] = core::array::from_fn(|i| join(event.v[2 * i], event.v[2 * i + 1]));
+        for round in 0..NUM_BLAKE2B_ROUNDS {
+            let mut row = [F::ZERO; NUM_BLAKE2B_ROUNDS_COLS];
+            let cols: &mut Blake2bRoundsCols<F> = row.as_mut_slice().borrow_mut();
+
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.v_ptr = F::from_canonical_u32(event.v_ptr);
+            cols.m_ptr = F::from_canonical_u32(event.m_ptr);
+
+            cols.round[round] = F::ONE;
+            cols.is_real = F::ONE;
+            cols.is_first_round = F::from_bool(round == 0);
+            cols.is_last_round = F::from_bool(round == NUM_BLAKE2B_ROUNDS - 1);
+
+            // The memory is only accessed on the first round.
+            if round == 0 {
+                for i in 0..32 {
+                    cols.v_memory[i].populate(event.v_memory_records[i], blu);
+                    cols.m_memory[i].populate(event.m_memory_records[i], blu);
+                }
+            }
+
+            for i in 0..16 {
+                cols.m[2 * i] = Word::from(event.m[2 * i]);
+                cols.m[2 * i + 1] = Word::from(event.m[2 * i + 1]);
+                cols.v[2 * i] = Word::from(v[i] as u32);
+                cols.v[2 * i + 1] = Word::from((v[i] >> 32) as u32);
+                cols.v_final[2 * i] = Word::from(v_final[2 * i]);
+                cols.v_final[2 * i + 1] = Word::from(v_final[2 * i + 1]);
+            }
+
+            let schedule = BLAKE2_SIGMA[round % BLAKE2_SIGMA.len()];
+            for (i, (g, [a, b, c, d])) in cols.g.iter_mut().zip(G_INDICES).enumerate() {
+                let mx = m[schedule[2 * i]];
+                let my = m[schedule[2 * i + 1]];
+                (v[a], v[b], v[c], v[d]) =
+                    Self::populate_g(g, blu, (v[a], v[b], v[c], v[d]), mx, my);
+            }
+
+            if rows.as_ref().is_some() {
+                rows.as_mut().unwrap().push(row);
+            }
+        }
+
+        let v_out = v.iter().flat_map(|&x| [x as u32, (x >> 32) as u32]).collect_vec();
+        assert_eq!(v_out, v_final);
+    }
+
+    fn populate_g<F: PrimeField32>(
+        g: &mut G64Cols<F>,
+        blu: &mut impl ByteRecord,
+        (a, b, c, d): (u64, u64, u64, u64),
+        mx: u64,
+        my: u64,
+    ) -> (u64, u64, u64, u64) {
+        g.mx = Word::from(mx as u32);
+        g.mx_hi = Word::from((mx >> 32) as u32);
+        g.my = Word::from(my as u32);
+        g.my_hi = Word::from((my >> 32) as u32);
+
+        let a_plus_b = g.a_plus_b.populate(blu, a, b);
+        let a = g.a_1.populate(blu, a_plus_b, mx);
+        let d = populate_xor(&mut g.d_xor_a, &mut g.d_xor_a_hi, blu, d, a).rotate_right(32);
+        let c = g.c_1.populate(blu, c, d);
+        let b = populate_xor(&mut g.b_xor_c, &mut g.b_xor_c_hi, blu, b, c).rotate_right(24);
+
+        let a_plus_b = g.a_plus_b_2.populate(blu, a, b);
+        let a = g.a_2.populate(blu, a_plus_b, my);
+        let d = populate_xor(&mut g.d_xor_a_2, &mut g.d_xor_a_2_hi, blu, d, a).rotate_right(16);
+        let c = g.c_2.populate(blu, c, d);
+        let b_xor_c = populate_xor(&mut g.b_xor_c_2, &mut g.b_xor_c_2_hi, blu, b, c);
+        let b = g.b_2.populate(blu, b_xor_c, 63);
+
+        (a, b, c, d)
+    }
+}
+
+/// Populate the xor of two double words, one word at a time.
+fn populate_xor<F: PrimeField32>(
+    lo: &mut XorOperation<F>,
+    hi: &mut XorOperation<F>,
+    blu: &mut impl ByteRecord,
+    x: u64,
+    y: u64,
+) -> u64 {
+    let value_lo = lo.populate(blu, x as u32, y as u32);
+    let value_hi = hi.populate(blu, (x >> 32) as u32, (y >> 32) as u32);
+    join(value_lo, value_hi)
+}
+
+fn join(lo: u32, hi: u32) -> u64 {
+    lo as u64 | ((hi as u64) << 32)
+}
@@ -0,0 +1,245 @@
+use core::borrow::Borrow;
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::FieldAlgebra;
+use p3_matrix::Matrix;
+use zkm_stark::{
+    air::{BaseAirBuilder, LookupScope, ZKMAirBuilder},
+    Word,
+};
+
+use super::{
+    columns::{BlakeRoundsCols, NUM_BLAKE_ROUNDS_COLS},
+    BlakeRoundsChip, MAX_ROUNDS,
+};
+use crate::{
+    air::{MemoryAirBuilder, WordAirBuilder},
+    memory::MemoryCols,
+    operations::{AddOperation, FixedRotateRightOperation, XorOperation},
+    syscall::precompiles::blake::G_INDICES,
+};
+
+impl<F> BaseAir<F> for BlakeRoundsChip {
+    fn width(&self) -> usize {
+        NUM_BLAKE_ROUNDS_COLS
+    }
+}
+
+impl<AB> Air<AB> for BlakeRoundsChip
+where
+    AB: ZKMAirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &BlakeRoundsCols<AB::Var> = (*local).borrow();
+        let next: &BlakeRoundsCols<AB::Var> = (*next).borrow();
+
+        self.eval_control_flow_flags(builder, local, next);
+
+        self.eval_message_schedule(builder, local);
+
+        let v_out = self.eval_round(builder, local);
+
+        // The output of a round is the input of the next one, and the output of the last round
+        // is the final working vector.
+        for i in 0..16 {
+            builder
+                .when_transition()
+                .when(local.is_real)
+                .when_not(local.is_last_round)
+                .assert_word_eq(v_out[i], next.v[i]);
+            builder.when(local.is_last_round).assert_word_eq(v_out[i], local.v_final[i]);
+        }
+
+        self.eval_memory(builder, local);
+
+        builder.receive_syscall(
+            local.shard,
+            local.clk,
+            AB::F::from_canonical_u32(self.variant().syscall_code().syscall_id()),
+            local.v_ptr,
+            local.m_ptr,
+            local.is_first_round,
+            LookupScope::Local,
+        );
+    }
+}
+
+impl BlakeRoundsChip {
+    fn eval_control_flow_flags<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &BlakeRoundsCols<AB::Var>,
+        next: &BlakeRoundsCols<AB::Var>,
+    ) {
+        let num_rounds = self.variant().num_rounds();
+
+        // Verify that the round columns are a one-hot encoding of a round of this variant.
+        let mut round_sum = AB::Expr::zero();
+        for i in 0..MAX_ROUNDS {
+            if i < num_rounds {
+                builder.assert_bool(local.round[i]);
+                round_sum = round_sum.clone() + local.round[i].into();
+            } else {
+                builder.assert_zero(local.round[i]);
+            }
+        }
+        builder.assert_one(round_sum);
+
+        // The first row is the first round, and the rounds cycle from there on, including in the
+        // padding rows.
+        builder.when_first_row().assert_one(local.round[0]);
+        for i in 0..num_rounds {
+            builder
+                .when_transition()
+                .when(local.round[i])
+                .assert_one(next.round[(i + 1) % num_rounds]);
+        }
+
+        builder.assert_bool(local.is_real);
+        builder.assert_eq(local.is_first_round, local.is_real * local.round[0]);
+        builder.assert_eq(local.is_last_round, local.is_real * local.round[num_rounds - 1]);
+
+        // If this row is real and not the last round, then the next row belongs to the same
+        // syscall.
+        let mut when_same_syscall =
+            builder.when_transition().when(local.is_real).when_not(local.is_last_round);
+        when_same_syscall.assert_one(next.is_real);
+        when_same_syscall.assert_eq(local.shard, next.shard);
+        when_same_syscall.assert_eq(local.clk, next.clk);
+        when_same_syscall.assert_eq(local.v_ptr, next.v_ptr);
+        when_same_syscall.assert_eq(local.m_ptr, next.m_ptr);
+        for i in 0..16 {
+            when_same_syscall.assert_word_eq(local.m[i], next.m[i]);
+            when_same_syscall.assert_word_eq(local.v_final[i], next.v_final[i]);
+        }
+
+        // Once the is_real flag is changed to false, it should not be changed back.
+        builder.when_transition().when_not(local.is_real).assert_zero(next.is_real);
+
+        // The number of rounds is never a power of two, so the table always ends in padding.
+        builder.when_last_row().assert_zero(local.is_real);
+    }
+
+    /// Constrain the message words of every G step to the ones selected by the round's schedule.
+    fn eval_message_schedule<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &BlakeRoundsCols<AB::Var>,
+    ) {
+        let schedule = self.variant().schedule();
+        for (i, g) in local.g.iter().enumerate() {
+            for (word, offset) in [(g.mx, 0), (g.my, 1)] {
+                for byte in 0..4 {
+                    let selected = schedule.iter().enumerate().fold(
+                        AB::Expr::zero(),
+                        |acc, (round, schedule)| {
+                            acc + local.round[round] * local.m[schedule[2 * i + offset]][byte]
+                        },
+                    );
+                    builder.when(local.is_real).assert_eq(word[byte], selected);
+                }
+            }
+        }
+    }
+
+    /// Evaluate the eight G steps of the round and return the resulting working vector.
+    fn eval_round<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &BlakeRoundsCols<AB::Var>,
+    ) -> [Word<AB::Var>; 16] {
+        let mut v = local.v;
+        for (g, [a, b, c, d]) in local.g.iter().zip(G_INDICES) {
+            AddOperation::<AB::F>::eval(builder, v[a], v[b], g.a_plus_b, local.is_real.into());
+            AddOperation::<AB::F>::eval(
+                builder,
+                g.a_plus_b.value,
+                g.mx,
+                g.a_1,
+                local.is_real.into(),
+            );
+            v[a] = g.a_1.value;
+
+            XorOperation::<AB::F>::eval(builder, v[d], v[a], g.d_xor_a, local.is_real);
+            v[d] = rotate_bytes(g.d_xor_a.value, 2);
+
+            AddOperation::<AB::F>::eval(builder, v[c], v[d], g.c_1, local.is_real.into());
+            v[c] = g.c_1.value;
+
+            XorOperation::<AB::F>::eval(builder, v[b], v[c], g.b_xor_c, local.is_real);
+            FixedRotateRightOperation::<AB::F>::eval(
+                builder,
+                g.b_xor_c.value,
+                12,
+                g.b_1,
+                local.is_real,
+            );
+            v[b] = g.b_1.value;
+
+            AddOperation::<AB::F>::eval(builder, v[a], v[b], g.a_plus_b_2, local.is_real.into());
+            AddOperation::<AB::F>::eval(
+                builder,
+                g.a_plus_b_2.value,
+                g.my,
+                g.a_2,
+                local.is_real.into(),
+            );
+            v[a] = g.a_2.value;
+
+            XorOperation::<AB::F>::eval(builder, v[d], v[a], g.d_xor_a_2, local.is_real);
+            v[d] = rotate_bytes(g.d_xor_a_2.value, 1);
+
+            AddOperation::<AB::F>::eval(builder, v[c], v[d], g.c_2, local.is_real.into());
+            v[c] = g.c_2.value;
+
+            XorOperation::<AB::F>::eval(builder, v[b], v[c], g.b_xor_c_2, local.is_real);
+            FixedRotateRightOperation::<AB::F>::eval(
+                builder,
+                g.b_xor_c_2.value,
+                7,
+                g.b_2,
+                local.is_real,
+            );
+            v[b] = g.b_2.value;
+        }
+        v
+    }
+
+    /// On the first round, read the message block and overwrite the working vector with the
+    /// final one.
+    fn eval_memory<AB: ZKMAirBuilder>(&self, builder: &mut AB, local: &BlakeRoundsCols<AB::Var>) {
+        for i in 0..16 {
+            builder
+                .when(local.is_first_round)
+                .assert_word_eq(*local.v_memory[i].prev_value(), local.v[i]);
+            builder
+                .when(local.is_first_round)
+                .assert_word_eq(*local.v_memory[i].value(), local.v_final[i]);
+            builder
+                .when(local.is_first_round)
+                .assert_word_eq(*local.m_memory[i].value(), local.m[i]);
+        }
+
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.clk.into(),
+            local.m_ptr,
+            &local.m_memory,
+            local.is_first_round,
+        );
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.clk.into() + AB::Expr::one(),
+            local.v_ptr,
+            &local.v_memory,
+            local.is_first_round,
+        );
+    }
+}
+
+/// Rotate a word right by a whole number of bytes.
+fn rotate_bytes<T: Copy>(word: Word<T>, nb_bytes: usize) -> Word<T> {
+    Word(core::array::from_fn(|i| word[(i + nb_bytes) % 4]))
+}
@@ -0,0 +1,83 @@
+use std::mem::size_of;
+
+use zkm_derive::AlignedBorrow;
+use zkm_stark::Word;
+
+use super::MAX_ROUNDS;
+use crate::{
+    memory::{MemoryReadCols, MemoryWriteCols},
+    operations::{AddOperation, FixedRotateRightOperation, XorOperation},
+};
+
+pub const NUM_BLAKE_ROUNDS_COLS: usize = size_of::<BlakeRoundsCols<u8>>();
+
+/// The columns of one application of the G function on 32-bit words.
+///
+/// The rotations by 16 and 8 are byte permutations and need no columns.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct GCols<T> {
+    /// The two message words mixed in, selected by the schedule of the current round.
+    pub mx: Word<T>,
+    pub my: Word<T>,
+
+    /// `a := a + b + mx`.
+    pub a_plus_b: AddOperation<T>,
+    pub a_1: AddOperation<T>,
+    /// `d := (d ^ a) >>> 16`.
+    pub d_xor_a: XorOperation<T>,
+    /// `c := c + d`.
+    pub c_1: AddOperation<T>,
+    /// `b := (b ^ c) >>> 12`.
+    pub b_xor_c: XorOperation<T>,
+    pub b_1: FixedRotateRightOperation<T>,
+
+    /// `a := a + b + my`.
+    pub a_plus_b_2: AddOperation<T>,
+    pub a_2: AddOperation<T>,
+    /// `d := (d ^ a) >>> 8`.
+    pub d_xor_a_2: XorOperation<T>,
+    /// `c := c + d`.
+    pub c_2: AddOperation<T>,
+    /// `b := (b ^ c) >>> 7`.
+    pub b_xor_c_2: XorOperation<T>,
+    pub b_2: FixedRotateRightOperation<T>,
+}
+
+/// A set of columns needed to compute the rounds of the BLAKE2s or BLAKE3 compression function.
+///
+/// Each syscall is processed over one row per round. The working vector and the message block are
+/// read and the result is written back on the first row, so the message block and the final
+/// working vector are carried through all the rows of the syscall.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct BlakeRoundsCols<T> {
+    /// Inputs.
+    pub shard: T,
+    pub clk: T,
+    pub v_ptr: T,
+    pub m_ptr: T,
+
+    /// Which round we are currently processing. BLAKE3 only uses the first seven.
+    pub round: [T; MAX_ROUNDS],
+
+    pub is_real: T,
+    /// `is_real` on the first round, where memory is accessed and the syscall is received.
+    pub is_first_round: T,
+    /// `is_real` on the last round.
+    pub is_last_round: T,
+
+    /// The working vector is read and overwritten with the result.
+    pub v_memory: [MemoryWriteCols<T>; 16],
+    pub m_memory: [MemoryReadCols<T>; 16],
+
+    /// The message block.
+    pub m: [Word<T>; 16],
+    /// The working vector at the start of the current round.
+    pub v: [Word<T>; 16],
+    /// The working vector after the last round.
+    pub v_final: [Word<T>; 16],
+
+    /// The column steps followed by the diagonal steps of the round.
+    pub g: [GCols<T>; 8],
+}
@@ -0,0 +1,105 @@
+mod air;
+mod columns;
+mod trace;
+
+use zkm_core_executor::syscalls::SyscallCode;
+
+use super::{BLAKE2_SIGMA, BLAKE3_MSG_SCHEDULE};
+
+/// The number of rounds of BLAKE2s, the larger of the two hashes sharing this chip.
+pub const MAX_ROUNDS: usize = 10;
+
+/// The hashes whose compression function is built on the 32-bit BLAKE G function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlakeVariant {
+    Blake2s,
+    Blake3,
+}
+
+impl BlakeVariant {
+    /// The message schedule, one entry per round.
+    pub const fn schedule(self) -> &'static [[usize; 16]] {
+        match self {
+            Self::Blake2s => &BLAKE2_SIGMA,
+            Self::Blake3 => &BLAKE3_MSG_SCHEDULE,
+        }
+    }
+
+    pub const fn num_rounds(self) -> usize {
+        self.schedule().len()
+    }
+
+    pub const fn syscall_code(self) -> SyscallCode {
+        match self {
+            Self::Blake2s => SyscallCode::BLAKE2S_ROUNDS,
+            Self::Blake3 => SyscallCode::BLAKE3_ROUNDS,
+        }
+    }
+}
+
+/// Implements all the rounds of the BLAKE2s or BLAKE3 compression function on a 16-word working
+/// vector. The inputs to the syscall are a pointer to the working vector, which is overwritten
+/// with the result, and a pointer to the 16-word message block.
+///
+/// In the AIR, each syscall takes up one row per round: 10 rows for BLAKE2s and 7 for BLAKE3.
+pub struct BlakeRoundsChip {
+    variant: BlakeVariant,
+}
+
+impl BlakeRoundsChip {
+    pub const fn blake2s() -> Self {
+        Self { variant: BlakeVariant::Blake2s }
+    }
+
+    pub const fn blake3() -> Self {
+        Self { variant: BlakeVariant::Blake3 }
+    }
+
+    pub const fn variant(&self) -> BlakeVariant {
+        self.variant
+    }
+}
+
+#[cfg(test)]
+pub mod rounds_tests {
+    use zkm_core_executor::{syscalls::SyscallCode, Instruction, Opcode, Program};
+    use zkm_stark::CpuProver;
+
+    use crate::utils::{run_test, setup_logger};
+
+    pub fn blake_rounds_program(syscall_code: SyscallCode) -> Program {
+        let v_ptr = 100;
+        let m_ptr = 1000;
+        let mut instructions = vec![];
+        for i in 0..16 {
+            instructions.extend(vec![
+                Instruction::new(Opcode::ADD, 29, 0, 0x0101_0101 * (i + 1), false, true),
+                Instruction::new(Opcode::ADD, 30, 0, v_ptr + i * 4, false, true),
+                Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+                Instruction::new(Opcode::ADD, 30, 0, m_ptr + i * 4, false, true),
+                Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+            ]);
+        }
+        instructions.extend(vec![
+            Instruction::new(Opcode::ADD, 2, 0, syscall_code as u32, false, true),
+            Instruction::new(Opcode::ADD, 4, 0, v_ptr, false, true),
+            Instruction::new(Opcode::ADD, 5, 0, m_ptr, false, true),
+            Instruction::new(Opcode::SYSCALL, 2, 4, 5, false, false),
+        ]);
+        Program::new(instructions, 0, 0)
+    }
+
+    #[test]
+    fn prove_blake2s_koalabear() {
+        setup_logger();
+        let program = blake_rounds_program(SyscallCode::BLAKE2S_ROUNDS);
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    fn prove_blake3_koalabear() {
+        setup_logger();
+        let program = blake_rounds_program(SyscallCode::BLAKE3_ROUNDS);
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+}
@@ -0,0 +1,187 @@
+use std::borrow::BorrowMut;
+
+use hashbrown::HashMap;
+use itertools::Itertools;
+use p3_field::PrimeField32;
+use p3_matrix::dense::RowMajorMatrix;
+use p3_maybe_rayon::prelude::{ParallelIterator, ParallelSlice};
+use zkm_core_executor::{
+    events::{BlakeRoundsEvent, ByteLookupEvent, ByteRecord, PrecompileEvent},
+    ExecutionRecord, Program,
+};
+use zkm_stark::{air::MachineAir, Word};
+
+use super::{
+    columns::{BlakeRoundsCols, GCols, NUM_BLAKE_ROUNDS_COLS},
+    BlakeRoundsChip, BlakeVariant,
+};
+use crate::{syscall::precompiles::blake::G_INDICES, utils::pad_rows_fixed, CoreChipError};
+
+impl<F: PrimeField32> MachineAir<F> for BlakeRoundsChip {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    type Error = CoreChipError;
+
+    fn name(&self) -> String {
+        match self.variant() {
+            BlakeVariant::Blake2s => "Blake2sRounds".to_string(),
+            BlakeVariant::Blake3 => "Blake3Rounds".to_string(),
+        }
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        _: &mut ExecutionRecord,
+    ) -> Result<RowMajorMatrix<F>, Self::Error> {
+        let mut wrapped_rows = Some(Vec::new());
+        for (_, event) in input.get_precompile_events(self.variant().syscall_code()) {
+            self.event_to_rows(self.unwrap_event(event), &mut wrapped_rows, &mut Vec::new());
+        }
+        let mut rows = wrapped_rows.unwrap();
+
+        let num_real_rows = rows.len();
+
+        pad_rows_fixed(
+            &mut rows,
+            || [F::ZERO; NUM_BLAKE_ROUNDS_COLS],
+            input.fixed_log2_rows::<F, _>(self),
+        );
+
+        // Keep cycling through the rounds in the padded rows.
+        let num_rounds = self.variant().num_rounds();
+        for (i, row) in rows[num_real_rows..].iter_mut().enumerate() {
+            let cols: &mut BlakeRoundsCols<F> = row.as_mut_slice().borrow_mut();
+            cols.round[i % num_rounds] = F::ONE;
+        }
+
+        // Convert the trace to a row major matrix.
+        Ok(RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_BLAKE_ROUNDS_COLS,
+        ))
+    }
+
+    fn generate_dependencies(
+        &self,
+        input: &Self::Record,
+        output: &mut Self::Record,
+    ) -> Result<(), Self::Error> {
+        let events = input.get_precompile_events(self.variant().syscall_code());
+        let chunk_size = std::cmp::max(events.len() / num_cpus::get(), 1);
+
+        let blu_batches = events
+            .par_chunks(chunk_size)
+            .map(|events| {
+                let mut blu: HashMap<ByteLookupEvent, usize> = HashMap::new();
+                events.iter().for_each(|(_, event)| {
+                    self.event_to_rows::<F>(self.unwrap_event(event), &mut None, &mut blu);
+                });
+                blu
+            })
+            .collect::<Vec<_>>();
+
+        output.add_byte_lookup_events_from_maps(blu_batches.iter().collect_vec());
+        Ok(())
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        if let Some(shape) = shard.shape.as_ref() {
+            shape.included::<F, _>(self)
+        } else {
+            !shard.get_precompile_events(self.variant().syscall_code()).is_empty()
+        }
+    }
+}
+
+impl BlakeRoundsChip {
+    fn unwrap_event<'a>(&self, event: &'a PrecompileEvent) -> &'a BlakeRoundsEvent {
+        match (self.variant(), event) {
+            (BlakeVariant::Blake2s, PrecompileEvent::Blake2sRounds(event))
+            | (BlakeVariant::Blake3, PrecompileEvent::Blake3Rounds(event)) => event,
+            _ => unreachable!(),
+        }
+    }
+
+    fn event_to_rows<F: PrimeField32>(
+        &self,
+        event: &BlakeRoundsEvent,
+        rows: &mut Option<Vec<[F; NUM_BLAKE_ROUNDS_COLS]>>,
+        blu: &mut impl ByteRecord,
+    ) {
+        let num_rounds = self.variant().num_rounds();
+        let v_final = event.v_memory_records.iter().map(|record| record.value).collect_vec();
+
+        let mut v = event.v;
+        for (round, schedule) in self.variant().schedule().iter().enumerate() {
+            let mut row = [F::ZERO; NUM_BLAKE_ROUNDS_COLS];
+            let cols: &mut BlakeRoundsCols<F> = row.as_mut_slice().borrow_mut();
+
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.v_ptr = F::from_canonical_u32(event.v_ptr);
+            cols.m_ptr = F::from_canonical_u32(event.m_ptr);
+
+            cols.round[round] = F::ONE;
+            cols.is_real = F::ONE;
+            cols.is_first_round = F::from_bool(round == 0);
+            cols.is_last_round = F::from_bool(round == num_rounds - 1);
+
+            // The memory is only accessed on the first round.
+            if round == 0 {
+                for i in 0..16 {
+                    cols.v_memory[i].populate(event.v_memory_records[i], blu);
+                    cols.m_memory[i].populate(event.m_memory_records[i], blu);
+                }
+            }
+
+            for i in 0..16 {
+                cols.m[i] = Word::from(event.m[i]);
+                cols.v[i] = Word::from(v[i]);
+                cols.v_final[i] = Word::from(v_final[i]);
+            }
+
+            for (i, (g, [a, b, c, d])) in cols.g.iter_mut().zip(G_INDICES).enumerate() {
+                let mx = event.m[schedule[2 * i]];
+                let my = event.m[schedule[2 * i + 1]];
+                (v[a], v[b], v[c], v[d]) =
+                    Self::populate_g(g, blu, (v[a], v[b], v[c], v[d]), mx, my);
+            }
+
+            if rows.as_ref().is_some() {
+                rows.as_mut().unwrap().push(row);
+            }
+        }
+
+        assert_eq!(v.as_slice(), v_final.as_slice());
+    }
+
+    fn populate_g<F: PrimeField32>(
+        g: &mut GCols<F>,
+        blu: &mut impl ByteRecord,
+        (a, b, c, d): (u32, u32, u32, u32),
+        mx: u32,
+        my: u32,
+    ) -> (u32, u32, u32, u32) {
+        g.mx = Word::from(mx);
+        g.my = Word::from(my);
+
+        let a_plus_b = g.a_plus_b.populate(blu, a, b);
+        let a = g.a_1.populate(blu, a_plus_b, mx);
+        let d = g.d_xor_a.populate(blu, d, a).rotate_right(16);
+        let c = g.c_1.populate(blu, c, d);
+        let b_xor_c = g.b_xor_c.populate(blu, b, c);
+        let b = g.b_1.populate(blu, b_xor_c, 12);
+
+        let a_plus_b = g.a_plus_b_2.populate(blu, a, b);
+        let a = g.a_2.populate(blu, a_plus_b, my);
+        let d = g.d_xor_a_2.populate(blu, d, a).rotate_right(8);
+        let c = g.c_2.populate(blu, c, d);
+        let b_xor_c = g.b_xor_c_2.populate(blu, b, c);
+        let b = g.b_2.populate(blu, b_xor_c, 7);
+
+        (a, b, c, d)
+    }
+}
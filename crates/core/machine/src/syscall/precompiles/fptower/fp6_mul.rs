@@ -0,0 +1,526 @@
+use std::{
+    borrow::{Borrow, BorrowMut},
+    marker::PhantomData,
+    mem::size_of,
+};
+
+use crate::{air::MemoryAirBuilder, utils::zeroed_f_vec, CoreChipError};
+use generic_array::GenericArray;
+use itertools::Itertools;
+use num::BigUint;
+use p3_air::{Air, BaseAir};
+use p3_field::{FieldAlgebra, PrimeField32};
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use typenum::Unsigned;
+use zkm_core_executor::{
+    events::{ByteLookupEvent, ByteRecord, FieldOperation, PrecompileEvent},
+    syscalls::SyscallCode,
+    ExecutionRecord, Program,
+};
+use zkm_curves::{
+    params::{FieldParameters, Limbs, NumLimbs, NumWords},
+    weierstrass::{FieldType, FpOpField},
+};
+use zkm_derive::AlignedBorrow;
+use zkm_stark::air::{BaseAirBuilder, LookupScope, MachineAir, Polynomial, ZKMAirBuilder};
+
+use crate::{
+    memory::{value_as_limbs, MemoryReadCols, MemoryWriteCols},
+    operations::field::{field_inner_product::FieldInnerProductCols, field_op::FieldOpCols},
+    utils::{limbs_from_prev_access, pad_rows_fixed, words_to_bytes_le_vec},
+};
+
+pub const fn num_fp6_mul_cols<P: FieldParameters + NumWords>() -> usize {
+    size_of::<Fp6MulAssignCols<u8, P>>()
+}
+
+/// The columns multiplying an Fp2 element `a + b * u` by the non-residue `xi = x0 + u`, which is
+/// `(x0 * a - b) + (a + x0 * b) * u`.
+#[derive(Debug, Clone)]
+#[repr(C)]
+pub struct MulByNonResidueCols<T, P: FieldParameters> {
+    pub(crate) x0_mul_a: FieldOpCols<T, P>,
+    pub(crate) c0: FieldOpCols<T, P>,
+    pub(crate) c1: FieldOpCols<T, P>,
+}
+
+/// The columns computing one Fp2 coefficient `a_0 * e_0 + a_1 * e_1 + a_2 * e_2` of an Fp6
+/// product.
+#[derive(Debug, Clone)]
+#[repr(C)]
+pub struct Fp2SumOfProductsCols<T, P: FieldParameters> {
+    /// The sum of the products of the real parts.
+    pub(crate) c0_pos_ip: FieldInnerProductCols<T, P>,
+    pub(crate) c0_pos: FieldOpCols<T, P>,
+    /// The sum of the products of the imaginary parts.
+    pub(crate) c0_neg_ip: FieldInnerProductCols<T, P>,
+    pub(crate) c0_neg: FieldOpCols<T, P>,
+    pub(crate) c0: FieldOpCols<T, P>,
+    /// The sum of the cross products, starting with the two of `a_0 * e_0` and accumulating the
+    /// other four one at a time. The last one holds the imaginary part of the result.
+    pub(crate) c1_ip: FieldInnerProductCols<T, P>,
+    pub(crate) c1: [FieldOpCols<T, P>; 4],
+}
+
+/// A set of columns for the Fp6Mul operation.
+///
+/// An Fp6 element is made of three Fp2 coefficients `c0 + c1 * v + c2 * v^2`, where
+/// `v^3 = xi` is the non-residue of [`FpOpField::FP6_NON_RESIDUE`].
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct Fp6MulAssignCols<T, P: FieldParameters + NumWords> {
+    pub is_real: T,
+    pub shard: T,
+    pub clk: T,
+    pub x_ptr: T,
+    pub y_ptr: T,
+    pub x_access: [GenericArray<MemoryWriteCols<T>, P::WordsCurvePoint>; 3],
+    pub y_access: [GenericArray<MemoryReadCols<T>, P::WordsCurvePoint>; 3],
+    /// `xi * y1` and `xi * y2`.
+    pub(crate) xi_y: [MulByNonResidueCols<T, P>; 2],
+    /// The coefficients of the product.
+    pub(crate) c: [Fp2SumOfProductsCols<T, P>; 3],
+}
+
+#[derive(Default)]
+pub struct Fp6MulAssignChip<P> {
+    _marker: PhantomData<P>,
+}
+
+impl<P: FpOpField> Fp6MulAssignChip<P> {
+    pub const fn new() -> Self {
+        Self { _marker: PhantomData }
+    }
+
+    fn syscall_code() -> SyscallCode {
+        match P::FIELD_TYPE {
+            FieldType::Bn254 => SyscallCode::BN254_FP6_MUL,
+            FieldType::Bls12381 => SyscallCode::BLS12381_FP6_MUL,
+        }
+    }
+
+    fn populate_field_ops<F: PrimeField32>(
+        blu_events: &mut Vec<ByteLookupEvent>,
+        cols: &mut Fp6MulAssignCols<F, P>,
+        x: &[[BigUint; 2]; 3],
+        y: &[[BigUint; 2]; 3],
+    ) {
+        let xi_y1 = Self::populate_mul_by_non_residue(blu_events, &mut cols.xi_y[0], &y[1]);
+        let xi_y2 = Self::populate_mul_by_non_residue(blu_events, &mut cols.xi_y[1], &y[2]);
+
+        let operands = [
+            [y[0].clone(), xi_y2.clone(), xi_y1],
+            [y[1].clone(), y[0].clone(), xi_y2],
+            [y[2].clone(), y[1].clone(), y[0].clone()],
+        ];
+        for (c, e) in cols.c.iter_mut().zip(operands.iter()) {
+            Self::populate_sum_of_products(blu_events, c, x, e);
+        }
+    }
+
+    fn populate_mul_by_non_residue<F: PrimeField32>(
+        blu_events: &mut Vec<ByteLookupEvent>,
+        cols: &mut MulByNonResidueCols<F, P>,
+        a: &[BigUint; 2],
+    ) -> [BigUint; 2] {
+        debug_assert_eq!(P::FP6_NON_RESIDUE.1, 1);
+        let modulus = BigUint::from_bytes_le(P::MODULUS);
+        let x0 = BigUint::from(P::FP6_NON_RESIDUE.0);
+
+        let x0_mul_a = cols.x0_mul_a.populate_with_modulus(
+            blu_events,
+            &a[0],
+            &x0,
+            &modulus,
+            FieldOperation::Mul,
+        );
+        let c0 = cols.c0.populate_with_modulus(
+            blu_events,
+            &x0_mul_a,
+            &a[1],
+            &modulus,
+            FieldOperation::Sub,
+        );
+        let (c1, _) = cols.c1.populate_mul_and_carry(blu_events, &a[1], &x0, &a[0], &modulus);
+        [c0, c1]
+    }
+
+    fn populate_sum_of_products<F: PrimeField32>(
+        blu_events: &mut Vec<ByteLookupEvent>,
+        cols: &mut Fp2SumOfProductsCols<F, P>,
+        a: &[[BigUint; 2]; 3],
+        e: &[[BigUint; 2]; 3],
+    ) {
+        let modulus = BigUint::from_bytes_le(P::MODULUS);
+
+        let c0_pos_ip = cols.c0_pos_ip.populate(
+            blu_events,
+            &[a[0][0].clone(), a[1][0].clone()],
+            &[e[0][0].clone(), e[1][0].clone()],
+        );
+        let (c0_pos, _) = cols
+            .c0_pos
+            .populate_mul_and_carry(blu_events, &a[2][0], &e[2][0], &c0_pos_ip, &modulus);
+        let c0_neg_ip = cols.c0_neg_ip.populate(
+            blu_events,
+            &[a[0][1].clone(), a[1][1].clone()],
+            &[e[0][1].clone(), e[1][1].clone()],
+        );
+        let (c0_neg, _) = cols
+            .c0_neg
+            .populate_mul_and_carry(blu_events, &a[2][1], &e[2][1], &c0_neg_ip, &modulus);
+        cols.c0.populate_with_modulus(blu_events, &c0_pos, &c0_neg, &modulus, FieldOperation::Sub);
+
+        let mut c1 = cols.c1_ip.populate(
+            blu_events,
+            &[a[0][0].clone(), a[0][1].clone()],
+            &[e[0][1].clone(), e[0][0].clone()],
+        );
+        let terms = [
+            (&a[1][0], &e[1][1]),
+            (&a[1][1], &e[1][0]),
+            (&a[2][0], &e[2][1]),
+            (&a[2][1], &e[2][0]),
+        ];
+        for (cols, (a, e)) in cols.c1.iter_mut().zip(terms) {
+            c1 = cols.populate_mul_and_carry(blu_events, a, e, &c1, &modulus).0;
+        }
+    }
+}
+
+impl<F: PrimeField32, P: FpOpField> MachineAir<F> for Fp6MulAssignChip<P> {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    type Error = CoreChipError;
+
+    fn name(&self) -> String {
+        match P::FIELD_TYPE {
+            FieldType::Bn254 => "Bn254Fp6MulAssign".to_string(),
+            FieldType::Bls12381 => "Bls12381Fp6MulAssign".to_string(),
+        }
+    }
+
+    fn generate_trace(
+        &self,
+        input: &Self::Record,
+        output: &mut Self::Record,
+    ) -> Result<RowMajorMatrix<F>, Self::Error> {
+        let events = input.get_precompile_events(Self::syscall_code());
+
+        let mut rows = Vec::new();
+        let mut new_byte_lookup_events = Vec::new();
+
+        let num_words_field_element = <P as NumLimbs>::Limbs::USIZE / 4;
+        let to_fp6 = |words: &[u32]| -> [[BigUint; 2]; 3] {
+            let mut coefficients = words
+                .chunks_exact(num_words_field_element)
+                .map(|limbs| BigUint::from_bytes_le(&words_to_bytes_le_vec(limbs)));
+            core::array::from_fn(|_| core::array::from_fn(|_| coefficients.next().unwrap()))
+        };
+
+        for (_, event) in events {
+            let event = match (P::FIELD_TYPE, event) {
+                (FieldType::Bn254, PrecompileEvent::Bn254Fp6Mul(event)) => event,
+                (FieldType::Bls12381, PrecompileEvent::Bls12381Fp6Mul(event)) => event,
+                _ => unreachable!(),
+            };
+
+            let mut row = zeroed_f_vec(num_fp6_mul_cols::<P>());
+            let cols: &mut Fp6MulAssignCols<F, P> = row.as_mut_slice().borrow_mut();
+
+            cols.is_real = F::ONE;
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.x_ptr = F::from_canonical_u32(event.x_ptr);
+            cols.y_ptr = F::from_canonical_u32(event.y_ptr);
+
+            Self::populate_field_ops(
+                &mut new_byte_lookup_events,
+                cols,
+                &to_fp6(&event.x),
+                &to_fp6(&event.y),
+            );
+
+            // Populate the memory access columns.
+            for (i, y_access) in cols.y_access.iter_mut().flat_map(|a| a.iter_mut()).enumerate() {
+                y_access.populate(event.y_memory_records[i], &mut new_byte_lookup_events);
+            }
+            for (i, x_access) in cols.x_access.iter_mut().flat_map(|a| a.iter_mut()).enumerate() {
+                x_access.populate(event.x_memory_records[i], &mut new_byte_lookup_events);
+            }
+            rows.push(row);
+        }
+
+        output.add_byte_lookup_events(new_byte_lookup_events);
+
+        pad_rows_fixed(
+            &mut rows,
+            || {
+                let mut row = zeroed_f_vec(num_fp6_mul_cols::<P>());
+                let cols: &mut Fp6MulAssignCols<F, P> = row.as_mut_slice().borrow_mut();
+                let zero = || core::array::from_fn(|_| [BigUint::ZERO, BigUint::ZERO]);
+                Self::populate_field_ops(&mut vec![], cols, &zero(), &zero());
+                row
+            },
+            input.fixed_log2_rows::<F, _>(self),
+        );
+
+        // Convert the trace to a row major matrix.
+        Ok(RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            num_fp6_mul_cols::<P>(),
+        ))
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        if let Some(shape) = shard.shape.as_ref() {
+            shape.included::<F, _>(self)
+        } else {
+            !shard.get_precompile_events(Self::syscall_code()).is_empty()
+        }
+    }
+
+    fn local_only(&self) -> bool {
+        true
+    }
+}
+
+impl<F, P: FpOpField> BaseAir<F> for Fp6MulAssignChip<P> {
+    fn width(&self) -> usize {
+        num_fp6_mul_cols::<P>()
+    }
+}
+
+impl<AB, P: FpOpField> Air<AB> for Fp6MulAssignChip<P>
+where
+    AB: ZKMAirBuilder,
+    Limbs<AB::Var, <P as NumLimbs>::Limbs>: Copy,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let local: &Fp6MulAssignCols<AB::Var, P> = (*local).borrow();
+
+        let num_words_field_element = <P as NumLimbs>::Limbs::USIZE / 4;
+        let x: [[Limbs<AB::Var, <P as NumLimbs>::Limbs>; 2]; 3] = core::array::from_fn(|i| {
+            [
+                limbs_from_prev_access(&local.x_access[i][0..num_words_field_element]),
+                limbs_from_prev_access(&local.x_access[i][num_words_field_element..]),
+            ]
+        });
+        let y: [[Limbs<AB::Var, <P as NumLimbs>::Limbs>; 2]; 3] = core::array::from_fn(|i| {
+            [
+                limbs_from_prev_access(&local.y_access[i][0..num_words_field_element]),
+                limbs_from_prev_access(&local.y_access[i][num_words_field_element..]),
+            ]
+        });
+
+        let modulus_coeffs =
+            P::MODULUS.iter().map(|&limbs| AB::Expr::from_canonical_u8(limbs)).collect_vec();
+        let p_modulus = Polynomial::from_coefficients(&modulus_coeffs);
+
+        let xi_y1 = Self::eval_mul_by_non_residue(
+            builder,
+            &local.xi_y[0],
+            &y[1],
+            &p_modulus,
+            local.is_real,
+        );
+        let xi_y2 = Self::eval_mul_by_non_residue(
+            builder,
+            &local.xi_y[1],
+            &y[2],
+            &p_modulus,
+            local.is_real,
+        );
+
+        let operands = [[y[0], xi_y2, xi_y1], [y[1], y[0], xi_y2], [y[2], y[1], y[0]]];
+        for (i, (c, e)) in local.c.iter().zip(operands.iter()).enumerate() {
+            let [c0, c1] = Self::eval_sum_of_products(builder, c, &x, e, &p_modulus, local.is_real);
+            builder
+                .when(local.is_real)
+                .assert_all_eq(c0, value_as_limbs(&local.x_access[i][0..num_words_field_element]));
+            builder
+                .when(local.is_real)
+                .assert_all_eq(c1, value_as_limbs(&local.x_access[i][num_words_field_element..]));
+        }
+
+        // The coefficients are laid out contiguously in memory.
+        let num_bytes_fp2 = 4 * P::WordsCurvePoint::USIZE;
+        for (i, y_access) in local.y_access.iter().enumerate() {
+            builder.eval_memory_access_slice(
+                local.shard,
+                local.clk.into(),
+                local.y_ptr + AB::F::from_canonical_usize(i * num_bytes_fp2),
+                y_access,
+                local.is_real,
+            );
+        }
+        for (i, x_access) in local.x_access.iter().enumerate() {
+            builder.eval_memory_access_slice(
+                local.shard,
+                local.clk + AB::F::from_canonical_u32(1), /* We read p at +1 since p, q could be
+                                                           * the same. */
+                local.x_ptr + AB::F::from_canonical_usize(i * num_bytes_fp2),
+                x_access,
+                local.is_real,
+            );
+        }
+
+        builder.receive_syscall(
+            local.shard,
+            local.clk,
+            AB::F::from_canonical_u32(Self::syscall_code().syscall_id()),
+            local.x_ptr,
+            local.y_ptr,
+            local.is_real,
+            LookupScope::Local,
+        );
+    }
+}
+
+impl<P: FpOpField> Fp6MulAssignChip<P> {
+    fn eval_mul_by_non_residue<AB: ZKMAirBuilder>(
+        builder: &mut AB,
+        cols: &MulByNonResidueCols<AB::Var, P>,
+        a: &[Limbs<AB::Var, <P as NumLimbs>::Limbs>; 2],
+        p_modulus: &Polynomial<AB::Expr>,
+        is_real: AB::Var,
+    ) -> [Limbs<AB::Var, <P as NumLimbs>::Limbs>; 2]
+    where
+        Limbs<AB::Var, <P as NumLimbs>::Limbs>: Copy,
+    {
+        let x0 = P::to_limbs_field::<AB::Expr, _>(&BigUint::from(P::FP6_NON_RESIDUE.0));
+
+        cols.x0_mul_a.eval_with_modulus(
+            builder,
+            &a[0],
+            &x0,
+            p_modulus,
+            FieldOperation::Mul,
+            is_real,
+        );
+        cols.c0.eval_with_modulus(
+            builder,
+            &cols.x0_mul_a.result,
+            &a[1],
+            p_modulus,
+            FieldOperation::Sub,
+            is_real,
+        );
+        cols.c1.eval_mul_and_carry(builder, &a[1], &x0, &a[0], p_modulus, is_real);
+        [cols.c0.result, cols.c1.result]
+    }
+
+    fn eval_sum_of_products<AB: ZKMAirBuilder>(
+        builder: &mut AB,
+        cols: &Fp2SumOfProductsCols<AB::Var, P>,
+        a: &[[Limbs<AB::Var, <P as NumLimbs>::Limbs>; 2]; 3],
+        e: &[[Limbs<AB::Var, <P as NumLimbs>::Limbs>; 2]; 3],
+        p_modulus: &Polynomial<AB::Expr>,
+        is_real: AB::Var,
+    ) -> [Limbs<AB::Var, <P as NumLimbs>::Limbs>; 2]
+    where
+        Limbs<AB::Var, <P as NumLimbs>::Limbs>: Copy,
+    {
+        cols.c0_pos_ip.eval(builder, &[a[0][0], a[1][0]], &[e[0][0], e[1][0]], is_real);
+        cols.c0_pos.eval_mul_and_carry(
+            builder,
+            &a[2][0],
+            &e[2][0],
+            &cols.c0_pos_ip.result,
+            p_modulus,
+            is_real,
+        );
+        cols.c0_neg_ip.eval(builder, &[a[0][1], a[1][1]], &[e[0][1], e[1][1]], is_real);
+        cols.c0_neg.eval_mul_and_carry(
+            builder,
+            &a[2][1],
+            &e[2][1],
+            &cols.c0_neg_ip.result,
+            p_modulus,
+            is_real,
+        );
+        cols.c0.eval_with_modulus(
+            builder,
+            &cols.c0_pos.result,
+            &cols.c0_neg.result,
+            p_modulus,
+            FieldOperation::Sub,
+            is_real,
+        );
+
+        cols.c1_ip.eval(builder, &[a[0][0], a[0][1]], &[e[0][1], e[0][0]], is_real);
+        let terms =
+            [(a[1][0], e[1][1]), (a[1][1], e[1][0]), (a[2][0], e[2][1]), (a[2][1], e[2][0])];
+        let mut acc = cols.c1_ip.result;
+        for (cols, (a, e)) in cols.c1.iter().zip(terms) {
+            cols.eval_mul_and_carry(builder, &a, &e, &acc, p_modulus, is_real);
+            acc = cols.result;
+        }
+
+        [cols.c0.result, acc]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+    use typenum::Unsigned;
+    use zkm_core_executor::{syscalls::SyscallCode, Instruction, Opcode, Program};
+    use zkm_curves::weierstrass::{bls12_381::Bls12381BaseField, bn254::Bn254BaseField, FpOpField};
+    use zkm_stark::CpuProver;
+
+    use crate::utils::{run_test, setup_logger};
+
+    /// Multiply two Fp6 elements whose coefficients are close to the modulus, then square the
+    /// result in place.
+    fn fp6_mul_program<P: FpOpField>(syscall_code: SyscallCode) -> Program {
+        let x_ptr = 100;
+        let y_ptr = 1000;
+        let num_words = 3 * P::WordsCurvePoint::USIZE;
+        let num_words_field_element = num_words / 6;
+        let modulus = BigUint::from_bytes_le(P::MODULUS);
+
+        let mut instructions = vec![];
+        for (ptr, offset) in [(x_ptr, 1u32), (y_ptr, 7u32)] {
+            for i in 0..6 {
+                let mut words = (&modulus - (offset + i as u32) * 0x1234_5678u32).to_u32_digits();
+                words.resize(num_words_field_element, 0);
+                for (j, word) in words.into_iter().enumerate() {
+                    let addr = ptr + 4 * (i * num_words_field_element + j) as u32;
+                    instructions.extend(vec![
+                        Instruction::new(Opcode::ADD, 29, 0, word, false, true),
+                        Instruction::new(Opcode::ADD, 30, 0, addr, false, true),
+                        Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+                    ]);
+                }
+            }
+        }
+        for (x, y) in [(x_ptr, y_ptr), (x_ptr, x_ptr)] {
+            instructions.extend(vec![
+                Instruction::new(Opcode::ADD, 2, 0, syscall_code as u32, false, true),
+                Instruction::new(Opcode::ADD, 4, 0, x, false, true),
+                Instruction::new(Opcode::ADD, 5, 0, y, false, true),
+                Instruction::new(Opcode::SYSCALL, 2, 4, 5, false, false),
+            ]);
+        }
+        Program::new(instructions, 0, 0)
+    }
+
+    #[test]
+    fn test_bls12381_fp6_mul() {
+        setup_logger();
+        let program = fp6_mul_program::<Bls12381BaseField>(SyscallCode::BLS12381_FP6_MUL);
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    fn test_bn254_fp6_mul() {
+        setup_logger();
+        let program = fp6_mul_program::<Bn254BaseField>(SyscallCode::BN254_FP6_MUL);
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+}
@@ -3,7 +3,12 @@ use core::borrow::Borrow;
 use p3_air::{Air, AirBuilder, BaseAir};
 use p3_field::FieldAlgebra;
 use p3_matrix::Matrix;
-use zkm_core_executor::{syscalls::SyscallCode, Register};
+use zkm_core_executor::{
+    linux::{TIMESPEC64_SIZE, TIMESPEC_SIZE, UTSNAME_SIZE},
+    syscalls::SyscallCode,
+    vfs::STAT64_SIZE,
+    ByteOpcode, Register,
+};
 use zkm_stark::{
     air::{LookupScope, ZKMAirBuilder},
     Word,
@@ -16,9 +21,9 @@ use super::{
 use crate::{
     air::{MemoryAirBuilder, WordAirBuilder},
     memory::MemoryCols,
-    operations::GtColsBytes,
+    operations::{GtColsBytes, KoalaBearWordRangeChecker},
 };
-use zkm_stark::air::BaseAirBuilder;
+use zkm_stark::air::{BaseAirBuilder, ByteAirBuilder};
 
 impl<F> BaseAir<F> for SysLinuxChip {
     fn width(&self) -> usize {
@@ -42,15 +47,21 @@ where
         self.eval_read(builder, local);
         self.eval_write(builder, local);
         self.eval_mmap(builder, local);
+        self.eval_set_thread_area(builder, local);
+        self.eval_vfs(builder, local);
+        self.eval_personality(builder, local);
+        self.eval_errors(builder, local);
+        self.eval_buf(builder, local);
         self.eval_nop(builder, local);
 
-        // Check that the a3 memory access.
+        // Check that the a3 memory access. The buffer word rows don't belong to a syscall of
+        // their own, so they don't write a3.
         builder.eval_memory_access(
             local.shard,
             local.clk,
             AB::Expr::from_canonical_u32(Register::A3 as u32),
             &local.output,
-            local.is_real,
+            local.is_real - local.is_buf_word,
         );
 
         // Check that the flags are boolean.
@@ -73,6 +84,20 @@ where
                 local.is_fnctl_a1_3,
                 local.is_read,
                 local.is_write,
+                local.is_open,
+                local.is_openat,
+                local.is_close,
+                local.is_lseek,
+                local.is_fstat,
+                local.is_error,
+                local.is_uname,
+                local.is_futex,
+                local.is_clock_gettime,
+                local.is_clock_gettime64,
+                local.is_getrandom,
+                local.is_set_thread_area,
+                local.writes_buf,
+                local.is_buf_word,
                 local.is_nop,
                 local.is_real,
             ];
@@ -139,6 +164,50 @@ where
                 local.syscall_id,
                 AB::Expr::from_canonical_u32(SyscallCode::SYS_WRITE as u32),
             );
+            builder.when(local.is_open).assert_eq(
+                local.syscall_id,
+                AB::Expr::from_canonical_u32(SyscallCode::SYS_OPEN as u32),
+            );
+            builder.when(local.is_openat).assert_eq(
+                local.syscall_id,
+                AB::Expr::from_canonical_u32(SyscallCode::SYS_OPENAT as u32),
+            );
+            builder.when(local.is_close).assert_eq(
+                local.syscall_id,
+                AB::Expr::from_canonical_u32(SyscallCode::SYS_CLOSE as u32),
+            );
+            builder.when(local.is_lseek).assert_eq(
+                local.syscall_id,
+                AB::Expr::from_canonical_u32(SyscallCode::SYS_LSEEK as u32),
+            );
+            builder.when(local.is_fstat).assert_eq(
+                local.syscall_id,
+                AB::Expr::from_canonical_u32(SyscallCode::SYS_FSTAT64 as u32),
+            );
+            builder.when(local.is_uname).assert_eq(
+                local.syscall_id,
+                AB::Expr::from_canonical_u32(SyscallCode::SYS_UNAME as u32),
+            );
+            builder.when(local.is_futex).assert_eq(
+                local.syscall_id,
+                AB::Expr::from_canonical_u32(SyscallCode::SYS_FUTEX as u32),
+            );
+            builder.when(local.is_clock_gettime).assert_eq(
+                local.syscall_id,
+                AB::Expr::from_canonical_u32(SyscallCode::SYS_CLOCK_GETTIME as u32),
+            );
+            builder.when(local.is_clock_gettime64).assert_eq(
+                local.syscall_id,
+                AB::Expr::from_canonical_u32(SyscallCode::SYS_CLOCK_GETTIME64 as u32),
+            );
+            builder.when(local.is_getrandom).assert_eq(
+                local.syscall_id,
+                AB::Expr::from_canonical_u32(SyscallCode::SYS_GETRANDOM as u32),
+            );
+            builder.when(local.is_set_thread_area).assert_eq(
+                local.syscall_id,
+                AB::Expr::from_canonical_u32(SyscallCode::SYS_SET_THREAD_AREA as u32),
+            );
             builder.when(local.is_real).assert_one(
                 local.is_mmap
                     + local.is_clone
@@ -147,6 +216,18 @@ where
                     + local.is_fnctl
                     + local.is_read
                     + local.is_write
+                    + local.is_open
+                    + local.is_openat
+                    + local.is_close
+                    + local.is_lseek
+                    + local.is_fstat
+                    + local.is_uname
+                    + local.is_futex
+                    + local.is_clock_gettime
+                    + local.is_clock_gettime64
+                    + local.is_getrandom
+                    + local.is_set_thread_area
+                    + local.is_buf_word
                     + local.is_nop,
             );
         }
@@ -157,7 +238,7 @@ where
             local.syscall_id,
             local.a0.reduce::<AB>(),
             local.a1.reduce::<AB>(),
-            local.is_real,
+            local.is_real - local.is_buf_word,
             LookupScope::Local,
         );
     }
@@ -244,6 +325,25 @@ impl SysLinuxChip {
         builder.when(local.is_mmap).when_not(local.is_a0_0).assert_word_eq(local.a0, local.result);
     }
 
+    fn eval_set_thread_area<AB: ZKMAirBuilder>(
+        &self,
+        builder: &mut AB,
+        local: &SysLinuxCols<AB::Var>,
+    ) {
+        // The thread pointer is set to a0.
+        builder.eval_memory_access(
+            local.shard,
+            local.clk,
+            AB::Expr::from_canonical_u32(Register::LOCAL_USER as u32),
+            &local.inorout,
+            local.is_set_thread_area,
+        );
+        builder.when(local.is_set_thread_area).assert_word_eq(*local.inorout.value(), local.a0);
+
+        builder.when(local.is_set_thread_area).assert_word_zero(*local.output.value());
+        builder.when(local.is_set_thread_area).assert_word_zero(local.result);
+    }
+
     fn eval_exit_group<AB: ZKMAirBuilder>(&self, builder: &mut AB, local: &SysLinuxCols<AB::Var>) {
         builder.when(local.is_exit_group).assert_word_zero(*local.output.value());
     }
@@ -269,11 +369,11 @@ impl SysLinuxChip {
         builder
             .when(local.is_fnctl_a1_3)
             .when_not(local.is_a0_0 + local.is_a0_1 + local.is_a0_2)
-            .assert_word_eq(local.result, Word::<AB::Expr>::from(0xFFFFFFFFu32));
+            .assert_word_eq(local.result, Word::<AB::Expr>::from(9u32));
         builder
             .when(local.is_fnctl)
             .when_not(local.is_a1_3 + local.is_a1_1)
-            .assert_word_eq(local.result, Word::<AB::Expr>::from(0xFFFFFFFFu32));
+            .assert_word_eq(local.result, Word::<AB::Expr>::from(9u32));
 
         builder
             .when(local.is_fnctl_a1_3 + local.is_fnctl_a1_1)
@@ -282,25 +382,244 @@ impl SysLinuxChip {
         builder
             .when(local.is_fnctl_a1_3 + local.is_fnctl_a1_1)
             .when_not(local.is_a0_0 + local.is_a0_1 + local.is_a0_2)
-            .assert_word_eq(*local.output.value(), Word::<AB::Expr>::from(9u32));
+            .assert_word_eq(*local.output.value(), Word::<AB::Expr>::from(1u32));
         builder
             .when(local.is_fnctl)
             .when_not(local.is_a1_3 + local.is_a1_1)
-            .assert_word_eq(*local.output.value(), Word::<AB::Expr>::from(0x9u32));
+            .assert_word_eq(*local.output.value(), Word::<AB::Expr>::from(1u32));
     }
 
     fn eval_read<AB: ZKMAirBuilder>(&self, builder: &mut AB, local: &SysLinuxCols<AB::Var>) {
-        builder.when(local.is_read).when(local.is_a0_0).assert_word_zero(local.result);
-        builder.when(local.is_read).when(local.is_a0_0).assert_word_zero(*local.output.value());
+        // The number of bytes to read is in a2.
+        builder.eval_memory_access(
+            local.shard,
+            local.clk,
+            AB::Expr::from_canonical_u32(Register::A2 as u32),
+            &local.inorout,
+            local.is_read,
+        );
+        builder
+            .when(local.is_read)
+            .assert_word_eq(*local.inorout.value(), local.inorout.prev_value);
 
+        // A successful read returns at most the requested number of bytes, all of which are
+        // written to the buffer.
+        GtColsBytes::<AB::F>::eval(
+            builder,
+            local.result,
+            *local.inorout.value(),
+            local.is_read,
+            local.is_result_gt_count,
+        );
         builder
             .when(local.is_read)
-            .when_not(local.is_a0_0)
-            .assert_word_eq(local.result, Word::<AB::Expr>::from(0xFFFFFFFFu32));
+            .when(local.writes_buf)
+            .assert_zero(local.is_result_gt_count.result);
+        builder.when(local.is_read).when(local.writes_buf).assert_zero(local.result[3]);
+
+        // Reads that neither fail nor write the buffer return 0 (end of file).
         builder
             .when(local.is_read)
-            .when_not(local.is_a0_0)
-            .assert_word_eq(*local.output.value(), Word::<AB::Expr>::from(9));
+            .when_not(local.writes_buf + local.is_error)
+            .assert_word_zero(local.result);
+    }
+
+    /// Constrains the syscalls served by the virtual filesystem.
+    ///
+    /// The file table lives outside of the trace, so the file descriptors and offsets returned by
+    /// `open`, `openat`, `lseek` and `close` are untrusted input, just like the file contents.
+    /// The constraints only pin down the error convention, see [`Self::eval_errors`].
+    fn eval_vfs<AB: ZKMAirBuilder>(&self, builder: &mut AB, local: &SysLinuxCols<AB::Var>) {
+        // fstat64 either fails or fills the stat buffer, which must be word-aligned.
+        builder.when(local.is_fstat).assert_one(local.writes_buf + local.is_error);
+        builder.when(local.is_fstat).when(local.writes_buf).assert_word_zero(local.result);
+        builder.when(local.is_fstat).when(local.writes_buf).assert_one(local.buf_offset[0]);
+    }
+
+    /// Constrains the syscalls of the Linux personality.
+    ///
+    /// Like file contents, the time and the random bytes are chosen by the host, so only the
+    /// shape of the results is constrained: the clocks and `uname` fill a buffer of fixed size,
+    /// `getrandom` fills at most the requested number of bytes, and `futex` returns 0.
+    fn eval_personality<AB: ZKMAirBuilder>(&self, builder: &mut AB, local: &SysLinuxCols<AB::Var>) {
+        let fixed_size = [
+            (local.is_clock_gettime, TIMESPEC_SIZE),
+            (local.is_clock_gettime64, TIMESPEC64_SIZE),
+            (local.is_uname, UTSNAME_SIZE),
+        ];
+        for (flag, size) in fixed_size {
+            builder.when(flag).assert_one(local.writes_buf + local.is_error);
+            builder.when(flag).when(local.writes_buf).assert_word_zero(local.result);
+            builder
+                .when(flag)
+                .when(local.writes_buf)
+                .assert_eq(local.buf_len, AB::Expr::from_canonical_u32(size));
+        }
+
+        builder.when(local.is_futex).assert_zero(local.writes_buf);
+        builder.when(local.is_futex).when_not(local.is_error).assert_word_zero(local.result);
+
+        GtColsBytes::<AB::F>::eval(
+            builder,
+            local.result,
+            local.a1,
+            local.is_getrandom,
+            local.is_result_gt_len,
+        );
+        builder
+            .when(local.is_getrandom)
+            .when(local.writes_buf)
+            .assert_zero(local.is_result_gt_len.result);
+        builder.when(local.is_getrandom).when(local.writes_buf).assert_zero(local.result[3]);
+        builder
+            .when(local.is_getrandom)
+            .when_not(local.writes_buf + local.is_error)
+            .assert_word_zero(local.result);
+    }
+
+    /// Constrains the error convention of the syscalls that can fail: failures set a3 to 1 and
+    /// return the errno, and successes clear a3.
+    fn eval_errors<AB: ZKMAirBuilder>(&self, builder: &mut AB, local: &SysLinuxCols<AB::Var>) {
+        let is_fallible = local.is_read
+            + local.is_open
+            + local.is_openat
+            + local.is_close
+            + local.is_lseek
+            + local.is_fstat
+            + local.is_uname
+            + local.is_futex
+            + local.is_clock_gettime
+            + local.is_clock_gettime64
+            + local.is_getrandom;
+
+        builder.when(local.is_error).assert_one(is_fallible.clone());
+        builder
+            .when(local.is_error)
+            .assert_word_eq(*local.output.value(), Word::<AB::Expr>::from(1u32));
+        builder.when(is_fallible).when_not(local.is_error).assert_word_zero(*local.output.value());
+    }
+
+    /// Constrains the buffers written by `read`, `fstat64` and the personality syscalls.
+    ///
+    /// The syscall row sends the first word of the buffer as the message
+    /// `(shard, clk, SYS_LINUX, addr, len + offset * 2^24)`, where `addr` is the word-aligned
+    /// address, `offset` the position of the first byte in that word, and `len` the number of
+    /// bytes left. Each buffer word row receives one such message, writes the bytes of its word
+    /// that are in range, and sends the message for the next word unless it is the last one. The
+    /// written bytes are untrusted input, like the data written by `SYSHINTREAD`.
+    fn eval_buf<AB: ZKMAirBuilder>(&self, builder: &mut AB, local: &SysLinuxCols<AB::Var>) {
+        let buf_word_id = AB::Expr::from_canonical_u32(SyscallCode::SYS_LINUX as u32);
+        let offset_shift = AB::Expr::from_canonical_u32(1 << 24);
+
+        for flag in local.buf_offset.into_iter().chain(local.buf_end) {
+            builder.assert_bool(flag);
+        }
+        let offset = (0..4).fold(AB::Expr::zero(), |acc, i| {
+            acc + local.buf_offset[i] * AB::Expr::from_canonical_usize(i)
+        });
+        let offset_flags = local.buf_offset.into_iter().map(Into::into).sum::<AB::Expr>();
+        let is_last = local.buf_end.into_iter().map(Into::into).sum::<AB::Expr>();
+
+        builder.when(local.writes_buf).assert_one(
+            local.is_read
+                + local.is_fstat
+                + local.is_uname
+                + local.is_clock_gettime
+                + local.is_clock_gettime64
+                + local.is_getrandom,
+        );
+        builder.when(local.writes_buf).assert_zero(local.is_error);
+        builder.when(local.writes_buf + local.is_buf_word).assert_one(offset_flags.clone());
+        builder.when_not(local.writes_buf + local.is_buf_word).assert_zero(offset_flags);
+        builder.assert_bool(is_last.clone());
+        builder.when_not(local.is_buf_word).assert_zero(is_last.clone());
+
+        // The syscall row derives the aligned address and the offset from the buffer pointer,
+        // which is range checked so that its bytes are canonical.
+        let buf_in_a0 = local.is_uname + local.is_getrandom;
+        builder
+            .when(local.writes_buf)
+            .when(buf_in_a0.clone())
+            .assert_word_eq(local.buf_ptr, local.a0);
+        builder.when(local.writes_buf).when_not(buf_in_a0).assert_word_eq(local.buf_ptr, local.a1);
+        KoalaBearWordRangeChecker::<AB::F>::range_check(
+            builder,
+            local.buf_ptr,
+            local.buf_ptr_range_checker,
+            local.writes_buf.into(),
+        );
+        builder.slice_range_check_u8(&local.buf_ptr.0[1..3], local.writes_buf);
+        builder.send_byte(
+            ByteOpcode::AND.as_field::<AB::F>(),
+            offset.clone(),
+            local.buf_ptr[0],
+            AB::Expr::from_canonical_u8(0b11),
+            local.writes_buf,
+        );
+        builder
+            .when(local.is_read + local.is_getrandom)
+            .when(local.writes_buf)
+            .assert_eq(local.buf_len, local.result.reduce::<AB>());
+        builder
+            .when(local.is_fstat)
+            .when(local.writes_buf)
+            .assert_eq(local.buf_len, AB::Expr::from_canonical_u32(STAT64_SIZE));
+        builder.send_syscall(
+            local.shard,
+            local.clk,
+            buf_word_id.clone(),
+            local.buf_ptr.reduce::<AB>() - offset.clone(),
+            local.buf_len + offset.clone() * offset_shift.clone(),
+            local.writes_buf,
+            LookupScope::Local,
+        );
+
+        // The buffer word rows.
+        builder.receive_syscall(
+            local.shard,
+            local.clk,
+            buf_word_id.clone(),
+            local.buf_addr,
+            local.buf_len + offset.clone() * offset_shift,
+            local.is_buf_word,
+            LookupScope::Local,
+        );
+        builder.eval_memory_access(
+            local.shard,
+            local.clk,
+            local.buf_addr,
+            &local.inorout,
+            local.is_buf_word,
+        );
+        builder.slice_range_check_u8(&local.inorout.value().0, local.is_buf_word);
+
+        // If this is the last word, the buffer ends at position `end` within it.
+        let end = (0..4).fold(AB::Expr::zero(), |acc, i| {
+            acc + local.buf_end[i] * AB::Expr::from_canonical_usize(i + 1)
+        });
+        builder
+            .when(local.is_buf_word)
+            .assert_eq(is_last.clone() * (local.buf_len + offset.clone()), end);
+
+        // Byte `i` is outside of the buffer if it comes before the offset or after the end.
+        for i in 0..4 {
+            let before_start = (i + 1..4).map(|j| local.buf_offset[j].into()).sum::<AB::Expr>();
+            let after_end = (0..i).map(|j| local.buf_end[j].into()).sum::<AB::Expr>();
+            builder
+                .when(local.is_buf_word)
+                .when(before_start + after_end)
+                .assert_eq(local.inorout.value()[i], local.inorout.prev_value[i]);
+        }
+
+        builder.send_syscall(
+            local.shard,
+            local.clk,
+            buf_word_id,
+            local.buf_addr + AB::Expr::from_canonical_u32(4),
+            local.buf_len + offset - AB::Expr::from_canonical_u32(4),
+            local.is_buf_word - is_last,
+            LookupScope::Local,
+        );
     }
 
     fn eval_write<AB: ZKMAirBuilder>(&self, builder: &mut AB, local: &SysLinuxCols<AB::Var>) {
@@ -3,7 +3,10 @@ use std::mem::size_of;
 use zkm_derive::AlignedBorrow;
 use zkm_stark::Word;
 
-use crate::{memory::MemoryReadWriteCols, operations::GtColsBytes};
+use crate::{
+    memory::MemoryReadWriteCols,
+    operations::{GtColsBytes, KoalaBearWordRangeChecker},
+};
 
 pub const NUM_SYS_LINUX_COLS: usize = size_of::<SysLinuxCols<u8>>();
 
@@ -51,6 +54,46 @@ pub struct SysLinuxCols<T> {
 
     /// Columns for sys read
     pub is_read: T,
+    pub is_result_gt_count: GtColsBytes<T>,
+
+    /// Columns for the virtual filesystem syscalls
+    pub is_open: T,
+    pub is_openat: T,
+    pub is_close: T,
+    pub is_lseek: T,
+    pub is_fstat: T,
+    /// Whether the syscall failed, in which case the result is the errno and A3 is 1.
+    pub is_error: T,
+
+    /// Columns for the Linux personality syscalls
+    pub is_uname: T,
+    pub is_futex: T,
+    pub is_clock_gettime: T,
+    pub is_clock_gettime64: T,
+    pub is_getrandom: T,
+    pub is_result_gt_len: GtColsBytes<T>,
+
+    /// Columns for the buffer filled by sys read, sys fstat64 and the personality syscalls.
+    /// Whether the row sends the first word of the buffer.
+    pub writes_buf: T,
+    /// The address of the buffer, which is a0 for uname and getrandom and a1 otherwise.
+    pub buf_ptr: Word<T>,
+    pub buf_ptr_range_checker: KoalaBearWordRangeChecker<T>,
+    /// One-hot encoding of the offset of the first buffer byte within its word.
+    pub buf_offset: [T; 4],
+
+    /// Columns for the rows writing one word of a buffer.
+    pub is_buf_word: T,
+    /// The word-aligned address written by the row.
+    pub buf_addr: T,
+    /// The number of buffer bytes left, counted from the offset given by `buf_offset`. On the
+    /// syscall row, the length of the whole buffer.
+    pub buf_len: T,
+    /// One-hot encoding of the end of the buffer within the word, if this is the last word.
+    pub buf_end: [T; 4],
+
+    /// Columns for sys set_thread_area
+    pub is_set_thread_area: T,
 
     /// Columns for sys write
     pub is_write: T,
@@ -14,10 +14,17 @@ impl SysLinuxChip {
 #[cfg(test)]
 pub mod sys_linux_tests {
 
-    use zkm_core_executor::{syscalls::SyscallCode, Instruction, Opcode, Program};
+    use zkm_core_executor::{
+        syscalls::SyscallCode,
+        vfs::{errno, FD_VFS_START},
+        Executor, Instruction, Opcode, Program, Register, ZKMCoreOpts,
+    };
     use zkm_stark::CpuProver;
 
-    use crate::utils::{run_test, setup_logger};
+    use crate::{
+        io::ZKMStdin,
+        utils::{run_test, run_test_io, setup_logger},
+    };
 
     pub fn sys_linux_program() -> Program {
         let w_ptr = 100;
@@ -178,6 +185,188 @@ pub mod sys_linux_tests {
         Program::new(instructions, 0, 0)
     }
 
+    fn syscall(code: SyscallCode, a0: u32, a1: u32, a2: u32) -> Vec<Instruction> {
+        vec![
+            Instruction::new(Opcode::ADD, 2, 0, code as u32, false, true),
+            Instruction::new(Opcode::ADD, 4, 0, a0, false, true),
+            Instruction::new(Opcode::ADD, 5, 0, a1, false, true),
+            Instruction::new(Opcode::ADD, 6, 0, a2, false, true),
+            Instruction::new(Opcode::SYSCALL, 2, 4, 5, false, false),
+        ]
+    }
+
+    /// Opens `a.txt`, reads it in two parts into unaligned buffers, stats it and reads 7 bytes
+    /// from stdin.
+    pub fn sys_vfs_program() -> Program {
+        let path_ptr = 0x1000;
+        let fd = FD_VFS_START;
+        let mut instructions = vec![
+            // "a.txt\0"
+            Instruction::new(Opcode::ADD, 30, 0, path_ptr, false, true),
+            Instruction::new(Opcode::ADD, 29, 0, 0x78742e61, false, true),
+            Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+            Instruction::new(Opcode::ADD, 29, 0, 0x74, false, true),
+            Instruction::new(Opcode::SW, 29, 30, 4, false, true),
+        ];
+        instructions.extend(syscall(SyscallCode::SYS_OPEN, path_ptr, 0, 0));
+        instructions.extend(syscall(SyscallCode::SYS_READ, fd, 0x2001, 7));
+        instructions.extend(syscall(SyscallCode::SYS_READ, fd, 0x2102, 100));
+        instructions.extend(syscall(SyscallCode::SYS_LSEEK, fd, -5i32 as u32, 2));
+        instructions.extend(syscall(SyscallCode::SYS_READ, fd, 0x2203, 1));
+        instructions.extend(syscall(SyscallCode::SYS_FSTAT64, fd, 0x3000, 0));
+        instructions.extend(syscall(SyscallCode::SYS_CLOSE, fd, 0, 0));
+        instructions.extend(syscall(SyscallCode::SYS_READ, fd, 0x2300, 1));
+        instructions.extend(syscall(SyscallCode::SYS_OPENAT, -100i32 as u32, 0x1000, 1));
+        instructions.extend(syscall(SyscallCode::SYS_READ, 0, 0x2401, 6));
+        instructions.extend(syscall(SyscallCode::SYS_READ, 0, 0x2407, 6));
+        instructions.extend(syscall(SyscallCode::SYS_EXT_GROUP, 0, 0, 0));
+        Program::new(instructions, 0, 0)
+    }
+
+    fn sys_vfs_stdin() -> ZKMStdin {
+        let mut stdin = ZKMStdin::new();
+        stdin.write_file("/a.txt", b"hello, world".to_vec());
+        stdin.write_vec(vec![1, 2, 3]);
+        stdin.write_vec(vec![4, 5, 6, 7]);
+        stdin
+    }
+
+    #[test]
+    fn test_sys_vfs_execute() {
+        let stdin = sys_vfs_stdin();
+        let mut runtime = Executor::new(sys_vfs_program(), ZKMCoreOpts::default());
+        runtime.write_vecs(&stdin.buffer);
+        runtime.write_files(&stdin.files);
+        runtime.set_linux_env(stdin.linux_env);
+        runtime.run().unwrap();
+
+        let mut bytes =
+            |addr: u32, len: u32| (addr..addr + len).map(|a| runtime.byte(a)).collect::<Vec<_>>();
+        assert_eq!(bytes(0x2000, 9), b"\0hello, \0");
+        assert_eq!(bytes(0x2102, 6), b"world\0");
+        assert_eq!(bytes(0x2203, 1), b"w");
+        assert_eq!(bytes(0x3018, 4), 0o100444u32.to_le_bytes());
+        assert_eq!(bytes(0x3038, 8), 12u64.to_le_bytes());
+        assert_eq!(bytes(0x2300, 1), [0]);
+        assert_eq!(bytes(0x2401, 7), [1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(runtime.state.input_stream_ptr, 2);
+    }
+
+    /// Reads the clocks, random bytes and kernel name into unaligned buffers, waits on and wakes
+    /// a futex, and sets and reads back the thread pointer.
+    pub fn sys_personality_program() -> Program {
+        let futex_ptr = 0x4000;
+        let mut instructions = vec![
+            Instruction::new(Opcode::ADD, 30, 0, futex_ptr, false, true),
+            Instruction::new(Opcode::ADD, 29, 0, 5, false, true),
+            Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+        ];
+        instructions.extend(syscall(SyscallCode::SYS_CLOCK_GETTIME, 0, 0x2001, 0));
+        instructions.extend(syscall(SyscallCode::SYS_CLOCK_GETTIME64, 1, 0x2100, 0));
+        instructions.extend(syscall(SyscallCode::SYS_CLOCK_GETTIME, 99, 0x2200, 0));
+        instructions.extend(syscall(SyscallCode::SYS_GETRANDOM, 0x3003, 10, 0));
+        instructions.extend(syscall(SyscallCode::SYS_GETRANDOM, 0x3103, 10, 8));
+        instructions.extend(syscall(SyscallCode::SYS_UNAME, 0x3202, 0, 0));
+        instructions.push(Instruction::new(Opcode::ADD, 7, 0, 0, false, true));
+        instructions.extend(syscall(SyscallCode::SYS_FUTEX, futex_ptr, 128, 4));
+        instructions.push(Instruction::new(Opcode::ADD, 7, 0, 0, false, true));
+        instructions.extend(syscall(SyscallCode::SYS_FUTEX, futex_ptr, 129, 1));
+        instructions.extend(syscall(SyscallCode::SYS_SET_THREAD_AREA, 0x7008, 0, 0));
+        // rdhwr $3, $29
+        instructions.push(Instruction::new(
+            Opcode::ADD,
+            3,
+            Register::LOCAL_USER as u32,
+            0,
+            false,
+            true,
+        ));
+        instructions.push(Instruction::new(Opcode::SW, 3, 30, 4, false, true));
+        instructions.extend(syscall(SyscallCode::SYS_SCHED_YIELD, 0, 0, 0));
+        instructions.extend(syscall(SyscallCode::SYS_EXT_GROUP, 0, 0, 0));
+        Program::new(instructions, 0, 0)
+    }
+
+    /// Issues failing syscalls and stores the v0 and a3 registers after each of them.
+    pub fn sys_errors_program() -> Program {
+        let out_ptr = 0x5000;
+        let failing = [
+            (SyscallCode::SYS_CLOSE, 100, 0, 0),
+            (SyscallCode::SYS_READ, 100, 0x2000, 4),
+            (SyscallCode::SYS_FCNTL, 100, 3, 0),
+            (SyscallCode::SYS_FCNTL, 100, 1, 0),
+            (SyscallCode::SYS_FCNTL, 0, 2, 0),
+            (SyscallCode::SYS_CLOCK_GETTIME, 99, 0x2100, 0),
+        ];
+        let mut instructions = vec![Instruction::new(Opcode::ADD, 30, 0, out_ptr, false, true)];
+        for (i, (code, a0, a1, a2)) in failing.into_iter().enumerate() {
+            instructions.extend(syscall(code, a0, a1, a2));
+            instructions.push(Instruction::new(Opcode::SW, 2, 30, i as u32 * 8, false, true));
+            instructions.push(Instruction::new(Opcode::SW, 7, 30, i as u32 * 8 + 4, false, true));
+        }
+        instructions.extend(syscall(SyscallCode::SYS_EXT_GROUP, 0, 0, 0));
+        Program::new(instructions, 0, 0)
+    }
+
+    #[test]
+    fn test_sys_errors_execute() {
+        let mut runtime = Executor::new(sys_errors_program(), ZKMCoreOpts::default());
+        runtime.run().unwrap();
+
+        // Failures return the errno in v0 and set a3 to 1, like the o32 kernel ABI.
+        let (ebadf, einval) = (errno::EBADF, errno::EINVAL);
+        let results = (0..6).map(|i| (runtime.word(0x5000 + i * 8), runtime.word(0x5004 + i * 8)));
+        assert_eq!(
+            results.collect::<Vec<_>>(),
+            [(ebadf, 1), (ebadf, 1), (ebadf, 1), (ebadf, 1), (ebadf, 1), (einval, 1)]
+        );
+    }
+
+    #[test]
+    fn prove_sys_errors_koalabear() {
+        setup_logger();
+        run_test::<CpuProver<_, _>>(sys_errors_program()).unwrap();
+    }
+
+    fn sys_personality_stdin() -> ZKMStdin {
+        let mut stdin = ZKMStdin::new();
+        stdin.set_realtime(1_700_000_000_000_000_000);
+        stdin.set_random_seed([3; 32]);
+        stdin
+    }
+
+    #[test]
+    fn test_sys_personality_execute() {
+        let stdin = sys_personality_stdin();
+        let mut runtime = Executor::new(sys_personality_program(), ZKMCoreOpts::default());
+        runtime.set_linux_env(stdin.linux_env);
+        runtime.run().unwrap();
+
+        let mut bytes =
+            |addr: u32, len: u32| (addr..addr + len).map(|a| runtime.byte(a)).collect::<Vec<_>>();
+        assert_eq!(bytes(0x2001, 4), 1_700_000_000u32.to_le_bytes());
+        assert_eq!(bytes(0x2100, 8), 0u64.to_le_bytes());
+        assert_ne!(bytes(0x2108, 8), 0u64.to_le_bytes());
+        assert_eq!(bytes(0x2200, 4), [0; 4]);
+        assert_ne!(bytes(0x3003, 10), [0; 10]);
+        assert_eq!(bytes(0x3103, 10), [0; 10]);
+        assert_eq!(bytes(0x3202, 6), b"Linux\0");
+        assert_eq!(bytes(0x4004, 4), 0x7008u32.to_le_bytes());
+        assert_eq!(runtime.state.linux.random_offset, 10);
+    }
+
+    #[test]
+    fn prove_sys_personality_koalabear() {
+        setup_logger();
+        run_test_io::<CpuProver<_, _>>(sys_personality_program(), sys_personality_stdin()).unwrap();
+    }
+
+    #[test]
+    fn prove_sys_vfs_koalabear() {
+        setup_logger();
+        run_test_io::<CpuProver<_, _>>(sys_vfs_program(), sys_vfs_stdin()).unwrap();
+    }
+
     #[test]
     fn prove_koalabear() {
         setup_logger();
@@ -8,7 +8,7 @@ use p3_maybe_rayon::prelude::{IntoParallelRefIterator, ParallelIterator, Paralle
 use zkm_core_executor::{
     events::{ByteLookupEvent, ByteRecord, LinuxEvent, PrecompileEvent},
     syscalls::SyscallCode,
-    ExecutionRecord, Program,
+    ByteOpcode, ExecutionRecord, Program,
 };
 use zkm_stark::air::MachineAir;
 
@@ -38,18 +38,15 @@ impl<F: PrimeField32> MachineAir<F> for SysLinuxChip {
 
         let mut rows = events
             .par_iter()
-            .map(|(_, event)| {
+            .flat_map_iter(|(_, event)| {
                 let event = if let PrecompileEvent::Linux(event) = event {
                     event
                 } else {
                     unreachable!();
                 };
 
-                let mut row = [F::ZERO; NUM_SYS_LINUX_COLS];
-                let cols: &mut SysLinuxCols<F> = row.as_mut_slice().borrow_mut();
                 let mut blu = Vec::new();
-                self.event_to_row(event, cols, &mut blu);
-                row
+                self.event_to_rows(event, &mut blu)
             })
             .collect::<Vec<_>>();
 
@@ -81,9 +78,7 @@ impl<F: PrimeField32> MachineAir<F> for SysLinuxChip {
                     } else {
                         unreachable!()
                     };
-                    let mut row = [F::ZERO; NUM_SYS_LINUX_COLS];
-                    let cols: &mut SysLinuxCols<F> = row.as_mut_slice().borrow_mut();
-                    self.event_to_row(event, cols, &mut blu);
+                    self.event_to_rows::<F>(event, &mut blu);
                 });
                 blu
             })
@@ -103,6 +98,45 @@ impl<F: PrimeField32> MachineAir<F> for SysLinuxChip {
 }
 
 impl SysLinuxChip {
+    /// Generate the syscall row of the event, followed by one row per buffer word it writes.
+    fn event_to_rows<F: PrimeField32>(
+        &self,
+        event: &LinuxEvent,
+        blu: &mut impl ByteRecord,
+    ) -> Vec<[F; NUM_SYS_LINUX_COLS]> {
+        let mut rows = Vec::with_capacity(event.num_rows());
+        let mut row = [F::ZERO; NUM_SYS_LINUX_COLS];
+        let cols: &mut SysLinuxCols<F> = row.as_mut_slice().borrow_mut();
+        self.event_to_row(event, cols, blu);
+        rows.push(row);
+
+        let mut addr = event.buf_addr & !3;
+        let mut offset = event.buf_addr & 3;
+        let mut len = event.buf_len;
+        for record in event.buf_records.iter() {
+            let mut row = [F::ZERO; NUM_SYS_LINUX_COLS];
+            let cols: &mut SysLinuxCols<F> = row.as_mut_slice().borrow_mut();
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.is_buf_word = F::ONE;
+            cols.is_real = F::ONE;
+            cols.buf_addr = F::from_canonical_u32(addr);
+            cols.buf_len = F::from_canonical_u32(len);
+            cols.buf_offset[offset as usize] = F::ONE;
+            if offset + len <= 4 {
+                cols.buf_end[(offset + len - 1) as usize] = F::ONE;
+            }
+            cols.inorout.populate_write(*record, blu);
+            blu.add_u8_range_checks(&record.value.to_le_bytes());
+            rows.push(row);
+
+            len = (offset + len).saturating_sub(4);
+            offset = 0;
+            addr += 4;
+        }
+        rows
+    }
+
     fn event_to_row<F: PrimeField32>(
         &self,
         event: &LinuxEvent,
@@ -153,7 +187,46 @@ impl SysLinuxChip {
                 }
             }
             4003 => {
+                assert!(event.read_records.len() == 1);
                 cols.is_read = F::ONE;
+                cols.inorout.populate_read(event.read_records[0], blu);
+                cols.is_result_gt_count.populate(event.v0, event.read_records[0].value, blu);
+            }
+            4005 => {
+                cols.is_open = F::ONE;
+            }
+            4288 => {
+                cols.is_openat = F::ONE;
+            }
+            4006 => {
+                cols.is_close = F::ONE;
+            }
+            4019 => {
+                cols.is_lseek = F::ONE;
+            }
+            4215 => {
+                cols.is_fstat = F::ONE;
+            }
+            4122 => {
+                cols.is_uname = F::ONE;
+            }
+            4238 => {
+                cols.is_futex = F::ONE;
+            }
+            4263 => {
+                cols.is_clock_gettime = F::ONE;
+            }
+            4403 => {
+                cols.is_clock_gettime64 = F::ONE;
+            }
+            4353 => {
+                cols.is_getrandom = F::ONE;
+                cols.is_result_gt_len.populate(event.v0, event.a1, blu);
+            }
+            4283 => {
+                assert!(event.write_records.len() == 2);
+                cols.is_set_thread_area = F::ONE;
+                cols.inorout.populate_write(event.write_records[1], blu);
             }
             4004 => {
                 assert!(event.read_records.len() == 1);
@@ -164,5 +237,31 @@ impl SysLinuxChip {
                 cols.is_nop = F::ONE;
             }
         };
+
+        if matches!(
+            event.syscall_code,
+            4003 | 4005 | 4006 | 4019 | 4122 | 4215 | 4238 | 4263 | 4288 | 4353 | 4403
+        ) {
+            cols.is_error = F::from_bool(event.write_records[0].value != 0);
+        }
+        if !event.buf_records.is_empty() {
+            let buf_ptr =
+                if matches!(event.syscall_code, 4122 | 4353) { event.a0 } else { event.a1 };
+            let offset = buf_ptr & 3;
+            cols.writes_buf = F::ONE;
+            cols.buf_ptr = buf_ptr.into();
+            cols.buf_offset[offset as usize] = F::ONE;
+            cols.buf_len = F::from_canonical_u32(event.buf_len);
+            cols.buf_ptr_range_checker.populate(buf_ptr);
+            let buf_ptr_bytes = buf_ptr.to_le_bytes();
+            blu.add_u8_range_checks(&buf_ptr_bytes[1..3]);
+            blu.add_byte_lookup_event(ByteLookupEvent {
+                opcode: ByteOpcode::AND,
+                a1: offset as u16,
+                a2: 0,
+                b: buf_ptr_bytes[0],
+                c: 0b11,
+            });
+        }
     }
 }
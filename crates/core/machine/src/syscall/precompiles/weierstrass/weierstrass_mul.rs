@@ -0,0 +1,860 @@
+use core::{
+    borrow::{Borrow, BorrowMut},
+    mem::size_of,
+};
+use std::{fmt::Debug, marker::PhantomData};
+
+use crate::{air::MemoryAirBuilder, utils::zeroed_f_vec, CoreChipError};
+use generic_array::GenericArray;
+use num::{BigUint, One, Zero};
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::{FieldAlgebra, PrimeField32};
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use p3_maybe_rayon::prelude::{IntoParallelRefIterator, ParallelBridge, ParallelIterator};
+use typenum::Unsigned;
+use zkm_core_executor::{
+    events::{ByteLookupEvent, ByteRecord, EllipticCurveMulEvent, FieldOperation, PrecompileEvent},
+    syscalls::SyscallCode,
+    ExecutionRecord, Program,
+};
+use zkm_curves::{
+    params::{FieldParameters, Limbs, NumLimbs, NumWords},
+    weierstrass::WeierstrassParameters,
+    AffinePoint, CurveType, EllipticCurve,
+};
+use zkm_derive::AlignedBorrow;
+use zkm_primitives::consts::words_to_bytes_le_vec;
+use zkm_stark::air::{LookupScope, MachineAir, Polynomial, ZKMAirBuilder};
+
+use crate::{
+    memory::{MemoryCols, MemoryReadCols, MemoryWriteCols},
+    operations::field::field_op::FieldOpCols,
+    utils::{limbs_from_access, limbs_from_prev_access, next_power_of_two},
+};
+
+pub const fn num_weierstrass_mul_cols<P: FieldParameters + NumWords>() -> usize {
+    size_of::<WeierstrassMulAssignCols<u8, P>>()
+}
+
+/// A set of columns to multiply a point on a Weierstrass curve by a scalar.
+///
+/// An event spans one row per bit of the scalar, least significant bit first. Each row doubles the
+/// running base point `2^i * P` and, when the bit is set, adds it to the accumulator. The scalar is
+/// read on the first row of the event and the result is written on the last one.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct WeierstrassMulAssignCols<T, P: FieldParameters + NumWords> {
+    pub is_real: T,
+    pub shard: T,
+    pub clk: T,
+    pub p_ptr: T,
+    pub s_ptr: T,
+
+    /// One-hot selector of the scalar byte processed by this row.
+    pub byte_selector: GenericArray<T, P::Limbs>,
+    /// One-hot selector of the bit of that byte processed by this row.
+    pub bit_selector: [T; 8],
+    /// Whether this row processes the first bit of the scalar.
+    pub is_first: T,
+    /// Whether this row processes the last bit of the scalar.
+    pub is_last: T,
+    pub is_first_real: T,
+    pub is_last_real: T,
+
+    pub s_access: GenericArray<MemoryReadCols<T>, P::WordsFieldElement>,
+    pub p_access: GenericArray<MemoryWriteCols<T>, P::WordsCurvePoint>,
+
+    /// The scalar and the input point, copied across the rows of an event.
+    pub scalar: Limbs<T, P::Limbs>,
+    pub p_x: Limbs<T, P::Limbs>,
+    pub p_y: Limbs<T, P::Limbs>,
+
+    /// The bits of the selected scalar byte, and the selected bit.
+    pub byte_bits: [T; 8],
+    pub bit: T,
+
+    pub acc_x: Limbs<T, P::Limbs>,
+    pub acc_y: Limbs<T, P::Limbs>,
+    pub acc_is_infinity: T,
+    pub base_x: Limbs<T, P::Limbs>,
+    pub base_y: Limbs<T, P::Limbs>,
+
+    /// `is_real * bit * (1 - acc_is_infinity)`: the base is added to the accumulator.
+    pub do_add: T,
+    /// `is_real * bit * acc_is_infinity`: the base replaces the accumulator.
+    pub take_base: T,
+
+    pub(crate) add_slope_numerator: FieldOpCols<T, P>,
+    pub(crate) add_slope_denominator: FieldOpCols<T, P>,
+    pub(crate) add_slope: FieldOpCols<T, P>,
+    pub(crate) add_slope_squared: FieldOpCols<T, P>,
+    pub(crate) add_p_x_plus_q_x: FieldOpCols<T, P>,
+    pub(crate) add_x3_ins: FieldOpCols<T, P>,
+    pub(crate) add_p_x_minus_x: FieldOpCols<T, P>,
+    pub(crate) add_slope_times_p_x_minus_x: FieldOpCols<T, P>,
+    pub(crate) add_y3_ins: FieldOpCols<T, P>,
+
+    pub(crate) double_slope_denominator: FieldOpCols<T, P>,
+    pub(crate) double_slope_numerator: FieldOpCols<T, P>,
+    pub(crate) double_slope: FieldOpCols<T, P>,
+    pub(crate) double_p_x_squared: FieldOpCols<T, P>,
+    pub(crate) double_p_x_squared_times_3: FieldOpCols<T, P>,
+    pub(crate) double_slope_squared: FieldOpCols<T, P>,
+    pub(crate) double_p_x_plus_p_x: FieldOpCols<T, P>,
+    pub(crate) double_x3_ins: FieldOpCols<T, P>,
+    pub(crate) double_p_x_minus_x: FieldOpCols<T, P>,
+    pub(crate) double_y3_ins: FieldOpCols<T, P>,
+    pub(crate) double_slope_times_p_x_minus_x: FieldOpCols<T, P>,
+}
+
+#[derive(Default)]
+pub struct WeierstrassMulAssignChip<E> {
+    _marker: PhantomData<E>,
+}
+
+impl<E: EllipticCurve + WeierstrassParameters> WeierstrassMulAssignChip<E> {
+    pub const fn new() -> Self {
+        Self { _marker: PhantomData }
+    }
+
+    /// The number of rows used by a single scalar multiplication, one per bit of the scalar.
+    pub fn rows_per_event() -> usize {
+        8 * E::BaseField::NB_LIMBS
+    }
+
+    pub fn syscall_code() -> SyscallCode {
+        match E::CURVE_TYPE {
+            CurveType::Secp256k1 => SyscallCode::SECP256K1_MUL,
+            CurveType::Secp256r1 => SyscallCode::SECP256R1_MUL,
+            CurveType::Bn254 => SyscallCode::BN254_MUL,
+            CurveType::Bls12381 => SyscallCode::BLS12381_MUL,
+            _ => panic!("Unsupported curve"),
+        }
+    }
+
+    /// Populates the columns adding `q` to `p`, returning the sum.
+    ///
+    /// The slope division is scaled by `do_add` so that it is satisfiable on rows which do not use
+    /// the sum, where `p` may be the accumulator placeholder or share its x coordinate with `q`.
+    fn populate_add_ops<F: PrimeField32>(
+        blu_events: &mut Vec<ByteLookupEvent>,
+        cols: &mut WeierstrassMulAssignCols<F, E::BaseField>,
+        (p_x, p_y): (&BigUint, &BigUint),
+        (q_x, q_y): (&BigUint, &BigUint),
+        do_add: bool,
+    ) -> (BigUint, BigUint) {
+        // slope = (q.y - p.y) / (q.x - p.x).
+        let slope = {
+            let slope_numerator =
+                cols.add_slope_numerator.populate(blu_events, q_y, p_y, FieldOperation::Sub);
+            let slope_denominator =
+                cols.add_slope_denominator.populate(blu_events, q_x, p_x, FieldOperation::Sub);
+            let (slope_numerator, slope_denominator) = if do_add {
+                (slope_numerator, slope_denominator)
+            } else {
+                (BigUint::zero(), BigUint::zero())
+            };
+            cols.add_slope.populate(
+                blu_events,
+                &slope_numerator,
+                &slope_denominator,
+                FieldOperation::Div,
+            )
+        };
+
+        // x = slope * slope - (p.x + q.x).
+        let x = {
+            let slope_squared =
+                cols.add_slope_squared.populate(blu_events, &slope, &slope, FieldOperation::Mul);
+            let p_x_plus_q_x =
+                cols.add_p_x_plus_q_x.populate(blu_events, p_x, q_x, FieldOperation::Add);
+            cols.add_x3_ins.populate(blu_events, &slope_squared, &p_x_plus_q_x, FieldOperation::Sub)
+        };
+
+        // y = slope * (p.x - x) - p.y.
+        let y = {
+            let p_x_minus_x =
+                cols.add_p_x_minus_x.populate(blu_events, p_x, &x, FieldOperation::Sub);
+            let slope_times_p_x_minus_x = cols.add_slope_times_p_x_minus_x.populate(
+                blu_events,
+                &slope,
+                &p_x_minus_x,
+                FieldOperation::Mul,
+            );
+            cols.add_y3_ins.populate(blu_events, &slope_times_p_x_minus_x, p_y, FieldOperation::Sub)
+        };
+
+        (x, y)
+    }
+
+    /// Populates the columns doubling `p`, returning the double.
+    fn populate_double_ops<F: PrimeField32>(
+        blu_events: &mut Vec<ByteLookupEvent>,
+        cols: &mut WeierstrassMulAssignCols<F, E::BaseField>,
+        (p_x, p_y): (&BigUint, &BigUint),
+    ) -> (BigUint, BigUint) {
+        let a = E::a_int();
+
+        // slope = (a + 3 * p.x * p.x) / (2 * p.y).
+        let slope = {
+            let p_x_squared =
+                cols.double_p_x_squared.populate(blu_events, p_x, p_x, FieldOperation::Mul);
+            let p_x_squared_times_3 = cols.double_p_x_squared_times_3.populate(
+                blu_events,
+                &p_x_squared,
+                &BigUint::from(3u32),
+                FieldOperation::Mul,
+            );
+            let slope_numerator = cols.double_slope_numerator.populate(
+                blu_events,
+                &a,
+                &p_x_squared_times_3,
+                FieldOperation::Add,
+            );
+            let slope_denominator = cols.double_slope_denominator.populate(
+                blu_events,
+                &BigUint::from(2u32),
+                p_y,
+                FieldOperation::Mul,
+            );
+            cols.double_slope.populate(
+                blu_events,
+                &slope_numerator,
+                &slope_denominator,
+                FieldOperation::Div,
+            )
+        };
+
+        // x = slope * slope - (p.x + p.x).
+        let x = {
+            let slope_squared =
+                cols.double_slope_squared.populate(blu_events, &slope, &slope, FieldOperation::Mul);
+            let p_x_plus_p_x =
+                cols.double_p_x_plus_p_x.populate(blu_events, p_x, p_x, FieldOperation::Add);
+            cols.double_x3_ins.populate(
+                blu_events,
+                &slope_squared,
+                &p_x_plus_p_x,
+                FieldOperation::Sub,
+            )
+        };
+
+        // y = slope * (p.x - x) - p.y.
+        let y = {
+            let p_x_minus_x =
+                cols.double_p_x_minus_x.populate(blu_events, p_x, &x, FieldOperation::Sub);
+            let slope_times_p_x_minus_x = cols.double_slope_times_p_x_minus_x.populate(
+                blu_events,
+                &slope,
+                &p_x_minus_x,
+                FieldOperation::Mul,
+            );
+            cols.double_y3_ins.populate(
+                blu_events,
+                &slope_times_p_x_minus_x,
+                p_y,
+                FieldOperation::Sub,
+            )
+        };
+
+        (x, y)
+    }
+
+    /// Populates the selector columns of the `index`-th row of an event.
+    fn populate_selectors<F: PrimeField32>(
+        cols: &mut WeierstrassMulAssignCols<F, E::BaseField>,
+        index: usize,
+    ) {
+        cols.byte_selector[index / 8] = F::ONE;
+        cols.bit_selector[index % 8] = F::ONE;
+        cols.is_first = F::from_bool(index == 0);
+        cols.is_last = F::from_bool(index == Self::rows_per_event() - 1);
+    }
+
+    /// Populates a padding row. The base is the placeholder point `(0, 1)` so that the doubling
+    /// is well defined.
+    fn populate_dummy_row<F: PrimeField32>(rows: &mut [F], index: usize) {
+        let cols: &mut WeierstrassMulAssignCols<F, E::BaseField> = rows.borrow_mut();
+        let zero = BigUint::zero();
+        let one = BigUint::one();
+        cols.base_y = E::BaseField::to_limbs_field::<F, _>(&one);
+        Self::populate_add_ops(&mut Vec::new(), cols, (&zero, &zero), (&zero, &one), false);
+        Self::populate_double_ops(&mut Vec::new(), cols, (&zero, &one));
+        Self::populate_selectors(cols, index);
+    }
+
+    /// Populates the rows of a scalar multiplication event.
+    pub fn populate_rows<F: PrimeField32>(
+        event: &EllipticCurveMulEvent,
+        rows: &mut [F],
+        blu_events: &mut Vec<ByteLookupEvent>,
+    ) {
+        let num_cols = num_weierstrass_mul_cols::<E::BaseField>();
+        let p = AffinePoint::<E>::from_words_le(&event.p);
+        let scalar = words_to_bytes_le_vec(&event.s);
+
+        let mut acc: Option<(BigUint, BigUint)> = None;
+        let mut base = (p.x.clone(), p.y.clone());
+        for (index, row) in rows.chunks_mut(num_cols).enumerate() {
+            let cols: &mut WeierstrassMulAssignCols<F, E::BaseField> = row.borrow_mut();
+            let is_first = index == 0;
+            let is_last = index == Self::rows_per_event() - 1;
+
+            cols.is_real = F::ONE;
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.p_ptr = F::from_canonical_u32(event.p_ptr);
+            cols.s_ptr = F::from_canonical_u32(event.s_ptr);
+            Self::populate_selectors(cols, index);
+            cols.is_first_real = F::from_bool(is_first);
+            cols.is_last_real = F::from_bool(is_last);
+
+            cols.scalar = scalar.iter().map(|b| F::from_canonical_u8(*b)).collect();
+            cols.p_x = E::BaseField::to_limbs_field::<F, _>(&p.x);
+            cols.p_y = E::BaseField::to_limbs_field::<F, _>(&p.y);
+
+            let byte = scalar[index / 8];
+            for (k, bit) in cols.byte_bits.iter_mut().enumerate() {
+                *bit = F::from_canonical_u8((byte >> k) & 1);
+            }
+            let bit = (byte >> (index % 8)) & 1 == 1;
+            cols.bit = F::from_bool(bit);
+
+            let (acc_x, acc_y) = acc.clone().unwrap_or_default();
+            cols.acc_x = E::BaseField::to_limbs_field::<F, _>(&acc_x);
+            cols.acc_y = E::BaseField::to_limbs_field::<F, _>(&acc_y);
+            cols.acc_is_infinity = F::from_bool(acc.is_none());
+            cols.base_x = E::BaseField::to_limbs_field::<F, _>(&base.0);
+            cols.base_y = E::BaseField::to_limbs_field::<F, _>(&base.1);
+
+            let do_add = bit && acc.is_some();
+            let take_base = bit && acc.is_none();
+            cols.do_add = F::from_bool(do_add);
+            cols.take_base = F::from_bool(take_base);
+
+            // The byte lookups of the addition are only sent when the sum is used.
+            let sum = if do_add {
+                Self::populate_add_ops(blu_events, cols, (&acc_x, &acc_y), (&base.0, &base.1), true)
+            } else {
+                Self::populate_add_ops(
+                    &mut Vec::new(),
+                    cols,
+                    (&acc_x, &acc_y),
+                    (&base.0, &base.1),
+                    false,
+                )
+            };
+            let double = Self::populate_double_ops(blu_events, cols, (&base.0, &base.1));
+
+            if is_first {
+                for (i, s_access) in cols.s_access.iter_mut().enumerate() {
+                    s_access.populate(event.s_memory_records[i], blu_events);
+                }
+            }
+            if is_last {
+                for (i, p_access) in cols.p_access.iter_mut().enumerate() {
+                    p_access.populate(event.p_memory_records[i], blu_events);
+                }
+            }
+
+            if do_add {
+                acc = Some(sum);
+            } else if take_base {
+                acc = Some(base);
+            }
+            base = double;
+        }
+    }
+}
+
+impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
+    for WeierstrassMulAssignChip<E>
+{
+    type Record = ExecutionRecord;
+    type Program = Program;
+    type Error = CoreChipError;
+
+    fn name(&self) -> String {
+        match E::CURVE_TYPE {
+            CurveType::Secp256k1 => "Secp256k1MulAssign".to_string(),
+            CurveType::Secp256r1 => "Secp256r1MulAssign".to_string(),
+            CurveType::Bn254 => "Bn254MulAssign".to_string(),
+            CurveType::Bls12381 => "Bls12381MulAssign".to_string(),
+            _ => panic!("Unsupported curve"),
+        }
+    }
+
+    fn generate_dependencies(
+        &self,
+        input: &Self::Record,
+        output: &mut Self::Record,
+    ) -> Result<(), Self::Error> {
+        let events = input.get_precompile_events(Self::syscall_code());
+        let num_cols = num_weierstrass_mul_cols::<E::BaseField>();
+
+        let blu_events: Vec<Vec<ByteLookupEvent>> = events
+            .par_iter()
+            .map(|(_, event)| {
+                let event = match event {
+                    PrecompileEvent::Secp256k1Mul(event)
+                    | PrecompileEvent::Secp256r1Mul(event)
+                    | PrecompileEvent::Bn254Mul(event)
+                    | PrecompileEvent::Bls12381Mul(event) => event,
+                    _ => unreachable!(),
+                };
+                let mut blu = Vec::new();
+                let mut rows = zeroed_f_vec::<F>(Self::rows_per_event() * num_cols);
+                Self::populate_rows(event, &mut rows, &mut blu);
+                blu
+            })
+            .collect();
+
+        for blu in blu_events {
+            output.add_byte_lookup_events(blu);
+        }
+        Ok(())
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        _: &mut ExecutionRecord,
+    ) -> Result<RowMajorMatrix<F>, Self::Error> {
+        let events = input.get_precompile_events(Self::syscall_code());
+
+        let num_cols = num_weierstrass_mul_cols::<E::BaseField>();
+        let rows_per_event = Self::rows_per_event();
+        let num_rows =
+            next_power_of_two(events.len() * rows_per_event, input.fixed_log2_rows::<F, _>(self));
+        let mut values = zeroed_f_vec(num_rows * num_cols);
+
+        // Padding rows keep cycling through the selectors, so that the transition constraints
+        // hold across the whole trace.
+        values.chunks_mut(rows_per_event * num_cols).enumerate().par_bridge().for_each(
+            |(i, rows)| {
+                if i < events.len() {
+                    match &events[i].1 {
+                        PrecompileEvent::Secp256k1Mul(event)
+                        | PrecompileEvent::Secp256r1Mul(event)
+                        | PrecompileEvent::Bn254Mul(event)
+                        | PrecompileEvent::Bls12381Mul(event) => {
+                            Self::populate_rows(event, rows, &mut Vec::new());
+                        }
+                        _ => unreachable!(),
+                    }
+                } else {
+                    for (index, row) in rows.chunks_mut(num_cols).enumerate() {
+                        Self::populate_dummy_row(row, index);
+                    }
+                }
+            },
+        );
+
+        Ok(RowMajorMatrix::new(values, num_cols))
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        if let Some(shape) = shard.shape.as_ref() {
+            shape.included::<F, _>(self)
+        } else {
+            !shard.get_precompile_events(Self::syscall_code()).is_empty()
+        }
+    }
+
+    fn local_only(&self) -> bool {
+        true
+    }
+}
+
+impl<F, E: EllipticCurve + WeierstrassParameters> BaseAir<F> for WeierstrassMulAssignChip<E> {
+    fn width(&self) -> usize {
+        num_weierstrass_mul_cols::<E::BaseField>()
+    }
+}
+
+impl<AB, E: EllipticCurve + WeierstrassParameters> Air<AB> for WeierstrassMulAssignChip<E>
+where
+    AB: ZKMAirBuilder,
+    Limbs<AB::Var, <E::BaseField as NumLimbs>::Limbs>: Copy,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &WeierstrassMulAssignCols<AB::Var, E::BaseField> = (*local).borrow();
+        let next: &WeierstrassMulAssignCols<AB::Var, E::BaseField> = (*next).borrow();
+
+        let num_limbs = <E::BaseField as NumLimbs>::Limbs::USIZE;
+        let num_words_field_element = num_limbs / 4;
+
+        // The selectors start at the first bit of the first byte, and then cycle through the bits
+        // of each byte of the scalar.
+        builder.when_first_row().assert_one(local.bit_selector[0]);
+        builder.when_first_row().assert_one(local.byte_selector[0]);
+        for k in 1..8 {
+            builder.when_first_row().assert_zero(local.bit_selector[k]);
+        }
+        for j in 1..num_limbs {
+            builder.when_first_row().assert_zero(local.byte_selector[j]);
+        }
+        for k in 0..8 {
+            builder
+                .when_transition()
+                .assert_eq(next.bit_selector[(k + 1) % 8], local.bit_selector[k]);
+        }
+        let end_of_byte = local.bit_selector[7];
+        for j in 0..num_limbs {
+            builder.when_transition().assert_eq(
+                next.byte_selector[j],
+                local.byte_selector[j] * (AB::Expr::one() - end_of_byte)
+                    + local.byte_selector[(j + num_limbs - 1) % num_limbs] * end_of_byte,
+            );
+        }
+        builder.assert_eq(local.is_first, local.byte_selector[0] * local.bit_selector[0]);
+        builder.assert_eq(local.is_last, local.byte_selector[num_limbs - 1] * end_of_byte);
+
+        // An event is made of consecutive real rows, and the trace cannot end in the middle of it.
+        builder.assert_bool(local.is_real);
+        builder.assert_eq(local.is_first_real, local.is_first * local.is_real);
+        builder.assert_eq(local.is_last_real, local.is_last * local.is_real);
+        builder.when_transition().when_not(local.is_last).assert_eq(next.is_real, local.is_real);
+        builder.when_last_row().when(local.is_real).assert_one(local.is_last);
+
+        // Copy the inputs across the rows of an event.
+        {
+            let mut when_continue = builder.when_transition();
+            let mut when_continue = when_continue.when(local.is_real);
+            let mut when_continue = when_continue.when_not(local.is_last);
+            when_continue.assert_eq(next.shard, local.shard);
+            when_continue.assert_eq(next.clk, local.clk);
+            when_continue.assert_eq(next.p_ptr, local.p_ptr);
+            when_continue.assert_eq(next.s_ptr, local.s_ptr);
+            for i in 0..num_limbs {
+                when_continue.assert_eq(next.scalar[i], local.scalar[i]);
+                when_continue.assert_eq(next.p_x[i], local.p_x[i]);
+                when_continue.assert_eq(next.p_y[i], local.p_y[i]);
+            }
+        }
+
+        // Decompose the selected byte of the scalar and select its bit.
+        let mut byte = AB::Expr::zero();
+        let mut bit = AB::Expr::zero();
+        for k in 0..8 {
+            builder.when(local.is_real).assert_bool(local.byte_bits[k]);
+            byte = byte + local.byte_bits[k] * AB::F::from_canonical_u32(1 << k);
+            bit = bit + local.bit_selector[k] * local.byte_bits[k];
+        }
+        let mut selected_byte = AB::Expr::zero();
+        for j in 0..num_limbs {
+            selected_byte = selected_byte + local.byte_selector[j] * local.scalar[j];
+        }
+        builder.when(local.is_real).assert_eq(selected_byte, byte);
+        builder.when(local.is_real).assert_eq(local.bit, bit);
+
+        builder.assert_bool(local.acc_is_infinity);
+        builder.assert_eq(
+            local.do_add,
+            local.is_real * local.bit * (AB::Expr::one() - local.acc_is_infinity),
+        );
+        builder.assert_eq(local.take_base, local.is_real * local.bit * local.acc_is_infinity);
+        let keep = local.is_real - local.do_add - local.take_base;
+
+        // sum = acc + base, only constrained when the sum is used.
+        let (sum_x, sum_y) = {
+            let (p_x, p_y) = (&local.acc_x, &local.acc_y);
+            let (q_x, q_y) = (&local.base_x, &local.base_y);
+
+            local.add_slope_numerator.eval(builder, q_y, p_y, FieldOperation::Sub, local.do_add);
+            local.add_slope_denominator.eval(builder, q_x, p_x, FieldOperation::Sub, local.do_add);
+            let do_add: AB::Expr = local.do_add.into();
+            let slope_numerator: Polynomial<AB::Expr> =
+                Polynomial::from(local.add_slope_numerator.result) * do_add.clone();
+            let slope_denominator: Polynomial<AB::Expr> =
+                Polynomial::from(local.add_slope_denominator.result) * do_add;
+            local.add_slope.eval(
+                builder,
+                &slope_numerator,
+                &slope_denominator,
+                FieldOperation::Div,
+                local.do_add,
+            );
+            let slope = &local.add_slope.result;
+
+            local.add_slope_squared.eval(builder, slope, slope, FieldOperation::Mul, local.do_add);
+            local.add_p_x_plus_q_x.eval(builder, p_x, q_x, FieldOperation::Add, local.do_add);
+            local.add_x3_ins.eval(
+                builder,
+                &local.add_slope_squared.result,
+                &local.add_p_x_plus_q_x.result,
+                FieldOperation::Sub,
+                local.do_add,
+            );
+            local.add_p_x_minus_x.eval(
+                builder,
+                p_x,
+                &local.add_x3_ins.result,
+                FieldOperation::Sub,
+                local.do_add,
+            );
+            local.add_slope_times_p_x_minus_x.eval(
+                builder,
+                slope,
+                &local.add_p_x_minus_x.result,
+                FieldOperation::Mul,
+                local.do_add,
+            );
+            local.add_y3_ins.eval(
+                builder,
+                &local.add_slope_times_p_x_minus_x.result,
+                p_y,
+                FieldOperation::Sub,
+                local.do_add,
+            );
+            (local.add_x3_ins.result, local.add_y3_ins.result)
+        };
+
+        // double = 2 * base.
+        let (double_x, double_y) = {
+            let (p_x, p_y) = (&local.base_x, &local.base_y);
+            let a = E::BaseField::to_limbs_field::<AB::Expr, _>(&E::a_int());
+
+            local.double_p_x_squared.eval(builder, p_x, p_x, FieldOperation::Mul, local.is_real);
+            local.double_p_x_squared_times_3.eval(
+                builder,
+                &local.double_p_x_squared.result,
+                &E::BaseField::to_limbs_field::<AB::Expr, _>(&BigUint::from(3u32)),
+                FieldOperation::Mul,
+                local.is_real,
+            );
+            local.double_slope_numerator.eval(
+                builder,
+                &a,
+                &local.double_p_x_squared_times_3.result,
+                FieldOperation::Add,
+                local.is_real,
+            );
+            local.double_slope_denominator.eval(
+                builder,
+                &E::BaseField::to_limbs_field::<AB::Expr, _>(&BigUint::from(2u32)),
+                p_y,
+                FieldOperation::Mul,
+                local.is_real,
+            );
+            local.double_slope.eval(
+                builder,
+                &local.double_slope_numerator.result,
+                &local.double_slope_denominator.result,
+                FieldOperation::Div,
+                local.is_real,
+            );
+            let slope = &local.double_slope.result;
+
+            local.double_slope_squared.eval(
+                builder,
+                slope,
+                slope,
+                FieldOperation::Mul,
+                local.is_real,
+            );
+            local.double_p_x_plus_p_x.eval(builder, p_x, p_x, FieldOperation::Add, local.is_real);
+            local.double_x3_ins.eval(
+                builder,
+                &local.double_slope_squared.result,
+                &local.double_p_x_plus_p_x.result,
+                FieldOperation::Sub,
+                local.is_real,
+            );
+            local.double_p_x_minus_x.eval(
+                builder,
+                p_x,
+                &local.double_x3_ins.result,
+                FieldOperation::Sub,
+                local.is_real,
+            );
+            local.double_slope_times_p_x_minus_x.eval(
+                builder,
+                slope,
+                &local.double_p_x_minus_x.result,
+                FieldOperation::Mul,
+                local.is_real,
+            );
+            local.double_y3_ins.eval(
+                builder,
+                &local.double_slope_times_p_x_minus_x.result,
+                p_y,
+                FieldOperation::Sub,
+                local.is_real,
+            );
+            (local.double_x3_ins.result, local.double_y3_ins.result)
+        };
+
+        // The first row starts from the identity and the input point.
+        builder.when(local.is_first_real).assert_one(local.acc_is_infinity);
+        for i in 0..num_limbs {
+            builder.when(local.is_first_real).assert_eq(local.base_x[i], local.p_x[i]);
+            builder.when(local.is_first_real).assert_eq(local.base_y[i], local.p_y[i]);
+            builder
+                .when(local.is_first_real)
+                .assert_eq(local.scalar[i], local.s_access[i / 4].value()[i % 4]);
+        }
+
+        // Each row passes on the updated accumulator and the doubled base to the next one.
+        builder
+            .when_transition()
+            .when(local.is_real)
+            .when_not(local.is_last)
+            .assert_eq(next.acc_is_infinity, local.acc_is_infinity - local.take_base);
+        for i in 0..num_limbs {
+            let mut when_continue = builder.when_transition();
+            let mut when_continue = when_continue.when_not(local.is_last);
+            when_continue.when(local.do_add).assert_eq(next.acc_x[i], sum_x[i]);
+            when_continue.when(local.do_add).assert_eq(next.acc_y[i], sum_y[i]);
+            when_continue.when(local.take_base).assert_eq(next.acc_x[i], local.base_x[i]);
+            when_continue.when(local.take_base).assert_eq(next.acc_y[i], local.base_y[i]);
+            when_continue.when(keep.clone()).assert_eq(next.acc_x[i], local.acc_x[i]);
+            when_continue.when(keep.clone()).assert_eq(next.acc_y[i], local.acc_y[i]);
+            when_continue.when(local.is_real).assert_eq(next.base_x[i], double_x[i]);
+            when_continue.when(local.is_real).assert_eq(next.base_y[i], double_y[i]);
+        }
+
+        // The last row writes the updated accumulator over the input point, which cannot be the
+        // identity.
+        builder.when(local.is_last_real).assert_eq(local.acc_is_infinity, local.take_base);
+        let p_x = limbs_from_prev_access(&local.p_access[0..num_words_field_element]);
+        let p_y = limbs_from_prev_access(&local.p_access[num_words_field_element..]);
+        let result_x = limbs_from_access(&local.p_access[0..num_words_field_element]);
+        let result_y = limbs_from_access(&local.p_access[num_words_field_element..]);
+        for i in 0..num_limbs {
+            builder.when(local.is_last_real).assert_eq(p_x[i], local.p_x[i]);
+            builder.when(local.is_last_real).assert_eq(p_y[i], local.p_y[i]);
+
+            let mut when_last = builder.when(local.is_last);
+            when_last.when(local.do_add).assert_eq(result_x[i], sum_x[i]);
+            when_last.when(local.do_add).assert_eq(result_y[i], sum_y[i]);
+            when_last.when(local.take_base).assert_eq(result_x[i], local.base_x[i]);
+            when_last.when(local.take_base).assert_eq(result_y[i], local.base_y[i]);
+            when_last.when(keep.clone()).assert_eq(result_x[i], local.acc_x[i]);
+            when_last.when(keep.clone()).assert_eq(result_y[i], local.acc_y[i]);
+        }
+
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.clk.into(),
+            local.s_ptr,
+            &local.s_access,
+            local.is_first_real,
+        );
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.clk + AB::F::from_canonical_u32(1), /* We write p at +1 since p, s could
+                                                       * overlap. */
+            local.p_ptr,
+            &local.p_access,
+            local.is_last_real,
+        );
+
+        builder.receive_syscall(
+            local.shard,
+            local.clk,
+            AB::F::from_canonical_u32(Self::syscall_code().syscall_id()),
+            local.p_ptr,
+            local.s_ptr,
+            local.is_first_real,
+            LookupScope::Local,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+    use typenum::Unsigned;
+    use zkm_core_executor::{syscalls::SyscallCode, Instruction, Opcode, Program};
+    use zkm_curves::{
+        params::NumWords,
+        weierstrass::{
+            bls12_381::Bls12381, bn254::Bn254, secp256k1::Secp256k1, secp256r1::Secp256r1,
+            WeierstrassParameters,
+        },
+        EllipticCurve,
+    };
+    use zkm_stark::CpuProver;
+
+    use crate::utils::{run_test, setup_logger};
+
+    /// Multiply the generator by `n - 1`, then multiply the result by a small scalar.
+    fn mul_program<E: EllipticCurve + WeierstrassParameters>(syscall_code: SyscallCode) -> Program {
+        let p_ptr = 100;
+        let s_ptr = 1000;
+        let num_words_scalar = <E::BaseField as NumWords>::WordsFieldElement::USIZE;
+
+        let generator = E::ec_generator();
+        let mut scalar = (E::prime_group_order() - BigUint::from(1u32)).to_u32_digits();
+        scalar.resize(num_words_scalar, 0);
+
+        let mut memory = vec![];
+        memory.extend(generator.to_words_le().into_iter().enumerate().map(|(i, w)| (p_ptr, i, w)));
+        memory.extend(scalar.into_iter().enumerate().map(|(i, w)| (s_ptr, i, w)));
+        let mut instructions = vec![];
+        for (ptr, i, word) in memory {
+            instructions.extend(vec![
+                Instruction::new(Opcode::ADD, 29, 0, word, false, true),
+                Instruction::new(Opcode::ADD, 30, 0, ptr + 4 * i as u32, false, true),
+                Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+            ]);
+        }
+        instructions.extend(vec![
+            Instruction::new(Opcode::ADD, 2, 0, syscall_code as u32, false, true),
+            Instruction::new(Opcode::ADD, 4, 0, p_ptr, false, true),
+            Instruction::new(Opcode::ADD, 5, 0, s_ptr, false, true),
+            Instruction::new(Opcode::SYSCALL, 2, 4, 5, false, false),
+        ]);
+
+        // The second scalar reuses the scalar buffer with only its low word set.
+        instructions.extend(vec![
+            Instruction::new(Opcode::ADD, 29, 0, 0xdead_beef, false, true),
+            Instruction::new(Opcode::ADD, 30, 0, s_ptr, false, true),
+            Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+        ]);
+        for i in 1..num_words_scalar {
+            instructions.extend(vec![
+                Instruction::new(Opcode::ADD, 30, 0, s_ptr + 4 * i as u32, false, true),
+                Instruction::new(Opcode::SW, 0, 30, 0, false, true),
+            ]);
+        }
+        instructions.extend(vec![
+            Instruction::new(Opcode::ADD, 2, 0, syscall_code as u32, false, true),
+            Instruction::new(Opcode::ADD, 4, 0, p_ptr, false, true),
+            Instruction::new(Opcode::ADD, 5, 0, s_ptr, false, true),
+            Instruction::new(Opcode::SYSCALL, 2, 4, 5, false, false),
+        ]);
+        Program::new(instructions, 0, 0)
+    }
+
+    #[test]
+    fn test_secp256k1_mul() {
+        setup_logger();
+        let program = mul_program::<Secp256k1>(SyscallCode::SECP256K1_MUL);
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    fn test_secp256r1_mul() {
+        setup_logger();
+        let program = mul_program::<Secp256r1>(SyscallCode::SECP256R1_MUL);
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    fn test_bn254_mul() {
+        setup_logger();
+        let program = mul_program::<Bn254>(SyscallCode::BN254_MUL);
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    fn test_bls12381_mul() {
+        setup_logger();
+        let program = mul_program::<Bls12381>(SyscallCode::BLS12381_MUL);
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+}
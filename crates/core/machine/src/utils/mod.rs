@@ -3,12 +3,16 @@ mod logger;
 mod prove;
 mod span;
 mod tracer;
+mod verify;
+mod work_dir;
 
 pub use logger::*;
 use p3_field::Field;
 pub use prove::*;
 pub use span::*;
 pub use tracer::*;
+pub use verify::*;
+pub use work_dir::*;
 use zkm_curves::params::Limbs;
 
 use crate::{memory::MemoryCols, CoreChipError};
@@ -1,4 +1,5 @@
 use crate::mips::MipsAir;
+use hashbrown::HashMap;
 use p3_maybe_rayon::prelude::*;
 use p3_uni_stark::SymbolicAirBuilder;
 use serde::{de::DeserializeOwned, Serialize};
@@ -20,14 +21,15 @@ use zkm_stark::{
 use p3_field::PrimeField32;
 use p3_koala_bear::KoalaBear;
 
-use crate::shape::CoreShapeConfig;
+use crate::shape::{CoreShapeConfig, CoreShapeError};
 use crate::{
     io::ZKMStdin,
-    utils::{chunk_vec, concurrency::TurnBasedSync},
+    utils::{chunk_vec, concurrency::TurnBasedSync, CheckpointEntry, WorkDir},
 };
 use zkm_core_executor::{
     events::{format_table_line, sorted_table_lines},
     subproof::NoOpSubproofVerifier,
+    syscalls::{Syscall, SyscallCode},
     ExecutionError, ExecutionRecord, ExecutionReport, ExecutionState, Executor, Program,
     ZKMContext,
 };
@@ -36,9 +38,9 @@ use zkm_primitives::io::ZKMPublicValues;
 use zkm_stark::{
     air::{MachineAir, PublicValues},
     Com, CpuProver, DebugConstraintBuilder, LookupBuilder, MachineProof, MachineProver,
-    MachineRecord, OpeningProof, PcsProverData, ProverConstraintFolder, StarkGenericConfig,
-    StarkMachine, StarkProvingKey, StarkVerifyingKey, UniConfig, Val, VerifierConstraintFolder,
-    ZKMCoreOpts,
+    MachineRecord, OpeningProof, PcsProverData, ProverConstraintFolder, SecurityProfile,
+    StarkGenericConfig, StarkMachine, StarkProvingKey, StarkVerifyingKey, UniConfig, Val,
+    VerifierConstraintFolder, ZKMCoreOpts,
 };
 
 #[derive(Error, Debug)]
@@ -53,6 +55,10 @@ pub enum ZKMCoreProverError {
     TracesGenerationError,
     #[error("dependencies generation error")]
     DependenciesGenerationError,
+    #[error("insufficient security: the prover has {0}, but {1} is required")]
+    InsufficientSecurity(SecurityProfile, SecurityProfile),
+    #[error("failed to fix the shape of a shard: {0}")]
+    ShapeError(CoreShapeError),
 }
 
 pub fn prove_simple<SC: StarkGenericConfig, P: MachineProver<SC, MipsAir<SC::Val>>>(
@@ -114,7 +120,7 @@ where
     let machine = MipsAir::machine(config);
     let prover = P::new(machine);
     let (pk, _) = prover.setup(&program);
-    prove_with_context::<SC, _>(
+    prove_with_context::<SC, _, _>(
         &prover,
         &pk,
         program,
@@ -125,7 +131,7 @@ where
     )
 }
 
-pub fn prove_with_context<SC: StarkGenericConfig, P: MachineProver<SC, MipsAir<SC::Val>>>(
+pub fn prove_with_context<SC, A, P>(
     prover: &P,
     pk: &P::DeviceProvingKey,
     program: Program,
@@ -135,19 +141,53 @@ pub fn prove_with_context<SC: StarkGenericConfig, P: MachineProver<SC, MipsAir<S
     shape_config: Option<&CoreShapeConfig<SC::Val>>,
 ) -> Result<(MachineProof<SC>, Vec<u8>, u64), ZKMCoreProverError>
 where
+    SC: StarkGenericConfig,
     SC::Val: PrimeField32,
     SC::Challenger: 'static + Clone + Send,
     OpeningProof<SC>: Send,
     Com<SC>: Send + Sync,
     PcsProverData<SC>: Send + Sync,
+    A: MachineAir<SC::Val, Record = ExecutionRecord, Program = Program>
+        + for<'a> Air<DebugConstraintBuilder<'a, Val<SC>, SC::Challenge>>,
+    P: MachineProver<SC, A>,
+{
+    prove_with_work_dir(prover, pk, program, stdin, opts, context, shape_config, None)
+}
+
+/// Like [`prove_with_context`], but persists the checkpoints and the shard proofs in `work_dir`,
+/// and reuses the ones saved by a previous attempt.
+#[allow(clippy::too_many_arguments)]
+pub fn prove_with_work_dir<SC, A, P>(
+    prover: &P,
+    pk: &P::DeviceProvingKey,
+    program: Program,
+    stdin: &ZKMStdin,
+    opts: ZKMCoreOpts,
+    context: ZKMContext,
+    shape_config: Option<&CoreShapeConfig<SC::Val>>,
+    work_dir: Option<&WorkDir>,
+) -> Result<(MachineProof<SC>, Vec<u8>, u64), ZKMCoreProverError>
+where
+    SC: StarkGenericConfig,
+    SC::Val: PrimeField32,
+    SC::Challenger: 'static + Clone + Send,
+    OpeningProof<SC>: Send,
+    Com<SC>: Send + Sync,
+    PcsProverData<SC>: Send + Sync,
+    A: MachineAir<SC::Val, Record = ExecutionRecord, Program = Program>
+        + for<'a> Air<DebugConstraintBuilder<'a, Val<SC>, SC::Challenge>>,
+    P: MachineProver<SC, A>,
 {
     // Setup the runtime.
+    let custom_syscalls = Arc::new(context.custom_syscalls.clone());
     let mut runtime = Executor::with_context(program.clone(), opts, context);
     runtime.maximal_shapes = shape_config.map(|config| {
         config.maximal_core_shapes(opts.shard_size.ilog2() as usize).into_iter().collect()
     });
 
     runtime.write_vecs(&stdin.buffer);
+    runtime.write_files(&stdin.files);
+    runtime.set_linux_env(stdin.linux_env);
     for proof in stdin.proofs.iter() {
         let (proof, vk) = proof.clone();
         runtime.write_proof(proof, vk);
@@ -170,7 +210,29 @@ where
             s.spawn(move || {
                 let _span = checkpoint_generator_span.enter();
                 tracing::debug_span!("checkpoint generator").in_scope(|| {
+                    // Reuse the checkpoints of a previous attempt which completed the execution.
+                    if let Some((checkpoints, public_values_stream)) =
+                        work_dir.and_then(WorkDir::completed_execution)
+                    {
+                        for (index, checkpoint) in checkpoints.into_iter().enumerate() {
+                            let checkpoint_file = work_dir
+                                .unwrap()
+                                .open_checkpoint(index)
+                                .map_err(ZKMCoreProverError::IoError)?;
+                            checkpoints_tx
+                                .send((
+                                    index,
+                                    checkpoint_file,
+                                    checkpoint.done,
+                                    checkpoint.global_clk,
+                                ))
+                                .unwrap();
+                        }
+                        return Ok(public_values_stream);
+                    }
+
                     let mut index = 0;
+                    let mut checkpoints = Vec::new();
                     loop {
                         // Enter the span.
                         let span = tracing::debug_span!("batch");
@@ -181,9 +243,12 @@ where
                             .execute_state(false)
                             .map_err(ZKMCoreProverError::ExecutionError)?;
 
-                        // Save the checkpoint to a temp file.
-                        let mut checkpoint_file =
-                            tempfile::tempfile().map_err(ZKMCoreProverError::IoError)?;
+                        // Save the checkpoint to a temp file, or to the work directory.
+                        let mut checkpoint_file = match work_dir {
+                            Some(work_dir) => work_dir.create_checkpoint(index),
+                            None => tempfile::tempfile(),
+                        }
+                        .map_err(ZKMCoreProverError::IoError)?;
                         checkpoint
                             .save(&mut checkpoint_file)
                             .map_err(ZKMCoreProverError::IoError)?;
@@ -193,8 +258,19 @@ where
                             .send((index, checkpoint_file, done, runtime.state.global_clk))
                             .unwrap();
 
+                        checkpoints
+                            .push(CheckpointEntry { done, global_clk: runtime.state.global_clk });
+
                         // If we've reached the final checkpoint, break out of the loop.
                         if done {
+                            if let Some(work_dir) = work_dir {
+                                work_dir
+                                    .complete_execution(
+                                        checkpoints,
+                                        runtime.state.public_values_stream.clone(),
+                                    )
+                                    .map_err(ZKMCoreProverError::IoError)?;
+                            }
                             break Ok(runtime.state.public_values_stream);
                         }
 
@@ -232,6 +308,7 @@ where
             let state = Arc::clone(&state);
             let deferred = Arc::clone(&deferred);
             let program = program.clone();
+            let custom_syscalls = Arc::clone(&custom_syscalls);
 
             let span = tracing::Span::current().clone();
 
@@ -257,6 +334,7 @@ where
                                         execution_state,
                                         opts,
                                         shape_config,
+                                        &custom_syscalls,
                                     )
                                 });
                             log::debug!("generated {} records", records.len());
@@ -423,15 +501,24 @@ where
                             #[cfg(feature = "debug")]
                             all_records_tx.send(records.clone()).unwrap();
 
-                            let main_traces_results: Vec<Result<_, _>> =
-                                tracing::debug_span!("generate main traces", index).in_scope(
-                                    || {
-                                        records
-                                            .par_iter()
-                                            .map(|record| prover.generate_traces(record))
-                                            .collect()
-                                    },
-                                );
+                            let main_traces_results: Vec<Result<_, _>> = tracing::debug_span!(
+                                "generate main traces",
+                                index
+                            )
+                            .in_scope(|| {
+                                records
+                                    .par_iter()
+                                    .map(|record| {
+                                        // Shards proven by a previous attempt need no traces.
+                                        let name =
+                                            WorkDir::shard_proof_name(record.public_values.shard);
+                                        if work_dir.is_some_and(|dir| dir.contains(&name)) {
+                                            return Ok(Vec::new());
+                                        }
+                                        prover.generate_traces(record)
+                                    })
+                                    .collect()
+                            });
                             let (successes, errors): (Vec<_>, Vec<_>) =
                                 main_traces_results.into_iter().partition(Result::is_ok);
                             let main_traces = successes.into_iter().map(Result::unwrap).collect();
@@ -489,6 +576,18 @@ where
                                 |(record, main_traces)| {
                                     let _span = span.enter();
 
+                                    // Reuse the proof of a previous attempt.
+                                    let name =
+                                        WorkDir::shard_proof_name(record.public_values.shard);
+                                    if let Some(proof) = work_dir.and_then(|dir| dir.load(&name)) {
+                                        return proof;
+                                    }
+                                    let main_traces = if main_traces.is_empty() {
+                                        prover.generate_traces(&record).unwrap()
+                                    } else {
+                                        main_traces
+                                    };
+
                                     let main_data = prover.commit(&record, main_traces);
 
                                     let opening_span = tracing::debug_span!("opening").entered();
@@ -506,11 +605,18 @@ where
                                                     .clone()
                                                     .into_iter()
                                                     .map(|(k, v)| (k.to_string(), v as usize))
+                                                    .chain(record.custom_shape.clone())
                                                     .collect(),
                                             );
                                         }
                                     }
 
+                                    if let Some(work_dir) = work_dir {
+                                        if let Err(e) = work_dir.store(&name, &proof) {
+                                            tracing::warn!("failed to save shard proof: {}", e);
+                                        }
+                                    }
+
                                     rayon::spawn(move || {
                                         drop(record);
                                     });
@@ -602,6 +708,8 @@ pub fn run_test_io<P: MachineProver<KoalaBearPoseidon2, MipsAir<KoalaBear>>>(
     let runtime = tracing::debug_span!("runtime.run(...)").in_scope(|| {
         let mut runtime = Executor::new(program, ZKMCoreOpts::default());
         runtime.write_vecs(&inputs.buffer);
+        runtime.write_files(&inputs.files);
+        runtime.set_linux_env(inputs.linux_env);
         runtime.run().unwrap();
         runtime
     });
@@ -727,6 +835,7 @@ pub fn trace_checkpoint<SC: StarkGenericConfig>(
     state: ExecutionState,
     opts: ZKMCoreOpts,
     shape_config: Option<&CoreShapeConfig<SC::Val>>,
+    custom_syscalls: &HashMap<SyscallCode, Arc<dyn Syscall>>,
 ) -> (Vec<ExecutionRecord>, ExecutionReport)
 where
     <SC as StarkGenericConfig>::Val: PrimeField32,
@@ -734,6 +843,7 @@ where
     let noop = NoOpSubproofVerifier;
 
     let mut runtime = Executor::recover(program, state, opts);
+    runtime.add_custom_syscalls(custom_syscalls);
     runtime.maximal_shapes = shape_config.map(|config| {
         config.maximal_core_shapes(opts.shard_size.ilog2() as usize).into_iter().collect()
     });
@@ -0,0 +1,215 @@
+use std::{
+    fs::{self, File},
+    io,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use zkm_core_executor::Program;
+use zkm_stark::{StarkGenericConfig, ZKMCoreOpts};
+
+use crate::{io::ZKMStdin, ZKM_CIRCUIT_VERSION};
+
+/// The name of the manifest of a work directory.
+const MANIFEST: &str = "manifest.json";
+
+/// The subdirectories holding the artifacts, which are cleared when the inputs change.
+const ARTIFACT_DIRS: [&str; 3] = ["checkpoints", "shards", "reduce"];
+
+/// A checkpoint of the execution, as sent to the record generators.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct CheckpointEntry {
+    /// Whether this is the last checkpoint of the execution.
+    pub done: bool,
+    /// The global clock of the executor after the checkpoint.
+    pub global_clk: u64,
+}
+
+/// The manifest of a work directory.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    /// The hash of the inputs of the proof, see [`WorkDir::key`].
+    key: String,
+    /// The checkpoints saved so far.
+    checkpoints: Vec<CheckpointEntry>,
+    /// The public values stream, once the execution is complete.
+    public_values_stream: Option<Vec<u8>>,
+}
+
+/// A directory holding the intermediate artifacts of a proof, so that a proof which was
+/// interrupted can be resumed from where it stopped.
+///
+/// The directory holds the checkpoints of the execution, the shard proofs, and the proofs of the
+/// recursion tree. The manifest records the inputs the artifacts belong to, and the artifacts are
+/// cleared when the directory is reused for other inputs. Artifacts are written through temporary
+/// files, so an interrupted write never leaves a partial artifact behind.
+#[derive(Debug)]
+pub struct WorkDir {
+    dir: PathBuf,
+    manifest: Mutex<Manifest>,
+}
+
+impl WorkDir {
+    /// Opens a work directory for proving `program` on `stdin` with the core `config`.
+    ///
+    /// The artifacts of a previous attempt are kept if they belong to the same inputs, and removed
+    /// otherwise.
+    pub fn open<SC: StarkGenericConfig>(
+        dir: impl Into<PathBuf>,
+        program: &Program,
+        stdin: &ZKMStdin,
+        opts: ZKMCoreOpts,
+        config: &SC,
+    ) -> io::Result<Self> {
+        let dir = dir.into();
+        let key = Self::key(program, stdin, opts, config).map_err(io::Error::other)?;
+
+        let manifest = fs::read(dir.join(MANIFEST))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<Manifest>(&bytes).ok());
+        let manifest = match manifest {
+            Some(manifest) if manifest.key == key => {
+                tracing::info!("resuming proof from {}", dir.display());
+                manifest
+            }
+            Some(_) => {
+                tracing::info!("clearing stale artifacts from {}", dir.display());
+                for entry in ARTIFACT_DIRS {
+                    let path = dir.join(entry);
+                    if path.exists() {
+                        fs::remove_dir_all(path)?;
+                    }
+                }
+                Manifest { key, ..Default::default() }
+            }
+            None => Manifest { key, ..Default::default() },
+        };
+
+        let work_dir = Self { dir, manifest: Mutex::new(manifest) };
+        fs::create_dir_all(work_dir.dir.join("checkpoints"))?;
+        work_dir.save_manifest()?;
+        Ok(work_dir)
+    }
+
+    /// The hash of the inputs which determine the artifacts of a proof.
+    ///
+    /// The security profile and the zero-knowledge mode of the core config also determine those of
+    /// the recursion proofs.
+    fn key<SC: StarkGenericConfig>(
+        program: &Program,
+        stdin: &ZKMStdin,
+        opts: ZKMCoreOpts,
+        config: &SC,
+    ) -> bincode::Result<String> {
+        let mut hasher = Sha256::new();
+        hasher.update(ZKM_CIRCUIT_VERSION.as_bytes());
+        hasher.update(bincode::serialize(program)?);
+        hasher.update(bincode::serialize(stdin)?);
+        hasher.update(bincode::serialize(&(opts.shard_size, opts.split_opts))?);
+        hasher.update(bincode::serialize(&(config.security_profile(), config.zk().is_some()))?);
+        Ok(hex::encode(hasher.finalize()))
+    }
+
+    /// The directory.
+    pub fn path(&self) -> &Path {
+        &self.dir
+    }
+
+    fn save_manifest(&self) -> io::Result<()> {
+        let manifest = self.manifest.lock().unwrap();
+        let manifest = serde_json::to_vec(&*manifest).map_err(io::Error::other)?;
+        write_atomic(&self.dir.join(MANIFEST), &manifest)
+    }
+
+    fn checkpoint_path(&self, index: usize) -> PathBuf {
+        self.dir.join("checkpoints").join(format!("{index}.bin"))
+    }
+
+    /// The saved checkpoints and the public values stream, if the execution was completed by a
+    /// previous attempt.
+    pub fn completed_execution(&self) -> Option<(Vec<CheckpointEntry>, Vec<u8>)> {
+        let manifest = self.manifest.lock().unwrap();
+        let public_values_stream = manifest.public_values_stream.clone()?;
+        Some((manifest.checkpoints.clone(), public_values_stream))
+    }
+
+    /// Creates the file of a new checkpoint.
+    pub fn create_checkpoint(&self, index: usize) -> io::Result<File> {
+        File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(self.checkpoint_path(index))
+    }
+
+    /// Opens the file of a saved checkpoint.
+    pub fn open_checkpoint(&self, index: usize) -> io::Result<File> {
+        File::open(self.checkpoint_path(index))
+    }
+
+    /// Records the checkpoints of a completed execution.
+    pub fn complete_execution(
+        &self,
+        checkpoints: Vec<CheckpointEntry>,
+        public_values_stream: Vec<u8>,
+    ) -> io::Result<()> {
+        {
+            let mut manifest = self.manifest.lock().unwrap();
+            manifest.checkpoints = checkpoints;
+            manifest.public_values_stream = Some(public_values_stream);
+        }
+        self.save_manifest()
+    }
+
+    fn artifact_path(&self, name: &str) -> PathBuf {
+        self.dir.join(name).with_extension("bin")
+    }
+
+    /// Whether an artifact was saved.
+    pub fn contains(&self, name: &str) -> bool {
+        self.artifact_path(name).is_file()
+    }
+
+    /// Loads an artifact, if it was saved and can be deserialized.
+    pub fn load<T: DeserializeOwned>(&self, name: &str) -> Option<T> {
+        let bytes = fs::read(self.artifact_path(name)).ok()?;
+        match bincode::deserialize(&bytes) {
+            Ok(artifact) => Some(artifact),
+            Err(e) => {
+                tracing::warn!("discarding artifact {}: {}", name, e);
+                None
+            }
+        }
+    }
+
+    /// Saves an artifact.
+    pub fn store<T: Serialize>(&self, name: &str, artifact: &T) -> io::Result<()> {
+        let path = self.artifact_path(name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let bytes = bincode::serialize(artifact).map_err(io::Error::other)?;
+        write_atomic(&path, &bytes)
+    }
+
+    /// The name of the proof of a shard.
+    pub fn shard_proof_name(shard: u32) -> String {
+        format!("shards/{shard}")
+    }
+
+    /// The name of the proof of a node of the recursion tree, by order of generation.
+    pub fn reduce_proof_name(index: usize) -> String {
+        format!("reduce/{index}")
+    }
+}
+
+/// Writes a file through a temporary file, so that readers never see a partial file.
+fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    fs::write(&tmp_path, contents)?;
+    fs::rename(tmp_path, path)
+}
@@ -55,6 +55,8 @@ impl FieldParameters for Bls12381BaseField {
 
 impl FpOpField for Bls12381BaseField {
     const FIELD_TYPE: FieldType = FieldType::Bls12381;
+
+    const FP6_NON_RESIDUE: (u32, u32) = (1, 1);
 }
 
 impl NumLimbs for Bls12381BaseField {
@@ -41,6 +41,8 @@ impl FieldParameters for Bn254BaseField {
 
 impl FpOpField for Bn254BaseField {
     const FIELD_TYPE: FieldType = FieldType::Bn254;
+
+    const FP6_NON_RESIDUE: (u32, u32) = (9, 1);
 }
 
 impl NumLimbs for Bn254BaseField {
@@ -287,6 +287,10 @@ pub enum FieldType {
 
 pub trait FpOpField: FieldParameters + NumWords {
     const FIELD_TYPE: FieldType;
+
+    /// The non-residue `xi = c0 + c1 * u` of the quadratic extension defining the sextic extension
+    /// `Fp6 = Fp2[v] / (v^3 - xi)` of the pairing tower.
+    const FP6_NON_RESIDUE: (u32, u32);
 }
 
 #[cfg(test)]
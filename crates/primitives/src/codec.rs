@@ -0,0 +1,340 @@
+//! The encodings of the values read from the input stream and committed to the public values.
+//!
+//! Every encoding is self-delimiting, so that the values committed one after the other to the
+//! public values can be decoded back in order:
+//!
+//! - [`Codec::Bincode`]: the bincode encoding, the default of `read`/`commit`.
+//! - [`Codec::Raw`]: a byte string, prefixed by its length as a big-endian `u32`.
+//! - `Codec::Json`: a JSON document followed by a `\n`, i.e. newline-delimited JSON.
+//! - `Codec::Cbor`: a CBOR data item (RFC 8949).
+//!
+//! The JSON and CBOR encodings pull in `serde_json` and `ciborium`, so they are only available
+//! with the `serialization` feature, which guest programs can leave off.
+//!
+//! The public values are the concatenation of the encodings of the committed values, in the order
+//! in which they were committed. With [`Codec::Raw`], they can be decoded in Solidity with:
+//!
+//! ```solidity
+//! function readRaw(bytes calldata data, uint256 offset) pure returns (bytes calldata, uint256) {
+//!     uint256 len = uint32(bytes4(data[offset:offset + 4]));
+//!     return (data[offset + 4:offset + 4 + len], offset + 4 + len);
+//! }
+//! ```
+
+use std::fmt;
+
+use serde::{
+    de::{value::SeqDeserializer, DeserializeOwned},
+    ser::{self, Impossible},
+    Deserialize, Serialize,
+};
+
+/// The size of the length prefix of [`Codec::Raw`].
+pub const RAW_LEN_PREFIX_SIZE: usize = 4;
+
+/// An encoding of serializable values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Codec {
+    /// The bincode encoding.
+    #[default]
+    Bincode,
+    /// A byte string prefixed by its big-endian `u32` length. Only byte sequences such as
+    /// `Vec<u8>` and `[u8; N]` can be encoded this way.
+    Raw,
+    /// A JSON document terminated by a newline.
+    #[cfg(feature = "serialization")]
+    Json,
+    /// A CBOR data item.
+    #[cfg(feature = "serialization")]
+    Cbor,
+}
+
+/// An error while encoding or decoding a value with a [`Codec`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodecError {
+    codec: Codec,
+    message: String,
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} codec: {}", self.codec, self.message)
+    }
+}
+
+impl std::error::Error for CodecError {}
+
+impl Codec {
+    fn error(self, message: impl fmt::Display) -> CodecError {
+        CodecError { codec: self, message: message.to_string() }
+    }
+
+    /// Encode `value`.
+    pub fn encode<T: Serialize + ?Sized>(self, value: &T) -> Result<Vec<u8>, CodecError> {
+        let mut buf = Vec::new();
+        self.encode_into(&mut buf, value)?;
+        Ok(buf)
+    }
+
+    /// Encode `value` at the end of `buf`.
+    pub fn encode_into<T: Serialize + ?Sized>(
+        self,
+        buf: &mut Vec<u8>,
+        value: &T,
+    ) -> Result<(), CodecError> {
+        match self {
+            Codec::Bincode => bincode::serialize_into(buf, value).map_err(|e| self.error(e)),
+            #[cfg(feature = "serialization")]
+            Codec::Json => {
+                serde_json::to_writer(&mut *buf, value).map_err(|e| self.error(e))?;
+                buf.push(b'\n');
+                Ok(())
+            }
+            #[cfg(feature = "serialization")]
+            Codec::Cbor => ciborium::into_writer(value, buf).map_err(|e| self.error(e)),
+            Codec::Raw => {
+                let start = buf.len();
+                buf.extend_from_slice(&[0; RAW_LEN_PREFIX_SIZE]);
+                value.serialize(RawSerializer(&mut *buf)).map_err(|e| self.error(e))?;
+                let len = u32::try_from(buf.len() - start - RAW_LEN_PREFIX_SIZE)
+                    .map_err(|_| self.error("the value is longer than u32::MAX bytes"))?;
+                buf[start..start + RAW_LEN_PREFIX_SIZE].copy_from_slice(&len.to_be_bytes());
+                Ok(())
+            }
+        }
+    }
+
+    /// Decode a value from the beginning of `bytes`, and return it with the number of bytes read.
+    pub fn decode<T: DeserializeOwned>(self, bytes: &[u8]) -> Result<(T, usize), CodecError> {
+        let mut reader = bytes;
+        let value = match self {
+            Codec::Bincode => bincode::deserialize_from(&mut reader).map_err(|e| self.error(e))?,
+            #[cfg(feature = "serialization")]
+            Codec::Json => {
+                let mut stream = serde_json::Deserializer::from_slice(bytes).into_iter();
+                let value = stream
+                    .next()
+                    .ok_or_else(|| self.error("unexpected end of input"))?
+                    .map_err(|e| self.error(e))?;
+                let end = stream.byte_offset();
+                let end = if bytes.get(end) == Some(&b'\n') { end + 1 } else { end };
+                return Ok((value, end));
+            }
+            #[cfg(feature = "serialization")]
+            Codec::Cbor => ciborium::from_reader(&mut reader).map_err(|e| self.error(e))?,
+            Codec::Raw => {
+                let Some((prefix, rest)) = bytes.split_first_chunk::<RAW_LEN_PREFIX_SIZE>() else {
+                    return Err(self.error("missing length prefix"));
+                };
+                let len = u32::from_be_bytes(*prefix) as usize;
+                let data = rest.get(..len).ok_or_else(|| {
+                    self.error(format_args!("expected {len} bytes, got {}", rest.len()))
+                })?;
+                let deserializer = SeqDeserializer::<_, RawError>::new(data.iter().copied());
+                let value = T::deserialize(deserializer).map_err(|e| self.error(e))?;
+                return Ok((value, RAW_LEN_PREFIX_SIZE + len));
+            }
+        };
+        Ok((value, bytes.len() - reader.len()))
+    }
+}
+
+/// Serializes byte sequences as their bytes, and rejects everything else.
+struct RawSerializer<'a>(&'a mut Vec<u8>);
+
+type RawError = serde::de::value::Error;
+
+fn not_bytes<T>() -> Result<T, RawError> {
+    Err(ser::Error::custom("only byte sequences can be encoded"))
+}
+
+macro_rules! reject {
+    ($($method:ident($($ty:ty),*)),* $(,)?) => {
+        $(fn $method(self, $(_: $ty),*) -> Result<(), Self::Error> {
+            not_bytes()
+        })*
+    };
+}
+
+impl ser::Serializer for RawSerializer<'_> {
+    type Ok = ();
+    type Error = RawError;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Impossible<(), RawError>;
+    type SerializeTupleVariant = Impossible<(), RawError>;
+    type SerializeMap = Impossible<(), RawError>;
+    type SerializeStruct = Impossible<(), RawError>;
+    type SerializeStructVariant = Impossible<(), RawError>;
+
+    fn serialize_u8(self, v: u8) -> Result<(), RawError> {
+        self.0.push(v);
+        Ok(())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), RawError> {
+        self.0.extend_from_slice(v);
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<(), RawError> {
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self, RawError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self, RawError> {
+        Ok(self)
+    }
+
+    reject! {
+        serialize_bool(bool), serialize_i8(i8), serialize_i16(i16), serialize_i32(i32),
+        serialize_i64(i64), serialize_u16(u16), serialize_u32(u32), serialize_u64(u64),
+        serialize_f32(f32), serialize_f64(f64), serialize_char(char), serialize_str(&str),
+        serialize_none(), serialize_unit(), serialize_unit_struct(&'static str),
+        serialize_unit_variant(&'static str, u32, &'static str),
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _: &T) -> Result<(), RawError> {
+        not_bytes()
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<(), RawError> {
+        not_bytes()
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, RawError> {
+        not_bytes()
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, RawError> {
+        not_bytes()
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, RawError> {
+        not_bytes()
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStruct, RawError> {
+        not_bytes()
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, RawError> {
+        not_bytes()
+    }
+}
+
+impl ser::SerializeSeq for RawSerializer<'_> {
+    type Ok = ();
+    type Error = RawError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), RawError> {
+        value.serialize(RawSerializer(&mut *self.0))
+    }
+
+    fn end(self) -> Result<(), RawError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTuple for RawSerializer<'_> {
+    type Ok = ();
+    type Error = RawError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), RawError> {
+        value.serialize(RawSerializer(&mut *self.0))
+    }
+
+    fn end(self) -> Result<(), RawError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Output {
+        n: u32,
+        hash: [u8; 4],
+    }
+
+    #[test]
+    fn test_codec_roundtrip() {
+        let output = Output { n: 10, hash: [1, 2, 3, 4] };
+        let codecs = [
+            Codec::Bincode,
+            #[cfg(feature = "serialization")]
+            Codec::Json,
+            #[cfg(feature = "serialization")]
+            Codec::Cbor,
+        ];
+        for codec in codecs {
+            let mut buf = Vec::new();
+            codec.encode_into(&mut buf, &output).unwrap();
+            codec.encode_into(&mut buf, &7u64).unwrap();
+
+            let (decoded, len) = codec.decode::<Output>(&buf).unwrap();
+            assert_eq!(decoded, output);
+            let (decoded, rest) = codec.decode::<u64>(&buf[len..]).unwrap();
+            assert_eq!(decoded, 7);
+            assert_eq!(len + rest, buf.len());
+        }
+    }
+
+    #[test]
+    fn test_codec_raw_layout() {
+        let mut buf = Codec::Raw.encode(&vec![0xaau8, 0xbb]).unwrap();
+        Codec::Raw.encode_into(&mut buf, &[0xccu8; 3]).unwrap();
+        assert_eq!(buf, [0, 0, 0, 2, 0xaa, 0xbb, 0, 0, 0, 3, 0xcc, 0xcc, 0xcc]);
+
+        let (decoded, len) = Codec::Raw.decode::<Vec<u8>>(&buf).unwrap();
+        assert_eq!((decoded, len), (vec![0xaa, 0xbb], 6));
+        let (decoded, _) = Codec::Raw.decode::<[u8; 3]>(&buf[len..]).unwrap();
+        assert_eq!(decoded, [0xcc; 3]);
+
+        assert!(Codec::Raw.encode(&1u32).is_err());
+        assert!(Codec::Raw.decode::<Vec<u8>>(&buf[..5]).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serialization")]
+    fn test_codec_json_is_newline_delimited() {
+        let mut buf = Codec::Json.encode(&1u32).unwrap();
+        Codec::Json.encode_into(&mut buf, &2u32).unwrap();
+        assert_eq!(buf, b"1\n2\n");
+    }
+}
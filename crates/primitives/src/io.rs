@@ -1,4 +1,4 @@
-use crate::types::Buffer;
+use crate::{codec::Codec, types::Buffer};
 use num_bigint::BigUint;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use sha2::{Digest, Sha256};
@@ -37,6 +37,12 @@ impl ZKMPublicValues {
         self.buffer.read()
     }
 
+    /// Read a value encoded with `codec` from the buffer, e.g. one committed in the program with
+    /// `zkm_zkvm::io::commit_with`.
+    pub fn read_with<T: DeserializeOwned>(&mut self, codec: Codec) -> T {
+        self.buffer.read_with(codec)
+    }
+
     /// Read a slice of bytes from the buffer.
     pub fn read_slice(&mut self, slice: &mut [u8]) {
         self.buffer.read_slice(slice);
@@ -47,6 +53,11 @@ impl ZKMPublicValues {
         self.buffer.write(data);
     }
 
+    /// Write a value to the buffer, encoded with `codec`.
+    pub fn write_with<T: Serialize>(&mut self, data: &T, codec: Codec) {
+        self.buffer.write_with(data, codec);
+    }
+
     /// Write a slice of bytes to the buffer.
     pub fn write_slice(&mut self, slice: &[u8]) {
         self.buffer.write_slice(slice);
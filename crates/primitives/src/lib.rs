@@ -7,6 +7,7 @@ use p3_koala_bear::{KoalaBear, Poseidon2KoalaBear};
 use p3_poseidon2::{ExternalLayerConstants, Poseidon2};
 //use p3_monty_31::{Poseidon2InternalLayerMonty31, Poseidon2ExternalLayerMonty31};
 
+pub mod codec;
 pub mod consts;
 pub mod io;
 pub mod types;
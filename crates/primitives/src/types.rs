@@ -1,5 +1,7 @@
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
+use crate::codec::Codec;
+
 #[derive(Debug, Clone, Copy)]
 pub enum RecursionProgramType {
     Core,
@@ -40,6 +42,14 @@ impl Buffer {
         result
     }
 
+    /// Read an object encoded with `codec` from the buffer.
+    pub fn read_with<T: DeserializeOwned>(&mut self, codec: Codec) -> T {
+        let (result, nb_bytes) =
+            codec.decode(&self.data[self.ptr..]).expect("failed to deserialize");
+        self.ptr += nb_bytes;
+        result
+    }
+
     pub fn read_slice(&mut self, slice: &mut [u8]) {
         slice.copy_from_slice(&self.data[self.ptr..self.ptr + slice.len()]);
         self.ptr += slice.len();
@@ -52,6 +62,11 @@ impl Buffer {
         self.data.extend(tmp);
     }
 
+    /// Write the object to the buffer, encoded with `codec`.
+    pub fn write_with<T: Serialize>(&mut self, data: &T, codec: Codec) {
+        codec.encode_into(&mut self.data, data).expect("serialization failed");
+    }
+
     /// Write the slice of bytes to the buffer.                                                   
     pub fn write_slice(&mut self, slice: &[u8]) {
         self.data.extend_from_slice(slice);
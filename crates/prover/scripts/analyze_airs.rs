@@ -0,0 +1,69 @@
+use clap::{Parser, ValueEnum};
+use p3_koala_bear::KoalaBear;
+use zkm_core_machine::{mips::MipsAir, utils::setup_logger};
+use zkm_prover::{CompressAir, ShrinkAir, WrapAir};
+use zkm_stark::{koala_bear_poseidon2::KoalaBearPoseidon2, AirReport};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Machine {
+    Core,
+    Compress,
+    Shrink,
+    Wrap,
+}
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    #[clap(
+        short,
+        long,
+        value_enum,
+        value_delimiter = ',',
+        default_value = "core,compress,shrink,wrap"
+    )]
+    machines: Vec<Machine>,
+    #[clap(long)]
+    max_degree: Option<usize>,
+}
+
+fn main() {
+    // Setup the logger.
+    setup_logger();
+
+    // Parse the arguments.
+    let args = Args::parse();
+
+    let mut ok = true;
+    for machine in args.machines {
+        let config = KoalaBearPoseidon2::default();
+        let report = match machine {
+            Machine::Core => AirReport::new(&MipsAir::<KoalaBear>::chips()),
+            Machine::Compress => {
+                AirReport::new(CompressAir::<KoalaBear>::compress_machine(config).chips())
+            }
+            Machine::Shrink => {
+                AirReport::new(ShrinkAir::<KoalaBear>::shrink_machine(config).chips())
+            }
+            Machine::Wrap => AirReport::new(WrapAir::<KoalaBear>::wrap_machine(config).chips()),
+        };
+
+        println!("== {machine:?} ==");
+        println!("{report}");
+
+        ok &= report.is_balanced();
+        if let Some(max_degree) = args.max_degree {
+            for chip in report.degree_violations(max_degree) {
+                println!(
+                    "chip {} has degree {} above {max_degree}",
+                    chip.name, chip.max_constraint_degree
+                );
+                ok = false;
+            }
+        }
+    }
+
+    if !ok {
+        std::process::exit(1);
+    }
+}
@@ -95,6 +95,8 @@ fn test_shape_fixing(
         shape_config.maximal_core_shapes(log2_ceil_usize(opts.shard_size)).into_iter().collect(),
     );
     executor.write_vecs(&stdin.buffer);
+    executor.write_files(&stdin.files);
+    executor.set_linux_env(stdin.linux_env);
     for (proof, vkey) in stdin.proofs.iter() {
         executor.write_proof(proof.clone(), vkey.clone());
     }
@@ -0,0 +1,21 @@
+use std::{net::TcpListener, sync::Arc};
+
+use clap::Parser;
+use zkm_core_machine::utils::setup_logger;
+use zkm_prover::{components::DefaultProverComponents, distributed::ZKMWorker, ZKMProver};
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    #[clap(short, long, default_value = "0.0.0.0:3030")]
+    addr: String,
+}
+
+fn main() {
+    setup_logger();
+    let args = Args::parse();
+
+    let prover = ZKMProver::<DefaultProverComponents>::new();
+    let listener = TcpListener::bind(&args.addr).unwrap();
+    Arc::new(ZKMWorker::new(prover)).serve(listener).unwrap();
+}
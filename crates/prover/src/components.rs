@@ -1,13 +1,25 @@
-use zkm_core_machine::mips::MipsAir;
-use zkm_stark::{CpuProver, MachineProver, StarkGenericConfig};
+use p3_air::Air;
+use p3_koala_bear::KoalaBear;
+use zkm_core_machine::mips::{CoreMachineAir, MipsAir};
+use zkm_recursion_circuit::constraints::RecursiveVerifierConstraintFolder;
+use zkm_recursion_compiler::config::InnerConfig;
+use zkm_stark::{
+    CpuProver, DebugConstraintBuilder, MachineProver, StarkGenericConfig, VerifierConstraintFolder,
+};
 
 use crate::{CompressAir, CoreSC, InnerSC, OuterSC, ShrinkAir, WrapAir};
 
 pub trait ZKMProverComponents: Send + Sync {
+    /// The AIR of the core shards, with the chips of the custom precompiles of the prover.
+    type CoreAir: CoreMachineAir<KoalaBear>
+        + for<'a> Air<VerifierConstraintFolder<'a, CoreSC>>
+        + for<'a> Air<RecursiveVerifierConstraintFolder<'a, InnerConfig>>
+        + for<'a> Air<
+            DebugConstraintBuilder<'a, KoalaBear, <CoreSC as StarkGenericConfig>::Challenge>,
+        >;
+
     /// The prover for making Ziren core proofs.
-    type CoreProver: MachineProver<CoreSC, MipsAir<<CoreSC as StarkGenericConfig>::Val>>
-        + Send
-        + Sync;
+    type CoreProver: MachineProver<CoreSC, Self::CoreAir> + Send + Sync;
 
     /// The prover for making Ziren recursive proofs.
     type CompressProver: MachineProver<InnerSC, CompressAir<<InnerSC as StarkGenericConfig>::Val>>
@@ -28,7 +40,8 @@ pub trait ZKMProverComponents: Send + Sync {
 pub struct DefaultProverComponents;
 
 impl ZKMProverComponents for DefaultProverComponents {
-    type CoreProver = CpuProver<CoreSC, MipsAir<<CoreSC as StarkGenericConfig>::Val>>;
+    type CoreAir = MipsAir<KoalaBear>;
+    type CoreProver = CpuProver<CoreSC, Self::CoreAir>;
     type CompressProver = CpuProver<InnerSC, CompressAir<<InnerSC as StarkGenericConfig>::Val>>;
     type ShrinkProver = CpuProver<InnerSC, ShrinkAir<<InnerSC as StarkGenericConfig>::Val>>;
     type WrapProver = CpuProver<OuterSC, WrapAir<<OuterSC as StarkGenericConfig>::Val>>;
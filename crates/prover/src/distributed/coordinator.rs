@@ -0,0 +1,348 @@
+use std::{
+    collections::VecDeque,
+    mem,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use zkm_core_executor::{ExecutionRecord, Executor, ZKMContext};
+use zkm_core_machine::{io::ZKMStdin, reduce::ZKMReduceProof, utils::ZKMCoreProverError};
+use zkm_primitives::io::ZKMPublicValues;
+use zkm_recursion_circuit::machine::ZKMCompressWitnessValues;
+use zkm_stark::{air::PublicValues, MachineRecord, ZKMCoreOpts, ZKMProverOpts};
+
+use super::{
+    protocol::{self, TASK_PATH},
+    CheckpointTask, DeferredTask, DistributedError, ReduceTask, ShardsTask, WorkerResult,
+    WorkerTask,
+};
+use crate::{
+    components::ZKMProverComponents, InnerSC, ZKMCircuitWitness, ZKMCoreProof, ZKMCoreProofData,
+    ZKMProver, ZKMProvingKey, ZKMVerifyingKey,
+};
+
+/// The default number of times a task is attempted before the proof is aborted.
+const DEFAULT_MAX_ATTEMPTS: usize = 3;
+
+/// How long an idle worker connection waits for failed tasks to be requeued.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Splits the proving work of a [`ZKMProver`] into tasks, and dispatches them to [`ZKMWorker`]s.
+///
+/// The coordinator only executes the program, threads the public values through the shards and
+/// splits the deferred events, which is cheap compared to tracing and proving. A worker that fails
+/// a task is no longer used for the rest of the batch, and the task is retried on another worker.
+///
+/// [`ZKMWorker`]: super::ZKMWorker
+pub struct Coordinator<'a, C: ZKMProverComponents> {
+    prover: &'a ZKMProver<C>,
+    workers: Vec<String>,
+    max_attempts: usize,
+}
+
+impl<'a, C: ZKMProverComponents> Coordinator<'a, C> {
+    /// Creates a coordinator dispatching tasks to the workers at the given addresses.
+    pub fn new(prover: &'a ZKMProver<C>, workers: Vec<String>) -> Self {
+        Self { prover, workers, max_attempts: DEFAULT_MAX_ATTEMPTS }
+    }
+
+    /// Sets the number of times a task is attempted before the proof is aborted.
+    pub fn with_max_attempts(mut self, max_attempts: usize) -> Self {
+        assert!(max_attempts > 0, "a task must be attempted at least once");
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Generates the shard proofs of a program, like [`ZKMProver::prove_core`].
+    ///
+    /// Checkpoints are summarized and proven by the workers, in windows of one checkpoint per
+    /// worker, so that the shards of a window are proven while the next one is summarized.
+    #[tracing::instrument(name = "distributed prove_core", level = "info", skip_all)]
+    pub fn prove_core(
+        &self,
+        pk: &ZKMProvingKey,
+        stdin: &ZKMStdin,
+        opts: ZKMProverOpts,
+        mut context: ZKMContext<'a>,
+    ) -> Result<ZKMCoreProof, DistributedError> {
+        if self.workers.is_empty() {
+            return Err(DistributedError::NoWorkers);
+        }
+        let precompiles = &self.prover.core_precompiles;
+        let mut core_opts = opts.core_opts;
+        core_opts.split_opts = precompiles.split_opts(core_opts.split_opts);
+        let shape_config = self.prover.core_shape_config.as_ref();
+
+        // Setup the runtime.
+        context.subproof_verifier = Some(self.prover);
+        precompiles.add_missing_syscalls(&mut context);
+        let program = self.prover.get_program(&pk.elf).unwrap();
+        let mut runtime = Executor::with_context(program.clone(), core_opts, context);
+        runtime.maximal_shapes = shape_config.map(|config| {
+            config.maximal_core_shapes(core_opts.shard_size.ilog2() as usize).into_iter().collect()
+        });
+        runtime.write_vecs(&stdin.buffer);
+        runtime.write_files(&stdin.files);
+        runtime.set_linux_env(stdin.linux_env);
+        for (proof, vk) in stdin.proofs.iter() {
+            runtime.write_proof(proof.clone(), vk.clone());
+        }
+
+        let mut state = PublicValues::<u32, u32>::default().reset();
+        let mut deferred = ExecutionRecord::new(program.into());
+        let mut shard_proofs = Vec::new();
+        let mut pending_shards = Vec::new();
+        let mut cycles = 0;
+        let mut done = false;
+        while !done || !pending_shards.is_empty() {
+            // Execute the runtime until the next window of checkpoints.
+            let mut checkpoints = Vec::new();
+            while !done && checkpoints.len() < self.workers.len() {
+                let (checkpoint, is_done) =
+                    runtime.execute_state(false).map_err(ZKMCoreProverError::ExecutionError)?;
+                checkpoints.push(checkpoint);
+                done = is_done;
+            }
+
+            // Summarize the checkpoints, and prove the shards of the previous window.
+            let num_checkpoints = checkpoints.len();
+            let mut tasks = checkpoints
+                .iter()
+                .map(|checkpoint| {
+                    WorkerTask::Checkpoint(CheckpointTask {
+                        elf: pk.elf.clone(),
+                        state: checkpoint.clone(),
+                        opts: core_opts,
+                    })
+                })
+                .collect::<Vec<_>>();
+            tasks.append(&mut pending_shards);
+            let mut results = self.run_tasks(tasks)?.into_iter();
+
+            for (index, (checkpoint, result)) in
+                checkpoints.into_iter().zip(results.by_ref().take(num_checkpoints)).enumerate()
+            {
+                let WorkerResult::Checkpoint {
+                    public_values,
+                    deferred: mut checkpoint_deferred,
+                    cycles: checkpoint_cycles,
+                } = result
+                else {
+                    return Err(DistributedError::UnexpectedResult);
+                };
+                cycles += checkpoint_cycles;
+
+                let is_last = done && index == num_checkpoints - 1;
+                let (public_values, deferred_records) = Self::assemble_records(
+                    public_values,
+                    &mut checkpoint_deferred,
+                    &mut state,
+                    &mut deferred,
+                    is_last,
+                    core_opts,
+                );
+                pending_shards.push(WorkerTask::Shards(ShardsTask {
+                    elf: pk.elf.clone(),
+                    state: checkpoint,
+                    opts: core_opts,
+                    public_values,
+                }));
+                pending_shards.extend(deferred_records.chunks(core_opts.shard_batch_size).map(
+                    |records| {
+                        WorkerTask::Deferred(DeferredTask {
+                            elf: pk.elf.clone(),
+                            records: records.to_vec(),
+                            opts: core_opts,
+                        })
+                    },
+                ));
+            }
+            for result in results {
+                let WorkerResult::Shards(mut proofs) = result else {
+                    return Err(DistributedError::UnexpectedResult);
+                };
+                shard_proofs.append(&mut proofs);
+            }
+        }
+
+        let public_values = ZKMPublicValues::from(&runtime.state.public_values_stream);
+        Ok(ZKMCoreProof {
+            proof: ZKMCoreProofData(shard_proofs),
+            stdin: stdin.clone(),
+            public_values,
+            cycles,
+        })
+    }
+
+    /// Threads the public values through the records of a checkpoint, given their own public
+    /// values and their deferred events, and returns the public values of the records with the
+    /// records of deferred events which are ready to be proven.
+    fn assemble_records(
+        mut public_values: Vec<PublicValues<u32, u32>>,
+        checkpoint_deferred: &mut ExecutionRecord,
+        state: &mut PublicValues<u32, u32>,
+        deferred: &mut ExecutionRecord,
+        done: bool,
+        opts: ZKMCoreOpts,
+    ) -> (Vec<PublicValues<u32, u32>>, Vec<ExecutionRecord>) {
+        // Update the public values & prover state for the shards which contain "cpu events".
+        for public_values in public_values.iter_mut() {
+            state.shard += 1;
+            state.execution_shard = public_values.execution_shard;
+            state.start_pc = public_values.start_pc;
+            state.next_pc = public_values.next_pc;
+            state.committed_value_digest = public_values.committed_value_digest;
+            state.deferred_proofs_digest = public_values.deferred_proofs_digest;
+            *public_values = *state;
+        }
+
+        // Defer events that are too expensive to include in every shard.
+        deferred.append(checkpoint_deferred);
+
+        // See if any deferred shards are ready to be committed to, and update their public values
+        // & prover state before committing to them.
+        let mut deferred = deferred.split(done, None, opts.split_opts);
+        if !done {
+            state.execution_shard += 1;
+        }
+        for record in deferred.iter_mut() {
+            state.shard += 1;
+            state.previous_init_addr_bits = record.public_values.previous_init_addr_bits;
+            state.last_init_addr_bits = record.public_values.last_init_addr_bits;
+            state.previous_finalize_addr_bits = record.public_values.previous_finalize_addr_bits;
+            state.last_finalize_addr_bits = record.public_values.last_finalize_addr_bits;
+            state.start_pc = state.next_pc;
+            record.public_values = *state;
+        }
+        (public_values, deferred)
+    }
+
+    /// Reduces shard proofs to a single shard proof, like [`ZKMProver::compress`].
+    ///
+    /// Each layer of the recursion tree is proven by the workers before the next one.
+    #[tracing::instrument(name = "distributed compress", level = "info", skip_all)]
+    pub fn compress(
+        &self,
+        vk: &ZKMVerifyingKey,
+        proof: ZKMCoreProof,
+        deferred_proofs: Vec<ZKMReduceProof<InnerSC>>,
+        opts: ZKMProverOpts,
+    ) -> Result<ZKMReduceProof<InnerSC>, DistributedError> {
+        let mut inputs =
+            self.prover.get_first_layer_inputs(vk, &proof.proof.0, &deferred_proofs, 1);
+
+        // Calculate the expected height of the tree.
+        let mut expected_height = if inputs.len() == 1 { 0 } else { 1 };
+        let mut num_layer_inputs = inputs.len();
+        while num_layer_inputs > 2 {
+            num_layer_inputs = num_layer_inputs.div_ceil(2);
+            expected_height += 1;
+        }
+
+        let mut height = 0;
+        loop {
+            let tasks = mem::take(&mut inputs)
+                .into_iter()
+                .map(|input| WorkerTask::Reduce(ReduceTask { input, opts }))
+                .collect();
+            let proofs = self
+                .run_tasks(tasks)?
+                .into_iter()
+                .map(|result| match result {
+                    WorkerResult::Reduce { vk, proof } => Ok((vk, proof)),
+                    _ => Err(DistributedError::UnexpectedResult),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if height == expected_height {
+                let (vk, proof) = proofs.into_iter().next().unwrap();
+                return Ok(ZKMReduceProof { vk, proof });
+            }
+
+            height += 1;
+            inputs = proofs
+                .chunks(2)
+                .map(|vks_and_proofs| {
+                    ZKMCircuitWitness::Compress(ZKMCompressWitnessValues {
+                        vks_and_proofs: vks_and_proofs.to_vec(),
+                        is_complete: height == expected_height,
+                    })
+                })
+                .collect();
+        }
+    }
+
+    /// Runs the tasks on the workers, and returns their results in the order of the tasks.
+    fn run_tasks(&self, tasks: Vec<WorkerTask>) -> Result<Vec<WorkerResult>, DistributedError> {
+        let queue = tasks
+            .iter()
+            .enumerate()
+            .map(|(index, task)| Ok((index, bincode::serialize(task)?, 0)))
+            .collect::<Result<VecDeque<_>, DistributedError>>()?;
+        drop(tasks);
+
+        let num_tasks = queue.len();
+        let queue = Mutex::new(queue);
+        let remaining = AtomicUsize::new(num_tasks);
+        let results = Mutex::new((0..num_tasks).map(|_| None).collect::<Vec<_>>());
+        let error = Mutex::new(None);
+
+        thread::scope(|s| {
+            for addr in self.workers.iter() {
+                let (queue, remaining, results, error) = (&queue, &remaining, &results, &error);
+                s.spawn(move || {
+                    while remaining.load(Ordering::SeqCst) > 0 && error.lock().unwrap().is_none() {
+                        let Some((index, body, attempts)) = queue.lock().unwrap().pop_front()
+                        else {
+                            // Another worker may still fail a task and requeue it.
+                            thread::sleep(POLL_INTERVAL);
+                            continue;
+                        };
+
+                        match self.send_task(addr, &body) {
+                            Ok(result) => {
+                                results.lock().unwrap()[index] = Some(result);
+                                remaining.fetch_sub(1, Ordering::SeqCst);
+                            }
+                            Err(e) => {
+                                tracing::warn!("worker {} failed task {}: {}", addr, index, e);
+                                if attempts + 1 < self.max_attempts {
+                                    queue.lock().unwrap().push_back((index, body, attempts + 1));
+                                } else {
+                                    *error.lock().unwrap() = Some(e);
+                                }
+                                // Stop sending tasks to the failed worker.
+                                break;
+                            }
+                        }
+                    }
+                });
+            }
+        });
+
+        if let Some(e) = error.into_inner().unwrap() {
+            return Err(e);
+        }
+        results
+            .into_inner()
+            .unwrap()
+            .into_iter()
+            .map(|result| result.ok_or(DistributedError::NoWorkers))
+            .collect()
+    }
+
+    /// Sends a serialized task to a worker, and waits for its result.
+    fn send_task(&self, addr: &str, body: &[u8]) -> Result<WorkerResult, DistributedError> {
+        let response = protocol::send_request(addr, "POST", TASK_PATH, body)?;
+        if response.status != 200 {
+            return Err(DistributedError::Worker(
+                String::from_utf8_lossy(&response.body).into_owned(),
+            ));
+        }
+        Ok(bincode::deserialize(&response.body)?)
+    }
+}
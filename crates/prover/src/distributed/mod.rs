@@ -0,0 +1,189 @@
+//! Distributed proving over a fleet of workers.
+//!
+//! The [`Coordinator`] executes the program to produce checkpoints, and splits the rest of the
+//! proving work into serialized [`WorkerTask`]s:
+//!
+//! 1. [`CheckpointTask`]s trace a checkpoint, and return the public values of its shard records
+//!    and the events deferred to the precompile and memory shards.
+//! 2. [`ShardsTask`]s trace a checkpoint again and prove its shard records, once the coordinator
+//!    has threaded the public values through the records of every previous checkpoint.
+//! 3. [`DeferredTask`]s prove batches of precompile and memory shard records, which the
+//!    coordinator splits from the deferred events.
+//! 4. [`ReduceTask`]s prove a node of the recursion tree, from the first layer of recursive proofs
+//!    of the shard proofs up to the compressed proof.
+//!
+//! The records of the shards are regenerated by the workers from the checkpoints, like in
+//! [`ZKMProver::prove_core`](crate::ZKMProver::prove_core), and only the deferred events go
+//! through the coordinator.
+//!
+//! [`ZKMWorker`]s execute the tasks, in other processes or on other hosts, and speak a small HTTP
+//! protocol described in [`protocol`].
+
+mod coordinator;
+pub mod protocol;
+mod worker;
+
+pub use coordinator::*;
+pub use worker::*;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use zkm_core_executor::{ExecutionRecord, ExecutionState};
+use zkm_core_machine::utils::ZKMCoreProverError;
+use zkm_stark::{air::PublicValues, ShardProof, StarkVerifyingKey, ZKMCoreOpts, ZKMProverOpts};
+
+use crate::{CoreSC, InnerSC, ZKMCircuitWitness};
+
+/// A unit of proving work executed by a [`ZKMWorker`].
+#[derive(Serialize, Deserialize)]
+#[allow(clippy::large_enum_variant)]
+pub enum WorkerTask {
+    Checkpoint(CheckpointTask),
+    Shards(ShardsTask),
+    Deferred(DeferredTask),
+    Reduce(ReduceTask),
+}
+
+/// Traces the execution from a checkpoint to summarize the records of its shards.
+#[derive(Serialize, Deserialize)]
+pub struct CheckpointTask {
+    /// The ELF of the program.
+    pub elf: Vec<u8>,
+    /// The state of the executor at the checkpoint.
+    pub state: ExecutionState,
+    /// The options of the core prover.
+    pub opts: ZKMCoreOpts,
+}
+
+/// Traces the execution from a checkpoint and proves the records of its shards.
+#[derive(Serialize, Deserialize)]
+pub struct ShardsTask {
+    /// The ELF of the program.
+    pub elf: Vec<u8>,
+    /// The state of the executor at the checkpoint.
+    pub state: ExecutionState,
+    /// The options of the core prover.
+    pub opts: ZKMCoreOpts,
+    /// The public values of the records, threaded through the previous shards.
+    pub public_values: Vec<PublicValues<u32, u32>>,
+}
+
+/// Proves a batch of records of deferred events, which are ready to be committed to.
+#[derive(Serialize, Deserialize)]
+pub struct DeferredTask {
+    /// The ELF of the program.
+    pub elf: Vec<u8>,
+    /// The records of the shards.
+    pub records: Vec<ExecutionRecord>,
+    /// The options of the core prover.
+    pub opts: ZKMCoreOpts,
+}
+
+/// Proves a node of the recursion tree.
+#[derive(Serialize, Deserialize)]
+pub struct ReduceTask {
+    /// The witness of the recursion program.
+    pub input: ZKMCircuitWitness,
+    /// The options of the prover.
+    pub opts: ZKMProverOpts,
+}
+
+/// The result of a [`WorkerTask`].
+#[derive(Serialize, Deserialize)]
+#[allow(clippy::large_enum_variant)]
+pub enum WorkerResult {
+    Checkpoint {
+        /// The public values of the records of the shards executed from the checkpoint.
+        public_values: Vec<PublicValues<u32, u32>>,
+        /// The events of the records deferred to the precompile and memory shards.
+        deferred: ExecutionRecord,
+        /// The number of cycles executed from the checkpoint.
+        cycles: u64,
+    },
+    Shards(Vec<ShardProof<CoreSC>>),
+    Reduce {
+        vk: StarkVerifyingKey<InnerSC>,
+        proof: ShardProof<InnerSC>,
+    },
+}
+
+/// An error that occurs during distributed proving.
+#[derive(Error, Debug)]
+pub enum DistributedError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("serialization error: {0}")]
+    Serialization(#[from] bincode::Error),
+    #[error("core prover error: {0}")]
+    Core(#[from] ZKMCoreProverError),
+    #[error("protocol error: {0}")]
+    Protocol(String),
+    #[error("worker error: {0}")]
+    Worker(String),
+    #[error("unexpected result for the task")]
+    UnexpectedResult,
+    #[error("no worker is available")]
+    NoWorkers,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{net::TcpListener, sync::Arc, thread};
+
+    use serial_test::serial;
+    use zkm_core_executor::ZKMContext;
+    use zkm_core_machine::{io::ZKMStdin, utils::setup_logger};
+
+    use super::*;
+    use crate::{components::DefaultProverComponents, ZKMProver};
+
+    /// Spawns a worker on localhost, and returns its address.
+    fn spawn_worker() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let worker = Arc::new(ZKMWorker::new(ZKMProver::<DefaultProverComponents>::new()));
+        thread::spawn(move || worker.serve(listener));
+        addr
+    }
+
+    /// Proves the shards of a small program over several checkpoints with two workers, and
+    /// checks that they verify.
+    #[test]
+    #[serial]
+    fn test_distributed_prove_core() {
+        setup_logger();
+        let workers = vec![spawn_worker(), spawn_worker()];
+
+        let prover = ZKMProver::<DefaultProverComponents>::new();
+        let (pk, _, _, vk) = prover.setup(test_artifacts::HELLO_WORLD_ELF);
+        let mut opts = ZKMProverOpts::default();
+        opts.core_opts.shard_size = 1 << 14;
+        opts.core_opts.shard_batch_size = 1;
+        let coordinator = Coordinator::new(&prover, workers);
+
+        let core_proof =
+            coordinator.prove_core(&pk, &ZKMStdin::default(), opts, ZKMContext::default()).unwrap();
+        assert!(core_proof.proof.0.len() > 1);
+        prover.verify(&core_proof.proof, &vk).unwrap();
+    }
+
+    /// Proves a program with a worker on localhost, and checks that the proofs verify.
+    #[test]
+    #[ignore]
+    fn test_distributed_prove() {
+        setup_logger();
+        let addr = spawn_worker();
+
+        let prover = ZKMProver::<DefaultProverComponents>::new();
+        let (pk, _, _, vk) = prover.setup(test_artifacts::FIBONACCI_ELF);
+        let opts = ZKMProverOpts::default();
+        let coordinator = Coordinator::new(&prover, vec![addr]);
+
+        let core_proof =
+            coordinator.prove_core(&pk, &ZKMStdin::default(), opts, ZKMContext::default()).unwrap();
+        prover.verify(&core_proof.proof, &vk).unwrap();
+
+        let compressed_proof = coordinator.compress(&vk, core_proof, vec![], opts).unwrap();
+        prover.verify_compressed(&compressed_proof, &vk).unwrap();
+    }
+}
@@ -0,0 +1,137 @@
+//! A minimal HTTP/1.1 framing for exchanging bincode-encoded tasks and results.
+//!
+//! Every exchange is a single request on its own connection:
+//!
+//! - `POST /task` with a bincode-encoded [`WorkerTask`](super::WorkerTask) body, answered by
+//!   `200 OK` with a bincode-encoded [`WorkerResult`](super::WorkerResult) body, or by
+//!   `500 Internal Server Error` with a UTF-8 error message.
+//! - `GET /health`, answered by `200 OK` once the worker is ready to accept tasks.
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpStream,
+};
+
+use super::DistributedError;
+
+/// The path of the task endpoint.
+pub const TASK_PATH: &str = "/task";
+
+/// The path of the health endpoint.
+pub const HEALTH_PATH: &str = "/health";
+
+/// The largest body accepted by either side, to bound the memory used by a malformed message.
+const MAX_BODY_LEN: usize = 64 << 30;
+
+/// An HTTP request, reduced to what the protocol uses.
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub body: Vec<u8>,
+}
+
+/// An HTTP response, reduced to what the protocol uses.
+pub struct Response {
+    pub status: u16,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    pub fn ok(body: Vec<u8>) -> Self {
+        Self { status: 200, body }
+    }
+
+    pub fn error(status: u16, message: impl Into<String>) -> Self {
+        Self { status, body: message.into().into_bytes() }
+    }
+}
+
+/// Reads the start line, the headers and the body of a message, returning the start line.
+fn read_message(reader: &mut impl BufRead) -> Result<(String, Vec<u8>), DistributedError> {
+    let mut start_line = String::new();
+    reader.read_line(&mut start_line)?;
+    if start_line.is_empty() {
+        return Err(DistributedError::Protocol("connection closed".to_string()));
+    }
+
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().map_err(|_| {
+                    DistributedError::Protocol(format!("invalid content length: {value}"))
+                })?;
+            }
+        }
+    }
+    if content_length > MAX_BODY_LEN {
+        return Err(DistributedError::Protocol(format!("body too large: {content_length}")));
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    Ok((start_line.trim_end().to_string(), body))
+}
+
+fn write_message(
+    stream: &mut impl Write,
+    start_line: &str,
+    body: &[u8],
+) -> Result<(), DistributedError> {
+    write!(
+        stream,
+        "{start_line}\r\nContent-Type: application/octet-stream\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n",
+        body.len()
+    )?;
+    stream.write_all(body)?;
+    stream.flush()?;
+    Ok(())
+}
+
+/// Reads a request from a server-side connection.
+pub fn read_request(stream: &TcpStream) -> Result<Request, DistributedError> {
+    let (start_line, body) = read_message(&mut BufReader::new(stream))?;
+    let mut parts = start_line.split_whitespace();
+    match (parts.next(), parts.next()) {
+        (Some(method), Some(path)) => {
+            Ok(Request { method: method.to_string(), path: path.to_string(), body })
+        }
+        _ => Err(DistributedError::Protocol(format!("invalid request line: {start_line}"))),
+    }
+}
+
+/// Writes a response to a server-side connection.
+pub fn write_response(mut stream: &TcpStream, response: &Response) -> Result<(), DistributedError> {
+    let reason = match response.status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        _ => "Internal Server Error",
+    };
+    write_message(&mut stream, &format!("HTTP/1.1 {} {reason}", response.status), &response.body)
+}
+
+/// Sends a request to `addr` and waits for the response.
+pub fn send_request(
+    addr: &str,
+    method: &str,
+    path: &str,
+    body: &[u8],
+) -> Result<Response, DistributedError> {
+    let mut stream = TcpStream::connect(addr)?;
+    write_message(&mut stream, &format!("{method} {path} HTTP/1.1\r\nHost: {addr}"), body)?;
+
+    let (status_line, body) = read_message(&mut BufReader::new(&stream))?;
+    let status =
+        status_line.split_whitespace().nth(1).and_then(|status| status.parse().ok()).ok_or_else(
+            || DistributedError::Protocol(format!("invalid status line: {status_line}")),
+        )?;
+    Ok(Response { status, body })
+}
@@ -0,0 +1,207 @@
+use std::{
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use rayon::prelude::*;
+use zkm_core_executor::{ExecutionRecord, ExecutionState, Program};
+use zkm_core_machine::utils::{trace_checkpoint, ZKMCoreProverError};
+use zkm_stark::{MachineProver, MachineProvingKey, MachineRecord, StarkGenericConfig, ZKMCoreOpts};
+
+use super::{
+    protocol::{self, Request, Response, HEALTH_PATH, TASK_PATH},
+    CheckpointTask, DeferredTask, DistributedError, ReduceTask, ShardsTask, WorkerResult,
+    WorkerTask,
+};
+use crate::{components::ZKMProverComponents, CoreSC, DeviceProvingKey, ZKMProver};
+
+/// A worker that executes the tasks emitted by a [`Coordinator`](super::Coordinator).
+pub struct ZKMWorker<C: ZKMProverComponents> {
+    prover: ZKMProver<C>,
+    /// The program and proving key of the last ELF seen by the worker.
+    setup: Mutex<Option<(Vec<u8>, Arc<Program>, Arc<DeviceProvingKey<C>>)>>,
+    /// Serializes the tasks, which already use all the cores of the host.
+    busy: Mutex<()>,
+}
+
+impl<C: ZKMProverComponents> ZKMWorker<C> {
+    /// Creates a worker backed by the given prover.
+    pub fn new(prover: ZKMProver<C>) -> Self {
+        Self { prover, setup: Mutex::new(None), busy: Mutex::new(()) }
+    }
+
+    /// The program and proving key of an ELF, which are set up once per ELF.
+    fn setup(&self, elf: &[u8]) -> (Arc<Program>, Arc<DeviceProvingKey<C>>) {
+        let mut setup = self.setup.lock().unwrap();
+        if let Some((cached_elf, program, pk)) = setup.as_ref() {
+            if cached_elf == elf {
+                return (program.clone(), pk.clone());
+            }
+        }
+        let (_, pk, program, _) = self.prover.setup(elf);
+        let (program, pk) = (Arc::new(program), Arc::new(pk));
+        *setup = Some((elf.to_vec(), program.clone(), pk.clone()));
+        (program, pk)
+    }
+
+    /// Executes a task.
+    pub fn execute(&self, task: WorkerTask) -> Result<WorkerResult, DistributedError> {
+        let _busy = self.busy.lock().unwrap_or_else(|e| e.into_inner());
+        match task {
+            WorkerTask::Checkpoint(task) => self.summarize_checkpoint(task),
+            WorkerTask::Shards(task) => self.prove_shards(task),
+            WorkerTask::Deferred(task) => self.prove_deferred(task),
+            WorkerTask::Reduce(task) => self.prove_reduce(task),
+        }
+    }
+
+    /// Traces the execution from a checkpoint into the records of its shards.
+    fn trace_checkpoint(
+        &self,
+        program: &Program,
+        state: ExecutionState,
+        opts: ZKMCoreOpts,
+    ) -> (Vec<ExecutionRecord>, u64) {
+        let (records, report) = trace_checkpoint::<CoreSC>(
+            program.clone(),
+            state,
+            opts,
+            self.prover.core_shape_config.as_ref(),
+            &self.prover.core_precompiles.syscalls(),
+        );
+        (records, report.total_instruction_count())
+    }
+
+    #[tracing::instrument(name = "summarize checkpoint", level = "info", skip_all)]
+    fn summarize_checkpoint(&self, task: CheckpointTask) -> Result<WorkerResult, DistributedError> {
+        let CheckpointTask { elf, state, opts } = task;
+        let (program, _) = self.setup(&elf);
+        let (mut records, cycles) = self.trace_checkpoint(&program, state, opts);
+        let mut deferred = ExecutionRecord::new(program);
+        for record in records.iter_mut() {
+            deferred.append(&mut record.defer());
+        }
+        let public_values = records.iter().map(|record| record.public_values).collect();
+        Ok(WorkerResult::Checkpoint { public_values, deferred, cycles })
+    }
+
+    #[tracing::instrument(name = "prove shards", level = "info", skip_all)]
+    fn prove_shards(&self, task: ShardsTask) -> Result<WorkerResult, DistributedError> {
+        let ShardsTask { elf, state, opts, public_values } = task;
+        let (program, pk) = self.setup(&elf);
+        let (mut records, _) = self.trace_checkpoint(&program, state, opts);
+        if records.len() != public_values.len() {
+            return Err(DistributedError::Worker(format!(
+                "the checkpoint has {} records, but {} were summarized",
+                records.len(),
+                public_values.len()
+            )));
+        }
+        for (record, public_values) in records.iter_mut().zip(public_values) {
+            record.public_values = public_values;
+            // The deferred events are proven by the deferred tasks.
+            let _ = record.defer();
+        }
+        self.prove_records(&pk, records, opts)
+    }
+
+    #[tracing::instrument(name = "prove deferred", level = "info", skip_all)]
+    fn prove_deferred(&self, task: DeferredTask) -> Result<WorkerResult, DistributedError> {
+        let DeferredTask { elf, records, opts } = task;
+        let (_, pk) = self.setup(&elf);
+        self.prove_records(&pk, records, opts)
+    }
+
+    /// Proves records whose public values are set.
+    fn prove_records(
+        &self,
+        pk: &DeviceProvingKey<C>,
+        mut records: Vec<ExecutionRecord>,
+        opts: ZKMCoreOpts,
+    ) -> Result<WorkerResult, DistributedError> {
+        let core_prover = &self.prover.core_prover;
+
+        // Generate the dependencies.
+        core_prover.machine().generate_dependencies(&mut records, &opts, None).map_err(|e| {
+            tracing::error!("Error generating dependencies: {:?}", e);
+            ZKMCoreProverError::DependenciesGenerationError
+        })?;
+
+        // Fix the shape of the records.
+        if let Some(shape_config) = &self.prover.core_shape_config {
+            for record in records.iter_mut() {
+                shape_config.fix_shape(record).map_err(ZKMCoreProverError::ShapeError)?;
+            }
+        }
+
+        let mut challenger = core_prover.config().challenger();
+        pk.observe_into(&mut challenger);
+
+        let proofs = records
+            .into_par_iter()
+            .map(|record| {
+                let traces = core_prover
+                    .generate_traces(&record)
+                    .map_err(|e| DistributedError::Worker(e.to_string()))?;
+                let data = core_prover.commit(&record, traces);
+                core_prover
+                    .open(pk, data, &mut challenger.clone())
+                    .map_err(|e| DistributedError::Worker(e.to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(WorkerResult::Shards(proofs))
+    }
+
+    #[tracing::instrument(name = "prove reduce", level = "info", skip_all)]
+    fn prove_reduce(&self, task: ReduceTask) -> Result<WorkerResult, DistributedError> {
+        let (vk, proof) = self
+            .prover
+            .prove_circuit_witness(task.input, task.opts)
+            .map_err(|e| DistributedError::Worker(e.to_string()))?;
+        Ok(WorkerResult::Reduce { vk, proof })
+    }
+
+    /// Serves tasks on the given listener, until it fails.
+    pub fn serve(self: Arc<Self>, listener: TcpListener) -> Result<(), DistributedError> {
+        tracing::info!("worker listening on {}", listener.local_addr()?);
+        for stream in listener.incoming() {
+            let stream = stream?;
+            let worker = Arc::clone(&self);
+            thread::spawn(move || {
+                if let Err(e) = worker.handle_connection(&stream) {
+                    tracing::error!("failed to handle connection: {}", e);
+                }
+            });
+        }
+        Ok(())
+    }
+
+    fn handle_connection(&self, stream: &TcpStream) -> Result<(), DistributedError> {
+        let response = match protocol::read_request(stream) {
+            Ok(request) => self.handle_request(request),
+            Err(e) => Response::error(400, e.to_string()),
+        };
+        protocol::write_response(stream, &response)
+    }
+
+    fn handle_request(&self, request: Request) -> Response {
+        match (request.method.as_str(), request.path.as_str()) {
+            ("GET", HEALTH_PATH) => Response::ok(b"ok".to_vec()),
+            ("POST", TASK_PATH) => {
+                let result = bincode::deserialize(&request.body)
+                    .map_err(DistributedError::from)
+                    .and_then(|task| self.execute(task))
+                    .and_then(|result| Ok(bincode::serialize(&result)?));
+                match result {
+                    Ok(body) => Response::ok(body),
+                    Err(e) => {
+                        tracing::error!("task failed: {}", e);
+                        Response::error(500, e.to_string())
+                    }
+                }
+            }
+            _ => Response::error(404, "not found"),
+        }
+    }
+}
@@ -0,0 +1,231 @@
+//! The encoding of the wrap proofs hashed with Keccak-256 for verifiers on the EVM.
+//!
+//! [`encode_proof`] and [`encode_vk`] flatten a proof and its verifying key into 32-byte words,
+//! in the order a Solidity verifier reads them with `calldataload`:
+//!
+//! - A field element is a `uint256` holding its canonical value.
+//! - An extension field element is 4 words, its coefficients in the basis `1, X, X^2, X^3`.
+//! - A Keccak-256 digest is a `bytes32`.
+//! - A list is a `uint256` length followed by its elements.
+//!
+//! A verifying key is its preprocessed commitment, the start pc, the 14 coordinates of the initial
+//! global cumulative sum, the log blowup, the number of queries and the proof of work bits, and
+//! the list of its preprocessed traces as `(log_n, shift, width, height)`.
+//!
+//! A proof is the main, permutation and quotient commitments, the list of the opened values of
+//! the chips, the FRI proof and the list of public values. The opened values of a chip are its log
+//! degree, the local and next rows of its preprocessed, main and permutation traces, the list of
+//! its quotient chunks, its global cumulative sum and its local cumulative sum. The FRI proof is
+//! the list of the commit phase commitments, the list of the queries, the final polynomial and the
+//! proof of work witness. A query is the list of its batch openings, each a list of opened rows
+//! and a Merkle path, followed by the list of its commit phase steps, each a sibling value and a
+//! Merkle path.
+
+use std::fmt::Write;
+
+use p3_field::{FieldExtensionAlgebra, PrimeField32};
+use p3_koala_bear::KoalaBear;
+use zkm_recursion_core::stark::{OuterChallenge, KECCAK_DIGEST_SIZE};
+use zkm_stark::{septic_digest::SepticDigest, AirOpenedValues, ShardProof, StarkVerifyingKey};
+
+use crate::{KeccakSC, ZKM_CIRCUIT_VERSION};
+
+/// The size in bytes of a word read by the EVM.
+pub const EVM_WORD_SIZE: usize = 32;
+
+/// A sequence of 32-byte words.
+#[derive(Debug, Clone, Default)]
+struct EvmWords(Vec<u8>);
+
+impl EvmWords {
+    fn uint(&mut self, value: u64) {
+        self.0.extend_from_slice(&[0; EVM_WORD_SIZE - 8]);
+        self.0.extend_from_slice(&value.to_be_bytes());
+    }
+
+    fn len(&mut self, len: usize) {
+        self.uint(len as u64);
+    }
+
+    fn felt(&mut self, value: KoalaBear) {
+        self.uint(value.as_canonical_u32().into());
+    }
+
+    fn felts(&mut self, values: &[KoalaBear]) {
+        self.len(values.len());
+        values.iter().for_each(|value| self.felt(*value));
+    }
+
+    fn ext(&mut self, value: OuterChallenge) {
+        value.as_base_slice().iter().for_each(|value| self.felt(*value));
+    }
+
+    fn exts(&mut self, values: &[OuterChallenge]) {
+        self.len(values.len());
+        values.iter().for_each(|value| self.ext(*value));
+    }
+
+    fn digest(&mut self, digest: impl Into<[u8; KECCAK_DIGEST_SIZE]>) {
+        self.0.extend_from_slice(&digest.into());
+    }
+
+    fn merkle_path(&mut self, path: &[[u8; KECCAK_DIGEST_SIZE]]) {
+        self.len(path.len());
+        path.iter().for_each(|digest| self.digest(*digest));
+    }
+
+    fn air_opened_values(&mut self, values: &AirOpenedValues<OuterChallenge>) {
+        self.exts(&values.local);
+        self.exts(&values.next);
+    }
+
+    fn septic_digest(&mut self, digest: &SepticDigest<KoalaBear>) {
+        digest.0.x.0.iter().chain(&digest.0.y.0).for_each(|value| self.felt(*value));
+    }
+}
+
+/// Encodes a verifying key as 32-byte words.
+pub fn encode_vk(vk: &StarkVerifyingKey<KeccakSC>) -> Vec<u8> {
+    let mut words = EvmWords::default();
+    words.digest(vk.commit);
+    words.felt(vk.pc_start);
+    words.septic_digest(&vk.initial_global_cumulative_sum);
+    words.len(vk.security_profile.log_blowup);
+    words.len(vk.security_profile.num_queries);
+    words.len(vk.security_profile.proof_of_work_bits);
+    words.len(vk.chip_information.len());
+    for (_, domain, dimensions) in &vk.chip_information {
+        words.len(domain.log_n);
+        words.felt(domain.shift);
+        words.len(dimensions.width);
+        words.len(dimensions.height);
+    }
+    words.0
+}
+
+/// Encodes a shard proof as 32-byte words.
+pub fn encode_proof(proof: &ShardProof<KeccakSC>) -> Vec<u8> {
+    let mut words = EvmWords::default();
+    words.digest(proof.commitment.main_commit);
+    words.digest(proof.commitment.permutation_commit);
+    words.digest(proof.commitment.quotient_commit);
+
+    words.len(proof.opened_values.chips.len());
+    for chip in &proof.opened_values.chips {
+        words.len(chip.log_degree);
+        words.air_opened_values(&chip.preprocessed);
+        words.air_opened_values(&chip.main);
+        words.air_opened_values(&chip.permutation);
+        words.len(chip.quotient.len());
+        chip.quotient.iter().for_each(|chunk| words.exts(chunk));
+        words.septic_digest(&chip.global_cumulative_sum);
+        words.ext(chip.local_cumulative_sum);
+    }
+
+    let fri_proof = &proof.opening_proof;
+    words.len(fri_proof.commit_phase_commits.len());
+    fri_proof.commit_phase_commits.iter().for_each(|commit| words.digest(*commit));
+    words.len(fri_proof.query_proofs.len());
+    for query in &fri_proof.query_proofs {
+        words.len(query.input_proof.len());
+        for batch in &query.input_proof {
+            words.len(batch.opened_values.len());
+            batch.opened_values.iter().for_each(|row| words.felts(row));
+            words.merkle_path(&batch.opening_proof);
+        }
+        words.len(query.commit_phase_openings.len());
+        for step in &query.commit_phase_openings {
+            words.ext(step.sibling_value);
+            words.merkle_path(&step.opening_proof);
+        }
+    }
+    words.ext(fri_proof.final_poly);
+    words.felt(fri_proof.pow_witness);
+
+    words.felts(&proof.public_values);
+    words.0
+}
+
+/// Generates a Solidity library with the parameters of a verifying key, and its encoding.
+pub fn solidity_constants(vk: &StarkVerifyingKey<KeccakSC>) -> String {
+    let hex = |bytes: &[u8]| bytes.iter().map(|b| format!("{b:02x}")).collect::<String>();
+    let commit: [u8; KECCAK_DIGEST_SIZE] = vk.commit.into();
+    let profile = vk.security_profile;
+
+    let mut out = String::new();
+    writeln!(out, "// SPDX-License-Identifier: MIT").unwrap();
+    writeln!(out, "pragma solidity ^0.8.20;").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "/// @title Ziren Keccak Verifier Constants").unwrap();
+    writeln!(out, "/// @notice The verifying key of the wrap program proven with Keccak-256.")
+        .unwrap();
+    writeln!(out, "/// @dev Generated for the circuit version {ZKM_CIRCUIT_VERSION}.").unwrap();
+    writeln!(out, "library ZKMKeccakVerifierConstants {{").unwrap();
+    let mut constant = |ty: &str, name: &str, value: String| {
+        writeln!(out, "    {ty} internal constant {name} = {value};").unwrap();
+    };
+    constant("uint256", "MODULUS", KoalaBear::ORDER_U32.to_string());
+    constant("bytes32", "PREPROCESSED_COMMIT", format!("0x{}", hex(&commit)));
+    constant("uint256", "PC_START", vk.pc_start.as_canonical_u32().to_string());
+    constant("uint256", "LOG_BLOWUP", profile.log_blowup.to_string());
+    constant("uint256", "NUM_QUERIES", profile.num_queries.to_string());
+    constant("uint256", "PROOF_OF_WORK_BITS", profile.proof_of_work_bits.to_string());
+    constant("uint256", "NUM_PREPROCESSED_CHIPS", vk.chip_information.len().to_string());
+    writeln!(out).unwrap();
+    writeln!(out, "    // The chips with preprocessed traces, in the order of the commitment:")
+        .unwrap();
+    for (i, (name, domain, dimensions)) in vk.chip_information.iter().enumerate() {
+        writeln!(
+            out,
+            "    // {i}: {name}, log_n = {}, width = {}, height = {}",
+            domain.log_n, dimensions.width, dimensions.height
+        )
+        .unwrap();
+    }
+    writeln!(out).unwrap();
+    writeln!(out, "    /// @dev The verifying key encoded as 32-byte words.").unwrap();
+    writeln!(out, "    bytes internal constant VERIFYING_KEY =").unwrap();
+    writeln!(out, "        hex\"{}\";", hex(&encode_vk(vk))).unwrap();
+    writeln!(out, "}}").unwrap();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_commit::TwoAdicMultiplicativeCoset;
+    use p3_field::FieldAlgebra;
+    use p3_matrix::Dimensions;
+    use zkm_recursion_core::stark::KeccakDigest;
+    use zkm_stark::SecurityProfile;
+
+    use super::*;
+
+    #[test]
+    fn test_encode_vk() {
+        let vk = StarkVerifyingKey::<KeccakSC> {
+            commit: KeccakDigest::from([0xab; KECCAK_DIGEST_SIZE]),
+            pc_start: KoalaBear::from_canonical_u32(7),
+            initial_global_cumulative_sum: SepticDigest::default(),
+            chip_information: vec![(
+                "Cpu".to_string(),
+                TwoAdicMultiplicativeCoset { log_n: 3, shift: KoalaBear::ONE },
+                Dimensions { width: 2, height: 8 },
+            )],
+            chip_ordering: Default::default(),
+            security_profile: SecurityProfile::OUTER,
+        };
+
+        let encoded = encode_vk(&vk);
+        let words = encoded.chunks_exact(EVM_WORD_SIZE).collect::<Vec<_>>();
+        assert_eq!(words.len(), 1 + 1 + 14 + 3 + 1 + 4);
+        assert_eq!(words[0], [0xab; EVM_WORD_SIZE]);
+        assert_eq!(words[1][EVM_WORD_SIZE - 1], 7);
+        assert_eq!(words[16][EVM_WORD_SIZE - 1], 4);
+        assert_eq!(words[19][EVM_WORD_SIZE - 1], 1);
+        assert_eq!(words[20][EVM_WORD_SIZE - 1], 3);
+
+        let constants = solidity_constants(&vk);
+        assert!(constants.contains("uint256 internal constant NUM_QUERIES = 21;"));
+        assert!(constants.contains("// 0: Cpu, log_n = 3, width = 2, height = 8"));
+    }
+}
@@ -0,0 +1,262 @@
+//! A content-addressed on-disk cache of proving and verifying keys.
+//!
+//! Entries are keyed by the hash of the ELF, the circuit version, whether the core shapes are
+//! fixed, the security profile and whether the proofs are hiding, so that a stale key is never
+//! served after an upgrade or to a prover of another configuration. Each entry carries a checksum of
+//! its contents, and the least recently used entries are evicted once the cache is full.
+
+use std::{
+    env, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use zkm_stark::SecurityProfile;
+
+use crate::{ZKMProvingKey, ZKM_CIRCUIT_VERSION};
+
+/// The magic bytes at the start of every cache entry.
+const MAGIC: &[u8; 8] = b"ZKMKEYS1";
+
+/// The extension of the cache entries.
+const EXTENSION: &str = "bin";
+
+/// The default maximum number of entries kept in the cache.
+const DEFAULT_MAX_ENTRIES: usize = 16;
+
+/// An error that occurs while reading or writing the key cache.
+#[derive(Error, Debug)]
+pub enum KeyCacheError {
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("serialization error: {0}")]
+    Serialization(#[from] bincode::Error),
+    #[error("corrupted cache entry")]
+    Corrupted,
+    #[error("cache entry does not match the requested program")]
+    Mismatch,
+}
+
+/// A persistent cache of [`ZKMProvingKey`]s, which also contain the verifying keys.
+#[derive(Debug, Clone)]
+pub struct KeyCache {
+    dir: PathBuf,
+    max_entries: usize,
+}
+
+impl KeyCache {
+    /// Creates a cache in the given directory, which keeps at most `max_entries` keys.
+    pub fn new(dir: impl Into<PathBuf>, max_entries: usize) -> Self {
+        assert!(max_entries > 0, "the key cache must hold at least one entry");
+        Self { dir: dir.into(), max_entries }
+    }
+
+    /// The default cache directory, `~/.zkm/keys`, or `zkm/keys` in the temporary directory when
+    /// there is no home directory.
+    pub fn default_dir() -> PathBuf {
+        match dirs::home_dir() {
+            Some(home) => home.join(".zkm").join("keys"),
+            None => env::temp_dir().join("zkm").join("keys"),
+        }
+    }
+
+    /// Creates a cache from the environment, if enabled with `ZKM_KEY_CACHE=true`.
+    ///
+    /// The directory and the maximum number of entries can be overridden with
+    /// `ZKM_KEY_CACHE_DIR` and `ZKM_KEY_CACHE_SIZE`.
+    pub fn from_env() -> Option<Self> {
+        let enabled =
+            env::var("ZKM_KEY_CACHE").map(|v| v.eq_ignore_ascii_case("true")).unwrap_or(false);
+        if !enabled {
+            return None;
+        }
+
+        let dir = env::var("ZKM_KEY_CACHE_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| Self::default_dir());
+        let max_entries = env::var("ZKM_KEY_CACHE_SIZE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|size| *size > 0)
+            .unwrap_or(DEFAULT_MAX_ENTRIES);
+        Some(Self::new(dir, max_entries))
+    }
+
+    /// The directory of the cache.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The cache key of an ELF, given whether the core shapes of its program are fixed, the
+    /// security profile of the core prover and whether its proofs are hiding.
+    pub fn key(elf: &[u8], fixed_shapes: bool, profile: SecurityProfile, zk: bool) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(ZKM_CIRCUIT_VERSION.as_bytes());
+        hasher.update([0, fixed_shapes as u8, zk as u8]);
+        for param in [profile.log_blowup, profile.num_queries, profile.proof_of_work_bits] {
+            hasher.update((param as u64).to_le_bytes());
+        }
+        hasher.update(elf);
+        hasher.finalize().iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(key).with_extension(EXTENSION)
+    }
+
+    /// Loads the proving key of an ELF, if it is cached.
+    ///
+    /// Entries that fail the integrity checks are removed from the cache.
+    pub fn load(
+        &self,
+        elf: &[u8],
+        fixed_shapes: bool,
+        profile: SecurityProfile,
+        zk: bool,
+    ) -> Option<ZKMProvingKey> {
+        let key = Self::key(elf, fixed_shapes, profile, zk);
+        let path = self.path(&key);
+        if !path.exists() {
+            return None;
+        }
+
+        match Self::read_entry(&path, &key, elf) {
+            Ok(pk) => {
+                // Mark the entry as recently used.
+                if let Err(e) = fs::File::options()
+                    .write(true)
+                    .open(&path)
+                    .and_then(|f| f.set_modified(SystemTime::now()))
+                {
+                    tracing::warn!("failed to touch key cache entry {}: {}", path.display(), e);
+                }
+                tracing::debug!("key cache hit: {}", key);
+                Some(pk)
+            }
+            Err(e) => {
+                tracing::warn!("discarding key cache entry {}: {}", path.display(), e);
+                let _ = fs::remove_file(&path);
+                None
+            }
+        }
+    }
+
+    fn read_entry(path: &Path, key: &str, elf: &[u8]) -> Result<ZKMProvingKey, KeyCacheError> {
+        let bytes = fs::read(path)?;
+        if bytes.len() < MAGIC.len() + 32 || &bytes[..MAGIC.len()] != MAGIC {
+            return Err(KeyCacheError::Corrupted);
+        }
+        let (checksum, payload) = bytes[MAGIC.len()..].split_at(32);
+        if Sha256::digest(payload).as_slice() != checksum {
+            return Err(KeyCacheError::Corrupted);
+        }
+
+        let (version, entry_key, pk): (String, String, ZKMProvingKey) =
+            bincode::deserialize(payload)?;
+        if version != ZKM_CIRCUIT_VERSION || entry_key != key || pk.elf != elf {
+            return Err(KeyCacheError::Mismatch);
+        }
+        Ok(pk)
+    }
+
+    /// Stores the proving key of an ELF, evicting the least recently used entries if the cache is
+    /// full.
+    pub fn store(
+        &self,
+        pk: &ZKMProvingKey,
+        fixed_shapes: bool,
+        profile: SecurityProfile,
+        zk: bool,
+    ) -> Result<(), KeyCacheError> {
+        let key = Self::key(&pk.elf, fixed_shapes, profile, zk);
+        let payload = bincode::serialize(&(ZKM_CIRCUIT_VERSION, &key, pk))?;
+
+        fs::create_dir_all(&self.dir)?;
+
+        // Write to a temporary file first, so that concurrent readers never see a partial entry.
+        let path = self.path(&key);
+        let tmp_path = path.with_extension(format!("{EXTENSION}.{}.tmp", std::process::id()));
+        {
+            let mut file = fs::File::create(&tmp_path)?;
+            file.write_all(MAGIC)?;
+            file.write_all(&Sha256::digest(&payload))?;
+            file.write_all(&payload)?;
+            file.sync_all()?;
+        }
+        fs::rename(&tmp_path, &path)?;
+
+        self.evict()
+    }
+
+    /// Removes the least recently used entries until at most `max_entries` remain.
+    fn evict(&self) -> Result<(), KeyCacheError> {
+        let mut entries = fs::read_dir(&self.dir)?
+            .filter_map(Result::ok)
+            .filter(|entry| entry.path().extension().is_some_and(|ext| ext == EXTENSION))
+            .filter_map(|entry| {
+                let modified = entry.metadata().and_then(|m| m.modified()).ok()?;
+                Some((modified, entry.path()))
+            })
+            .collect::<Vec<_>>();
+        if entries.len() <= self.max_entries {
+            return Ok(());
+        }
+
+        entries.sort();
+        let num_evicted = entries.len() - self.max_entries;
+        for (_, path) in entries.into_iter().take(num_evicted) {
+            tracing::debug!("evicting key cache entry {}", path.display());
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    /// Removes all the entries of the cache.
+    pub fn clear(&self) -> Result<(), KeyCacheError> {
+        if !self.dir.exists() {
+            return Ok(());
+        }
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == EXTENSION) {
+                fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_depends_on_elf_shapes_and_config() {
+        let profile = SecurityProfile::DEFAULT;
+        let key = KeyCache::key(b"elf", true, profile, false);
+        assert_eq!(key, KeyCache::key(b"elf", true, profile, false));
+        assert_ne!(key, KeyCache::key(b"elf", false, profile, false));
+        assert_ne!(key, KeyCache::key(b"other elf", true, profile, false));
+        assert_ne!(key, KeyCache::key(b"elf", true, SecurityProfile::COMPRESSED, false));
+        assert_ne!(key, KeyCache::key(b"elf", true, profile, true));
+    }
+
+    #[test]
+    fn test_corrupted_entry_is_discarded() {
+        let dir = env::temp_dir().join(format!("zkm-key-cache-test-{}", std::process::id()));
+        let cache = KeyCache::new(&dir, 1);
+        fs::create_dir_all(&dir).unwrap();
+
+        let profile = SecurityProfile::DEFAULT;
+        let path = cache.path(&KeyCache::key(b"elf", true, profile, false));
+        fs::write(&path, b"ZKMKEYS1 but not a valid entry").unwrap();
+        assert!(cache.load(b"elf", true, profile, false).is_none());
+        assert!(!path.exists());
+
+        cache.clear().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -13,6 +13,9 @@
 
 pub mod build;
 pub mod components;
+pub mod distributed;
+pub mod evm;
+pub mod key_cache;
 pub mod shapes;
 pub mod types;
 pub mod utils;
@@ -20,7 +23,7 @@ pub mod verify;
 
 use std::{
     borrow::Borrow,
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     env,
     num::NonZeroUsize,
     path::Path,
@@ -38,13 +41,15 @@ use p3_koala_bear::KoalaBear;
 use p3_matrix::dense::RowMajorMatrix;
 use shapes::ZKMProofShape;
 use tracing::instrument;
-use zkm_core_executor::{ExecutionError, ExecutionReport, Executor, Program, ZKMContext};
+use zkm_core_executor::{
+    ExecutionError, ExecutionReport, ExecutionState, Executor, Program, SnapshotTrigger, ZKMContext,
+};
 use zkm_core_machine::{
     io::ZKMStdin,
-    mips::MipsAir,
+    mips::{CoreMachineAir, PrecompileRegistry},
     reduce::ZKMReduceProof,
     shape::CoreShapeConfig,
-    utils::{concurrency::TurnBasedSync, ZKMCoreProverError},
+    utils::{concurrency::TurnBasedSync, WorkDir, ZKMCoreProverError},
 };
 use zkm_primitives::{hash_deferred_proof, io::ZKMPublicValues};
 use zkm_recursion_circuit::{
@@ -70,15 +75,15 @@ use zkm_recursion_core::{
     machine::RecursionAir,
     runtime::ExecutionRecord,
     shape::{RecursionShape, RecursionShapeConfig},
-    stark::KoalaBearPoseidon2Outer,
+    stark::{zkm_dev_mode, KoalaBearKeccak, KoalaBearPoseidon2Outer},
     RecursionProgram, Runtime as RecursionRuntime,
 };
 pub use zkm_recursion_gnark_ffi::proof::{Groth16Bn254Proof, PlonkBn254Proof};
 use zkm_recursion_gnark_ffi::{groth16_bn254::Groth16Bn254Prover, plonk_bn254::PlonkBn254Prover};
 use zkm_stark::{
-    air::PublicValues, koala_bear_poseidon2::KoalaBearPoseidon2, Challenge, MachineProver,
-    ShardProof, StarkGenericConfig, StarkVerifyingKey, Val, Word, ZKMCoreOpts, ZKMProverOpts,
-    DIGEST_SIZE,
+    air::PublicValues, koala_bear_poseidon2::KoalaBearPoseidon2, Challenge, CpuProver,
+    MachineProver, SecurityProfile, ShardProof, StarkGenericConfig, StarkVerifyingKey, Val, Word,
+    ZKMCoreOpts, ZKMProverOpts, DIGEST_SIZE,
 };
 use zkm_stark::{shape::OrderedShape, MachineProvingKey};
 
@@ -86,6 +91,7 @@ pub use types::*;
 use utils::{words_to_bytes, zkm_committed_values_digest_bn254, zkm_vkey_digest_bn254};
 
 use components::{DefaultProverComponents, ZKMProverComponents};
+use key_cache::KeyCache;
 
 pub use zkm_core_machine::ZKM_CIRCUIT_VERSION;
 
@@ -98,9 +104,12 @@ pub type InnerSC = KoalaBearPoseidon2;
 /// The configuration for the outer prover.
 pub type OuterSC = KoalaBearPoseidon2Outer;
 
+/// The configuration for the outer prover whose proofs are verified on the EVM without a SNARK.
+pub type KeccakSC = KoalaBearKeccak;
+
 pub type DeviceProvingKey<C> = <<C as ZKMProverComponents>::CoreProver as MachineProver<
     KoalaBearPoseidon2,
-    MipsAir<KoalaBear>,
+    <C as ZKMProverComponents>::CoreAir,
 >>::DeviceProvingKey;
 
 const COMPRESS_DEGREE: usize = 3;
@@ -110,6 +119,17 @@ const WRAP_DEGREE: usize = 9;
 const CORE_CACHE_SIZE: usize = 5;
 pub const REDUCE_BATCH_SIZE: usize = 2;
 
+/// The work of a node of the recursion tree, unless its proof was saved by a previous attempt.
+#[allow(clippy::large_enum_variant)]
+enum ReduceWork {
+    Prove(
+        Arc<RecursionProgram<KoalaBear>>,
+        ExecutionRecord<KoalaBear>,
+        Vec<(String, RowMajorMatrix<KoalaBear>)>,
+    ),
+    Saved(StarkVerifyingKey<InnerSC>, ShardProof<InnerSC>),
+}
+
 // TODO: FIX
 //
 // const SHAPES_URL_PREFIX: &str = "https://zkm-circuits.s3.us-east-2.amazonaws.com/shapes";
@@ -127,6 +147,9 @@ pub struct ZKMProver<C: ZKMProverComponents = DefaultProverComponents> {
     /// The machine used for proving the core step.
     pub core_prover: C::CoreProver,
 
+    /// The custom precompiles of the core machine.
+    pub core_precompiles: PrecompileRegistry<C::CoreAir>,
+
     /// The machine used for proving the recursive and reduction steps.
     pub compress_prover: C::CompressProver,
 
@@ -169,8 +192,14 @@ pub struct ZKMProver<C: ZKMProverComponents = DefaultProverComponents> {
     /// The verifying key for wrapping.
     pub wrap_vk: OnceLock<StarkVerifyingKey<OuterSC>>,
 
+    /// The machine used for proving the wrapping step with Keccak-256.
+    pub keccak_wrap_prover: OnceLock<CpuProver<KeccakSC, WrapAir<KoalaBear>>>,
+
     /// Whether to verify verification keys.
     pub vk_verification: bool,
+
+    /// The on-disk cache of proving keys, if enabled.
+    pub key_cache: Option<KeyCache>,
 }
 
 impl<C: ZKMProverComponents> ZKMProver<C> {
@@ -182,18 +211,62 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
 
     /// Creates a new [ZKMProver] with lazily initialized components.
     pub fn uninitialized() -> Self {
+        Self::with_security_profile(SecurityProfile::default())
+    }
+
+    /// Creates a new [ZKMProver] whose core and compress proofs have the given security profile.
+    ///
+    /// The shrink and wrap proofs have the same security at the larger blowups they are proven
+    /// with. The allowed recursion verification keys are only valid for the default profile, so a
+    /// different one requires `VERIFY_VK=false`. The proofs are zero-knowledge if `HIDING` is set,
+    /// see [`ZKMProver::with_profile_and_hiding`].
+    pub fn with_security_profile(profile: SecurityProfile) -> Self {
+        let hiding = env::var("HIDING").map(|v| v.eq_ignore_ascii_case("true")).unwrap_or(false);
+        Self::with_profile_and_hiding(profile, hiding)
+    }
+
+    /// Creates a new [ZKMProver] whose core and compress proofs have the given security profile,
+    /// and are zero-knowledge if `hiding` is set.
+    ///
+    /// The shrink proof does not reveal more than the compressed proof it verifies, and the wrap
+    /// proof is zero-knowledge by itself. The allowed recursion verification keys only cover the
+    /// recursion programs of non-hiding proofs, so hiding requires `VERIFY_VK=false`.
+    pub fn with_profile_and_hiding(profile: SecurityProfile, hiding: bool) -> Self {
+        assert!(profile.log_blowup >= 1, "the log blowup of the security profile must be positive");
+        let profile_with_min_blowup =
+            |log_blowup: usize| profile.with_log_blowup(profile.log_blowup.max(log_blowup));
+
+        let (core_config, compress_config) = if hiding {
+            let hiding_profile = profile_with_min_blowup(SecurityProfile::COMPRESSED.log_blowup);
+            (
+                CoreSC::hiding_with_profile(hiding_profile),
+                InnerSC::hiding_with_profile(hiding_profile),
+            )
+        } else {
+            (CoreSC::with_profile(profile), InnerSC::with_profile(profile))
+        };
+
         // Initialize the provers.
-        let core_machine = MipsAir::machine(CoreSC::default());
+        let core_precompiles = C::CoreAir::precompiles();
+        let core_machine = core_precompiles.machine(core_config);
         let core_prover = C::CoreProver::new(core_machine);
 
-        let compress_machine = CompressAir::compress_machine(InnerSC::default());
+        let compress_machine = CompressAir::compress_machine(compress_config);
         let compress_prover = C::CompressProver::new(compress_machine);
 
         // TODO: Put the correct shrink and wrap machines here.
-        let shrink_machine = ShrinkAir::shrink_machine(InnerSC::compressed());
+        let shrink_config =
+            InnerSC::with_profile(profile_with_min_blowup(SecurityProfile::COMPRESSED.log_blowup));
+        let shrink_machine = ShrinkAir::shrink_machine(shrink_config);
         let shrink_prover = C::ShrinkProver::new(shrink_machine);
 
-        let wrap_machine = WrapAir::wrap_machine(OuterSC::default());
+        // In dev mode, the wrap proof has a single query.
+        let wrap_config = if zkm_dev_mode() {
+            OuterSC::default()
+        } else {
+            OuterSC::with_profile(profile_with_min_blowup(SecurityProfile::OUTER.log_blowup))
+        };
+        let wrap_machine = WrapAir::wrap_machine(wrap_config);
         let wrap_prover = C::WrapProver::new(wrap_machine);
 
         let core_cache_size = NonZeroUsize::new(
@@ -207,7 +280,11 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
         let core_shape_config = env::var("FIX_CORE_SHAPES")
             .map(|v| v.eq_ignore_ascii_case("true"))
             .unwrap_or(true)
-            .then_some(CoreShapeConfig::default());
+            .then(|| {
+                let mut core_shape_config = CoreShapeConfig::default();
+                core_precompiles.extend_shape_config(&mut core_shape_config);
+                core_shape_config
+            });
 
         let recursion_shape_config = env::var("FIX_RECURSION_SHAPES")
             .map(|v| v.eq_ignore_ascii_case("true"))
@@ -218,6 +295,16 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
             env::var("VERIFY_VK").map(|v| v.eq_ignore_ascii_case("true")).unwrap_or(true);
 
         tracing::debug!("vk verification: {}", vk_verification);
+        assert!(
+            !(hiding && vk_verification),
+            "HIDING=true requires VERIFY_VK=false, the allowed vk map is for non-hiding proofs"
+        );
+        assert!(
+            profile == SecurityProfile::default() || !vk_verification,
+            "a non-default security profile requires VERIFY_VK=false, the allowed vk map is for \
+             the default one"
+        );
+        tracing::info!("security profile: {}", profile);
 
         // Read the shapes from the shapes directory and deserialize them into memory.
         let allowed_vk_map: BTreeMap<[KoalaBear; DIGEST_SIZE], usize> = if vk_verification {
@@ -258,6 +345,7 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
 
         Self {
             core_prover,
+            core_precompiles,
             compress_prover,
             shrink_prover,
             wrap_prover,
@@ -273,20 +361,65 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
             vk_verification,
             wrap_program: OnceLock::new(),
             wrap_vk: OnceLock::new(),
+            keccak_wrap_prover: OnceLock::new(),
+            key_cache: KeyCache::from_env(),
         }
     }
 
+    /// Uses the given on-disk cache of proving keys in [`ZKMProver::setup`].
+    pub fn with_key_cache(mut self, key_cache: KeyCache) -> Self {
+        self.key_cache = Some(key_cache);
+        self
+    }
+
+    /// Allows more recursion verifying keys, such as the ones of the programs verifying the shards
+    /// of custom precompiles, and recommits the Merkle tree of the allowed verifying keys.
+    ///
+    /// The indices of the verifying keys follow the order of their digests, as when the map is
+    /// built. The wrap program is recompiled for the new root, and the cached compression
+    /// programs are kept only if the height of the tree is unchanged.
+    pub fn extend_vk_map(
+        &mut self,
+        new_digests: impl IntoIterator<Item = <InnerSC as FieldHasher<KoalaBear>>::Digest>,
+    ) {
+        let mut digests = self.recursion_vk_map.keys().copied().collect::<BTreeSet<_>>();
+        digests.extend(new_digests);
+        let digests = digests.into_iter().collect::<Vec<_>>();
+
+        self.recursion_vk_map = digests.iter().enumerate().map(|(i, vk)| (*vk, i)).collect();
+        let (root, merkle_tree) = MerkleTree::commit(digests);
+        self.join_programs_map.retain(|shape, _| shape.merkle_tree_height == merkle_tree.height);
+        self.recursion_vk_root = root;
+        self.recursion_vk_tree = merkle_tree;
+        self.wrap_program = OnceLock::new();
+        self.wrap_vk = OnceLock::new();
+    }
+
     /// Fully initializes the programs, proving keys, and verifying keys that are normally
     /// lazily initialized. TODO: remove this.
     pub fn initialize(&mut self) {}
 
     /// Creates a proving key and a verifying key for a given MIPS ELF.
+    ///
+    /// If a key cache is configured, the keys are loaded from it when present, and stored in it
+    /// otherwise.
     #[instrument(name = "setup", level = "debug", skip_all)]
     pub fn setup(
         &self,
         elf: &[u8],
     ) -> (ZKMProvingKey, DeviceProvingKey<C>, Program, ZKMVerifyingKey) {
         let program = self.get_program(elf).unwrap();
+        let fixed_shapes = self.core_shape_config.is_some();
+        let profile = self.core_prover.config().security_profile();
+        let zk = self.core_prover.config().zk().is_some();
+        if let Some(pk) =
+            self.key_cache.as_ref().and_then(|cache| cache.load(elf, fixed_shapes, profile, zk))
+        {
+            let pk_d = self.core_prover.pk_to_device(&pk.pk);
+            let vk = pk.vk.clone();
+            return (pk, pk_d, program, vk);
+        }
+
         let (pk, vk) = self.core_prover.setup(&program);
         let vk = ZKMVerifyingKey { vk };
         let pk = ZKMProvingKey {
@@ -294,10 +427,38 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
             elf: elf.to_vec(),
             vk: vk.clone(),
         };
+        if let Some(cache) = &self.key_cache {
+            if let Err(e) = cache.store(&pk, fixed_shapes, profile, zk) {
+                tracing::warn!("failed to store the proving key in the key cache: {}", e);
+            }
+        }
         let pk_d = self.core_prover.pk_to_device(&pk.pk);
         (pk, pk_d, program, vk)
     }
 
+    /// Creates a proving key and a verifying key for a MIPS ELF resumed from a snapshot of its
+    /// execution.
+    ///
+    /// The keys commit to the memory image and the program counter of the snapshot, see
+    /// [`Program::resume_from`]. They are never stored in the key cache.
+    #[instrument(name = "setup_from_snapshot", level = "debug", skip_all)]
+    pub fn setup_from_snapshot(
+        &self,
+        elf: &[u8],
+        snapshot: &ExecutionState,
+    ) -> eyre::Result<(ZKMProvingKey, DeviceProvingKey<C>, Program, ZKMVerifyingKey)> {
+        let program = self.get_resumed_program(elf, snapshot)?;
+        let (pk, vk) = self.core_prover.setup(&program);
+        let vk = ZKMVerifyingKey { vk };
+        let pk = ZKMProvingKey {
+            pk: self.core_prover.pk_to_host(&pk),
+            elf: elf.to_vec(),
+            vk: vk.clone(),
+        };
+        let pk_d = self.core_prover.pk_to_device(&pk.pk);
+        Ok((pk, pk_d, program, vk))
+    }
+
     /// Get a program with an allowed preprocessed shape.
     pub fn get_program(&self, elf: &[u8]) -> eyre::Result<Program> {
         let mut program = Program::from(elf).unwrap();
@@ -307,6 +468,33 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
         Ok(program)
     }
 
+    /// Get the program resumed from a snapshot, with an allowed preprocessed shape.
+    pub fn get_resumed_program(
+        &self,
+        elf: &[u8],
+        snapshot: &ExecutionState,
+    ) -> eyre::Result<Program> {
+        let mut program =
+            Program::from(elf).unwrap().resume_from(snapshot).map_err(|e| eyre::eyre!("{e}"))?;
+        if let Some(core_shape_config) = &self.core_shape_config {
+            core_shape_config.fix_preprocessed_shape(&mut program)?;
+        }
+        Ok(program)
+    }
+
+    /// Get the program to run in `context`, i.e. resumed from the snapshot of the context if it
+    /// has one.
+    pub fn get_program_with_context(
+        &self,
+        elf: &[u8],
+        context: &ZKMContext,
+    ) -> eyre::Result<Program> {
+        match context.snapshot {
+            Some(snapshot) => self.get_resumed_program(elf, snapshot),
+            None => self.get_program(elf),
+        }
+    }
+
     /// Generate a proof of a Ziren program with the specified inputs.
     #[instrument(name = "execute", level = "info", skip_all)]
     pub fn execute<'a>(
@@ -316,10 +504,13 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
         mut context: ZKMContext<'a>,
     ) -> Result<(ZKMPublicValues, ExecutionReport), ExecutionError> {
         context.subproof_verifier = Some(self);
-        let program = self.get_program(elf).unwrap();
+        self.core_precompiles.add_missing_syscalls(&mut context);
+        let program = self.get_program_with_context(elf, &context).unwrap();
         let opts = ZKMCoreOpts::default();
         let mut runtime = Executor::with_context(program, opts, context);
         runtime.write_vecs(&stdin.buffer);
+        runtime.write_files(&stdin.files);
+        runtime.set_linux_env(stdin.linux_env);
         for (proof, vkey) in stdin.proofs.iter() {
             runtime.write_proof(proof.clone(), vkey.clone());
         }
@@ -327,24 +518,66 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
         Ok((ZKMPublicValues::from(&runtime.state.public_values_stream), runtime.report))
     }
 
+    /// Execute a Ziren program with the specified inputs until `trigger` fires, and return the
+    /// snapshot of the execution at that point, or `None` if the program finished first.
+    #[instrument(name = "snapshot", level = "info", skip_all)]
+    pub fn snapshot<'a>(
+        &'a self,
+        elf: &[u8],
+        stdin: &ZKMStdin,
+        mut context: ZKMContext<'a>,
+        trigger: SnapshotTrigger,
+    ) -> Result<Option<ExecutionState>, ExecutionError> {
+        context.subproof_verifier = Some(self);
+        self.core_precompiles.add_missing_syscalls(&mut context);
+        let program = self.get_program_with_context(elf, &context).unwrap();
+        let opts = ZKMCoreOpts::default();
+        let mut runtime = Executor::with_context(program, opts, context);
+        runtime.write_vecs(&stdin.buffer);
+        runtime.write_files(&stdin.files);
+        runtime.set_linux_env(stdin.linux_env);
+        for (proof, vkey) in stdin.proofs.iter() {
+            runtime.write_proof(proof.clone(), vkey.clone());
+        }
+        runtime.run_until_snapshot(trigger)
+    }
+
     /// Generate shard proofs which split up and prove the valid execution of a MIPS program with
     /// the core prover. Uses the provided context.
-    #[instrument(name = "prove_core", level = "info", skip_all)]
     pub fn prove_core<'a>(
         &'a self,
-        pk_d: &<<C as ZKMProverComponents>::CoreProver as MachineProver<
-            KoalaBearPoseidon2,
-            MipsAir<KoalaBear>,
-        >>::DeviceProvingKey,
+        pk_d: &DeviceProvingKey<C>,
         program: Program,
         stdin: &ZKMStdin,
         opts: ZKMProverOpts,
+        context: ZKMContext<'a>,
+    ) -> Result<ZKMCoreProof, ZKMCoreProverError> {
+        self.prove_core_with_work_dir(pk_d, program, stdin, opts, context, None)
+    }
+
+    /// Like [`prove_core`](Self::prove_core), but persists the checkpoints and the shard proofs in
+    /// `work_dir`, and reuses the ones saved by a previous attempt.
+    #[instrument(name = "prove_core", level = "info", skip_all)]
+    pub fn prove_core_with_work_dir<'a>(
+        &'a self,
+        pk_d: &DeviceProvingKey<C>,
+        program: Program,
+        stdin: &ZKMStdin,
+        mut opts: ZKMProverOpts,
         mut context: ZKMContext<'a>,
+        work_dir: Option<&WorkDir>,
     ) -> Result<ZKMCoreProof, ZKMCoreProverError> {
+        let profile = self.core_prover.config().security_profile();
+        if !profile.is_at_least(&opts.security_profile) {
+            return Err(ZKMCoreProverError::InsufficientSecurity(profile, opts.security_profile));
+        }
+
         context.subproof_verifier = Some(self);
+        self.core_precompiles.add_missing_syscalls(&mut context);
+        opts.core_opts.split_opts = self.core_precompiles.split_opts(opts.core_opts.split_opts);
         let pk = pk_d;
         let (proof, public_values_stream, cycles) =
-            zkm_core_machine::utils::prove_with_context::<_, C::CoreProver>(
+            zkm_core_machine::utils::prove_with_work_dir::<_, _, C::CoreProver>(
                 &self.core_prover,
                 pk,
                 program,
@@ -352,6 +585,7 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
                 opts.core_opts,
                 context,
                 self.core_shape_config.as_ref(),
+                work_dir,
             )?;
         Self::check_for_high_cycles(cycles);
         let public_values = ZKMPublicValues::from(&public_values_stream);
@@ -610,7 +844,6 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
     }
 
     /// Reduce shard proofs to a single shard proof using the recursion prover.
-    #[instrument(name = "compress", level = "info", skip_all)]
     pub fn compress(
         &self,
         vk: &ZKMVerifyingKey,
@@ -618,6 +851,22 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
         deferred_proofs: Vec<ZKMReduceProof<InnerSC>>,
         opts: ZKMProverOpts,
     ) -> Result<ZKMReduceProof<InnerSC>, ZKMRecursionProverError> {
+        self.compress_with_work_dir(vk, proof, deferred_proofs, opts, None)
+    }
+
+    /// Like [`compress`](Self::compress), but persists the proofs of the recursion tree in
+    /// `work_dir`, and reuses the ones saved by a previous attempt.
+    #[instrument(name = "compress", level = "info", skip_all)]
+    pub fn compress_with_work_dir(
+        &self,
+        vk: &ZKMVerifyingKey,
+        proof: ZKMCoreProof,
+        deferred_proofs: Vec<ZKMReduceProof<InnerSC>>,
+        opts: ZKMProverOpts,
+        work_dir: Option<&WorkDir>,
+    ) -> Result<ZKMReduceProof<InnerSC>, ZKMRecursionProverError> {
+        Self::check_security_profile(self.compress_prover.config(), &opts)?;
+
         // The batch size for reducing two layers of recursion.
         let batch_size = REDUCE_BATCH_SIZE;
         // The batch size for reducing the first layer of recursion.
@@ -663,13 +912,9 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
             // Spawn workers who generate the records and traces.
             let record_and_trace_sync = Arc::new(TurnBasedSync::new());
             let (record_and_trace_tx, record_and_trace_rx) =
-                sync_channel::<(
-                    usize,
-                    usize,
-                    Arc<RecursionProgram<KoalaBear>>,
-                    ExecutionRecord<KoalaBear>,
-                    Vec<(String, RowMajorMatrix<KoalaBear>)>,
-                )>(opts.recursion_opts.records_and_traces_channel_capacity);
+                sync_channel::<(usize, usize, ReduceWork)>(
+                    opts.recursion_opts.records_and_traces_channel_capacity,
+                );
             let record_and_trace_tx = Arc::new(Mutex::new(record_and_trace_tx));
             let record_and_trace_rx = Arc::new(Mutex::new(record_and_trace_rx));
             let input_rx = Arc::new(Mutex::new(input_rx));
@@ -683,6 +928,19 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
                     loop {
                         let received = { input_rx.lock().unwrap().recv() };
                         if let Ok((index, height, input)) = received {
+                            // Reuse the proof of a previous attempt.
+                            let name = WorkDir::reduce_proof_name(index);
+                            if let Some((vk, proof)) = work_dir.and_then(|dir| dir.load(&name)) {
+                                record_and_trace_sync.wait_for_turn(index);
+                                record_and_trace_tx
+                                    .lock()
+                                    .unwrap()
+                                    .send((index, height, ReduceWork::Saved(vk, proof)))
+                                    .unwrap();
+                                record_and_trace_sync.advance_turn();
+                                continue;
+                            }
+
                             // Get the program and witness stream.
                             let (program, witness_stream) = tracing::debug_span!(
                                 "get program and witness stream"
@@ -770,7 +1028,7 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
                             record_and_trace_tx
                                 .lock()
                                 .unwrap()
-                                .send((index, height, program, record, traces))
+                                .send((index, height, ReduceWork::Prove(program, record, traces)))
                                 .unwrap();
 
                             // Advance the turn.
@@ -800,8 +1058,24 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
                     let _span = span.enter();
                     loop {
                         let received = { record_and_trace_rx.lock().unwrap().recv() };
-                        if let Ok((index, height, program, record, traces)) = received {
+                        if let Ok((index, height, work)) = received {
                             tracing::debug_span!("batch").in_scope(|| {
+                                let (program, record, traces) = match work {
+                                    ReduceWork::Prove(program, record, traces) => {
+                                        (program, record, traces)
+                                    }
+                                    ReduceWork::Saved(vk, proof) => {
+                                        prover_sync.wait_for_turn(index);
+                                        proofs_tx
+                                            .lock()
+                                            .unwrap()
+                                            .send((index, height, vk, proof))
+                                            .unwrap();
+                                        prover_sync.advance_turn();
+                                        return;
+                                    }
+                                };
+
                                 // Get the keys.
                                 let (pk, vk) = tracing::debug_span!("Setup compress program")
                                     .in_scope(|| self.compress_prover.setup(&program));
@@ -841,6 +1115,14 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
                                     )
                                     .unwrap();
 
+                                // Save the proof for later attempts.
+                                if let Some(work_dir) = work_dir {
+                                    let name = WorkDir::reduce_proof_name(index);
+                                    if let Err(e) = work_dir.store(&name, &(&vk, &proof)) {
+                                        tracing::warn!("failed to save reduce proof: {}", e);
+                                    }
+                                }
+
                                 // Wait for our turn to update the state.
                                 prover_sync.wait_for_turn(index);
 
@@ -953,6 +1235,81 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
         Ok(ZKMReduceProof { vk, proof })
     }
 
+    /// Prove a single node of the recursion tree, i.e. the recursion program of the witness.
+    ///
+    /// This is the unit of work of [`compress`](Self::compress), which distributed provers use to
+    /// spread the tree over several hosts.
+    #[instrument(name = "prove circuit witness", level = "debug", skip_all)]
+    pub fn prove_circuit_witness(
+        &self,
+        input: ZKMCircuitWitness,
+        opts: ZKMProverOpts,
+    ) -> Result<(StarkVerifyingKey<InnerSC>, ShardProof<InnerSC>), ZKMRecursionProverError> {
+        // Get the program and witness stream.
+        let mut witness_stream = Vec::new();
+        let program = match input {
+            ZKMCircuitWitness::Core(input) => {
+                Witnessable::<InnerConfig>::write(&input, &mut witness_stream);
+                self.recursion_program(&input)
+            }
+            ZKMCircuitWitness::Deferred(input) => {
+                Witnessable::<InnerConfig>::write(&input, &mut witness_stream);
+                self.deferred_program(&input)
+            }
+            ZKMCircuitWitness::Compress(input) => {
+                let input_with_merkle = self.make_merkle_proofs(input);
+                Witnessable::<InnerConfig>::write(&input_with_merkle, &mut witness_stream);
+                self.compress_program(&input_with_merkle)
+            }
+        };
+
+        // Execute the runtime.
+        let mut runtime = RecursionRuntime::<Val<InnerSC>, Challenge<InnerSC>, _>::new(
+            program.clone(),
+            self.compress_prover.config().perm.clone(),
+        );
+        runtime.witness_stream = witness_stream.into();
+        runtime.run().map_err(|e| ZKMRecursionProverError::RuntimeError(e.to_string()))?;
+
+        // Generate the dependencies and the traces.
+        let mut records = vec![runtime.record];
+        self.compress_prover
+            .machine()
+            .generate_dependencies(&mut records, &opts.recursion_opts, None)
+            .map_err(|_| ZKMRecursionProverError::DependenciesGenerationError)?;
+        let record = records.into_iter().next().unwrap();
+        let traces = self
+            .compress_prover
+            .generate_traces(&record)
+            .map_err(|_| ZKMRecursionProverError::TracesGenerationError)?;
+
+        // Commit to the record and traces, and generate the proof.
+        let (pk, vk) = self.compress_prover.setup(&program);
+        let mut challenger = self.compress_prover.config().challenger();
+        pk.observe_into(&mut challenger);
+        let data = self.compress_prover.commit(&record, traces);
+        let proof = self
+            .compress_prover
+            .open(&pk, data, &mut challenger)
+            .map_err(|e| ZKMRecursionProverError::RuntimeError(e.to_string()))?;
+        Ok((vk, proof))
+    }
+
+    /// Checks that the proofs of `config` reach the security profile required by `opts`.
+    fn check_security_profile<SC: StarkGenericConfig>(
+        config: &SC,
+        opts: &ZKMProverOpts,
+    ) -> Result<(), ZKMRecursionProverError> {
+        let profile = config.security_profile();
+        if !profile.is_at_least(&opts.security_profile) {
+            return Err(ZKMRecursionProverError::InsufficientSecurity(
+                profile,
+                opts.security_profile,
+            ));
+        }
+        Ok(())
+    }
+
     /// Wrap a reduce proof into a STARK proven over a SNARK-friendly field.
     #[instrument(name = "shrink", level = "info", skip_all)]
     pub fn shrink(
@@ -960,6 +1317,8 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
         reduced_proof: ZKMReduceProof<InnerSC>,
         opts: ZKMProverOpts,
     ) -> Result<ZKMReduceProof<InnerSC>, ZKMRecursionProverError> {
+        Self::check_security_profile(self.shrink_prover.config(), &opts)?;
+
         // Make the compress proof.
         let ZKMReduceProof { vk: compressed_vk, proof: compressed_proof } = reduced_proof;
         let input = ZKMCompressWitnessValues {
@@ -1008,6 +1367,78 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
         compressed_proof: ZKMReduceProof<InnerSC>,
         opts: ZKMProverOpts,
     ) -> Result<ZKMReduceProof<OuterSC>, ZKMRecursionProverError> {
+        // The wrap proof of dev mode has a single query, and is not meant to be secure.
+        if !zkm_dev_mode() {
+            Self::check_security_profile(self.wrap_prover.config(), &opts)?;
+        }
+
+        let (program, record) = self.execute_wrap_program(compressed_proof)?;
+
+        // Setup the wrap program.
+        let (wrap_pk, wrap_vk) =
+            tracing::debug_span!("setup wrap").in_scope(|| self.wrap_prover.setup(&program));
+
+        if self.wrap_vk.set(wrap_vk.clone()).is_ok() {
+            tracing::debug!("wrap verifier key set");
+        }
+
+        // Prove the wrap program.
+        let mut wrap_challenger = self.wrap_prover.config().challenger();
+        let time = std::time::Instant::now();
+        let mut wrap_proof = self
+            .wrap_prover
+            .prove(&wrap_pk, vec![record], &mut wrap_challenger, opts.recursion_opts)
+            .unwrap();
+        let elapsed = time.elapsed();
+        tracing::debug!("wrap proving time: {:?}", elapsed);
+        let mut wrap_challenger = self.wrap_prover.config().challenger();
+        self.wrap_prover.machine().verify(&wrap_vk, &wrap_proof, &mut wrap_challenger).unwrap();
+        tracing::info!("wrapping successful");
+
+        Ok(ZKMReduceProof { vk: wrap_vk, proof: wrap_proof.shard_proofs.pop().unwrap() })
+    }
+
+    /// Wrap a reduce proof into a STARK hashed with Keccak-256, which can be verified on the EVM
+    /// without a trusted setup, see [`evm`].
+    #[instrument(name = "wrap_keccak", level = "info", skip_all)]
+    pub fn wrap_keccak(
+        &self,
+        compressed_proof: ZKMReduceProof<InnerSC>,
+        opts: ZKMProverOpts,
+    ) -> Result<ZKMReduceProof<KeccakSC>, ZKMRecursionProverError> {
+        // The wrap prover has the same FRI parameters as the one over BN254.
+        let prover = self.keccak_wrap_prover.get_or_init(|| {
+            let config = KeccakSC::with_profile(self.wrap_prover.config().security_profile());
+            CpuProver::new(WrapAir::wrap_machine(config))
+        });
+        if !zkm_dev_mode() {
+            Self::check_security_profile(prover.config(), &opts)?;
+        }
+
+        let (program, record) = self.execute_wrap_program(compressed_proof)?;
+
+        let (wrap_pk, wrap_vk) =
+            tracing::debug_span!("setup wrap").in_scope(|| prover.setup(&program));
+
+        let mut wrap_challenger = prover.config().challenger();
+        let mut wrap_proof = prover
+            .prove(&wrap_pk, vec![record], &mut wrap_challenger, opts.recursion_opts)
+            .unwrap();
+        let mut wrap_challenger = prover.config().challenger();
+        prover.machine().verify(&wrap_vk, &wrap_proof, &mut wrap_challenger).unwrap();
+        tracing::info!("wrapping with keccak successful");
+
+        Ok(ZKMReduceProof { vk: wrap_vk, proof: wrap_proof.shard_proofs.pop().unwrap() })
+    }
+
+    /// Runs the wrap program on a compressed proof, and returns it with its execution record.
+    fn execute_wrap_program(
+        &self,
+        compressed_proof: ZKMReduceProof<InnerSC>,
+    ) -> Result<
+        (Arc<RecursionProgram<KoalaBear>>, ExecutionRecord<KoalaBear>),
+        ZKMRecursionProverError,
+    > {
         let ZKMReduceProof { vk: compressed_vk, proof: compressed_proof } = compressed_proof;
         let input = ZKMCompressWitnessValues {
             vks_and_proofs: vec![(compressed_vk, compressed_proof)],
@@ -1033,28 +1464,7 @@ impl<C: ZKMProverComponents> ZKMProver<C> {
         runtime.print_stats();
         tracing::debug!("wrap program executed successfully");
 
-        // Setup the wrap program.
-        let (wrap_pk, wrap_vk) =
-            tracing::debug_span!("setup wrap").in_scope(|| self.wrap_prover.setup(&program));
-
-        if self.wrap_vk.set(wrap_vk.clone()).is_ok() {
-            tracing::debug!("wrap verifier key set");
-        }
-
-        // Prove the wrap program.
-        let mut wrap_challenger = self.wrap_prover.config().challenger();
-        let time = std::time::Instant::now();
-        let mut wrap_proof = self
-            .wrap_prover
-            .prove(&wrap_pk, vec![runtime.record], &mut wrap_challenger, opts.recursion_opts)
-            .unwrap();
-        let elapsed = time.elapsed();
-        tracing::debug!("wrap proving time: {:?}", elapsed);
-        let mut wrap_challenger = self.wrap_prover.config().challenger();
-        self.wrap_prover.machine().verify(&wrap_vk, &wrap_proof, &mut wrap_challenger).unwrap();
-        tracing::info!("wrapping successful");
-
-        Ok(ZKMReduceProof { vk: wrap_vk, proof: wrap_proof.shard_proofs.pop().unwrap() })
+        Ok((program, runtime.record))
     }
 
     /// Wrap the STARK proven over a SNARK-friendly field into a PLONK proof.
@@ -1528,9 +1938,11 @@ pub mod tests {
     /// Tests an end-to-end workflow of proving a program across the entire proof generation
     /// pipeline.
     ///
-    /// Add `FRI_QUERIES`=1 to your environment for faster execution. Should only take a few minutes
-    /// on a Mac M2. Note: This test always re-builds the plonk bn254 artifacts, so setting ZKM_DEV
-    /// is not needed.
+    /// For faster execution, use a prover with a single query, e.g.
+    /// `ZKMProver::with_security_profile(SecurityProfile::new(1, 1, 16))` with `VERIFY_VK=false`,
+    /// and require the same profile in the options. Should only take a few minutes on a Mac M2.
+    /// Note: This test always re-builds the plonk bn254 artifacts, so setting ZKM_DEV is not
+    /// needed.
     #[test]
     #[serial]
     #[ignore]
@@ -1554,9 +1966,11 @@ pub mod tests {
     /// Tests an end-to-end workflow of proving a program across the entire proof generation
     /// pipeline.
     ///
-    /// Add `FRI_QUERIES`=1 to your environment for faster execution. Should only take a few minutes
-    /// on a Mac M2. Note: This test always re-builds the plonk bn254 artifacts, so setting ZKM_DEV
-    /// is not needed.
+    /// For faster execution, use a prover with a single query, e.g.
+    /// `ZKMProver::with_security_profile(SecurityProfile::new(1, 1, 16))` with `VERIFY_VK=false`,
+    /// and require the same profile in the options. Should only take a few minutes on a Mac M2.
+    /// Note: This test always re-builds the plonk bn254 artifacts, so setting ZKM_DEV is not
+    /// needed.
     #[test]
     #[serial]
     #[ignore]
@@ -1578,6 +1992,28 @@ pub mod tests {
         )
     }
 
+    /// Tests proving, compressing and verifying a program with zero-knowledge core and compressed
+    /// proofs. Run it with `VERIFY_VK=false`, as the allowed vk map is for non-hiding proofs.
+    #[test]
+    #[serial]
+    #[ignore]
+    fn test_e2e_hiding() -> Result<()> {
+        setup_logger();
+        let prover = ZKMProver::<DefaultProverComponents>::with_profile_and_hiding(
+            SecurityProfile::default(),
+            true,
+        );
+        assert!(prover.core_prover.machine().config().zk().is_some());
+        assert!(prover.compress_prover.machine().config().zk().is_some());
+        test_e2e_prover::<DefaultProverComponents>(
+            &prover,
+            test_artifacts::FIBONACCI_ELF,
+            ZKMStdin::default(),
+            ZKMProverOpts::default(),
+            Test::Compress,
+        )
+    }
+
     /// Tests an end-to-end workflow of proving a program across the entire proof generation
     /// pipeline in addition to verifying deferred proofs.
     #[test]
@@ -17,7 +17,10 @@ use zkm_recursion_circuit::machine::{
 use zkm_recursion_gnark_ffi::proof::{Groth16Bn254Proof, PlonkBn254Proof};
 
 use thiserror::Error;
-use zkm_stark::{ShardProof, StarkGenericConfig, StarkProvingKey, StarkVerifyingKey, DIGEST_SIZE};
+use zkm_stark::{
+    SecurityProfile, ShardProof, StarkGenericConfig, StarkProvingKey, StarkVerifyingKey,
+    DIGEST_SIZE,
+};
 
 use crate::{
     utils::{koalabears_to_bn254, words_to_bytes_be},
@@ -226,8 +229,11 @@ pub enum ZKMRecursionProverError {
     TracesGenerationError,
     #[error("Generate dependencies error")]
     DependenciesGenerationError,
+    #[error("Insufficient security: the prover has {0}, but {1} is required")]
+    InsufficientSecurity(SecurityProfile, SecurityProfile),
 }
 
+#[derive(Serialize, Deserialize)]
 #[allow(clippy::large_enum_variant)]
 pub enum ZKMCircuitWitness {
     Core(ZKMRecursionWitnessValues<CoreSC>),
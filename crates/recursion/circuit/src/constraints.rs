@@ -2,14 +2,17 @@ use p3_air::{Air, BaseAir};
 use p3_commit::{LagrangeSelectors, Mmcs, PolynomialSpace, TwoAdicMultiplicativeCoset};
 use p3_field::{Field, FieldAlgebra, FieldExtensionAlgebra, TwoAdicField};
 use p3_koala_bear::KoalaBear;
-use p3_matrix::dense::RowMajorMatrix;
+use p3_matrix::{
+    dense::{RowMajorMatrix, RowMajorMatrixView},
+    stack::VerticalPair,
+};
 
 use zkm_recursion_compiler::ir::{
     Builder, Config, Ext, ExtConst, ExtensionOperand, Felt, SymbolicExt, SymbolicFelt,
 };
 use zkm_stark::{
     air::MachineAir, AirOpenedValues, ChipOpenedValues, GenericVerifierConstraintFolder,
-    MachineChip, OpeningShapeError,
+    MachineChip, OpeningShapeError, ZkConfig,
 };
 
 use crate::{
@@ -42,6 +45,7 @@ where
         opening: &ChipOpenedValues<Felt<C::F>, Ext<C::F, C::EF>>,
         trace_domain: TwoAdicMultiplicativeCoset<C::F>,
         qc_domains: Vec<TwoAdicMultiplicativeCoset<C::F>>,
+        zk: Option<ZkConfig>,
         zeta: Ext<C::F, C::EF>,
         alpha: Ext<C::F, C::EF>,
         permutation_challenges: &[Ext<C::F, C::EF>],
@@ -50,7 +54,10 @@ where
         let sels = trace_domain.selectors_at_point_variable(builder, zeta);
 
         // Recompute the quotient at zeta from the chunks.
-        let quotient = Self::recompute_quotient(builder, opening, &qc_domains, zeta);
+        let quotient = match zk {
+            Some(_) => Self::recompute_zk_quotient(builder, opening, qc_domains[0].log_n - 1, zeta),
+            None => Self::recompute_quotient(builder, opening, &qc_domains, zeta),
+        };
 
         // Calculate the evaluations of the constraints at zeta.
         let folded_constraints = Self::eval_constraints(
@@ -99,9 +106,15 @@ where
             next: unflatten(&opening.permutation.next),
         };
 
+        // Ignore the random columns of the main trace in the zero-knowledge mode.
+        let main = VerticalPair::new(
+            RowMajorMatrixView::new_row(&opening.main.local[..chip.width()]),
+            RowMajorMatrixView::new_row(&opening.main.next[..chip.width()]),
+        );
+
         let mut folder = RecursiveVerifierConstraintFolder::<C> {
             preprocessed: opening.preprocessed.view(),
-            main: opening.main.view(),
+            main,
             perm: perm_opening.view(),
             perm_challenges: permutation_challenges,
             local_cumulative_sum: &opening.local_cumulative_sum,
@@ -194,11 +207,54 @@ where
         )
     }
 
+    /// Recomputes the quotient in the zero-knowledge mode, where the chunk `i` holds the
+    /// coefficients `i << log_chunk_size..(i + 1) << log_chunk_size` of the quotient, masked by
+    /// terms that cancel out in the sum.
+    #[allow(clippy::type_complexity)]
+    pub fn recompute_zk_quotient(
+        builder: &mut Builder<C>,
+        opening: &ChipOpenedValues<Felt<C::F>, Ext<C::F, C::EF>>,
+        log_chunk_size: usize,
+        zeta: Ext<C::F, C::EF>,
+    ) -> Ext<C::F, C::EF> {
+        let mut zeta_pow_chunk_size = zeta;
+        for _ in 0..log_chunk_size {
+            zeta_pow_chunk_size = builder.eval(zeta_pow_chunk_size * zeta_pow_chunk_size);
+            builder.reduce_e(zeta_pow_chunk_size);
+        }
+
+        let mut zeta_pow: Ext<_, _> = builder.constant(C::EF::ONE);
+        let mut quotient: Ext<_, _> = builder.constant(C::EF::ZERO);
+        for ch in &opening.quotient {
+            let chunk = ch[..C::EF::D]
+                .iter()
+                .enumerate()
+                .map(|(e_i, &c)| C::EF::monomial(e_i).cons() * SymbolicExt::from(c))
+                .sum::<SymbolicExt<_, _>>();
+            quotient =
+                builder.eval(SymbolicExt::from(quotient) + SymbolicExt::from(zeta_pow) * chunk);
+            zeta_pow = builder.eval(zeta_pow * zeta_pow_chunk_size);
+        }
+        quotient
+    }
+
     #[allow(clippy::type_complexity)]
     pub fn verify_opening_shape(
         chip: &MachineChip<SC, A>,
         opening: &ChipOpenedValues<Felt<C::F>, Ext<C::F, C::EF>>,
+        zk: Option<ZkConfig>,
     ) -> Result<(), OpeningShapeError> {
+        // In the zero-knowledge mode, the main traces and the quotient chunks are opened with
+        // their random columns.
+        let num_random_columns = zk.map_or(0, |zk| zk.num_random_columns);
+        let main_width = chip.width() + num_random_columns;
+        let quotient_width = match zk {
+            Some(_) => 1 << ZkConfig::log_quotient_degree(chip.log_quotient_degree()),
+            None => chip.quotient_width(),
+        };
+        let quotient_chunk_size =
+            <SC::Challenge as FieldExtensionAlgebra<C::F>>::D + num_random_columns;
+
         // Verify that the preprocessed width matches the expected value for the chip.
         if opening.preprocessed.local.len() != chip.preprocessed_width() {
             return Err(OpeningShapeError::PreprocessedWidthMismatch(
@@ -214,17 +270,11 @@ where
         }
 
         // Verify that the main width matches the expected value for the chip.
-        if opening.main.local.len() != chip.width() {
-            return Err(OpeningShapeError::MainWidthMismatch(
-                chip.width(),
-                opening.main.local.len(),
-            ));
+        if opening.main.local.len() != main_width {
+            return Err(OpeningShapeError::MainWidthMismatch(main_width, opening.main.local.len()));
         }
-        if opening.main.next.len() != chip.width() {
-            return Err(OpeningShapeError::MainWidthMismatch(
-                chip.width(),
-                opening.main.next.len(),
-            ));
+        if opening.main.next.len() != main_width {
+            return Err(OpeningShapeError::MainWidthMismatch(main_width, opening.main.next.len()));
         }
 
         // Verify that the permutation width matches the expected value for the chip.
@@ -246,18 +296,18 @@ where
         }
 
         // Verift that the number of quotient chunks matches the expected value for the chip.
-        if opening.quotient.len() != chip.quotient_width() {
+        if opening.quotient.len() != quotient_width {
             return Err(OpeningShapeError::QuotientWidthMismatch(
-                chip.quotient_width(),
+                quotient_width,
                 opening.quotient.len(),
             ));
         }
         // For each quotient chunk, verify that the number of elements is equal to the degree of the
-        // challenge extension field over the value field.
+        // challenge extension field over the value field, plus the random columns.
         for slice in &opening.quotient {
-            if slice.len() != <SC::Challenge as FieldExtensionAlgebra<C::F>>::D {
+            if slice.len() != quotient_chunk_size {
                 return Err(OpeningShapeError::QuotientChunkSizeMismatch(
-                    <SC::Challenge as FieldExtensionAlgebra<C::F>>::D,
+                    quotient_chunk_size,
                     slice.len(),
                 ));
             }
@@ -6,16 +6,14 @@ use std::{
 };
 
 use itertools::Itertools;
+use p3_air::Air;
 use p3_commit::Mmcs;
 use p3_field::FieldAlgebra;
 use p3_koala_bear::KoalaBear;
 use p3_matrix::dense::RowMajorMatrix;
 
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use zkm_core_machine::{
-    cpu::MAX_CPU_LOG_DEGREE,
-    mips::{MipsAir, MAX_LOG_NUMBER_OF_SHARDS},
-};
+use zkm_core_machine::{cpu::MAX_CPU_LOG_DEGREE, mips::MAX_LOG_NUMBER_OF_SHARDS};
 
 use zkm_recursion_core::air::PV_DIGEST_NUM_WORDS;
 use zkm_stark::air::LookupScope;
@@ -41,6 +39,7 @@ use zkm_recursion_core::{
 
 use crate::{
     challenger::{CanObserveVariable, DuplexChallengerVariable},
+    constraints::RecursiveVerifierConstraintFolder,
     machine::{assert_complete, recursion_public_values_digest},
     stark::{dummy_vk_and_shard_proof, ShardProofVariable, StarkVerifier},
     CircuitConfig, KoalaBearFriConfig, KoalaBearFriConfigVariable, VerifyingKeyVariable,
@@ -116,11 +115,16 @@ where
     /// In the course of the recursive verification, the challenger is reconstructed by observing
     /// the commitments one by one, and in the final step, the challenger is asserted to be the same
     /// as the one witnessed here.
-    pub fn verify(
+    ///
+    /// The machine is usually the MIPS machine, but it can be any machine extending it with more
+    /// chips, such as one built from a [`zkm_core_machine::mips::PrecompileRegistry`].
+    pub fn verify<A>(
         builder: &mut Builder<C>,
-        machine: &StarkMachine<SC, MipsAir<SC::Val>>,
+        machine: &StarkMachine<SC, A>,
         input: ZKMRecursionWitnessVariable<C, SC>,
-    ) {
+    ) where
+        A: MachineAir<SC::Val> + for<'a> Air<RecursiveVerifierConstraintFolder<'a, C>>,
+    {
         // Read input.
         let ZKMRecursionWitnessVariable { vk, shard_proofs, is_complete, is_first_shard, vk_root } =
             input;
@@ -578,8 +582,8 @@ impl<SC: KoalaBearFriConfig> ZKMRecursionWitnessValues<SC> {
 }
 
 impl ZKMRecursionWitnessValues<KoalaBearPoseidon2> {
-    pub fn dummy(
-        machine: &StarkMachine<KoalaBearPoseidon2, MipsAir<KoalaBear>>,
+    pub fn dummy<A: MachineAir<KoalaBear>>(
+        machine: &StarkMachine<KoalaBearPoseidon2, A>,
         shape: &ZKMRecursionShape,
     ) -> Self {
         let (mut vks, shard_proofs): (Vec<_>, Vec<_>) =
@@ -17,8 +17,8 @@ use zkm_recursion_compiler::{
 use zkm_stark::septic_digest::SepticDigest;
 use zkm_stark::{
     air::LookupScope, koala_bear_poseidon2::KoalaBearPoseidon2, shape::OrderedShape,
-    AirOpenedValues, Challenger, Chip, ChipOpenedValues, InnerChallenge, ShardCommitment,
-    ShardOpenedValues, ShardProof, Val, PROOF_MAX_NUM_PVS,
+    AirOpenedValues, Challenger, Chip, ChipOpenedValues, InnerChallenge, SecurityProfile,
+    ShardCommitment, ShardOpenedValues, ShardProof, Val, ZkConfig, PROOF_MAX_NUM_PVS,
 };
 use zkm_stark::{air::MachineAir, StarkGenericConfig, StarkMachine, StarkVerifyingKey};
 
@@ -44,6 +44,7 @@ pub struct ShardProofVariable<C: CircuitConfig<F = SC::Val>, SC: KoalaBearFriCon
     pub opening_proof: FriProofVariable<C, SC>,
     pub chip_ordering: HashMap<String, usize>,
     pub public_values: Vec<Felt<C::F>>,
+    pub security_profile: SecurityProfile,
 }
 
 /// Get a dummy duplex challenger for use in dummy proofs.
@@ -74,12 +75,13 @@ pub fn dummy_vk_and_shard_proof<A: MachineAir<KoalaBear>>(
         .map(|(i, (name, _))| (name.clone(), i))
         .collect::<HashMap<_, _>>();
     let shard_chips = machine.shard_chips_ordered(&chip_ordering).collect::<Vec<_>>();
+    let zk = machine.config().zk();
     let opened_values = ShardOpenedValues {
         chips: shard_chips
             .iter()
             .zip_eq(shape.inner.iter())
             .map(|(chip, (_, log_degree))| {
-                dummy_opened_values::<_, InnerChallenge, _>(chip, *log_degree)
+                dummy_opened_values::<_, InnerChallenge, _>(chip, *log_degree, zk)
             })
             .collect(),
     };
@@ -103,21 +105,26 @@ pub fn dummy_vk_and_shard_proof<A: MachineAir<KoalaBear>>(
             ));
             preprocessed_batch_shape.push(prep_shape);
         }
-        let main_shape = PolynomialShape {
-            width: chip_opening.main.local.len(),
-            log_degree: chip_opening.log_degree,
+        // In the zero-knowledge mode, the main and permutation traces are committed over an
+        // extended domain, and the quotient chunks over a domain twice as large.
+        let (log_degree, log_quotient_chunk_degree) = match zk {
+            Some(zk) => {
+                let log_degree =
+                    chip_opening.log_degree + zk.log_extension(chip_opening.log_degree);
+                (log_degree, log_degree + 1)
+            }
+            None => (chip_opening.log_degree, chip_opening.log_degree),
         };
+        let main_shape = PolynomialShape { width: chip_opening.main.local.len(), log_degree };
         main_batch_shape.push(main_shape);
-        let permutation_shape = PolynomialShape {
-            width: chip_opening.permutation.local.len(),
-            log_degree: chip_opening.log_degree,
-        };
+        let permutation_shape =
+            PolynomialShape { width: chip_opening.permutation.local.len(), log_degree };
         permutation_batch_shape.push(permutation_shape);
         for quot_chunk in chip_opening.quotient.iter() {
-            assert_eq!(quot_chunk.len(), 4);
+            assert_eq!(quot_chunk.len(), 4 + zk.map_or(0, |zk| zk.num_random_columns));
             quotient_batch_shape.push(PolynomialShape {
                 width: quot_chunk.len(),
-                log_degree: chip_opening.log_degree,
+                log_degree: log_quotient_chunk_degree,
             });
         }
     }
@@ -161,10 +168,17 @@ pub fn dummy_vk_and_shard_proof<A: MachineAir<KoalaBear>>(
         initial_global_cumulative_sum: SepticDigest::<KoalaBear>::zero(),
         chip_information: preprocessed_chip_information,
         chip_ordering: preprocessed_chip_ordering,
+        security_profile: machine.config().security_profile(),
     };
 
-    let shard_proof =
-        ShardProof { commitment, opened_values, opening_proof, chip_ordering, public_values };
+    let shard_proof = ShardProof {
+        commitment,
+        opened_values,
+        opening_proof,
+        chip_ordering,
+        public_values,
+        security_profile: machine.config().security_profile(),
+    };
 
     (vk, shard_proof)
 }
@@ -172,13 +186,15 @@ pub fn dummy_vk_and_shard_proof<A: MachineAir<KoalaBear>>(
 fn dummy_opened_values<F: Field, EF: ExtensionField<F>, A: MachineAir<F>>(
     chip: &Chip<F, A>,
     log_degree: usize,
+    zk: Option<ZkConfig>,
 ) -> ChipOpenedValues<F, EF> {
+    let num_random_columns = zk.map_or(0, |zk| zk.num_random_columns);
     let preprocessed_width = chip.preprocessed_width();
     let preprocessed = AirOpenedValues {
         local: vec![EF::ZERO; preprocessed_width],
         next: vec![EF::ZERO; preprocessed_width],
     };
-    let main_width = chip.width();
+    let main_width = chip.width() + num_random_columns;
     let main =
         AirOpenedValues { local: vec![EF::ZERO; main_width], next: vec![EF::ZERO; main_width] };
 
@@ -187,8 +203,12 @@ fn dummy_opened_values<F: Field, EF: ExtensionField<F>, A: MachineAir<F>>(
         local: vec![EF::ZERO; permutation_width * EF::D],
         next: vec![EF::ZERO; permutation_width * EF::D],
     };
-    let quotient_width = chip.quotient_width();
-    let quotient = (0..quotient_width).map(|_| vec![EF::ZERO; EF::D]).collect::<Vec<_>>();
+    let quotient_width = match zk {
+        Some(_) => 1 << ZkConfig::log_quotient_degree(chip.log_quotient_degree()),
+        None => chip.quotient_width(),
+    };
+    let quotient =
+        (0..quotient_width).map(|_| vec![EF::ZERO; EF::D + num_random_columns]).collect::<Vec<_>>();
 
     ChipOpenedValues {
         preprocessed,
@@ -260,8 +280,16 @@ where
             opening_proof,
             chip_ordering,
             public_values,
+            security_profile,
         } = proof;
 
+        // The proof must be made with the FRI parameters the circuit verifies it with.
+        assert_eq!(
+            *security_profile,
+            machine.config().security_profile(),
+            "the security profile of the proof differs from the one of the machine"
+        );
+
         // Assert that the byte multiplicities don't overflow.
         let mut max_byte_lookup_mult = 0u64;
         chips.iter().zip(opened_values.chips.iter()).for_each(|(chip, val)| {
@@ -281,14 +309,35 @@ where
 
         let log_degrees = opened_values.chips.iter().map(|val| val.log_degree).collect::<Vec<_>>();
 
-        let log_quotient_degrees =
-            chips.iter().map(|chip| chip.log_quotient_degree()).collect::<Vec<_>>();
+        let zk = machine.config().zk();
+        let log_extensions = log_degrees
+            .iter()
+            .map(|log_degree| zk.map_or(0, |zk| zk.log_extension(*log_degree)))
+            .collect::<Vec<_>>();
+
+        let log_quotient_degrees = chips
+            .iter()
+            .map(|chip| match zk {
+                Some(_) => ZkConfig::log_quotient_degree(chip.log_quotient_degree()),
+                None => chip.log_quotient_degree(),
+            })
+            .collect::<Vec<_>>();
 
         let trace_domains = log_degrees
             .iter()
             .map(|log_degree| Self::natural_domain_for_degree(machine.config(), 1 << log_degree))
             .collect::<Vec<_>>();
 
+        // The domains the main and permutation traces are committed over, which are extended in
+        // the zero-knowledge mode.
+        let commit_domains = log_degrees
+            .iter()
+            .zip_eq(log_extensions.iter())
+            .map(|(log_degree, log_extension)| {
+                Self::natural_domain_for_degree(machine.config(), 1 << (log_degree + log_extension))
+            })
+            .collect::<Vec<_>>();
+
         let ShardCommitment { main_commit, permutation_commit, quotient_commit } = *commitment;
 
         challenger.observe(builder, main_commit);
@@ -348,18 +397,19 @@ where
 
         let main_domains_points_and_opens = trace_domains
             .iter()
+            .zip_eq(commit_domains.iter())
             .zip_eq(opened_values.chips.iter())
             .zip_eq(chips.iter())
-            .map(|((domain, values), chip)| {
+            .map(|(((domain, commit_domain), values), chip)| {
                 if !chip.local_only() {
                     TwoAdicPcsMatsVariable::<C> {
-                        domain: *domain,
+                        domain: *commit_domain,
                         points: vec![zeta, domain.next_point_variable(builder, zeta)],
                         values: vec![values.main.local.clone(), values.main.next.clone()],
                     }
                 } else {
                     TwoAdicPcsMatsVariable::<C> {
-                        domain: *domain,
+                        domain: *commit_domain,
                         points: vec![zeta],
                         values: vec![values.main.local.clone()],
                     }
@@ -369,9 +419,10 @@ where
 
         let perm_domains_points_and_opens = trace_domains
             .iter()
+            .zip_eq(commit_domains.iter())
             .zip_eq(opened_values.chips.iter())
-            .map(|(domain, values)| TwoAdicPcsMatsVariable::<C> {
-                domain: *domain,
+            .map(|((domain, commit_domain), values)| TwoAdicPcsMatsVariable::<C> {
+                domain: *commit_domain,
                 points: vec![zeta, domain.next_point_variable(builder, zeta)],
                 values: vec![values.permutation.local.clone(), values.permutation.next.clone()],
             })
@@ -380,12 +431,26 @@ where
         let quotient_chunk_domains = trace_domains
             .iter()
             .zip_eq(log_degrees)
+            .zip_eq(log_extensions)
             .zip_eq(log_quotient_degrees)
-            .map(|((domain, log_degree), log_quotient_degree)| {
+            .map(|(((domain, log_degree), log_extension), log_quotient_degree)| {
                 let quotient_degree = 1 << log_quotient_degree;
-                let quotient_domain =
-                    domain.create_disjoint_domain(1 << (log_degree + log_quotient_degree));
-                quotient_domain.split_domains(quotient_degree)
+                match zk {
+                    // The masked chunks are committed over a subgroup twice their size.
+                    Some(_) => (0..quotient_degree)
+                        .map(|_| {
+                            Self::natural_domain_for_degree(
+                                machine.config(),
+                                2 << (log_degree + log_extension),
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                    None => {
+                        let quotient_domain =
+                            domain.create_disjoint_domain(1 << (log_degree + log_quotient_degree));
+                        quotient_domain.split_domains(quotient_degree)
+                    }
+                }
             })
             .collect::<Vec<_>>();
 
@@ -440,7 +505,7 @@ where
             izip!(chips.iter(), trace_domains, quotient_chunk_domains, opened_values.chips.iter(),)
         {
             // Verify the shape of the opening arguments matches the expected values.
-            Self::verify_opening_shape(chip, values).unwrap();
+            Self::verify_opening_shape(chip, values, zk).unwrap();
             // Verify the constraint evaluation.
             Self::verify_constraints(
                 builder,
@@ -448,6 +513,7 @@ where
                 values,
                 trace_domain,
                 qc_domains,
+                zk,
                 zeta,
                 alpha,
                 &permutation_challenges,
@@ -535,12 +601,12 @@ pub mod tests {
         num_shards_in_batch: Option<usize>,
     ) -> (TracedVec<DslIr<C>>, Vec<Block<KoalaBear>>) {
         setup_logger();
-        let machine = MipsAir::<C::F>::machine(SC::default());
+        let machine = MipsAir::<C::F>::machine(config.clone());
         let (_, vk) = machine.setup(&Program::from(elf).unwrap());
         let (proof, _, _) = prove::<_, CoreP>(
             Program::from(elf).unwrap(),
             &ZKMStdin::new(),
-            SC::default(),
+            config.clone(),
             opts,
             None,
         )
@@ -592,4 +658,16 @@ pub mod tests {
             );
         run_test_recursion_with_prover::<CpuProver<_, _>>(operations, stream);
     }
+
+    #[test]
+    fn test_verify_hiding_shard_inner() {
+        let (operations, stream) =
+            build_verify_shard_with_provers::<InnerConfig, CpuProver<_, _>, CpuProver<_, _>>(
+                KoalaBearPoseidon2::hiding(),
+                FIBONACCI_ELF,
+                ZKMCoreOpts::default(),
+                Some(2),
+            );
+        run_test_recursion_with_prover::<CpuProver<_, _>>(operations, stream);
+    }
 }
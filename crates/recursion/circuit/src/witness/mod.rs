@@ -144,6 +144,7 @@ where
         let opening_proof = self.opening_proof.read(builder);
         let public_values = self.public_values.read(builder);
         let chip_ordering = self.chip_ordering.clone();
+        let security_profile = self.security_profile;
 
         ShardProofVariable {
             commitment,
@@ -151,6 +152,7 @@ where
             opening_proof,
             public_values,
             chip_ordering,
+            security_profile,
         }
     }
 
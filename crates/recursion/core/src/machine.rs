@@ -275,7 +275,7 @@ pub mod tests {
     use p3_koala_bear::Poseidon2InternalLayerKoalaBear;
     use rand::prelude::*;
     use zkm_core_machine::utils::run_test_machine;
-    use zkm_stark::{koala_bear_poseidon2::KoalaBearPoseidon2, StarkGenericConfig};
+    use zkm_stark::{koala_bear_poseidon2::KoalaBearPoseidon2, AirReport, StarkGenericConfig};
 
     use crate::{
         runtime::{
@@ -394,4 +394,15 @@ pub mod tests {
 
         test_instructions(instructions);
     }
+
+    #[test]
+    fn test_air_report() {
+        let wide = AirReport::new(A::machine_wide_with_all_chips(SC::default()).chips());
+        let skinny = AirReport::new(B::machine_skinny_with_all_chips(SC::default()).chips());
+        for (report, degree) in [(wide, 3), (skinny, 9)] {
+            assert!(report.is_balanced(), "unbalanced lookups:\n{report}");
+            let violations = report.degree_violations(degree);
+            assert!(violations.is_empty(), "degree above {degree}:\n{report}");
+        }
+    }
 }
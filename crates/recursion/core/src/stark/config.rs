@@ -9,7 +9,7 @@ use p3_merkle_tree::MerkleTreeMmcs;
 use p3_poseidon2::ExternalLayerConstants;
 use p3_symmetric::{Hash, MultiField32PaddingFreeSponge, TruncatedPermutation};
 use serde::{Deserialize, Serialize};
-use zkm_stark::{Com, StarkGenericConfig, ZeroCommitment};
+use zkm_stark::{Com, SecurityProfile, StarkGenericConfig, ZeroCommitment};
 
 use super::{poseidon2::bn254_poseidon2_rc3, zkm_dev_mode};
 
@@ -65,40 +65,35 @@ pub fn outer_perm() -> OuterPerm {
     OuterPerm::new(external_round_constants, internal_round_constants)
 }
 
+/// The security profile of the outer recursion with the given blowup, which targets 100 bits of
+/// security, or a single query in dev mode.
+pub fn outer_security_profile(log_blowup: usize) -> SecurityProfile {
+    if zkm_dev_mode() {
+        SecurityProfile::new(log_blowup, 1, 16)
+    } else {
+        SecurityProfile::DEFAULT.with_log_blowup(log_blowup)
+    }
+}
+
 /// The FRI config for outer recursion.
 /// This targets by default 100 bits of security.
 pub fn outer_fri_config() -> FriConfig<OuterChallengeMmcs> {
-    let perm = outer_perm();
-    let hash = OuterHash::new(perm.clone()).unwrap();
-    let compress = OuterCompress::new(perm.clone());
-    let challenge_mmcs = OuterChallengeMmcs::new(OuterValMmcs::new(hash, compress));
-    let num_queries = if zkm_dev_mode() {
-        1
-    } else {
-        match std::env::var("FRI_QUERIES") {
-            Ok(value) => value.parse().unwrap(),
-            Err(_) => 21,
-        }
-    };
-    FriConfig { log_blowup: 4, num_queries, proof_of_work_bits: 16, mmcs: challenge_mmcs }
+    outer_fri_config_with_profile(outer_security_profile(SecurityProfile::OUTER.log_blowup))
 }
 
 /// The FRI config for outer recursion.
 /// This targets by default 100 bits of security.
 pub fn outer_fri_config_with_blowup(log_blowup: usize) -> FriConfig<OuterChallengeMmcs> {
+    outer_fri_config_with_profile(outer_security_profile(log_blowup))
+}
+
+/// The FRI config for outer recursion with the given security profile.
+pub fn outer_fri_config_with_profile(profile: SecurityProfile) -> FriConfig<OuterChallengeMmcs> {
     let perm = outer_perm();
     let hash = OuterHash::new(perm.clone()).unwrap();
     let compress = OuterCompress::new(perm.clone());
     let challenge_mmcs = OuterChallengeMmcs::new(OuterValMmcs::new(hash, compress));
-    let num_queries = if zkm_dev_mode() {
-        1
-    } else {
-        match std::env::var("FRI_QUERIES") {
-            Ok(value) => value.parse().unwrap(),
-            Err(_) => 84 / log_blowup,
-        }
-    };
-    FriConfig { log_blowup, num_queries, proof_of_work_bits: 16, mmcs: challenge_mmcs }
+    profile.fri_config(challenge_mmcs)
 }
 
 #[derive(Deserialize)]
@@ -110,7 +105,7 @@ pub struct KoalaBearPoseidon2Outer {
 
 impl Clone for KoalaBearPoseidon2Outer {
     fn clone(&self) -> Self {
-        Self::new()
+        Self::with_profile(self.security_profile())
     }
 }
 
@@ -131,22 +126,18 @@ impl From<std::marker::PhantomData<KoalaBearPoseidon2Outer>> for KoalaBearPoseid
 
 impl KoalaBearPoseidon2Outer {
     pub fn new() -> Self {
-        let perm = outer_perm();
-        let hash = OuterHash::new(perm.clone()).unwrap();
-        let compress = OuterCompress::new(perm.clone());
-        let val_mmcs = OuterValMmcs::new(hash, compress);
-        let dft = OuterDft::default();
-        let fri_config = outer_fri_config();
-        let pcs = OuterPcs::new(dft, val_mmcs, fri_config);
-        Self { pcs, perm }
+        Self::with_profile(outer_security_profile(SecurityProfile::OUTER.log_blowup))
     }
     pub fn new_with_log_blowup(log_blowup: usize) -> Self {
+        Self::with_profile(outer_security_profile(log_blowup))
+    }
+    pub fn with_profile(profile: SecurityProfile) -> Self {
         let perm = outer_perm();
         let hash = OuterHash::new(perm.clone()).unwrap();
         let compress = OuterCompress::new(perm.clone());
         let val_mmcs = OuterValMmcs::new(hash, compress);
         let dft = OuterDft::default();
-        let fri_config = outer_fri_config_with_blowup(log_blowup);
+        let fri_config = outer_fri_config_with_profile(profile);
         let pcs = OuterPcs::new(dft, val_mmcs, fri_config);
         Self { pcs, perm }
     }
@@ -172,6 +163,10 @@ impl StarkGenericConfig for KoalaBearPoseidon2Outer {
     fn challenger(&self) -> Self::Challenger {
         OuterChallenger::new(self.perm.clone()).unwrap()
     }
+
+    fn security_profile(&self) -> SecurityProfile {
+        SecurityProfile::of(self.pcs.fri_config())
+    }
 }
 
 impl ZeroCommitment<KoalaBearPoseidon2Outer> for OuterPcs {
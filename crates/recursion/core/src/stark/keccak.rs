@@ -0,0 +1,247 @@
+//! A configuration for outer recursion hashing with Keccak-256, whose proofs are cheap to verify
+//! on the EVM without a trusted setup.
+//!
+//! The commitments are Merkle trees of Keccak-256 digests of the little-endian `u32` encodings of
+//! the rows, and the Fiat-Shamir transcript is a Keccak-256 hash chain, so a Solidity verifier
+//! only needs the `keccak256` opcode and arithmetic modulo the KoalaBear prime.
+
+use p3_challenger::{CanObserve, CanSample, CanSampleBits, FieldChallenger, GrindingChallenger};
+use p3_commit::ExtensionMmcs;
+use p3_field::{ExtensionField, FieldAlgebra, PrimeField32, PrimeField64};
+use p3_fri::{FriConfig, TwoAdicFriPcs};
+use p3_keccak::Keccak256Hash;
+use p3_maybe_rayon::prelude::*;
+use p3_merkle_tree::MerkleTreeMmcs;
+use p3_symmetric::{CompressionFunctionFromHasher, CryptographicHasher, Hash, SerializingHasher32};
+use p3_util::log2_ceil_u64;
+use serde::{Deserialize, Serialize};
+use zkm_stark::{Com, SecurityProfile, StarkGenericConfig, ZeroCommitment};
+
+use super::{outer_security_profile, OuterChallenge, OuterDft, OuterVal};
+
+/// The number of bytes of a Keccak-256 digest.
+pub const KECCAK_DIGEST_SIZE: usize = 32;
+
+pub type KeccakByteHash = Keccak256Hash;
+pub type KeccakFieldHash = SerializingHasher32<KeccakByteHash>;
+pub type KeccakCompress = CompressionFunctionFromHasher<KeccakByteHash, 2, KECCAK_DIGEST_SIZE>;
+pub type KeccakDigest = Hash<OuterVal, u8, KECCAK_DIGEST_SIZE>;
+pub type KeccakValMmcs =
+    MerkleTreeMmcs<OuterVal, u8, KeccakFieldHash, KeccakCompress, KECCAK_DIGEST_SIZE>;
+pub type KeccakChallengeMmcs = ExtensionMmcs<OuterVal, OuterChallenge, KeccakValMmcs>;
+pub type KeccakPcs = TwoAdicFriPcs<OuterVal, OuterDft, KeccakValMmcs, KeccakChallengeMmcs>;
+
+/// A Fiat-Shamir challenger over a Keccak-256 hash chain.
+///
+/// It draws the same challenges as
+/// `SerializingChallenger32<KoalaBear, HashChallenger<u8, Keccak256Hash, 32>>`:
+///
+/// - A field element is observed as the little-endian bytes of its canonical `u32` value.
+/// - A sample hashes the observed bytes if any, and replaces them with the digest. The bytes are
+///   then taken from the end of the last digest.
+/// - A field element is sampled from 4 bytes read as a little-endian `u32`, masked to the bit
+///   length of the modulus, and rejected if it is not smaller than the modulus.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeccakChallenger {
+    input_buffer: Vec<u8>,
+    output_buffer: Vec<u8>,
+}
+
+impl KeccakChallenger {
+    pub const fn new() -> Self {
+        Self { input_buffer: Vec::new(), output_buffer: Vec::new() }
+    }
+
+    fn observe_byte(&mut self, byte: u8) {
+        self.output_buffer.clear();
+        self.input_buffer.push(byte);
+    }
+
+    fn flush(&mut self) {
+        let output = KeccakByteHash {}.hash_iter(self.input_buffer.drain(..));
+        self.output_buffer = output.to_vec();
+        self.input_buffer.extend(output);
+    }
+
+    fn sample_byte(&mut self) -> u8 {
+        if self.output_buffer.is_empty() {
+            self.flush();
+        }
+        self.output_buffer.pop().expect("the output buffer is not empty after a flush")
+    }
+
+    fn sample_base(&mut self) -> OuterVal {
+        let modulus = OuterVal::ORDER_U32;
+        let mask = ((1u64 << log2_ceil_u64(OuterVal::ORDER_U64)) - 1) as u32;
+        loop {
+            let bytes = core::array::from_fn(|_| self.sample_byte());
+            let value = u32::from_le_bytes(bytes) & mask;
+            if value < modulus {
+                return OuterVal::from_canonical_u32(value);
+            }
+        }
+    }
+}
+
+impl CanObserve<OuterVal> for KeccakChallenger {
+    fn observe(&mut self, value: OuterVal) {
+        value.as_canonical_u32().to_le_bytes().into_iter().for_each(|b| self.observe_byte(b));
+    }
+}
+
+impl CanObserve<KeccakDigest> for KeccakChallenger {
+    fn observe(&mut self, digest: KeccakDigest) {
+        let bytes: [u8; KECCAK_DIGEST_SIZE] = digest.into();
+        bytes.into_iter().for_each(|b| self.observe_byte(b));
+    }
+}
+
+impl<EF: ExtensionField<OuterVal>> CanSample<EF> for KeccakChallenger {
+    fn sample(&mut self) -> EF {
+        EF::from_base_fn(|_| self.sample_base())
+    }
+}
+
+impl CanSampleBits<usize> for KeccakChallenger {
+    fn sample_bits(&mut self, bits: usize) -> usize {
+        assert!((1u64 << bits) < OuterVal::ORDER_U64);
+        let value = self.sample_base().as_canonical_u64() as usize;
+        value & ((1 << bits) - 1)
+    }
+}
+
+impl FieldChallenger<OuterVal> for KeccakChallenger {}
+
+impl GrindingChallenger for KeccakChallenger {
+    type Witness = OuterVal;
+
+    fn grind(&mut self, bits: usize) -> Self::Witness {
+        assert!((1u64 << bits) < OuterVal::ORDER_U64);
+        let witness = (0..OuterVal::ORDER_U32)
+            .into_par_iter()
+            .map(OuterVal::from_canonical_u32)
+            .find_any(|witness| self.clone().check_witness(bits, *witness))
+            .expect("failed to find a proof of work witness");
+        assert!(self.check_witness(bits, witness));
+        witness
+    }
+}
+
+/// The FRI config for outer recursion with Keccak-256 and the given security profile.
+pub fn keccak_fri_config_with_profile(profile: SecurityProfile) -> FriConfig<KeccakChallengeMmcs> {
+    profile.fri_config(KeccakChallengeMmcs::new(keccak_val_mmcs()))
+}
+
+fn keccak_val_mmcs() -> KeccakValMmcs {
+    let byte_hash = KeccakByteHash {};
+    KeccakValMmcs::new(KeccakFieldHash::new(byte_hash), KeccakCompress::new(byte_hash))
+}
+
+/// A configuration for outer recursion, with the same field and FRI parameters as
+/// [`super::KoalaBearPoseidon2Outer`], but hashing with Keccak-256 instead of Poseidon2 over BN254.
+#[derive(Deserialize)]
+#[serde(from = "std::marker::PhantomData<KoalaBearKeccak>")]
+pub struct KoalaBearKeccak {
+    pub pcs: KeccakPcs,
+}
+
+impl Clone for KoalaBearKeccak {
+    fn clone(&self) -> Self {
+        Self::with_profile(self.security_profile())
+    }
+}
+
+impl Serialize for KoalaBearKeccak {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        std::marker::PhantomData::<KoalaBearKeccak>.serialize(serializer)
+    }
+}
+
+impl From<std::marker::PhantomData<KoalaBearKeccak>> for KoalaBearKeccak {
+    fn from(_: std::marker::PhantomData<KoalaBearKeccak>) -> Self {
+        Self::new()
+    }
+}
+
+impl KoalaBearKeccak {
+    pub fn new() -> Self {
+        Self::with_profile(outer_security_profile(SecurityProfile::OUTER.log_blowup))
+    }
+
+    pub fn with_profile(profile: SecurityProfile) -> Self {
+        let fri_config = keccak_fri_config_with_profile(profile);
+        let pcs = KeccakPcs::new(OuterDft::default(), keccak_val_mmcs(), fri_config);
+        Self { pcs }
+    }
+}
+
+impl Default for KoalaBearKeccak {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StarkGenericConfig for KoalaBearKeccak {
+    type Val = OuterVal;
+    type Domain = <KeccakPcs as p3_commit::Pcs<OuterChallenge, KeccakChallenger>>::Domain;
+    type Pcs = KeccakPcs;
+    type Challenge = OuterChallenge;
+    type Challenger = KeccakChallenger;
+
+    fn pcs(&self) -> &Self::Pcs {
+        &self.pcs
+    }
+
+    fn challenger(&self) -> Self::Challenger {
+        KeccakChallenger::new()
+    }
+
+    fn security_profile(&self) -> SecurityProfile {
+        SecurityProfile::of(self.pcs.fri_config())
+    }
+}
+
+impl ZeroCommitment<KoalaBearKeccak> for KeccakPcs {
+    fn zero_commitment(&self) -> Com<KoalaBearKeccak> {
+        KeccakDigest::from([0; KECCAK_DIGEST_SIZE])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_challenger::{HashChallenger, SerializingChallenger32};
+
+    use super::*;
+
+    #[test]
+    fn test_keccak_challenger_matches_serializing_challenger() {
+        let mut challenger = KeccakChallenger::new();
+        let mut expected = SerializingChallenger32::<
+            OuterVal,
+            HashChallenger<u8, KeccakByteHash, 32>,
+        >::from_hasher(vec![], KeccakByteHash {});
+
+        for i in 0..10u32 {
+            let value =
+                OuterVal::from_canonical_u32(i.wrapping_mul(0x1234_5678) % OuterVal::ORDER_U32);
+            challenger.observe(value);
+            expected.observe(value);
+            let digest = KeccakDigest::from([i as u8; KECCAK_DIGEST_SIZE]);
+            challenger.observe(digest);
+            expected.observe(digest);
+
+            let sample: OuterChallenge = challenger.sample();
+            assert_eq!(sample, CanSample::<OuterChallenge>::sample(&mut expected));
+            let sample: OuterVal = challenger.sample();
+            assert_eq!(sample, CanSample::<OuterVal>::sample(&mut expected));
+            assert_eq!(challenger.sample_bits(10), expected.sample_bits(10));
+        }
+
+        let witness = challenger.grind(4);
+        assert!(expected.check_witness(4, witness));
+        assert_eq!(challenger.sample_bits(20), expected.sample_bits(20));
+    }
+}
@@ -1,7 +1,9 @@
 mod config;
+mod keccak;
 mod poseidon2;
 mod utils;
 
 pub use config::*;
+pub use keccak::*;
 pub use poseidon2::*;
 pub use utils::*;
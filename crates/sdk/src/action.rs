@@ -1,11 +1,13 @@
-use zkm_core_executor::{ExecutionError, ExecutionReport, HookEnv, ZKMContextBuilder};
+use zkm_core_executor::{
+    ExecutionError, ExecutionReport, ExecutionState, HookEnv, SnapshotTrigger, ZKMContextBuilder,
+};
 use zkm_core_machine::io::ZKMStdin;
 use zkm_primitives::io::ZKMPublicValues;
 use zkm_prover::{components::DefaultProverComponents, ZKMProvingKey};
 
 use anyhow::{Ok, Result};
-use std::time::Duration;
-use zkm_stark::{ZKMCoreOpts, ZKMProverOpts};
+use std::{path::PathBuf, time::Duration};
+use zkm_stark::{SecurityProfile, ZKMCoreOpts, ZKMProverOpts};
 
 use crate::{provers::ProofOpts, Prover, ZKMProofKind, ZKMProofWithPublicValues};
 
@@ -38,6 +40,25 @@ impl<'a> Execute<'a> {
         Ok(prover.zkm_prover().execute(elf, &stdin, context)?)
     }
 
+    /// Execute the program on the input until `trigger` fires, consuming the built action `self`.
+    ///
+    /// Returns the snapshot of the execution at that point, or `None` if the program finished
+    /// first. The snapshot may be saved with [`ExecutionState::save`], and the execution resumed
+    /// from it with [`Self::from_snapshot`] and [`Prove::from_snapshot`].
+    pub fn snapshot(self, trigger: SnapshotTrigger) -> Result<Option<ExecutionState>> {
+        let Self { prover, elf, stdin, mut context_builder } = self;
+        let context = context_builder.build();
+        Ok(prover.zkm_prover().snapshot(elf, &stdin, context, trigger)?)
+    }
+
+    /// Resume the execution from a snapshot taken with [`Self::snapshot`].
+    ///
+    /// The inputs of `stdin` are read after the ones left unread in the snapshot.
+    pub fn from_snapshot(mut self, snapshot: &'a ExecutionState) -> Self {
+        self.context_builder.snapshot(snapshot);
+        self
+    }
+
     /// Add a runtime [Hook](super::Hook) into the context.
     ///
     /// Hooks may be invoked from within Ziren by writing to the specified file descriptor `fd`
@@ -87,7 +108,9 @@ pub struct Prove<'a> {
     stdin: ZKMStdin,
     core_opts: ZKMCoreOpts,
     recursion_opts: ZKMCoreOpts,
+    security_profile: SecurityProfile,
     timeout: Option<Duration>,
+    work_dir: Option<PathBuf>,
 }
 
 impl<'a> Prove<'a> {
@@ -108,7 +131,9 @@ impl<'a> Prove<'a> {
             context_builder: Default::default(),
             core_opts: ZKMCoreOpts::default(),
             recursion_opts: ZKMCoreOpts::recursion(),
+            security_profile: SecurityProfile::default(),
             timeout: None,
+            work_dir: None,
         }
     }
 
@@ -122,10 +147,12 @@ impl<'a> Prove<'a> {
             mut context_builder,
             core_opts,
             recursion_opts,
+            security_profile,
             timeout,
+            work_dir,
         } = self;
-        let opts = ZKMProverOpts { core_opts, recursion_opts };
-        let proof_opts = ProofOpts { zkm_prover_opts: opts, timeout };
+        let opts = ZKMProverOpts { core_opts, recursion_opts, security_profile };
+        let proof_opts = ProofOpts { zkm_prover_opts: opts, timeout, work_dir };
         let context = context_builder.build();
 
         // Dump the program and stdin to files for debugging if `ZKM_DUMP` is set.
@@ -214,6 +241,15 @@ impl<'a> Prove<'a> {
         self
     }
 
+    /// Set the minimum security the proof must reach.
+    ///
+    /// Proving fails if the prover is configured with a less secure profile, see
+    /// [`zkm_prover::ZKMProver::with_security_profile`].
+    pub fn security_profile(mut self, profile: SecurityProfile) -> Self {
+        self.security_profile = profile;
+        self
+    }
+
     /// Set the timeout for the proof's generation.
     ///
     /// This parameter is only used when the prover is run in network mode.
@@ -227,4 +263,24 @@ impl<'a> Prove<'a> {
         self.context_builder.set_skip_deferred_proof_verification(value);
         self
     }
+
+    /// Prove the execution resumed from a snapshot taken with [`Execute::snapshot`].
+    ///
+    /// The proving key must be generated from the same snapshot with
+    /// [`ProverClient::setup_from_snapshot`](super::ProverClient::setup_from_snapshot). The inputs
+    /// of `stdin` are read after the ones left unread in the snapshot.
+    pub fn from_snapshot(mut self, snapshot: &'a ExecutionState) -> Self {
+        self.context_builder.snapshot(snapshot);
+        self
+    }
+
+    /// Persist the intermediate artifacts of the proof in a work directory.
+    ///
+    /// If the proof is interrupted, running it again with the same program, input and work
+    /// directory resumes it from the last saved shard and recursion proofs. This parameter is
+    /// only used by the CPU prover.
+    pub fn work_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.work_dir = Some(dir.into());
+        self
+    }
 }
@@ -0,0 +1,16 @@
+use std::env;
+
+use zkm_sdk::{
+    network::server::{StageServer, StageServerConfig},
+    utils, CpuProver,
+};
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    utils::setup_logger();
+
+    let addr = env::var("STAGE_ADDR").unwrap_or("0.0.0.0:20002".to_string()).parse()?;
+    let config = StageServerConfig::from_env()?;
+    let server = StageServer::new(config, CpuProver::new())?;
+    server.serve(addr).await
+}
@@ -26,12 +26,14 @@ use zkm_prover::components::DefaultProverComponents;
 pub use provers::{CpuProver, MockProver, Prover};
 
 pub use zkm_build::include_elf;
-pub use zkm_core_executor::{ExecutionReport, HookEnv, ZKMContext, ZKMContextBuilder};
+pub use zkm_core_executor::{
+    ExecutionReport, ExecutionState, HookEnv, SnapshotTrigger, ZKMContext, ZKMContextBuilder,
+};
 pub use zkm_core_machine::{io::ZKMStdin, ZKM_CIRCUIT_VERSION};
-pub use zkm_primitives::io::ZKMPublicValues;
+pub use zkm_primitives::{codec::Codec, io::ZKMPublicValues};
 pub use zkm_prover::{
-    CoreSC, HashableKey, InnerSC, OuterSC, PlonkBn254Proof, ProverMode, ZKMProver, ZKMProvingKey,
-    ZKMVerifyingKey,
+    key_cache::KeyCache, CoreSC, HashableKey, InnerSC, OuterSC, PlonkBn254Proof, ProverMode,
+    ZKMProver, ZKMProvingKey, ZKMVerifyingKey,
 };
 
 // Re-export the utilities.
@@ -287,6 +289,35 @@ impl ProverClient {
     pub fn setup(&self, elf: &[u8]) -> (ZKMProvingKey, ZKMVerifyingKey) {
         self.prover.setup(elf)
     }
+
+    /// Setup a program resumed from a snapshot of its execution, taken with
+    /// [action::Execute::snapshot].
+    ///
+    /// The verifying key commits to the memory image and the program counter of the snapshot, so
+    /// it identifies the snapshot that proofs made with the proving key start from.
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// use zkm_sdk::{ProverClient, SnapshotTrigger, ZKMStdin};
+    ///
+    /// let elf = test_artifacts::FIBONACCI_ELF;
+    /// let client = ProverClient::new();
+    /// let snapshot = client
+    ///     .execute(elf, ZKMStdin::new())
+    ///     .snapshot(SnapshotTrigger::Cycle(1000))
+    ///     .unwrap()
+    ///     .expect("the program exited before the snapshot");
+    /// let (pk, vk) = client.setup_from_snapshot(elf, &snapshot).unwrap();
+    /// let proof = client.prove(&pk, ZKMStdin::new()).from_snapshot(&snapshot).run().unwrap();
+    /// client.verify(&proof, &vk).unwrap();
+    /// ```
+    pub fn setup_from_snapshot(
+        &self,
+        elf: &[u8],
+        snapshot: &ExecutionState,
+    ) -> anyhow::Result<(ZKMProvingKey, ZKMVerifyingKey)> {
+        self.prover.setup_from_snapshot(elf, snapshot)
+    }
 }
 
 impl Default for ProverClient {
@@ -302,6 +333,7 @@ pub struct ProverClientBuilder {
     private_key: Option<String>,
     rpc_url: Option<String>,
     skip_simulation: bool,
+    key_cache: Option<KeyCache>,
 }
 
 impl ProverClientBuilder {
@@ -329,10 +361,23 @@ impl ProverClientBuilder {
         self
     }
 
+    /// Caches the proving keys of the local CPU prover on disk, see [`KeyCache`].
+    pub fn key_cache(mut self, key_cache: KeyCache) -> Self {
+        self.key_cache = Some(key_cache);
+        self
+    }
+
     /// Builds a [ProverClient], using the provided private key.
     pub fn build(self) -> ProverClient {
         match self.mode.expect("The prover mode is required") {
-            ProverMode::Cpu => ProverClient::cpu(),
+            ProverMode::Cpu => match self.key_cache {
+                Some(key_cache) => ProverClient {
+                    prover: Box::new(CpuProver::from_prover(
+                        ZKMProver::new().with_key_cache(key_cache),
+                    )),
+                },
+                None => ProverClient::cpu(),
+            },
             ProverMode::Cuda => ProverClient::cuda(),
             ProverMode::Network => {
                 cfg_if! {
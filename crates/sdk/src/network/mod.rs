@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 
 pub mod prover;
+pub mod server;
 
 #[derive(Debug, Default, Deserialize, Serialize, Clone)]
 pub struct ProverInput {
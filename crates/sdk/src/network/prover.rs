@@ -110,20 +110,17 @@ impl NetworkProver {
     }
 
     pub async fn sign_ecdsa(&self, request: &mut GenerateProofRequest) -> Result<()> {
-        let sign_data = match request.block_no {
-            Some(block_no) => {
-                format!("{}&{}&{}", request.proof_id, block_no, request.seg_size)
-            }
-            None => {
-                format!("{}&{}", request.proof_id, request.seg_size)
-            }
-        };
+        let sign_data = signing_message(request);
         let signature = self.wallet.sign_message(sign_data).await?;
         request.signature = signature.to_string();
         Ok(())
     }
 
     pub async fn download_file(url: &str) -> Result<Vec<u8>> {
+        // The URLs come from the service, which must not make the client read its local files.
+        if !url.starts_with("https://") && !url.starts_with("http://") {
+            bail!("unsupported file URL: {url}");
+        }
         let response = reqwest::get(url).await?;
         let content = response.bytes().await?;
         Ok(content.to_vec())
@@ -213,6 +210,9 @@ impl NetworkProver {
                 Some(Status::Success) => {
                     let public_values = if kind == ZKMProofKind::CompressToGroth16 {
                         ZKMPublicValues::default()
+                    } else if get_status_response.public_values_url.is_empty() {
+                        // Self-hosted stage servers may return the public values inline.
+                        ZKMPublicValues::from(&get_status_response.output_stream)
                     } else {
                         let public_values_bytes =
                             NetworkProver::download_file(&get_status_response.public_values_url)
@@ -249,6 +249,12 @@ impl NetworkProver {
         elf_id: Option<String>,
         timeout: Option<Duration>,
     ) -> Result<(ZKMProofWithPublicValues, u64)> {
+        if !stdin.files.is_empty() {
+            bail!("the network prover does not support files registered with `write_file` yet");
+        }
+        if stdin.linux_env != Default::default() {
+            bail!("the network prover does not support a custom time or random seed yet");
+        }
         let private_input = stdin.buffer.clone();
         let mut pri_buf = Vec::new();
         bincode::serialize_into(&mut pri_buf, &private_input)?;
@@ -308,15 +314,30 @@ impl Prover<DefaultProverComponents> for NetworkProver {
         pk: &ZKMProvingKey,
         stdin: ZKMStdin,
         _opts: ProofOpts,
-        _context: ZKMContext<'a>,
+        context: ZKMContext<'a>,
         kind: ZKMProofKind,
         elf_id: Option<String>,
     ) -> Result<(ZKMProofWithPublicValues, u64)> {
+        if context.snapshot.is_some() {
+            bail!("the network prover cannot resume from a snapshot");
+        }
         block_on(self.prove_with_cycles(&pk.elf, stdin, kind, elf_id, None))
     }
 }
 
-fn get_cert_and_identity(
+/// The message signed by the requester of a proof, which the stage service checks.
+///
+/// It only covers the proof id, the block number and the segment size, not the ELF or the
+/// input, so the signature of a request can be replayed with another ELF or input, under a proof
+/// id that the service has not seen yet.
+pub fn signing_message(request: &GenerateProofRequest) -> String {
+    match request.block_no {
+        Some(block_no) => format!("{}&{}&{}", request.proof_id, block_no, request.seg_size),
+        None => format!("{}&{}", request.proof_id, request.seg_size),
+    }
+}
+
+pub(crate) fn get_cert_and_identity(
     ca_cert_path: &str,
     ssl_cert_path: &str,
     ssl_key_path: &str,
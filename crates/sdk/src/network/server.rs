@@ -0,0 +1,595 @@
+//! A self-hostable implementation of the `stage.v1` proving service.
+//!
+//! Proof requests are queued and proven one at a time with a local [`CpuProver`]. The requests and
+//! the status of the jobs are persisted in the data directory, so that the jobs which were not
+//! finished are resumed after a restart, and the proofs and public values are stored next to them.
+
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    env, fs,
+    net::SocketAddr,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{bail, Result};
+use ethers::types::{Address, Signature};
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tonic::transport::{Identity, Server, ServerTlsConfig};
+use zkm_core_executor::ZKMContext;
+use zkm_core_machine::io::ZKMStdin;
+
+use crate::network::prover::{
+    get_cert_and_identity, signing_message,
+    stage_service::{
+        stage_service_server::{StageService, StageServiceServer},
+        GenerateProofRequest, GenerateProofResponse, GetStatusRequest, GetStatusResponse, Status,
+        Step,
+    },
+};
+use crate::{provers::ProofOpts, CpuProver, Prover, ZKMProofKind};
+
+/// The name of the stored proof, serialized as JSON like in [`GetStatusResponse`].
+const PROOF_FILE: &str = "proof.json";
+
+/// The name of the stored public values.
+const PUBLIC_VALUES_FILE: &str = "public_values.bin";
+
+/// The configuration of a [`StageServer`].
+#[derive(Debug, Clone)]
+pub struct StageServerConfig {
+    /// The directory of the jobs, the cached ELFs and the stored files.
+    pub data_dir: PathBuf,
+    /// The addresses allowed to request proofs, which must not be empty.
+    pub allowed_signers: Vec<Address>,
+    /// The base URL under which the stored files are served, e.g. by a static file server of the
+    /// files directory. The public values are returned inline in the status if `None`.
+    pub files_url: Option<String>,
+}
+
+impl StageServerConfig {
+    /// Creates a configuration from the environment.
+    ///
+    /// - `STAGE_DATA_DIR`: the data directory, defaults to `~/.zkm/stage` and is required when
+    ///   there is no home directory.
+    /// - `STAGE_ALLOWED_SIGNERS`: a comma separated list of the allowed addresses, required.
+    /// - `STAGE_FILES_URL`: the base URL of the stored files.
+    pub fn from_env() -> Result<Self> {
+        let data_dir = match env::var("STAGE_DATA_DIR") {
+            Ok(dir) => PathBuf::from(dir),
+            Err(_) => match dirs::home_dir() {
+                Some(home) => home.join(".zkm").join("stage"),
+                None => bail!("STAGE_DATA_DIR must be set when there is no home directory"),
+            },
+        };
+        let Ok(allowed_signers) = env::var("STAGE_ALLOWED_SIGNERS") else {
+            bail!("STAGE_ALLOWED_SIGNERS must be set to the addresses allowed to request proofs");
+        };
+        let allowed_signers = allowed_signers
+            .split(',')
+            .map(|signer| Address::from_str(signer.trim()))
+            .collect::<Result<_, _>>()?;
+        let files_url = env::var("STAGE_FILES_URL").ok();
+        Ok(Self { data_dir, allowed_signers, files_url })
+    }
+
+    fn jobs_dir(&self) -> PathBuf {
+        self.data_dir.join("jobs")
+    }
+
+    fn elfs_dir(&self) -> PathBuf {
+        self.data_dir.join("elfs")
+    }
+
+    /// The directory of the stored files, which `files_url` refers to.
+    pub fn files_dir(&self) -> PathBuf {
+        self.data_dir.join("files")
+    }
+}
+
+/// The persisted status of a proof request.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Job {
+    proof_id: String,
+    /// A [`Status`].
+    status: i32,
+    /// A [`Step`].
+    step: i32,
+    error_message: String,
+    elf_id: String,
+    created_at: u64,
+    stark_proof_url: String,
+    snark_proof_url: String,
+    public_values_url: String,
+    total_steps: u64,
+    proving_time: u64,
+}
+
+/// A `stage.v1` service proving the requests on this host.
+#[derive(Clone)]
+pub struct StageServer {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    config: StageServerConfig,
+    jobs: Mutex<HashMap<String, Job>>,
+    queue: Mutex<mpsc::Sender<String>>,
+}
+
+impl StageServer {
+    /// Creates a server proving with the given prover, and resumes the unfinished jobs.
+    pub fn new(config: StageServerConfig, prover: CpuProver) -> Result<Self> {
+        if config.allowed_signers.is_empty() {
+            bail!("at least one signer must be allowed to request proofs");
+        }
+        fs::create_dir_all(config.jobs_dir())?;
+        fs::create_dir_all(config.elfs_dir())?;
+        fs::create_dir_all(config.files_dir())?;
+
+        let mut jobs = HashMap::new();
+        for entry in fs::read_dir(config.jobs_dir())? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                let job: Job = serde_json::from_slice(&fs::read(&path)?)?;
+                jobs.insert(job.proof_id.clone(), job);
+            }
+        }
+        let mut unfinished = jobs
+            .values()
+            .filter(|job| job.status == Status::Computing as i32)
+            .map(|job| (job.created_at, job.proof_id.clone()))
+            .collect::<Vec<_>>();
+        unfinished.sort();
+
+        let (queue, receiver) = mpsc::channel();
+        let inner = Arc::new(Inner { config, jobs: Mutex::new(jobs), queue: Mutex::new(queue) });
+        for (_, proof_id) in unfinished {
+            tracing::info!("resuming job {}", proof_id);
+            inner.queue.lock().unwrap().send(proof_id)?;
+        }
+
+        let worker = Arc::clone(&inner);
+        thread::spawn(move || {
+            for proof_id in receiver {
+                worker.run_job(&prover, &proof_id);
+            }
+        });
+
+        Ok(Self { inner })
+    }
+
+    /// Serves the service on the given address until the process is interrupted.
+    ///
+    /// If `SSL_CERT_PATH` and `SSL_KEY_PATH` are set, the service is served over TLS, and clients
+    /// must present a certificate signed by `CA_CERT_PATH` when it is set as well.
+    pub async fn serve(self, addr: SocketAddr) -> Result<()> {
+        let mut server = Server::builder();
+        if let (Ok(cert_path), Ok(key_path)) = (env::var("SSL_CERT_PATH"), env::var("SSL_KEY_PATH"))
+        {
+            let (ca_cert, identity) = match env::var("CA_CERT_PATH") {
+                Ok(ca_cert_path) => get_cert_and_identity(&ca_cert_path, &cert_path, &key_path)?,
+                Err(_) => {
+                    (None, Some(Identity::from_pem(fs::read(cert_path)?, fs::read(key_path)?)))
+                }
+            };
+            let mut tls_config = ServerTlsConfig::new().identity(identity.unwrap());
+            if let Some(ca_cert) = ca_cert {
+                tls_config = tls_config.client_ca_root(ca_cert);
+            }
+            server = server.tls_config(tls_config)?;
+        }
+
+        tracing::info!("stage service listening on {}", addr);
+        server
+            .add_service(StageServiceServer::new(self))
+            .serve_with_shutdown(addr, async {
+                tokio::signal::ctrl_c().await.ok();
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Checks the signature of a request against the allowed signers.
+    fn verify_signature(&self, request: &GenerateProofRequest) -> Result<Address> {
+        let signature = Signature::from_str(&request.signature)?;
+        let signer = signature.recover(signing_message(request))?;
+        if !self.inner.config.allowed_signers.contains(&signer) {
+            bail!("signer {signer:?} is not allowed to request proofs");
+        }
+        Ok(signer)
+    }
+}
+
+impl Inner {
+    fn job_path(&self, proof_id: &str) -> PathBuf {
+        self.config.jobs_dir().join(proof_id).with_extension("json")
+    }
+
+    fn request_path(&self, proof_id: &str) -> PathBuf {
+        self.config.jobs_dir().join(proof_id).with_extension("req")
+    }
+
+    fn elf_path(&self, elf_id: &str) -> PathBuf {
+        self.config.elfs_dir().join(elf_id)
+    }
+
+    /// The URL of a stored file, which is empty if the files are not served.
+    fn file_url(&self, proof_id: &str, name: &str) -> String {
+        match &self.config.files_url {
+            Some(files_url) => format!("{}/{proof_id}/{name}", files_url.trim_end_matches('/')),
+            None => String::new(),
+        }
+    }
+
+    /// Updates a job and persists it.
+    fn update_job(&self, proof_id: &str, update: impl FnOnce(&mut Job)) -> Result<()> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let job = jobs.get_mut(proof_id).unwrap();
+        update(job);
+        write_atomic(&self.job_path(proof_id), &serde_json::to_vec(job)?)
+    }
+
+    fn run_job(&self, prover: &CpuProver, proof_id: &str) {
+        tracing::info!("proving job {}", proof_id);
+        let start = Instant::now();
+        let result = self
+            .update_job(proof_id, |job| job.step = Step::InProve as i32)
+            .map_err(JobError::internal)
+            .and_then(|_| {
+                // A panicking prover must not take the worker down with the queued jobs.
+                panic::catch_unwind(AssertUnwindSafe(|| self.prove(prover, proof_id)))
+                    .unwrap_or_else(|e| Err(JobError::internal(panic_message(&*e))))
+            });
+
+        let proving_time = start.elapsed().as_millis() as u64;
+        let update = self.update_job(proof_id, |job| {
+            job.proving_time = proving_time;
+            match result {
+                Ok((urls, cycles)) => {
+                    job.status = Status::Success as i32;
+                    job.step = Step::End as i32;
+                    job.total_steps = cycles;
+                    (job.stark_proof_url, job.snark_proof_url, job.public_values_url) = urls;
+                }
+                Err(JobError(status, message)) => {
+                    tracing::error!("job {} failed: {}", proof_id, message);
+                    job.status = status as i32;
+                    job.error_message = message;
+                }
+            }
+        });
+        if let Err(e) = update {
+            tracing::error!("failed to persist job {}: {}", proof_id, e);
+        }
+    }
+
+    /// Proves a job, and returns the URLs of the STARK proof, the SNARK proof and the public
+    /// values, along with the number of cycles.
+    fn prove(
+        &self,
+        prover: &CpuProver,
+        proof_id: &str,
+    ) -> Result<((String, String, String), u64), JobError> {
+        let request = fs::read(self.request_path(proof_id)).map_err(JobError::internal)?;
+        let request =
+            GenerateProofRequest::decode(request.as_slice()).map_err(JobError::internal)?;
+        let elf_id = self.jobs.lock().unwrap()[proof_id].elf_id.clone();
+        let elf = fs::read(self.elf_path(&elf_id)).map_err(JobError::internal)?;
+
+        // Decode the inputs, as encoded by the `NetworkProver`.
+        let kind = proof_kind(&request)?;
+        let mut stdin = ZKMStdin::new();
+        if !request.private_input_stream.is_empty() {
+            stdin.buffer = bincode::deserialize(&request.private_input_stream)
+                .map_err(JobError::invalid_parameter)?;
+        }
+        for receipt in request.receipt_inputs.iter() {
+            let (proof, vk) = bincode::deserialize(receipt).map_err(JobError::invalid_parameter)?;
+            stdin.write_proof(proof, vk);
+        }
+        let mut opts = ProofOpts::default();
+        if request.seg_size > 0 {
+            if !request.seg_size.is_power_of_two() {
+                return Err(JobError::invalid_parameter("the segment size must be a power of two"));
+            }
+            opts.zkm_prover_opts.core_opts.shard_size = request.seg_size as usize;
+        }
+
+        let (pk, _) = prover.setup(&elf);
+        let (proof, cycles) = prover
+            .prove_impl(&pk, stdin, opts, ZKMContext::default(), kind, None)
+            .map_err(|e| JobError(status_of(kind), e.to_string()))?;
+
+        // Store the proof and the public values.
+        let dir = self.config.files_dir().join(proof_id);
+        fs::create_dir_all(&dir).map_err(JobError::internal)?;
+        let proof_json = serde_json::to_vec(&proof.proof).map_err(JobError::internal)?;
+        write_atomic(&dir.join(PROOF_FILE), &proof_json).map_err(JobError::internal)?;
+        write_atomic(&dir.join(PUBLIC_VALUES_FILE), proof.public_values.as_slice())
+            .map_err(JobError::internal)?;
+
+        let proof_url = self.file_url(proof_id, PROOF_FILE);
+        let (stark_proof_url, snark_proof_url) = match kind {
+            ZKMProofKind::Groth16 | ZKMProofKind::CompressToGroth16 => (String::new(), proof_url),
+            _ => (proof_url, String::new()),
+        };
+        let public_values_url = self.file_url(proof_id, PUBLIC_VALUES_FILE);
+        Ok(((stark_proof_url, snark_proof_url, public_values_url), cycles))
+    }
+}
+
+#[tonic::async_trait]
+impl StageService for StageServer {
+    async fn generate_proof(
+        &self,
+        request: tonic::Request<GenerateProofRequest>,
+    ) -> Result<tonic::Response<GenerateProofResponse>, tonic::Status> {
+        let mut request = request.into_inner();
+        let signer = self
+            .verify_signature(&request)
+            .map_err(|e| tonic::Status::unauthenticated(e.to_string()))?;
+        if !is_valid_id(&request.proof_id) {
+            return Err(tonic::Status::invalid_argument("invalid proof id"));
+        }
+        proof_kind(&request).map_err(|e| tonic::Status::invalid_argument(e.1))?;
+        let inner = &self.inner;
+
+        // Cache the ELF, so that later requests can refer to it by its id.
+        let elf_id = if request.elf_data.is_empty() {
+            let elf_id = request.elf_id.clone().unwrap_or_default();
+            if !is_valid_id(&elf_id) || !inner.elf_path(&elf_id).is_file() {
+                return Err(tonic::Status::not_found("unknown ELF id"));
+            }
+            elf_id
+        } else {
+            let elf_id = hex::encode(Sha256::digest(&request.elf_data));
+            write_atomic(&inner.elf_path(&elf_id), &request.elf_data)
+                .map_err(|e| tonic::Status::internal(e.to_string()))?;
+            request.elf_data.clear();
+            elf_id
+        };
+
+        let job = Job {
+            proof_id: request.proof_id.clone(),
+            status: Status::Computing as i32,
+            step: Step::Init as i32,
+            elf_id,
+            created_at: SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64,
+            ..Default::default()
+        };
+        let proof_id = job.proof_id.clone();
+
+        // Reserve the proof id before persisting the request, so that concurrent requests with the
+        // same id don't overwrite each other's files.
+        match inner.jobs.lock().unwrap().entry(proof_id.clone()) {
+            Entry::Occupied(_) => {
+                return Err(tonic::Status::already_exists("the proof id is already used"));
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(job.clone());
+            }
+        }
+        let persisted = write_atomic(&inner.request_path(&proof_id), &request.encode_to_vec())
+            .and_then(|_| write_atomic(&inner.job_path(&proof_id), &serde_json::to_vec(&job)?));
+        if let Err(e) = persisted {
+            inner.jobs.lock().unwrap().remove(&proof_id);
+            return Err(tonic::Status::internal(e.to_string()));
+        }
+
+        tracing::info!("queued job {} from {:?}", proof_id, signer);
+        inner
+            .queue
+            .lock()
+            .unwrap()
+            .send(proof_id.clone())
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+
+        Ok(tonic::Response::new(GenerateProofResponse {
+            status: Status::Computing as i32,
+            proof_id,
+            ..Default::default()
+        }))
+    }
+
+    async fn get_status(
+        &self,
+        request: tonic::Request<GetStatusRequest>,
+    ) -> Result<tonic::Response<GetStatusResponse>, tonic::Status> {
+        let proof_id = request.into_inner().proof_id;
+        let job = self.inner.jobs.lock().unwrap().get(&proof_id).cloned();
+        let Some(job) = job else {
+            return Err(tonic::Status::not_found("unknown proof id"));
+        };
+
+        let (proof_with_public_inputs, output_stream) = if job.status == Status::Success as i32 {
+            let dir = self.inner.config.files_dir().join(&proof_id);
+            let proof = fs::read(dir.join(PROOF_FILE))
+                .map_err(|e| tonic::Status::internal(e.to_string()))?;
+            // The public values are returned inline when they are not served.
+            let public_values = if job.public_values_url.is_empty() {
+                fs::read(dir.join(PUBLIC_VALUES_FILE))
+                    .map_err(|e| tonic::Status::internal(e.to_string()))?
+            } else {
+                Vec::new()
+            };
+            (proof, public_values)
+        } else {
+            (Vec::new(), Vec::new())
+        };
+        Ok(tonic::Response::new(GetStatusResponse {
+            proof_id,
+            status: job.status,
+            proof_with_public_inputs,
+            snark_proof_url: job.snark_proof_url,
+            stark_proof_url: job.stark_proof_url,
+            output_stream,
+            step: job.step,
+            public_values_url: job.public_values_url,
+            total_steps: job.total_steps,
+            elf_id: job.elf_id.into_bytes(),
+            proving_time: job.proving_time,
+            ..Default::default()
+        }))
+    }
+}
+
+/// A failed job, with the status reported to the client.
+struct JobError(Status, String);
+
+impl JobError {
+    fn internal(e: impl ToString) -> Self {
+        Self(Status::InternalError, e.to_string())
+    }
+
+    fn invalid_parameter(e: impl ToString) -> Self {
+        Self(Status::InvalidParameter, e.to_string())
+    }
+}
+
+/// The kind of proof requested, following the steps set by the `NetworkProver`.
+fn proof_kind(request: &GenerateProofRequest) -> Result<ZKMProofKind, JobError> {
+    let from_step = request.from_step.and_then(Step::from_i32);
+    let target_step = request.target_step.and_then(Step::from_i32);
+    match (from_step, target_step) {
+        (None | Some(Step::Init), Some(Step::InProve)) => Ok(ZKMProofKind::Core),
+        (None | Some(Step::Init), Some(Step::InAgg)) => Ok(ZKMProofKind::Compressed),
+        (None | Some(Step::Init), Some(Step::InSnark)) => Ok(ZKMProofKind::Groth16),
+        (Some(Step::InAgg), Some(Step::InSnark)) => Ok(ZKMProofKind::CompressToGroth16),
+        _ => Err(JobError::invalid_parameter("unsupported steps")),
+    }
+}
+
+/// The status of a failed proof of the given kind.
+fn status_of(kind: ZKMProofKind) -> Status {
+    match kind {
+        ZKMProofKind::Core => Status::ProveError,
+        ZKMProofKind::Compressed => Status::AggError,
+        _ => Status::SnarkError,
+    }
+}
+
+/// The message of a caught panic.
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    let message = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic");
+    format!("the prover panicked: {message}")
+}
+
+/// Whether an id can be used as a file name, i.e. is non-empty and only made of alphanumeric
+/// characters, `-` and `_`.
+fn is_valid_id(id: &str) -> bool {
+    !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Writes a file through a temporary file, so that readers never see a partial file.
+fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    fs::write(&tmp_path, contents)?;
+    fs::rename(tmp_path, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::signers::{LocalWallet, Signer};
+
+    use std::time::Duration;
+
+    use tonic::transport::Endpoint;
+
+    use super::*;
+    use crate::{block_on, network::prover::NetworkProver, utils, ProverClient};
+
+    #[test]
+    fn test_verify_signature() {
+        let wallet = "0x0123456789012345678901234567890123456789012345678901234567890123"
+            .parse::<LocalWallet>()
+            .unwrap();
+        let mut request = GenerateProofRequest {
+            proof_id: "proof".to_string(),
+            seg_size: 1 << 16,
+            ..Default::default()
+        };
+        request.signature =
+            block_on(wallet.sign_message(signing_message(&request))).unwrap().to_string();
+
+        let config = StageServerConfig {
+            data_dir: env::temp_dir(),
+            allowed_signers: vec![wallet.address()],
+            files_url: None,
+        };
+        let inner = Arc::new(Inner {
+            config,
+            jobs: Mutex::new(HashMap::new()),
+            queue: Mutex::new(mpsc::channel().0),
+        });
+        let server = StageServer { inner };
+        assert_eq!(server.verify_signature(&request).unwrap(), wallet.address());
+
+        // The signature does not cover another proof.
+        request.proof_id = "another proof".to_string();
+        assert!(server.verify_signature(&request).is_err());
+    }
+
+    #[test]
+    fn test_stage_server_e2e() {
+        utils::setup_logger();
+        let wallet = "0x0123456789012345678901234567890123456789012345678901234567890123"
+            .parse::<LocalWallet>()
+            .unwrap();
+        let data_dir = tempfile::tempdir().unwrap();
+        let config = StageServerConfig {
+            data_dir: data_dir.path().to_path_buf(),
+            allowed_signers: vec![wallet.address()],
+            files_url: None,
+        };
+        let server = StageServer::new(config, CpuProver::new()).unwrap();
+
+        let addr = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.spawn(server.serve(addr));
+        let endpoint = Endpoint::new(format!("http://{addr}")).unwrap();
+        block_on(async {
+            while endpoint.connect().await.is_err() {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        });
+
+        let elf = test_artifacts::FIBONACCI_ELF;
+        let mut stdin = ZKMStdin::new();
+        stdin.write(&10usize);
+        let prover = |wallet| NetworkProver {
+            endpoint: endpoint.clone(),
+            wallet,
+            local_prover: CpuProver::new(),
+            poll_interval: 100,
+        };
+        let timeout = Some(Duration::from_secs(3600));
+
+        // The requests of other signers are rejected.
+        let stranger = "0x3210987654321098765432109876543210987654321098765432109876543210"
+            .parse::<LocalWallet>()
+            .unwrap();
+        let kind = ZKMProofKind::Compressed;
+        let rejected = prover(stranger).prove_with_cycles(elf, stdin.clone(), kind, None, timeout);
+        assert!(block_on(rejected).is_err());
+
+        let (proof, cycles) =
+            block_on(prover(wallet).prove_with_cycles(elf, stdin, kind, None, timeout)).unwrap();
+        assert!(cycles > 0);
+        let client = ProverClient::cpu();
+        let (_, vk) = client.setup(elf);
+        client.verify(&proof, &vk).unwrap();
+    }
+}
@@ -67,7 +67,7 @@ impl ZKMProofWithPublicValues {
     /// encoded proof, in a form optimized for onchain verification.
     pub fn bytes(&self) -> Vec<u8> {
         match &self.proof {
-            ZKMProof::Compressed(_) => {
+            ZKMProof::Core(_) | ZKMProof::Compressed(_) => {
                 bincode::serialize(&self.proof).expect("Invalid stark proof")
             }
             ZKMProof::Plonk(plonk_proof) => {
@@ -1,7 +1,8 @@
 use anyhow::Result;
 use zkm_core_executor::ZKMContext;
-use zkm_core_machine::io::ZKMStdin;
+use zkm_core_machine::{io::ZKMStdin, utils::WorkDir};
 use zkm_prover::{components::DefaultProverComponents, ZKMProver};
+use zkm_stark::MachineProver;
 
 use crate::install::try_install_circuit_artifacts;
 use crate::{
@@ -90,11 +91,26 @@ impl Prover<DefaultProverComponents> for CpuProver {
             return Ok((self.compress_to_groth16(stdin, opts)?, 0));
         }
 
-        let program = self.prover.get_program(&pk.elf).unwrap();
+        let program = self.prover.get_program_with_context(&pk.elf, &context).unwrap();
+        let work_dir = opts
+            .work_dir
+            .as_ref()
+            .map(|dir| {
+                let config = self.prover.core_prover.config();
+                WorkDir::open(dir, &program, &stdin, opts.zkm_prover_opts.core_opts, config)
+            })
+            .transpose()?;
 
         // Generate the core proof.
         let proof: zkm_prover::ZKMProofWithMetadata<zkm_prover::ZKMCoreProofData> =
-            self.prover.prove_core(&pk.pk, program, &stdin, opts.zkm_prover_opts, context)?;
+            self.prover.prove_core_with_work_dir(
+                &pk.pk,
+                program,
+                &stdin,
+                opts.zkm_prover_opts,
+                context,
+                work_dir.as_ref(),
+            )?;
         let cycles = proof.cycles;
         if kind == ZKMProofKind::Core {
             return Ok((
@@ -112,8 +128,13 @@ impl Prover<DefaultProverComponents> for CpuProver {
         let public_values = proof.public_values.clone();
 
         // Generate the compressed proof.
-        let reduce_proof =
-            self.prover.compress(&pk.vk, proof, deferred_proofs, opts.zkm_prover_opts)?;
+        let reduce_proof = self.prover.compress_with_work_dir(
+            &pk.vk,
+            proof,
+            deferred_proofs,
+            opts.zkm_prover_opts,
+            work_dir.as_ref(),
+        )?;
         if kind == ZKMProofKind::Compressed {
             return Ok((
                 ZKMProofWithPublicValues {
@@ -1,4 +1,4 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 use tonic::async_trait;
 use zkm_core_executor::ZKMContext;
 use zkm_core_machine::io::ZKMStdin;
@@ -163,10 +163,13 @@ impl Prover<DefaultProverComponents> for CudaProver {
         pk: &ZKMProvingKey,
         stdin: ZKMStdin,
         _opts: ProofOpts,
-        _context: ZKMContext<'a>,
+        context: ZKMContext<'a>,
         kind: ZKMProofKind,
         _elf_id: Option<String>,
     ) -> Result<(ZKMProofWithPublicValues, u64)> {
+        if context.snapshot.is_some() {
+            bail!("the CUDA prover cannot resume from a snapshot");
+        }
         self.prove_with_cycles(pk, &stdin, kind)
     }
 }
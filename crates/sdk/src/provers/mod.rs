@@ -9,12 +9,14 @@ pub use mock::MockProver;
 use itertools::Itertools;
 use p3_field::PrimeField32;
 use std::borrow::Borrow;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use anyhow::Result;
 use strum_macros::EnumString;
 use thiserror::Error;
 use zkm_core_executor::ExecutionReport;
+use zkm_core_executor::ExecutionState;
 use zkm_core_executor::ZKMContext;
 use zkm_core_machine::{io::ZKMStdin, ZKM_CIRCUIT_VERSION};
 use zkm_primitives::io::ZKMPublicValues;
@@ -44,6 +46,9 @@ pub struct ProofOpts {
     pub zkm_prover_opts: ZKMProverOpts,
     /// Optional timeout duration for proof generation.
     pub timeout: Option<Duration>,
+    /// Optional directory persisting the intermediate artifacts of the proof, so that an
+    /// interrupted proof can be resumed by proving the same program on the same input again.
+    pub work_dir: Option<PathBuf>,
 }
 
 #[derive(Error, Debug)]
@@ -80,6 +85,20 @@ pub trait Prover<C: ZKMProverComponents>: Send + Sync {
     /// Generate the proving and verifying keys for the given program.
     fn setup(&self, elf: &[u8]) -> (ZKMProvingKey, ZKMVerifyingKey);
 
+    /// Generate the proving and verifying keys for the given program resumed from a snapshot of
+    /// its execution.
+    fn setup_from_snapshot(
+        &self,
+        elf: &[u8],
+        snapshot: &ExecutionState,
+    ) -> Result<(ZKMProvingKey, ZKMVerifyingKey)> {
+        let (pk, _, _, vk) = self
+            .zkm_prover()
+            .setup_from_snapshot(elf, snapshot)
+            .map_err(|e| anyhow::anyhow!("{e}"))?;
+        Ok((pk, vk))
+    }
+
     /// Prove the execution of a MIPS ELF with the given inputs, according to the given proof mode.
     fn prove(
         &self,
@@ -229,6 +248,14 @@ impl Prover<DefaultProverComponents> for ProverClient {
         self.prover.setup(elf)
     }
 
+    fn setup_from_snapshot(
+        &self,
+        elf: &[u8],
+        snapshot: &ExecutionState,
+    ) -> Result<(ZKMProvingKey, ZKMVerifyingKey)> {
+        self.prover.setup_from_snapshot(elf, snapshot)
+    }
+
     fn prove_impl<'a>(
         &'a self,
         pk: &ZKMProvingKey,
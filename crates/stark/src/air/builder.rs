@@ -206,6 +206,7 @@ pub trait InstructionAirBuilder: BaseAirBuilder {
         is_check_memory: impl Into<Self::Expr>,
         is_halt: impl Into<Self::Expr>,
         is_sequential: impl Into<Self::Expr>,
+        is_redirect: impl Into<Self::Expr>,
         multiplicity: impl Into<Self::Expr>,
     ) {
         let values = once(shard.into())
@@ -224,6 +225,7 @@ pub trait InstructionAirBuilder: BaseAirBuilder {
             .chain(once(is_check_memory.into()))
             .chain(once(is_halt.into()))
             .chain(once(is_sequential.into()))
+            .chain(once(is_redirect.into()))
             .collect();
 
         self.send(
@@ -252,6 +254,7 @@ pub trait InstructionAirBuilder: BaseAirBuilder {
         is_check_memory: impl Into<Self::Expr>,
         is_halt: impl Into<Self::Expr>,
         is_sequential: impl Into<Self::Expr>,
+        is_redirect: impl Into<Self::Expr>,
         multiplicity: impl Into<Self::Expr>,
     ) {
         let values = once(shard.into())
@@ -270,6 +273,7 @@ pub trait InstructionAirBuilder: BaseAirBuilder {
             .chain(once(is_check_memory.into()))
             .chain(once(is_halt.into()))
             .chain(once(is_sequential.into()))
+            .chain(once(is_redirect.into()))
             .collect();
 
         self.receive(
@@ -319,6 +323,7 @@ pub trait InstructionAirBuilder: BaseAirBuilder {
             Self::Expr::zero(),
             Self::Expr::zero(),
             Self::Expr::one(),
+            Self::Expr::zero(),
             multiplicity,
         )
     }
@@ -0,0 +1,335 @@
+//! A static analysis of the AIRs of a machine.
+//!
+//! The analysis evaluates every chip with the symbolic builders only, so it needs neither a
+//! program nor a trace. For each chip it reports the number and the maximum degree of the
+//! constraints, the widths of the traces and the signatures of the lookups. For the machine it
+//! counts the sends and the receives of every signature in each scope, and reports the lookups
+//! that are sent but never received, or received but never sent, which are usually a mismatch in
+//! the number of values or in the scope between the two sides of a lookup.
+
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter},
+};
+
+use p3_air::{Air, BaseAir};
+use p3_field::Field;
+use p3_uni_stark::{get_symbolic_constraints, SymbolicAirBuilder, SymbolicExpression};
+
+use crate::{
+    air::{LookupScope, MachineAir},
+    count_permutation_constraints,
+    lookup::{Lookup, LookupKind},
+    Chip, PROOF_MAX_NUM_PVS,
+};
+
+/// The shape of a lookup, which must be the same for a send and the receives that balance it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LookupSignature {
+    /// The kind of the lookup.
+    pub kind: LookupKind,
+    /// The scope of the lookup.
+    pub scope: LookupScope,
+    /// The number of values of the lookup.
+    pub num_values: usize,
+}
+
+impl<F: Field> From<&Lookup<F>> for LookupSignature {
+    fn from(lookup: &Lookup<F>) -> Self {
+        Self { kind: lookup.kind, scope: lookup.scope, num_values: lookup.values.len() }
+    }
+}
+
+impl Display for LookupSignature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}({}, {} values)", self.kind, self.scope, self.num_values)
+    }
+}
+
+/// The static properties of a chip.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChipReport {
+    /// The name of the chip.
+    pub name: String,
+    /// The number of constraints of the underlying air.
+    pub num_constraints: usize,
+    /// The number of constraints of the permutation argument.
+    pub num_permutation_constraints: usize,
+    /// The maximum degree of the constraints of the underlying air.
+    pub max_constraint_degree: usize,
+    /// The relative log degree of the quotient polynomial.
+    pub log_quotient_degree: usize,
+    /// The number of preprocessed columns.
+    pub preprocessed_width: usize,
+    /// The number of main columns.
+    pub main_width: usize,
+    /// The number of permutation columns, in extension field elements.
+    pub permutation_width: usize,
+    /// The signatures of the sends, with the number of sends of each.
+    pub sends: Vec<(LookupSignature, usize)>,
+    /// The signatures of the receives, with the number of receives of each.
+    pub receives: Vec<(LookupSignature, usize)>,
+}
+
+impl ChipReport {
+    /// Analyzes a chip.
+    pub fn new<F, A>(chip: &Chip<F, A>) -> Self
+    where
+        F: Field,
+        A: MachineAir<F> + Air<SymbolicAirBuilder<F>>,
+    {
+        let constraints =
+            get_symbolic_constraints(&chip.air, chip.preprocessed_width(), PROOF_MAX_NUM_PVS);
+        let max_constraint_degree =
+            constraints.iter().map(SymbolicExpression::degree_multiple).max().unwrap_or(0);
+        let num_permutation_constraints = count_permutation_constraints(
+            chip.sends(),
+            chip.receives(),
+            chip.logup_batch_size(),
+            chip.commit_scope(),
+        );
+
+        Self {
+            name: chip.name(),
+            num_constraints: constraints.len(),
+            num_permutation_constraints,
+            max_constraint_degree,
+            log_quotient_degree: chip.log_quotient_degree(),
+            preprocessed_width: chip.preprocessed_width(),
+            main_width: chip.width(),
+            permutation_width: chip.permutation_width(),
+            sends: signatures(chip.sends()),
+            receives: signatures(chip.receives()),
+        }
+    }
+}
+
+/// Groups lookups by signature and counts them.
+fn signatures<F: Field>(lookups: &[Lookup<F>]) -> Vec<(LookupSignature, usize)> {
+    let mut signatures = lookups.iter().map(LookupSignature::from).collect::<Vec<_>>();
+    signatures.sort();
+    signatures.dedup_by_key(|signature| *signature);
+    signatures
+        .into_iter()
+        .map(|signature| {
+            let count = lookups.iter().filter(|l| LookupSignature::from(*l) == signature).count();
+            (signature, count)
+        })
+        .collect()
+}
+
+/// The number of sends and receives of a lookup signature over all the chips of a machine.
+///
+/// The counts of the two sides usually differ, since many chips can send to a single receiver,
+/// but a signature that is sent must be received at least once in the same scope, and the other
+/// way around.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LookupBalance {
+    /// The signature of the lookups.
+    pub signature: LookupSignature,
+    /// The number of sends with the signature, over all the chips.
+    pub num_sends: usize,
+    /// The number of receives with the signature, over all the chips.
+    pub num_receives: usize,
+    /// The names of the chips that send the signature.
+    pub senders: Vec<String>,
+    /// The names of the chips that receive the signature.
+    pub receivers: Vec<String>,
+}
+
+impl LookupBalance {
+    fn new(signature: LookupSignature) -> Self {
+        Self { signature, num_sends: 0, num_receives: 0, senders: vec![], receivers: vec![] }
+    }
+
+    /// Whether the signature is either both sent and received, or neither.
+    pub fn is_matched(&self) -> bool {
+        (self.num_sends == 0) == (self.num_receives == 0)
+    }
+}
+
+/// The static properties of all the chips of a machine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AirReport {
+    /// The reports of the chips, in the order of the machine.
+    pub chips: Vec<ChipReport>,
+    /// The balance of every lookup signature, ordered by scope.
+    pub lookups: Vec<LookupBalance>,
+}
+
+impl AirReport {
+    /// Analyzes the chips of a machine.
+    pub fn new<F, A>(chips: &[Chip<F, A>]) -> Self
+    where
+        F: Field,
+        A: MachineAir<F> + Air<SymbolicAirBuilder<F>>,
+    {
+        Self::from_chip_reports(chips.iter().map(ChipReport::new).collect())
+    }
+
+    /// Computes the balance of the lookups of the given chip reports.
+    pub fn from_chip_reports(chips: Vec<ChipReport>) -> Self {
+        let mut lookups = BTreeMap::new();
+        for chip in &chips {
+            for (signature, count) in &chip.sends {
+                let balance = lookups
+                    .entry((signature.scope, *signature))
+                    .or_insert_with(|| LookupBalance::new(*signature));
+                balance.num_sends += count;
+                balance.senders.push(chip.name.clone());
+            }
+            for (signature, count) in &chip.receives {
+                let balance = lookups
+                    .entry((signature.scope, *signature))
+                    .or_insert_with(|| LookupBalance::new(*signature));
+                balance.num_receives += count;
+                balance.receivers.push(chip.name.clone());
+            }
+        }
+
+        Self { chips, lookups: lookups.into_values().collect() }
+    }
+
+    /// The signatures that some chip sends but no chip receives in the same scope.
+    pub fn unmatched_sends(&self) -> Vec<&LookupBalance> {
+        self.lookups.iter().filter(|lookup| lookup.num_receives == 0).collect()
+    }
+
+    /// The signatures that some chip receives but no chip sends in the same scope.
+    pub fn unmatched_receives(&self) -> Vec<&LookupBalance> {
+        self.lookups.iter().filter(|lookup| lookup.num_sends == 0).collect()
+    }
+
+    /// Whether every sent signature is received by some chip, and every received signature is
+    /// sent by some chip.
+    pub fn is_balanced(&self) -> bool {
+        self.lookups.iter().all(LookupBalance::is_matched)
+    }
+
+    /// The chips whose constraints have a degree greater than `max_degree`.
+    pub fn degree_violations(&self, max_degree: usize) -> Vec<&ChipReport> {
+        self.chips.iter().filter(|chip| chip.max_constraint_degree > max_degree).collect()
+    }
+
+    /// The maximum degree of the constraints over all the chips.
+    pub fn max_constraint_degree(&self) -> usize {
+        self.chips.iter().map(|chip| chip.max_constraint_degree).max().unwrap_or(0)
+    }
+}
+
+impl Display for AirReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{:<32} {:>11} {:>9} {:>6} {:>6} {:>6} {:>6} {:>5}",
+            "chip", "constraints", "perm_cons", "degree", "prep", "main", "perm", "log_q"
+        )?;
+        for chip in &self.chips {
+            writeln!(
+                f,
+                "{:<32} {:>11} {:>9} {:>6} {:>6} {:>6} {:>6} {:>5}",
+                chip.name,
+                chip.num_constraints,
+                chip.num_permutation_constraints,
+                chip.max_constraint_degree,
+                chip.preprocessed_width,
+                chip.main_width,
+                chip.permutation_width,
+                chip.log_quotient_degree,
+            )?;
+        }
+
+        writeln!(f)?;
+        for chip in &self.chips {
+            writeln!(f, "{}:", chip.name)?;
+            for (signature, count) in &chip.sends {
+                writeln!(f, "    sends    {count:>3} x {signature}")?;
+            }
+            for (signature, count) in &chip.receives {
+                writeln!(f, "    receives {count:>3} x {signature}")?;
+            }
+        }
+
+        writeln!(f)?;
+        for lookup in &self.lookups {
+            writeln!(
+                f,
+                "{:<40} sent {:>4} x, received {:>4} x",
+                lookup.signature.to_string(),
+                lookup.num_sends,
+                lookup.num_receives
+            )?;
+        }
+
+        writeln!(f)?;
+        for lookup in self.unmatched_sends() {
+            writeln!(f, "unmatched send {} from {}", lookup.signature, lookup.senders.join(", "))?;
+        }
+        for lookup in self.unmatched_receives() {
+            let receivers = lookup.receivers.join(", ");
+            writeln!(f, "unmatched receive {} by {receivers}", lookup.signature)?;
+        }
+        if self.is_balanced() {
+            writeln!(f, "all lookups are matched")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chip(
+        name: &str,
+        sends: Vec<(LookupSignature, usize)>,
+        receives: Vec<(LookupSignature, usize)>,
+    ) -> ChipReport {
+        ChipReport {
+            name: name.to_string(),
+            num_constraints: 0,
+            num_permutation_constraints: 0,
+            max_constraint_degree: 0,
+            log_quotient_degree: 0,
+            preprocessed_width: 0,
+            main_width: 0,
+            permutation_width: 0,
+            sends,
+            receives,
+        }
+    }
+
+    #[test]
+    fn test_lookup_balance() {
+        let byte = |scope| LookupSignature { kind: LookupKind::Byte, scope, num_values: 4 };
+        let syscall = |scope| LookupSignature { kind: LookupKind::Syscall, scope, num_values: 5 };
+
+        let report = AirReport::from_chip_reports(vec![
+            chip(
+                "Cpu",
+                vec![(byte(LookupScope::Local), 3), (syscall(LookupScope::Global), 1)],
+                vec![],
+            ),
+            chip("Add", vec![(byte(LookupScope::Local), 2)], vec![]),
+            chip("Byte", vec![], vec![(byte(LookupScope::Local), 1)]),
+            chip("Precompile", vec![], vec![(syscall(LookupScope::Local), 1)]),
+        ]);
+
+        // The global lookups come first, and the byte lookups are counted over all the senders.
+        let byte_balance = &report.lookups[1];
+        assert_eq!(byte_balance.signature, byte(LookupScope::Local));
+        assert_eq!((byte_balance.num_sends, byte_balance.num_receives), (5, 1));
+        assert_eq!(byte_balance.senders, ["Cpu", "Add"]);
+        assert!(byte_balance.is_matched());
+
+        // A syscall sent globally but received locally is unmatched in both scopes.
+        assert!(!report.is_balanced());
+        let sends = report.unmatched_sends();
+        assert_eq!(sends.len(), 1);
+        assert_eq!((sends[0].signature, sends[0].num_sends), (syscall(LookupScope::Global), 1));
+        let receives = report.unmatched_receives();
+        assert_eq!(receives.len(), 1);
+        assert_eq!(receives[0].signature, syscall(LookupScope::Local));
+        assert_eq!(receives[0].receivers, ["Precompile"]);
+    }
+}
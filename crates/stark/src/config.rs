@@ -3,6 +3,8 @@ use p3_commit::{Pcs, PolynomialSpace};
 use p3_field::{ExtensionField, Field, PrimeField};
 use serde::{de::DeserializeOwned, Serialize};
 
+use crate::{SecurityProfile, ZkConfig};
+
 pub type PcsError<SC> = <<SC as StarkGenericConfig>::Pcs as Pcs<
     <SC as StarkGenericConfig>::Challenge,
     <SC as StarkGenericConfig>::Challenger,
@@ -72,6 +74,14 @@ pub trait StarkGenericConfig: 'static + Send + Sync + Serialize + DeserializeOwn
 
     /// Initialize a new challenger.
     fn challenger(&self) -> Self::Challenger;
+
+    /// The FRI parameters which determine the security level of the proofs.
+    fn security_profile(&self) -> SecurityProfile;
+
+    /// The parameters of the zero-knowledge mode, or `None` if the proofs are not hiding.
+    fn zk(&self) -> Option<ZkConfig> {
+        None
+    }
 }
 
 pub trait ZeroCommitment<SC: StarkGenericConfig> {
@@ -1,6 +1,6 @@
 #![allow(missing_docs)]
 
-use crate::{Com, StarkGenericConfig, ZeroCommitment};
+use crate::{Com, SecurityProfile, StarkGenericConfig, ZeroCommitment};
 use p3_challenger::DuplexChallenger;
 use p3_commit::ExtensionMmcs;
 use p3_dft::Radix2DitParallel;
@@ -52,30 +52,24 @@ pub fn inner_perm() -> InnerPerm {
 /// The FRI config for Ziren proofs.
 #[must_use]
 pub fn zkm_fri_config() -> FriConfig<InnerChallengeMmcs> {
-    let perm = inner_perm();
-    let hash = InnerHash::new(perm.clone());
-    let compress = InnerCompress::new(perm.clone());
-    let challenge_mmcs = InnerChallengeMmcs::new(InnerValMmcs::new(hash, compress));
-    let num_queries = match std::env::var("FRI_QUERIES") {
-        Ok(value) => value.parse().unwrap(),
-        Err(_) => 84,
-    };
-    FriConfig { log_blowup: 1, num_queries, proof_of_work_bits: 16, mmcs: challenge_mmcs }
+    inner_fri_config_with_profile(SecurityProfile::DEFAULT)
 }
 
 /// The FRI config for inner recursion.
-/// This targets by default 100 bits of security.
+/// This targets 100 bits of security.
 #[must_use]
 pub fn inner_fri_config() -> FriConfig<InnerChallengeMmcs> {
+    inner_fri_config_with_profile(SecurityProfile::DEFAULT)
+}
+
+/// The FRI config for inner recursion with the given security profile.
+#[must_use]
+pub fn inner_fri_config_with_profile(profile: SecurityProfile) -> FriConfig<InnerChallengeMmcs> {
     let perm = inner_perm();
     let hash = InnerHash::new(perm.clone());
     let compress = InnerCompress::new(perm.clone());
     let challenge_mmcs = InnerChallengeMmcs::new(InnerValMmcs::new(hash, compress));
-    let num_queries = match std::env::var("FRI_QUERIES") {
-        Ok(value) => value.parse().unwrap(),
-        Err(_) => 84,
-    };
-    FriConfig { log_blowup: 1, num_queries, proof_of_work_bits: 16, mmcs: challenge_mmcs }
+    profile.fri_config(challenge_mmcs)
 }
 
 /// The recursion config used for recursive reduce circuit.
@@ -142,6 +136,10 @@ impl StarkGenericConfig for KoalaBearPoseidon2Inner {
     fn challenger(&self) -> Self::Challenger {
         InnerChallenger::new(self.perm.clone())
     }
+
+    fn security_profile(&self) -> SecurityProfile {
+        SecurityProfile::of(self.pcs.fri_config())
+    }
 }
 
 impl ZeroCommitment<KoalaBearPoseidon2Inner> for InnerPcs {
@@ -155,7 +153,7 @@ pub mod koala_bear_poseidon2 {
     use p3_challenger::DuplexChallenger;
     use p3_commit::ExtensionMmcs;
     use p3_dft::Radix2DitParallel;
-    use p3_field::{extension::BinomialExtensionField, Field, FieldAlgebra};
+    use p3_field::{extension::BinomialExtensionField, Field, FieldAlgebra, FieldExtensionAlgebra};
     use p3_fri::{FriConfig, TwoAdicFriPcs};
     use p3_koala_bear::{KoalaBear, Poseidon2KoalaBear};
     use p3_merkle_tree::MerkleTreeMmcs;
@@ -164,7 +162,7 @@ pub mod koala_bear_poseidon2 {
     use serde::{Deserialize, Serialize};
     use zkm_primitives::RC_16_30;
 
-    use crate::{Com, StarkGenericConfig, ZeroCommitment, DIGEST_SIZE};
+    use crate::{Com, SecurityProfile, StarkGenericConfig, ZeroCommitment, ZkConfig, DIGEST_SIZE};
 
     pub type Val = KoalaBear;
     pub type Challenge = BinomialExtensionField<Val, 4>;
@@ -198,51 +196,32 @@ pub mod koala_bear_poseidon2 {
         Perm::new(external_round_constants, internal_round_constants)
     }
 
+    /// The FRI config with the given security profile.
     #[must_use]
-    /// This targets by default 100 bits of security.
-    pub fn default_fri_config() -> FriConfig<ChallengeMmcs> {
+    pub fn fri_config_with_profile(profile: SecurityProfile) -> FriConfig<ChallengeMmcs> {
         let perm = my_perm();
         let hash = MyHash::new(perm.clone());
         let compress = MyCompress::new(perm.clone());
         let challenge_mmcs = ChallengeMmcs::new(ValMmcs::new(hash, compress));
-        let num_queries = match std::env::var("FRI_QUERIES") {
-            Ok(value) => value.parse().unwrap(),
-            Err(_) => 84,
-        };
-        FriConfig { log_blowup: 1, num_queries, proof_of_work_bits: 16, mmcs: challenge_mmcs }
+        profile.fri_config(challenge_mmcs)
     }
 
     #[must_use]
-    /// This targets by default 100 bits of security.
-    pub fn compressed_fri_config() -> FriConfig<ChallengeMmcs> {
-        let perm = my_perm();
-        let hash = MyHash::new(perm.clone());
-        let compress = MyCompress::new(perm.clone());
-        let challenge_mmcs = ChallengeMmcs::new(ValMmcs::new(hash, compress));
-        let num_queries = match std::env::var("FRI_QUERIES") {
-            Ok(value) => value.parse().unwrap(),
-            Err(_) => 42,
-        };
-        FriConfig { log_blowup: 2, num_queries, proof_of_work_bits: 16, mmcs: challenge_mmcs }
+    /// This targets 100 bits of security.
+    pub fn default_fri_config() -> FriConfig<ChallengeMmcs> {
+        fri_config_with_profile(SecurityProfile::DEFAULT)
     }
 
     #[must_use]
-    /// This targets by default 100 bits of security.
-    pub fn ultra_compressed_fri_config() -> FriConfig<ChallengeMmcs> {
-        let perm = my_perm();
-        let hash = MyHash::new(perm.clone());
-        let compress = MyCompress::new(perm.clone());
-        let challenge_mmcs = ChallengeMmcs::new(ValMmcs::new(hash, compress));
-        let num_queries = match std::env::var("FRI_QUERIES") {
-            Ok(value) => value.parse().unwrap(),
-            Err(_) => 28,
-        };
-        FriConfig { log_blowup: 3, num_queries, proof_of_work_bits: 16, mmcs: challenge_mmcs }
+    /// This targets 100 bits of security.
+    pub fn compressed_fri_config() -> FriConfig<ChallengeMmcs> {
+        fri_config_with_profile(SecurityProfile::COMPRESSED)
     }
 
-    enum KoalaBearPoseidon2Type {
-        Default,
-        Compressed,
+    #[must_use]
+    /// This targets 100 bits of security.
+    pub fn ultra_compressed_fri_config() -> FriConfig<ChallengeMmcs> {
+        fri_config_with_profile(SecurityProfile::ULTRA_COMPRESSED)
     }
 
     #[derive(Deserialize)]
@@ -250,53 +229,63 @@ pub mod koala_bear_poseidon2 {
     pub struct KoalaBearPoseidon2 {
         pub perm: Perm,
         pcs: Pcs,
-        config_type: KoalaBearPoseidon2Type,
+        hiding: bool,
     }
 
     impl KoalaBearPoseidon2 {
         #[must_use]
         pub fn new() -> Self {
-            let perm = my_perm();
-            let hash = MyHash::new(perm.clone());
-            let compress = MyCompress::new(perm.clone());
-            let val_mmcs = ValMmcs::new(hash, compress);
-            let dft = Dft::default();
-            let fri_config = default_fri_config();
-            let pcs = Pcs::new(dft, val_mmcs, fri_config);
-            Self { pcs, perm, config_type: KoalaBearPoseidon2Type::Default }
+            Self::with_profile(SecurityProfile::DEFAULT)
         }
 
         #[must_use]
         pub fn compressed() -> Self {
-            let perm = my_perm();
-            let hash = MyHash::new(perm.clone());
-            let compress = MyCompress::new(perm.clone());
-            let val_mmcs = ValMmcs::new(hash, compress);
-            let dft = Dft::default();
-            let fri_config = compressed_fri_config();
-            let pcs = Pcs::new(dft, val_mmcs, fri_config);
-            Self { pcs, perm, config_type: KoalaBearPoseidon2Type::Compressed }
+            Self::with_profile(SecurityProfile::COMPRESSED)
         }
 
         #[must_use]
         pub fn ultra_compressed() -> Self {
+            Self::with_profile(SecurityProfile::ULTRA_COMPRESSED)
+        }
+
+        /// A configuration whose proofs are zero-knowledge, see [`crate::ZkConfig`].
+        ///
+        /// The quotient of the extended traces has twice as many chunks, so this uses the blowup
+        /// of the compressed configuration.
+        #[must_use]
+        pub fn hiding() -> Self {
+            Self::hiding_with_profile(SecurityProfile::COMPRESSED)
+        }
+
+        /// A configuration with the given security profile.
+        #[must_use]
+        pub fn with_profile(profile: SecurityProfile) -> Self {
+            Self::build(profile, false)
+        }
+
+        /// A zero-knowledge configuration with the given security profile, whose blowup must be
+        /// at least 4 to fit the quotient chunks.
+        #[must_use]
+        pub fn hiding_with_profile(profile: SecurityProfile) -> Self {
+            assert!(profile.log_blowup >= 2, "hiding proofs require a log blowup of at least 2");
+            Self::build(profile, true)
+        }
+
+        fn build(profile: SecurityProfile, hiding: bool) -> Self {
             let perm = my_perm();
             let hash = MyHash::new(perm.clone());
             let compress = MyCompress::new(perm.clone());
             let val_mmcs = ValMmcs::new(hash, compress);
             let dft = Dft::default();
-            let fri_config = ultra_compressed_fri_config();
+            let fri_config = fri_config_with_profile(profile);
             let pcs = Pcs::new(dft, val_mmcs, fri_config);
-            Self { pcs, perm, config_type: KoalaBearPoseidon2Type::Compressed }
+            Self { pcs, perm, hiding }
         }
     }
 
     impl Clone for KoalaBearPoseidon2 {
         fn clone(&self) -> Self {
-            match self.config_type {
-                KoalaBearPoseidon2Type::Default => Self::new(),
-                KoalaBearPoseidon2Type::Compressed => Self::compressed(),
-            }
+            Self::build(self.security_profile(), self.hiding)
         }
     }
 
@@ -336,6 +325,14 @@ pub mod koala_bear_poseidon2 {
         fn challenger(&self) -> Self::Challenger {
             Challenger::new(self.perm.clone())
         }
+
+        fn security_profile(&self) -> SecurityProfile {
+            SecurityProfile::of(self.pcs.fri_config())
+        }
+
+        fn zk(&self) -> Option<ZkConfig> {
+            self.hiding.then(|| ZkConfig::new(self.pcs.fri_config().num_queries, Challenge::D))
+        }
     }
 
     impl ZeroCommitment<KoalaBearPoseidon2> for Pcs {
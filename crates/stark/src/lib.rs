@@ -5,6 +5,7 @@
 extern crate alloc;
 
 pub mod air;
+mod analysis;
 mod chip;
 mod config;
 mod debug;
@@ -18,6 +19,7 @@ mod proof;
 mod prover;
 mod quotient;
 mod record;
+mod security;
 pub mod septic_curve;
 pub mod septic_digest;
 pub mod septic_extension;
@@ -28,8 +30,10 @@ mod types;
 mod verifier;
 mod word;
 mod zerofier_coset;
+mod zk;
 
 pub use air::*;
+pub use analysis::*;
 pub use chip::*;
 pub use config::*;
 pub use debug::*;
@@ -43,7 +47,9 @@ pub use proof::*;
 pub use prover::*;
 pub use quotient::*;
 pub use record::*;
+pub use security::*;
 pub use types::*;
 pub use verifier::*;
 pub use word::*;
 pub use zerofier_coset::*;
+pub use zk::*;
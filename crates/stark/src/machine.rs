@@ -23,7 +23,7 @@ use crate::{
     septic_curve::SepticCurve,
     septic_digest::SepticDigest,
     septic_extension::SepticExtension,
-    DebugConstraintBuilder, ShardProof, VerifierConstraintFolder,
+    DebugConstraintBuilder, SecurityProfile, ShardProof, VerifierConstraintFolder,
 };
 
 use super::{
@@ -101,6 +101,9 @@ pub struct StarkVerifyingKey<SC: StarkGenericConfig> {
     pub chip_information: Vec<(String, Dom<SC>, Dimensions)>,
     /// The chip ordering.
     pub chip_ordering: HashMap<String, usize>,
+    /// The security profile of the proofs. It is not part of the hash of the key, as the
+    /// recursion verifier fixes it in the program.
+    pub security_profile: SecurityProfile,
 }
 
 impl<SC: StarkGenericConfig> StarkVerifyingKey<SC> {
@@ -434,6 +437,7 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>> + Air<SymbolicAirBuilder<Val
                 initial_global_cumulative_sum,
                 chip_information,
                 chip_ordering,
+                security_profile: self.config.security_profile(),
             },
         )
     }
@@ -549,6 +553,7 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>> + Air<SymbolicAirBuilder<Val
                 initial_global_cumulative_sum,
                 chip_information,
                 chip_ordering,
+                security_profile: self.config.security_profile(),
             },
         )
     }
@@ -614,6 +619,19 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>> + Air<SymbolicAirBuilder<Val
             return Err(MachineVerificationError::EmptyProof);
         }
 
+        // Verify the proofs were made with the security profile of the machine.
+        let security_profile = self.config.security_profile();
+        for actual in once(&vk.security_profile)
+            .chain(proof.shard_proofs.iter().map(|shard_proof| &shard_proof.security_profile))
+        {
+            if *actual != security_profile {
+                return Err(MachineVerificationError::SecurityProfileMismatch(
+                    security_profile,
+                    *actual,
+                ));
+            }
+        }
+
         tracing::debug_span!("verify shard proofs").in_scope(|| {
             for (i, shard_proof) in proof.shard_proofs.iter().enumerate() {
                 tracing::debug_span!("verifying shard", shard = i).in_scope(|| {
@@ -681,6 +699,8 @@ pub enum MachineVerificationError<SC: StarkGenericConfig> {
     CpuLogDegreeTooLarge(usize),
     /// The verification key is not allowed.
     InvalidVerificationKey,
+    /// The security profile of the proof differs from the one of the machine.
+    SecurityProfileMismatch(SecurityProfile, SecurityProfile),
 }
 
 impl<SC: StarkGenericConfig> Debug for MachineVerificationError<SC> {
@@ -723,6 +743,9 @@ impl<SC: StarkGenericConfig> Debug for MachineVerificationError<SC> {
             MachineVerificationError::InvalidVerificationKey => {
                 write!(f, "Invalid verification key")
             }
+            MachineVerificationError::SecurityProfileMismatch(expected, actual) => {
+                write!(f, "Security profile mismatch: expected {}, got {}", expected, actual)
+            }
         }
     }
 }
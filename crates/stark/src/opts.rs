@@ -3,6 +3,8 @@ use std::env;
 use serde::{Deserialize, Serialize};
 use sysinfo::System;
 
+use crate::SecurityProfile;
+
 const MAX_SHARD_SIZE: usize = 1 << 21;
 const RECURSION_MAX_SHARD_SIZE: usize = 1 << 21;
 const MAX_SHARD_BATCH_SIZE: usize = 8;
@@ -13,6 +15,9 @@ const DEFAULT_RECORDS_AND_TRACES_CHANNEL_CAPACITY: usize = 1;
 /// The threshold for splitting deferred events.
 pub const MAX_DEFERRED_SPLIT_THRESHOLD: usize = 1 << 15;
 
+/// The number of syscall codes reserved for custom precompiles.
+pub const NUM_CUSTOM_SYSCALLS: usize = 16;
+
 /// Options to configure the Ziren prover for core and recursive proofs.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ZKMProverOpts {
@@ -20,11 +25,18 @@ pub struct ZKMProverOpts {
     pub core_opts: ZKMCoreOpts,
     /// Options for the recursion prover.
     pub recursion_opts: ZKMCoreOpts,
+    /// The minimum security the proofs must reach. The prover fails if its configuration is less
+    /// secure than this profile.
+    pub security_profile: SecurityProfile,
 }
 
 impl Default for ZKMProverOpts {
     fn default() -> Self {
-        Self { core_opts: ZKMCoreOpts::default(), recursion_opts: ZKMCoreOpts::recursion() }
+        Self {
+            core_opts: ZKMCoreOpts::default(),
+            recursion_opts: ZKMCoreOpts::recursion(),
+            security_profile: SecurityProfile::default(),
+        }
     }
 }
 
@@ -68,6 +80,9 @@ impl ZKMProverOpts {
         opts.core_opts.split_opts.keccak /= divisor;
         opts.core_opts.split_opts.sha_extend /= divisor;
         opts.core_opts.split_opts.sha_compress /= divisor;
+        opts.core_opts.split_opts.blake2s /= divisor;
+        opts.core_opts.split_opts.blake3 /= divisor;
+        opts.core_opts.split_opts.blake2b /= divisor;
         opts.core_opts.split_opts.memory /= divisor;
 
         opts.recursion_opts.shard_batch_size = 2;
@@ -175,6 +190,11 @@ impl Default for ZKMCoreOpts {
         opts.split_opts.keccak /= divisor;
         opts.split_opts.sha_extend /= divisor;
         opts.split_opts.sha_compress /= divisor;
+        opts.split_opts.blake2s /= divisor;
+        opts.split_opts.blake3 /= divisor;
+        opts.split_opts.blake2b /= divisor;
+        opts.split_opts.weierstrass_mul = (opts.split_opts.weierstrass_mul / divisor).max(1);
+        opts.split_opts.bigint_mulmod = (opts.split_opts.bigint_mulmod / divisor).max(1);
         opts.split_opts.memory /= divisor;
 
         opts
@@ -241,11 +261,24 @@ pub struct SplitOpts {
     pub sha_extend: usize,
     /// The threshold for sha compress events.
     pub sha_compress: usize,
+    /// The threshold for blake2s rounds events.
+    pub blake2s: usize,
+    /// The threshold for blake3 rounds events.
+    pub blake3: usize,
+    /// The threshold for blake2b rounds events.
+    pub blake2b: usize,
+    /// The threshold for weierstrass scalar multiplication events.
+    pub weierstrass_mul: usize,
+    /// The threshold for variable-width modular multiplication events.
+    pub bigint_mulmod: usize,
     /// The threshold for memory events.
     pub memory: usize,
     /// The threshold for combining the memory init/finalize events in to the current shard in
     /// terms of cycles.
     pub combine_memory_threshold: usize,
+    /// The thresholds for the events of the custom precompiles, by index. A zero threshold falls
+    /// back to `deferred`.
+    pub custom: [usize; NUM_CUSTOM_SYSCALLS],
 }
 
 impl SplitOpts {
@@ -257,8 +290,31 @@ impl SplitOpts {
             keccak: 8 * deferred_split_threshold / 24,
             sha_extend: 32 * deferred_split_threshold / 48,
             sha_compress: 32 * deferred_split_threshold / 80,
+            blake2s: 16 * deferred_split_threshold / 10,
+            blake3: 16 * deferred_split_threshold / 7,
+            blake2b: 8 * deferred_split_threshold / 12,
+            weierstrass_mul: (deferred_split_threshold / 384).max(1),
+            bigint_mulmod: (deferred_split_threshold / 16).max(1),
             memory: 64 * deferred_split_threshold,
             combine_memory_threshold: 1 << 17,
+            custom: [0; NUM_CUSTOM_SYSCALLS],
+        }
+    }
+
+    /// Sets the threshold for the events of the custom precompile with the given index, whose
+    /// chip has `rows_per_event` rows per event.
+    #[must_use]
+    pub fn with_custom_rows_per_event(mut self, index: usize, rows_per_event: usize) -> Self {
+        self.custom[index] = (self.deferred / rows_per_event).max(1);
+        self
+    }
+
+    /// The threshold for the events of the custom precompile with the given index.
+    #[must_use]
+    pub fn custom_threshold(&self, index: usize) -> usize {
+        match self.custom[index] {
+            0 => self.deferred,
+            threshold => threshold,
         }
     }
 }
@@ -301,4 +357,13 @@ mod tests {
         let opts = ZKMProverOpts::auto();
         println!("auto: {:?}", opts.core_opts);
     }
+
+    #[test]
+    fn test_custom_threshold() {
+        let opts = SplitOpts::new(1 << 12).with_custom_rows_per_event(3, 16);
+        assert_eq!(opts.custom_threshold(3), 1 << 8);
+        assert_eq!(opts.custom_threshold(4), 1 << 12);
+        let opts = SplitOpts::new(8).with_custom_rows_per_event(0, 16);
+        assert_eq!(opts.custom_threshold(0), 1);
+    }
 }
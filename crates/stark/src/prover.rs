@@ -10,20 +10,23 @@ use crate::{air::LookupScope, AirOpenedValues, ChipOpenedValues, ShardOpenedValu
 use p3_air::Air;
 use p3_challenger::{CanObserve, FieldChallenger};
 use p3_commit::{Pcs, PolynomialSpace};
-use p3_field::{FieldAlgebra, FieldExtensionAlgebra, PrimeField32};
+use p3_field::{FieldAlgebra, FieldExtensionAlgebra, PrimeField32, TwoAdicField};
 use p3_matrix::{dense::RowMajorMatrix, Matrix};
 use p3_maybe_rayon::prelude::*;
 use p3_uni_stark::SymbolicAirBuilder;
 use p3_util::log2_strict_usize;
+use rand::distributions::{Distribution, Standard};
 
 use super::{
     quotient_values, Com, OpeningProof, StarkGenericConfig, StarkMachine, StarkProvingKey, Val,
     VerifierConstraintFolder,
 };
 use crate::{
-    air::MachineAir, lookup::LookupBuilder, opts::ZKMCoreOpts, record::MachineRecord, Challenger,
+    air::MachineAir, coset_lde, lookup::LookupBuilder, opts::ZKMCoreOpts, randomize_trace,
+    record::MachineRecord, split_and_mask_quotient, truncate_columns, Challenger,
     DebugConstraintBuilder, MachineChip, MachineProof, PackedChallenge, PcsProverData,
     ProverConstraintFolder, ShardCommitment, ShardMainData, ShardProof, StarkVerifyingKey,
+    ZkConfig,
 };
 
 /// An algorithmic & hardware independent prover implementation for any [`MachineAir`].
@@ -216,7 +219,8 @@ where
         + for<'a> Air<VerifierConstraintFolder<'a, SC>>
         + for<'a> Air<SymbolicAirBuilder<Val<SC>>>,
     A::Record: MachineRecord<Config = ZKMCoreOpts>,
-    SC::Val: PrimeField32,
+    SC::Val: PrimeField32 + TwoAdicField,
+    Standard: Distribution<Val<SC>>,
     Com<SC>: Send + Sync,
     PcsProverData<SC>: Send + Sync + Serialize + DeserializeOwned,
     OpeningProof<SC>: Send + Sync,
@@ -264,12 +268,20 @@ where
         named_traces.sort_by_key(|(name, trace)| (Reverse(trace.height()), name.clone()));
 
         let pcs = self.config().pcs();
+        let zk = self.config().zk();
 
+        // In the zero-knowledge mode, the traces are randomized on an extended domain.
         let domains_and_traces = named_traces
             .iter()
             .map(|(_, trace)| {
-                let domain = pcs.natural_domain_for_degree(trace.height());
-                (domain, trace.to_owned())
+                let trace = match zk {
+                    Some(zk) => {
+                        let log_extension = zk.log_extension(log2_strict_usize(trace.height()));
+                        randomize_trace(trace, log_extension, zk.num_random_columns)
+                    }
+                    None => trace.to_owned(),
+                };
+                (pcs.natural_domain_for_degree(trace.height()), trace)
             })
             .collect::<Vec<_>>();
 
@@ -311,8 +323,19 @@ where
         let log_degrees =
             degrees.iter().map(|degree| log2_strict_usize(*degree)).collect::<Vec<_>>();
 
-        let log_quotient_degrees =
-            chips.iter().map(|chip| chip.log_quotient_degree()).collect::<Vec<_>>();
+        let zk = config.zk();
+        let log_extensions = log_degrees
+            .iter()
+            .map(|log_degree| zk.map_or(0, |zk| zk.log_extension(*log_degree)))
+            .collect::<Vec<_>>();
+
+        let log_quotient_degrees = chips
+            .iter()
+            .map(|chip| match zk {
+                Some(_) => ZkConfig::log_quotient_degree(chip.log_quotient_degree()),
+                None => chip.log_quotient_degree(),
+            })
+            .collect::<Vec<_>>();
 
         let pcs = config.pcs();
         let trace_domains =
@@ -389,9 +412,16 @@ where
                 permutation_traces
                     .into_iter()
                     .zip(trace_domains.iter())
-                    .map(|(perm_trace, domain)| {
+                    .zip(log_extensions.iter())
+                    .map(|((perm_trace, domain), log_extension)| {
                         let trace = perm_trace.flatten_to_base();
-                        (*domain, trace.clone())
+                        match zk {
+                            Some(_) => {
+                                let trace = randomize_trace(&trace, *log_extension, 0);
+                                (pcs.natural_domain_for_degree(trace.height()), trace)
+                            }
+                            None => (*domain, trace),
+                        }
                     })
                     .collect::<Vec<_>>()
             });
@@ -416,9 +446,11 @@ where
         let quotient_domains = trace_domains
             .iter()
             .zip_eq(log_degrees.iter())
+            .zip_eq(log_extensions.iter())
             .zip_eq(log_quotient_degrees.iter())
-            .map(|((domain, log_degree), log_quotient_degree)| {
-                domain.create_disjoint_domain(1 << (log_degree + log_quotient_degree))
+            .map(|(((domain, log_degree), log_extension), log_quotient_degree)| {
+                domain
+                    .create_disjoint_domain(1 << (log_degree + log_extension + log_quotient_degree))
             })
             .collect::<Vec<_>>();
 
@@ -432,14 +464,27 @@ where
                 .map(|(i, quotient_domain)| {
                     tracing::debug_span!(parent: &parent_span, "compute quotient values for domain")
                         .in_scope(|| {
+                            // The preprocessed traces are not extended in the zero-knowledge mode,
+                            // so their committed evaluations do not cover the quotient domain.
                             let preprocessed_trace_on_quotient_domains =
-                                pk.chip_ordering.get(&chips[i].name()).map(|&index| {
-                                    pcs.get_evaluations_on_domain(&pk.data, index, *quotient_domain)
-                                        .to_row_major_matrix()
+                                pk.chip_ordering.get(&chips[i].name()).map(|&index| match zk {
+                                    Some(_) => coset_lde(
+                                        pk.traces[index].clone(),
+                                        log2_strict_usize(quotient_domain.size()) - log_degrees[i],
+                                        quotient_domain.first_point(),
+                                    ),
+                                    None => pcs
+                                        .get_evaluations_on_domain(
+                                            &pk.data,
+                                            index,
+                                            *quotient_domain,
+                                        )
+                                        .to_row_major_matrix(),
                                 });
-                            let main_trace_on_quotient_domains = pcs
-                                .get_evaluations_on_domain(&data.main_data, i, *quotient_domain)
-                                .to_row_major_matrix();
+                            let main_trace_on_quotient_domains =
+                                pcs.get_evaluations_on_domain(&data.main_data, i, *quotient_domain);
+                            let main_trace_on_quotient_domains =
+                                truncate_columns(&main_trace_on_quotient_domains, chips[i].width());
                             let permutation_trace_on_quotient_domains = pcs
                                 .get_evaluations_on_domain(&permutation_data, i, *quotient_domain)
                                 .to_row_major_matrix();
@@ -481,16 +526,33 @@ where
             .flat_map(|((quotient_domain, quotient_values), log_quotient_degree)| {
                 let quotient_degree = 1 << *log_quotient_degree;
                 let quotient_flat = RowMajorMatrix::new_col(quotient_values).flatten_to_base();
-                let quotient_chunks = quotient_domain.split_evals(quotient_degree, quotient_flat);
-                let qc_domains = quotient_domain.split_domains(quotient_degree);
-                qc_domains.into_iter().zip_eq(quotient_chunks)
+                match zk {
+                    Some(zk) => {
+                        let quotient_chunks = split_and_mask_quotient(
+                            quotient_flat,
+                            quotient_domain.first_point(),
+                            quotient_degree,
+                            zk.num_random_columns,
+                        );
+                        quotient_chunks
+                            .into_iter()
+                            .map(|chunk| (pcs.natural_domain_for_degree(chunk.height()), chunk))
+                            .collect::<Vec<_>>()
+                    }
+                    None => {
+                        let quotient_chunks =
+                            quotient_domain.split_evals(quotient_degree, quotient_flat);
+                        let qc_domains = quotient_domain.split_domains(quotient_degree);
+                        qc_domains.into_iter().zip_eq(quotient_chunks).collect::<Vec<_>>()
+                    }
+                }
             })
             .collect::<Vec<_>>();
 
         let num_quotient_chunks = quotient_domains_and_chunks.len();
         assert_eq!(
             num_quotient_chunks,
-            chips.iter().map(|c| 1 << c.log_quotient_degree()).sum::<usize>()
+            log_quotient_degrees.iter().map(|log_quotient_degree| 1 << log_quotient_degree).sum()
         );
 
         let (quotient_commit, quotient_data) = tracing::debug_span!("commit to quotient traces")
@@ -649,6 +711,7 @@ where
             opening_proof,
             chip_ordering: data.chip_ordering,
             public_values: data.public_values,
+            security_profile: config.security_profile(),
         })
     }
 
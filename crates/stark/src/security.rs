@@ -0,0 +1,142 @@
+use core::fmt::{Display, Formatter};
+
+use p3_fri::FriConfig;
+use serde::{Deserialize, Serialize};
+
+/// The parameters of FRI which determine the security level of a proof.
+///
+/// A FRI query with a rate of `2^-log_blowup` rejects a polynomial far from a low-degree one with
+/// probability about `1 - 2^-log_blowup` under the usual conjecture on the soundness of FRI, and
+/// `1 - 2^(-log_blowup / 2)` up to the Johnson bound. The grinding adds `proof_of_work_bits` to
+/// both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SecurityProfile {
+    /// The log2 of the inverse of the rate of the Reed-Solomon code.
+    pub log_blowup: usize,
+    /// The number of FRI queries.
+    pub num_queries: usize,
+    /// The number of bits of proof of work before the queries are sampled.
+    pub proof_of_work_bits: usize,
+}
+
+impl SecurityProfile {
+    /// The profile of the core and compress proofs, targeting 100 bits of conjectured security.
+    pub const DEFAULT: Self = Self::new(1, 84, 16);
+
+    /// The profile of the shrink proofs, with the same security at a larger blowup.
+    pub const COMPRESSED: Self = Self::new(2, 42, 16);
+
+    /// The same security as [`Self::COMPRESSED`] at an even larger blowup.
+    pub const ULTRA_COMPRESSED: Self = Self::new(3, 28, 16);
+
+    /// The profile of the wrap proofs verified by the Groth16 and Plonk circuits.
+    pub const OUTER: Self = Self::new(4, 21, 16);
+
+    /// A profile with the given FRI parameters.
+    #[must_use]
+    pub const fn new(log_blowup: usize, num_queries: usize, proof_of_work_bits: usize) -> Self {
+        Self { log_blowup, num_queries, proof_of_work_bits }
+    }
+
+    /// The profile with the fewest queries reaching `bits` of conjectured security.
+    #[must_use]
+    pub const fn conjectured(bits: usize, log_blowup: usize, proof_of_work_bits: usize) -> Self {
+        let query_bits = bits.saturating_sub(proof_of_work_bits);
+        Self::new(log_blowup, query_bits.div_ceil(log_blowup), proof_of_work_bits)
+    }
+
+    /// The profile with the fewest queries reaching `bits` of proven security.
+    #[must_use]
+    pub const fn proven(bits: usize, log_blowup: usize, proof_of_work_bits: usize) -> Self {
+        let query_bits = bits.saturating_sub(proof_of_work_bits);
+        Self::new(log_blowup, (2 * query_bits).div_ceil(log_blowup), proof_of_work_bits)
+    }
+
+    /// The profile with the given blowup and at least the same security as this one.
+    #[must_use]
+    pub const fn with_log_blowup(self, log_blowup: usize) -> Self {
+        let num_queries = (self.num_queries * self.log_blowup).div_ceil(log_blowup);
+        Self::new(log_blowup, num_queries, self.proof_of_work_bits)
+    }
+
+    /// The estimated security in bits, under the conjecture on the soundness of FRI.
+    ///
+    /// This does not account for the size of the challenge field, which bounds the security too.
+    #[must_use]
+    pub const fn conjectured_bits(&self) -> usize {
+        self.log_blowup * self.num_queries + self.proof_of_work_bits
+    }
+
+    /// The estimated security in bits, proven up to the Johnson bound.
+    ///
+    /// This does not account for the size of the challenge field, which bounds the security too.
+    #[must_use]
+    pub const fn proven_bits(&self) -> usize {
+        self.log_blowup * self.num_queries / 2 + self.proof_of_work_bits
+    }
+
+    /// Whether this profile is at least as secure as `other`, under both estimates.
+    #[must_use]
+    pub const fn is_at_least(&self, other: &Self) -> bool {
+        self.conjectured_bits() >= other.conjectured_bits()
+            && self.proven_bits() >= other.proven_bits()
+    }
+
+    /// The FRI configuration with this profile.
+    #[must_use]
+    pub fn fri_config<M>(&self, mmcs: M) -> FriConfig<M> {
+        FriConfig {
+            log_blowup: self.log_blowup,
+            num_queries: self.num_queries,
+            proof_of_work_bits: self.proof_of_work_bits,
+            mmcs,
+        }
+    }
+
+    /// The profile of a FRI configuration.
+    #[must_use]
+    pub fn of<M>(config: &FriConfig<M>) -> Self {
+        Self::new(config.log_blowup, config.num_queries, config.proof_of_work_bits)
+    }
+}
+
+impl Default for SecurityProfile {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl Display for SecurityProfile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "log_blowup = {}, queries = {}, grinding = {} bits (~{} bits conjectured, ~{} bits \
+             proven)",
+            self.log_blowup,
+            self.num_queries,
+            self.proof_of_work_bits,
+            self.conjectured_bits(),
+            self.proven_bits()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_security_profiles() {
+        assert_eq!(SecurityProfile::DEFAULT.conjectured_bits(), 100);
+        assert_eq!(SecurityProfile::DEFAULT.proven_bits(), 58);
+        assert_eq!(SecurityProfile::conjectured(100, 1, 16), SecurityProfile::DEFAULT);
+        assert_eq!(SecurityProfile::proven(100, 2, 16).proven_bits(), 100);
+        for profile in
+            [SecurityProfile::COMPRESSED, SecurityProfile::ULTRA_COMPRESSED, SecurityProfile::OUTER]
+        {
+            assert_eq!(SecurityProfile::DEFAULT.with_log_blowup(profile.log_blowup), profile);
+            assert!(profile.is_at_least(&SecurityProfile::DEFAULT));
+        }
+        assert!(!SecurityProfile::new(1, 1, 16).is_at_least(&SecurityProfile::DEFAULT));
+    }
+}
@@ -7,7 +7,7 @@ use itertools::Itertools;
 use p3_matrix::{dense::RowMajorMatrixView, stack::VerticalPair};
 use serde::{Deserialize, Serialize};
 
-use super::{Challenge, Com, OpeningProof, StarkGenericConfig, Val};
+use super::{Challenge, Com, OpeningProof, SecurityProfile, StarkGenericConfig, Val};
 use crate::septic_digest::SepticDigest;
 use crate::shape::OrderedShape;
 
@@ -80,6 +80,8 @@ pub struct ShardProof<SC: StarkGenericConfig> {
     pub opening_proof: OpeningProof<SC>,
     pub chip_ordering: HashMap<String, usize>,
     pub public_values: Vec<Val<SC>>,
+    /// The security profile of the proof.
+    pub security_profile: SecurityProfile,
 }
 
 impl<SC: StarkGenericConfig> Debug for ShardProof<SC> {
@@ -10,6 +10,7 @@ use p3_air::{Air, BaseAir};
 use p3_challenger::{CanObserve, FieldChallenger};
 use p3_commit::{LagrangeSelectors, Pcs, PolynomialSpace};
 use p3_field::{Field, FieldAlgebra, FieldExtensionAlgebra};
+use p3_matrix::{dense::RowMajorMatrixView, stack::VerticalPair};
 
 use super::{
     folder::VerifierConstraintFolder,
@@ -18,7 +19,7 @@ use super::{
 };
 use crate::{
     air::{LookupScope, MachineAir},
-    MachineChip,
+    MachineChip, ZkConfig,
 };
 
 /// A verifier for a collection of air chips.
@@ -73,14 +74,35 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> Verifier<SC, A> {
 
         let log_degrees = opened_values.chips.iter().map(|val| val.log_degree).collect::<Vec<_>>();
 
-        let log_quotient_degrees =
-            chips.iter().map(|chip| chip.log_quotient_degree()).collect::<Vec<_>>();
+        let zk = config.zk();
+        let log_extensions = log_degrees
+            .iter()
+            .map(|log_degree| zk.map_or(0, |zk| zk.log_extension(*log_degree)))
+            .collect::<Vec<_>>();
+
+        let log_quotient_degrees = chips
+            .iter()
+            .map(|chip| match zk {
+                Some(_) => ZkConfig::log_quotient_degree(chip.log_quotient_degree()),
+                None => chip.log_quotient_degree(),
+            })
+            .collect::<Vec<_>>();
 
         let trace_domains = log_degrees
             .iter()
             .map(|log_degree| pcs.natural_domain_for_degree(1 << log_degree))
             .collect::<Vec<_>>();
 
+        // The domains the main and permutation traces are committed over, which are extended in
+        // the zero-knowledge mode.
+        let commit_domains = log_degrees
+            .iter()
+            .zip_eq(log_extensions.iter())
+            .map(|(log_degree, log_extension)| {
+                pcs.natural_domain_for_degree(1 << (log_degree + log_extension))
+            })
+            .collect::<Vec<_>>();
+
         let ShardCommitment { main_commit, permutation_commit, quotient_commit } = commitment;
 
         challenger.observe(main_commit.clone());
@@ -140,29 +162,31 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> Verifier<SC, A> {
 
         let main_domains_points_and_opens = trace_domains
             .iter()
+            .zip_eq(commit_domains.iter())
             .zip_eq(opened_values.chips.iter())
             .zip_eq(chips.iter())
-            .map(|((domain, values), chip)| {
+            .map(|(((domain, commit_domain), values), chip)| {
                 if !chip.local_only() {
                     (
-                        *domain,
+                        *commit_domain,
                         vec![
                             (zeta, values.main.local.clone()),
                             (domain.next_point(zeta).unwrap(), values.main.next.clone()),
                         ],
                     )
                 } else {
-                    (*domain, vec![(zeta, values.main.local.clone())])
+                    (*commit_domain, vec![(zeta, values.main.local.clone())])
                 }
             })
             .collect::<Vec<_>>();
 
         let perm_domains_points_and_opens = trace_domains
             .iter()
+            .zip_eq(commit_domains.iter())
             .zip_eq(opened_values.chips.iter())
-            .map(|(domain, values)| {
+            .map(|((domain, commit_domain), values)| {
                 (
-                    *domain,
+                    *commit_domain,
                     vec![
                         (zeta, values.permutation.local.clone()),
                         (domain.next_point(zeta).unwrap(), values.permutation.next.clone()),
@@ -174,12 +198,21 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> Verifier<SC, A> {
         let quotient_chunk_domains = trace_domains
             .iter()
             .zip_eq(log_degrees)
+            .zip_eq(log_extensions)
             .zip_eq(log_quotient_degrees)
-            .map(|((domain, log_degree), log_quotient_degree)| {
+            .map(|(((domain, log_degree), log_extension), log_quotient_degree)| {
                 let quotient_degree = 1 << log_quotient_degree;
-                let quotient_domain =
-                    domain.create_disjoint_domain(1 << (log_degree + log_quotient_degree));
-                quotient_domain.split_domains(quotient_degree)
+                match zk {
+                    // The masked chunks are committed over a subgroup twice their size.
+                    Some(_) => (0..quotient_degree)
+                        .map(|_| pcs.natural_domain_for_degree(2 << (log_degree + log_extension)))
+                        .collect::<Vec<_>>(),
+                    None => {
+                        let quotient_domain =
+                            domain.create_disjoint_domain(1 << (log_degree + log_quotient_degree));
+                        quotient_domain.split_domains(quotient_degree)
+                    }
+                }
             })
             .collect::<Vec<_>>();
 
@@ -216,7 +249,7 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> Verifier<SC, A> {
             izip!(chips.iter(), trace_domains, quotient_chunk_domains, opened_values.chips.iter(),)
         {
             // Verify the shape of the opening arguments matches the expected values.
-            Self::verify_opening_shape(chip, values)
+            Self::verify_opening_shape(chip, values, zk)
                 .map_err(|e| VerificationError::OpeningShapeError(chip.name(), e))?;
             // Verify the constraint evaluation.
             Self::verify_constraints(
@@ -224,6 +257,7 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> Verifier<SC, A> {
                 values,
                 trace_domain,
                 qc_domains,
+                zk,
                 zeta,
                 alpha,
                 &permutation_challenges,
@@ -248,7 +282,18 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> Verifier<SC, A> {
     fn verify_opening_shape(
         chip: &MachineChip<SC, A>,
         opening: &ChipOpenedValues<Val<SC>, SC::Challenge>,
+        zk: Option<ZkConfig>,
     ) -> Result<(), OpeningShapeError> {
+        // In the zero-knowledge mode, the main traces and the quotient chunks are opened with
+        // their random columns.
+        let num_random_columns = zk.map_or(0, |zk| zk.num_random_columns);
+        let main_width = chip.width() + num_random_columns;
+        let quotient_width = match zk {
+            Some(_) => 1 << ZkConfig::log_quotient_degree(chip.log_quotient_degree()),
+            None => chip.quotient_width(),
+        };
+        let quotient_chunk_size = SC::Challenge::D + num_random_columns;
+
         // Verify that the preprocessed width matches the expected value for the chip.
         if opening.preprocessed.local.len() != chip.preprocessed_width() {
             return Err(OpeningShapeError::PreprocessedWidthMismatch(
@@ -264,17 +309,11 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> Verifier<SC, A> {
         }
 
         // Verify that the main width matches the expected value for the chip.
-        if opening.main.local.len() != chip.width() {
-            return Err(OpeningShapeError::MainWidthMismatch(
-                chip.width(),
-                opening.main.local.len(),
-            ));
+        if opening.main.local.len() != main_width {
+            return Err(OpeningShapeError::MainWidthMismatch(main_width, opening.main.local.len()));
         }
-        if opening.main.next.len() != chip.width() {
-            return Err(OpeningShapeError::MainWidthMismatch(
-                chip.width(),
-                opening.main.next.len(),
-            ));
+        if opening.main.next.len() != main_width {
+            return Err(OpeningShapeError::MainWidthMismatch(main_width, opening.main.next.len()));
         }
 
         // Verify that the permutation width matches the expected value for the chip.
@@ -291,18 +330,18 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> Verifier<SC, A> {
             ));
         }
         // Verift that the number of quotient chunks matches the expected value for the chip.
-        if opening.quotient.len() != chip.quotient_width() {
+        if opening.quotient.len() != quotient_width {
             return Err(OpeningShapeError::QuotientWidthMismatch(
-                chip.quotient_width(),
+                quotient_width,
                 opening.quotient.len(),
             ));
         }
         // For each quotient chunk, verify that the number of elements is equal to the degree of the
-        // challenge extension field over the value field.
+        // challenge extension field over the value field, plus the random columns.
         for slice in &opening.quotient {
-            if slice.len() != SC::Challenge::D {
+            if slice.len() != quotient_chunk_size {
                 return Err(OpeningShapeError::QuotientChunkSizeMismatch(
-                    SC::Challenge::D,
+                    quotient_chunk_size,
                     slice.len(),
                 ));
             }
@@ -318,6 +357,7 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> Verifier<SC, A> {
         opening: &ChipOpenedValues<Val<SC>, SC::Challenge>,
         trace_domain: Domain<SC>,
         qc_domains: Vec<Domain<SC>>,
+        zk: Option<ZkConfig>,
         zeta: SC::Challenge,
         alpha: SC::Challenge,
         permutation_challenges: &[SC::Challenge],
@@ -329,7 +369,10 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> Verifier<SC, A> {
         let sels = trace_domain.selectors_at_point(zeta);
 
         // Recompute the quotient at zeta from the chunks.
-        let quotient = Self::recompute_quotient(opening, &qc_domains, zeta);
+        let quotient = match zk {
+            Some(_) => Self::recompute_zk_quotient(opening, qc_domains[0].size() / 2, zeta),
+            None => Self::recompute_quotient(opening, &qc_domains, zeta),
+        };
         // Calculate the evaluations of the constraints at zeta.
         let folded_constraints = Self::eval_constraints(
             chip,
@@ -375,9 +418,15 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> Verifier<SC, A> {
             next: unflatten(&opening.permutation.next),
         };
 
+        // Ignore the random columns of the main trace in the zero-knowledge mode.
+        let main = VerticalPair::new(
+            RowMajorMatrixView::new_row(&opening.main.local[..chip.width()]),
+            RowMajorMatrixView::new_row(&opening.main.next[..chip.width()]),
+        );
+
         let mut folder = VerifierConstraintFolder::<SC> {
             preprocessed: opening.preprocessed.view(),
-            main: opening.main.view(),
+            main,
             perm: perm_opening.view(),
             perm_challenges: permutation_challenges,
             local_cumulative_sum: &opening.local_cumulative_sum,
@@ -433,6 +482,29 @@ impl<SC: StarkGenericConfig, A: MachineAir<Val<SC>>> Verifier<SC, A> {
             })
             .sum::<SC::Challenge>()
     }
+
+    /// Recomputes the quotient for a chip and opening in the zero-knowledge mode, where the chunk
+    /// `i` holds the coefficients `i * chunk_size..(i + 1) * chunk_size` of the quotient, masked by
+    /// terms that cancel out in the sum.
+    pub fn recompute_zk_quotient(
+        opening: &ChipOpenedValues<Val<SC>, SC::Challenge>,
+        chunk_size: usize,
+        zeta: SC::Challenge,
+    ) -> SC::Challenge {
+        let zeta_pow_chunk_size = zeta.exp_u64(chunk_size as u64);
+        opening
+            .quotient
+            .iter()
+            .zip(zeta_pow_chunk_size.powers())
+            .map(|(ch, zeta_pow)| {
+                ch[..SC::Challenge::D]
+                    .iter()
+                    .enumerate()
+                    .map(|(e_i, &c)| zeta_pow * SC::Challenge::monomial(e_i) * c)
+                    .sum::<SC::Challenge>()
+            })
+            .sum::<SC::Challenge>()
+    }
 }
 
 /// An error that occurs when the openings do not match the expected shape.
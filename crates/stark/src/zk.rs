@@ -0,0 +1,243 @@
+//! The zero-knowledge mode of the prover and verifier.
+//!
+//! In this mode, a shard proof reveals nothing about the traces beyond the opened values at the
+//! out-of-domain point, which are themselves random:
+//!
+//! - The main and permutation traces of height `n` are committed over a subgroup `K` of size
+//!   `m = n << log_extension` containing the trace domain `H`. The rows of `H` hold the trace and
+//!   the other rows are random, so each column has `m - n` random degrees of freedom, at least the
+//!   number of points it is opened at.
+//! - The quotient polynomial of degree less than `2^(log_quotient_degree + 1) * m` is split by
+//!   coefficients into chunks `q_i` of `m` coefficients, such that `q = sum_i X^(i m) q_i`. Each
+//!   chunk is masked as `q_i + X^m r_i - r_{i-1}` with random `r_i` of degree less than `m`, which
+//!   cancel out in the sum, and committed over a subgroup of size `2m`.
+//! - Every main trace and quotient chunk is committed with [`ZkConfig::num_random_columns`] extra
+//!   random columns, so that the FRI batch polynomial is masked by a uniformly random one at every
+//!   height.
+//!
+//! The preprocessed traces are public and are committed as is, so that the verifying key does not
+//! depend on the randomness.
+
+use p3_dft::{Radix2DitParallel, TwoAdicSubgroupDft};
+use p3_field::{Field, TwoAdicField};
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use p3_maybe_rayon::prelude::*;
+use rand::{
+    distributions::{Distribution, Standard},
+    Rng,
+};
+use serde::{Deserialize, Serialize};
+
+/// The parameters of the zero-knowledge mode of a [`crate::StarkGenericConfig`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ZkConfig {
+    /// The number of points a committed polynomial is opened at: the FRI queries and the two
+    /// out-of-domain points.
+    pub num_openings: usize,
+    /// The number of random columns committed with every main trace and quotient chunk. It should
+    /// be at least the degree of the challenge field, as FRI batches the columns over it.
+    pub num_random_columns: usize,
+}
+
+impl ZkConfig {
+    /// The parameters for a FRI configuration with `num_queries` queries and challenges in an
+    /// extension of degree `extension_degree`.
+    #[must_use]
+    pub const fn new(num_queries: usize, extension_degree: usize) -> Self {
+        Self { num_openings: num_queries + 2, num_random_columns: extension_degree }
+    }
+
+    /// The log2 of the factor a trace of height `2^log_degree` is extended by when committed.
+    #[must_use]
+    pub fn log_extension(&self, log_degree: usize) -> usize {
+        let mut log_extension = 1;
+        while ((1 << log_extension) - 1) << log_degree < self.num_openings {
+            log_extension += 1;
+        }
+        log_extension
+    }
+
+    /// The log2 of the number of quotient chunks of a chip with the given log quotient degree.
+    ///
+    /// The degree of the constraints is at most `2^log_quotient_degree + 1`, so the quotient of
+    /// the extended traces has a degree less than `2^(log_quotient_degree + 1) * m`.
+    #[must_use]
+    pub const fn log_quotient_degree(log_quotient_degree: usize) -> usize {
+        log_quotient_degree + 1
+    }
+}
+
+/// Extends `trace` to a matrix of `trace.height() << log_extension` rows, where the rows
+/// `i << log_extension` hold the rows of `trace` and the other rows are random, and appends
+/// `num_random_columns` random columns to it.
+pub fn randomize_trace<F>(
+    trace: &RowMajorMatrix<F>,
+    log_extension: usize,
+    num_random_columns: usize,
+) -> RowMajorMatrix<F>
+where
+    F: Field,
+    Standard: Distribution<F>,
+{
+    let trace_width = trace.width();
+    let width = trace_width + num_random_columns;
+    let mask = (1 << log_extension) - 1;
+    let mut values = F::zero_vec(width * (trace.height() << log_extension));
+    values.par_chunks_mut(width).enumerate().for_each_init(rand::thread_rng, |rng, (i, row)| {
+        let (row, random_columns) = row.split_at_mut(trace_width);
+        if i & mask == 0 {
+            let j = i >> log_extension;
+            row.copy_from_slice(&trace.values[j * trace_width..(j + 1) * trace_width]);
+        } else {
+            row.iter_mut().for_each(|x| *x = rng.gen());
+        }
+        random_columns.iter_mut().for_each(|x| *x = rng.gen());
+    });
+    RowMajorMatrix::new(values, width)
+}
+
+/// Splits a quotient polynomial, given by its evaluations over the coset `shift * K` in natural
+/// order, into `num_chunks` masked chunks, and returns their evaluations over the subgroup of size
+/// `2 * height / num_chunks` with `num_random_columns` random columns appended.
+pub fn split_and_mask_quotient<F>(
+    quotient: RowMajorMatrix<F>,
+    shift: F,
+    num_chunks: usize,
+    num_random_columns: usize,
+) -> Vec<RowMajorMatrix<F>>
+where
+    F: TwoAdicField + Ord,
+    Standard: Distribution<F>,
+{
+    let mut rng = rand::thread_rng();
+    let dft = Radix2DitParallel::<F>::default();
+    let width = quotient.width();
+    let coefficients = dft.coset_idft_batch(quotient, shift);
+    let chunk_len = coefficients.values.len() / num_chunks;
+
+    // The chunk `i` is masked with `X^m r_i - r_{i-1}`, where `r_{-1} = r_{num_chunks - 1} = 0`.
+    let masks = (0..num_chunks - 1)
+        .map(|_| (0..chunk_len).map(|_| rng.gen()).collect::<Vec<F>>())
+        .collect::<Vec<_>>();
+
+    coefficients
+        .values
+        .chunks_exact(chunk_len)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let mut masked = chunk.to_vec();
+            if let Some(previous) = i.checked_sub(1).map(|j| &masks[j]) {
+                masked.iter_mut().zip(previous).for_each(|(c, r)| *c -= *r);
+            }
+            match masks.get(i) {
+                Some(mask) => masked.extend_from_slice(mask),
+                None => masked.resize(2 * chunk_len, F::ZERO),
+            }
+            let evaluations =
+                dft.dft_batch(RowMajorMatrix::new(masked, width)).to_row_major_matrix();
+            let extension = RowMajorMatrix::new(
+                (0..evaluations.height() * num_random_columns).map(|_| rng.gen()).collect(),
+                num_random_columns,
+            );
+            concat_columns(&evaluations, &extension)
+        })
+        .collect()
+}
+
+/// The evaluations over the coset `shift * K` of size `trace.height() << added_bits` of the
+/// polynomials given by their evaluations `trace` over the subgroup of size `trace.height()`.
+pub fn coset_lde<F: TwoAdicField + Ord>(
+    trace: RowMajorMatrix<F>,
+    added_bits: usize,
+    shift: F,
+) -> RowMajorMatrix<F> {
+    Radix2DitParallel::<F>::default()
+        .coset_lde_batch(trace, added_bits, shift)
+        .to_row_major_matrix()
+}
+
+/// The first `width` columns of `matrix`.
+pub fn truncate_columns<F: Clone + Send + Sync>(
+    matrix: &impl Matrix<F>,
+    width: usize,
+) -> RowMajorMatrix<F> {
+    let values = (0..matrix.height()).flat_map(|i| matrix.row(i).take(width)).collect::<Vec<_>>();
+    RowMajorMatrix::new(values, width)
+}
+
+fn concat_columns<F: Copy>(
+    left: &RowMajorMatrix<F>,
+    right: &RowMajorMatrix<F>,
+) -> RowMajorMatrix<F> {
+    let width = left.width + right.width;
+    let values = left
+        .values
+        .chunks_exact(left.width)
+        .zip(right.values.chunks_exact(right.width))
+        .flat_map(|(l, r)| l.iter().chain(r).copied())
+        .collect();
+    RowMajorMatrix::new(values, width)
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_field::FieldAlgebra;
+    use p3_koala_bear::KoalaBear;
+
+    use super::*;
+
+    type F = KoalaBear;
+
+    fn eval(coefficients: &[F], x: F) -> F {
+        coefficients.iter().rev().fold(F::ZERO, |acc, &c| acc * x + c)
+    }
+
+    #[test]
+    fn test_log_extension() {
+        let zk = ZkConfig::new(42, 4);
+        assert_eq!(zk.log_extension(10), 1);
+        assert_eq!(zk.log_extension(6), 1);
+        assert_eq!(zk.log_extension(5), 2);
+        assert_eq!(zk.log_extension(2), 4);
+    }
+
+    #[test]
+    fn test_randomize_trace() {
+        let trace = RowMajorMatrix::new((0..32).map(F::from_canonical_u32).collect(), 4);
+        let randomized = randomize_trace(&trace, 2, 3);
+        assert_eq!((randomized.width(), randomized.height()), (7, 32));
+        for i in 0..8 {
+            assert_eq!(
+                randomized.row(4 * i).take(4).collect::<Vec<_>>(),
+                trace.row(i).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_split_and_mask_quotient() {
+        let (log_height, num_chunks) = (4, 4);
+        let shift = F::GENERATOR;
+        let coefficients = (0..1 << log_height).map(|i| F::from_canonical_u32(i + 1)).collect();
+        let coefficients = RowMajorMatrix::new_col(coefficients);
+        let quotient = Radix2DitParallel::default()
+            .coset_dft_batch(coefficients.clone(), shift)
+            .to_row_major_matrix();
+        let chunks = split_and_mask_quotient(quotient, shift, num_chunks, 2);
+
+        // The chunks are evaluated over the subgroup of size 2m, so they can be interpolated back.
+        let m = (1 << log_height) / num_chunks;
+        let zeta = F::from_canonical_u32(7);
+        let recombined = chunks
+            .iter()
+            .enumerate()
+            .map(|(i, chunk)| {
+                assert_eq!((chunk.width(), chunk.height()), (3, 2 * m));
+                let column = truncate_columns(chunk, 1);
+                let coefficients = Radix2DitParallel::default().idft_batch(column);
+                zeta.exp_u64((i * m) as u64) * eval(&coefficients.values, zeta)
+            })
+            .sum::<F>();
+        assert_eq!(recombined, eval(&coefficients.values, zeta));
+    }
+}
@@ -1,7 +1,10 @@
-//! This crate provides verifiers for Ziren Groth16 and Plonk BN254 proofs in a no-std environment.
+//! This crate provides verifiers for Ziren Groth16 and Plonk BN254 proofs, as well as core and
+//! compressed STARK proofs.
 //! It is patched for efficient verification within the Ziren zkVM context.
+//!
+//! The crate requires `std`, as the STARK verifiers depend on the core machine and the recursion
+//! machine, but it does not depend on the prover or the SDK.
 
-#![cfg_attr(not(feature = "std"), no_std)]
 extern crate alloc;
 
 use lazy_static::lazy_static;
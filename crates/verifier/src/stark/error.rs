@@ -6,6 +6,12 @@ use super::{CoreSC, InnerSC};
 
 #[derive(Error, Debug)]
 pub enum StarkError {
+    #[error("Failed to deserialize the proof: {0}")]
+    InvalidProof(bincode::Error),
+    #[error("Failed to deserialize the vkey: {0}")]
+    InvalidVk(bincode::Error),
+    #[error("Expected a core or compressed proof")]
+    UnsupportedProofKind,
     #[error("Invalid public values")]
     InvalidPublicValues,
     #[error("Version mismatch")]
@@ -12,11 +12,12 @@ use p3_koala_bear::KoalaBear;
 use serde::{Deserialize, Serialize};
 use strum_macros::{EnumDiscriminants, EnumTryAs};
 use zkm_core_executor::ZKMReduceProof;
+use zkm_core_machine::{mips::MipsAir, utils::verify_core_proof};
 use zkm_primitives::{io::ZKMPublicValues, poseidon2_hash};
 use zkm_stark::ShardProof;
 use zkm_stark::{
-    air::PublicValues, koala_bear_poseidon2::KoalaBearPoseidon2, StarkGenericConfig,
-    StarkVerifyingKey, Word, DIGEST_SIZE,
+    air::PublicValues, koala_bear_poseidon2::KoalaBearPoseidon2, MachineVerificationError,
+    StarkGenericConfig, StarkVerifyingKey, Word, DIGEST_SIZE, PROOF_MAX_NUM_PVS,
 };
 
 use error::StarkError;
@@ -26,7 +27,7 @@ pub mod error;
 mod verify;
 
 /// A proof generated with Ziren of a particular proof mode.
-/// Redefined due to SDK's non-no_std limitation, and is used only for deserialization.
+/// Redefined so that the verifier does not depend on the SDK, and is used only for deserialization.
 #[derive(Debug, Clone, Serialize, Deserialize, EnumDiscriminants, EnumTryAs)]
 #[strum_discriminants(derive(Default, Hash, PartialOrd, Ord))]
 #[strum_discriminants(name(ZKMProofKind))]
@@ -89,7 +90,7 @@ pub trait HashableKey {
 pub struct StarkVerifier;
 
 impl StarkVerifier {
-    /// Verifies a Ziren compressed proof, as generated by the Ziren SDK.
+    /// Verifies a Ziren core or compressed proof, as generated by the Ziren SDK.
     ///
     /// # Arguments
     ///
@@ -106,19 +107,28 @@ impl StarkVerifier {
     ///
     /// # Returns
     ///
-    /// A success [`Result`] if verification succeeds, or a [`StarkError`] if verification fails.
+    /// A success [`Result`] if verification succeeds, or a [`StarkError`] if verification fails,
+    /// including when the bytes cannot be deserialized or the proof is not a STARK proof.
     ///
     /// Compared to `verify_proof()`, it performs a consistency check between
     /// user-supplied public values and those committed in the proof.
     pub fn verify(proof: &[u8], zkm_public_inputs: &[u8], zkm_vk: &[u8]) -> Result<(), StarkError> {
-        let proof: ZKMProof = bincode::deserialize(proof).expect("failed to deserialize the proof");
-        let ZKMProof::Compressed(proof) = proof else { panic!("expected a compressed proof") };
+        let (proof, vk) = Self::deserialize(proof, zkm_vk)?;
         let public_inputs = ZKMPublicValues::from(zkm_public_inputs);
-        let vk: ZKMVerifyingKey =
-            bincode::deserialize(zkm_vk).expect("failed to deserialize the vk");
 
+        // For core proofs, the committed value digest is final in the last shard.
+        let proof_public_values = match &proof {
+            ZKMProof::Core(shard_proofs) => {
+                let Some(last_shard) = shard_proofs.last() else {
+                    return Err(StarkError::Core(MachineVerificationError::EmptyProof));
+                };
+                &last_shard.public_values
+            }
+            ZKMProof::Compressed(proof) => &proof.proof.public_values,
+            _ => return Err(StarkError::UnsupportedProofKind),
+        };
         let proof_public_values: &PublicValues<Word<_>, _> =
-            proof.proof.public_values.as_slice().borrow();
+            proof_public_values.as_slice().borrow();
 
         // Get the committed value digest bytes.
         let committed_value_digest_bytes = proof_public_values
@@ -134,10 +144,10 @@ impl StarkVerifier {
             }
         }
 
-        verify_stark_compressed_proof(&vk, &proof).map_err(StarkError::Recursion)
+        Self::verify_deserialized(&proof, &vk)
     }
 
-    /// Verifies a Ziren compressed proof, as generated by the Ziren SDK.
+    /// Verifies a Ziren core or compressed proof, as generated by the Ziren SDK.
     ///
     /// # Arguments
     ///
@@ -147,12 +157,38 @@ impl StarkVerifier {
     /// Compared to `verify()`, it does not perform a consistency check between
     /// user-supplied public values and those committed in the proof.
     pub fn verify_proof(proof: &[u8], zkm_vk: &[u8]) -> Result<(), StarkError> {
-        let proof: ZKMProof = bincode::deserialize(proof).expect("failed to deserialize the proof");
-        let ZKMProof::Compressed(proof) = proof else { panic!("expected a compressed proof") };
-        let vk: ZKMVerifyingKey =
-            bincode::deserialize(zkm_vk).expect("failed to deserialize the vk");
+        let (proof, vk) = Self::deserialize(proof, zkm_vk)?;
+        Self::verify_deserialized(&proof, &vk)
+    }
+
+    /// Deserializes a proof and a vkey, and checks that the public values of the shard proofs
+    /// have the expected length, so that reading them cannot panic.
+    fn deserialize(proof: &[u8], zkm_vk: &[u8]) -> Result<(ZKMProof, ZKMVerifyingKey), StarkError> {
+        let proof: ZKMProof = bincode::deserialize(proof).map_err(StarkError::InvalidProof)?;
+        let vk: ZKMVerifyingKey = bincode::deserialize(zkm_vk).map_err(StarkError::InvalidVk)?;
+
+        let shard_proofs = match &proof {
+            ZKMProof::Core(shard_proofs) => shard_proofs.iter().collect_vec(),
+            ZKMProof::Compressed(proof) => vec![&proof.proof],
+            _ => return Err(StarkError::UnsupportedProofKind),
+        };
+        if shard_proofs.iter().any(|shard| shard.public_values.len() != PROOF_MAX_NUM_PVS) {
+            return Err(StarkError::InvalidPublicValues);
+        }
+        Ok((proof, vk))
+    }
 
-        verify_stark_compressed_proof(&vk, &proof).map_err(StarkError::Recursion)
+    fn verify_deserialized(proof: &ZKMProof, vk: &ZKMVerifyingKey) -> Result<(), StarkError> {
+        match proof {
+            ZKMProof::Core(shard_proofs) => {
+                let machine = MipsAir::<KoalaBear>::machine(CoreSC::default());
+                verify_core_proof(&machine, &vk.vk, shard_proofs).map_err(StarkError::Core)
+            }
+            ZKMProof::Compressed(proof) => {
+                verify_stark_compressed_proof(vk, proof).map_err(StarkError::Recursion)
+            }
+            _ => Err(StarkError::UnsupportedProofKind),
+        }
     }
 }
 
@@ -79,6 +79,57 @@ fn test_verify_stark() {
     crate::StarkVerifier::verify_proof(&proof, &vk_bytes).expect("Stark proof is invalid");
 }
 
+#[test]
+fn test_verify_core() {
+    // Set up the pk and vk.
+    let client = ProverClient::cpu();
+    let (pk, vk) = client.setup(HELLO_WORLD_ELF);
+
+    // Generate the core proof.
+    let zkm_proof_with_public_values = client.prove(&pk, ZKMStdin::new()).core().run().unwrap();
+
+    // Extract the proof and public inputs.
+    let proof = zkm_proof_with_public_values.bytes();
+    let public_inputs = zkm_proof_with_public_values.public_values.to_vec();
+
+    let vk_bytes = bincode::serialize(&vk).unwrap();
+
+    crate::StarkVerifier::verify(&proof, &public_inputs, &vk_bytes).expect("Core proof is invalid");
+
+    crate::StarkVerifier::verify_proof(&proof, &vk_bytes).expect("Core proof is invalid");
+
+    // Tampered public values must be rejected.
+    let mut tampered_inputs = public_inputs.clone();
+    tampered_inputs.push(0);
+    assert!(crate::StarkVerifier::verify(&proof, &tampered_inputs, &vk_bytes).is_err());
+}
+
+#[test]
+fn test_verify_stark_malformed() {
+    let client = ProverClient::cpu();
+    let (pk, vk) = client.setup(HELLO_WORLD_ELF);
+    let vk_bytes = bincode::serialize(&vk).unwrap();
+
+    // Malformed bytes and other proof kinds are rejected without panicking.
+    let result = crate::StarkVerifier::verify_proof(&[1, 2, 3], &vk_bytes);
+    assert!(matches!(result, Err(crate::StarkError::InvalidProof(_))));
+    let proof = bincode::serialize(&crate::stark::ZKMProof::Core(vec![])).unwrap();
+    let result = crate::StarkVerifier::verify(&proof, &[], &[1, 2, 3]);
+    assert!(matches!(result, Err(crate::StarkError::InvalidVk(_))));
+    let proof = bincode::serialize(&crate::stark::ZKMProof::CompressToGroth16).unwrap();
+    let result = crate::StarkVerifier::verify(&proof, &[], &vk_bytes);
+    assert!(matches!(result, Err(crate::StarkError::UnsupportedProofKind)));
+
+    // So are shard proofs whose public values are truncated.
+    let proof = client.prove(&pk, ZKMStdin::new()).core().run().unwrap().bytes();
+    let mut proof: crate::stark::ZKMProof = bincode::deserialize(&proof).unwrap();
+    let crate::stark::ZKMProof::Core(shard_proofs) = &mut proof else { unreachable!() };
+    shard_proofs.last_mut().unwrap().public_values.truncate(4);
+    let proof = bincode::serialize(&proof).unwrap();
+    let result = crate::StarkVerifier::verify(&proof, &[], &vk_bytes);
+    assert!(matches!(result, Err(crate::StarkError::InvalidPublicValues)));
+}
+
 // ZKM_DEV=true RUST_LOG=debug cargo test -r test_e2e_verify_groth16 --features ark -- --nocapture
 #[test]
 #[ignore]
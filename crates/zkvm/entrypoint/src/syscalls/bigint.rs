@@ -1,8 +1,11 @@
-use super::syscall_uint256_mulmod;
+use super::{syscall_bigint_mulmod, syscall_uint256_mulmod};
 
 /// The number of limbs in a "uint256".
 const N: usize = 8;
 
+/// The maximum number of "uint256" digits of the operands of [`sys_bigint_varwidth`].
+const MAX_DIGITS: usize = 16;
+
 /// Sets `result` to be `(x op y) % modulus`.
 ///
 /// Currently only multiplication is supported and `op` is not used. If the modulus is zero, then
@@ -46,3 +49,40 @@ pub extern "C" fn sys_bigint(
         syscall_uint256_mulmod(result_ptr, concat_ptr);
     }
 }
+
+/// Sets `result` to be `(x * y) % modulus`, where all values are `num_digits` "uint256" digits
+/// wide.
+///
+/// Unlike [`sys_bigint`], the modulus must be nonzero, and `x` or `y` must be smaller than it.
+///
+/// ### Safety
+///
+/// The caller must ensure that `result`, `x`, `y`, and `modulus` are valid pointers to
+/// `num_digits * 8` words that are aligned along a four byte boundary, and that
+/// `1 <= num_digits <= 16`.
+#[no_mangle]
+pub extern "C" fn sys_bigint_varwidth(
+    result: *mut u32,
+    x: *const u32,
+    y: *const u32,
+    modulus: *const u32,
+    num_digits: u32,
+) {
+    assert!((1..=MAX_DIGITS as u32).contains(&num_digits));
+    let width = num_digits as usize * N;
+
+    // Instantiate a new uninitialized array of words to place the concatenated y and modulus.
+    let mut concat_y_modulus = core::mem::MaybeUninit::<[u32; MAX_DIGITS * N * 2]>::uninit();
+    unsafe {
+        let concat_ptr = concat_y_modulus.as_mut_ptr() as *mut u32;
+
+        // Place y and the modulus next to each other, as expected by the syscall.
+        core::ptr::copy(y, concat_ptr, width);
+        core::ptr::copy(modulus, concat_ptr.add(width), width);
+
+        // Copy x into the result array, as our syscall will write the result into the first input.
+        core::ptr::copy(x, result, width);
+
+        syscall_bigint_mulmod(result, concat_ptr, num_digits);
+    }
+}
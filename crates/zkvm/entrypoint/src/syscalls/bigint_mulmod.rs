@@ -0,0 +1,30 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// Variable-width modular multiplication operation.
+///
+/// The operands are `num_digits` 256-bit digits wide, with `1 <= num_digits <= 16`. `y_modulus`
+/// holds the y value followed by the modulus, and the result is written over the first input.
+///
+/// ### Safety
+///
+/// The caller must ensure that `x` and `y_modulus` are valid pointers to data that is aligned
+/// along a four byte boundary, that the modulus is nonzero and that `x` or `y` is smaller than
+/// the modulus.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_bigint_mulmod(x: *mut u32, y_modulus: *const u32, num_digits: u32) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "syscall",
+            in("$2") crate::syscalls::BIGINT_MULMOD,
+            in("$4") x,
+            in("$5") y_modulus,
+            in("$6") num_digits,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
@@ -0,0 +1,65 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// Executes the ten rounds of the BLAKE2s compression function on the given working vector and
+/// message block.
+///
+/// ### Safety
+///
+/// The caller must ensure that `v` and `m` are valid pointers to data that is aligned along a
+/// four byte boundary.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_blake2s_rounds(v: *mut [u32; 16], m: *const [u32; 16]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "syscall",
+            in("$2") crate::syscalls::BLAKE2S_ROUNDS,
+            in("$4") v,
+            in("$5") m,
+        );
+    }
+}
+
+/// Executes the seven rounds of the BLAKE3 compression function on the given working vector and
+/// message block.
+///
+/// ### Safety
+///
+/// The caller must ensure that `v` and `m` are valid pointers to data that is aligned along a
+/// four byte boundary.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_blake3_rounds(v: *mut [u32; 16], m: *const [u32; 16]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "syscall",
+            in("$2") crate::syscalls::BLAKE3_ROUNDS,
+            in("$4") v,
+            in("$5") m,
+        );
+    }
+}
+
+/// Executes the twelve rounds of the BLAKE2b compression function on the given working vector and
+/// message block.
+///
+/// ### Safety
+///
+/// The caller must ensure that `v` and `m` are valid pointers to data that is aligned along an
+/// eight byte boundary.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_blake2b_rounds(v: *mut [u64; 16], m: *const [u64; 16]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "syscall",
+            in("$2") crate::syscalls::BLAKE2B_ROUNDS,
+            in("$4") v,
+            in("$5") m,
+        );
+    }
+}
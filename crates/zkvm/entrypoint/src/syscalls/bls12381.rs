@@ -48,6 +48,33 @@ pub extern "C" fn syscall_bls12381_double(p: *mut [u32; 24]) {
     }
 }
 
+/// Multiplies a BLS12-381 point by a scalar.
+///
+/// The scalar is given as 12 little-endian words and the result is stored in-place in the
+/// point.
+///
+/// ### Safety
+///
+/// The caller must ensure that `p` and `s` are valid pointers to data that is aligned along a four
+/// byte boundary. Additionally, `p` must be a valid point on the BLS12-381 curve of prime order `n`
+/// that is not the identity, and the scalar must be in `[1, n)`.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_bls12381_mul(p: *mut [u32; 24], s: *const [u32; 12]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "syscall",
+            in("$2") crate::syscalls::BLS12381_MUL,
+            in("$4") p,
+            in("$5") s,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
 /// Decompresses a compressed BLS12-381 point.
 ///
 /// The first half of the input array should contain the X coordinate. The second half of the input
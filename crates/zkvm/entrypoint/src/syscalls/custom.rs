@@ -0,0 +1,30 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// Executes the custom precompile with the given index, in `0..16`.
+///
+/// Custom precompiles are registered by the host on the executor and the machine, so the meaning
+/// of `arg1` and `arg2` is up to the precompile. By convention they are the addresses of its
+/// input and output.
+///
+/// ### Safety
+///
+/// The caller must ensure that the arguments are valid for the registered precompile.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_custom(index: u32, arg1: u32, arg2: u32) {
+    assert!(index < 16, "invalid custom precompile index {index}");
+
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "syscall",
+            in("$2") crate::syscalls::CUSTOM_0 + index,
+            in("$4") arg1,
+            in("$5") arg2,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
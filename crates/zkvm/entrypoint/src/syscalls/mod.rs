@@ -1,6 +1,9 @@
 mod bigint;
+mod bigint_mulmod;
+mod blake;
 mod bls12381;
 mod bn254;
+mod custom;
 mod ed25519;
 mod fptower;
 mod halt;
@@ -20,8 +23,11 @@ mod unconstrained;
 mod verify;
 
 pub use bigint::*;
+pub use bigint_mulmod::*;
+pub use blake::*;
 pub use bls12381::*;
 pub use bn254::*;
+pub use custom::*;
 pub use ed25519::*;
 pub use fptower::*;
 pub use halt::*;
@@ -162,3 +168,36 @@ pub const BN254_FP2_MUL: u32 = 0x01_01_00_2B;
 
 /// Executes the `POSEIDON2_PERMUTE` precompile.
 pub const POSEIDON2_PERMUTE: u32 = 0x00_01_00_30;
+
+/// Executes the `BLAKE2S_ROUNDS` precompile.
+pub const BLAKE2S_ROUNDS: u32 = 0x01_01_00_31;
+
+/// Executes the `BLAKE3_ROUNDS` precompile.
+pub const BLAKE3_ROUNDS: u32 = 0x01_01_00_32;
+
+/// Executes the `BLAKE2B_ROUNDS` precompile.
+pub const BLAKE2B_ROUNDS: u32 = 0x01_01_00_33;
+
+/// Executes the `BLS12381_FP6_MUL` precompile.
+pub const BLS12381_FP6_MUL: u32 = 0x01_01_00_34;
+
+/// Executes the `BN254_FP6_MUL` precompile.
+pub const BN254_FP6_MUL: u32 = 0x01_01_00_35;
+
+/// Executes the `SECP256K1_MUL` precompile.
+pub const SECP256K1_MUL: u32 = 0x01_01_00_36;
+
+/// Executes the `SECP256R1_MUL` precompile.
+pub const SECP256R1_MUL: u32 = 0x01_01_00_37;
+
+/// Executes the `BN254_MUL` precompile.
+pub const BN254_MUL: u32 = 0x01_01_00_38;
+
+/// Executes the `BLS12381_MUL` precompile.
+pub const BLS12381_MUL: u32 = 0x01_01_00_39;
+
+/// Executes the `BIGINT_MULMOD` precompile.
+pub const BIGINT_MULMOD: u32 = 0x01_01_00_3A;
+
+/// The first of the 16 codes reserved for custom precompiles, see [`syscall_custom`].
+pub const CUSTOM_0: u32 = 0x01_01_00_C0;
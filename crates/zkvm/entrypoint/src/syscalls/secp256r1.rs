@@ -52,6 +52,33 @@ pub extern "C" fn syscall_secp256r1_double(p: *mut [u32; 16]) {
     unreachable!()
 }
 
+/// Multiplies a Secp256r1 point by a scalar.
+///
+/// The scalar is given as 8 little-endian words and the result is stored in-place in the
+/// point.
+///
+/// ### Safety
+///
+/// The caller must ensure that `p` and `s` are valid pointers to data that is aligned along a four
+/// byte boundary. Additionally, `p` must be a valid point on the secp256r1 curve of prime order `n`
+/// that is not the identity, and the scalar must be in `[1, n)`.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_secp256r1_mul(p: *mut [u32; 16], s: *const [u32; 8]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "syscall",
+            in("$2") crate::syscalls::SECP256R1_MUL,
+            in("$4") p,
+            in("$5") s,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
 /// Decompresses a compressed Secp256r1 point.
 ///
 /// The input array should be 64 bytes long, with the first 32 bytes containing the X coordinate in
@@ -0,0 +1,319 @@
+use crate::syscall_bigint_mulmod;
+
+/// The number of words of a 256-bit digit.
+pub const BIGINT_DIGIT_WORDS: usize = 8;
+
+/// The maximum number of digits of the operands of [`mulmod`], i.e. 4096-bit operands.
+pub const BIGINT_MAX_DIGITS: usize = 16;
+
+/// Sets `x` to `(x * y) % modulus`, where all values are little endian words.
+///
+/// The width of the operands must be a nonzero multiple of [`BIGINT_DIGIT_WORDS`] of at most
+/// [`BIGINT_MAX_DIGITS`] digits. The modulus must be nonzero, and `x` or `y` must be smaller than
+/// it.
+pub fn mulmod(x: &mut [u32], y: &[u32], modulus: &[u32]) {
+    let width = x.len();
+    assert!(y.len() == width && modulus.len() == width, "operands must have the same width");
+    assert!(
+        width > 0 && width % BIGINT_DIGIT_WORDS == 0,
+        "operand width must be a nonzero multiple of {BIGINT_DIGIT_WORDS} words"
+    );
+    let num_digits = width / BIGINT_DIGIT_WORDS;
+    assert!(num_digits <= BIGINT_MAX_DIGITS, "operands must be at most 4096 bits wide");
+
+    // The syscall expects y to be followed by the modulus.
+    let mut y_modulus = Vec::with_capacity(2 * width);
+    y_modulus.extend_from_slice(y);
+    y_modulus.extend_from_slice(modulus);
+
+    unsafe {
+        syscall_bigint_mulmod(x.as_mut_ptr(), y_modulus.as_ptr(), num_digits as u32);
+    }
+}
+
+/// Computes `base ^ exponent % modulus` with the semantics of the EVM `MODEXP` precompile.
+///
+/// All values are big endian byte strings, and the result has the length of `modulus`. A zero
+/// modulus yields zero. The modulus can be at most 4096 bits, while `base` and `exponent` can be
+/// of any length.
+///
+/// There is no exponentiation precompile: this is a square-and-multiply loop in the guest, which
+/// costs one [`mulmod`] syscall per bit of the exponent and one more per set bit.
+pub fn modexp(base: &[u8], exponent: &[u8], modulus: &[u8]) -> Vec<u8> {
+    let significant = &modulus[modulus.iter().take_while(|b| **b == 0).count()..];
+    let num_digits = significant.len().div_ceil(4 * BIGINT_DIGIT_WORDS);
+    assert!(num_digits <= BIGINT_MAX_DIGITS, "modexp modulus must be at most 4096 bits");
+
+    // Both a zero modulus and a modulus of one reduce everything to zero.
+    if significant.is_empty() || significant == [1] {
+        return vec![0; modulus.len()];
+    }
+
+    let width = num_digits * BIGINT_DIGIT_WORDS;
+    let m = be_bytes_to_words(significant, width);
+    let base = reduce(base, &m);
+
+    // Square and multiply, starting at the most significant set bit of the exponent.
+    let mut acc = one(width);
+    let bits = exponent.iter().flat_map(|byte| (0..8).rev().map(move |i| (byte >> i) & 1 == 1));
+    for bit in bits.skip_while(|bit| !bit) {
+        let square = acc.clone();
+        mulmod(&mut acc, &square, &m);
+        if bit {
+            mulmod(&mut acc, &base, &m);
+        }
+    }
+
+    let bytes = words_to_be_bytes(&acc);
+    let mut result = vec![0; modulus.len()];
+    result[modulus.len() - significant.len()..]
+        .copy_from_slice(&bytes[bytes.len() - significant.len()..]);
+    result
+}
+
+/// Reduces the big endian integer `value` of any length modulo `modulus`, which must be at least
+/// two.
+fn reduce(value: &[u8], modulus: &[u32]) -> Vec<u32> {
+    let width = modulus.len();
+    let chunk_len = 4 * width;
+    let unit = one(width);
+
+    // Process the value in chunks of the width of the modulus, most significant first, so that
+    // the accumulator is `acc * 2^(32 * width) + chunk` at each step.
+    let head_len = match value.len() % chunk_len {
+        0 => chunk_len.min(value.len()),
+        head_len => head_len,
+    };
+    let (head, tail) = value.split_at(head_len);
+
+    // Since the modulus is at least two, multiplying by one has a quotient within the width.
+    let mut acc = be_bytes_to_words(head, width);
+    mulmod(&mut acc, &unit, modulus);
+    if tail.is_empty() {
+        return acc;
+    }
+
+    // `2^(32 * width) % modulus`, as twice `2^(32 * width - 1) % modulus`.
+    let mut shift = vec![0; width];
+    shift[width - 1] = 1 << 31;
+    mulmod(&mut shift, &unit, modulus);
+    let shift = add_mod(&shift, &shift, modulus);
+
+    for chunk in tail.chunks(chunk_len) {
+        mulmod(&mut acc, &shift, modulus);
+        let mut chunk = be_bytes_to_words(chunk, width);
+        mulmod(&mut chunk, &unit, modulus);
+        acc = add_mod(&acc, &chunk, modulus);
+    }
+    acc
+}
+
+/// Computes `(a + b) % modulus` for `a` and `b` smaller than the modulus.
+fn add_mod(a: &[u32], b: &[u32], modulus: &[u32]) -> Vec<u32> {
+    let mut sum = vec![0; a.len()];
+    let mut carry = 0;
+    for (s, (a, b)) in sum.iter_mut().zip(a.iter().zip(b)) {
+        let (value, c1) = a.overflowing_add(*b);
+        let (value, c2) = value.overflowing_add(carry);
+        *s = value;
+        carry = (c1 || c2) as u32;
+    }
+
+    let below_modulus = sum.iter().rev().cmp(modulus.iter().rev()).is_lt();
+    if carry == 1 || !below_modulus {
+        let mut borrow = false;
+        for (s, m) in sum.iter_mut().zip(modulus) {
+            let (value, b1) = s.overflowing_sub(*m);
+            let (value, b2) = value.overflowing_sub(borrow as u32);
+            *s = value;
+            borrow = b1 || b2;
+        }
+    }
+    sum
+}
+
+/// The little endian words of one, with the given width.
+fn one(width: usize) -> Vec<u32> {
+    let mut words = vec![0; width];
+    words[0] = 1;
+    words
+}
+
+/// Converts big endian bytes to little endian words, zero-padded to `width` words.
+fn be_bytes_to_words(bytes: &[u8], width: usize) -> Vec<u32> {
+    assert!(bytes.len() <= 4 * width);
+    let mut words = vec![0; width];
+    for (i, byte) in bytes.iter().rev().enumerate() {
+        words[i / 4] |= (*byte as u32) << (8 * (i % 4));
+    }
+    words
+}
+
+/// Converts little endian words to big endian bytes.
+fn words_to_be_bytes(words: &[u32]) -> Vec<u8> {
+    words.iter().rev().flat_map(|word| word.to_be_bytes()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+
+    /// The `BIGINT_MULMOD` syscall outside of the zkVM, with the semantics of the executor.
+    #[no_mangle]
+    extern "C" fn syscall_bigint_mulmod(x: *mut u32, y_modulus: *const u32, num_digits: u32) {
+        let width = num_digits as usize * BIGINT_DIGIT_WORDS;
+        let x = unsafe { std::slice::from_raw_parts_mut(x, width) };
+        let y_modulus = unsafe { std::slice::from_raw_parts(y_modulus, 2 * width) };
+        let a = BigUint::from_slice(x);
+        let b = BigUint::from_slice(&y_modulus[..width]);
+        let m = BigUint::from_slice(&y_modulus[width..]);
+        assert!(m != BigUint::ZERO, "zero modulus");
+        assert!(a < m || b < m, "neither operand is smaller than the modulus");
+        x.copy_from_slice(&to_words(&((a * b) % m), width));
+    }
+
+    fn to_words(value: &BigUint, width: usize) -> Vec<u32> {
+        let mut words = value.to_u32_digits();
+        words.resize(width, 0);
+        words
+    }
+
+    fn expected(base: &[u8], exponent: &[u8], modulus: &[u8]) -> Vec<u8> {
+        let m = BigUint::from_bytes_be(modulus);
+        let mut result = vec![0; modulus.len()];
+        if m != BigUint::ZERO {
+            let value = BigUint::from_bytes_be(base)
+                .modpow(&BigUint::from_bytes_be(exponent), &m)
+                .to_bytes_be();
+            if value != [0] {
+                result[modulus.len() - value.len()..].copy_from_slice(&value);
+            }
+        }
+        result
+    }
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_modexp_known_answers() {
+        assert_eq!(modexp(&[3], &[5], &[7]), [5]);
+        assert_eq!(modexp(&[2], &[10], &[0x03, 0xe8]), [0x00, 0x18]);
+
+        // Fermat's little theorem modulo the secp256k1 base field prime, from EIP-198.
+        let p = "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f";
+        let p_minus_one = "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2e";
+        let mut one = vec![0; 32];
+        one[31] = 1;
+        assert_eq!(modexp(&[3], &hex(p_minus_one), &hex(p)), one);
+    }
+
+    #[test]
+    fn test_modexp_edge_cases() {
+        // A zero exponent yields one, unless the modulus is one.
+        assert_eq!(modexp(&[5], &[], &[0, 7]), [0, 1]);
+        assert_eq!(modexp(&[5], &[0, 0], &[7]), [1]);
+        assert_eq!(modexp(&[], &[], &[7]), [1]);
+        assert_eq!(modexp(&[5], &[], &[0, 1]), [0, 0]);
+
+        // A modulus of zero or one yields zero with the length of the modulus.
+        assert_eq!(modexp(&[5], &[3], &[0, 0, 1]), [0, 0, 0]);
+        assert_eq!(modexp(&[5], &[3], &[0, 0]), [0, 0]);
+        assert_eq!(modexp(&[5], &[3], &[]), Vec::<u8>::new());
+
+        // A zero base.
+        assert_eq!(modexp(&[], &[3], &[7]), [0]);
+        assert_eq!(modexp(&[0], &[0], &[7]), [1]);
+
+        // A base equal to and larger than the modulus.
+        assert_eq!(modexp(&[7], &[3], &[7]), [0]);
+        assert_eq!(modexp(&[0x01, 0x00], &[3], &[7]), expected(&[0x01, 0x00], &[3], &[7]));
+        let base = [0xff; 100];
+        assert_eq!(modexp(&base, &[0x01, 0x01], &[0xfb]), expected(&base, &[0x01, 0x01], &[0xfb]));
+    }
+
+    #[test]
+    #[should_panic(expected = "at most 4096 bits")]
+    fn test_modexp_modulus_too_wide() {
+        modexp(&[2], &[3], &[0xff; 4 * BIGINT_DIGIT_WORDS * BIGINT_MAX_DIGITS + 1]);
+    }
+
+    #[test]
+    fn test_modexp_random() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0x5eed);
+        for modulus_len in [1, 2, 31, 32, 33, 64, 100, 256, 512] {
+            for _ in 0..4 {
+                let mut modulus: Vec<u8> = (0..modulus_len).map(|_| rng.gen()).collect();
+                // Leading zeros must be kept in the result.
+                modulus[0] = if rng.gen() { 0 } else { modulus[0] };
+                let base_len = rng.gen_range(0..3 * modulus_len + 40);
+                let base: Vec<u8> = (0..base_len).map(|_| rng.gen()).collect();
+                let exponent: Vec<u8> = (0..rng.gen_range(0..40)).map(|_| rng.gen()).collect();
+                assert_eq!(
+                    modexp(&base, &exponent, &modulus),
+                    expected(&base, &exponent, &modulus),
+                    "base {base:02x?} exponent {exponent:02x?} modulus {modulus:02x?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_reduce() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0x5eed);
+        for num_digits in [1, 2, BIGINT_MAX_DIGITS] {
+            let width = num_digits * BIGINT_DIGIT_WORDS;
+            let mut modulus: Vec<u32> = (0..width).map(|_| rng.gen()).collect();
+            modulus[width - 1] |= 1;
+            let m = BigUint::from_slice(&modulus);
+            let m_bytes = m.to_bytes_be();
+
+            // Values below, equal to, just above and many times wider than the modulus.
+            let mut values = vec![vec![], vec![0; 3], vec![1], m_bytes.clone()];
+            values.push((&m + 1u32).to_bytes_be());
+            values.push((&m * 2u32).to_bytes_be());
+            values.push(vec![0xff; 4 * width]);
+            for len in [4 * width - 1, 4 * width + 1, 8 * width, 10 * width + 3] {
+                values.push((0..len).map(|_| rng.gen()).collect());
+            }
+
+            for value in values {
+                let reduced = reduce(&value, &modulus);
+                assert_eq!(reduced, to_words(&(BigUint::from_bytes_be(&value) % &m), width));
+            }
+        }
+
+        // The smallest modulus.
+        let mut two = vec![0; BIGINT_DIGIT_WORDS];
+        two[0] = 2;
+        assert_eq!(reduce(&[0xff; 70], &two), one(BIGINT_DIGIT_WORDS));
+        assert_eq!(reduce(&[0x12, 0x34], &two), vec![0; BIGINT_DIGIT_WORDS]);
+    }
+
+    #[test]
+    fn test_add_mod() {
+        let width = BIGINT_DIGIT_WORDS;
+        let check = |a: &BigUint, b: &BigUint, m: &BigUint| {
+            let sum = add_mod(&to_words(a, width), &to_words(b, width), &to_words(m, width));
+            assert_eq!(sum, to_words(&((a + b) % m), width), "{a} + {b} mod {m}");
+        };
+
+        let small = BigUint::from(7u32);
+        let top = (BigUint::from(1u32) << 256usize) - 1u32;
+        for m in [small, top.clone(), BigUint::from(1u32) << 255usize] {
+            let max = &m - 1u32;
+            // Sums below the modulus, equal to it and above it.
+            check(&BigUint::ZERO, &BigUint::ZERO, &m);
+            check(&BigUint::from(1u32), &(&m - 2u32), &m);
+            check(&BigUint::from(1u32), &max, &m);
+            check(&max, &max, &m);
+        }
+
+        // A sum that carries out of the width.
+        check(&(&top - 1u32), &(&top - 2u32), &top);
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{syscall_blake2b_rounds, syscall_blake2s_rounds, syscall_blake3_rounds};
+
+/// The initialization vector of BLAKE2s and BLAKE3.
+pub const BLAKE2S_IV: [u32; 8] = [
+    0x6A09E667, 0xBB67AE85, 0x3C6EF372, 0xA54FF53A, 0x510E527F, 0x9B05688C, 0x1F83D9AB, 0x5BE0CD19,
+];
+
+/// The initialization vector of BLAKE2b.
+pub const BLAKE2B_IV: [u64; 8] = [
+    0x6A09E667F3BCC908,
+    0xBB67AE8584CAA73B,
+    0x3C6EF372FE94F82B,
+    0xA54FF53A5F1D36F1,
+    0x510E527FADE682D1,
+    0x9B05688C2B3E6C1F,
+    0x1F83D9ABFB41BD6B,
+    0x5BE0CD19137E2179,
+];
+
+/// Executes the BLAKE2s compression function on the given state and message block, where `t` is
+/// the number of bytes hashed so far and `last` marks the final block.
+pub fn blake2s_compress(h: &mut [u32; 8], m: &[u32; 16], t: u64, last: bool) {
+    let mut v = [0u32; 16];
+    v[..8].copy_from_slice(h);
+    v[8..].copy_from_slice(&BLAKE2S_IV);
+    v[12] ^= t as u32;
+    v[13] ^= (t >> 32) as u32;
+    if last {
+        v[14] = !v[14];
+    }
+
+    unsafe {
+        syscall_blake2s_rounds(&mut v, m);
+    }
+
+    for (i, h) in h.iter_mut().enumerate() {
+        *h ^= v[i] ^ v[i + 8];
+    }
+}
+
+/// Executes the BLAKE2b compression function on the given state and message block, where `t` is
+/// the number of bytes hashed so far and `last` marks the final block.
+pub fn blake2b_compress(h: &mut [u64; 8], m: &[u64; 16], t: u128, last: bool) {
+    let mut v = [0u64; 16];
+    v[..8].copy_from_slice(h);
+    v[8..].copy_from_slice(&BLAKE2B_IV);
+    v[12] ^= t as u64;
+    v[13] ^= (t >> 64) as u64;
+    if last {
+        v[14] = !v[14];
+    }
+
+    unsafe {
+        syscall_blake2b_rounds(&mut v, m);
+    }
+
+    for (i, h) in h.iter_mut().enumerate() {
+        *h ^= v[i] ^ v[i + 8];
+    }
+}
+
+/// Runs the rounds of the BLAKE3 compression function and returns the working vector.
+fn blake3_compress(
+    cv: &[u32; 8],
+    block: &[u32; 16],
+    block_len: u32,
+    counter: u64,
+    flags: u32,
+) -> [u32; 16] {
+    let mut v = [0u32; 16];
+    v[..8].copy_from_slice(cv);
+    v[8..12].copy_from_slice(&BLAKE2S_IV[..4]);
+    v[12] = counter as u32;
+    v[13] = (counter >> 32) as u32;
+    v[14] = block_len;
+    v[15] = flags;
+
+    unsafe {
+        syscall_blake3_rounds(&mut v, block);
+    }
+    v
+}
+
+/// Executes the BLAKE3 compression function and overwrites the chaining value with the result.
+pub fn blake3_compress_in_place(
+    cv: &mut [u32; 8],
+    block: &[u32; 16],
+    block_len: u32,
+    counter: u64,
+    flags: u32,
+) {
+    let v = blake3_compress(cv, block, block_len, counter, flags);
+    for (i, cv) in cv.iter_mut().enumerate() {
+        *cv = v[i] ^ v[i + 8];
+    }
+}
+
+/// Executes the BLAKE3 compression function and returns the full 64-byte extended output.
+pub fn blake3_compress_xof(
+    cv: &[u32; 8],
+    block: &[u32; 16],
+    block_len: u32,
+    counter: u64,
+    flags: u32,
+) -> [u32; 16] {
+    let mut v = blake3_compress(cv, block, block_len, counter, flags);
+    let (lo, hi) = v.split_at_mut(8);
+    for ((lo, hi), cv) in lo.iter_mut().zip(hi.iter_mut()).zip(cv) {
+        *lo ^= *hi;
+        *hi ^= cv;
+    }
+    v
+}
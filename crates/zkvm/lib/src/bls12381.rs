@@ -2,12 +2,22 @@ use std::io::ErrorKind;
 
 use crate::{
     syscall_bls12381_add, syscall_bls12381_decompress, syscall_bls12381_double,
-    utils::{AffinePoint, WeierstrassAffinePoint, WeierstrassPoint},
+    syscall_bls12381_mul,
+    utils::{
+        double_and_add_mul_assign, scalar_in_range, AffinePoint, WeierstrassAffinePoint,
+        WeierstrassPoint,
+    },
 };
 
 /// The number of limbs in [Bls12381AffinePoint].
 pub const N: usize = 24;
 
+/// The order of the BLS12-381 G1 subgroup, padded to the width of a coordinate.
+const SCALAR_ORDER: [u32; N / 2] = [
+    0x00000001, 0xffffffff, 0xfffe5bfe, 0x53bda402, 0x09a1d805, 0x3339d808, 0x299d7d48, 0x73eda753,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000,
+];
+
 /// A point on the BLS12-381 curve.
 #[derive(Copy, Clone)]
 #[repr(align(4))]
@@ -81,6 +91,19 @@ impl AffinePoint<N> for Bls12381Point {
             syscall_bls12381_double(a);
         }
     }
+
+    fn mul_assign(&mut self, scalar: &[u32]) {
+        // The precompile rejects the identity and unreduced scalars, so those fall back to
+        // double-and-add.
+        if self.is_infinity() || !scalar_in_range(scalar, &SCALAR_ORDER) {
+            double_and_add_mul_assign(self, scalar);
+            return;
+        }
+        let s: &[u32; N / 2] = scalar.try_into().unwrap();
+        unsafe {
+            syscall_bls12381_mul(self.limbs_mut(), s);
+        }
+    }
 }
 
 /// Decompresses a compressed public key using bls12381_decompress precompile.
@@ -1,11 +1,19 @@
 use crate::{
-    syscall_bn254_add, syscall_bn254_double,
-    utils::{AffinePoint, WeierstrassAffinePoint, WeierstrassPoint},
+    syscall_bn254_add, syscall_bn254_double, syscall_bn254_mul,
+    utils::{
+        double_and_add_mul_assign, scalar_in_range, AffinePoint, WeierstrassAffinePoint,
+        WeierstrassPoint,
+    },
 };
 
 /// The number of limbs in [Bn254AffinePoint].
 pub const N: usize = 16;
 
+/// The order of the BN254 G1 group.
+const SCALAR_ORDER: [u32; N / 2] = [
+    0xf0000001, 0x43e1f593, 0x79b97091, 0x2833e848, 0x8181585d, 0xb85045b6, 0xe131a029, 0x30644e72,
+];
+
 /// A point on the Bn254 curve.
 #[derive(Copy, Clone)]
 #[repr(align(4))]
@@ -77,4 +85,17 @@ impl AffinePoint<N> for Bn254Point {
             syscall_bn254_double(a);
         }
     }
+
+    fn mul_assign(&mut self, scalar: &[u32]) {
+        // The precompile rejects the identity and unreduced scalars, so those fall back to
+        // double-and-add.
+        if self.is_infinity() || !scalar_in_range(scalar, &SCALAR_ORDER) {
+            double_and_add_mul_assign(self, scalar);
+            return;
+        }
+        let s: &[u32; N / 2] = scalar.try_into().unwrap();
+        unsafe {
+            syscall_bn254_mul(self.limbs_mut(), s);
+        }
+    }
 }
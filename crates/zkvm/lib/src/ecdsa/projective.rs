@@ -128,16 +128,15 @@ impl<C: ECDSACurve> MulByGenerator for ProjectivePoint<C> {}
 
 impl<C: ECDSACurve> LinearCombination for ProjectivePoint<C> {
     fn lincomb(x: &Self, k: &Self::Scalar, y: &Self, l: &Self::Scalar) -> Self {
-        let x = x.to_zkvm_point();
-        let y = y.to_zkvm_point();
+        // Each product is a single scalar multiplication, which the curves with a precompile
+        // run in one syscall.
+        let mut a = x.to_zkvm_point();
+        a.mul_assign(&be_bytes_to_le_words(k.to_repr()));
+        let mut b = y.to_zkvm_point();
+        b.mul_assign(&be_bytes_to_le_words(l.to_repr()));
+        a.complete_add_assign(&b);
 
-        let a_bits_le = be_bytes_to_le_bits(k.to_repr().as_ref());
-        let b_bits_le = be_bytes_to_le_bits(l.to_repr().as_ref());
-
-        let zkm_point =
-            C::ZKMAffinePoint::multi_scalar_multiplication(&a_bits_le, x, &b_bits_le, y);
-
-        Self::from_zkvm_point(zkm_point)
+        Self::from_zkvm_point(a)
     }
 }
 
@@ -401,18 +400,3 @@ fn be_bytes_to_le_words<T: AsMut<[u8]>>(mut bytes: T) -> [u32; 8] {
     let mut iter = bytes.chunks(4).map(|b| u32::from_le_bytes(b.try_into().unwrap()));
     core::array::from_fn(|_| iter.next().unwrap())
 }
-
-/// Convert big-endian bytes with the most significant bit first to little-endian bytes with the least significant bit first.
-/// Panics: If the bytes have len > 32.
-#[inline]
-fn be_bytes_to_le_bits(be_bytes: &[u8]) -> [bool; 256] {
-    let mut bits = [false; 256];
-    // Reverse the byte order to little-endian.
-    for (i, &byte) in be_bytes.iter().rev().enumerate() {
-        for j in 0..8 {
-            // Flip the bit order so the least significant bit is now the first bit of the chunk.
-            bits[i * 8 + j] = ((byte >> j) & 1) == 1;
-        }
-    }
-    bits
-}
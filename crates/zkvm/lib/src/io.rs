@@ -2,7 +2,7 @@
 use crate::{read_vec_raw, syscall_write, ReadVecResult};
 use serde::{de::DeserializeOwned, Serialize};
 use std::io::{Result, Write};
-pub use zkm_primitives::consts::fd::*;
+pub use zkm_primitives::{codec::Codec, consts::fd::*};
 
 /// A writer that writes to a file descriptor inside the zkVM.
 struct SyscallWriter {
@@ -63,6 +63,21 @@ pub fn read<T: DeserializeOwned>() -> T {
     bincode::deserialize(&vec).expect("deserialization failed")
 }
 
+/// Read a deserializable object encoded with `codec` from the input stream, e.g. one written
+/// with `ZKMStdin::write_with` by a host in another language. `Codec::Json` and `Codec::Cbor`
+/// require the `serialization` feature.
+///
+/// ### Examples
+/// ```ignore
+/// use zkm_zkvm::io::Codec;
+///
+/// let data: Vec<u32> = zkm_zkvm::io::read_with(Codec::Json);
+/// ```
+pub fn read_with<T: DeserializeOwned>(codec: Codec) -> T {
+    let vec = read_vec();
+    codec.decode(&vec).expect("deserialization failed").0
+}
+
 /// Commit a serializable object to the public values stream.
 ///
 /// ### Examples
@@ -86,6 +101,21 @@ pub fn commit<T: Serialize>(value: &T) {
     bincode::serialize_into(writer, value).expect("serialization failed");
 }
 
+/// Commit a serializable object encoded with `codec` to the public values stream. The layout of
+/// each encoding is documented in [`zkm_primitives::codec`].
+///
+/// ### Examples
+/// ```ignore
+/// use zkm_zkvm::io::Codec;
+///
+/// let hash = [0u8; 32];
+/// zkm_zkvm::io::commit_with(&hash, Codec::Raw);
+/// ```
+pub fn commit_with<T: Serialize>(value: &T, codec: Codec) {
+    let buf = codec.encode(value).expect("serialization failed");
+    commit_slice(&buf);
+}
+
 /// Commit bytes to the public values stream.
 ///
 /// ### Examples
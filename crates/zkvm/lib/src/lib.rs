@@ -3,6 +3,8 @@
 //! Documentation for these syscalls can be found in the zkVM entrypoint
 //! `zkm_zkvm::syscalls` module.
 
+pub mod bigint;
+pub mod blake;
 pub mod bls12381;
 pub mod bn254;
 #[cfg(feature = "ecdsa")]
@@ -11,6 +13,7 @@ pub mod ecdsa;
 pub mod ed25519;
 pub mod io;
 pub mod keccak256;
+pub mod pairing;
 pub mod poseidon2;
 pub mod secp256k1;
 pub mod secp256r1;
@@ -48,6 +51,9 @@ extern "C" {
     /// Executes an Secp256k1 curve doubling on the given point.
     pub fn syscall_secp256k1_double(p: *mut [u32; 16]);
 
+    /// Executes an Secp256k1 curve scalar multiplication on the given point.
+    pub fn syscall_secp256k1_mul(p: *mut [u32; 16], s: *const [u32; 8]);
+
     /// Executes an Secp256k1 curve decompression on the given point.
     pub fn syscall_secp256k1_decompress(point: &mut [u8; 64], is_odd: bool);
 
@@ -57,6 +63,9 @@ extern "C" {
     /// Executes an Secp256r1 curve doubling on the given point.
     pub fn syscall_secp256r1_double(p: *mut [u32; 16]);
 
+    /// Executes an Secp256r1 curve scalar multiplication on the given point.
+    pub fn syscall_secp256r1_mul(p: *mut [u32; 16], s: *const [u32; 8]);
+
     /// Executes an Secp256r1 curve decompression on the given point.
     pub fn syscall_secp256r1_decompress(point: &mut [u8; 64], is_odd: bool);
 
@@ -66,18 +75,33 @@ extern "C" {
     /// Executes a Bn254 curve doubling on the given point.
     pub fn syscall_bn254_double(p: *mut [u32; 16]);
 
+    /// Executes a Bn254 curve scalar multiplication on the given point.
+    pub fn syscall_bn254_mul(p: *mut [u32; 16], s: *const [u32; 8]);
+
     /// Executes a BLS12-381 curve addition on the given points.
     pub fn syscall_bls12381_add(p: *mut [u32; 24], q: *const [u32; 24]);
 
     /// Executes a BLS12-381 curve doubling on the given point.
     pub fn syscall_bls12381_double(p: *mut [u32; 24]);
 
+    /// Executes a BLS12-381 curve scalar multiplication on the given point.
+    pub fn syscall_bls12381_mul(p: *mut [u32; 24], s: *const [u32; 12]);
+
     /// Executes the Keccak Sponge
     pub fn syscall_keccak_sponge(input: *const u32, result: *mut [u32; 17]);
 
     /// Executes the Poseidon2 permutation
     pub fn syscall_poseidon2_permute(state: *mut [u32; 16]);
 
+    /// Executes the rounds of the BLAKE2s compression function on the given working vector.
+    pub fn syscall_blake2s_rounds(v: *mut [u32; 16], m: *const [u32; 16]);
+
+    /// Executes the rounds of the BLAKE3 compression function on the given working vector.
+    pub fn syscall_blake3_rounds(v: *mut [u32; 16], m: *const [u32; 16]);
+
+    /// Executes the rounds of the BLAKE2b compression function on the given working vector.
+    pub fn syscall_blake2b_rounds(v: *mut [u64; 16], m: *const [u64; 16]);
+
     /// Executes an uint256 multiplication on the given inputs.
     pub fn syscall_uint256_mulmod(x: *mut [u32; 8], y: *const [u32; 8]);
 
@@ -88,6 +112,13 @@ extern "C" {
         lo: *mut [u32; 64],
         hi: *mut [u32; 8],
     );
+
+    /// Executes a variable-width modular multiplication on the given inputs.
+    pub fn syscall_bigint_mulmod(x: *mut u32, y_modulus: *const u32, num_digits: u32);
+
+    /// Executes the custom precompile with the given index, registered by the host.
+    pub fn syscall_custom(index: u32, arg1: u32, arg2: u32);
+
     /// Enters unconstrained mode.
     pub fn syscall_enter_unconstrained() -> bool;
 
@@ -118,6 +149,15 @@ extern "C" {
         modulus: *const [u32; 8],
     );
 
+    /// Computes a variable-width modular multiplication of big integers.
+    pub fn sys_bigint_varwidth(
+        result: *mut u32,
+        x: *const u32,
+        y: *const u32,
+        modulus: *const u32,
+        num_digits: u32,
+    );
+
     /// Executes a BLS12-381 field addition on the given inputs.
     pub fn syscall_bls12381_fp_addmod(p: *mut u32, q: *const u32);
 
@@ -136,6 +176,9 @@ extern "C" {
     /// Executes a BLS12-381 Fp2 multiplication on the given inputs.
     pub fn syscall_bls12381_fp2_mulmod(p: *mut u32, q: *const u32);
 
+    /// Executes a BLS12-381 Fp6 multiplication on the given inputs.
+    pub fn syscall_bls12381_fp6_mulmod(p: *mut u32, q: *const u32);
+
     /// Executes a BN254 field addition on the given inputs.
     pub fn syscall_bn254_fp_addmod(p: *mut u32, q: *const u32);
 
@@ -154,6 +197,9 @@ extern "C" {
     /// Executes a BN254 Fp2 multiplication on the given inputs.
     pub fn syscall_bn254_fp2_mulmod(p: *mut u32, q: *const u32);
 
+    /// Executes a BN254 Fp6 multiplication on the given inputs.
+    pub fn syscall_bn254_fp6_mulmod(p: *mut u32, q: *const u32);
+
     /// Reads a buffer from the input stream.
     pub fn read_vec_raw() -> ReadVecResult;
 }
@@ -0,0 +1,310 @@
+use super::{FieldSyscall, Fp, Fp2, G1Affine, G2Affine, PairingCurve, PairingFamily, TwistType};
+use crate::{
+    syscall_bls12381_fp2_addmod, syscall_bls12381_fp2_mulmod, syscall_bls12381_fp2_submod,
+    syscall_bls12381_fp6_mulmod, syscall_bls12381_fp_addmod, syscall_bls12381_fp_mulmod,
+    syscall_bls12381_fp_submod,
+};
+
+/// The BLS12-381 curve, as the parameters of its pairing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bls12381;
+
+pub type Bls12381G1Affine = G1Affine<Bls12381>;
+pub type Bls12381G2Affine = G2Affine<Bls12381>;
+
+/// The generator of G1.
+pub const G1_GENERATOR: Bls12381G1Affine = G1Affine::new(
+    Fp::from_limbs([
+        0xdb22c6bb, 0xfb3af00a, 0xf97a1aef, 0x6c55e83f, 0x171bac58, 0xa14e3a3f, 0x9774b905,
+        0xc3688c4f, 0x4fa9ac0f, 0x2695638c, 0x3197d794, 0x17f1d3a7,
+    ]),
+    Fp::from_limbs([
+        0x46c5e7e1, 0x0caa2329, 0xa2888ae4, 0xd03cc744, 0x2c04b3ed, 0x00db18cb, 0xd5d00af6,
+        0xfcf5e095, 0x741d8ae4, 0xa09e30ed, 0xe3aaa0f1, 0x08b3f481,
+    ]),
+);
+
+/// The generator of G2.
+pub const G2_GENERATOR: Bls12381G2Affine = G2Affine::new(
+    Fp2::new(
+        Fp::from_limbs([
+            0xc121bdb8, 0xd48056c8, 0xa805bbef, 0x0bac0326, 0x7ae3d177, 0xb4510b64, 0xfa403b02,
+            0xc6e47ad4, 0x2dc51051, 0x26080527, 0xf08f0a91, 0x024aa2b2,
+        ]),
+        Fp::from_limbs([
+            0x5d042b7e, 0xe5ac7d05, 0x13945d57, 0x334cf112, 0xdc7f5049, 0xb5da61bb, 0x9920b61a,
+            0x596bd0d0, 0x88274f65, 0x7dacd3a0, 0x52719f60, 0x13e02b60,
+        ]),
+    ),
+    Fp2::new(
+        Fp::from_limbs([
+            0x08b82801, 0xe1935486, 0x3baca289, 0x923ac9cc, 0x5160d12c, 0x6d429a69, 0x8cbdd3a7,
+            0xadfd9baa, 0xda2e351a, 0x8cc9cdc6, 0x727d6e11, 0x0ce5d527,
+        ]),
+        Fp::from_limbs([
+            0xf05f79be, 0xaaa9075f, 0x5cec1da1, 0x3f370d27, 0x572e99ab, 0x267492ab, 0x85a763af,
+            0xcb3e287e, 0x2bc28b99, 0x32acd2b0, 0x2ea734cc, 0x0606c4a0,
+        ]),
+    ),
+);
+
+impl PairingCurve for Bls12381 {
+    type Limbs = [u32; 12];
+
+    const FAMILY: PairingFamily = PairingFamily::Bls12;
+    const TWIST: TwistType = TwistType::M;
+
+    const X: u64 = 0xd201000000010000;
+    const X_IS_NEGATIVE: bool = true;
+
+    const ATE_LOOP_COUNT: &'static [i8] = &[
+        1, 1, 0, 1, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0,
+    ];
+
+    const MODULUS_MINUS_TWO: [u32; 12] = [
+        0xffffaaa9, 0xb9feffff, 0xb153ffff, 0x1eabfffe, 0xf6b0f624, 0x6730d2a0, 0xf38512bf,
+        0x64774b84, 0x434bacd7, 0x4b1ba7b6, 0x397fe69a, 0x1a0111ea,
+    ];
+    const TWO_INV: Fp<Self> = Fp::from_limbs([
+        0xffffd556, 0xdcff7fff, 0x58a9ffff, 0x0f55ffff, 0x7b587b12, 0xb3986950, 0x79c2895f,
+        0xb23ba5c2, 0x21a5d66b, 0x258dd3db, 0x1cbff34d, 0x0d0088f5,
+    ]);
+    const XI: Fp2<Self> = Fp2::new(
+        Fp::from_limbs([
+            0x00000001, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+            0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        ]),
+        Fp::from_limbs([
+            0x00000001, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+            0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        ]),
+    );
+    const TWIST_B: Fp2<Self> = Fp2::new(
+        Fp::from_limbs([
+            0x00000004, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+            0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        ]),
+        Fp::from_limbs([
+            0x00000004, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+            0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        ]),
+    );
+    const TWIST_MUL_BY_Q_X: Fp2<Self> = Fp2::new(
+        Fp::from_limbs([
+            0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+            0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        ]),
+        Fp::from_limbs([
+            0x0000aaac, 0x8bfd0000, 0x4f49fffd, 0x409427eb, 0x0fb85f9b, 0x897d2965, 0x89759ad4,
+            0xaa0d857d, 0x63d4de85, 0xec024086, 0x397fe699, 0x1a0111ea,
+        ]),
+    );
+    const TWIST_MUL_BY_Q_Y: Fp2<Self> = Fp2::new(
+        Fp::from_limbs([
+            0xede3cc09, 0xc81084fb, 0x72ec05f4, 0xee67992f, 0x009241c5, 0x77f76e17, 0xc2d3435e,
+            0x48395dab, 0x6bd17ffe, 0x6831e36d, 0x37ff400b, 0x06af0e04,
+        ]),
+        Fp::from_limbs([
+            0xede3cc09, 0xc81084fb, 0x72ec05f4, 0xee67992f, 0x009241c5, 0x77f76e17, 0xc2d3435e,
+            0x48395dab, 0x6bd17ffe, 0x6831e36d, 0x37ff400b, 0x06af0e04,
+        ]),
+    );
+    const FROBENIUS_COEFFS: [[Fp2<Self>; 6]; 3] = [
+        [
+            Fp2::new(
+                Fp::from_limbs([
+                    0x00000001, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                ]),
+                Fp::from_limbs([
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                ]),
+            ),
+            Fp2::new(
+                Fp::from_limbs([
+                    0x92235fb8, 0x8d0775ed, 0x63e7813d, 0xf67ea53d, 0x84bab9c4, 0x7b2443d7,
+                    0x3cbd5f4f, 0x0fd603fd, 0x202c0d1f, 0xc231beb4, 0x02bb0667, 0x1904d3bf,
+                ]),
+                Fp::from_limbs([
+                    0x6ddc4af3, 0x2cf78a12, 0x4d6c7ec2, 0x282d5ac1, 0x71f63c5f, 0xec0c8ec9,
+                    0xb6c7b36f, 0x54a14787, 0x231f9fb8, 0x88e9e902, 0x36c4e032, 0x00fc3e2b,
+                ]),
+            ),
+            Fp2::new(
+                Fp::from_limbs([
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                ]),
+                Fp::from_limbs([
+                    0x0000aaac, 0x8bfd0000, 0x4f49fffd, 0x409427eb, 0x0fb85f9b, 0x897d2965,
+                    0x89759ad4, 0xaa0d857d, 0x63d4de85, 0xec024086, 0x397fe699, 0x1a0111ea,
+                ]),
+            ),
+            Fp2::new(
+                Fp::from_limbs([
+                    0xede3cc09, 0xc81084fb, 0x72ec05f4, 0xee67992f, 0x009241c5, 0x77f76e17,
+                    0xc2d3435e, 0x48395dab, 0x6bd17ffe, 0x6831e36d, 0x37ff400b, 0x06af0e04,
+                ]),
+                Fp::from_limbs([
+                    0xede3cc09, 0xc81084fb, 0x72ec05f4, 0xee67992f, 0x009241c5, 0x77f76e17,
+                    0xc2d3435e, 0x48395dab, 0x6bd17ffe, 0x6831e36d, 0x37ff400b, 0x06af0e04,
+                ]),
+            ),
+            Fp2::new(
+                Fp::from_limbs([
+                    0x0000aaad, 0x8bfd0000, 0x4f49fffd, 0x409427eb, 0x0fb85f9b, 0x897d2965,
+                    0x89759ad4, 0xaa0d857d, 0x63d4de85, 0xec024086, 0x397fe699, 0x1a0111ea,
+                ]),
+                Fp::from_limbs([
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                ]),
+            ),
+            Fp2::new(
+                Fp::from_limbs([
+                    0x80078116, 0x9b18fae9, 0x257f8732, 0xc63a3e6e, 0x8e9c0566, 0x8beadf4d,
+                    0x0c0b8fee, 0xf3981624, 0x48b1e045, 0xdf47fa6b, 0x013a5fd8, 0x05b2cfd9,
+                ]),
+                Fp::from_limbs([
+                    0x7ff82995, 0x1ee60516, 0x8bd478cd, 0x5871c190, 0x6814f0bd, 0xdb45f353,
+                    0xe77982d0, 0x70df3560, 0xfa99cc91, 0x6bd3ad4a, 0x384586c1, 0x144e4211,
+                ]),
+            ),
+        ],
+        [
+            Fp2::new(
+                Fp::from_limbs([
+                    0x00000001, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                ]),
+                Fp::from_limbs([
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                ]),
+            ),
+            Fp2::new(
+                Fp::from_limbs([
+                    0xfffeffff, 0x2e01ffff, 0x620a0002, 0xde17d813, 0xe6f89688, 0xddb3a93b,
+                    0x6a0f77ea, 0xba69c607, 0xdf76ce51, 0x5f19672f, 0x00000000, 0x00000000,
+                ]),
+                Fp::from_limbs([
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                ]),
+            ),
+            Fp2::new(
+                Fp::from_limbs([
+                    0xfffefffe, 0x2e01ffff, 0x620a0002, 0xde17d813, 0xe6f89688, 0xddb3a93b,
+                    0x6a0f77ea, 0xba69c607, 0xdf76ce51, 0x5f19672f, 0x00000000, 0x00000000,
+                ]),
+                Fp::from_limbs([
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                ]),
+            ),
+            Fp2::new(
+                Fp::from_limbs([
+                    0xffffaaaa, 0xb9feffff, 0xb153ffff, 0x1eabfffe, 0xf6b0f624, 0x6730d2a0,
+                    0xf38512bf, 0x64774b84, 0x434bacd7, 0x4b1ba7b6, 0x397fe69a, 0x1a0111ea,
+                ]),
+                Fp::from_limbs([
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                ]),
+            ),
+            Fp2::new(
+                Fp::from_limbs([
+                    0x0000aaac, 0x8bfd0000, 0x4f49fffd, 0x409427eb, 0x0fb85f9b, 0x897d2965,
+                    0x89759ad4, 0xaa0d857d, 0x63d4de85, 0xec024086, 0x397fe699, 0x1a0111ea,
+                ]),
+                Fp::from_limbs([
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                ]),
+            ),
+            Fp2::new(
+                Fp::from_limbs([
+                    0x0000aaad, 0x8bfd0000, 0x4f49fffd, 0x409427eb, 0x0fb85f9b, 0x897d2965,
+                    0x89759ad4, 0xaa0d857d, 0x63d4de85, 0xec024086, 0x397fe699, 0x1a0111ea,
+                ]),
+                Fp::from_limbs([
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                ]),
+            ),
+        ],
+        [
+            Fp2::new(
+                Fp::from_limbs([
+                    0x00000001, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                ]),
+                Fp::from_limbs([
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                ]),
+            ),
+            Fp2::new(
+                Fp::from_limbs([
+                    0x121bdea2, 0xf1ee7b04, 0x3e67fa0a, 0x304466cf, 0xf61eb45e, 0xef396489,
+                    0x30b1cf60, 0x1c3dedd9, 0xd77a2cd9, 0xe2e9c448, 0x0180a68e, 0x135203e6,
+                ]),
+                Fp::from_limbs([
+                    0xede3cc09, 0xc81084fb, 0x72ec05f4, 0xee67992f, 0x009241c5, 0x77f76e17,
+                    0xc2d3435e, 0x48395dab, 0x6bd17ffe, 0x6831e36d, 0x37ff400b, 0x06af0e04,
+                ]),
+            ),
+            Fp2::new(
+                Fp::from_limbs([
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                ]),
+                Fp::from_limbs([
+                    0x00000001, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                ]),
+            ),
+            Fp2::new(
+                Fp::from_limbs([
+                    0x121bdea2, 0xf1ee7b04, 0x3e67fa0a, 0x304466cf, 0xf61eb45e, 0xef396489,
+                    0x30b1cf60, 0x1c3dedd9, 0xd77a2cd9, 0xe2e9c448, 0x0180a68e, 0x135203e6,
+                ]),
+                Fp::from_limbs([
+                    0x121bdea2, 0xf1ee7b04, 0x3e67fa0a, 0x304466cf, 0xf61eb45e, 0xef396489,
+                    0x30b1cf60, 0x1c3dedd9, 0xd77a2cd9, 0xe2e9c448, 0x0180a68e, 0x135203e6,
+                ]),
+            ),
+            Fp2::new(
+                Fp::from_limbs([
+                    0xffffaaaa, 0xb9feffff, 0xb153ffff, 0x1eabfffe, 0xf6b0f624, 0x6730d2a0,
+                    0xf38512bf, 0x64774b84, 0x434bacd7, 0x4b1ba7b6, 0x397fe69a, 0x1a0111ea,
+                ]),
+                Fp::from_limbs([
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                ]),
+            ),
+            Fp2::new(
+                Fp::from_limbs([
+                    0xede3cc09, 0xc81084fb, 0x72ec05f4, 0xee67992f, 0x009241c5, 0x77f76e17,
+                    0xc2d3435e, 0x48395dab, 0x6bd17ffe, 0x6831e36d, 0x37ff400b, 0x06af0e04,
+                ]),
+                Fp::from_limbs([
+                    0x121bdea2, 0xf1ee7b04, 0x3e67fa0a, 0x304466cf, 0xf61eb45e, 0xef396489,
+                    0x30b1cf60, 0x1c3dedd9, 0xd77a2cd9, 0xe2e9c448, 0x0180a68e, 0x135203e6,
+                ]),
+            ),
+        ],
+    ];
+
+    const FP_ADD: FieldSyscall = syscall_bls12381_fp_addmod;
+    const FP_SUB: FieldSyscall = syscall_bls12381_fp_submod;
+    const FP_MUL: FieldSyscall = syscall_bls12381_fp_mulmod;
+    const FP2_ADD: FieldSyscall = syscall_bls12381_fp2_addmod;
+    const FP2_SUB: FieldSyscall = syscall_bls12381_fp2_submod;
+    const FP2_MUL: FieldSyscall = syscall_bls12381_fp2_mulmod;
+    const FP6_MUL: FieldSyscall = syscall_bls12381_fp6_mulmod;
+}
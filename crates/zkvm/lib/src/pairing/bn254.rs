@@ -0,0 +1,311 @@
+use super::{FieldSyscall, Fp, Fp2, G1Affine, G2Affine, PairingCurve, PairingFamily, TwistType};
+use crate::{
+    syscall_bn254_fp2_addmod, syscall_bn254_fp2_mulmod, syscall_bn254_fp2_submod,
+    syscall_bn254_fp6_mulmod, syscall_bn254_fp_addmod, syscall_bn254_fp_mulmod,
+    syscall_bn254_fp_submod,
+};
+
+/// The BN254 curve, as the parameters of its pairing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bn254;
+
+pub type Bn254G1Affine = G1Affine<Bn254>;
+pub type Bn254G2Affine = G2Affine<Bn254>;
+
+/// The generator of G1.
+pub const G1_GENERATOR: Bn254G1Affine = G1Affine::new(
+    Fp::from_limbs([
+        0x00000001, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000,
+    ]),
+    Fp::from_limbs([
+        0x00000002, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+        0x00000000,
+    ]),
+);
+
+/// The generator of G2.
+pub const G2_GENERATOR: Bn254G2Affine = G2Affine::new(
+    Fp2::new(
+        Fp::from_limbs([
+            0xd992f6ed, 0x46debd5c, 0xf75edadd, 0x674322d4, 0x5e5c4479, 0x426a0066, 0x121f1e76,
+            0x1800deef,
+        ]),
+        Fp::from_limbs([
+            0xaef312c2, 0x97e485b7, 0x35a9e712, 0xf1aa4933, 0x31fb5d25, 0x7260bfb7, 0x920d483a,
+            0x198e9393,
+        ]),
+    ),
+    Fp2::new(
+        Fp::from_limbs([
+            0x66fa7daa, 0x4ce6cc01, 0x0c43d37b, 0xe3d1e769, 0x8dcb408f, 0x4aab7180, 0xdb8c6deb,
+            0x12c85ea5,
+        ]),
+        Fp::from_limbs([
+            0xd122975b, 0x55acdadc, 0x70b38ef3, 0xbc4b3133, 0x690c3395, 0xec9e99ad, 0x585ff075,
+            0x090689d0,
+        ]),
+    ),
+);
+
+impl PairingCurve for Bn254 {
+    type Limbs = [u32; 8];
+
+    const FAMILY: PairingFamily = PairingFamily::Bn;
+    const TWIST: TwistType = TwistType::D;
+
+    const X: u64 = 0x44e992b44a6909f1;
+    const X_IS_NEGATIVE: bool = false;
+
+    /// The non-adjacent form of `6x + 2`.
+    const ATE_LOOP_COUNT: &'static [i8] = &[
+        1, 0, -1, 0, 1, 0, 0, 0, -1, 0, -1, 0, 0, 0, -1, 0, 1, 0, -1, 0, 0, -1, 0, 0, 0, 0, 0, 1,
+        0, 0, -1, 0, 1, 0, 0, -1, 0, 0, 0, 0, -1, 0, 1, 0, 0, 0, -1, 0, -1, 0, 0, 1, 0, 0, 0, -1,
+        0, 0, -1, 0, 1, 0, 1, 0, 0, 0,
+    ];
+
+    const MODULUS_MINUS_TWO: [u32; 8] = [
+        0xd87cfd45, 0x3c208c16, 0x6871ca8d, 0x97816a91, 0x8181585d, 0xb85045b6, 0xe131a029,
+        0x30644e72,
+    ];
+    const TWO_INV: Fp<Self> = Fp::from_limbs([
+        0x6c3e7ea4, 0x9e10460b, 0xb438e546, 0xcbc0b548, 0x40c0ac2e, 0xdc2822db, 0x7098d014,
+        0x18322739,
+    ]);
+    const XI: Fp2<Self> = Fp2::new(
+        Fp::from_limbs([
+            0x00000009, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+            0x00000000,
+        ]),
+        Fp::from_limbs([
+            0x00000001, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+            0x00000000,
+        ]),
+    );
+    const TWIST_B: Fp2<Self> = Fp2::new(
+        Fp::from_limbs([
+            0x24a138e5, 0x3267e6dc, 0x59dbefa3, 0xb5b4c5e5, 0x1be06ac3, 0x81be1899, 0xceb8aaae,
+            0x2b149d40,
+        ]),
+        Fp::from_limbs([
+            0x85c315d2, 0xe4a2bd06, 0xe52d1852, 0xa74fa084, 0xeed8fdf4, 0xcd2cafad, 0x3af0fed4,
+            0x009713b0,
+        ]),
+    );
+    const TWIST_MUL_BY_Q_X: Fp2<Self> = Fp2::new(
+        Fp::from_limbs([
+            0x176f553d, 0x99e39557, 0xc2c3330c, 0xb78cc310, 0xf559b143, 0x4c0bec3c, 0x4f7911f7,
+            0x2fb34798,
+        ]),
+        Fp::from_limbs([
+            0x640fcba2, 0x1665d51c, 0x0b7c9dce, 0x32ae2a1d, 0xd75a0794, 0x4ba4cc8b, 0x61ebae20,
+            0x16c9e550,
+        ]),
+    );
+    const TWIST_MUL_BY_Q_Y: Fp2<Self> = Fp2::new(
+        Fp::from_limbs([
+            0x71a0135a, 0xdc540146, 0xa9c95998, 0xdbaae0ed, 0xb6e2f9b9, 0xdc5ec698, 0x489af5dc,
+            0x063cf305,
+        ]),
+        Fp::from_limbs([
+            0x2623b0e3, 0x82d37f63, 0x8fa25bd2, 0x21807dc9, 0xec796f2b, 0x0704b5a7, 0xac41049a,
+            0x07c03cbc,
+        ]),
+    );
+    const FROBENIUS_COEFFS: [[Fp2<Self>; 6]; 3] = [
+        [
+            Fp2::new(
+                Fp::from_limbs([
+                    0x00000001, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                    0x00000000, 0x00000000,
+                ]),
+                Fp::from_limbs([
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                    0x00000000, 0x00000000,
+                ]),
+            ),
+            Fp2::new(
+                Fp::from_limbs([
+                    0xdcc9e470, 0xd60b35da, 0x292f2176, 0x5c521e08, 0x76e68b60, 0xe8b99fdd,
+                    0x2865a7df, 0x1284b71c,
+                ]),
+                Fp::from_limbs([
+                    0x80f362ac, 0xca5cf05f, 0x8eeec7e5, 0x74799277, 0x12150b8e, 0xa6327cfe,
+                    0xb4fae7e6, 0x246996f3,
+                ]),
+            ),
+            Fp2::new(
+                Fp::from_limbs([
+                    0x176f553d, 0x99e39557, 0xc2c3330c, 0xb78cc310, 0xf559b143, 0x4c0bec3c,
+                    0x4f7911f7, 0x2fb34798,
+                ]),
+                Fp::from_limbs([
+                    0x640fcba2, 0x1665d51c, 0x0b7c9dce, 0x32ae2a1d, 0xd75a0794, 0x4ba4cc8b,
+                    0x61ebae20, 0x16c9e550,
+                ]),
+            ),
+            Fp2::new(
+                Fp::from_limbs([
+                    0x71a0135a, 0xdc540146, 0xa9c95998, 0xdbaae0ed, 0xb6e2f9b9, 0xdc5ec698,
+                    0x489af5dc, 0x063cf305,
+                ]),
+                Fp::from_limbs([
+                    0x2623b0e3, 0x82d37f63, 0x8fa25bd2, 0x21807dc9, 0xec796f2b, 0x0704b5a7,
+                    0xac41049a, 0x07c03cbc,
+                ]),
+            ),
+            Fp2::new(
+                Fp::from_limbs([
+                    0x921ea762, 0x848a1f55, 0xbe94ec72, 0xd33365f7, 0x5a181e84, 0x80f3c0b7,
+                    0x64eea801, 0x05b54f5e,
+                ]),
+                Fp::from_limbs([
+                    0xcd2b8126, 0xc13b4711, 0x1bdec763, 0x3685d2ea, 0x3b0b1c92, 0x9f3a80b0,
+                    0xe7fd8aee, 0x2c145edb,
+                ]),
+            ),
+            Fp2::new(
+                Fp::from_limbs([
+                    0xeab7692f, 0x2ea2c810, 0x55aa1bd3, 0x425c459b, 0xa4353ff4, 0xe93a3661,
+                    0x4f798649, 0x0183c1e7,
+                ]),
+                Fp::from_limbs([
+                    0x6e0c2c4b, 0x24c6b8ee, 0x678e2ac0, 0xb080cb99, 0xc7729f7d, 0xa27fb246,
+                    0x76fd0675, 0x12acf2ca,
+                ]),
+            ),
+        ],
+        [
+            Fp2::new(
+                Fp::from_limbs([
+                    0x00000001, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                    0x00000000, 0x00000000,
+                ]),
+                Fp::from_limbs([
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                    0x00000000, 0x00000000,
+                ]),
+            ),
+            Fp2::new(
+                Fp::from_limbs([
+                    0x607cfd49, 0xe4bd44e5, 0xbb966e3d, 0xc28f069f, 0xe0acccb0, 0x5e6dd9e7,
+                    0xe131a029, 0x30644e72,
+                ]),
+                Fp::from_limbs([
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                    0x00000000, 0x00000000,
+                ]),
+            ),
+            Fp2::new(
+                Fp::from_limbs([
+                    0x607cfd48, 0xe4bd44e5, 0xbb966e3d, 0xc28f069f, 0xe0acccb0, 0x5e6dd9e7,
+                    0xe131a029, 0x30644e72,
+                ]),
+                Fp::from_limbs([
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                    0x00000000, 0x00000000,
+                ]),
+            ),
+            Fp2::new(
+                Fp::from_limbs([
+                    0xd87cfd46, 0x3c208c16, 0x6871ca8d, 0x97816a91, 0x8181585d, 0xb85045b6,
+                    0xe131a029, 0x30644e72,
+                ]),
+                Fp::from_limbs([
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                    0x00000000, 0x00000000,
+                ]),
+            ),
+            Fp2::new(
+                Fp::from_limbs([
+                    0x77fffffe, 0x57634731, 0xacdb5c4f, 0xd4f263f1, 0xa0d48bac, 0x59e26bce,
+                    0x00000000, 0x00000000,
+                ]),
+                Fp::from_limbs([
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                    0x00000000, 0x00000000,
+                ]),
+            ),
+            Fp2::new(
+                Fp::from_limbs([
+                    0x77ffffff, 0x57634731, 0xacdb5c4f, 0xd4f263f1, 0xa0d48bac, 0x59e26bce,
+                    0x00000000, 0x00000000,
+                ]),
+                Fp::from_limbs([
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                    0x00000000, 0x00000000,
+                ]),
+            ),
+        ],
+        [
+            Fp2::new(
+                Fp::from_limbs([
+                    0x00000001, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                    0x00000000, 0x00000000,
+                ]),
+                Fp::from_limbs([
+                    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+                    0x00000000, 0x00000000,
+                ]),
+            ),
+            Fp2::new(
+                Fp::from_limbs([
+                    0x1ed4a67f, 0xe86f7d39, 0xbe55d24a, 0x894cb38d, 0xd0acaa90, 0xefe9608c,
+                    0xcc82e4bb, 0x19dc81cf,
+                ]),
+                Fp::from_limbs([
+                    0xf4c0c101, 0x7694aa2b, 0x97d439ec, 0x7f03a5e3, 0x3576139d, 0x06cbeee3,
+                    0x0be77d73, 0x00abf8b6,
+                ]),
+            ),
+            Fp2::new(
+                Fp::from_limbs([
+                    0x7bdcfb6d, 0x7b746ee8, 0x5d6942d3, 0x805ffd3d, 0x959f25ac, 0xbaff1c77,
+                    0xb755ef0a, 0x0856e078,
+                ]),
+                Fp::from_limbs([
+                    0xaaa586de, 0x380cab2b, 0x98ff2631, 0x0fdf31bf, 0xec26094f, 0xa9f30e6d,
+                    0xb3d1766f, 0x04f1de41,
+                ]),
+            ),
+            Fp2::new(
+                Fp::from_limbs([
+                    0x66dce9ed, 0x5fcc8ad0, 0xbea870f4, 0xbbd689a3, 0xca9e5ea3, 0xdbf17f1d,
+                    0x9896aa4c, 0x2a275b6d,
+                ]),
+                Fp::from_limbs([
+                    0xb2594c64, 0xb94d0cb3, 0xd8cf6eba, 0x7600ecc7, 0x9507e932, 0xb14b900e,
+                    0x34f09b8f, 0x28a411b6,
+                ]),
+            ),
+            Fp2::new(
+                Fp::from_limbs([
+                    0x3ccbf066, 0x0e1a92bc, 0x75b06bcb, 0xe6330945, 0xb5b2444e, 0x19bee0f7,
+                    0x11c08dab, 0x0bc58c66,
+                ]),
+                Fp::from_limbs([
+                    0x730c239f, 0x5fe3ed9d, 0x737f96e5, 0xa44a9e08, 0x0cd21d04, 0xfeb0f6ef,
+                    0xe1910a12, 0x23d5e999,
+                ]),
+            ),
+            Fp2::new(
+                Fp::from_limbs([
+                    0x76261b43, 0xebde8470, 0x967c84a5, 0x2ed68098, 0x3b4d3f69, 0x711699fa,
+                    0x952c0905, 0x13c49044,
+                ]),
+                Fp::from_limbs([
+                    0x84282499, 0x1f250413, 0x20028021, 0x3e2ddaea, 0x2a48633d, 0x9fb1b228,
+                    0x59b1dd0b, 0x16db366a,
+                ]),
+            ),
+        ],
+    ];
+
+    const FP_ADD: FieldSyscall = syscall_bn254_fp_addmod;
+    const FP_SUB: FieldSyscall = syscall_bn254_fp_submod;
+    const FP_MUL: FieldSyscall = syscall_bn254_fp_mulmod;
+    const FP2_ADD: FieldSyscall = syscall_bn254_fp2_addmod;
+    const FP2_SUB: FieldSyscall = syscall_bn254_fp2_submod;
+    const FP2_MUL: FieldSyscall = syscall_bn254_fp2_mulmod;
+    const FP6_MUL: FieldSyscall = syscall_bn254_fp6_mulmod;
+}
@@ -0,0 +1,323 @@
+//! Known-answer tests of the pairings against arkworks.
+//!
+//! Outside of the zkVM the field tower syscalls are defined here, with the semantics of the
+//! executor and the field parameters of `zkm_curves`, so that the whole pairing runs natively.
+
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::{Field, PrimeField, UniformRand};
+use num_bigint::BigUint;
+use zkm_curves::{
+    params::FieldParameters,
+    weierstrass::{
+        bls12_381::{Bls12381BaseField, Bls12381Parameters},
+        bn254::{Bn254BaseField, Bn254Parameters},
+        FpOpField, SwCurve, WeierstrassParameters,
+    },
+};
+
+use super::{
+    bls12381::Bls12381, bn254::Bn254, final_exponentiation, multi_miller_loop, pairing,
+    pairing_check, Fp, Fp12, Fp2, Fp6, G1Affine, G2Affine, PairingCurve,
+};
+
+type NativeFp2 = (BigUint, BigUint);
+
+/// The number of `u32` limbs of an element of the base field.
+fn num_limbs<F: FpOpField>() -> usize {
+    F::MODULUS.len() / 4
+}
+
+fn read<F: FpOpField>(ptr: *const u32, len: usize) -> Vec<BigUint> {
+    let words = unsafe { std::slice::from_raw_parts(ptr, len * num_limbs::<F>()) };
+    words
+        .chunks_exact(num_limbs::<F>())
+        .map(|limbs| BigUint::from_slice(limbs) % F::modulus())
+        .collect()
+}
+
+fn write<F: FpOpField>(ptr: *mut u32, values: &[BigUint]) {
+    let words = unsafe { std::slice::from_raw_parts_mut(ptr, values.len() * num_limbs::<F>()) };
+    for (limbs, value) in words.chunks_exact_mut(num_limbs::<F>()).zip(values) {
+        let mut digits = value.to_u32_digits();
+        digits.resize(num_limbs::<F>(), 0);
+        limbs.copy_from_slice(&digits);
+    }
+}
+
+fn fp2_add<F: FpOpField>(a: &NativeFp2, b: &NativeFp2) -> NativeFp2 {
+    let p = F::modulus();
+    ((&a.0 + &b.0) % &p, (&a.1 + &b.1) % &p)
+}
+
+fn fp2_sub<F: FpOpField>(a: &NativeFp2, b: &NativeFp2) -> NativeFp2 {
+    let p = F::modulus();
+    ((&a.0 + &p - &b.0) % &p, (&a.1 + &p - &b.1) % &p)
+}
+
+fn fp2_mul<F: FpOpField>(a: &NativeFp2, b: &NativeFp2) -> NativeFp2 {
+    let p = F::modulus();
+    ((&a.0 * &b.0 + &p * &p - &a.1 * &b.1) % &p, (&a.0 * &b.1 + &a.1 * &b.0) % &p)
+}
+
+fn fp_op<F: FpOpField>(x: *mut u32, y: *const u32, op: fn(&BigUint, &BigUint) -> BigUint) {
+    let (a, b) = (read::<F>(x, 1), read::<F>(y, 1));
+    write::<F>(x, &[op(&a[0], &b[0]) % F::modulus()]);
+}
+
+fn fp2_op<F: FpOpField>(x: *mut u32, y: *const u32, op: fn(&NativeFp2, &NativeFp2) -> NativeFp2) {
+    let (a, b) = (read::<F>(x, 2), read::<F>(y, 2));
+    let c = op(&(a[0].clone(), a[1].clone()), &(b[0].clone(), b[1].clone()));
+    write::<F>(x, &[c.0, c.1]);
+}
+
+fn fp6_mul<F: FpOpField>(x: *mut u32, y: *const u32) {
+    let to_fp6 = |values: Vec<BigUint>| -> Vec<NativeFp2> {
+        values.chunks_exact(2).map(|c| (c[0].clone(), c[1].clone())).collect()
+    };
+    let (a, b) = (to_fp6(read::<F>(x, 6)), to_fp6(read::<F>(y, 6)));
+    let xi = (BigUint::from(F::FP6_NON_RESIDUE.0), BigUint::from(F::FP6_NON_RESIDUE.1));
+    let mul = fp2_mul::<F>;
+    let add = fp2_add::<F>;
+
+    let c0 = add(&mul(&a[0], &b[0]), &mul(&xi, &add(&mul(&a[1], &b[2]), &mul(&a[2], &b[1]))));
+    let c1 = add(&add(&mul(&a[0], &b[1]), &mul(&a[1], &b[0])), &mul(&xi, &mul(&a[2], &b[2])));
+    let c2 = add(&add(&mul(&a[0], &b[2]), &mul(&a[1], &b[1])), &mul(&a[2], &b[0]));
+    write::<F>(x, &[c0.0, c0.1, c1.0, c1.1, c2.0, c2.1]);
+}
+
+macro_rules! native_syscalls {
+    ($field:ty, $add:ident, $sub:ident, $mul:ident, $add2:ident, $sub2:ident, $mul2:ident,
+     $mul6:ident) => {
+        #[no_mangle]
+        extern "C" fn $add(x: *mut u32, y: *const u32) {
+            fp_op::<$field>(x, y, |a, b| a + b);
+        }
+
+        #[no_mangle]
+        extern "C" fn $sub(x: *mut u32, y: *const u32) {
+            fp_op::<$field>(x, y, |a, b| a + <$field>::modulus() - b);
+        }
+
+        #[no_mangle]
+        extern "C" fn $mul(x: *mut u32, y: *const u32) {
+            fp_op::<$field>(x, y, |a, b| a * b);
+        }
+
+        #[no_mangle]
+        extern "C" fn $add2(x: *mut u32, y: *const u32) {
+            fp2_op::<$field>(x, y, fp2_add::<$field>);
+        }
+
+        #[no_mangle]
+        extern "C" fn $sub2(x: *mut u32, y: *const u32) {
+            fp2_op::<$field>(x, y, fp2_sub::<$field>);
+        }
+
+        #[no_mangle]
+        extern "C" fn $mul2(x: *mut u32, y: *const u32) {
+            fp2_op::<$field>(x, y, fp2_mul::<$field>);
+        }
+
+        #[no_mangle]
+        extern "C" fn $mul6(x: *mut u32, y: *const u32) {
+            fp6_mul::<$field>(x, y);
+        }
+    };
+}
+
+native_syscalls!(
+    Bn254BaseField,
+    syscall_bn254_fp_addmod,
+    syscall_bn254_fp_submod,
+    syscall_bn254_fp_mulmod,
+    syscall_bn254_fp2_addmod,
+    syscall_bn254_fp2_submod,
+    syscall_bn254_fp2_mulmod,
+    syscall_bn254_fp6_mulmod
+);
+
+native_syscalls!(
+    Bls12381BaseField,
+    syscall_bls12381_fp_addmod,
+    syscall_bls12381_fp_submod,
+    syscall_bls12381_fp_mulmod,
+    syscall_bls12381_fp2_addmod,
+    syscall_bls12381_fp2_submod,
+    syscall_bls12381_fp2_mulmod,
+    syscall_bls12381_fp6_mulmod
+);
+
+/// Conversions between the field tower of a curve and the one of its arkworks counterpart.
+trait ArkCurve: PairingCurve {
+    type Engine: Pairing;
+
+    fn fp(x: &<<Self::Engine as Pairing>::G1Affine as AffineRepr>::BaseField) -> Fp<Self>;
+
+    fn fp2(x: &<<Self::Engine as Pairing>::G2Affine as AffineRepr>::BaseField) -> Fp2<Self>;
+
+    fn fp12(x: &<Self::Engine as Pairing>::TargetField) -> Fp12<Self>;
+
+    fn ark_fp12(x: &Fp12<Self>) -> <Self::Engine as Pairing>::TargetField;
+
+    fn g1(p: &<Self::Engine as Pairing>::G1Affine) -> G1Affine<Self> {
+        match p.xy() {
+            Some((x, y)) => G1Affine::new(Self::fp(&x), Self::fp(&y)),
+            None => G1Affine::identity(),
+        }
+    }
+
+    fn g2(q: &<Self::Engine as Pairing>::G2Affine) -> G2Affine<Self> {
+        match q.xy() {
+            Some((x, y)) => G2Affine::new(Self::fp2(&x), Self::fp2(&y)),
+            None => G2Affine::identity(),
+        }
+    }
+}
+
+fn limbs<C: PairingCurve>(x: BigUint) -> Fp<C> {
+    let mut limbs = C::Limbs::default();
+    let digits = x.to_u32_digits();
+    limbs.as_mut()[..digits.len()].copy_from_slice(&digits);
+    Fp::from_limbs(limbs)
+}
+
+fn biguint<C: PairingCurve>(x: &Fp<C>) -> BigUint {
+    BigUint::from_slice(x.limbs().as_ref())
+}
+
+macro_rules! ark_curve {
+    ($curve:ty, $engine:ty, $ark:ident) => {
+        impl ArkCurve for $curve {
+            type Engine = $engine;
+
+            fn fp(x: &$ark::Fq) -> Fp<Self> {
+                limbs(x.into_bigint().into())
+            }
+
+            fn fp2(x: &$ark::Fq2) -> Fp2<Self> {
+                Fp2::new(Self::fp(&x.c0), Self::fp(&x.c1))
+            }
+
+            fn fp12(x: &$ark::Fq12) -> Fp12<Self> {
+                let fp6 =
+                    |x: &$ark::Fq6| Fp6::new(Self::fp2(&x.c0), Self::fp2(&x.c1), Self::fp2(&x.c2));
+                Fp12::new(fp6(&x.c0), fp6(&x.c1))
+            }
+
+            fn ark_fp12(x: &Fp12<Self>) -> $ark::Fq12 {
+                let fp = |x: &Fp<Self>| $ark::Fq::from(biguint(x));
+                let fp2 = |x: &Fp2<Self>| $ark::Fq2::new(fp(&x.c0), fp(&x.c1));
+                let fp6 = |x: &Fp6<Self>| $ark::Fq6::new(fp2(&x.c0), fp2(&x.c1), fp2(&x.c2));
+                $ark::Fq12::new(fp6(&x.c0), fp6(&x.c1))
+            }
+        }
+    };
+}
+
+ark_curve!(Bn254, ark_bn254::Bn254, ark_bn254);
+ark_curve!(Bls12381, ark_bls12_381::Bls12_381, ark_bls12_381);
+
+type G1<C> = <<C as ArkCurve>::Engine as Pairing>::G1;
+type G2<C> = <<C as ArkCurve>::Engine as Pairing>::G2;
+type ScalarField<C> = <<C as ArkCurve>::Engine as Pairing>::ScalarField;
+
+fn test_generators<C: ArkCurve, E: WeierstrassParameters>(g1: G1Affine<C>, g2: G2Affine<C>) {
+    let generator = SwCurve::<E>::generator();
+    assert_eq!(G1Affine::new(limbs(generator.x), limbs(generator.y)), g1);
+    assert_eq!(C::g1(&<C::Engine as Pairing>::G1Affine::generator()), g1);
+    assert_eq!(C::g2(&<C::Engine as Pairing>::G2Affine::generator()), g2);
+}
+
+fn test_final_exponentiation<C: ArkCurve>() {
+    let mut rng = test_rng();
+    for _ in 0..3 {
+        let f = <C::Engine as Pairing>::TargetField::rand(&mut rng);
+        let expected =
+            C::Engine::final_exponentiation(ark_ec::pairing::MillerLoopOutput(f)).unwrap().0;
+        assert_eq!(final_exponentiation(&C::fp12(&f)), C::fp12(&expected));
+    }
+}
+
+fn test_pairing<C: ArkCurve>() {
+    let mut rng = test_rng();
+    for _ in 0..2 {
+        let p = G1::<C>::rand(&mut rng).into_affine();
+        let q = G2::<C>::rand(&mut rng).into_affine();
+        let expected = C::Engine::pairing(p, q).0;
+        assert_eq!(pairing(&C::g1(&p), &C::g2(&q)), C::fp12(&expected));
+    }
+}
+
+fn test_bilinearity<C: ArkCurve>() {
+    let mut rng = test_rng();
+    let p = G1::<C>::rand(&mut rng);
+    let q = G2::<C>::rand(&mut rng);
+    let a = ScalarField::<C>::rand(&mut rng);
+    let b = ScalarField::<C>::rand(&mut rng);
+
+    let (ap, bq) = ((p * a).into_affine(), (q * b).into_affine());
+    let (p, q) = (p.into_affine(), q.into_affine());
+
+    // e(aP, bQ) = e(P, Q)^(ab).
+    let e_pq = C::ark_fp12(&pairing(&C::g1(&p), &C::g2(&q)));
+    let e_ab = C::ark_fp12(&pairing(&C::g1(&ap), &C::g2(&bq)));
+    assert_eq!(e_ab, e_pq.pow((a * b).into_bigint()));
+
+    // e(aP, bQ) * e(-abP, Q) = 1.
+    let abp = (p * (a * b)).into_affine();
+    assert!(pairing_check(&[(C::g1(&ap), C::g2(&bq)), (C::g1(&abp).neg(), C::g2(&q))]));
+    assert!(!pairing_check(&[(C::g1(&ap), C::g2(&bq)), (C::g1(&abp), C::g2(&q))]));
+
+    // The points at infinity are skipped.
+    let identity = (G1Affine::identity(), C::g2(&q));
+    assert_eq!(multi_miller_loop(&[identity]), Fp12::one());
+    assert!(pairing_check(&[identity, (C::g1(&ap), C::g2(&bq)), (C::g1(&abp).neg(), C::g2(&q))]));
+}
+
+fn test_rng() -> impl rand::Rng {
+    use rand::SeedableRng;
+    rand::rngs::StdRng::seed_from_u64(0x5eed)
+}
+
+#[test]
+fn test_bn254_generators() {
+    test_generators::<_, Bn254Parameters>(super::bn254::G1_GENERATOR, super::bn254::G2_GENERATOR);
+}
+
+#[test]
+fn test_bn254_final_exponentiation() {
+    test_final_exponentiation::<Bn254>();
+}
+
+#[test]
+fn test_bn254_pairing() {
+    test_pairing::<Bn254>();
+}
+
+#[test]
+fn test_bn254_bilinearity() {
+    test_bilinearity::<Bn254>();
+}
+
+#[test]
+fn test_bls12381_generators() {
+    test_generators::<_, Bls12381Parameters>(
+        super::bls12381::G1_GENERATOR,
+        super::bls12381::G2_GENERATOR,
+    );
+}
+
+#[test]
+fn test_bls12381_final_exponentiation() {
+    test_final_exponentiation::<Bls12381>();
+}
+
+#[test]
+fn test_bls12381_pairing() {
+    test_pairing::<Bls12381>();
+}
+
+#[test]
+fn test_bls12381_bilinearity() {
+    test_bilinearity::<Bls12381>();
+}
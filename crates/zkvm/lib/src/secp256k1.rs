@@ -1,11 +1,19 @@
 use crate::{
-    syscall_secp256k1_add, syscall_secp256k1_double,
-    utils::{AffinePoint, WeierstrassAffinePoint, WeierstrassPoint},
+    syscall_secp256k1_add, syscall_secp256k1_double, syscall_secp256k1_mul,
+    utils::{
+        double_and_add_mul_assign, scalar_in_range, AffinePoint, WeierstrassAffinePoint,
+        WeierstrassPoint,
+    },
 };
 
 /// The number of limbs in [Secp256k1Point].
 pub const N: usize = 16;
 
+/// The order of the Secp256k1 group.
+const SCALAR_ORDER: [u32; N / 2] = [
+    0xd0364141, 0xbfd25e8c, 0xaf48a03b, 0xbaaedce6, 0xfffffffe, 0xffffffff, 0xffffffff, 0xffffffff,
+];
+
 /// An affine point on the Secp256k1 curve.
 #[derive(Copy, Clone, Debug)]
 #[repr(align(4))]
@@ -78,4 +86,17 @@ impl AffinePoint<N> for Secp256k1Point {
             },
         }
     }
+
+    fn mul_assign(&mut self, scalar: &[u32]) {
+        // The precompile rejects the identity and unreduced scalars, so those fall back to
+        // double-and-add.
+        if self.is_infinity() || !scalar_in_range(scalar, &SCALAR_ORDER) {
+            double_and_add_mul_assign(self, scalar);
+            return;
+        }
+        let s: &[u32; N / 2] = scalar.try_into().unwrap();
+        unsafe {
+            syscall_secp256k1_mul(self.limbs_mut(), s);
+        }
+    }
 }
@@ -1,11 +1,19 @@
 use crate::{
-    syscall_secp256r1_add, syscall_secp256r1_double,
-    utils::{AffinePoint, WeierstrassAffinePoint, WeierstrassPoint},
+    syscall_secp256r1_add, syscall_secp256r1_double, syscall_secp256r1_mul,
+    utils::{
+        double_and_add_mul_assign, scalar_in_range, AffinePoint, WeierstrassAffinePoint,
+        WeierstrassPoint,
+    },
 };
 
 /// The number of limbs in [Secp256r1Point].
 pub const N: usize = 16;
 
+/// The order of the Secp256r1 group.
+const SCALAR_ORDER: [u32; N / 2] = [
+    0xfc632551, 0xf3b9cac2, 0xa7179e84, 0xbce6faad, 0xffffffff, 0xffffffff, 0x00000000, 0xffffffff,
+];
+
 /// An affine point on the Secp256k1 curve.
 #[derive(Copy, Clone, Debug)]
 #[repr(align(4))]
@@ -78,4 +86,17 @@ impl AffinePoint<N> for Secp256r1Point {
             },
         }
     }
+
+    fn mul_assign(&mut self, scalar: &[u32]) {
+        // The precompile rejects the identity and unreduced scalars, so those fall back to
+        // double-and-add.
+        if self.is_infinity() || !scalar_in_range(scalar, &SCALAR_ORDER) {
+            double_and_add_mul_assign(self, scalar);
+            return;
+        }
+        let s: &[u32; N / 2] = scalar.try_into().unwrap();
+        unsafe {
+            syscall_secp256r1_mul(self.limbs_mut(), s);
+        }
+    }
 }
@@ -65,21 +65,7 @@ pub trait AffinePoint<const N: usize>: Clone + Sized {
 
     /// Multiplies `self` by the given scalar.
     fn mul_assign(&mut self, scalar: &[u32]) {
-        debug_assert!(scalar.len() == N / 2);
-
-        let mut res: Self = Self::identity();
-        let mut temp = self.clone();
-
-        for &words in scalar.iter() {
-            for i in 0..32 {
-                if (words >> i) & 1 == 1 {
-                    res.complete_add_assign(&temp);
-                }
-                temp.double();
-            }
-        }
-
-        *self = res;
+        double_and_add_mul_assign(self, scalar);
     }
 
     /// Performs multi-scalar multiplication (MSM) on slices of bit vectors and points. Note:
@@ -110,6 +96,42 @@ pub trait AffinePoint<const N: usize>: Clone + Sized {
     }
 }
 
+/// Multiplies `point` by the given scalar with a double-and-add loop built on
+/// [`AffinePoint::complete_add_assign`] and [`AffinePoint::double`].
+pub fn double_and_add_mul_assign<P: AffinePoint<N>, const N: usize>(point: &mut P, scalar: &[u32]) {
+    debug_assert!(scalar.len() == N / 2);
+
+    let mut res = P::identity();
+    let mut temp = point.clone();
+
+    for &words in scalar.iter() {
+        for i in 0..32 {
+            if (words >> i) & 1 == 1 {
+                res.complete_add_assign(&temp);
+            }
+            temp.double();
+        }
+    }
+
+    *point = res;
+}
+
+/// Returns true if the little-endian `scalar` lies in `[1, order)`, which is the range accepted by
+/// the scalar multiplication precompiles.
+pub fn scalar_in_range(scalar: &[u32], order: &[u32]) -> bool {
+    debug_assert!(scalar.len() == order.len());
+
+    if scalar.iter().all(|&word| word == 0) {
+        return false;
+    }
+    for (s, n) in scalar.iter().zip(order.iter()).rev() {
+        if s != n {
+            return s < n;
+        }
+    }
+    false
+}
+
 /// Errors that can occur during scalar multiplication of an [`AffinePoint`].
 #[derive(Debug)]
 pub enum MulAssignError {